@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scte35::{
+    splice_info_section::{ParserConfig, SpliceInfoSection},
+    splice_info_section_ref::SpliceInfoSectionRef,
+};
+
+// Parsing untrusted data must never panic, whichever error handling is configured.
+fuzz_target!(|data: &[u8]| {
+    let _ = SpliceInfoSection::try_from_bytes(data);
+    let _ = SpliceInfoSection::try_from_bytes_with_config(data, &ParserConfig::strict());
+    let _ = SpliceInfoSection::try_from_bytes_with_config(data, &ParserConfig::lenient());
+    if let Ok(section_ref) = SpliceInfoSectionRef::try_from_bytes(data) {
+        let _ = section_ref.try_to_owned();
+    }
+});
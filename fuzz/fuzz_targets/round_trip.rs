@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scte35::splice_info_section::{ParserConfig, SpliceInfoSection};
+
+// Any section that can be encoded must parse back (leniently, so that `Unknown` descriptors are
+// preserved) to a section that encodes to the same bytes.
+fuzz_target!(|section: SpliceInfoSection| {
+    let Ok(bytes) = section.try_to_bytes() else {
+        return;
+    };
+    let parsed = SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::lenient())
+        .expect("encoded section should parse");
+    assert_eq!(
+        bytes,
+        parsed.try_to_bytes().expect("parsed section should encode")
+    );
+});
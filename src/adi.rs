@@ -0,0 +1,153 @@
+use crate::error::ParseError;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A CableLabs metadata identifier, as carried in an ADI `SegmentationUPID`, which has the
+/// abbreviated syntax of `<element> : <identifier>`.
+///
+/// For CableLabs Content metadata 1.1 the `<identifier>` of the content elements (`PREVIEW`,
+/// `MPEG2HD`, `MPEG2SD`, `AVCHD`, `AVCSD`, `HEVCSD` and `HEVCHD`) takes the form
+/// `<providerID>/<assetID>`.
+/// ```
+/// use scte35::adi::{Adi, AdiElement};
+///
+/// let adi: Adi = "MPEG2HD:example.com/ABCD1234567890123456".parse().unwrap();
+/// assert_eq!(AdiElement::Mpeg2Hd, adi.element);
+/// assert_eq!(Some("example.com"), adi.provider_id());
+/// assert_eq!(Some("ABCD1234567890123456"), adi.asset_id());
+///
+/// let adi: Adi = "SIGNAL:Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0".parse().unwrap();
+/// assert_eq!(AdiElement::Signal, adi.element);
+/// assert_eq!("Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0", adi.identifier);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Adi {
+    pub element: AdiElement,
+    pub identifier: String,
+}
+
+impl Adi {
+    /// The `<providerID>` of the identifier of a content element.
+    pub fn provider_id(&self) -> Option<&str> {
+        self.content_ids().map(|(provider_id, _)| provider_id)
+    }
+
+    /// The `<assetID>` of the identifier of a content element.
+    pub fn asset_id(&self) -> Option<&str> {
+        self.content_ids().map(|(_, asset_id)| asset_id)
+    }
+
+    fn content_ids(&self) -> Option<(&str, &str)> {
+        if self.element.is_content() {
+            self.identifier.split_once('/')
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Adi {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.element.value(), self.identifier)
+    }
+}
+
+impl FromStr for Adi {
+    type Err = ParseError;
+
+    /// Parses `<element> : <identifier>`, where the whitespace around the colon is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::InvalidADI(String::from(s));
+        let (element, identifier) = s.split_once(':').ok_or_else(invalid)?;
+        let identifier = identifier.trim();
+        if identifier.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            element: element.trim().parse().map_err(|_| invalid())?,
+            identifier: String::from(identifier),
+        })
+    }
+}
+
+/// The `<element>` of an ADI identifier.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdiElement {
+    /// `PREVIEW`
+    Preview,
+    /// `MPEG2HD`
+    Mpeg2Hd,
+    /// `MPEG2SD`
+    Mpeg2Sd,
+    /// `AVCHD`
+    AvcHd,
+    /// `AVCSD`
+    AvcSd,
+    /// `HEVCSD`
+    HevcSd,
+    /// `HEVCHD`
+    HevcHd,
+    /// `SIGNAL`
+    Signal,
+    /// `PO`
+    PlacementOpportunity,
+    /// `BLACKOUT`
+    Blackout,
+    /// `OTHER`
+    Other,
+}
+
+impl AdiElement {
+    pub fn value(&self) -> &'static str {
+        match *self {
+            AdiElement::Preview => "PREVIEW",
+            AdiElement::Mpeg2Hd => "MPEG2HD",
+            AdiElement::Mpeg2Sd => "MPEG2SD",
+            AdiElement::AvcHd => "AVCHD",
+            AdiElement::AvcSd => "AVCSD",
+            AdiElement::HevcSd => "HEVCSD",
+            AdiElement::HevcHd => "HEVCHD",
+            AdiElement::Signal => "SIGNAL",
+            AdiElement::PlacementOpportunity => "PO",
+            AdiElement::Blackout => "BLACKOUT",
+            AdiElement::Other => "OTHER",
+        }
+    }
+
+    /// Whether the element identifies content (a preview or a rendition of the asset), in which
+    /// case the identifier takes the form `<providerID>/<assetID>`.
+    pub fn is_content(&self) -> bool {
+        !matches!(
+            self,
+            AdiElement::Signal
+                | AdiElement::PlacementOpportunity
+                | AdiElement::Blackout
+                | AdiElement::Other
+        )
+    }
+}
+
+impl FromStr for AdiElement {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PREVIEW" => Ok(AdiElement::Preview),
+            "MPEG2HD" => Ok(AdiElement::Mpeg2Hd),
+            "MPEG2SD" => Ok(AdiElement::Mpeg2Sd),
+            "AVCHD" => Ok(AdiElement::AvcHd),
+            "AVCSD" => Ok(AdiElement::AvcSd),
+            "HEVCSD" => Ok(AdiElement::HevcSd),
+            "HEVCHD" => Ok(AdiElement::HevcHd),
+            "SIGNAL" => Ok(AdiElement::Signal),
+            "PO" => Ok(AdiElement::PlacementOpportunity),
+            "BLACKOUT" => Ok(AdiElement::Blackout),
+            "OTHER" => Ok(AdiElement::Other),
+            _ => Err(()),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Annotation of the fields of a SCTE-35 message with their position in the data, for debugging
+//! messages that do not parse as expected (e.g. to point at exactly which bytes of a malformed
+//! message from a vendor are wrong).
+//! ```
+//! use scte35::splice_info_section::SpliceInfoSection;
+//!
+//! let data = [0xFC, 0x30, 0x11, 0x00, 0x00, 0x00, 0x00];
+//! let annotated_section = SpliceInfoSection::annotate(&data);
+//! let error = annotated_section.result.as_ref().unwrap_err();
+//! assert_eq!(24, error.bit_offset);
+//! let table_id = &annotated_section.annotations[0];
+//! assert_eq!(
+//!     ("table_id", 0, 8, Some(0xFC)),
+//!     (table_id.name, table_id.bit_offset, table_id.bit_width, table_id.value)
+//! );
+//! assert_eq!(
+//!     "\
+//! offset  width  bytes                    field
+//!      0      8  FC                       table_id = 252 (0xFC)
+//!      8      1  30                       section_syntax_indicator = 0
+//!      9      1  30                       private_indicator = 0
+//!     10      2  30                       sap_type = 3 (0x3)
+//!     12     12  30 11                    section_length = 17 (0x11)
+//! At bit 24: Expected at least 136 bits left and instead was 32 when parsing: SpliceInfoSection; not enough bytes left to read section_length.
+//! ",
+//!     annotated_section.hex_dump()
+//! );
+//! ```
+use crate::{
+    bit_reader::Bits,
+    error::LocatedParseError,
+    hex::encode_hex,
+    splice_info_section::{ParserConfig, SpliceInfoSection},
+};
+use std::fmt::Write;
+
+/// A field (or a structure of fields) that was read from the data, with its position in the data.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Annotation {
+    /// The name of the field or structure, which is the name given in the specification where
+    /// there is one (e.g. `splice_command_length`).
+    pub name: &'static str,
+    /// The position of the structure within a list (e.g. the index of a splice descriptor).
+    pub index: Option<usize>,
+    /// The number of bits from the start of the data to the first bit of the field.
+    pub bit_offset: usize,
+    /// The number of bits in the field. For a structure that could not be fully read, this is
+    /// the number of bits that were read before parsing failed.
+    pub bit_width: usize,
+    /// The value of an integer or flag field, which is `None` for structures and byte strings.
+    pub value: Option<u64>,
+    /// The fields of a structure, in the order that they were read.
+    pub children: Vec<Annotation>,
+}
+
+impl Annotation {
+    pub(crate) fn new(name: &'static str, bit_offset: usize, value: Option<u64>) -> Self {
+        Self {
+            name,
+            index: None,
+            bit_offset,
+            bit_width: 0,
+            value,
+            children: vec![],
+        }
+    }
+
+    /// This annotation followed by all of the annotations that are nested within it (depth
+    /// first).
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let annotation = stack.pop()?;
+            stack.extend(annotation.children.iter().rev());
+            Some(annotation)
+        })
+    }
+}
+
+/// The result of parsing a message along with the `Annotation`s of the fields that were read
+/// (see `SpliceInfoSection::annotate`).
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct AnnotatedSection {
+    /// The data that was parsed.
+    pub data: Vec<u8>,
+    /// The top-level fields and structures that were read, up to the point that parsing failed.
+    pub annotations: Vec<Annotation>,
+    /// The result of parsing the data.
+    pub result: Result<SpliceInfoSection, LocatedParseError>,
+}
+
+impl AnnotatedSection {
+    /// All of the annotations (see `Annotation::iter`).
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.annotations.iter().flat_map(Annotation::iter)
+    }
+
+    /// A table of every field that was read, with its bit offset, bit width, the bytes that it
+    /// occupies (the first 8 of them for longer fields) and its value, followed by the error
+    /// when parsing failed. Structures are listed before their (indented) fields.
+    pub fn hex_dump(&self) -> String {
+        let mut dump = format!("{:>6}  {:>5}  {:<23}  field\n", "offset", "width", "bytes");
+        for annotation in &self.annotations {
+            self.write_annotation(&mut dump, annotation, 0);
+        }
+        if let Err(error) = &self.result {
+            _ = writeln!(dump, "{}", error);
+        }
+        dump
+    }
+
+    fn write_annotation(&self, dump: &mut String, annotation: &Annotation, depth: usize) {
+        let start = (annotation.bit_offset / 8).min(self.data.len());
+        let end = annotation
+            .bit_offset
+            .saturating_add(annotation.bit_width)
+            .div_ceil(8)
+            .min(self.data.len());
+        let mut bytes = self.data[start..end.min(start + 8)]
+            .iter()
+            .map(|byte| encode_hex(&[*byte]).to_uppercase())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if end - start > 8 {
+            bytes.push_str(" ..");
+        }
+        let bytes = if annotation.children.is_empty() {
+            bytes
+        } else {
+            String::new()
+        };
+        _ = write!(
+            dump,
+            "{:>6}  {:>5}  {:<23}  {:indent$}{}",
+            annotation.bit_offset,
+            annotation.bit_width,
+            bytes,
+            "",
+            annotation.name,
+            indent = depth * 2
+        );
+        if let Some(index) = annotation.index {
+            _ = write!(dump, "[{}]", index);
+        }
+        match annotation.value {
+            Some(value) if annotation.bit_width > 1 => {
+                _ = write!(dump, " = {} (0x{:X})", value, value)
+            }
+            Some(value) => _ = write!(dump, " = {}", value),
+            None => (),
+        }
+        dump.push('\n');
+        for child in &annotation.children {
+            self.write_annotation(dump, child, depth + 1);
+        }
+    }
+}
+
+impl SpliceInfoSection {
+    /// Parses the `data`, annotating each field that is read with its position in the data (see
+    /// `AnnotatedSection`).
+    pub fn annotate(data: &[u8]) -> AnnotatedSection {
+        Self::annotate_with_config(data, &ParserConfig::default())
+    }
+
+    /// Parses the `data` with the `config`, annotating each field that is read with its position
+    /// in the data (see `AnnotatedSection`).
+    pub fn annotate_with_config(data: &[u8], config: &ParserConfig) -> AnnotatedSection {
+        let mut bits = Bits::new(data);
+        bits.set_config(config);
+        bits.start_annotating();
+        let result =
+            Self::try_from_bits(&mut bits, data, vec![]).map_err(|error| bits.locate(error));
+        AnnotatedSection {
+            data: data.to_vec(),
+            annotations: bits.take_annotations(),
+            result,
+        }
+    }
+}
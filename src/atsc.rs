@@ -1,4 +1,8 @@
-use crate::{bit_reader::Bits, error::ParseError};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
 
 /// The ATSC Content Identifier is a structure that is composed of a TSID and a “house number” with
 /// a period of uniqueness. A “house number” is any number that the holder of the TSID wishes as
@@ -14,7 +18,9 @@ use crate::{bit_reader::Bits, error::ParseError};
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ATSCContentIdentifier {
     /// This 16 bit unsigned integer field shall contain a value of `transport_stream_id` per
     /// section 6.3.1 of A/65 \[3\]. Note: The assigning authority for these values for the United
@@ -54,11 +60,12 @@ impl ATSCContentIdentifier {
             return Err(ParseError::InvalidATSCContentIdentifierInUPID { upid_length });
         }
 
-        let tsid = bits.u16(16);
-        bits.consume(2);
-        let end_of_day = bits.u8(5);
-        let unique_for = bits.u16(9);
+        let tsid = bits.u16("tsid", 16)?;
+        bits.consume(2)?;
+        let end_of_day = bits.u8("end_of_day", 5)?;
+        let unique_for = bits.u16("unique_for", 9)?;
         let content_id = bits.string(
+            "content_id",
             content_id_length as usize,
             "Reading content_id for ATSCContentIdentifier",
         )?;
@@ -70,6 +77,25 @@ impl ATSCContentIdentifier {
             content_id,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.validate(
+            5,
+            self.end_of_day as u64,
+            "ATSCContentIdentifier end_of_day",
+        )?;
+        bits.validate(
+            9,
+            self.unique_for as u64,
+            "ATSCContentIdentifier unique_for",
+        )?;
+        bits.u16(16, self.tsid);
+        bits.reserved(2);
+        bits.u8(5, self.end_of_day);
+        bits.u16(9, self.unique_for);
+        bits.string(&self.content_id);
+        Ok(())
+    }
 }
 
 /// ATSC A/52 Table 5.8 Audio Coding Mode.
@@ -99,7 +125,9 @@ acmod Audio Coding Mode nfchans Channel Array Ordering
 ‘111’ 3/2               5       L, C, R, SL, SR
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum AudioCodingMode {
     /**
     ```text
@@ -209,7 +237,9 @@ bsmod acmod         Type of Service
 ‘111’ ‘010’ - ‘111’ main audio service: karaoke
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BitStreamMode {
     CompleteMain,
     MusicAndEffects,
@@ -240,4 +270,18 @@ impl BitStreamMode {
             _ => Err(ParseError::InvalidBitStreamMode { bsmod, acmod }),
         }
     }
+
+    pub fn value(&self) -> u8 {
+        match *self {
+            Self::CompleteMain => 0,
+            Self::MusicAndEffects => 1,
+            Self::VisuallyImpaired => 2,
+            Self::HearingImpaired => 3,
+            Self::Dialogue => 4,
+            Self::Commentary => 5,
+            Self::Emergeny => 6,
+            Self::VoiceOver => 7,
+            Self::Karaoke => 7,
+        }
+    }
 }
@@ -0,0 +1,104 @@
+use crate::{
+    format::{parse_text, stdin_text, write_section, InputFormat, OutputFormat},
+    CliResult, Failures,
+};
+use clap::Args;
+use scte35::{
+    parser::Parser,
+    splice_info_section::{ParserConfig, SpliceInfoSection},
+};
+use std::io::{self, Read, Write};
+
+#[derive(Args)]
+pub struct DecodeArgs {
+    /// The message to decode. When omitted, messages are read from stdin: either one per line, or
+    /// as consecutive binary sections.
+    message: Option<String>,
+
+    /// The encoding of the messages.
+    #[arg(short, long, value_enum, default_value_t = InputFormat::Auto)]
+    input: InputFormat,
+
+    /// How the decoded messages are printed.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Reports recoverable problems (e.g. an unexpected UPID length) as warnings rather than
+    /// failing, as with `ParserConfig::lenient`.
+    #[arg(long)]
+    lenient: bool,
+}
+
+pub fn run(args: DecodeArgs) -> CliResult {
+    let config = if args.lenient {
+        ParserConfig::lenient()
+    } else {
+        ParserConfig::default()
+    };
+    let mut out = io::stdout().lock();
+    if let Some(message) = &args.message {
+        if args.input == InputFormat::Binary {
+            return Err("binary messages can only be read from stdin.".into());
+        }
+        let mut parser = Parser::with_config(config);
+        let splice_info_section = parse_text(&mut parser, message, args.input)?;
+        return print(&mut out, &splice_info_section, args.output);
+    }
+
+    let mut data = Vec::new();
+    io::stdin().lock().read_to_end(&mut data)?;
+    let mut failures = 0;
+    match stdin_text(&data, args.input)? {
+        Some(text) => {
+            let mut parser = Parser::with_config(config);
+            for (index, line) in text.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match parse_text(&mut parser, line, args.input) {
+                    Ok(splice_info_section) => print(&mut out, &splice_info_section, args.output)?,
+                    Err(error) => {
+                        eprintln!("error: line {}: {}", index + 1, error);
+                        failures += 1;
+                    }
+                }
+            }
+        }
+        None => {
+            let mut offset = 0;
+            while offset < data.len() {
+                match SpliceInfoSection::try_from_bytes_prefix(&data[offset..], &config) {
+                    Ok((splice_info_section, consumed)) => {
+                        print(&mut out, &splice_info_section, args.output)?;
+                        offset += consumed;
+                    }
+                    // The length of an invalid section may not be known, and so the rest of the
+                    // data cannot be read.
+                    Err(error) => {
+                        eprintln!("error: offset {}: {}", offset, error);
+                        failures += 1;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(Box::new(Failures {
+            count: failures,
+            action: "decoded",
+        }));
+    }
+    Ok(())
+}
+
+fn print(
+    out: &mut impl Write,
+    splice_info_section: &SpliceInfoSection,
+    format: OutputFormat,
+) -> CliResult {
+    for error in &splice_info_section.non_fatal_errors {
+        eprintln!("warning: {}", error);
+    }
+    write_section(out, splice_info_section, format)
+}
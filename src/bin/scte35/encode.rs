@@ -0,0 +1,60 @@
+use crate::{
+    format::{write_encoded, Encoding},
+    CliResult,
+};
+use clap::Args;
+use scte35::splice_info_section::SpliceInfoSection;
+use serde_json::Value;
+use std::io::{self, Read};
+
+#[derive(Args)]
+pub struct EncodeArgs {
+    /// The JSON description of the message, in the form printed by `decode --output json`. Any
+    /// top-level field that is omitted takes its default (e.g. a `SpliceNull` command with no
+    /// descriptors, and a `tier` of 0xFFF), and the `crc_32` is always recalculated. When
+    /// omitted, the description is read from stdin.
+    spec: Option<String>,
+
+    /// The encoding of the message.
+    #[arg(short, long, value_enum, default_value_t = Encoding::Base64)]
+    output: Encoding,
+}
+
+pub fn run(args: EncodeArgs) -> CliResult {
+    let spec = match args.spec {
+        Some(spec) => spec,
+        None => {
+            let mut spec = String::new();
+            io::stdin().lock().read_to_string(&mut spec)?;
+            spec
+        }
+    };
+    let splice_info_section = section_from_spec(&spec)?;
+    let bytes = splice_info_section.try_to_bytes()?;
+    write_encoded(&mut io::stdout().lock(), &bytes, args.output)?;
+    Ok(())
+}
+
+fn section_from_spec(spec: &str) -> Result<SpliceInfoSection, Box<dyn std::error::Error>> {
+    let Value::Object(fields) = serde_json::from_str(spec)? else {
+        return Err("the description must be a JSON object.".into());
+    };
+    let mut value = serde_json::to_value(SpliceInfoSection::builder().build()?)?;
+    if let Value::Object(defaults) = &mut value {
+        defaults.extend(fields);
+    }
+    let section: SpliceInfoSection = serde_json::from_value(value)?;
+    if section.encrypted_packet.is_some() {
+        return Err("encrypted messages cannot be encoded.".into());
+    }
+    // Builds the section again so that the descriptors are validated against the command.
+    Ok(SpliceInfoSection::builder()
+        .table_id(section.table_id)
+        .sap_type(section.sap_type)
+        .protocol_version(section.protocol_version)
+        .pts_adjustment(section.pts_adjustment)
+        .tier(section.tier)
+        .splice_command(section.splice_command)
+        .splice_descriptors(section.splice_descriptors)
+        .build()?)
+}
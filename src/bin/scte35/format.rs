@@ -0,0 +1,111 @@
+use base64::prelude::*;
+use clap::ValueEnum;
+use scte35::{
+    error::ParseError, hex::encode_hex, parser::Parser, splice_info_section::SpliceInfoSection,
+};
+use std::{
+    io::{self, Write},
+    str::Utf8Error,
+};
+
+/// The encoding of the messages that are read.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputFormat {
+    /// Detects the encoding: binary data when it is not valid UTF-8, and otherwise hex (with or
+    /// without a `0x` prefix) or base64 for each line.
+    Auto,
+    Hex,
+    Base64,
+    Binary,
+}
+
+/// The representation of the messages that are written.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    /// A human-readable, indented report.
+    Text,
+    Json,
+    Xml,
+}
+
+/// The encoding of the messages that are written.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    Base64,
+    /// Uppercase hex digits prefixed with `0x`.
+    Hex,
+    Binary,
+}
+
+/// Parses a hex or base64 encoded message, detecting the encoding in the same way as the
+/// `FromStr` implementation of `SpliceInfoSection` when the `format` is not `Hex` or `Base64`.
+pub fn parse_text(
+    parser: &mut Parser,
+    message: &str,
+    format: InputFormat,
+) -> Result<SpliceInfoSection, ParseError> {
+    let (bytes, _) = decode_text(parser, message, format)?;
+    parser.parse(&bytes)
+}
+
+/// Decodes a hex or base64 encoded message without parsing it, returning the bytes along with
+/// the encoding that was used (detected as with `parse_text`).
+pub fn decode_text(
+    parser: &mut Parser,
+    message: &str,
+    format: InputFormat,
+) -> Result<(Vec<u8>, Encoding), ParseError> {
+    let message = message.trim();
+    let is_hex = match format {
+        InputFormat::Hex => true,
+        InputFormat::Base64 => false,
+        InputFormat::Auto | InputFormat::Binary => {
+            message.starts_with("0x")
+                || message.starts_with("0X")
+                || message.bytes().all(|byte| byte.is_ascii_hexdigit())
+        }
+    };
+    if is_hex {
+        Ok((parser.decode_hex(message)?.to_vec(), Encoding::Hex))
+    } else {
+        Ok((parser.decode_base64(message)?.to_vec(), Encoding::Base64))
+    }
+}
+
+/// The text of the messages that were read from stdin, or `None` when they are binary.
+pub fn stdin_text(data: &[u8], format: InputFormat) -> Result<Option<&str>, Utf8Error> {
+    match format {
+        InputFormat::Binary => Ok(None),
+        InputFormat::Auto => Ok(std::str::from_utf8(data).ok()),
+        InputFormat::Hex | InputFormat::Base64 => std::str::from_utf8(data).map(Some),
+    }
+}
+
+/// Writes the `splice_info_section` in the `format`, ending with a newline.
+pub fn write_section(
+    out: &mut impl Write,
+    splice_info_section: &SpliceInfoSection,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        // The report already ends with a newline.
+        OutputFormat::Text => write!(out, "{}", splice_info_section)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, splice_info_section)
+                .map_err(io::Error::from)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Xml => writeln!(out, "{}", splice_info_section.to_xml_string()?)?,
+    }
+    Ok(())
+}
+
+/// Writes the encoded bytes of a message in the `encoding`, followed by a newline unless the
+/// `encoding` is `Binary`.
+pub fn write_encoded(out: &mut impl Write, bytes: &[u8], encoding: Encoding) -> io::Result<()> {
+    match encoding {
+        Encoding::Base64 => writeln!(out, "{}", BASE64_STANDARD.encode(bytes)),
+        Encoding::Hex => writeln!(out, "0x{}", encode_hex(bytes).to_uppercase()),
+        Encoding::Binary => out.write_all(bytes),
+    }
+}
@@ -0,0 +1,87 @@
+//! The `scte35` command line tool (built with the `cli` feature).
+mod decode;
+mod encode;
+mod format;
+mod monitor;
+mod patch;
+mod scan;
+mod stream;
+
+use clap::{Parser, Subcommand};
+use std::{io, process::ExitCode};
+
+/// Tools for working with SCTE-35 splice info sections.
+#[derive(Parser)]
+#[command(name = "scte35", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decodes messages and prints them as text, JSON or XML.
+    Decode(decode::DecodeArgs),
+    /// Encodes a message from a JSON description, as base64, hex or binary.
+    Encode(encode::EncodeArgs),
+    /// Scans a transport stream file for messages, printing each with its position in the stream.
+    Scan(scan::ScanArgs),
+    /// Monitors a live transport stream from UDP or stdin, printing each message as a line of JSON
+    /// along with the time at which it was received.
+    Monitor(monitor::MonitorArgs),
+    /// Recalculates the `crc_32` of messages, leaving the rest of each message unchanged.
+    FixCrc(patch::FixCrcArgs),
+    /// Sets (or adds to) the `pts_adjustment` of messages, recalculating the `crc_32`.
+    Restamp(patch::RestampArgs),
+}
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// The error returned once all of the messages have been read, when some of them failed (each
+/// failure having already been reported).
+#[derive(Debug)]
+struct Failures {
+    count: usize,
+    /// e.g. "decoded", as in "1 message could not be decoded."
+    action: &'static str,
+}
+
+impl std::fmt::Display for Failures {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.count == 1 {
+            write!(f, "1 message could not be {}.", self.action)
+        } else {
+            write!(f, "{} messages could not be {}.", self.count, self.action)
+        }
+    }
+}
+
+impl std::error::Error for Failures {}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Decode(args) => decode::run(args),
+        Command::Encode(args) => encode::run(args),
+        Command::Scan(args) => scan::run(args),
+        Command::Monitor(args) => monitor::run(args),
+        Command::FixCrc(args) => patch::run_fix_crc(args),
+        Command::Restamp(args) => patch::run_restamp(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // The output was closed early (e.g. when piped to `head`), which is not a failure.
+        Err(error)
+            if matches!(
+                error.downcast_ref::<io::Error>(),
+                Some(error) if error.kind() == io::ErrorKind::BrokenPipe
+            ) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
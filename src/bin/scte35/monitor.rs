@@ -0,0 +1,122 @@
+use crate::{
+    stream::{parse_pid, Cue, StreamScanner},
+    CliResult,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::Args;
+use scte35::transport_stream::{PACKET_SIZE, SYNC_BYTE};
+use std::{
+    io::{self, Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::SystemTime,
+};
+
+#[derive(Args)]
+pub struct MonitorArgs {
+    /// The address to receive the transport stream on over UDP (e.g. `239.1.1.1:5000`), which is
+    /// joined when it is a multicast group. Datagrams may carry the packets directly or within RTP.
+    /// When omitted, the transport stream is read from stdin.
+    #[arg(long)]
+    udp: Option<SocketAddr>,
+
+    /// The address of the local interface on which to join an IPv4 multicast group. When omitted,
+    /// the system chooses the interface.
+    #[arg(long, requires = "udp")]
+    interface: Option<Ipv4Addr>,
+
+    /// The PID that carries the messages (in decimal, or hex prefixed with `0x`). When omitted,
+    /// the PIDs are discovered from the program map tables.
+    #[arg(long, value_parser = parse_pid)]
+    pid: Option<u16>,
+}
+
+pub fn run(args: MonitorArgs) -> CliResult {
+    let mut out = io::stdout().lock();
+    let mut scanner = StreamScanner::new(args.pid);
+    match args.udp {
+        Some(address) => {
+            let socket = bind(address, args.interface)?;
+            let mut datagram = [0; 65536];
+            loop {
+                let length = socket.recv(&mut datagram)?;
+                for packet in
+                    transport_stream_payload(&datagram[..length]).chunks_exact(PACKET_SIZE)
+                {
+                    push_packet(&mut out, &mut scanner, packet)?;
+                }
+            }
+        }
+        None => {
+            let mut stdin = io::stdin().lock();
+            let mut packet = [0; PACKET_SIZE];
+            loop {
+                match stdin.read_exact(&mut packet) {
+                    Ok(()) => push_packet(&mut out, &mut scanner, &packet)?,
+                    Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
+    }
+}
+
+fn bind(address: SocketAddr, interface: Option<Ipv4Addr>) -> io::Result<UdpSocket> {
+    match address.ip() {
+        IpAddr::V4(group) if group.is_multicast() => {
+            let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, address.port()))?;
+            socket.join_multicast_v4(&group, &interface.unwrap_or(Ipv4Addr::UNSPECIFIED))?;
+            Ok(socket)
+        }
+        IpAddr::V6(group) if group.is_multicast() => {
+            let socket = UdpSocket::bind((Ipv6Addr::UNSPECIFIED, address.port()))?;
+            socket.join_multicast_v6(&group, 0)?;
+            Ok(socket)
+        }
+        _ => UdpSocket::bind(address),
+    }
+}
+
+// The transport stream packets of a datagram, skipping the RTP header when the datagram does not
+// start with a packet.
+fn transport_stream_payload(datagram: &[u8]) -> &[u8] {
+    let is_rtp = datagram.len() >= 12 && datagram[0] != SYNC_BYTE && datagram[0] >> 6 == 2;
+    if !is_rtp {
+        return datagram;
+    }
+    let csrc_count = (datagram[0] & 0x0F) as usize;
+    let mut header_length = 12 + 4 * csrc_count;
+    let has_extension = datagram[0] & 0x10 != 0;
+    if has_extension {
+        let Some(extension) = datagram.get(header_length..header_length + 4) else {
+            return &[];
+        };
+        header_length += 4 + 4 * u16::from_be_bytes([extension[2], extension[3]]) as usize;
+    }
+    datagram.get(header_length..).unwrap_or(&[])
+}
+
+fn push_packet(out: &mut impl Write, scanner: &mut StreamScanner, packet: &[u8]) -> CliResult {
+    for result in scanner.push_packet(packet) {
+        match result {
+            Ok(cue) => print(out, &cue)?,
+            Err(error) => eprintln!("warning: {}", error),
+        }
+    }
+    Ok(())
+}
+
+// Writes the cue as a single line of JSON, flushing so that it is seen as soon as it arrives.
+fn print(out: &mut impl Write, cue: &Cue) -> CliResult {
+    let received_at = DateTime::<Utc>::from(SystemTime::now());
+    let value = serde_json::json!({
+        "received_at": received_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        "pid": cue.pid,
+        "program_number": cue.program_number,
+        "last_pts": cue.last_pts,
+        "splice_info_section": cue.splice_info_section,
+    });
+    serde_json::to_writer(&mut *out, &value).map_err(io::Error::from)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use crate::{
+    format::{decode_text, stdin_text, write_encoded, Encoding, InputFormat},
+    CliResult, Failures,
+};
+use clap::Args;
+use scte35::{
+    edit::SectionEditor, error::SectionEditError, parser::Parser,
+    splice_info_section::SpliceInfoSection, time::Pts90k,
+};
+use std::io::{self, Read};
+
+#[derive(Args)]
+pub struct FixCrcArgs {
+    #[command(flatten)]
+    patch: PatchArgs,
+}
+
+#[derive(Args)]
+pub struct RestampArgs {
+    /// The `pts_adjustment` to set, in 90 kHz ticks.
+    #[arg(
+        long,
+        value_name = "TICKS",
+        required_unless_present = "add_pts_adjustment"
+    )]
+    pts_adjustment: Option<u64>,
+
+    /// The number of 90 kHz ticks to add to the existing `pts_adjustment` (wrapping at 33 bits),
+    /// as is done by each device that restamps the PTS of the stream.
+    #[arg(long, value_name = "TICKS", conflicts_with = "pts_adjustment")]
+    add_pts_adjustment: Option<u64>,
+
+    #[command(flatten)]
+    patch: PatchArgs,
+}
+
+#[derive(Args)]
+struct PatchArgs {
+    /// The message to patch. When omitted, messages are read from stdin: either one per line, or
+    /// as consecutive binary sections.
+    message: Option<String>,
+
+    /// The encoding of the messages.
+    #[arg(short, long, value_enum, default_value_t = InputFormat::Auto)]
+    input: InputFormat,
+
+    /// The encoding of the patched messages. When omitted, each message is written in the
+    /// encoding that it was read in.
+    #[arg(short, long, value_enum)]
+    output: Option<Encoding>,
+
+    /// Re-encodes each patched message in its canonical form (as with
+    /// `SpliceInfoSection::canonicalize`), rather than preserving all of its other bytes.
+    #[arg(long)]
+    canonicalize: bool,
+}
+
+pub fn run_fix_crc(args: FixCrcArgs) -> CliResult {
+    run(args.patch, |editor| {
+        if !editor.recalculate_crc_32() {
+            eprintln!("warning: the crc_32 was already valid.");
+        }
+        Ok(())
+    })
+}
+
+pub fn run_restamp(args: RestampArgs) -> CliResult {
+    run(args.patch, |editor| {
+        match (args.pts_adjustment, args.add_pts_adjustment) {
+            (Some(pts_adjustment), _) => editor.set_pts_adjustment(Pts90k(pts_adjustment)),
+            (None, Some(delta)) => editor.add_pts_adjustment(Pts90k(delta)),
+            (None, None) => unreachable!("one of the options is required"),
+        }
+    })
+}
+
+// Reads the messages in the same way as `decode`, applying the `edit` to each and writing it out.
+fn run(
+    args: PatchArgs,
+    edit: impl Fn(&mut SectionEditor) -> Result<(), SectionEditError>,
+) -> CliResult {
+    let mut out = io::stdout().lock();
+    let mut parser = Parser::new();
+    if let Some(message) = &args.message {
+        if args.input == InputFormat::Binary {
+            return Err("binary messages can only be read from stdin.".into());
+        }
+        let (mut bytes, encoding) = decode_text(&mut parser, message, args.input)?;
+        let bytes = patch(&mut bytes, &edit, args.canonicalize)?;
+        write_encoded(&mut out, &bytes, args.output.unwrap_or(encoding))?;
+        return Ok(());
+    }
+
+    let mut data = Vec::new();
+    io::stdin().lock().read_to_end(&mut data)?;
+    let mut failures = 0;
+    match stdin_text(&data, args.input)? {
+        Some(text) => {
+            for (index, line) in text.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let result = decode_text(&mut parser, line, args.input)
+                    .map_err(Box::<dyn std::error::Error>::from)
+                    .and_then(|(mut bytes, encoding)| {
+                        let bytes = patch(&mut bytes, &edit, args.canonicalize)?;
+                        Ok((bytes, encoding))
+                    });
+                match result {
+                    Ok((bytes, encoding)) => {
+                        write_encoded(&mut out, &bytes, args.output.unwrap_or(encoding))?
+                    }
+                    Err(error) => {
+                        eprintln!("error: line {}: {}", index + 1, error);
+                        failures += 1;
+                    }
+                }
+            }
+        }
+        None => {
+            let mut offset = 0;
+            while offset < data.len() {
+                // The length is taken before patching, as the canonicalized section may have a
+                // different length.
+                let length =
+                    SectionEditor::new(&mut data[offset..]).map(|editor| editor.as_bytes().len());
+                let result =
+                    length
+                        .map_err(Box::<dyn std::error::Error>::from)
+                        .and_then(|length| {
+                            let section = &mut data[offset..offset + length];
+                            Ok((patch(section, &edit, args.canonicalize)?, length))
+                        });
+                match result {
+                    Ok((bytes, length)) => {
+                        write_encoded(&mut out, &bytes, args.output.unwrap_or(Encoding::Binary))?;
+                        offset += length;
+                    }
+                    // The length of an invalid section may not be known, and so the rest of the
+                    // data cannot be read.
+                    Err(error) => {
+                        eprintln!("error: offset {}: {}", offset, error);
+                        failures += 1;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(Box::new(Failures {
+            count: failures,
+            action: "patched",
+        }));
+    }
+    Ok(())
+}
+
+// Applies the `edit` to the section at the start of `bytes`, returning the patched section.
+fn patch(
+    bytes: &mut [u8],
+    edit: impl Fn(&mut SectionEditor) -> Result<(), SectionEditError>,
+    canonicalize: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut editor = SectionEditor::new(bytes)?;
+    edit(&mut editor)?;
+    if !canonicalize {
+        return Ok(editor.as_bytes().to_vec());
+    }
+    let mut splice_info_section = SpliceInfoSection::try_from_bytes(editor.as_bytes())?;
+    Ok(splice_info_section.canonicalize()?)
+}
@@ -0,0 +1,119 @@
+use crate::{
+    format::{write_section, OutputFormat},
+    stream::{parse_pid, Cue, StreamScanner},
+    CliResult,
+};
+use clap::Args;
+use scte35::transport_stream::PACKET_SIZE;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    path::PathBuf,
+};
+
+#[derive(Args)]
+pub struct ScanArgs {
+    /// The transport stream file to scan.
+    file: PathBuf,
+
+    /// The PID that carries the messages (in decimal, or hex prefixed with `0x`). When omitted,
+    /// the PIDs are discovered from the program map tables.
+    #[arg(long, value_parser = parse_pid)]
+    pid: Option<u16>,
+
+    /// How the messages are printed.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+}
+
+pub fn run(args: ScanArgs) -> CliResult {
+    let mut reader = BufReader::new(File::open(&args.file)?);
+    let mut out = io::stdout().lock();
+    let mut scanner = StreamScanner::new(args.pid);
+    let mut packet = [0; PACKET_SIZE];
+    let mut packet_index = 0;
+    let mut cue_count = 0;
+    loop {
+        match reader.read_exact(&mut packet) {
+            Ok(()) => (),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error.into()),
+        }
+        for result in scanner.push_packet(&packet) {
+            match result {
+                Ok(cue) => {
+                    print(&mut out, &cue, packet_index, args.output)?;
+                    cue_count += 1;
+                }
+                Err(error) => eprintln!(
+                    "warning: offset {}: {}",
+                    packet_index * PACKET_SIZE as u64,
+                    error
+                ),
+            }
+        }
+        packet_index += 1;
+    }
+    if !scanner.has_streams() {
+        return Err("no SCTE-35 streams were found (use --pid to scan a known PID).".into());
+    }
+    eprintln!("Messages: {} (in {} packets).", cue_count, packet_index);
+    Ok(())
+}
+
+// The `packet_index` is the index of the packet that completed the section.
+fn print(out: &mut impl Write, cue: &Cue, packet_index: u64, format: OutputFormat) -> CliResult {
+    match format {
+        OutputFormat::Text => {
+            writeln!(out, "{}", describe(cue, packet_index))?;
+            write_section(out, &cue.splice_info_section, format)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Xml => {
+            writeln!(out, "<!-- {} -->", describe(cue, packet_index))?;
+            write_section(out, &cue.splice_info_section, format)?;
+        }
+        OutputFormat::Json => {
+            let value = serde_json::json!({
+                "pid": cue.pid,
+                "program_number": cue.program_number,
+                "packet_index": packet_index,
+                "offset": packet_index * PACKET_SIZE as u64,
+                "last_pts": cue.last_pts,
+                "splice_info_section": cue.splice_info_section,
+            });
+            serde_json::to_writer_pretty(&mut *out, &value).map_err(io::Error::from)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+// e.g. "PID 0x01F0 (program 1), packet 12 (offset 2256), last PTS 1924809008 (21386.766756s),
+// splice PTS 1924989008 (+2.000000s)"
+fn describe(cue: &Cue, packet_index: u64) -> String {
+    let mut description = format!("PID 0x{:04X}", cue.pid);
+    if let Some(program_number) = cue.program_number {
+        description.push_str(&format!(" (program {})", program_number));
+    }
+    description.push_str(&format!(
+        ", packet {} (offset {})",
+        packet_index,
+        packet_index * PACKET_SIZE as u64
+    ));
+    if let Some(last_pts) = cue.last_pts {
+        description.push_str(&format!(
+            ", last PTS {} ({:.6}s)",
+            last_pts,
+            last_pts.as_duration().as_secs_f64()
+        ));
+    }
+    if let Some(splice_pts) = cue.splice_info_section.splice_pts() {
+        description.push_str(&format!(", splice PTS {}", splice_pts));
+        if let Some(last_pts) = cue.last_pts {
+            let ticks = last_pts.signed_distance_to(splice_pts);
+            description.push_str(&format!(" ({:+.6}s)", ticks as f64 / 90_000.0));
+        }
+    }
+    description
+}
@@ -0,0 +1,103 @@
+use scte35::{
+    error::TransportStreamError,
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+    transport_stream::{pes_pts, ProgramScanner, SectionExtractor},
+};
+
+/// A message found within a transport stream.
+pub struct Cue {
+    pub pid: u16,
+    /// The program that the PID was discovered from, when it was discovered from the program map
+    /// tables.
+    pub program_number: Option<u16>,
+    /// The PTS of the PES packet that most recently started before the section was completed.
+    pub last_pts: Option<Pts90k>,
+    pub splice_info_section: SpliceInfoSection,
+}
+
+/// Extracts the messages from a transport stream, packet by packet, either from a known PID or
+/// from the PIDs that are discovered from the program map tables.
+pub struct StreamScanner {
+    program_scanner: Option<ProgramScanner>,
+    extractors: Vec<(Option<u16>, SectionExtractor)>,
+    last_pts: Option<Pts90k>,
+}
+
+impl StreamScanner {
+    pub fn new(pid: Option<u16>) -> Self {
+        match pid {
+            Some(pid) => Self {
+                program_scanner: None,
+                extractors: vec![(None, SectionExtractor::new(pid))],
+                last_pts: None,
+            },
+            None => Self {
+                program_scanner: Some(ProgramScanner::new()),
+                extractors: vec![],
+                last_pts: None,
+            },
+        }
+    }
+
+    /// Whether any PID is being filtered (i.e. one was given, or has been discovered).
+    pub fn has_streams(&self) -> bool {
+        !self.extractors.is_empty()
+    }
+
+    /// Processes a single 188-byte packet, returning the messages that it completed along with
+    /// any errors, which do not prevent later packets from being processed.
+    pub fn push_packet(&mut self, packet: &[u8]) -> Vec<Result<Cue, TransportStreamError>> {
+        match pes_pts(packet) {
+            Ok(Some(pts)) => self.last_pts = Some(pts),
+            Ok(None) => (),
+            Err(error) => return vec![Err(error)],
+        }
+        let mut results = vec![];
+        if let Some(program_scanner) = &mut self.program_scanner {
+            match program_scanner.push_packet(packet) {
+                Ok(streams) => {
+                    for stream in streams {
+                        if !self
+                            .extractors
+                            .iter()
+                            .any(|(_, extractor)| extractor.pid() == stream.pid)
+                        {
+                            let extractor = SectionExtractor::new(stream.pid);
+                            self.extractors
+                                .push((Some(stream.program_number), extractor));
+                        }
+                    }
+                }
+                Err(error) => results.push(Err(error)),
+            }
+        }
+        for (program_number, extractor) in &mut self.extractors {
+            match extractor.push_packet(packet) {
+                Ok(sections) => results.extend(sections.into_iter().map(|section| {
+                    Ok(Cue {
+                        pid: extractor.pid(),
+                        program_number: *program_number,
+                        last_pts: self.last_pts,
+                        splice_info_section: section?,
+                    })
+                })),
+                Err(error) => results.push(Err(error)),
+            }
+        }
+        results
+    }
+}
+
+/// Parses a PID given in decimal, or in hex prefixed with `0x`.
+pub fn parse_pid(s: &str) -> Result<u16, String> {
+    let pid = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(|error| error.to_string())?;
+    if pid > 0x1FFF {
+        return Err("a PID must be at most 0x1FFF".to_string());
+    }
+    Ok(pid)
+}
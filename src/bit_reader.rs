@@ -1,72 +1,392 @@
-use crate::error::ParseError;
+//! A big-endian bit reader, for parsing the private bytes of custom splice descriptors (see
+//! `SpliceDescriptorRegistry`) and other vendor data with the same field-by-field reads, bounds
+//! checks and errors that are used to parse the structures defined in the specification.
+//! ```
+//! use scte35::{bit_reader::Bits, error::ParseError};
+//!
+//! let private_bytes = [0x81, 0x2C, 0x03, 0x41, 0x42, 0x43];
+//! let mut bits = Bits::new(&private_bytes);
+//! assert!(bits.bool("enabled").unwrap());
+//! assert_eq!(0x12C, bits.u16("channel", 15).unwrap());
+//! let name_length = bits.byte("name_length").unwrap() as usize;
+//! assert_eq!("ABC", bits.string("name", name_length, "reading name").unwrap());
+//! assert_eq!(0, bits.bits_remaining());
+//! assert!(matches!(
+//!     bits.byte("extra"),
+//!     Err(ParseError::UnexpectedEndOfData { .. })
+//! ));
+//! ```
+use crate::{
+    annotation::Annotation,
+    error::{LocatedParseError, ParseError},
+    splice_info_section::{ErrorHandling, ParserConfig},
+};
 use bitter::{BigEndianReader, BitReader};
+use std::borrow::Cow;
 
+/// The most bits that can be read at once by `peek`.
+pub const MAX_PEEK_BITS: u32 = bitter::MAX_READ_BITS;
+
+/// Reads big-endian fields of any bit width from a byte slice, failing with
+/// `ParseError::UnexpectedEndOfData` (rather than panicking) when there is not enough data left.
+///
+/// Reads take the `name` of the field that is being read, which is used to label the field when
+/// the data is annotated (see `annotation`).
 pub struct Bits<'a> {
-    bits: &'a mut BigEndianReader<'a>,
+    bits: BigEndianReader<'a>,
     non_fatal_errors: Vec<ParseError>,
+    config: Cow<'a, ParserConfig>,
+    allocated_bytes: usize,
+    upid_nesting_depth: usize,
+    total_bits: usize,
+    // The annotations of the fields read so far, when annotating (see `annotate`). The last
+    // element is the innermost scope that is being read, and the first holds the top-level fields.
+    annotations: Option<Vec<Annotation>>,
+    // The structures that are being read, from the outermost to the innermost.
+    path: Vec<(&'static str, Option<usize>)>,
+    // The innermost location of the last error that was returned from a structure.
+    error_location: Option<LocatedParseError>,
 }
 
 impl<'a> Bits<'a> {
-    pub fn new(bits: &'a mut BigEndianReader<'a>) -> Self {
+    /// Reads from the start of `data`, with the default `ParserConfig`.
+    pub fn new(data: &'a [u8]) -> Self {
+        let bits = BigEndianReader::new(data);
+        let total_bits = bits.bits_remaining().unwrap_or(0);
         Self {
             bits,
             non_fatal_errors: vec![],
+            config: Cow::Owned(ParserConfig::default()),
+            allocated_bytes: 0,
+            upid_nesting_depth: 0,
+            total_bits,
+            annotations: None,
+            path: vec![],
+            error_location: None,
         }
     }
 
+    /// Uses the `config` (e.g. its `limits` and error handling) for the reads that follow, rather
+    /// than the default `ParserConfig`.
+    // Borrows the `config` rather than cloning it, as it may hold a `SpliceDescriptorRegistry`.
+    pub fn set_config(&mut self, config: &'a ParserConfig) {
+        self.config = Cow::Borrowed(config);
+    }
+
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
+    /// The number of bits that are left to be read.
     pub fn bits_remaining(&self) -> usize {
         self.bits.bits_remaining().unwrap_or(0)
     }
 
-    pub fn u8(&mut self, n: u32) -> u8 {
-        self.bits.read_bits(n).unwrap() as u8
+    /// The number of bits that have been read from the start of the data.
+    pub fn bit_offset(&self) -> usize {
+        self.total_bits - self.bits_remaining()
     }
 
-    pub fn u16(&mut self, n: u32) -> u16 {
-        self.bits.read_bits(n).unwrap() as u16
+    // The reads below take the `name` of the field that is being read, which is used to label
+    // the field when annotating.
+
+    /// Reads the next `n` bits (up to 8) as an unsigned integer.
+    pub fn u8(&mut self, name: &'static str, n: u32) -> Result<u8, ParseError> {
+        self.read(name, n).map(|value| value as u8)
     }
 
-    pub fn u32(&mut self, n: u32) -> u32 {
-        self.bits.read_bits(n).unwrap() as u32
+    /// Reads the next `n` bits (up to 16) as an unsigned integer.
+    pub fn u16(&mut self, name: &'static str, n: u32) -> Result<u16, ParseError> {
+        self.read(name, n).map(|value| value as u16)
     }
 
-    pub fn u64(&mut self, n: u32) -> u64 {
-        self.bits.read_bits(n).unwrap()
+    /// Reads the next `n` bits (up to 32) as an unsigned integer.
+    pub fn u32(&mut self, name: &'static str, n: u32) -> Result<u32, ParseError> {
+        self.read(name, n).map(|value| value as u32)
     }
 
-    pub fn bool(&mut self) -> bool {
-        self.u8(1) == 1
+    /// Reads the next `n` bits (up to 64) as an unsigned integer.
+    pub fn u64(&mut self, name: &'static str, n: u32) -> Result<u64, ParseError> {
+        self.read(name, n)
     }
 
-    pub fn byte(&mut self) -> u8 {
-        self.u8(8)
+    /// Reads the next bit as a flag.
+    pub fn bool(&mut self, name: &'static str) -> Result<bool, ParseError> {
+        self.u8(name, 1).map(|value| value == 1)
     }
 
-    pub fn consume(&mut self, n: u32) {
-        self.bits.consume(n)
+    /// Reads the next 8 bits, which need not be byte aligned.
+    pub fn byte(&mut self, name: &'static str) -> Result<u8, ParseError> {
+        self.u8(name, 8)
+    }
+
+    /// Consumes `n` reserved bits.
+    pub fn consume(&mut self, n: u32) -> Result<(), ParseError> {
+        self.read("reserved", n).map(|_| ())
+    }
+
+    /// Skips the next `n` bytes without copying them.
+    pub fn skip_bytes(&mut self, name: &'static str, n: usize) -> Result<(), ParseError> {
+        self.expect_bytes(n)?;
+        let bit_offset = self.bit_offset();
+        for _ in 0..n {
+            if self.bits.read_u8().is_none() {
+                return Err(self.end_of_data_error(8));
+            }
+        }
+        self.annotate_field(name, bit_offset, None);
+        Ok(())
     }
 
+    /// Skips the next `n` bits, which need not be byte aligned.
+    pub fn skip_bits(&mut self, name: &'static str, n: usize) -> Result<(), ParseError> {
+        if !self.bits.has_bits_remaining(n) {
+            return Err(self.end_of_data_error(n));
+        }
+        let bit_offset = self.bit_offset();
+        let mut bits_left = n;
+        while bits_left > 0 {
+            let count = bits_left.min(56);
+            if self.bits.read_bits(count as u32).is_none() {
+                return Err(self.end_of_data_error(bits_left));
+            }
+            bits_left -= count;
+        }
+        self.annotate_field(name, bit_offset, None);
+        Ok(())
+    }
+
+    /// Reads the next `n` bytes as a UTF-8 string, failing with `Utf8ConversionError` (with the
+    /// `error_description`) when they are not valid UTF-8.
     pub fn string(
         &mut self,
+        name: &'static str,
         n: usize,
         error_description: &'static str,
     ) -> Result<String, ParseError> {
+        let buf = self.bytes(name, n)?;
+        String::from_utf8(buf).map_err(|e| ParseError::Utf8ConversionError {
+            error: e.utf8_error(),
+            description: error_description,
+        })
+    }
+
+    /// Reads the next `N` bytes into an array, which (unlike `bytes`) does not allocate.
+    pub fn array<const N: usize>(&mut self, name: &'static str) -> Result<[u8; N], ParseError> {
+        self.expect_bytes(N)?;
+        let bit_offset = self.bit_offset();
+        let mut buf = [0; N];
+        if !self.bits.read_bytes(&mut buf) {
+            return Err(self.end_of_data_error(N.saturating_mul(8)));
+        }
+        self.annotate_field(name, bit_offset, None);
+        Ok(buf)
+    }
+
+    /// Reads (and copies) the next `n` bytes, which counts towards the `max_allocation_bytes`
+    /// limit.
+    pub fn bytes(&mut self, name: &'static str, n: usize) -> Result<Vec<u8>, ParseError> {
+        self.expect_bytes(n)?;
+        self.allocate(n)?;
+        let bit_offset = self.bit_offset();
         let mut buf = vec![0; n];
-        self.bits.read_bytes(&mut buf);
-        std::str::from_utf8(&buf)
-            .map(ToString::to_string)
-            .map_err(|e| ParseError::Utf8ConversionError {
-                error: e,
-                description: error_description,
+        if self.bits.read_bytes(&mut buf) {
+            self.annotate_field(name, bit_offset, None);
+            Ok(buf)
+        } else {
+            Err(self.end_of_data_error(n.saturating_mul(8)))
+        }
+    }
+
+    // Reads the next `n` bits (up to 64), failing with `UnexpectedEndOfData` rather than panicking
+    // when there are not enough bits left.
+    fn read(&mut self, name: &'static str, n: u32) -> Result<u64, ParseError> {
+        if !self.bits.has_bits_remaining(n as usize) {
+            return Err(self.end_of_data_error(n as usize));
+        }
+        let bit_offset = self.bit_offset();
+        let mut value = 0;
+        let mut bits_left = n;
+        while bits_left > 0 {
+            let count = bits_left.min(MAX_PEEK_BITS);
+            match self.bits.read_bits(count) {
+                Some(bits) => value = (value << count) | bits,
+                None => return Err(self.end_of_data_error(bits_left as usize)),
+            }
+            bits_left -= count;
+        }
+        self.annotate_field(name, bit_offset, Some(value));
+        Ok(value)
+    }
+
+    /// Starts recording an `Annotation` for every field that is read (see `take_annotations`).
+    pub(crate) fn start_annotating(&mut self) {
+        self.annotations = Some(vec![Annotation::new("", self.bit_offset(), None)]);
+    }
+
+    /// The annotations of the fields that have been read, closing any scopes that are still open
+    /// (e.g. because parsing failed within them).
+    pub(crate) fn take_annotations(&mut self) -> Vec<Annotation> {
+        let Some(mut annotations) = self.annotations.take() else {
+            return vec![];
+        };
+        let bit_offset = self.bit_offset();
+        while annotations.len() > 1 {
+            Self::close_scope(&mut annotations, bit_offset);
+        }
+        annotations
+            .pop()
+            .map(|root| root.children)
+            .unwrap_or_default()
+    }
+
+    /// Reads a structure with `read`, grouping the fields that it reads under `name` when
+    /// annotating.
+    pub(crate) fn scope<T>(
+        &mut self,
+        name: &'static str,
+        read: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.scope_at(name, None, read)
+    }
+
+    /// Reads the structure at `index` of a list with `read` (see `scope`).
+    pub(crate) fn scope_at<T>(
+        &mut self,
+        name: &'static str,
+        index: Option<usize>,
+        read: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let bit_offset = self.bit_offset();
+        let depth = self.annotations.as_mut().map(|annotations| {
+            let mut annotation = Annotation::new(name, bit_offset, None);
+            annotation.index = index;
+            annotations.push(annotation);
+            annotations.len()
+        });
+        self.path.push((name, index));
+        let result = read(self);
+        if let Err(error) = &result {
+            // The error is only located when it was not already returned from a nested structure,
+            // so that it is located in the innermost structure.
+            if !matches!(&self.error_location, Some(location) if &location.error == error) {
+                self.error_location = Some(self.located(error.clone()));
+            }
+        }
+        self.path.pop();
+        let bit_offset = self.bit_offset();
+        if let (Some(annotations), Some(depth)) = (self.annotations.as_mut(), depth) {
+            while annotations.len() >= depth {
+                Self::close_scope(annotations, bit_offset);
+            }
+        }
+        result
+    }
+
+    /// The `error` along with the bit offset and path of the structure in which it occurred,
+    /// which is the current position when it was not returned from a structure.
+    pub(crate) fn locate(&mut self, error: ParseError) -> LocatedParseError {
+        match self.error_location.take() {
+            Some(location) if location.error == error => location,
+            _ => self.located(error),
+        }
+    }
+
+    fn located(&self, error: ParseError) -> LocatedParseError {
+        let mut path = String::new();
+        for (name, index) in &self.path {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(name);
+            if let Some(index) = index {
+                path.push_str(&format!("[{}]", index));
+            }
+        }
+        LocatedParseError {
+            error,
+            bit_offset: self.bit_offset(),
+            path,
+        }
+    }
+
+    fn annotate_field(&mut self, name: &'static str, bit_offset: usize, value: Option<u64>) {
+        let end_bit_offset = self.bit_offset();
+        if let Some(scope) = self
+            .annotations
+            .as_mut()
+            .and_then(|annotations| annotations.last_mut())
+        {
+            let mut annotation = Annotation::new(name, bit_offset, value);
+            annotation.bit_width = end_bit_offset - bit_offset;
+            scope.children.push(annotation);
+        }
+    }
+
+    fn close_scope(annotations: &mut Vec<Annotation>, bit_offset: usize) {
+        if let Some(mut annotation) = annotations.pop() {
+            annotation.bit_width = bit_offset - annotation.bit_offset;
+            if let Some(parent) = annotations.last_mut() {
+                parent.children.push(annotation);
+            }
+        }
+    }
+
+    // Fails before anything is allocated or consumed when fewer than `n` bytes are left.
+    fn expect_bytes(&self, n: usize) -> Result<(), ParseError> {
+        let expected_bits = n.saturating_mul(8);
+        if self.bits.has_bits_remaining(expected_bits) {
+            Ok(())
+        } else {
+            Err(self.end_of_data_error(expected_bits))
+        }
+    }
+
+    fn end_of_data_error(&self, expected_minimum_bits_left: usize) -> ParseError {
+        ParseError::UnexpectedEndOfData {
+            expected_minimum_bits_left: u32::try_from(expected_minimum_bits_left)
+                .unwrap_or(u32::MAX),
+            actual_bits_left: u32::try_from(self.bits_remaining()).unwrap_or(u32::MAX),
+            description: "reading bits",
+        }
+    }
+
+    // Accounts for `n` bytes being copied out of the data, failing when this exceeds the
+    // `max_allocation_bytes` limit.
+    fn allocate(&mut self, n: usize) -> Result<(), ParseError> {
+        self.allocated_bytes += n;
+        let limit = self.config.limits.max_allocation_bytes;
+        if self.allocated_bytes > limit {
+            Err(ParseError::LimitExceeded {
+                limit,
+                description: "max_allocation_bytes",
             })
+        } else {
+            Ok(())
+        }
     }
 
-    pub fn bytes(&mut self, n: usize) -> Vec<u8> {
-        let mut buf = vec![0; n];
-        self.bits.read_bytes(&mut buf);
-        buf
+    /// Marks the start of parsing a UPID nested within a `MID`, failing when this exceeds the
+    /// `max_upid_nesting_depth` limit. Must be balanced with `exit_nested_upid`.
+    pub(crate) fn enter_nested_upid(&mut self) -> Result<(), ParseError> {
+        self.upid_nesting_depth += 1;
+        let limit = self.config.limits.max_upid_nesting_depth;
+        if self.upid_nesting_depth > limit {
+            Err(ParseError::LimitExceeded {
+                limit,
+                description: "max_upid_nesting_depth",
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn exit_nested_upid(&mut self) {
+        self.upid_nesting_depth -= 1;
     }
 
+    /// Fails with `UnexpectedEndOfData` (with the `description`) when fewer than
+    /// `expected_minimum_bits_left` bits are left, so that a structure can be checked up front.
     pub fn validate(
         &mut self,
         expected_minimum_bits_left: u32,
@@ -85,15 +405,50 @@ impl<'a> Bits<'a> {
         }
     }
 
-    pub fn refill_lookahead(&mut self) -> u32 {
-        self.bits.refill_lookahead()
+    /// Reads the next `n` bits (from 1 up to `MAX_PEEK_BITS`) without consuming them, or `None`
+    /// when there are not enough bits left.
+    pub fn peek(&mut self, n: u32) -> Option<u64> {
+        if n == 0 || n > MAX_PEEK_BITS {
+            return None;
+        }
+        if self.bits.refill_lookahead() >= n {
+            Some(self.bits.peek(n))
+        } else {
+            None
+        }
     }
 
+    /// Records an error that does not stop the data from being read (see `handle_error`).
     pub fn push_non_fatal_error(&mut self, error: ParseError) {
         self.non_fatal_errors.push(error);
     }
 
+    /// Returns the `error` when the `handling` is `Fatal`, records it as non-fatal when the
+    /// `handling` is `NonFatal`, and otherwise discards it.
+    pub fn handle_error(
+        &mut self,
+        handling: ErrorHandling,
+        error: ParseError,
+    ) -> Result<(), ParseError> {
+        match handling {
+            ErrorHandling::Fatal => return Err(error),
+            ErrorHandling::NonFatal => {
+                #[cfg(feature = "tracing")]
+                crate::trace::non_fatal_error(&error);
+                self.push_non_fatal_error(error)
+            }
+            ErrorHandling::Ignore => (),
+        }
+        Ok(())
+    }
+
+    /// The non-fatal errors that have been recorded so far.
     pub fn get_non_fatal_errors(&self) -> &Vec<ParseError> {
         &self.non_fatal_errors
     }
+
+    /// Takes the non-fatal errors that have been recorded so far.
+    pub fn take_non_fatal_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.non_fatal_errors)
+    }
 }
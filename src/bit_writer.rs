@@ -0,0 +1,111 @@
+use crate::error::EncodeError;
+
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    bits_in_last_byte: u32,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self {
+            bytes: vec![],
+            bits_in_last_byte: 0,
+        }
+    }
+
+    pub fn u8(&mut self, n: u32, value: u8) {
+        self.u64(n, value as u64)
+    }
+
+    pub fn u16(&mut self, n: u32, value: u16) {
+        self.u64(n, value as u64)
+    }
+
+    pub fn u32(&mut self, n: u32, value: u32) {
+        self.u64(n, value as u64)
+    }
+
+    pub fn u64(&mut self, n: u32, value: u64) {
+        for i in (0..n).rev() {
+            self.bit((value >> i) & 1 == 1);
+        }
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.bit(value)
+    }
+
+    pub fn byte(&mut self, value: u8) {
+        self.u8(8, value)
+    }
+
+    /// Reserved bits are always written as `1`.
+    pub fn reserved(&mut self, n: u32) {
+        for _ in 0..n {
+            self.bit(true);
+        }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        if self.bits_in_last_byte == 0 {
+            self.bytes.extend_from_slice(bytes);
+        } else {
+            for byte in bytes {
+                self.byte(*byte);
+            }
+        }
+    }
+
+    pub fn string(&mut self, string: &str) {
+        self.bytes(string.as_bytes())
+    }
+
+    pub fn validate(
+        &self,
+        n: u32,
+        value: u64,
+        description: &'static str,
+    ) -> Result<(), EncodeError> {
+        if n < 64 && value >> n != 0 {
+            Err(EncodeError::ValueOutOfRange {
+                value,
+                maximum_bits: n,
+                description,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn validate_length(
+        &self,
+        length: usize,
+        maximum_length: usize,
+        description: &'static str,
+    ) -> Result<(), EncodeError> {
+        if length > maximum_length {
+            Err(EncodeError::LengthOutOfRange {
+                length,
+                maximum_length,
+                description,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn bit(&mut self, value: bool) {
+        if self.bits_in_last_byte == 0 {
+            self.bytes.push(0);
+        }
+        if value {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 0x80 >> self.bits_in_last_byte;
+        }
+        self.bits_in_last_byte = (self.bits_in_last_byte + 1) % 8;
+    }
+}
@@ -0,0 +1,32 @@
+// The CRC_32 of the SpliceInfoSection uses the CRC-32/MPEG-2 parameters defined in ITU-T H.222.0
+// Annex A: polynomial 0x04C11DB7, initial value 0xFFFFFFFF, no reflection and no final XOR.
+
+const POLYNOMIAL: u32 = 0x04C11DB7;
+
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80000000 != 0 {
+                (crc << 1) ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+pub fn crc_32_mpeg_2(data: &[u8]) -> u32 {
+    data.iter().fold(0xFFFFFFFF, |crc, byte| {
+        (crc << 8) ^ TABLE[(((crc >> 24) as u8) ^ byte) as usize]
+    })
+}
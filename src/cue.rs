@@ -0,0 +1,453 @@
+//! High-level helpers for generating commonly used SCTE-35 cues, for summarising a message as a
+//! `Cue`, and for converting cues between the `SpliceInsert` and `TimeSignal` dialects.
+
+use crate::{
+    error::{CueConversionError, EncodeError},
+    splice_command::{
+        splice_insert::{SpliceInsert, SpliceMode},
+        time_signal::TimeSignal,
+        SpliceCommand, SpliceCommandType,
+    },
+    splice_descriptor::{
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationTypeID, SegmentationUPID},
+        SpliceDescriptor,
+    },
+    splice_info_section::{SpliceInfoSection, SpliceInfoSectionBuilder},
+    time::{duration_from_ticks, Pts90k, SpliceTime},
+};
+use std::{iter, time::Duration};
+
+/// Describes a single ad break, from which the matching pair of out (Provider Placement
+/// Opportunity Start) and in (Provider Placement Opportunity End) `time_signal` messages can be
+/// generated.
+///
+/// ```
+/// use scte35::cue::AdBreak;
+///
+/// let (cue_out, cue_in) = AdBreak::new(1207959694, 1924989008, 27630000)
+///     .upid_ti(0x2CA0A18A)
+///     .build()
+///     .unwrap();
+/// let cue_out_hex = cue_out.try_to_hex_string().unwrap();
+/// let cue_in_hex = cue_in.try_to_hex_string().unwrap();
+/// assert_ne!(cue_out_hex, cue_in_hex);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct AdBreak {
+    event_id: u32,
+    pts_time: u64,
+    duration: u64,
+    segmentation_upid: SegmentationUPID,
+}
+
+impl AdBreak {
+    /// Creates an `AdBreak` starting at `pts_time` and lasting for `duration`, both in terms of
+    /// ticks of the program's 90 kHz clock.
+    pub fn new(event_id: u32, pts_time: u64, duration: u64) -> Self {
+        Self {
+            event_id,
+            pts_time,
+            duration,
+            segmentation_upid: SegmentationUPID::NotUsed,
+        }
+    }
+
+    /// Sets the `segmentation_upid` used on both the out and the in cue.
+    pub fn upid(mut self, segmentation_upid: SegmentationUPID) -> Self {
+        self.segmentation_upid = segmentation_upid;
+        self
+    }
+
+    pub fn upid_ti(self, ti: u64) -> Self {
+        self.upid(SegmentationUPID::new_ti(ti))
+    }
+
+    /// The PTS at which the ad break ends (wrapped to 33 bits).
+    pub fn end_pts_time(&self) -> u64 {
+        (Pts90k(self.pts_time) + Pts90k(self.duration)).ticks()
+    }
+
+    /// Builds the out (Provider Placement Opportunity Start, including the `segmentation_duration`)
+    /// and in (Provider Placement Opportunity End) `SpliceInfoSection`s, in that order.
+    pub fn build(&self) -> Result<(SpliceInfoSection, SpliceInfoSection), EncodeError> {
+        let cue_out = Self::time_signal(
+            self.pts_time,
+            SegmentationDescriptor::builder(self.event_id)
+                .provider_placement_opportunity_start()
+                .duration_90khz(self.duration)
+                .upid(self.segmentation_upid.clone())
+                .build()?,
+        )?;
+        let cue_in = Self::time_signal(
+            self.end_pts_time(),
+            SegmentationDescriptor::builder(self.event_id)
+                .provider_placement_opportunity_end()
+                .upid(self.segmentation_upid.clone())
+                .build()?,
+        )?;
+        Ok((cue_out, cue_in))
+    }
+
+    fn time_signal(
+        pts_time: u64,
+        segmentation_descriptor: SegmentationDescriptor,
+    ) -> Result<SpliceInfoSection, EncodeError> {
+        SpliceInfoSection::builder()
+            .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+                splice_time: SpliceTime {
+                    pts_time: Some(Pts90k(pts_time)),
+                },
+            }))
+            .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+                segmentation_descriptor,
+            ))
+            .build()
+    }
+}
+
+/// A simplified summary of a message, for applications that only need to know what kind of event
+/// is signaled and when, without handling each of the splice commands and segmentation types.
+///
+/// A `SpliceInsert` is summarised from its `out_of_network_indicator` (as an ad break start or
+/// end), and a `TimeSignal` from the `segmentation_type_id` of its first segmentation descriptor.
+/// ```
+/// use scte35::{
+///     cue::Cue,
+///     splice_descriptor::segmentation_descriptor::SegmentationUPID,
+///     splice_info_section::SpliceInfoSection,
+///     time::Pts90k,
+/// };
+/// use std::time::Duration;
+///
+/// let hex = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+/// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex).unwrap();
+/// let Cue::AdBreakStart(details) = Cue::from(&splice_info_section) else {
+///     panic!("expected ad break start");
+/// };
+/// assert_eq!(Some(Pts90k(1924989008)), details.pts);
+/// assert_eq!(Some(Duration::from_secs(307)), details.duration);
+/// assert_eq!(Some(0x4800008E), details.event_id);
+/// assert_eq!(Some(SegmentationUPID::new_ti(0x2CA0A18A)), details.upid);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cue {
+    /// The start of an ad break: a `SpliceInsert` out, or the start of a break, advertisement,
+    /// placement opportunity (including overlays) or ad block.
+    AdBreakStart(CueDetails),
+    /// The end of an ad break: a `SpliceInsert` in, or the end of a break, advertisement,
+    /// placement opportunity (including overlays) or ad block.
+    AdBreakEnd(CueDetails),
+    /// A change of program, such as a `ProgramStart`, `ProgramEnd`, `ProgramEarlyTermination` or
+    /// `ProgramBreakaway` (see the `segmentation_type_id` of the `CueDetails`).
+    ProgramBoundary(CueDetails),
+    /// The start or end of a chapter (see the `segmentation_type_id` of the `CueDetails`).
+    Chapter(CueDetails),
+    /// A previously signaled event, identified by the `event_id`, has been cancelled.
+    Cancelled(CueDetails),
+    /// Any other message, such as a `SpliceNull` heartbeat, a `TimeSignal` without a segmentation
+    /// descriptor, or a segmentation type that is not summarised by the other cases.
+    Unknown(CueDetails),
+}
+
+/// The details of a `Cue`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CueDetails {
+    /// The time of the cue, including the `pts_adjustment` (see `SpliceInfoSection::splice_pts`),
+    /// which is `None` for an immediate message.
+    pub pts: Option<Pts90k>,
+    /// The `break_duration` of a `SpliceInsert`, or the `segmentation_duration` of the
+    /// segmentation descriptor.
+    pub duration: Option<Duration>,
+    /// The `event_id` of the `SpliceInsert`, or of the segmentation descriptor.
+    pub event_id: Option<u32>,
+    /// The `segmentation_type_id` of the segmentation descriptor, if any.
+    pub segmentation_type_id: Option<SegmentationTypeID>,
+    /// The `segmentation_upid` of the segmentation descriptor, if one is used.
+    pub upid: Option<SegmentationUPID>,
+}
+
+impl Cue {
+    pub fn details(&self) -> &CueDetails {
+        match self {
+            Cue::AdBreakStart(details)
+            | Cue::AdBreakEnd(details)
+            | Cue::ProgramBoundary(details)
+            | Cue::Chapter(details)
+            | Cue::Cancelled(details)
+            | Cue::Unknown(details) => details,
+        }
+    }
+}
+
+impl From<&SpliceInfoSection> for Cue {
+    fn from(splice_info_section: &SpliceInfoSection) -> Self {
+        let segmentation_descriptor = splice_info_section.segmentation_descriptors().next();
+        let segmentation_event =
+            segmentation_descriptor.and_then(|descriptor| descriptor.scheduled_event.as_ref());
+        let mut details = CueDetails {
+            pts: splice_info_section.splice_pts(),
+            duration: None,
+            event_id: segmentation_descriptor.map(|descriptor| descriptor.event_id),
+            segmentation_type_id: segmentation_event
+                .map(|event| event.segmentation_type_id.clone()),
+            upid: segmentation_event
+                .map(|event| event.segmentation_upid.clone())
+                .filter(|upid| *upid != SegmentationUPID::NotUsed),
+        };
+        match &splice_info_section.splice_command {
+            SpliceCommand::SpliceInsert(splice_insert) => {
+                details.event_id = Some(splice_insert.event_id);
+                let Some(event) = &splice_insert.scheduled_event else {
+                    return Cue::Cancelled(details);
+                };
+                details.duration = event
+                    .break_duration
+                    .as_ref()
+                    .map(|break_duration| duration_from_ticks(break_duration.duration.ticks()));
+                if event.out_of_network_indicator {
+                    Cue::AdBreakStart(details)
+                } else {
+                    Cue::AdBreakEnd(details)
+                }
+            }
+            SpliceCommand::TimeSignal(_) => {
+                if segmentation_descriptor.is_some_and(SegmentationDescriptor::is_cancelled) {
+                    return Cue::Cancelled(details);
+                }
+                let Some(event) = segmentation_event else {
+                    return Cue::Unknown(details);
+                };
+                details.duration = event.segmentation_duration.map(duration_from_ticks);
+                match event.segmentation_type_id {
+                    SegmentationTypeID::BreakStart
+                    | SegmentationTypeID::ProviderAdvertisementStart
+                    | SegmentationTypeID::DistributorAdvertisementStart
+                    | SegmentationTypeID::ProviderPlacementOpportunityStart
+                    | SegmentationTypeID::DistributorPlacementOpportunityStart
+                    | SegmentationTypeID::ProviderOverlayPlacementOpportunityStart
+                    | SegmentationTypeID::DistributorOverlayPlacementOpportunityStart
+                    | SegmentationTypeID::ProviderAdBlockStart
+                    | SegmentationTypeID::DistributorAdBlockStart => Cue::AdBreakStart(details),
+                    SegmentationTypeID::BreakEnd
+                    | SegmentationTypeID::ProviderAdvertisementEnd
+                    | SegmentationTypeID::DistributorAdvertisementEnd
+                    | SegmentationTypeID::ProviderPlacementOpportunityEnd
+                    | SegmentationTypeID::DistributorPlacementOpportunityEnd
+                    | SegmentationTypeID::ProviderOverlayPlacementOpportunityEnd
+                    | SegmentationTypeID::DistributorOverlayPlacementOpportunityEnd
+                    | SegmentationTypeID::ProviderAdBlockEnd
+                    | SegmentationTypeID::DistributorAdBlockEnd => Cue::AdBreakEnd(details),
+                    SegmentationTypeID::ProgramStart
+                    | SegmentationTypeID::ProgramEnd
+                    | SegmentationTypeID::ProgramEarlyTermination
+                    | SegmentationTypeID::ProgramBreakaway
+                    | SegmentationTypeID::ProgramResumption
+                    | SegmentationTypeID::ProgramRunoverPlanned
+                    | SegmentationTypeID::ProgramRunoverUnplanned
+                    | SegmentationTypeID::ProgramOverlapStart
+                    | SegmentationTypeID::ProgramBlackoutOverride
+                    | SegmentationTypeID::ProgramJoin => Cue::ProgramBoundary(details),
+                    SegmentationTypeID::ChapterStart | SegmentationTypeID::ChapterEnd => {
+                        Cue::Chapter(details)
+                    }
+                    _ => Cue::Unknown(details),
+                }
+            }
+            _ => Cue::Unknown(details),
+        }
+    }
+}
+
+/// Converts a `SpliceInsert` message into the equivalent `TimeSignal` message, for systems that
+/// only accept the `TimeSignal` dialect (see `time_signal_to_splice_insert` for the reverse).
+///
+/// An out (with the `out_of_network_indicator` set) becomes a Provider Placement Opportunity
+/// Start, with the `break_duration` as the `segmentation_duration`, and an in becomes a Provider
+/// Placement Opportunity End. The `event_id` is kept, and the `avail_num` and `avails_expected`
+/// become the `segment_num` and `segments_expected`. A cancelled `SpliceInsert` becomes an
+/// immediate `TimeSignal` with a cancelled segmentation descriptor.
+///
+/// The segmentation descriptor is placed before the descriptors of the message that are allowed
+/// with a `TimeSignal` (so avail and DTMF descriptors are not kept). The `auto_return` and
+/// `unique_program_id` have no equivalent, and the converted message is not encrypted.
+/// ```
+/// use scte35::{
+///     cue::{splice_insert_to_time_signal, time_signal_to_splice_insert},
+///     splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+///     splice_descriptor::segmentation_descriptor::SegmentationTypeID,
+///     splice_info_section::SpliceInfoSection,
+///     time::Pts90k,
+/// };
+///
+/// let splice_insert = SpliceInsert::builder(1)
+///     .cue_out()
+///     .splice_time(Pts90k(1924989008))
+///     .break_duration(Pts90k(27630000), false)
+///     .build()
+///     .unwrap();
+/// let cue_out = SpliceInfoSection::builder()
+///     .splice_command(SpliceCommand::SpliceInsert(splice_insert))
+///     .build()
+///     .unwrap();
+///
+/// let time_signal = splice_insert_to_time_signal(&cue_out).unwrap();
+/// assert_eq!(Some(Pts90k(1924989008)), time_signal.splice_pts());
+/// let segmentation_descriptor = time_signal
+///     .find_segmentation(&SegmentationTypeID::ProviderPlacementOpportunityStart)
+///     .unwrap();
+/// assert_eq!(1, segmentation_descriptor.event_id);
+///
+/// assert_eq!(cue_out, time_signal_to_splice_insert(&time_signal).unwrap());
+/// ```
+pub fn splice_insert_to_time_signal(
+    splice_info_section: &SpliceInfoSection,
+) -> Result<SpliceInfoSection, CueConversionError> {
+    let SpliceCommand::SpliceInsert(splice_insert) = &splice_info_section.splice_command else {
+        return Err(CueConversionError::UnexpectedSpliceCommand(
+            splice_info_section.splice_command.command_type(),
+        ));
+    };
+    let builder = SegmentationDescriptor::builder(splice_insert.event_id);
+    let (pts_time, segmentation_descriptor) = match &splice_insert.scheduled_event {
+        None => (None, builder.cancel().build()?),
+        Some(event) => {
+            let pts_time = match &event.splice_mode {
+                SpliceMode::ProgramSpliceMode(program) => program
+                    .splice_time
+                    .as_ref()
+                    .and_then(|splice_time| splice_time.pts_time),
+                SpliceMode::ComponentSpliceMode(_) => {
+                    return Err(CueConversionError::ComponentSpliceMode)
+                }
+            };
+            let builder = if event.out_of_network_indicator {
+                let builder = builder.provider_placement_opportunity_start();
+                match &event.break_duration {
+                    Some(break_duration) => builder.duration_90khz(break_duration.duration.ticks()),
+                    None => builder,
+                }
+            } else {
+                builder.provider_placement_opportunity_end()
+            };
+            let segmentation_descriptor = builder
+                .segment_num(event.avail_num)
+                .segments_expected(event.avails_expected)
+                .build()?;
+            (pts_time, segmentation_descriptor)
+        }
+    };
+    let splice_descriptors = iter::once(segmentation_descriptor.into())
+        .chain(descriptors_allowed_with(
+            splice_info_section.splice_descriptors.iter(),
+            &SpliceCommandType::TimeSignal,
+        ))
+        .collect();
+    Ok(section_builder(splice_info_section)
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime { pts_time },
+        }))
+        .splice_descriptors(splice_descriptors)
+        .build()?)
+}
+
+/// Converts a `TimeSignal` message into the equivalent `SpliceInsert` message, for systems that
+/// only accept the `SpliceInsert` dialect (see `splice_insert_to_time_signal` for the reverse).
+///
+/// The first segmentation descriptor that is a Provider Placement Opportunity Start or End (or
+/// that is cancelled) is converted: a start becomes an out (with the `segmentation_duration` as
+/// the `break_duration`, with `auto_return` not set), an end becomes an in, and a cancelled
+/// descriptor becomes a cancelled `SpliceInsert`. The `event_id` is kept, and the `segment_num`
+/// and `segments_expected` become the `avail_num` and `avails_expected`. A `TimeSignal` without a
+/// `pts_time` becomes an immediate `SpliceInsert`.
+///
+/// The converted segmentation descriptor is not kept (so neither is its `segmentation_upid`), the
+/// other descriptors of the message that are allowed with a `SpliceInsert` are kept (so audio
+/// descriptors are not kept), and the converted message is not encrypted.
+pub fn time_signal_to_splice_insert(
+    splice_info_section: &SpliceInfoSection,
+) -> Result<SpliceInfoSection, CueConversionError> {
+    let SpliceCommand::TimeSignal(time_signal) = &splice_info_section.splice_command else {
+        return Err(CueConversionError::UnexpectedSpliceCommand(
+            splice_info_section.splice_command.command_type(),
+        ));
+    };
+    let Some((index, segmentation_descriptor)) = splice_info_section
+        .splice_descriptors
+        .iter()
+        .enumerate()
+        .find_map(|(index, descriptor)| {
+            let segmentation_descriptor = descriptor.as_segmentation()?;
+            let is_placement_opportunity = segmentation_descriptor
+                .scheduled_event
+                .as_ref()
+                .is_none_or(|event| {
+                    matches!(
+                        event.segmentation_type_id,
+                        SegmentationTypeID::ProviderPlacementOpportunityStart
+                            | SegmentationTypeID::ProviderPlacementOpportunityEnd
+                    )
+                });
+            is_placement_opportunity.then_some((index, segmentation_descriptor))
+        })
+    else {
+        return Err(CueConversionError::MissingSegmentationDescriptor);
+    };
+    let builder = SpliceInsert::builder(segmentation_descriptor.event_id);
+    let splice_insert = match &segmentation_descriptor.scheduled_event {
+        None => builder.cancel().build()?,
+        Some(event) => {
+            let builder = match time_signal.splice_time.pts_time {
+                Some(pts_time) => builder.splice_time(pts_time),
+                None => builder.immediate(),
+            };
+            let builder = if event.segmentation_type_id
+                == SegmentationTypeID::ProviderPlacementOpportunityStart
+            {
+                let builder = builder.cue_out();
+                match event.segmentation_duration {
+                    Some(duration) => builder.break_duration(Pts90k(duration), false),
+                    None => builder,
+                }
+            } else {
+                builder.cue_in()
+            };
+            builder
+                .avail_num(event.segment_num)
+                .avails_expected(event.segments_expected)
+                .build()?
+        }
+    };
+    let other_descriptors = splice_info_section
+        .splice_descriptors
+        .iter()
+        .enumerate()
+        .filter(|(other_index, _)| *other_index != index)
+        .map(|(_, descriptor)| descriptor);
+    Ok(section_builder(splice_info_section)
+        .splice_command(SpliceCommand::SpliceInsert(splice_insert))
+        .splice_descriptors(
+            descriptors_allowed_with(other_descriptors, &SpliceCommandType::SpliceInsert).collect(),
+        )
+        .build()?)
+}
+
+// A builder with the fields of the `splice_info_section` that are kept by a conversion.
+fn section_builder(splice_info_section: &SpliceInfoSection) -> SpliceInfoSectionBuilder {
+    SpliceInfoSection::builder()
+        .table_id(splice_info_section.table_id)
+        .sap_type(splice_info_section.sap_type.clone())
+        .protocol_version(splice_info_section.protocol_version)
+        .pts_adjustment(splice_info_section.pts_adjustment)
+        .tier(splice_info_section.tier)
+}
+
+fn descriptors_allowed_with<'a>(
+    splice_descriptors: impl Iterator<Item = &'a SpliceDescriptor> + 'a,
+    splice_command_type: &'a SpliceCommandType,
+) -> impl Iterator<Item = SpliceDescriptor> + 'a {
+    splice_descriptors
+        .filter(|descriptor| descriptor.tag().is_allowed_with(splice_command_type))
+        .cloned()
+}
@@ -0,0 +1,104 @@
+//! Conversion of SCTE-35 messages into MPEG-DASH MPD `EventStream` elements, using the
+//! `urn:scte:scte35:2014:xml+bin` scheme, whereby each `Event` carries the base64 encoded binary
+//! `SpliceInfoSection` in its `messageData` attribute.
+//!
+//! ```
+//! use scte35::{dash::EventStream, splice_info_section::SpliceInfoSection};
+//!
+//! let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let event = splice_info_section.try_to_dash_event().unwrap();
+//! assert_eq!(Some(1936310318), event.presentation_time);
+//! assert_eq!(Some(5426421), event.duration);
+//! assert_eq!(Some(1207959695), event.id);
+//!
+//! let event_stream = EventStream::new(vec![event]);
+//! assert!(event_stream.to_xml_string().starts_with(
+//!     r#"<EventStream schemeIdUri="urn:scte:scte35:2014:xml+bin" timescale="90000">"#
+//! ));
+//! ```
+
+use crate::{
+    error::EncodeError, splice_info_section::SpliceInfoSection, time::Pts90k, xml::element::Element,
+};
+use base64::prelude::*;
+
+/// The scheme for SCTE-35 events carried as base64 encoded binary.
+pub const SCTE35_XML_BIN_SCHEME_ID_URI: &str = "urn:scte:scte35:2014:xml+bin";
+
+/// An MPD `EventStream` carrying SCTE-35 events in terms of the 90 kHz clock.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct EventStream {
+    pub scheme_id_uri: String,
+    /// The timescale of `presentation_time` and `duration` on each `Event`.
+    pub timescale: u64,
+    pub events: Vec<Event>,
+}
+
+/// An `Event` within an MPD `EventStream`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Event {
+    /// The PTS of the splice point (including the `pts_adjustment`), or `None` for an immediate
+    /// splice.
+    pub presentation_time: Option<u64>,
+    /// Derived from the `break_duration` of a `SpliceInsert` or the `segmentation_duration` of the
+    /// first `SegmentationDescriptor` that defines one.
+    pub duration: Option<u64>,
+    /// Derived from the `event_id` of a `SpliceInsert` or the first `SegmentationDescriptor`.
+    pub id: Option<u32>,
+    /// The base64 encoded binary `SpliceInfoSection`.
+    pub message_data: String,
+}
+
+impl EventStream {
+    /// Creates an `EventStream` with the `urn:scte:scte35:2014:xml+bin` scheme and a timescale of
+    /// 90000.
+    pub fn new(events: Vec<Event>) -> Self {
+        Self {
+            scheme_id_uri: String::from(SCTE35_XML_BIN_SCHEME_ID_URI),
+            timescale: 90000,
+            events,
+        }
+    }
+
+    pub fn to_xml_string(&self) -> String {
+        let element = Element::new("EventStream")
+            .with_attribute("schemeIdUri", &self.scheme_id_uri)
+            .with_attribute("timescale", self.timescale)
+            .with_children(self.events.iter().map(Event::to_element));
+        let mut xml = String::new();
+        element.write(&mut xml);
+        xml
+    }
+}
+
+impl Event {
+    pub fn to_xml_string(&self) -> String {
+        let mut xml = String::new();
+        self.to_element().write(&mut xml);
+        xml
+    }
+
+    fn to_element(&self) -> Element {
+        Element::new("Event")
+            .with_optional_attribute("presentationTime", self.presentation_time)
+            .with_optional_attribute("duration", self.duration)
+            .with_optional_attribute("id", self.id)
+            .with_attribute("messageData", &self.message_data)
+    }
+}
+
+impl SpliceInfoSection {
+    /// Creates an MPD `Event` for the `SpliceInfoSection`, with timing values in terms of the
+    /// 90 kHz clock.
+    pub fn try_to_dash_event(&self) -> Result<Event, EncodeError> {
+        let message_data = BASE64_STANDARD.encode(self.try_to_bytes()?);
+        let (id, duration) = self.event_id_and_duration();
+        Ok(Event {
+            presentation_time: self.adjusted_splice_time().map(Pts90k::ticks),
+            duration,
+            id,
+            message_data,
+        })
+    }
+}
@@ -0,0 +1,497 @@
+//! A field-level comparison of two `SpliceInfoSection`, e.g. to compare the cues produced by
+//! different encoders for the same event, or a cue before and after a conditioning service.
+//! ```
+//! use scte35::{
+//!     diff::FieldDifference,
+//!     splice_descriptor::SpliceDescriptor,
+//!     splice_info_section::SpliceInfoSection,
+//!     time::Pts90k,
+//! };
+//!
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let before = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let mut after = before.clone();
+//! after.pts_adjustment = Pts90k(90_000);
+//! if let SpliceDescriptor::SegmentationDescriptor(descriptor) = &mut after.splice_descriptors[0] {
+//!     descriptor.scheduled_event.as_mut().unwrap().segments_expected = 4;
+//! }
+//!
+//! let differences = before.diff(&after);
+//! assert_eq!(
+//!     vec![
+//!         "pts_adjustment: 0 (0.000000s) -> 90000 (1.000000s)",
+//!         "splice_descriptors[0].scheduled_event.segments_expected: 0 -> 4",
+//!     ],
+//!     differences.iter().map(FieldDifference::to_string).collect::<Vec<_>>()
+//! );
+//! ```
+use crate::{
+    display::{Identifier, Ticks},
+    hex::encode_hex,
+    splice_command::{
+        splice_insert::{self, SpliceInsert},
+        splice_schedule::{self, SpliceSchedule},
+        SpliceCommand,
+    },
+    splice_descriptor::{
+        audio_descriptor::{AudioDescriptor, NumChannels},
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationUPID},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::{BreakDuration, Pts90k, SpliceTime},
+};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+/// A field that differs between two `SpliceInfoSection`.
+///
+/// The `path` is made up of the names of the fields of the model types (e.g.
+/// `splice_command.scheduled_event.break_duration.duration`), with the index of any list element
+/// in square brackets (e.g. `splice_descriptors[0].event_id`). A value of `None` means that the
+/// field is not present in that section (e.g. because it has fewer descriptors, or a different
+/// `splice_command`), whereas an optional field that is present but not set has the value
+/// `"None"`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldDifference {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl Display for FieldDifference {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            self.before.as_deref().unwrap_or("(absent)"),
+            self.after.as_deref().unwrap_or("(absent)")
+        )
+    }
+}
+
+impl SpliceInfoSection {
+    /// Compares each field of this section (the "before") with that of `other` (the "after"),
+    /// returning the fields that differ (see `FieldDifference`). The fields are in the order that
+    /// they are encoded, with those only present in `other` at the end. The `non_fatal_errors`
+    /// are not compared, as they are not part of the message.
+    pub fn diff(&self, other: &SpliceInfoSection) -> Vec<FieldDifference> {
+        let before = Fields::from(self).0;
+        let after = Fields::from(other).0;
+        let after_values = after
+            .iter()
+            .map(|(path, value)| (path.as_str(), value.as_str()))
+            .collect::<HashMap<_, _>>();
+        let before_values = before
+            .iter()
+            .map(|(path, value)| (path.as_str(), value.as_str()))
+            .collect::<HashMap<_, _>>();
+        let changed_or_removed =
+            before
+                .iter()
+                .filter_map(|(path, value)| match after_values.get(path.as_str()) {
+                    Some(after_value) if after_value == value => None,
+                    after_value => Some(FieldDifference {
+                        path: path.clone(),
+                        before: Some(value.clone()),
+                        after: after_value.map(|value| value.to_string()),
+                    }),
+                });
+        let added = after
+            .iter()
+            .filter(|(path, _)| !before_values.contains_key(path.as_str()))
+            .map(|(path, value)| FieldDifference {
+                path: path.clone(),
+                before: None,
+                after: Some(value.clone()),
+            });
+        changed_or_removed.chain(added).collect()
+    }
+}
+
+// The path and formatted value of each field of a section, in encoding order.
+struct Fields(Vec<(String, String)>);
+
+impl From<&SpliceInfoSection> for Fields {
+    fn from(section: &SpliceInfoSection) -> Self {
+        let mut fields = Fields(vec![]);
+        fields.field("", "table_id", format!("0x{:02X}", section.table_id));
+        fields.field("", "sap_type", format!("{:?}", section.sap_type));
+        fields.field("", "protocol_version", section.protocol_version);
+        match &section.encrypted_packet {
+            Some(encrypted_packet) => {
+                fields.field(
+                    "encrypted_packet",
+                    "encryption_algorithm",
+                    format!("{:?}", encrypted_packet.encryption_algorithm),
+                );
+                fields.field("encrypted_packet", "cw_index", encrypted_packet.cw_index);
+            }
+            None => fields.field("", "encrypted_packet", "None"),
+        }
+        fields.field("", "pts_adjustment", Ticks(section.pts_adjustment));
+        fields.field("", "tier", format!("0x{:03X}", section.tier));
+        fields.splice_command(&section.splice_command);
+        for (index, splice_descriptor) in section.splice_descriptors.iter().enumerate() {
+            fields.splice_descriptor(&format!("splice_descriptors[{}]", index), splice_descriptor);
+        }
+        fields.field("", "crc_32", format!("0x{:08X}", section.crc_32));
+        fields
+    }
+}
+
+impl Fields {
+    fn field(&mut self, path: &str, name: &str, value: impl Display) {
+        self.0.push((join(path, name), value.to_string()));
+    }
+
+    fn splice_command(&mut self, splice_command: &SpliceCommand) {
+        let command_type = splice_command.command_type();
+        self.field(
+            "",
+            "splice_command",
+            format!("{} (0x{:02X})", command_type, command_type.value()),
+        );
+        let path = "splice_command";
+        match splice_command {
+            SpliceCommand::SpliceNull | SpliceCommand::BandwidthReservation => (),
+            SpliceCommand::SpliceSchedule(splice_schedule) => {
+                self.splice_schedule(path, splice_schedule)
+            }
+            SpliceCommand::SpliceInsert(splice_insert) => self.splice_insert(path, splice_insert),
+            SpliceCommand::TimeSignal(time_signal) => {
+                self.splice_time(&join(path, "splice_time"), &time_signal.splice_time)
+            }
+            SpliceCommand::PrivateCommand(private_command) => {
+                self.field(path, "identifier", &private_command.identifier);
+                self.field(
+                    path,
+                    "private_bytes",
+                    hex_bytes(&private_command.private_bytes),
+                );
+            }
+        }
+    }
+
+    fn splice_schedule(&mut self, path: &str, splice_schedule: &SpliceSchedule) {
+        for (index, event) in splice_schedule.events.iter().enumerate() {
+            let path = format!("{}.events[{}]", path, index);
+            self.field(&path, "event_id", event.event_id);
+            let Some(scheduled_event) = &event.scheduled_event else {
+                self.field(&path, "scheduled_event", "None");
+                continue;
+            };
+            let path = join(&path, "scheduled_event");
+            self.field(
+                &path,
+                "out_of_network_indicator",
+                scheduled_event.out_of_network_indicator,
+            );
+            match &scheduled_event.splice_mode {
+                splice_schedule::SpliceMode::ProgramSpliceMode(program_mode) => self.field(
+                    &path,
+                    "splice_mode.utc_splice_time",
+                    program_mode.utc_splice_time,
+                ),
+                splice_schedule::SpliceMode::ComponentSpliceMode(components) => {
+                    for (index, component) in components.iter().enumerate() {
+                        let path = format!("{}.splice_mode[{}]", path, index);
+                        self.field(&path, "component_tag", component.component_tag);
+                        self.field(&path, "utc_splice_time", component.utc_splice_time);
+                    }
+                }
+            }
+            self.break_duration(&path, scheduled_event.break_duration.as_ref());
+            self.field(
+                &path,
+                "unique_program_id",
+                scheduled_event.unique_program_id,
+            );
+            self.field(&path, "avail_num", scheduled_event.avail_num);
+            self.field(&path, "avails_expected", scheduled_event.avails_expected);
+        }
+    }
+
+    fn splice_insert(&mut self, path: &str, splice_insert: &SpliceInsert) {
+        self.field(path, "event_id", splice_insert.event_id);
+        let Some(scheduled_event) = &splice_insert.scheduled_event else {
+            return self.field(path, "scheduled_event", "None");
+        };
+        let path = join(path, "scheduled_event");
+        self.field(
+            &path,
+            "out_of_network_indicator",
+            scheduled_event.out_of_network_indicator,
+        );
+        self.field(
+            &path,
+            "is_immediate_splice",
+            scheduled_event.is_immediate_splice,
+        );
+        match &scheduled_event.splice_mode {
+            splice_insert::SpliceMode::ProgramSpliceMode(program_mode) => {
+                let path = join(&path, "splice_mode.splice_time");
+                match &program_mode.splice_time {
+                    Some(splice_time) => self.splice_time(&path, splice_time),
+                    None => self.field(&path, "", "None"),
+                }
+            }
+            splice_insert::SpliceMode::ComponentSpliceMode(components) => {
+                for (index, component) in components.iter().enumerate() {
+                    let path = format!("{}.splice_mode[{}]", path, index);
+                    self.field(&path, "component_tag", component.component_tag);
+                    match &component.splice_time {
+                        Some(splice_time) => {
+                            self.splice_time(&join(&path, "splice_time"), splice_time)
+                        }
+                        None => self.field(&path, "splice_time", "None"),
+                    }
+                }
+            }
+        }
+        self.break_duration(&path, scheduled_event.break_duration.as_ref());
+        self.field(
+            &path,
+            "unique_program_id",
+            scheduled_event.unique_program_id,
+        );
+        self.field(&path, "avail_num", scheduled_event.avail_num);
+        self.field(&path, "avails_expected", scheduled_event.avails_expected);
+    }
+
+    fn splice_time(&mut self, path: &str, splice_time: &SpliceTime) {
+        match splice_time.pts_time {
+            Some(pts_time) => self.field(path, "pts_time", Ticks(pts_time)),
+            None => self.field(path, "pts_time", "None"),
+        }
+    }
+
+    fn break_duration(&mut self, path: &str, break_duration: Option<&BreakDuration>) {
+        let Some(break_duration) = break_duration else {
+            return self.field(path, "break_duration", "None");
+        };
+        let path = join(path, "break_duration");
+        self.field(&path, "auto_return", break_duration.auto_return);
+        self.field(&path, "duration", Ticks(break_duration.duration));
+    }
+
+    fn splice_descriptor(&mut self, path: &str, splice_descriptor: &SpliceDescriptor) {
+        self.field(
+            path,
+            "",
+            format!(
+                "{:?} (0x{:02X})",
+                splice_descriptor.tag(),
+                splice_descriptor.tag().value()
+            ),
+        );
+        self.field(
+            path,
+            "identifier",
+            Identifier(splice_descriptor.identifier()),
+        );
+        match splice_descriptor {
+            SpliceDescriptor::AvailDescriptor(descriptor) => {
+                self.field(path, "provider_avail_id", descriptor.provider_avail_id)
+            }
+            SpliceDescriptor::DTMFDescriptor(descriptor) => {
+                self.field(path, "preroll", descriptor.preroll);
+                self.field(path, "dtmf_chars", &descriptor.dtmf_chars);
+            }
+            SpliceDescriptor::SegmentationDescriptor(descriptor) => {
+                self.segmentation_descriptor(path, descriptor)
+            }
+            SpliceDescriptor::TimeDescriptor(descriptor) => {
+                self.field(path, "tai_seconds", descriptor.tai_seconds);
+                self.field(path, "tai_ns", descriptor.tai_ns);
+                self.field(path, "utc_offset", descriptor.utc_offset);
+            }
+            SpliceDescriptor::AudioDescriptor(descriptor) => {
+                self.audio_descriptor(path, descriptor)
+            }
+            SpliceDescriptor::Custom(descriptor) => match descriptor.private_bytes() {
+                Ok(private_bytes) => self.field(path, "private_bytes", hex_bytes(&private_bytes)),
+                Err(error) => self.field(path, "private_bytes", error),
+            },
+            SpliceDescriptor::Unknown { private_bytes, .. } => {
+                self.field(path, "private_bytes", hex_bytes(private_bytes))
+            }
+        }
+    }
+
+    fn segmentation_descriptor(&mut self, path: &str, descriptor: &SegmentationDescriptor) {
+        self.field(
+            path,
+            "event_id",
+            format!("{} (0x{:08X})", descriptor.event_id, descriptor.event_id),
+        );
+        let Some(scheduled_event) = &descriptor.scheduled_event else {
+            return self.field(path, "scheduled_event", "None");
+        };
+        let path = join(path, "scheduled_event");
+        match &scheduled_event.delivery_restrictions {
+            Some(delivery_restrictions) => {
+                let path = join(&path, "delivery_restrictions");
+                self.field(
+                    &path,
+                    "web_delivery_allowed",
+                    delivery_restrictions.web_delivery_allowed,
+                );
+                self.field(
+                    &path,
+                    "no_regional_blackout",
+                    delivery_restrictions.no_regional_blackout,
+                );
+                self.field(
+                    &path,
+                    "archive_allowed",
+                    delivery_restrictions.archive_allowed,
+                );
+                self.field(
+                    &path,
+                    "device_restrictions",
+                    format!("{:?}", delivery_restrictions.device_restrictions),
+                );
+            }
+            None => self.field(&path, "delivery_restrictions", "None"),
+        }
+        match &scheduled_event.component_segments {
+            Some(component_segments) => {
+                for (index, component) in component_segments.iter().enumerate() {
+                    let path = format!("{}.component_segments[{}]", path, index);
+                    self.field(&path, "component_tag", component.component_tag);
+                    self.field(&path, "pts_offset", Ticks(component.pts_offset));
+                }
+            }
+            None => self.field(&path, "component_segments", "None"),
+        }
+        match scheduled_event.segmentation_duration {
+            Some(segmentation_duration) => self.field(
+                &path,
+                "segmentation_duration",
+                Ticks(Pts90k(segmentation_duration)),
+            ),
+            None => self.field(&path, "segmentation_duration", "None"),
+        }
+        self.segmentation_upid(
+            &join(&path, "segmentation_upid"),
+            &scheduled_event.segmentation_upid,
+        );
+        self.field(
+            &path,
+            "segmentation_type_id",
+            format!(
+                "{} (0x{:02X})",
+                scheduled_event.segmentation_type_id,
+                scheduled_event.segmentation_type_id.value()
+            ),
+        );
+        self.field(&path, "segment_num", scheduled_event.segment_num);
+        self.field(
+            &path,
+            "segments_expected",
+            scheduled_event.segments_expected,
+        );
+        match &scheduled_event.sub_segment {
+            Some(sub_segment) => {
+                let path = join(&path, "sub_segment");
+                self.field(&path, "sub_segment_num", sub_segment.sub_segment_num);
+                self.field(
+                    &path,
+                    "sub_segments_expected",
+                    sub_segment.sub_segments_expected,
+                );
+            }
+            None => self.field(&path, "sub_segment", "None"),
+        }
+    }
+
+    fn segmentation_upid(&mut self, path: &str, upid: &SegmentationUPID) {
+        let upid_type = upid.upid_type();
+        let upid_type = format!("{} (0x{:02X})", upid_type, upid_type.value());
+        match upid {
+            SegmentationUPID::NotUsed => self.field(path, "", upid_type),
+            SegmentationUPID::UserDefined(text)
+            | SegmentationUPID::ISCI(text)
+            | SegmentationUPID::AdID(text)
+            | SegmentationUPID::UMID(text)
+            | SegmentationUPID::TID(text)
+            | SegmentationUPID::TI(text)
+            | SegmentationUPID::ADI(text)
+            | SegmentationUPID::ADSInformation(text)
+            | SegmentationUPID::URI(text)
+            | SegmentationUPID::UUID(text) => {
+                self.field(path, "", format!("{}: {}", upid_type, text))
+            }
+            SegmentationUPID::DeprecatedISAN(isan) | SegmentationUPID::ISAN(isan) => {
+                self.field(path, "", format!("{}: {}", upid_type, isan))
+            }
+            SegmentationUPID::EIDR(eidr) => {
+                self.field(path, "", format!("{}: {}", upid_type, eidr))
+            }
+            SegmentationUPID::ATSCContentIdentifier(atsc) => {
+                self.field(path, "", upid_type);
+                self.field(path, "tsid", atsc.tsid);
+                self.field(path, "end_of_day", atsc.end_of_day);
+                self.field(path, "unique_for", atsc.unique_for);
+                self.field(path, "content_id", &atsc.content_id);
+            }
+            SegmentationUPID::MPU(mpu) => {
+                self.field(path, "", upid_type);
+                self.field(path, "format_specifier", &mpu.format_specifier);
+                self.field(path, "private_data", hex_bytes(&mpu.private_data));
+            }
+            SegmentationUPID::MID(upids) => {
+                self.field(path, "", format!("{}: {} UPIDs", upid_type, upids.len()));
+                for (index, upid) in upids.iter().enumerate() {
+                    self.segmentation_upid(&format!("{}[{}]", path, index), upid);
+                }
+            }
+            SegmentationUPID::Invalid { bytes, .. } => self.field(
+                path,
+                "",
+                format!("{}: {} (Invalid)", upid_type, hex_bytes(bytes)),
+            ),
+        }
+    }
+
+    fn audio_descriptor(&mut self, path: &str, descriptor: &AudioDescriptor) {
+        for (index, component) in descriptor.components.iter().enumerate() {
+            let path = format!("{}.components[{}]", path, index);
+            self.field(&path, "component_tag", component.component_tag);
+            let iso_code = component.iso_code.to_be_bytes();
+            self.field(&path, "iso_code", String::from_utf8_lossy(&iso_code[1..]));
+            self.field(
+                &path,
+                "bit_stream_mode",
+                format!("{:?}", component.bit_stream_mode),
+            );
+            match &component.num_channels {
+                NumChannels::AudioCodingMode(mode) => {
+                    self.field(&path, "num_channels", format!("{:?}", mode))
+                }
+                NumChannels::MaxNumberOfEncodedChannels(channels) => {
+                    self.field(&path, "num_channels", format!("{:?}", channels))
+                }
+            }
+            self.field(&path, "full_srvc_audio", component.full_srvc_audio);
+        }
+    }
+}
+
+// The path of the field `name` within `path`, where an empty `name` refers to `path` itself.
+fn join(path: &str, name: &str) -> String {
+    match (path.is_empty(), name.is_empty()) {
+        (true, _) => name.to_string(),
+        (_, true) => path.to_string(),
+        _ => format!("{}.{}", path, name),
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", encode_hex(bytes).to_uppercase())
+}
@@ -0,0 +1,485 @@
+//! The human-readable report produced by the `Display` implementation of `SpliceInfoSection`.
+use crate::{
+    hex::encode_hex,
+    splice_command::{
+        private_command::PrivateCommand,
+        splice_insert::{self, SpliceInsert},
+        splice_schedule::{self, SpliceSchedule},
+        SpliceCommand,
+    },
+    splice_descriptor::{
+        audio_descriptor::{AudioDescriptor, NumChannels},
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationUPID},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::{BreakDuration, Pts90k, SpliceTime},
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A multi-line, indented report of the section, intended for debugging (e.g. logging a cue
+/// that was not handled as expected). The format is not stable and should not be parsed.
+/// ```
+/// use scte35::splice_info_section::SpliceInfoSection;
+///
+/// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+/// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+/// assert_eq!(
+///     "\
+/// Splice Info Section
+///   Table ID: 0xFC
+///   SAP Type: Unspecified
+///   Protocol Version: 0
+///   Encrypted: false
+///   PTS Adjustment: 0 (0.000000s)
+///   Tier: 0xFFF
+///   Splice Command: Time Signal (0x06)
+///     PTS Time: 1924989008 (21388.766756s)
+///   Splice Descriptors: 1
+///     [0] Segmentation Descriptor (0x02)
+///       Identifier: CUEI (0x43554549)
+///       Event ID: 1207959694 (0x4800008E)
+///       Segmentation Type: Provider Placement Opportunity Start (0x34)
+///       Segmentation Duration: 27630000 (307.000000s)
+///       UPID: TI (0x08): 0x000000002CA0A18A
+///       Segment: 2 of 0
+///       Delivery Restrictions:
+///         Web Delivery Allowed: false
+///         No Regional Blackout: true
+///         Archive Allowed: true
+///         Device Restrictions: None
+///   CRC-32: 0x9AC9D17E
+/// ",
+///     splice_info_section.to_string()
+/// );
+/// ```
+impl Display for SpliceInfoSection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut report = Report { f, indent: 0 };
+        report.line(format_args!("Splice Info Section"))?;
+        report.nested(|report| {
+            report.line(format_args!("Table ID: 0x{:02X}", self.table_id))?;
+            report.line(format_args!("SAP Type: {:?}", self.sap_type))?;
+            report.line(format_args!("Protocol Version: {}", self.protocol_version))?;
+            match &self.encrypted_packet {
+                Some(encrypted_packet) => {
+                    report.line(format_args!("Encrypted: true"))?;
+                    report.nested(|report| {
+                        report.line(format_args!(
+                            "Encryption Algorithm: {:?}",
+                            encrypted_packet.encryption_algorithm
+                        ))?;
+                        report.line(format_args!("CW Index: {}", encrypted_packet.cw_index))
+                    })?;
+                }
+                None => report.line(format_args!("Encrypted: false"))?,
+            }
+            report.line(format_args!(
+                "PTS Adjustment: {}",
+                Ticks(self.pts_adjustment)
+            ))?;
+            report.line(format_args!("Tier: 0x{:03X}", self.tier))?;
+            report.splice_command(&self.splice_command)?;
+            report.line(format_args!(
+                "Splice Descriptors: {}",
+                self.splice_descriptors.len()
+            ))?;
+            report.nested(|report| {
+                for (index, splice_descriptor) in self.splice_descriptors.iter().enumerate() {
+                    report.splice_descriptor(index, splice_descriptor)?;
+                }
+                Ok(())
+            })?;
+            report.line(format_args!("CRC-32: 0x{:08X}", self.crc_32))?;
+            if !self.non_fatal_errors.is_empty() {
+                report.line(format_args!(
+                    "Non-fatal Errors: {}",
+                    self.non_fatal_errors.len()
+                ))?;
+                report.nested(|report| {
+                    for error in &self.non_fatal_errors {
+                        report.line(format_args!("{}", error))?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// A number of 90 kHz ticks, shown with the equivalent number of seconds.
+pub(crate) struct Ticks(pub(crate) Pts90k);
+
+impl Display for Ticks {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ({:.6}s)", self.0, self.0.as_duration().as_secs_f64())
+    }
+}
+
+// A 32-bit identifier, shown as ASCII when it is printable (e.g. "CUEI").
+pub(crate) struct Identifier(pub(crate) u32);
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let bytes = self.0.to_be_bytes();
+        if bytes.iter().all(|byte| byte.is_ascii_graphic()) {
+            write!(f, "{} ", String::from_utf8_lossy(&bytes))?;
+        }
+        write!(f, "(0x{:08X})", self.0)
+    }
+}
+
+struct Report<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    indent: usize,
+}
+
+impl Report<'_, '_> {
+    fn line(&mut self, args: fmt::Arguments) -> fmt::Result {
+        writeln!(self.f, "{:indent$}{}", "", args, indent = self.indent * 2)
+    }
+
+    fn nested(&mut self, write: impl FnOnce(&mut Self) -> fmt::Result) -> fmt::Result {
+        self.indent += 1;
+        let result = write(self);
+        self.indent -= 1;
+        result
+    }
+
+    fn splice_command(&mut self, splice_command: &SpliceCommand) -> fmt::Result {
+        let command_type = splice_command.command_type();
+        self.line(format_args!(
+            "Splice Command: {} (0x{:02X})",
+            command_type,
+            command_type.value()
+        ))?;
+        self.nested(|report| match splice_command {
+            SpliceCommand::SpliceNull | SpliceCommand::BandwidthReservation => Ok(()),
+            SpliceCommand::SpliceSchedule(splice_schedule) => {
+                report.splice_schedule(splice_schedule)
+            }
+            SpliceCommand::SpliceInsert(splice_insert) => report.splice_insert(splice_insert),
+            SpliceCommand::TimeSignal(time_signal) => {
+                report.splice_time("PTS Time", &time_signal.splice_time)
+            }
+            SpliceCommand::PrivateCommand(private_command) => {
+                report.private_command(private_command)
+            }
+        })
+    }
+
+    fn splice_schedule(&mut self, splice_schedule: &SpliceSchedule) -> fmt::Result {
+        for event in &splice_schedule.events {
+            self.line(format_args!("Event ID: {}", event.event_id))?;
+            self.nested(|report| {
+                let Some(scheduled_event) = &event.scheduled_event else {
+                    return report.line(format_args!("Cancelled: true"));
+                };
+                report.line(format_args!(
+                    "Out Of Network: {}",
+                    scheduled_event.out_of_network_indicator
+                ))?;
+                match &scheduled_event.splice_mode {
+                    splice_schedule::SpliceMode::ProgramSpliceMode(program_mode) => report.line(
+                        format_args!("UTC Splice Time: {}", program_mode.utc_splice_time),
+                    )?,
+                    splice_schedule::SpliceMode::ComponentSpliceMode(components) => {
+                        for component in components {
+                            report.line(format_args!(
+                                "Component {}: UTC Splice Time: {}",
+                                component.component_tag, component.utc_splice_time
+                            ))?;
+                        }
+                    }
+                }
+                report.break_duration(scheduled_event.break_duration.as_ref())?;
+                report.line(format_args!(
+                    "Unique Program ID: {}",
+                    scheduled_event.unique_program_id
+                ))?;
+                report.line(format_args!(
+                    "Avail: {} of {}",
+                    scheduled_event.avail_num, scheduled_event.avails_expected
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn splice_insert(&mut self, splice_insert: &SpliceInsert) -> fmt::Result {
+        self.line(format_args!("Event ID: {}", splice_insert.event_id))?;
+        let Some(scheduled_event) = &splice_insert.scheduled_event else {
+            return self.line(format_args!("Cancelled: true"));
+        };
+        self.line(format_args!(
+            "Out Of Network: {}",
+            scheduled_event.out_of_network_indicator
+        ))?;
+        self.line(format_args!(
+            "Immediate: {}",
+            scheduled_event.is_immediate_splice
+        ))?;
+        match &scheduled_event.splice_mode {
+            splice_insert::SpliceMode::ProgramSpliceMode(program_mode) => {
+                if let Some(splice_time) = &program_mode.splice_time {
+                    self.splice_time("PTS Time", splice_time)?;
+                }
+            }
+            splice_insert::SpliceMode::ComponentSpliceMode(components) => {
+                for component in components {
+                    let label = format!("Component {}: PTS Time", component.component_tag);
+                    match &component.splice_time {
+                        Some(splice_time) => self.splice_time(&label, splice_time)?,
+                        None => self.line(format_args!("{}: Not Specified", label))?,
+                    }
+                }
+            }
+        }
+        self.break_duration(scheduled_event.break_duration.as_ref())?;
+        self.line(format_args!(
+            "Unique Program ID: {}",
+            scheduled_event.unique_program_id
+        ))?;
+        self.line(format_args!(
+            "Avail: {} of {}",
+            scheduled_event.avail_num, scheduled_event.avails_expected
+        ))
+    }
+
+    fn splice_time(&mut self, label: &str, splice_time: &SpliceTime) -> fmt::Result {
+        match splice_time.pts_time {
+            Some(pts_time) => self.line(format_args!("{}: {}", label, Ticks(pts_time))),
+            None => self.line(format_args!("{}: Not Specified", label)),
+        }
+    }
+
+    fn break_duration(&mut self, break_duration: Option<&BreakDuration>) -> fmt::Result {
+        let Some(break_duration) = break_duration else {
+            return Ok(());
+        };
+        self.line(format_args!(
+            "Break Duration: {}",
+            Ticks(break_duration.duration)
+        ))?;
+        self.nested(|report| {
+            report.line(format_args!("Auto Return: {}", break_duration.auto_return))
+        })
+    }
+
+    fn private_command(&mut self, private_command: &PrivateCommand) -> fmt::Result {
+        self.line(format_args!("Identifier: {}", private_command.identifier))?;
+        self.line(format_args!(
+            "Private Bytes: 0x{}",
+            encode_hex(&private_command.private_bytes).to_uppercase()
+        ))
+    }
+
+    fn splice_descriptor(
+        &mut self,
+        index: usize,
+        splice_descriptor: &SpliceDescriptor,
+    ) -> fmt::Result {
+        let tag = splice_descriptor.tag();
+        let name = match splice_descriptor {
+            SpliceDescriptor::AvailDescriptor(_) => "Avail Descriptor",
+            SpliceDescriptor::DTMFDescriptor(_) => "DTMF Descriptor",
+            SpliceDescriptor::SegmentationDescriptor(_) => "Segmentation Descriptor",
+            SpliceDescriptor::TimeDescriptor(_) => "Time Descriptor",
+            SpliceDescriptor::AudioDescriptor(_) => "Audio Descriptor",
+            SpliceDescriptor::Custom(_) => "Custom Descriptor",
+            SpliceDescriptor::Unknown { .. } => "Unknown Descriptor",
+        };
+        self.line(format_args!("[{}] {} (0x{:02X})", index, name, tag.value()))?;
+        self.nested(|report| {
+            report.line(format_args!(
+                "Identifier: {}",
+                Identifier(splice_descriptor.identifier())
+            ))?;
+            match splice_descriptor {
+                SpliceDescriptor::AvailDescriptor(descriptor) => report.line(format_args!(
+                    "Provider Avail ID: {}",
+                    descriptor.provider_avail_id
+                )),
+                SpliceDescriptor::DTMFDescriptor(descriptor) => {
+                    report.line(format_args!(
+                        "Preroll: {} ({:.1}s)",
+                        descriptor.preroll,
+                        f64::from(descriptor.preroll) / 10.0
+                    ))?;
+                    report.line(format_args!("DTMF Chars: {}", descriptor.dtmf_chars))
+                }
+                SpliceDescriptor::SegmentationDescriptor(descriptor) => {
+                    report.segmentation_descriptor(descriptor)
+                }
+                SpliceDescriptor::TimeDescriptor(descriptor) => {
+                    report.line(format_args!("TAI Seconds: {}", descriptor.tai_seconds))?;
+                    report.line(format_args!("TAI Nanoseconds: {}", descriptor.tai_ns))?;
+                    report.line(format_args!("UTC Offset: {}", descriptor.utc_offset))
+                }
+                SpliceDescriptor::AudioDescriptor(descriptor) => {
+                    report.audio_descriptor(descriptor)
+                }
+                SpliceDescriptor::Custom(descriptor) => match descriptor.private_bytes() {
+                    Ok(private_bytes) => report.line(format_args!(
+                        "Private Bytes: 0x{}",
+                        encode_hex(&private_bytes).to_uppercase()
+                    )),
+                    Err(error) => report.line(format_args!("Private Bytes: {}", error)),
+                },
+                SpliceDescriptor::Unknown { private_bytes, .. } => report.line(format_args!(
+                    "Private Bytes: 0x{}",
+                    encode_hex(private_bytes).to_uppercase()
+                )),
+            }
+        })
+    }
+
+    fn segmentation_descriptor(&mut self, descriptor: &SegmentationDescriptor) -> fmt::Result {
+        self.line(format_args!(
+            "Event ID: {} (0x{:08X})",
+            descriptor.event_id, descriptor.event_id
+        ))?;
+        let Some(scheduled_event) = &descriptor.scheduled_event else {
+            return self.line(format_args!("Cancelled: true"));
+        };
+        self.line(format_args!(
+            "Segmentation Type: {} (0x{:02X})",
+            scheduled_event.segmentation_type_id,
+            scheduled_event.segmentation_type_id.value()
+        ))?;
+        if let Some(segmentation_duration) = scheduled_event.segmentation_duration {
+            self.line(format_args!(
+                "Segmentation Duration: {}",
+                Ticks(Pts90k(segmentation_duration))
+            ))?;
+        }
+        self.segmentation_upid("UPID", &scheduled_event.segmentation_upid)?;
+        self.line(format_args!(
+            "Segment: {} of {}",
+            scheduled_event.segment_num, scheduled_event.segments_expected
+        ))?;
+        if let Some(sub_segment) = &scheduled_event.sub_segment {
+            self.line(format_args!(
+                "Sub-Segment: {} of {}",
+                sub_segment.sub_segment_num, sub_segment.sub_segments_expected
+            ))?;
+        }
+        if let Some(component_segments) = &scheduled_event.component_segments {
+            for component in component_segments {
+                self.line(format_args!(
+                    "Component {}: PTS Offset: {}",
+                    component.component_tag,
+                    Ticks(component.pts_offset)
+                ))?;
+            }
+        }
+        if let Some(delivery_restrictions) = &scheduled_event.delivery_restrictions {
+            self.line(format_args!("Delivery Restrictions:"))?;
+            self.nested(|report| {
+                report.line(format_args!(
+                    "Web Delivery Allowed: {}",
+                    delivery_restrictions.web_delivery_allowed
+                ))?;
+                report.line(format_args!(
+                    "No Regional Blackout: {}",
+                    delivery_restrictions.no_regional_blackout
+                ))?;
+                report.line(format_args!(
+                    "Archive Allowed: {}",
+                    delivery_restrictions.archive_allowed
+                ))?;
+                report.line(format_args!(
+                    "Device Restrictions: {:?}",
+                    delivery_restrictions.device_restrictions
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn segmentation_upid(&mut self, label: &str, upid: &SegmentationUPID) -> fmt::Result {
+        let upid_type = upid.upid_type();
+        let prefix = format_args!("{}: {} (0x{:02X})", label, upid_type, upid_type.value());
+        match upid {
+            SegmentationUPID::NotUsed => self.line(prefix),
+            SegmentationUPID::UserDefined(text)
+            | SegmentationUPID::ISCI(text)
+            | SegmentationUPID::AdID(text)
+            | SegmentationUPID::UMID(text)
+            | SegmentationUPID::TID(text)
+            | SegmentationUPID::TI(text)
+            | SegmentationUPID::ADI(text)
+            | SegmentationUPID::ADSInformation(text)
+            | SegmentationUPID::URI(text)
+            | SegmentationUPID::UUID(text) => self.line(format_args!("{}: {}", prefix, text)),
+            SegmentationUPID::DeprecatedISAN(isan) | SegmentationUPID::ISAN(isan) => {
+                self.line(format_args!("{}: {}", prefix, isan))
+            }
+            SegmentationUPID::EIDR(eidr) => self.line(format_args!("{}: {}", prefix, eidr)),
+            SegmentationUPID::ATSCContentIdentifier(atsc) => {
+                self.line(prefix)?;
+                self.nested(|report| {
+                    report.line(format_args!("TSID: {}", atsc.tsid))?;
+                    report.line(format_args!("End Of Day: {}", atsc.end_of_day))?;
+                    report.line(format_args!("Unique For: {}", atsc.unique_for))?;
+                    report.line(format_args!("Content ID: {}", atsc.content_id))
+                })
+            }
+            SegmentationUPID::MPU(mpu) => {
+                self.line(prefix)?;
+                self.nested(|report| {
+                    report.line(format_args!("Format Specifier: {}", mpu.format_specifier))?;
+                    report.line(format_args!(
+                        "Private Data: 0x{}",
+                        encode_hex(&mpu.private_data).to_uppercase()
+                    ))
+                })
+            }
+            SegmentationUPID::MID(upids) => {
+                self.line(format_args!("{}: {} UPIDs", prefix, upids.len()))?;
+                self.nested(|report| {
+                    for (index, upid) in upids.iter().enumerate() {
+                        report.segmentation_upid(&format!("[{}]", index), upid)?;
+                    }
+                    Ok(())
+                })
+            }
+            SegmentationUPID::Invalid { bytes, .. } => self.line(format_args!(
+                "{}: 0x{} (Invalid)",
+                prefix,
+                encode_hex(bytes).to_uppercase()
+            )),
+        }
+    }
+
+    fn audio_descriptor(&mut self, descriptor: &AudioDescriptor) -> fmt::Result {
+        for component in &descriptor.components {
+            self.line(format_args!("Component {}", component.component_tag))?;
+            self.nested(|report| {
+                let iso_code = component.iso_code.to_be_bytes();
+                report.line(format_args!(
+                    "ISO Code: {}",
+                    String::from_utf8_lossy(&iso_code[1..])
+                ))?;
+                report.line(format_args!(
+                    "Bit Stream Mode: {:?}",
+                    component.bit_stream_mode
+                ))?;
+                match &component.num_channels {
+                    NumChannels::AudioCodingMode(mode) => {
+                        report.line(format_args!("Audio Coding Mode: {:?}", mode))?
+                    }
+                    NumChannels::MaxNumberOfEncodedChannels(channels) => report.line(
+                        format_args!("Max Number Of Encoded Channels: {:?}", channels),
+                    )?,
+                }
+                report.line(format_args!(
+                    "Full Service Audio: {}",
+                    component.full_srvc_audio
+                ))
+            })?;
+        }
+        Ok(())
+    }
+}
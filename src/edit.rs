@@ -0,0 +1,240 @@
+//! Editing of the encoded bytes of a `SpliceInfoSection` in place, for devices that restamp the
+//! PTS of a stream (e.g. transcoders), where only the `pts_adjustment` (or the `pts_time` or
+//! `tier`) should change and all other bytes of the message should be preserved as received.
+//!
+//! After each edit the `crc_32` is recalculated, without parsing or re-encoding the rest of the
+//! section (so any stuffing, unknown descriptors or vendor quirks are left untouched).
+//! ```
+//! use scte35::{edit::SectionEditor, splice_info_section::SpliceInfoSection, time::Pts90k};
+//!
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let mut bytes = SpliceInfoSection::try_from_hex_string(hex_string)
+//!     .unwrap()
+//!     .try_to_bytes()
+//!     .unwrap();
+//!
+//! let mut editor = SectionEditor::new(&mut bytes).unwrap();
+//! editor.add_pts_adjustment(Pts90k(90_000)).unwrap();
+//! assert_eq!(Some(Pts90k(1924989008)), editor.pts_time());
+//!
+//! let splice_info_section = SpliceInfoSection::try_from_bytes(&bytes).unwrap();
+//! assert_eq!(Pts90k(90_000), splice_info_section.pts_adjustment);
+//! assert_eq!(Some(Pts90k(1925079008)), splice_info_section.splice_pts());
+//! ```
+use crate::{
+    crc::crc_32_mpeg_2,
+    error::{EncodeError, ParseError, SectionEditError},
+    splice_command::SpliceCommandType,
+    time::Pts90k,
+};
+
+// The number of bytes from the `table_id` through the `splice_command_type`.
+const HEADER_LENGTH: usize = 14;
+const CRC_LENGTH: usize = 4;
+// The number of bytes of a `splice_time` that has the `time_specified_flag` set.
+const SPLICE_TIME_LENGTH: usize = 5;
+
+/// Edits the encoded bytes of a `SpliceInfoSection` in place (see the `edit` module).
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct SectionEditor<'a> {
+    // The bytes of the section, from the `table_id` through the `crc_32`.
+    section: &'a mut [u8],
+}
+
+impl<'a> SectionEditor<'a> {
+    /// Creates a `SectionEditor` for the section at the start of `bytes`. The length of the section
+    /// is taken from its `section_length`, and any bytes after the section are not edited.
+    pub fn new(bytes: &'a mut [u8]) -> Result<Self, SectionEditError> {
+        if bytes.len() < 3 {
+            return Err(end_of_data(3, bytes.len(), "section_length").into());
+        }
+        let section_length = 3 + (usize::from(bytes[1] & 0x0F) << 8 | usize::from(bytes[2]));
+        let section_length = section_length.max(HEADER_LENGTH + CRC_LENGTH);
+        if bytes.len() < section_length {
+            return Err(end_of_data(section_length, bytes.len(), "section").into());
+        }
+        Ok(Self {
+            section: &mut bytes[..section_length],
+        })
+    }
+
+    /// The bytes of the section, from the `table_id` through the `crc_32`.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.section
+    }
+
+    /// Whether the section has the `encrypted_packet` flag set.
+    pub fn is_encrypted(&self) -> bool {
+        self.section[4] & 0x80 != 0
+    }
+
+    pub fn pts_adjustment(&self) -> Pts90k {
+        let high_bit = u64::from(self.section[4] & 0x01) << 32;
+        let low_bits = u32::from_be_bytes([
+            self.section[5],
+            self.section[6],
+            self.section[7],
+            self.section[8],
+        ]);
+        Pts90k(high_bit | u64::from(low_bits))
+    }
+
+    /// Sets the `pts_adjustment`, which must be representable in 33 bits.
+    pub fn set_pts_adjustment(&mut self, pts_adjustment: Pts90k) -> Result<(), SectionEditError> {
+        validate(
+            33,
+            pts_adjustment.ticks(),
+            "SpliceInfoSection pts_adjustment",
+        )?;
+        let [_, _, _, high_bit, low_0, low_1, low_2, low_3] = pts_adjustment.ticks().to_be_bytes();
+        self.section[4] = (self.section[4] & 0xFE) | high_bit;
+        self.section[5..9].copy_from_slice(&[low_0, low_1, low_2, low_3]);
+        self.update_crc_32();
+        Ok(())
+    }
+
+    /// Adds `delta` to the `pts_adjustment` (wrapping at 33 bits), as is done by each device that
+    /// restamps the PTS of the stream.
+    pub fn add_pts_adjustment(&mut self, delta: Pts90k) -> Result<(), SectionEditError> {
+        self.set_pts_adjustment(self.pts_adjustment() + delta)
+    }
+
+    pub fn tier(&self) -> u16 {
+        u16::from(self.section[10]) << 4 | u16::from(self.section[11] >> 4)
+    }
+
+    /// Sets the `tier`, which must be representable in 12 bits.
+    pub fn set_tier(&mut self, tier: u16) -> Result<(), SectionEditError> {
+        validate(12, u64::from(tier), "SpliceInfoSection tier")?;
+        self.section[10] = (tier >> 4) as u8;
+        self.section[11] = (self.section[11] & 0x0F) | ((tier & 0x0F) as u8) << 4;
+        self.update_crc_32();
+        Ok(())
+    }
+
+    /// The `pts_time` of a `TimeSignal`, or of a `SpliceInsert` in the Program Splice Mode, which
+    /// is `None` when the command does not have one (see `SectionEditError::MissingPTSTime`) or is
+    /// encrypted.
+    pub fn pts_time(&self) -> Option<Pts90k> {
+        let offset = self.pts_time_offset().ok()?;
+        let splice_time = &self.section[offset..offset + SPLICE_TIME_LENGTH];
+        let high_bit = u64::from(splice_time[0] & 0x01) << 32;
+        let low_bits = u32::from_be_bytes([
+            splice_time[1],
+            splice_time[2],
+            splice_time[3],
+            splice_time[4],
+        ]);
+        Some(Pts90k(high_bit | u64::from(low_bits)))
+    }
+
+    /// Sets the `pts_time` of a `TimeSignal`, or of a `SpliceInsert` in the Program Splice Mode,
+    /// which must already have a `pts_time` (as the length of the command cannot change) and must
+    /// be representable in 33 bits.
+    pub fn set_pts_time(&mut self, pts_time: Pts90k) -> Result<(), SectionEditError> {
+        let offset = self.pts_time_offset()?;
+        validate(33, pts_time.ticks(), "SpliceTime pts_time")?;
+        let [_, _, _, high_bit, low_0, low_1, low_2, low_3] = pts_time.ticks().to_be_bytes();
+        self.section[offset] = (self.section[offset] & 0xFE) | high_bit;
+        self.section[offset + 1..offset + SPLICE_TIME_LENGTH]
+            .copy_from_slice(&[low_0, low_1, low_2, low_3]);
+        self.update_crc_32();
+        Ok(())
+    }
+
+    /// The `crc_32` at the end of the section, which is not validated.
+    pub fn crc_32(&self) -> u32 {
+        let crc_offset = self.section.len() - CRC_LENGTH;
+        u32::from_be_bytes([
+            self.section[crc_offset],
+            self.section[crc_offset + 1],
+            self.section[crc_offset + 2],
+            self.section[crc_offset + 3],
+        ])
+    }
+
+    /// Recalculates the `crc_32` from the rest of the section, without otherwise changing it (e.g.
+    /// to repair a message that was edited by a device that did not update the `crc_32`).
+    /// Returns whether the `crc_32` changed (i.e. whether it was invalid).
+    /// ```
+    /// use scte35::edit::SectionEditor;
+    ///
+    /// let mut bytes = vec![
+    ///     0xFC, 0x30, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xF0, 0x00, 0x00,
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    /// ];
+    /// let mut editor = SectionEditor::new(&mut bytes).unwrap();
+    /// assert!(editor.recalculate_crc_32());
+    /// assert_eq!(0x7A4FBFFF, editor.crc_32());
+    /// assert!(!editor.recalculate_crc_32());
+    /// ```
+    pub fn recalculate_crc_32(&mut self) -> bool {
+        let crc_32 = self.crc_32();
+        self.update_crc_32();
+        crc_32 != self.crc_32()
+    }
+
+    // The offset of the `splice_time` that has a `pts_time`, if any.
+    fn pts_time_offset(&self) -> Result<usize, SectionEditError> {
+        if self.is_encrypted() {
+            return Err(SectionEditError::EncryptedPacket);
+        }
+        let command_end = self.section.len() - CRC_LENGTH;
+        let byte = |offset: usize| {
+            if offset < command_end {
+                Ok(self.section[offset])
+            } else {
+                Err(SectionEditError::MissingPTSTime)
+            }
+        };
+        let offset = match SpliceCommandType::from_value(self.section[13]) {
+            SpliceCommandType::TimeSignal => HEADER_LENGTH,
+            SpliceCommandType::SpliceInsert => {
+                let splice_event_cancel_indicator = byte(HEADER_LENGTH + 4)? & 0x80 != 0;
+                let flags = byte(HEADER_LENGTH + 5)?;
+                let program_splice_flag = flags & 0x40 != 0;
+                let splice_immediate_flag = flags & 0x10 != 0;
+                if splice_event_cancel_indicator || !program_splice_flag || splice_immediate_flag {
+                    return Err(SectionEditError::MissingPTSTime);
+                }
+                HEADER_LENGTH + 6
+            }
+            _ => return Err(SectionEditError::MissingPTSTime),
+        };
+        let time_specified_flag = byte(offset)? & 0x80 != 0;
+        if !time_specified_flag || offset + SPLICE_TIME_LENGTH > command_end {
+            return Err(SectionEditError::MissingPTSTime);
+        }
+        Ok(offset)
+    }
+
+    fn update_crc_32(&mut self) {
+        let crc_offset = self.section.len() - CRC_LENGTH;
+        let crc_32 = crc_32_mpeg_2(&self.section[..crc_offset]);
+        self.section[crc_offset..].copy_from_slice(&crc_32.to_be_bytes());
+    }
+}
+
+fn validate(maximum_bits: u32, value: u64, description: &'static str) -> Result<(), EncodeError> {
+    if value >> maximum_bits != 0 {
+        Err(EncodeError::ValueOutOfRange {
+            value,
+            maximum_bits,
+            description,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn end_of_data(
+    expected_bytes: usize,
+    actual_bytes: usize,
+    description: &'static str,
+) -> ParseError {
+    ParseError::UnexpectedEndOfData {
+        expected_minimum_bits_left: expected_bytes as u32 * 8,
+        actual_bits_left: actual_bytes as u32 * 8,
+        description,
+    }
+}
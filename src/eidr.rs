@@ -0,0 +1,161 @@
+use crate::{bit_reader::Bits, bit_writer::BitWriter, error::ParseError, isan::check_char};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// The DOI prefix shared by all EIDR identifiers.
+pub const EIDR_DOI_PREFIX: u8 = 10;
+/// The sub-prefix of EIDR Content IDs.
+pub const EIDR_CONTENT_SUB_PREFIX: u16 = 5240;
+
+/// An EIDR identifier (see \[EIDR\]), held in the Compact Binary encoding defined in Section 2.1.1
+/// of EIDR ID Format (see [EIDR ID FORMAT]), which is a 16-bit sub-prefix followed by an 80-bit
+/// suffix.
+///
+/// The canonical representation depends on the registry that the identifier belongs to. Content
+/// IDs (sub-prefix `5240`) have a suffix of five groups of four hexadecimal digits followed by a
+/// check character (e.g. `10.5240/F85A-E100-B068-5B8F-B1C8-T`), whereas other registries (such as
+/// Party IDs and Video Service IDs) have a 32-bit suffix that is padded with zeros in the compact
+/// binary encoding and written without a check character (e.g. `10.5239/8BE5-E3F6`).
+/// ```
+/// use scte35::eidr::Eidr;
+///
+/// let eidr: Eidr = "10.5240/F85A-E100-B068-5B8F-B1C8-T".parse().unwrap();
+/// assert_eq!(5240, eidr.sub_prefix);
+/// assert_eq!(Some('T'), eidr.check_character());
+/// assert_eq!("10.5240/F85A-E100-B068-5B8F-B1C8-T", eidr.to_string());
+///
+/// let service = Eidr::from_compact_binary([
+///     0x14, 0x77, 0x8B, 0xE5, 0xE3, 0xF6, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+/// ]);
+/// assert_eq!("10.5239/8BE5-E3F6", service.to_string());
+/// ```
+/**
+```text
+{
+  sub_prefix  16 uimsbf
+  suffix      80 bslbf
+}
+```
+*/
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Eidr {
+    /// The registry that the identifier belongs to, following the `10.` DOI prefix (e.g. `5240`
+    /// for Content IDs).
+    pub sub_prefix: u16,
+    /// The raw suffix of the identifier, excluding any check character.
+    pub suffix: [u8; 10],
+}
+
+impl Eidr {
+    /// Creates an `Eidr` from its 12-byte compact binary encoding.
+    pub fn from_compact_binary(bytes: [u8; 12]) -> Self {
+        let mut suffix = [0; 10];
+        suffix.copy_from_slice(&bytes[2..]);
+        Self {
+            sub_prefix: u16::from_be_bytes([bytes[0], bytes[1]]),
+            suffix,
+        }
+    }
+
+    /// The 12-byte compact binary encoding of the identifier.
+    pub fn to_compact_binary(&self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+        bytes[..2].copy_from_slice(&self.sub_prefix.to_be_bytes());
+        bytes[2..].copy_from_slice(&self.suffix);
+        bytes
+    }
+
+    /// Whether the canonical representation uses the full 80-bit suffix and check character. This
+    /// is the case for Content IDs, and for any other identifier whose suffix does not fit in 32
+    /// bits.
+    pub fn is_full_suffix(&self) -> bool {
+        self.sub_prefix == EIDR_CONTENT_SUB_PREFIX || self.suffix[4..].iter().any(|b| *b != 0)
+    }
+
+    /// The ISO 7064 Mod 37,36 check character of the suffix, when the canonical representation
+    /// includes one (see `is_full_suffix`).
+    pub fn check_character(&self) -> Option<char> {
+        if self.is_full_suffix() {
+            Some(check_char(&self.suffix_groups()))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
+        Ok(Self::from_compact_binary(bits.array("eidr")?))
+    }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) {
+        bits.bytes(&self.to_compact_binary());
+    }
+
+    fn suffix_groups(&self) -> Vec<String> {
+        let length = if self.is_full_suffix() { 10 } else { 4 };
+        self.suffix[..length]
+            .chunks(2)
+            .map(|group| format!("{:02X}{:02X}", group[0], group[1]))
+            .collect()
+    }
+}
+
+impl Display for Eidr {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}/{}",
+            EIDR_DOI_PREFIX,
+            self.sub_prefix,
+            self.suffix_groups().join("-")
+        )?;
+        if let Some(check_character) = self.check_character() {
+            write!(f, "-{}", check_character)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Eidr {
+    type Err = ParseError;
+
+    /// Parses the canonical representation of an EIDR. The full 80-bit suffix may be given for any
+    /// registry, and when a check character is present it must match the suffix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::InvalidEIDR(String::from(s));
+        let (sub_prefix, suffix) = s
+            .strip_prefix("10.")
+            .and_then(|eidr| eidr.split_once('/'))
+            .ok_or_else(invalid)?;
+        let sub_prefix = sub_prefix.parse::<u16>().map_err(|_| invalid())?;
+        let mut groups: Vec<&str> = suffix.split('-').collect();
+        let check_character = match groups.last() {
+            Some(group) if group.len() == 1 => groups.pop(),
+            _ => None,
+        };
+        if !matches!(groups.len(), 2 | 5) || groups.iter().any(|group| group.len() != 4) {
+            return Err(invalid());
+        }
+        let mut eidr = Self {
+            sub_prefix,
+            suffix: [0; 10],
+        };
+        for (i, group) in groups.iter().enumerate() {
+            let value = u16::from_str_radix(group, 16).map_err(|_| invalid())?;
+            eidr.suffix[i * 2..i * 2 + 2].copy_from_slice(&value.to_be_bytes());
+        }
+        if groups.len() == 2 && eidr.is_full_suffix() {
+            return Err(invalid());
+        }
+        if let Some(check_character) = check_character {
+            let groups: Vec<String> = groups.iter().map(|group| group.to_uppercase()).collect();
+            if !check_character.eq_ignore_ascii_case(&check_char(&groups).to_string()) {
+                return Err(invalid());
+            }
+        }
+        Ok(eidr)
+    }
+}
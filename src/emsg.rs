@@ -0,0 +1,315 @@
+//! Extraction (and insertion) of SCTE-35 messages from ISO-BMFF `emsg` (DASH Event Message) boxes, as used by
+//! DASH and CMAF, where the binary `SpliceInfoSection` is carried in the `message_data` of a box
+//! with the `urn:scte:scte35:2013:bin` scheme (see SCTE 214-3).
+//!
+//! ```text
+//! aligned(8) class DASHEventMessageBox extends FullBox('emsg', version, flags = 0) {
+//!   if (version == 0) {
+//!     string            scheme_id_uri;
+//!     string            value;
+//!     unsigned int(32)  timescale;
+//!     unsigned int(32)  presentation_time_delta;
+//!     unsigned int(32)  event_duration;
+//!     unsigned int(32)  id;
+//!   } else if (version == 1) {
+//!     unsigned int(32)  timescale;
+//!     unsigned int(64)  presentation_time;
+//!     unsigned int(32)  event_duration;
+//!     unsigned int(32)  id;
+//!     string            scheme_id_uri;
+//!     string            value;
+//!   }
+//!   unsigned int(8)     message_data[];
+//! }
+//! ```
+//!
+//! Boxes can also be created from a `SpliceInfoSection` for insertion into CMAF chunks.
+//! ```
+//! use scte35::{emsg::EventMessage, splice_info_section::SpliceInfoSection};
+//!
+//! let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let data = splice_info_section.try_to_emsg(1000, 21514559).unwrap();
+//! let event_message = EventMessage::try_from_bytes(&data).unwrap();
+//! assert_eq!(1000, event_message.timescale);
+//! assert_eq!(60294, event_message.event_duration);
+//! assert_eq!(1207959695, event_message.id);
+//! assert_eq!(splice_info_section, event_message.try_splice_info_section().unwrap());
+//! ```
+
+use crate::{
+    error::{EmsgError, EncodeError},
+    splice_info_section::SpliceInfoSection,
+};
+
+/// The scheme for SCTE-35 messages carried as binary `SpliceInfoSection`s in `emsg` boxes.
+pub const SCTE35_BIN_SCHEME_ID_URI: &str = "urn:scte:scte35:2013:bin";
+
+const EMSG_BOX_TYPE: &[u8; 4] = b"emsg";
+/// The `event_duration` that indicates that the duration is unknown.
+const UNKNOWN_DURATION: u32 = 0xFFFFFFFF;
+
+/// An `emsg` box.
+/// ```
+/// use scte35::{emsg::{EventMessage, PresentationTime}, splice_command::SpliceCommand};
+///
+/// let hex_string = "0000006C656D73670100000000015F90000000007369C02E0052CCF54800008F75726E3A736374653A7363746533353A323031333A62696E0000FC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+/// let data = (0..hex_string.len())
+///     .step_by(2)
+///     .map(|i| u8::from_str_radix(&hex_string[i..i + 2], 16).unwrap())
+///     .collect::<Vec<u8>>();
+/// let event_message = EventMessage::try_from_bytes(&data).unwrap();
+/// assert_eq!(PresentationTime::Absolute(1936310318), event_message.presentation_time);
+/// assert_eq!(5426421, event_message.event_duration);
+/// let splice_info_section = event_message.try_splice_info_section().unwrap();
+/// assert!(matches!(splice_info_section.splice_command, SpliceCommand::SpliceInsert(_)));
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventMessage {
+    /// Identifies the message scheme (`urn:scte:scte35:2013:bin` for SCTE-35).
+    pub scheme_id_uri: String,
+    /// The value for the event, the semantics of which are defined by the scheme.
+    pub value: String,
+    /// The timescale, in ticks per second, of the `presentation_time` and `event_duration`.
+    pub timescale: u32,
+    /// The presentation time of the event, which depends on the version of the box.
+    pub presentation_time: PresentationTime,
+    /// The duration of the event in terms of the `timescale` (0xFFFFFFFF indicates an unknown
+    /// duration).
+    pub event_duration: u32,
+    /// Identifies the instance of the message.
+    pub id: u32,
+    /// The body of the message (the binary `SpliceInfoSection` for SCTE-35).
+    pub message_data: Vec<u8>,
+}
+
+/// The presentation time of an `EventMessage`, in terms of its `timescale`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PresentationTime {
+    /// Version 0: the delta from the earliest presentation time of the segment (or chunk) in
+    /// which the box is carried.
+    Delta(u32),
+    /// Version 1: the presentation time on the media timeline.
+    Absolute(u64),
+}
+
+impl EventMessage {
+    /// Parses an `emsg` box, starting at the box header.
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, EmsgError> {
+        let (box_type, payload, _) = try_box_from(data)?;
+        if box_type != EMSG_BOX_TYPE {
+            return Err(EmsgError::UnexpectedBoxType(
+                String::from_utf8_lossy(box_type).into_owned(),
+            ));
+        }
+        let mut reader = Reader(payload);
+        let version = reader.u8("version")?;
+        reader.bytes(3, "flags")?;
+        match version {
+            0 => {
+                let scheme_id_uri = reader.string("scheme_id_uri")?;
+                let value = reader.string("value")?;
+                Ok(Self {
+                    scheme_id_uri,
+                    value,
+                    timescale: reader.u32("timescale")?,
+                    presentation_time: PresentationTime::Delta(
+                        reader.u32("presentation_time_delta")?,
+                    ),
+                    event_duration: reader.u32("event_duration")?,
+                    id: reader.u32("id")?,
+                    message_data: reader.0.to_vec(),
+                })
+            }
+            1 => {
+                let timescale = reader.u32("timescale")?;
+                let presentation_time =
+                    PresentationTime::Absolute(reader.u64("presentation_time")?);
+                let event_duration = reader.u32("event_duration")?;
+                let id = reader.u32("id")?;
+                Ok(Self {
+                    scheme_id_uri: reader.string("scheme_id_uri")?,
+                    value: reader.string("value")?,
+                    timescale,
+                    presentation_time,
+                    event_duration,
+                    id,
+                    message_data: reader.0.to_vec(),
+                })
+            }
+            version => Err(EmsgError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Serializes the `emsg` box (including the box header). A `PresentationTime::Delta` is
+    /// written as a version 0 box and a `PresentationTime::Absolute` as a version 1 box.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = vec![];
+        let strings = [
+            self.scheme_id_uri.as_bytes(),
+            &[0],
+            self.value.as_bytes(),
+            &[0],
+        ]
+        .concat();
+        match self.presentation_time {
+            PresentationTime::Delta(presentation_time_delta) => {
+                payload.extend_from_slice(&[0, 0, 0, 0]);
+                payload.extend_from_slice(&strings);
+                payload.extend_from_slice(&self.timescale.to_be_bytes());
+                payload.extend_from_slice(&presentation_time_delta.to_be_bytes());
+            }
+            PresentationTime::Absolute(presentation_time) => {
+                payload.extend_from_slice(&[1, 0, 0, 0]);
+                payload.extend_from_slice(&self.timescale.to_be_bytes());
+                payload.extend_from_slice(&presentation_time.to_be_bytes());
+            }
+        }
+        payload.extend_from_slice(&self.event_duration.to_be_bytes());
+        payload.extend_from_slice(&self.id.to_be_bytes());
+        if let PresentationTime::Absolute(_) = self.presentation_time {
+            payload.extend_from_slice(&strings);
+        }
+        payload.extend_from_slice(&self.message_data);
+        let mut bytes = Vec::with_capacity(8 + payload.len());
+        bytes.extend_from_slice(&((8 + payload.len()) as u32).to_be_bytes());
+        bytes.extend_from_slice(EMSG_BOX_TYPE);
+        bytes.extend_from_slice(&payload);
+        bytes
+    }
+
+    /// Whether the `scheme_id_uri` indicates a binary SCTE-35 message.
+    pub fn is_scte35(&self) -> bool {
+        self.scheme_id_uri == SCTE35_BIN_SCHEME_ID_URI
+    }
+
+    /// Decodes the `message_data` as a `SpliceInfoSection`, provided that the `scheme_id_uri`
+    /// indicates a binary SCTE-35 message.
+    pub fn try_splice_info_section(&self) -> Result<SpliceInfoSection, EmsgError> {
+        if !self.is_scte35() {
+            return Err(EmsgError::UnexpectedScheme(self.scheme_id_uri.clone()));
+        }
+        Ok(SpliceInfoSection::try_from_bytes(&self.message_data)?)
+    }
+}
+
+impl SpliceInfoSection {
+    /// Creates a version 1 `EventMessage` carrying the `SpliceInfoSection`, at the given
+    /// `presentation_time` in terms of `timescale`.
+    ///
+    /// The `id` is derived from the `event_id` of a `SpliceInsert` or the first
+    /// `SegmentationDescriptor`, and the `event_duration` is derived from the `break_duration` of a
+    /// `SpliceInsert` or the first `segmentation_duration` (rounded to the `timescale`), or is
+    /// otherwise unknown (0xFFFFFFFF).
+    pub fn try_to_event_message(
+        &self,
+        timescale: u32,
+        presentation_time: u64,
+    ) -> Result<EventMessage, EncodeError> {
+        let message_data = self.try_to_bytes()?;
+        let (id, duration) = self.event_id_and_duration();
+        let event_duration = duration
+            .map(|duration| (duration as u128 * timescale as u128 + 45000) / 90000)
+            .and_then(|duration| u32::try_from(duration).ok())
+            .filter(|duration| *duration != UNKNOWN_DURATION)
+            .unwrap_or(UNKNOWN_DURATION);
+        Ok(EventMessage {
+            scheme_id_uri: String::from(SCTE35_BIN_SCHEME_ID_URI),
+            value: String::new(),
+            timescale,
+            presentation_time: PresentationTime::Absolute(presentation_time),
+            event_duration,
+            id: id.unwrap_or_default(),
+            message_data,
+        })
+    }
+
+    /// Creates a serialized version 1 `emsg` box carrying the `SpliceInfoSection` (see
+    /// `try_to_event_message`).
+    pub fn try_to_emsg(
+        &self,
+        timescale: u32,
+        presentation_time: u64,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Ok(self
+            .try_to_event_message(timescale, presentation_time)?
+            .to_bytes())
+    }
+}
+
+/// Finds the `emsg` boxes within `data`, which must be a sequence of whole top-level ISO-BMFF
+/// boxes (e.g. a CMAF chunk). Boxes of other types are skipped.
+pub fn find_event_messages(mut data: &[u8]) -> Result<Vec<EventMessage>, EmsgError> {
+    let mut event_messages = vec![];
+    while !data.is_empty() {
+        let (box_type, _, size) = try_box_from(data)?;
+        if box_type == EMSG_BOX_TYPE {
+            event_messages.push(EventMessage::try_from_bytes(&data[..size])?);
+        }
+        data = &data[size..];
+    }
+    Ok(event_messages)
+}
+
+/// Reads the box header, returning the box type, the payload (the remainder of the box) and the
+/// size of the box.
+fn try_box_from(data: &[u8]) -> Result<(&[u8], &[u8], usize), EmsgError> {
+    let mut reader = Reader(data);
+    let size = reader.u32("box size")? as u64;
+    let box_type = reader.bytes(4, "box type")?;
+    let (size, header_size) = match size {
+        1 => (reader.u64("box largesize")?, 16),
+        // A size of 0 indicates that the box extends to the end of the data.
+        0 => (data.len() as u64, 8),
+        size => (size, 8),
+    };
+    if size < header_size || size > data.len() as u64 {
+        return Err(EmsgError::InvalidBoxSize(size));
+    }
+    Ok((
+        box_type,
+        &data[header_size as usize..size as usize],
+        size as usize,
+    ))
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize, description: &'static str) -> Result<&'a [u8], EmsgError> {
+        let (bytes, remaining) = self
+            .0
+            .split_at_checked(n)
+            .ok_or(EmsgError::UnexpectedEndOfData { description })?;
+        self.0 = remaining;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self, description: &'static str) -> Result<u8, EmsgError> {
+        Ok(self.bytes(1, description)?[0])
+    }
+
+    fn u32(&mut self, description: &'static str) -> Result<u32, EmsgError> {
+        let bytes = self.bytes(4, description)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self, description: &'static str) -> Result<u64, EmsgError> {
+        Ok((self.u32(description)? as u64) << 32 | self.u32(description)? as u64)
+    }
+
+    /// Reads a null-terminated UTF-8 string.
+    fn string(&mut self, field: &'static str) -> Result<String, EmsgError> {
+        let length = self
+            .0
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(EmsgError::InvalidString { field })?;
+        let string = std::str::from_utf8(&self.0[..length])
+            .map_err(|_| EmsgError::InvalidString { field })?;
+        self.0 = &self.0[length + 1..];
+        Ok(String::from(string))
+    }
+}
@@ -0,0 +1,197 @@
+//! Parsing of encrypted messages (i.e. those with `encrypted_packet` set), where the portion of
+//! the `SpliceInfoSection` from the `splice_command_type` up to and including the `e_crc_32` is
+//! decrypted by the `Decryptor` of the `ParserConfig` before it is parsed.
+//!
+//! The `crc_32` of an encrypted message is calculated over the encrypted bytes, and the
+//! `e_crc_32` over the decrypted bytes. A mismatch of either is handled as configured by the
+//! `crc_mismatch` of the `ParserConfig` (see `ParseError::CRCMismatch` and
+//! `ParseError::ECRCMismatch`).
+//! ```
+//! use scte35::{
+//!     encryption::Decryptor,
+//!     error::ParseError,
+//!     splice_command::SpliceCommand,
+//!     splice_info_section::{EncryptionAlgorithm, ParserConfig, SpliceInfoSection},
+//!     time::Pts90k,
+//! };
+//! use std::{collections::HashMap, sync::Arc};
+//!
+//! /// A (very insecure) user private algorithm, that XORs each byte with the control word.
+//! #[derive(Debug)]
+//! struct XorDecryptor {
+//!     control_words: HashMap<u8, u8>,
+//! }
+//!
+//! impl Decryptor for XorDecryptor {
+//!     fn decrypt(
+//!         &self,
+//!         encryption_algorithm: &EncryptionAlgorithm,
+//!         cw_index: u8,
+//!         data: &[u8],
+//!     ) -> Result<Vec<u8>, ParseError> {
+//!         if encryption_algorithm != &EncryptionAlgorithm::UserPrivate(32) {
+//!             return Err(ParseError::DecryptionFailed("unsupported encryption_algorithm"));
+//!         }
+//!         let control_word = self
+//!             .control_words
+//!             .get(&cw_index)
+//!             .ok_or(ParseError::DecryptionFailed("unknown cw_index"))?;
+//!         Ok(data.iter().map(|byte| byte ^ control_word).collect())
+//!     }
+//! }
+//!
+//! let hex_string = "0xFC301E00C00000000007FFF0055CA428E75A0A5A5AA5A5A5A5C163D61BF66AED2A";
+//! let config = ParserConfig {
+//!     decryptor: Some(Arc::new(XorDecryptor {
+//!         control_words: HashMap::from([(7, 0x5A)]),
+//!     })),
+//!     ..ParserConfig::strict()
+//! };
+//! let data = scte35::parser::Parser::new().decode_hex(hex_string).unwrap().to_vec();
+//! let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&data, &config).unwrap();
+//! let SpliceCommand::TimeSignal(time_signal) = &splice_info_section.splice_command else {
+//!     panic!("expected time_signal");
+//! };
+//! assert_eq!(Some(Pts90k(1924989008)), time_signal.splice_time.pts_time);
+//! let encrypted_packet = splice_info_section.encrypted_packet.unwrap();
+//! assert_eq!(EncryptionAlgorithm::UserPrivate(32), encrypted_packet.encryption_algorithm);
+//! assert_eq!(7, encrypted_packet.cw_index);
+//! assert_eq!(4, encrypted_packet.alignment_stuffing);
+//! assert_eq!(0x9B398C41, encrypted_packet.e_crc_32);
+//!
+//! assert_eq!(
+//!     Err(ParseError::EncryptedMessageNotSupported),
+//!     SpliceInfoSection::try_from_bytes(&data)
+//! );
+//! ```
+use crate::{error::ParseError, splice_info_section::EncryptionAlgorithm};
+use std::fmt::Debug;
+
+/// Decrypts the encrypted portion of a `SpliceInfoSection` (see the `encryption` module).
+pub trait Decryptor: Debug + Send + Sync {
+    /// Decrypts the `data` (the bytes from the `splice_command_type` up to and including the
+    /// `e_crc_32`) with the control word (key) at `cw_index`, using the `encryption_algorithm`.
+    /// The decrypted data should be the same length as the encrypted `data`.
+    fn decrypt(
+        &self,
+        encryption_algorithm: &EncryptionAlgorithm,
+        cw_index: u8,
+        data: &[u8],
+    ) -> Result<Vec<u8>, ParseError>;
+}
+
+/// A `Decryptor` for the algorithms defined by the specification (DES in ECB and CBC mode, and
+/// Triple DES EDE3 in ECB mode), using the control words that have been provided for each
+/// `cw_index`. Available with the `crypto` feature.
+///
+/// DES in CBC mode uses an initialization vector of zero. For the “standard” two key Triple DES,
+/// the first 8 bytes of the key are repeated as the last 8 bytes.
+/// ```
+/// use scte35::{
+///     encryption::{DesDecryptor, Decryptor},
+///     splice_info_section::EncryptionAlgorithm,
+/// };
+///
+/// let decryptor = DesDecryptor::new().des_key(1, [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]);
+/// let clear = decryptor
+///     .decrypt(
+///         &EncryptionAlgorithm::DesEcbMode,
+///         1,
+///         &[0x85, 0xE8, 0x13, 0x54, 0x0F, 0x0A, 0xB4, 0x05],
+///     )
+///     .unwrap();
+/// assert_eq!(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF], clear);
+/// ```
+#[cfg(feature = "crypto")]
+#[derive(Clone, Default)]
+pub struct DesDecryptor {
+    des_keys: std::collections::HashMap<u8, [u8; 8]>,
+    triple_des_keys: std::collections::HashMap<u8, [u8; 24]>,
+}
+
+#[cfg(feature = "crypto")]
+impl DesDecryptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the 64-bit key used for DES (in either ECB or CBC mode) at `cw_index`.
+    pub fn des_key(mut self, cw_index: u8, key: [u8; 8]) -> Self {
+        self.des_keys.insert(cw_index, key);
+        self
+    }
+
+    /// Sets the three 64-bit keys used for Triple DES at `cw_index`.
+    pub fn triple_des_key(mut self, cw_index: u8, key: [u8; 24]) -> Self {
+        self.triple_des_keys.insert(cw_index, key);
+        self
+    }
+}
+
+#[cfg(feature = "crypto")]
+impl Decryptor for DesDecryptor {
+    fn decrypt(
+        &self,
+        encryption_algorithm: &EncryptionAlgorithm,
+        cw_index: u8,
+        data: &[u8],
+    ) -> Result<Vec<u8>, ParseError> {
+        use des::{
+            cipher::{BlockDecrypt, KeyInit},
+            Des, TdesEde3,
+        };
+
+        if !data.len().is_multiple_of(8) {
+            return Err(ParseError::DecryptionFailed(
+                "encrypted data is not a multiple of 8 bytes",
+            ));
+        }
+        let mut clear = data.to_vec();
+        match encryption_algorithm {
+            EncryptionAlgorithm::DesEcbMode | EncryptionAlgorithm::DesCbcMode => {
+                let key = self
+                    .des_keys
+                    .get(&cw_index)
+                    .ok_or(ParseError::DecryptionFailed("no DES key for cw_index"))?;
+                let cipher = Des::new(key.into());
+                for block in clear.chunks_exact_mut(8) {
+                    cipher.decrypt_block(block.into());
+                }
+                if encryption_algorithm == &EncryptionAlgorithm::DesCbcMode {
+                    // Each decrypted block is XORed with the previous encrypted block.
+                    for (clear_byte, previous_byte) in clear[8..].iter_mut().zip(data) {
+                        *clear_byte ^= previous_byte;
+                    }
+                }
+            }
+            EncryptionAlgorithm::TripleDes => {
+                let key =
+                    self.triple_des_keys
+                        .get(&cw_index)
+                        .ok_or(ParseError::DecryptionFailed(
+                            "no Triple DES key for cw_index",
+                        ))?;
+                let cipher = TdesEde3::new(key.into());
+                for block in clear.chunks_exact_mut(8) {
+                    cipher.decrypt_block(block.into());
+                }
+            }
+            _ => {
+                return Err(ParseError::DecryptionFailed(
+                    "unsupported encryption_algorithm",
+                ))
+            }
+        }
+        Ok(clear)
+    }
+}
+
+#[cfg(feature = "crypto")]
+impl Debug for DesDecryptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DesDecryptor")
+            .field("des_cw_indexes", &self.des_keys.keys())
+            .field("triple_des_cw_indexes", &self.triple_des_keys.keys())
+            .finish()
+    }
+}
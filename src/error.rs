@@ -1,14 +1,22 @@
 use crate::{
     hex::DecodeHexError,
     splice_command::SpliceCommandType,
-    splice_descriptor::{segmentation_descriptor::SegmentationUPIDType, SpliceDescriptorTag},
+    splice_descriptor::{
+        segmentation_descriptor::{SegmentationTypeID, SegmentationUPIDType},
+        SpliceDescriptorTag,
+    },
 };
 use std::{
     fmt::{Display, Formatter},
     str::Utf8Error,
 };
 
+/// The descriptive `&'static str` fields of errors, written as an alias so that the serde derive
+/// does not take them to be borrowed from the deserialized input.
+type StaticStr = &'static str;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseError {
     UnexpectedEndOfData {
         /// The expected minimum number of bits left in the data.
@@ -16,9 +24,25 @@ pub enum ParseError {
         /// The actual number of bits left in the data.
         actual_bits_left: u32,
         /// A description of what was being attempted to be parsed that resulted in error.
-        description: &'static str,
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
     },
     DecodeHexError(DecodeHexError),
+    /// The provided string was not valid base64.
+    #[cfg(feature = "base64")]
+    DecodeBase64Error(
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "crate::error::serialize_display",
+                deserialize_with = "crate::error::deserialize_base64_error"
+            )
+        )]
+        base64::DecodeError,
+    ),
     InvalidSectionSyntaxIndicator,
     InvalidPrivateIndicator,
     UnrecognisedSpliceCommandType(u8),
@@ -33,8 +57,35 @@ pub enum ParseError {
         /// This is the type of the UPID that failed to parse properly.
         segmentation_upid_type: SegmentationUPIDType,
     },
-    InvalidUUIDInSegmentationUPID(&'static str),
-    InvalidURLInSegmentationUPID(&'static str),
+    InvalidUUIDInSegmentationUPID(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        StaticStr,
+    ),
+    InvalidURLInSegmentationUPID(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        StaticStr,
+    ),
+    InvalidEIDR(String),
+    InvalidISAN(String),
+    InvalidADI(String),
+    InvalidISO639LanguageCode(String),
+    /// The `dtmf_chars` of a `DTMFDescriptor` contain a character other than `0` to `9`, `*` and
+    /// `#`.
+    InvalidDTMFChars(String),
+    /// The private bytes of a custom splice descriptor (or private command) could not be parsed.
+    InvalidPrivateData(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        StaticStr,
+    ),
     UnrecognisedSegmentationTypeID(u8),
     InvalidSegmentationDescriptorIdentifier(u32),
     InvalidATSCContentIdentifierInUPID {
@@ -49,7 +100,24 @@ pub enum ParseError {
     },
     UnrecognisedAudioCodingMode(u8),
     UnrecognisedSpliceDescriptorTag(u8),
+    /// The section is encrypted and the `ParserConfig` has no `Decryptor`.
     EncryptedMessageNotSupported,
+    /// The `Decryptor` could not decrypt the encrypted portion of the section.
+    DecryptionFailed(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        StaticStr,
+    ),
+    /// The `e_crc_32` of an encrypted section did not match the CRC calculated from its decrypted
+    /// bytes.
+    ECRCMismatch {
+        /// The `e_crc_32` carried in the section.
+        e_crc_32: u32,
+        /// The CRC calculated from the decrypted bytes of the section.
+        calculated_e_crc_32: u32,
+    },
     UnexpectedSpliceCommandLength {
         /// This is the number of bits that the SpliceCommand was expected to have as declared via
         /// `splice_command_length`.
@@ -78,9 +146,42 @@ pub enum ParseError {
         /// The tag for the splice descriptor.
         splice_descriptor_tag: SpliceDescriptorTag,
     },
+    /// The `crc_32` of the section did not match the CRC calculated from its bytes.
+    CRCMismatch {
+        /// The `crc_32` carried in the section.
+        crc_32: u32,
+        /// The CRC calculated from the bytes of the section.
+        calculated_crc_32: u32,
+    },
+    /// A limit of the `ParserLimits` was exceeded while parsing.
+    LimitExceeded {
+        /// The value of the limit that was exceeded.
+        limit: usize,
+        /// The name of the limit that was exceeded.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    /// The `table_id` of the section was not 0xFC.
+    UnexpectedTableID(u8),
+    /// The `protocol_version` of the section was not 0.
+    UnsupportedProtocolVersion(u8),
     Utf8ConversionError {
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "crate::error::serialize_display",
+                deserialize_with = "crate::error::deserialize_utf8_error"
+            )
+        )]
         error: Utf8Error,
-        description: &'static str,
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
     },
 }
 
@@ -90,6 +191,13 @@ impl From<DecodeHexError> for ParseError {
     }
 }
 
+#[cfg(feature = "base64")]
+impl From<base64::DecodeError> for ParseError {
+    fn from(e: base64::DecodeError) -> Self {
+        ParseError::DecodeBase64Error(e)
+    }
+}
+
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -105,6 +213,8 @@ impl Display for ParseError {
                 )
             }
             ParseError::DecodeHexError(e) => e.fmt(f),
+            #[cfg(feature = "base64")]
+            ParseError::DecodeBase64Error(e) => e.fmt(f),
             ParseError::InvalidSectionSyntaxIndicator => {
                 "The 1-bit section syntax indicator was not 0.".fmt(f)
             }
@@ -132,6 +242,18 @@ impl Display for ParseError {
                 write!(f, "{} is not a valid UUID.", id)
             }
             ParseError::InvalidURLInSegmentationUPID(id) => write!(f, "{} is not a valid URL.", id),
+            ParseError::InvalidEIDR(id) => write!(f, "{} is not a valid EIDR.", id),
+            ParseError::InvalidISAN(id) => write!(f, "{} is not a valid ISAN.", id),
+            ParseError::InvalidADI(id) => write!(f, "{} is not a valid ADI identifier.", id),
+            ParseError::InvalidISO639LanguageCode(code) => {
+                write!(f, "{} is not a valid ISO 639-2 language code.", code)
+            }
+            ParseError::InvalidDTMFChars(chars) => {
+                write!(f, "{} is not a valid DTMF sequence.", chars)
+            }
+            ParseError::InvalidPrivateData(description) => {
+                write!(f, "Invalid private data: {}.", description)
+            }
             ParseError::UnrecognisedSegmentationTypeID(t) => {
                 write!(f, "Value {} was obtained for segmentation type id and this does not match any known values.", t)
             }
@@ -173,6 +295,19 @@ impl Display for ParseError {
             ParseError::EncryptedMessageNotSupported => {
                 "The SpliceInfoSection was determined to be encrypted and this is not currently supported".fmt(f)
             }
+            ParseError::DecryptionFailed(description) => {
+                write!(f, "Decryption failed: {}.", description)
+            }
+            ParseError::ECRCMismatch {
+                e_crc_32,
+                calculated_e_crc_32,
+            } => {
+                write!(
+                    f,
+                    "E_CRC mismatch; e_crc_32 was {:#010x} and calculated CRC was {:#010x}.",
+                    e_crc_32, calculated_e_crc_32
+                )
+            }
             ParseError::UnexpectedSpliceCommandLength {
                 declared_splice_command_length_in_bits,
                 actual_splice_command_length_in_bits,
@@ -210,6 +345,25 @@ impl Display for ParseError {
                     actual_splice_descriptor_length_in_bits
                 )
             }
+            ParseError::CRCMismatch {
+                crc_32,
+                calculated_crc_32,
+            } => {
+                write!(
+                    f,
+                    "CRC mismatch; crc_32 was {:#010x} and calculated CRC was {:#010x}.",
+                    crc_32, calculated_crc_32
+                )
+            }
+            ParseError::LimitExceeded { limit, description } => {
+                write!(f, "Exceeded {} limit of {}.", description, limit)
+            }
+            ParseError::UnexpectedTableID(table_id) => {
+                write!(f, "Unexpected table_id {:#04x}; expected 0xfc.", table_id)
+            }
+            ParseError::UnsupportedProtocolVersion(protocol_version) => {
+                write!(f, "Unsupported protocol_version {}.", protocol_version)
+            }
             ParseError::Utf8ConversionError { error, description } => {
                 write!(f, "Utf8Error: {} - {}", error, description)
             }
@@ -219,8 +373,862 @@ impl Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// A `ParseError` along with the position in the data at which it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocatedParseError {
+    /// The error that occurred.
+    pub error: ParseError,
+    /// The number of bits that had been read from the start of the data when the error occurred.
+    pub bit_offset: usize,
+    /// The path of the structure that was being parsed when the error occurred (e.g.
+    /// `splice_descriptors[2].segmentation_upid`), which is empty for the top-level fields of the
+    /// `SpliceInfoSection`.
+    pub path: String,
+}
+
+impl From<LocatedParseError> for ParseError {
+    fn from(e: LocatedParseError) -> Self {
+        e.error
+    }
+}
+
+impl Display for LocatedParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "At bit {}: {}", self.bit_offset, self.error)
+        } else {
+            write!(
+                f,
+                "At bit {} in {}: {}",
+                self.bit_offset, self.path, self.error
+            )
+        }
+    }
+}
+
+impl std::error::Error for LocatedParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EncodeError {
+    ValueOutOfRange {
+        /// The value that was attempted to be encoded.
+        value: u64,
+        /// The number of bits available for the value in the encoded message.
+        maximum_bits: u32,
+        /// A description of what was being attempted to be encoded that resulted in error.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    LengthOutOfRange {
+        /// The length (in bytes, or number of items for loops) that was attempted to be encoded.
+        length: usize,
+        /// The maximum length that can be represented for this field in the encoded message.
+        maximum_length: usize,
+        /// A description of what was being attempted to be encoded that resulted in error.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    UnexpectedLength {
+        /// The length (in bytes) that was attempted to be encoded.
+        length: usize,
+        /// The exact length (in bytes) that the field is required to have.
+        expected_length: usize,
+        /// A description of what was being attempted to be encoded that resulted in error.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    UnexpectedSegmentationUPIDLength {
+        /// This is the number of bytes that the UPID would have when encoded.
+        actual_segmentation_upid_length: usize,
+        /// This is the number of bytes that the UPID is expected to have as defined by the
+        /// specification for the given UPID type.
+        expected_segmentation_upid_length: u8,
+        /// This is the type of the UPID that failed to encode.
+        segmentation_upid_type: SegmentationUPIDType,
+    },
+    InvalidSegmentationUPID {
+        /// This is the type of the UPID that failed to encode.
+        segmentation_upid_type: SegmentationUPIDType,
+        /// A description of why the UPID value could not be encoded.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    InvalidSpliceDescriptorForCommand {
+        /// The tag of the splice descriptor that is not allowed with the splice command.
+        splice_descriptor_tag: SpliceDescriptorTag,
+        /// The type of the splice command that the descriptor was provided with.
+        splice_command_type: SpliceCommandType,
+    },
+    EncryptedMessageNotSupported,
+    /// The identifier of `PrivateCommandData` is not valid UTF-8, and so cannot be represented as
+    /// the `identifier` of a `PrivateCommand`.
+    InvalidPrivateCommandIdentifier(u32),
+    /// A `SpliceDescriptor::Custom` or `SpliceDescriptor::Unknown` has no representation in the
+    /// SCTE-35 XML schema.
+    CustomSpliceDescriptorNotSupported {
+        /// The tag of the custom splice descriptor.
+        tag: u8,
+        /// The identifier of the custom splice descriptor.
+        identifier: u32,
+    },
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            EncodeError::ValueOutOfRange {
+                value,
+                maximum_bits,
+                description,
+            } => {
+                write!(
+                    f,
+                    "Value {} cannot be represented in {} bits when encoding: {}.",
+                    value, maximum_bits, description
+                )
+            }
+            EncodeError::LengthOutOfRange {
+                length,
+                maximum_length,
+                description,
+            } => {
+                write!(
+                    f,
+                    "Length {} exceeds the maximum of {} when encoding: {}.",
+                    length, maximum_length, description
+                )
+            }
+            EncodeError::UnexpectedLength {
+                length,
+                expected_length,
+                description,
+            } => {
+                write!(
+                    f,
+                    "Length {} does not match the expected length of {} when encoding: {}.",
+                    length, expected_length, description
+                )
+            }
+            EncodeError::UnexpectedSegmentationUPIDLength {
+                actual_segmentation_upid_length,
+                expected_segmentation_upid_length,
+                segmentation_upid_type,
+            } => {
+                write!(
+                    f,
+                    "Encoded upid length was {}; however, expected length for upid type {} is {}.",
+                    actual_segmentation_upid_length,
+                    segmentation_upid_type.value(),
+                    expected_segmentation_upid_length
+                )
+            }
+            EncodeError::InvalidSegmentationUPID {
+                segmentation_upid_type,
+                description,
+            } => {
+                write!(
+                    f,
+                    "Value for upid type {} could not be encoded: {}.",
+                    segmentation_upid_type.value(),
+                    description
+                )
+            }
+            EncodeError::InvalidSpliceDescriptorForCommand {
+                splice_descriptor_tag,
+                splice_command_type,
+            } => {
+                write!(
+                    f,
+                    "Splice descriptor ({}) is not allowed with splice command ({}).",
+                    splice_descriptor_tag.value(),
+                    splice_command_type.value()
+                )
+            }
+            EncodeError::EncryptedMessageNotSupported => {
+                "The SpliceInfoSection was determined to be encrypted and this is not currently supported".fmt(f)
+            }
+            EncodeError::InvalidPrivateCommandIdentifier(identifier) => {
+                write!(
+                    f,
+                    "Private command identifier {:#010x} is not valid UTF-8.",
+                    identifier
+                )
+            }
+            EncodeError::CustomSpliceDescriptorNotSupported { tag, identifier } => {
+                write!(
+                    f,
+                    "Custom splice descriptor with tag {:#04x} and identifier {:#010x} cannot be represented in XML.",
+                    tag, identifier
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HLSParseError {
+    /// The line is not the tag that was expected to be parsed.
+    UnexpectedTag(String),
+    /// The attribute list of the tag is not well-formed.
+    InvalidAttributeList(String),
+    /// A required attribute was not present in the tag.
+    MissingAttribute(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        StaticStr,
+    ),
+    /// An attribute value was not of the type expected for the attribute.
+    InvalidAttribute {
+        /// The name of the attribute that could not be parsed.
+        attribute: String,
+        /// The value of the attribute that could not be parsed.
+        value: String,
+    },
+    /// The value of a tag that does not have an attribute list could not be parsed.
+    InvalidTagValue {
+        /// The name of the tag (without the leading `#`).
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        tag: StaticStr,
+        /// The value that could not be parsed.
+        value: String,
+    },
+    /// The SCTE-35 message carried by the tag could not be parsed.
+    ParseError(ParseError),
+}
+
+impl From<ParseError> for HLSParseError {
+    fn from(e: ParseError) -> Self {
+        HLSParseError::ParseError(e)
+    }
+}
+
+impl Display for HLSParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            HLSParseError::UnexpectedTag(line) => write!(f, "Unexpected tag: {}.", line),
+            HLSParseError::InvalidAttributeList(attribute_list) => {
+                write!(f, "Invalid attribute list: {}.", attribute_list)
+            }
+            HLSParseError::MissingAttribute(attribute) => {
+                write!(f, "Missing attribute {}.", attribute)
+            }
+            HLSParseError::InvalidAttribute { attribute, value } => {
+                write!(
+                    f,
+                    "Invalid value \"{}\" for attribute {}.",
+                    value, attribute
+                )
+            }
+            HLSParseError::InvalidTagValue { tag, value } => {
+                write!(f, "Invalid value \"{}\" for tag {}.", value, tag)
+            }
+            HLSParseError::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HLSParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HLSInterstitialError {
+    /// The start (or end) message did not contain a (non-cancelled) `SegmentationDescriptor`
+    /// for the segmentation event.
+    MissingSegmentationDescriptor,
+    /// The `SegmentationTypeID` of the end message does not end the segment started by the
+    /// `SegmentationTypeID` of the start message.
+    MismatchedSegmentationTypes {
+        start: SegmentationTypeID,
+        end: SegmentationTypeID,
+    },
+    /// The duration could not be determined from either the PTS of the messages or the
+    /// `segmentation_duration` of the start message.
+    MissingDuration,
+    /// The start or end message could not be encoded.
+    EncodeError(EncodeError),
+}
+
+impl From<EncodeError> for HLSInterstitialError {
+    fn from(e: EncodeError) -> Self {
+        HLSInterstitialError::EncodeError(e)
+    }
+}
+
+impl Display for HLSInterstitialError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            HLSInterstitialError::MissingSegmentationDescriptor => {
+                "Missing segmentation descriptor for interstitial.".fmt(f)
+            }
+            HLSInterstitialError::MismatchedSegmentationTypes { start, end } => write!(
+                f,
+                "Segmentation type ({}) does not end segmentation type ({}).",
+                end.value(),
+                start.value()
+            ),
+            HLSInterstitialError::MissingDuration => {
+                "Unable to determine the duration of the interstitial.".fmt(f)
+            }
+            HLSInterstitialError::EncodeError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HLSInterstitialError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CueConversionError {
+    /// The splice command of the message is not the one that is converted from.
+    UnexpectedSpliceCommand(SpliceCommandType),
+    /// The `SpliceInsert` is in the Component Splice Mode, which has no equivalent `TimeSignal`.
+    ComponentSpliceMode,
+    /// The `TimeSignal` message did not contain a Provider Placement Opportunity (or cancelled)
+    /// `SegmentationDescriptor`.
+    MissingSegmentationDescriptor,
+    /// The converted message could not be encoded.
+    EncodeError(EncodeError),
+}
+
+impl From<EncodeError> for CueConversionError {
+    fn from(e: EncodeError) -> Self {
+        CueConversionError::EncodeError(e)
+    }
+}
+
+impl Display for CueConversionError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CueConversionError::UnexpectedSpliceCommand(splice_command_type) => write!(
+                f,
+                "Splice command ({}) cannot be converted.",
+                splice_command_type.value()
+            ),
+            CueConversionError::ComponentSpliceMode => {
+                "Splice insert in component splice mode cannot be converted.".fmt(f)
+            }
+            CueConversionError::MissingSegmentationDescriptor => {
+                "Missing provider placement opportunity segmentation descriptor to convert.".fmt(f)
+            }
+            CueConversionError::EncodeError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CueConversionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SectionEditError {
+    /// The bytes do not contain a complete section (as declared by the `section_length`).
+    ParseError(ParseError),
+    /// The value cannot be represented in the field being edited.
+    EncodeError(EncodeError),
+    /// The splice command is encrypted, so its `pts_time` cannot be edited.
+    EncryptedPacket,
+    /// The splice command does not have a `pts_time` to edit (it is not a `TimeSignal` or a
+    /// `SpliceInsert` in the Program Splice Mode, or it is immediate or cancelled).
+    MissingPTSTime,
+}
+
+impl From<ParseError> for SectionEditError {
+    fn from(e: ParseError) -> Self {
+        SectionEditError::ParseError(e)
+    }
+}
+
+impl From<EncodeError> for SectionEditError {
+    fn from(e: EncodeError) -> Self {
+        SectionEditError::EncodeError(e)
+    }
+}
+
+impl Display for SectionEditError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SectionEditError::ParseError(e) => e.fmt(f),
+            SectionEditError::EncodeError(e) => e.fmt(f),
+            SectionEditError::EncryptedPacket => {
+                "Splice command is encrypted so the pts_time cannot be edited.".fmt(f)
+            }
+            SectionEditError::MissingPTSTime => "Splice command has no pts_time to edit.".fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SectionEditError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportStreamError {
+    /// The packet was not 188 bytes long.
+    InvalidPacketLength(usize),
+    /// The packet did not start with the sync byte (0x47).
+    InvalidSyncByte(u8),
+    /// The adaptation field length extends beyond the end of the packet.
+    InvalidAdaptationFieldLength(u8),
+    /// The `pointer_field` points beyond the end of the packet payload.
+    InvalidPointerField(u8),
+    /// A program association or program map section is shorter than its fields require.
+    MalformedProgramSection {
+        /// The `table_id` of the section.
+        table_id: u8,
+    },
+    /// The `CRC_32` of a program association or program map section is not valid.
+    InvalidProgramSectionCRC {
+        /// The `table_id` of the section.
+        table_id: u8,
+    },
+    /// A reassembled section could not be parsed.
+    ParseError(ParseError),
+}
+
+impl From<ParseError> for TransportStreamError {
+    fn from(e: ParseError) -> Self {
+        TransportStreamError::ParseError(e)
+    }
+}
+
+impl Display for TransportStreamError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TransportStreamError::InvalidPacketLength(length) => {
+                write!(f, "Invalid packet length ({}); expected 188.", length)
+            }
+            TransportStreamError::InvalidSyncByte(sync_byte) => {
+                write!(f, "Invalid sync byte (0x{:02X}); expected 0x47.", sync_byte)
+            }
+            TransportStreamError::InvalidAdaptationFieldLength(length) => {
+                write!(
+                    f,
+                    "Adaptation field length ({}) exceeds the packet.",
+                    length
+                )
+            }
+            TransportStreamError::InvalidPointerField(pointer_field) => {
+                write!(
+                    f,
+                    "Pointer field ({}) exceeds the packet payload.",
+                    pointer_field
+                )
+            }
+            TransportStreamError::MalformedProgramSection { table_id } => {
+                write!(
+                    f,
+                    "Malformed program section (table_id 0x{:02X}).",
+                    table_id
+                )
+            }
+            TransportStreamError::InvalidProgramSectionCRC { table_id } => {
+                write!(
+                    f,
+                    "Invalid CRC_32 for program section (table_id 0x{:02X}).",
+                    table_id
+                )
+            }
+            TransportStreamError::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for TransportStreamError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SectionReaderError {
+    /// Reading from the underlying reader failed (the description of the `std::io::Error`).
+    Io(String),
+    /// A transport stream packet could not be processed.
+    TransportStreamError(TransportStreamError),
+    /// A section could not be parsed.
+    ParseError(ParseError),
+}
+
+impl From<std::io::Error> for SectionReaderError {
+    fn from(e: std::io::Error) -> Self {
+        SectionReaderError::Io(e.to_string())
+    }
+}
+
+impl From<TransportStreamError> for SectionReaderError {
+    fn from(e: TransportStreamError) -> Self {
+        SectionReaderError::TransportStreamError(e)
+    }
+}
+
+impl From<ParseError> for SectionReaderError {
+    fn from(e: ParseError) -> Self {
+        SectionReaderError::ParseError(e)
+    }
+}
+
+impl Display for SectionReaderError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SectionReaderError::Io(description) => write!(f, "IO error: {}.", description),
+            SectionReaderError::TransportStreamError(e) => e.fmt(f),
+            SectionReaderError::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SectionReaderError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmsgError {
+    /// The data ended before the box (or the field being read) was complete.
+    UnexpectedEndOfData {
+        /// A description of what was being read.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        description: StaticStr,
+    },
+    /// The size of the box is smaller than its header or larger than the data.
+    InvalidBoxSize(u64),
+    /// The box is not an `emsg` box.
+    UnexpectedBoxType(String),
+    /// The version of the `emsg` box is not 0 or 1.
+    UnsupportedVersion(u8),
+    /// A string field is not null-terminated UTF-8.
+    InvalidString {
+        /// The name of the field.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        field: StaticStr,
+    },
+    /// The `scheme_id_uri` does not indicate a binary SCTE-35 message.
+    UnexpectedScheme(String),
+    /// The `message_data` could not be parsed as a `SpliceInfoSection`.
+    ParseError(ParseError),
+}
+
+impl From<ParseError> for EmsgError {
+    fn from(e: ParseError) -> Self {
+        EmsgError::ParseError(e)
+    }
+}
+
+impl Display for EmsgError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            EmsgError::UnexpectedEndOfData { description } => {
+                write!(f, "Unexpected end of data while reading {}.", description)
+            }
+            EmsgError::InvalidBoxSize(size) => write!(f, "Invalid box size ({}).", size),
+            EmsgError::UnexpectedBoxType(box_type) => {
+                write!(f, "Unexpected box type ({}); expected emsg.", box_type)
+            }
+            EmsgError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported emsg version ({}).", version)
+            }
+            EmsgError::InvalidString { field } => {
+                write!(f, "The {} is not a null-terminated UTF-8 string.", field)
+            }
+            EmsgError::UnexpectedScheme(scheme_id_uri) => {
+                write!(
+                    f,
+                    "Unexpected scheme ({}) for SCTE-35 message.",
+                    scheme_id_uri
+                )
+            }
+            EmsgError::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for EmsgError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ID3Error {
+    /// The data does not start with an ID3v2 tag header.
+    InvalidHeader,
+    /// The major version of the ID3v2 tag is not 3 or 4.
+    UnsupportedVersion(u8),
+    /// The data ended before the tag (or the frame being read) was complete.
+    UnexpectedEndOfData,
+    /// The tag does not contain a `PRIV` frame with the SCTE-35 owner identifier.
+    MissingSCTE35Frame,
+    /// The private data of the `PRIV` frame could not be parsed as a `SpliceInfoSection`.
+    ParseError(ParseError),
+}
+
+impl From<ParseError> for ID3Error {
+    fn from(e: ParseError) -> Self {
+        ID3Error::ParseError(e)
+    }
+}
+
+impl Display for ID3Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ID3Error::InvalidHeader => "Invalid ID3v2 tag header.".fmt(f),
+            ID3Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported ID3v2 version (2.{}).", version)
+            }
+            ID3Error::UnexpectedEndOfData => "Unexpected end of data in ID3v2 tag.".fmt(f),
+            ID3Error::MissingSCTE35Frame => "No SCTE-35 PRIV frame in ID3v2 tag.".fmt(f),
+            ID3Error::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ID3Error {}
+
+#[cfg(feature = "xml")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XMLParseError {
+    /// The document is not well-formed XML.
+    Syntax(String),
+    /// The root element of the document is not one that can be parsed as a `SpliceInfoSection`.
+    UnexpectedRootElement(String),
+    UnexpectedElement {
+        /// The name of the element that was not expected.
+        element: String,
+        /// The name of the element that contained the unexpected element.
+        parent: String,
+    },
+    MissingElement {
+        /// The name of the element that was expected.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        element: StaticStr,
+        /// The name of the element that should have contained the missing element.
+        parent: String,
+    },
+    MissingAttribute {
+        /// The name of the element that is missing the attribute.
+        element: String,
+        /// The name of the attribute that is missing.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        attribute: StaticStr,
+    },
+    InvalidAttribute {
+        /// The name of the element that contained the attribute.
+        element: String,
+        /// The name of the attribute that could not be parsed.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::error::deserialize_static_str")
+        )]
+        attribute: StaticStr,
+        /// The value of the attribute that could not be parsed.
+        value: String,
+    },
+    InvalidContent {
+        /// The name of the element whose content could not be parsed.
+        element: String,
+        /// The content that could not be parsed.
+        content: String,
+    },
+    UnsupportedSegmentationUPIDFormat(String),
+    /// The parsed values could not be converted into the binary model (e.g. a `SegmentationUPID`
+    /// that is not valid for its declared type).
+    ParseError(ParseError),
+    /// The parsed values could not be encoded (necessary to derive the `crc_32`).
+    EncodeError(EncodeError),
+}
+
+#[cfg(feature = "xml")]
+impl From<ParseError> for XMLParseError {
+    fn from(e: ParseError) -> Self {
+        XMLParseError::ParseError(e)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl From<EncodeError> for XMLParseError {
+    fn from(e: EncodeError) -> Self {
+        XMLParseError::EncodeError(e)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl Display for XMLParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            XMLParseError::Syntax(description) => write!(f, "XML syntax error: {}.", description),
+            XMLParseError::UnexpectedRootElement(element) => {
+                write!(f, "Unexpected root element <{}>.", element)
+            }
+            XMLParseError::UnexpectedElement { element, parent } => {
+                write!(f, "Unexpected element <{}> in <{}>.", element, parent)
+            }
+            XMLParseError::MissingElement { element, parent } => {
+                write!(f, "Missing element <{}> in <{}>.", element, parent)
+            }
+            XMLParseError::MissingAttribute { element, attribute } => {
+                write!(f, "Missing attribute {} on <{}>.", attribute, element)
+            }
+            XMLParseError::InvalidAttribute {
+                element,
+                attribute,
+                value,
+            } => {
+                write!(
+                    f,
+                    "Invalid value \"{}\" for attribute {} on <{}>.",
+                    value, attribute, element
+                )
+            }
+            XMLParseError::InvalidContent { element, content } => {
+                write!(f, "Invalid content \"{}\" in <{}>.", content, element)
+            }
+            XMLParseError::UnsupportedSegmentationUPIDFormat(format) => {
+                write!(f, "Unsupported segmentationUpidFormat \"{}\".", format)
+            }
+            XMLParseError::ParseError(e) => e.fmt(f),
+            XMLParseError::EncodeError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "xml")]
+impl std::error::Error for XMLParseError {}
+
 const STATIC_BYTES_LENGTH: isize = 4;
 
 fn calculated_byte_count(upid_length: u8) -> isize {
     (upid_length as isize) - STATIC_BYTES_LENGTH
 }
+
+/// Errors wrapping standard library error types are serialized via their `Display`
+/// representation.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_display<T: Display, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// The `&'static str` fields of errors (e.g. a `description`) are only ever set from literals, so
+/// each distinct value that is deserialized is leaked once and then reused.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_static_str<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<&'static str, D::Error> {
+    use serde::Deserialize;
+    use std::{
+        collections::HashSet,
+        sync::{Mutex, OnceLock},
+    };
+
+    static VALUES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let value = String::deserialize(deserializer)?;
+    let mut values = VALUES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(value) = values.get(value.as_str()) {
+        return Ok(value);
+    }
+    let value: &'static str = Box::leak(value.into_boxed_str());
+    values.insert(value);
+    Ok(value)
+}
+
+/// The inverse of `serialize_display` for a `Utf8Error`, which can only be created by decoding
+/// bytes, and so is recreated by decoding bytes that fail in the same way at the same index.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_utf8_error<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Utf8Error, D::Error> {
+    use serde::{de::Error, Deserialize};
+
+    let value = String::deserialize(deserializer)?;
+    let (error_len, index) = match value.strip_prefix("invalid utf-8 sequence of ") {
+        Some(rest) => match rest.split_once(" bytes from index ") {
+            Some((error_len, index)) => (error_len.parse::<usize>().ok(), index),
+            None => (None, ""),
+        },
+        None => (
+            None,
+            value
+                .strip_prefix("incomplete utf-8 byte sequence from index ")
+                .unwrap_or_default(),
+        ),
+    };
+    let invalid_bytes: Option<&[u8]> = match error_len {
+        None if value.starts_with("incomplete") => Some(&[0xE0]),
+        Some(1) => Some(&[0xFF]),
+        Some(2) => Some(&[0xE0, 0xA0, 0x41]),
+        Some(3) => Some(&[0xF0, 0x90, 0x80, 0x41]),
+        _ => None,
+    };
+    index
+        .parse::<usize>()
+        .ok()
+        .zip(invalid_bytes)
+        .and_then(|(index, invalid_bytes)| {
+            let bytes = [&vec![b'0'; index][..], invalid_bytes].concat();
+            std::str::from_utf8(&bytes).err()
+        })
+        .ok_or_else(|| D::Error::custom(format!("invalid Utf8Error \"{}\"", value)))
+}
+
+/// The inverse of `serialize_display` for a `base64::DecodeError`.
+#[cfg(all(feature = "serde", feature = "base64"))]
+pub(crate) fn deserialize_base64_error<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<base64::DecodeError, D::Error> {
+    use base64::DecodeError;
+    use serde::{de::Error, Deserialize};
+
+    // The "<byte>, offset <index>." that follows the description of an invalid symbol.
+    fn byte_and_offset(rest: &str) -> Option<(usize, u8)> {
+        let (byte, index) = rest.strip_suffix('.')?.split_once(", offset ")?;
+        Some((index.parse().ok()?, byte.parse().ok()?))
+    }
+
+    let value = String::deserialize(deserializer)?;
+    let error = if let Some(rest) = value.strip_prefix("Invalid byte ") {
+        byte_and_offset(rest).map(|(index, byte)| DecodeError::InvalidByte(index, byte))
+    } else if let Some(rest) = value.strip_prefix("Invalid last symbol ") {
+        byte_and_offset(rest).map(|(index, byte)| DecodeError::InvalidLastSymbol(index, byte))
+    } else if value == DecodeError::InvalidLength.to_string() {
+        Some(DecodeError::InvalidLength)
+    } else if value == DecodeError::InvalidPadding.to_string() {
+        Some(DecodeError::InvalidPadding)
+    } else {
+        None
+    };
+    error.ok_or_else(|| D::Error::custom(format!("invalid base64 DecodeError \"{}\"", value)))
+}
@@ -0,0 +1,300 @@
+//! An interoperability layer for the CableLabs Event Signaling and Management (ESAM) API
+//! (OC-SP-ESAM-API), whereby an Acquisition Point sends a `SignalProcessingEvent` containing each
+//! acquired SCTE-35 signal to a Placement Opportunity Information System (POIS), and the POIS
+//! responds with a `SignalProcessingNotification` describing how each signal should be
+//! conditioned.
+//!
+//! ```
+//! use scte35::{
+//!     esam::{AcquiredSignal, Action, SignalProcessingEvent, SignalProcessingNotification},
+//!     splice_info_section::SpliceInfoSection,
+//! };
+//!
+//! let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let event = SignalProcessingEvent {
+//!     acquired_signals: vec![AcquiredSignal::new(
+//!         "ESAM_POC",
+//!         "signal-1",
+//!         "2012-09-18T10:14:34Z",
+//!         splice_info_section,
+//!     )],
+//! };
+//! let request_xml = event.try_to_xml_string().unwrap();
+//! assert_eq!(event, SignalProcessingEvent::try_from_xml_string(&request_xml).unwrap());
+//!
+//! let response_xml = r#"
+//! <SignalProcessingNotification xmlns="urn:cablelabs:iptvservices:esam:xsd:signal:1" xmlns:sig="urn:cablelabs:md:xsd:signaling:3.0">
+//!     <ResponseSignal action="noop" acquisitionPointIdentity="ESAM_POC" acquisitionSignalID="signal-1">
+//!         <sig:UTCPoint utcPoint="2012-09-18T10:14:34Z"/>
+//!     </ResponseSignal>
+//! </SignalProcessingNotification>
+//! "#;
+//! let notification = SignalProcessingNotification::try_from_xml_string(response_xml).unwrap();
+//! assert_eq!(Action::Noop, notification.response_signals[0].action);
+//! ```
+
+use crate::{
+    error::{EncodeError, XMLParseError},
+    splice_info_section::SpliceInfoSection,
+    xml::{element::Element, parse::try_splice_info_section_from},
+};
+use base64::prelude::*;
+use std::str::FromStr;
+
+/// The namespace of the ESAM signal schema.
+pub const ESAM_SIGNAL_NAMESPACE: &str = "urn:cablelabs:iptvservices:esam:xsd:signal:1";
+/// The namespace of the CableLabs signaling schema (used with the `sig` prefix).
+pub const SIGNALING_NAMESPACE: &str = "urn:cablelabs:md:xsd:signaling:3.0";
+
+/// The request sent from an Acquisition Point to the POIS, containing one or more acquired
+/// signals.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SignalProcessingEvent {
+    pub acquired_signals: Vec<AcquiredSignal>,
+}
+
+/// A SCTE-35 signal that has been acquired by an Acquisition Point.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct AcquiredSignal {
+    /// Identifies the Acquisition Point that acquired the signal.
+    pub acquisition_point_identity: String,
+    /// A unique identifier for the acquired signal, used to correlate the response.
+    pub acquisition_signal_id: String,
+    /// The `xsd:dateTime` at which the signal was acquired.
+    pub acquisition_time: Option<String>,
+    /// The `xsd:dateTime` of the signal point in the content.
+    pub utc_point: String,
+    /// The acquired SCTE-35 message, which is carried as base64 encoded `BinaryData`.
+    pub splice_info_section: SpliceInfoSection,
+    /// The stream times (e.g. PTS) associated with the signal point.
+    pub stream_times: Vec<StreamTime>,
+}
+
+/// A time value associated with a signal point, expressed in terms of the given `time_type` (e.g.
+/// `"PTS"`, `"HSS"` or `"HLS"`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct StreamTime {
+    pub time_type: String,
+    pub time_value: String,
+}
+
+/// The response sent from the POIS to the Acquisition Point, describing how each signal should be
+/// conditioned.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SignalProcessingNotification {
+    pub acquisition_point_identity: Option<String>,
+    pub response_signals: Vec<ResponseSignal>,
+}
+
+/// The conditioning instruction for an individual signal.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct ResponseSignal {
+    /// The action that the Acquisition Point should take for the signal.
+    pub action: Action,
+    pub acquisition_point_identity: Option<String>,
+    /// Correlates the response with the `AcquiredSignal` (if provided).
+    pub acquisition_signal_id: Option<String>,
+    pub signal_point_id: Option<String>,
+    /// The `xsd:dateTime` of the signal point in the content.
+    pub utc_point: Option<String>,
+    /// The SCTE-35 message that should be used in place of the acquired signal (if provided).
+    pub splice_info_section: Option<SpliceInfoSection>,
+}
+
+/// The action that the Acquisition Point should take for a signal.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Action {
+    /// Insert the provided signal.
+    Create,
+    /// Replace the acquired signal with the provided signal.
+    Replace,
+    /// Remove the acquired signal.
+    Delete,
+    /// Leave the acquired signal unchanged.
+    Noop,
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create" => Ok(Action::Create),
+            "replace" => Ok(Action::Replace),
+            "delete" => Ok(Action::Delete),
+            "noop" => Ok(Action::Noop),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Action {
+    pub fn value(&self) -> &'static str {
+        match *self {
+            Action::Create => "create",
+            Action::Replace => "replace",
+            Action::Delete => "delete",
+            Action::Noop => "noop",
+        }
+    }
+}
+
+impl SignalProcessingEvent {
+    pub fn try_to_xml_string(&self) -> Result<String, EncodeError> {
+        let element = Element::new("SignalProcessingEvent")
+            .with_attribute("xmlns", ESAM_SIGNAL_NAMESPACE)
+            .with_attribute("xmlns:sig", SIGNALING_NAMESPACE)
+            .with_children(
+                self.acquired_signals
+                    .iter()
+                    .map(AcquiredSignal::try_to_element)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        let mut xml = String::new();
+        element.write(&mut xml);
+        Ok(xml)
+    }
+
+    pub fn try_from_xml_string(xml: &str) -> Result<Self, XMLParseError> {
+        let root = Element::try_from_str(xml)?;
+        if root.name != "SignalProcessingEvent" {
+            return Err(XMLParseError::UnexpectedRootElement(root.name));
+        }
+        Ok(Self {
+            acquired_signals: root
+                .children("AcquiredSignal")
+                .map(AcquiredSignal::try_from_element)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl AcquiredSignal {
+    /// Wraps the `SpliceInfoSection` into an `AcquiredSignal`.
+    pub fn new(
+        acquisition_point_identity: &str,
+        acquisition_signal_id: &str,
+        utc_point: &str,
+        splice_info_section: SpliceInfoSection,
+    ) -> Self {
+        Self {
+            acquisition_point_identity: String::from(acquisition_point_identity),
+            acquisition_signal_id: String::from(acquisition_signal_id),
+            acquisition_time: None,
+            utc_point: String::from(utc_point),
+            splice_info_section,
+            stream_times: vec![],
+        }
+    }
+
+    fn try_to_element(&self) -> Result<Element, EncodeError> {
+        let binary_data = BASE64_STANDARD.encode(self.splice_info_section.try_to_bytes()?);
+        let element = Element::new("AcquiredSignal")
+            .with_attribute("acquisitionPointIdentity", &self.acquisition_point_identity)
+            .with_attribute("acquisitionSignalID", &self.acquisition_signal_id)
+            .with_optional_attribute("acquisitionTime", self.acquisition_time.as_ref())
+            .with_child(Element::new("sig:UTCPoint").with_attribute("utcPoint", &self.utc_point))
+            .with_child(
+                Element::new("sig:BinaryData")
+                    .with_attribute("signalType", "SCTE35")
+                    .with_text(&binary_data),
+            );
+        if self.stream_times.is_empty() {
+            return Ok(element);
+        }
+        Ok(
+            element.with_child(Element::new("sig:StreamTimes").with_children(
+                self.stream_times.iter().map(|stream_time| {
+                    Element::new("sig:StreamTime")
+                        .with_attribute("timeType", &stream_time.time_type)
+                        .with_attribute("timeValue", &stream_time.time_value)
+                }),
+            )),
+        )
+    }
+
+    fn try_from_element(element: &Element) -> Result<Self, XMLParseError> {
+        Ok(Self {
+            acquisition_point_identity: element.required("acquisitionPointIdentity")?,
+            acquisition_signal_id: element.required("acquisitionSignalID")?,
+            acquisition_time: element.optional("acquisitionTime")?,
+            utc_point: try_utc_point_from(element)?.ok_or_else(|| {
+                XMLParseError::MissingElement {
+                    element: "UTCPoint",
+                    parent: element.name.clone(),
+                }
+            })?,
+            splice_info_section: try_signal_from(element)?.ok_or_else(|| {
+                XMLParseError::MissingElement {
+                    element: "BinaryData",
+                    parent: element.name.clone(),
+                }
+            })?,
+            stream_times: match element.child("StreamTimes") {
+                Some(stream_times) => stream_times
+                    .children("StreamTime")
+                    .map(|stream_time| {
+                        Ok(StreamTime {
+                            time_type: stream_time.required("timeType")?,
+                            time_value: stream_time.required("timeValue")?,
+                        })
+                    })
+                    .collect::<Result<_, XMLParseError>>()?,
+                None => vec![],
+            },
+        })
+    }
+}
+
+impl SignalProcessingNotification {
+    pub fn try_from_xml_string(xml: &str) -> Result<Self, XMLParseError> {
+        let root = Element::try_from_str(xml)?;
+        if root.name != "SignalProcessingNotification" {
+            return Err(XMLParseError::UnexpectedRootElement(root.name));
+        }
+        Ok(Self {
+            acquisition_point_identity: root.optional("acquisitionPointIdentity")?,
+            response_signals: root
+                .children("ResponseSignal")
+                .map(ResponseSignal::try_from_element)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl ResponseSignal {
+    fn try_from_element(element: &Element) -> Result<Self, XMLParseError> {
+        let action = element.attribute("action").unwrap_or_default();
+        Ok(Self {
+            action: Action::from_str(action)
+                .map_err(|_| element.invalid_attribute("action", action))?,
+            acquisition_point_identity: element.optional("acquisitionPointIdentity")?,
+            acquisition_signal_id: element.optional("acquisitionSignalID")?,
+            signal_point_id: element.optional("signalPointID")?,
+            utc_point: try_utc_point_from(element)?,
+            splice_info_section: try_signal_from(element)?,
+        })
+    }
+}
+
+fn try_utc_point_from(element: &Element) -> Result<Option<String>, XMLParseError> {
+    match element.child("UTCPoint") {
+        Some(utc_point) => Ok(Some(utc_point.required("utcPoint")?)),
+        None => Ok(None),
+    }
+}
+
+/// Reads the SCTE-35 signal from either a `BinaryData` or a `SpliceInfoSection` child.
+fn try_signal_from(element: &Element) -> Result<Option<SpliceInfoSection>, XMLParseError> {
+    if let Some(binary_data) = element.child("BinaryData") {
+        let data = BASE64_STANDARD
+            .decode(binary_data.text.trim())
+            .map_err(|_| binary_data.invalid_content())?;
+        return Ok(Some(SpliceInfoSection::try_from_bytes(&data)?));
+    }
+    match element.child("SpliceInfoSection") {
+        Some(splice_info_section) => Ok(Some(try_splice_info_section_from(splice_info_section)?)),
+        None => Ok(None),
+    }
+}
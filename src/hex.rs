@@ -1,21 +1,67 @@
-use std::{
-    fmt::{self, Write},
-    num::ParseIntError,
-};
-
-// Solution is based on following SO answer: https://stackoverflow.com/a/52992629/7039100
+//! Hex encoding and decoding of bytes, as used for the hex representation of a
+//! `SpliceInfoSection` (see `SpliceInfoSection::try_from_hex_string` and
+//! `SpliceInfoSection::try_to_hex_string`), for when the bytes of a message need to be logged or
+//! read back in the same form.
+//! ```
+//! use scte35::hex::{decode_hex, encode_hex, DecodeHexError};
+//!
+//! assert_eq!(Ok(vec![0xFC, 0x30, 0x11]), decode_hex("FC3011"));
+//! assert_eq!("fc3011", encode_hex(&[0xFC, 0x30, 0x11]));
+//! assert_eq!(
+//!     Err(DecodeHexError::InvalidCharacter {
+//!         character: 'G',
+//!         offset: 3,
+//!     }),
+//!     decode_hex("FC3G11")
+//! );
+//! ```
+use std::fmt::{self, Write};
 
+/// Decodes the hex encoded string (of upper or lowercase digits, with no prefix or separators)
+/// into bytes.
 pub fn decode_hex(s: &str) -> Result<Vec<u8>, DecodeHexError> {
-    if s.len() % 2 != 0 {
-        Err(DecodeHexError::OddLength)
-    } else {
-        (0..s.len())
-            .step_by(2)
-            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| e.into()))
-            .collect()
+    let mut buf = Vec::new();
+    decode_hex_into(s, &mut buf)?;
+    Ok(buf)
+}
+
+/// Decodes the hex encoded string into `buf` (which is cleared first), so that its allocation can
+/// be reused. When decoding fails the contents of `buf` are unspecified.
+pub fn decode_hex_into(s: &str, buf: &mut Vec<u8>) -> Result<(), DecodeHexError> {
+    buf.clear();
+    buf.reserve(s.len() / 2);
+    let mut high = None;
+    for (offset, byte) in s.bytes().enumerate() {
+        let digit = hex_digit(byte).ok_or_else(|| invalid_character(s, offset))?;
+        match high.take() {
+            Some(high) => buf.push(high << 4 | digit),
+            None => high = Some(digit),
+        }
+    }
+    if high.is_some() {
+        return Err(DecodeHexError::OddLength);
     }
+    Ok(())
 }
 
+// Decodes a string that may be prefixed with `0x` (or `0X`), where the offset of any invalid
+// character is from the start of the string (i.e. including the prefix).
+pub(crate) fn decode_prefixed_hex_into(s: &str, buf: &mut Vec<u8>) -> Result<(), DecodeHexError> {
+    let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) else {
+        return decode_hex_into(s, buf);
+    };
+    decode_hex_into(digits, buf).map_err(|error| match error {
+        DecodeHexError::InvalidCharacter { character, offset } => {
+            DecodeHexError::InvalidCharacter {
+                character,
+                offset: offset + 2,
+            }
+        }
+        DecodeHexError::OddLength => DecodeHexError::OddLength,
+    })
+}
+
+/// Encodes the bytes as a string of lowercase hex digits.
 pub fn encode_hex(bytes: &[u8]) -> String {
     let mut s = String::with_capacity(bytes.len() * 2);
     for &b in bytes {
@@ -24,23 +70,55 @@ pub fn encode_hex(bytes: &[u8]) -> String {
     s
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DecodeHexError {
-    OddLength,
-    ParseInt(ParseIntError),
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
 }
 
-impl From<ParseIntError> for DecodeHexError {
-    fn from(e: ParseIntError) -> Self {
-        DecodeHexError::ParseInt(e)
+// A byte that is part of a multi-byte character is reported as that character, at the offset at
+// which the character starts.
+fn invalid_character(s: &str, offset: usize) -> DecodeHexError {
+    let offset = (0..=offset)
+        .rev()
+        .find(|index| s.is_char_boundary(*index))
+        .unwrap_or(0);
+    DecodeHexError::InvalidCharacter {
+        character: s[offset..]
+            .chars()
+            .next()
+            .unwrap_or(char::REPLACEMENT_CHARACTER),
+        offset,
     }
 }
 
+/// An error when decoding a hex encoded string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecodeHexError {
+    /// The string has an odd number of hex digits, and so does not decode to a whole number of
+    /// bytes.
+    OddLength,
+    /// The string contains a character that is not a hex digit.
+    InvalidCharacter {
+        character: char,
+        /// The byte offset of the character within the string.
+        offset: usize,
+    },
+}
+
 impl fmt::Display for DecodeHexError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            DecodeHexError::OddLength => "input string has an odd number of bytes".fmt(f),
-            DecodeHexError::ParseInt(e) => e.fmt(f),
+            DecodeHexError::OddLength => "The hex string has an odd number of digits.".fmt(f),
+            DecodeHexError::InvalidCharacter { character, offset } => write!(
+                f,
+                "The hex string has an invalid character {:?} at offset {}.",
+                character, offset
+            ),
         }
     }
 }
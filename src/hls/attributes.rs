@@ -0,0 +1,37 @@
+use crate::error::HLSParseError;
+
+/// Splits an attribute list (see [RFC 8216] Section 4.2) into its names and (unparsed) values.
+/// Quoted-string values retain their quotes.
+///
+/// [RFC 8216]: https://www.rfc-editor.org/rfc/rfc8216
+pub fn parse_attribute_list(attribute_list: &str) -> Result<Vec<(String, String)>, HLSParseError> {
+    let invalid = || HLSParseError::InvalidAttributeList(String::from(attribute_list));
+    let mut attributes = vec![];
+    let mut remaining = attribute_list.trim();
+    while !remaining.is_empty() {
+        let (name, rest) = remaining.split_once('=').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        let value_length = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.find('"').ok_or_else(invalid)? + 2
+        } else {
+            rest.find(',').unwrap_or(rest.len())
+        };
+        let (value, rest) = rest.split_at(value_length);
+        attributes.push((String::from(name), String::from(value.trim())));
+        let rest = rest.trim_start();
+        remaining = match rest.strip_prefix(',') {
+            Some(rest) => rest.trim_start(),
+            None if rest.is_empty() => rest,
+            None => return Err(invalid()),
+        };
+    }
+    Ok(attributes)
+}
+
+/// Removes the quotes from a quoted-string value.
+pub fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"')?.strip_suffix('"')
+}
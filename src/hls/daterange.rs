@@ -0,0 +1,275 @@
+use super::attributes::{parse_attribute_list, unquote};
+use crate::{
+    error::{EncodeError, HLSParseError},
+    splice_command::SpliceCommand,
+    splice_info_section::SpliceInfoSection,
+};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+const TAG: &str = "#EXT-X-DATERANGE:";
+
+/// An `EXT-X-DATERANGE` tag.
+///
+/// The `Display` implementation writes the tag (including the `#EXT-X-DATERANGE:` prefix).
+/// ```
+/// use scte35::splice_info_section::SpliceInfoSection;
+///
+/// let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+/// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+/// let daterange = splice_info_section.to_daterange("2014-03-05T11:15:00Z").unwrap();
+/// assert_eq!(
+///     format!(
+///         "#EXT-X-DATERANGE:ID=\"1207959695\",START-DATE=\"2014-03-05T11:15:00Z\",PLANNED-DURATION=60.294,SCTE35-OUT={}",
+///         hex_string
+///     ),
+///     daterange.to_string()
+/// );
+/// ```
+///
+/// The tag can also be parsed back, decoding any `SCTE35-*` attributes into `SpliceInfoSection`s.
+/// ```
+/// use scte35::{hls::DateRange, splice_command::SpliceCommand};
+///
+/// let tag = "#EXT-X-DATERANGE:ID=\"1207959695\",START-DATE=\"2014-03-05T11:15:00Z\",PLANNED-DURATION=60.294,SCTE35-OUT=0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+/// let daterange = DateRange::try_from_tag(tag).unwrap();
+/// assert_eq!("1207959695", daterange.id);
+/// assert_eq!(Some(60.294), daterange.planned_duration);
+/// assert!(matches!(
+///     daterange.scte35_out.unwrap().splice_command,
+///     SpliceCommand::SpliceInsert(_)
+/// ));
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateRange {
+    /// A quoted-string that uniquely identifies the date range in the playlist.
+    pub id: String,
+    /// A client-defined quoted-string that specifies some set of attributes and their associated
+    /// value semantics.
+    pub class: Option<String>,
+    /// A quoted-string containing the ISO-8601 date at which the date range begins.
+    pub start_date: String,
+    /// A quoted-string containing the ISO-8601 date at which the date range ends.
+    pub end_date: Option<String>,
+    /// The duration of the date range in seconds.
+    pub duration: Option<f64>,
+    /// The expected duration of the date range in seconds, used when the actual duration is not
+    /// yet known.
+    pub planned_duration: Option<f64>,
+    /// Carries a `SpliceInfoSection` that is not a splice out or splice in (e.g. a
+    /// `time_signal`).
+    pub scte35_cmd: Option<SpliceInfoSection>,
+    /// Carries a "splice out" `SpliceInfoSection` (`out_of_network_indicator` set to `true`).
+    pub scte35_out: Option<SpliceInfoSection>,
+    /// Carries a "splice in" `SpliceInfoSection` (`out_of_network_indicator` set to `false`).
+    pub scte35_in: Option<SpliceInfoSection>,
+    /// Indicates that the end of the range is equal to the `START-DATE` of the following range
+    /// that has the same `CLASS`.
+    pub end_on_next: bool,
+    /// Client-defined attributes (names prefixed with `X-`) and their (unparsed) values.
+    pub client_attributes: Vec<(String, String)>,
+}
+
+impl DateRange {
+    pub fn new(id: &str, start_date: &str) -> Self {
+        Self {
+            id: String::from(id),
+            class: None,
+            start_date: String::from(start_date),
+            end_date: None,
+            duration: None,
+            planned_duration: None,
+            scte35_cmd: None,
+            scte35_out: None,
+            scte35_in: None,
+            end_on_next: false,
+            client_attributes: vec![],
+        }
+    }
+
+    /// Parses an `EXT-X-DATERANGE` tag (including the `#EXT-X-DATERANGE:` prefix).
+    ///
+    /// The `ID` and `START-DATE` attributes are required. Client-defined attributes are retained
+    /// in `client_attributes` and any other unrecognised attributes are ignored.
+    pub fn try_from_tag(tag: &str) -> Result<Self, HLSParseError> {
+        let attribute_list = tag
+            .trim()
+            .strip_prefix(TAG)
+            .ok_or_else(|| HLSParseError::UnexpectedTag(String::from(tag)))?;
+        let mut id = None;
+        let mut start_date = None;
+        let mut daterange = Self::new("", "");
+        for (name, value) in parse_attribute_list(attribute_list)? {
+            match name.as_str() {
+                "ID" => id = Some(try_quoted_string(&name, &value)?),
+                "CLASS" => daterange.class = Some(try_quoted_string(&name, &value)?),
+                "START-DATE" => start_date = Some(try_quoted_string(&name, &value)?),
+                "END-DATE" => daterange.end_date = Some(try_quoted_string(&name, &value)?),
+                "DURATION" => daterange.duration = Some(try_seconds(&name, &value)?),
+                "PLANNED-DURATION" => {
+                    daterange.planned_duration = Some(try_seconds(&name, &value)?)
+                }
+                "SCTE35-CMD" => daterange.scte35_cmd = Some(try_splice_info_section(&value)?),
+                "SCTE35-OUT" => daterange.scte35_out = Some(try_splice_info_section(&value)?),
+                "SCTE35-IN" => daterange.scte35_in = Some(try_splice_info_section(&value)?),
+                "END-ON-NEXT" if value == "YES" => daterange.end_on_next = true,
+                "END-ON-NEXT" => {
+                    return Err(HLSParseError::InvalidAttribute {
+                        attribute: name,
+                        value,
+                    })
+                }
+                _ if name.starts_with("X-") => daterange.client_attributes.push((name, value)),
+                _ => (),
+            }
+        }
+        daterange.id = id.ok_or(HLSParseError::MissingAttribute("ID"))?;
+        daterange.start_date = start_date.ok_or(HLSParseError::MissingAttribute("START-DATE"))?;
+        Ok(daterange)
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = HLSParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_tag(s)
+    }
+}
+
+fn try_quoted_string(name: &str, value: &str) -> Result<String, HLSParseError> {
+    unquote(value)
+        .map(String::from)
+        .ok_or_else(|| HLSParseError::InvalidAttribute {
+            attribute: String::from(name),
+            value: String::from(value),
+        })
+}
+
+fn try_seconds(name: &str, value: &str) -> Result<f64, HLSParseError> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 => Ok(seconds),
+        _ => Err(HLSParseError::InvalidAttribute {
+            attribute: String::from(name),
+            value: String::from(value),
+        }),
+    }
+}
+
+fn try_splice_info_section(value: &str) -> Result<SpliceInfoSection, HLSParseError> {
+    Ok(SpliceInfoSection::try_from_hex_string(value)?)
+}
+
+impl Display for DateRange {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "#EXT-X-DATERANGE:ID=\"{}\"", self.id)?;
+        if let Some(class) = &self.class {
+            write!(f, ",CLASS=\"{}\"", class)?;
+        }
+        write!(f, ",START-DATE=\"{}\"", self.start_date)?;
+        if let Some(end_date) = &self.end_date {
+            write!(f, ",END-DATE=\"{}\"", end_date)?;
+        }
+        if let Some(duration) = self.duration {
+            write!(f, ",DURATION={}", format_seconds(duration))?;
+        }
+        if let Some(planned_duration) = self.planned_duration {
+            write!(f, ",PLANNED-DURATION={}", format_seconds(planned_duration))?;
+        }
+        for (name, value) in &self.client_attributes {
+            write!(f, ",{}={}", name, value)?;
+        }
+        for (name, splice_info_section) in [
+            ("SCTE35-CMD", &self.scte35_cmd),
+            ("SCTE35-OUT", &self.scte35_out),
+            ("SCTE35-IN", &self.scte35_in),
+        ] {
+            if let Some(splice_info_section) = splice_info_section {
+                let hex_string = splice_info_section
+                    .try_to_hex_string()
+                    .map_err(|_| std::fmt::Error)?;
+                write!(f, ",{}={}", name, hex_string)?;
+            }
+        }
+        if self.end_on_next {
+            write!(f, ",END-ON-NEXT=YES")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the seconds with millisecond precision, omitting trailing zeros.
+pub(super) fn format_seconds(seconds: f64) -> String {
+    let formatted = format!("{:.3}", seconds);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+impl SpliceInfoSection {
+    /// Creates an `EXT-X-DATERANGE` tag for the `SpliceInfoSection`, starting at the provided
+    /// ISO-8601 `start_date`.
+    ///
+    /// A `splice_insert` is carried in `SCTE35-OUT` or `SCTE35-IN` (depending on the
+    /// `out_of_network_indicator`) and all other commands are carried in `SCTE35-CMD`. The `ID` is
+    /// derived from the `event_id` of the `splice_insert` (or of the first segmentation
+    /// descriptor), so that the tags for the out and in of the same event share the same `ID`,
+    /// and the `PLANNED-DURATION` is derived from the `break_duration` (or the first
+    /// `segmentation_duration`).
+    pub fn to_daterange(&self, start_date: &str) -> Result<DateRange, EncodeError> {
+        // Validates that the message can be encoded, so that the tag can be written.
+        let hex_string = self.try_to_hex_string()?;
+        let segmentation_descriptors = self.segmentation_descriptors();
+        let (attribute, event_id, duration) = match &self.splice_command {
+            SpliceCommand::SpliceInsert(splice_insert) => {
+                let scheduled_event = splice_insert.scheduled_event.as_ref();
+                let attribute = match scheduled_event {
+                    Some(event) if event.out_of_network_indicator => Attribute::Out,
+                    Some(_) => Attribute::In,
+                    None => Attribute::Cmd,
+                };
+                let duration = scheduled_event
+                    .and_then(|event| event.break_duration.as_ref())
+                    .map(|break_duration| break_duration.duration.ticks());
+                (attribute, Some(splice_insert.event_id), duration)
+            }
+            _ => (
+                Attribute::Cmd,
+                segmentation_descriptors
+                    .clone()
+                    .next()
+                    .map(|descriptor| descriptor.event_id),
+                segmentation_descriptors.clone().find_map(|descriptor| {
+                    descriptor
+                        .scheduled_event
+                        .as_ref()
+                        .and_then(|event| event.segmentation_duration)
+                }),
+            ),
+        };
+        let id = match event_id {
+            Some(event_id) => event_id.to_string(),
+            None => hex_string,
+        };
+        let mut daterange = DateRange {
+            planned_duration: duration.map(|duration| duration as f64 / 90000.0),
+            ..DateRange::new(&id, start_date)
+        };
+        match attribute {
+            Attribute::Cmd => daterange.scte35_cmd = Some(self.clone()),
+            Attribute::Out => daterange.scte35_out = Some(self.clone()),
+            Attribute::In => daterange.scte35_in = Some(self.clone()),
+        }
+        Ok(daterange)
+    }
+}
+
+enum Attribute {
+    Cmd,
+    Out,
+    In,
+}
@@ -0,0 +1,90 @@
+use super::daterange::{format_seconds, DateRange};
+use crate::{error::HLSInterstitialError, splice_info_section::SpliceInfoSection};
+
+/// The `CLASS` of an `EXT-X-DATERANGE` tag that schedules an HLS interstitial.
+pub const INTERSTITIAL_CLASS: &str = "com.apple.hls.interstitial";
+
+impl DateRange {
+    /// Creates an interstitial `EXT-X-DATERANGE` tag (`CLASS="com.apple.hls.interstitial"`) that
+    /// replaces the content between a segmentation start message and its corresponding end
+    /// message with the interstitial assets listed at `asset_list_uri`.
+    ///
+    /// The `ID` is derived from the `event_id` of the start `SegmentationDescriptor`, and the end
+    /// message is matched to it by that same `event_id`. The `DURATION` and `X-RESUME-OFFSET` are
+    /// computed from the difference in the PTS of the two messages, falling back to the
+    /// `segmentation_duration` of the start message, such that the primary content resumes after
+    /// the replaced segment. The messages themselves are carried in `SCTE35-OUT` and `SCTE35-IN`.
+    /// ```
+    /// use scte35::{cue::AdBreak, hls::DateRange};
+    ///
+    /// let (cue_out, cue_in) = AdBreak::new(1207959694, 1924989008, 2700000).build().unwrap();
+    /// let daterange = DateRange::try_interstitial(
+    ///     "2014-03-05T11:15:00Z",
+    ///     &cue_out,
+    ///     &cue_in,
+    ///     "https://example.com/asset-list.json",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Some(30.0), daterange.duration);
+    /// assert!(daterange.to_string().contains(
+    ///     r#"X-ASSET-LIST="https://example.com/asset-list.json",X-RESUME-OFFSET=30"#
+    /// ));
+    /// ```
+    pub fn try_interstitial(
+        start_date: &str,
+        start: &SpliceInfoSection,
+        end: &SpliceInfoSection,
+        asset_list_uri: &str,
+    ) -> Result<Self, HLSInterstitialError> {
+        let start_descriptor = start
+            .segmentation_descriptors()
+            .next()
+            .ok_or(HLSInterstitialError::MissingSegmentationDescriptor)?;
+        let end_descriptor = end
+            .segmentation_descriptors()
+            .find(|descriptor| descriptor.event_id == start_descriptor.event_id)
+            .ok_or(HLSInterstitialError::MissingSegmentationDescriptor)?;
+        let (Some(start_event), Some(end_event)) = (
+            start_descriptor.scheduled_event.as_ref(),
+            end_descriptor.scheduled_event.as_ref(),
+        ) else {
+            return Err(HLSInterstitialError::MissingSegmentationDescriptor);
+        };
+        if start_event
+            .segmentation_type_id
+            .corresponding_end()
+            .as_ref()
+            != Some(&end_event.segmentation_type_id)
+        {
+            return Err(HLSInterstitialError::MismatchedSegmentationTypes {
+                start: start_event.segmentation_type_id.clone(),
+                end: end_event.segmentation_type_id.clone(),
+            });
+        }
+        let duration = match (start.adjusted_splice_time(), end.adjusted_splice_time()) {
+            (Some(start_pts_time), Some(end_pts_time)) => {
+                Some(start_pts_time.distance_to(end_pts_time))
+            }
+            _ => start_event.segmentation_duration,
+        }
+        .ok_or(HLSInterstitialError::MissingDuration)?;
+        let duration = duration as f64 / 90000.0;
+        // Validates that the messages can be encoded, so that the tag can be written.
+        start.try_to_hex_string()?;
+        end.try_to_hex_string()?;
+        Ok(DateRange {
+            class: Some(String::from(INTERSTITIAL_CLASS)),
+            duration: Some(duration),
+            scte35_out: Some(start.clone()),
+            scte35_in: Some(end.clone()),
+            client_attributes: vec![
+                (
+                    String::from("X-ASSET-LIST"),
+                    format!("\"{}\"", asset_list_uri),
+                ),
+                (String::from("X-RESUME-OFFSET"), format_seconds(duration)),
+            ],
+            ..DateRange::new(&start_descriptor.event_id.to_string(), start_date)
+        })
+    }
+}
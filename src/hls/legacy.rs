@@ -0,0 +1,205 @@
+use super::attributes::{parse_attribute_list, unquote};
+use crate::{
+    error::{EncodeError, HLSParseError},
+    splice_command::splice_insert::SpliceInsert,
+    splice_info_section::SpliceInfoSection,
+    time::{BreakDuration, Pts90k},
+};
+use base64::prelude::*;
+use std::str::FromStr;
+
+/// One of the legacy (pre `EXT-X-DATERANGE`) cue tags that are still commonly produced by live
+/// origins.
+/// ```
+/// use scte35::{hls::LegacyCue, splice_command::SpliceCommand, time::Pts90k};
+///
+/// let cue = LegacyCue::try_from_tag(
+///     "#EXT-OATCLS-SCTE35:/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==",
+/// )
+/// .unwrap();
+/// match cue {
+///     LegacyCue::OATCLSSCTE35(splice_info_section) => assert!(matches!(
+///         splice_info_section.splice_command,
+///         SpliceCommand::TimeSignal(_)
+///     )),
+///     _ => panic!("should be EXT-OATCLS-SCTE35"),
+/// }
+///
+/// let cue = LegacyCue::try_from_tag("#EXT-X-CUE-OUT:DURATION=30").unwrap();
+/// match cue {
+///     LegacyCue::CueOut(cue_out) => {
+///         assert_eq!(Some(30.0), cue_out.duration);
+///         assert_eq!(Some(Pts90k(2700000)), cue_out.break_duration().map(|b| b.duration));
+///     }
+///     _ => panic!("should be EXT-X-CUE-OUT"),
+/// }
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LegacyCue {
+    /// `#EXT-OATCLS-SCTE35:<base64>`, which carries the binary `SpliceInfoSection`.
+    OATCLSSCTE35(SpliceInfoSection),
+    /// `#EXT-X-CUE-OUT`, which marks the start of a break.
+    CueOut(CueOut),
+    /// `#EXT-X-CUE-OUT-CONT`, which marks a segment within a break.
+    CueOutCont(CueOutCont),
+    /// `#EXT-X-CUE-IN`, which marks the end of a break.
+    CueIn,
+}
+
+/// The start of a break, as signaled by `#EXT-X-CUE-OUT:<duration>` or
+/// `#EXT-X-CUE-OUT:DURATION=<duration>`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CueOut {
+    /// The duration of the break in seconds (if provided).
+    pub duration: Option<f64>,
+}
+
+/// A segment within a break, as signaled by
+/// `#EXT-X-CUE-OUT-CONT:ElapsedTime=<elapsed>,Duration=<duration>,SCTE35=<base64>` or
+/// `#EXT-X-CUE-OUT-CONT:<elapsed>/<duration>`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CueOutCont {
+    /// The time in seconds that has elapsed since the start of the break.
+    pub elapsed_time: Option<f64>,
+    /// The duration of the break in seconds.
+    pub duration: Option<f64>,
+    /// The `SpliceInfoSection` that started the break (if provided).
+    pub splice_info_section: Option<SpliceInfoSection>,
+}
+
+const OATCLS_SCTE35: &str = "EXT-OATCLS-SCTE35";
+const CUE_OUT: &str = "EXT-X-CUE-OUT";
+const CUE_OUT_CONT: &str = "EXT-X-CUE-OUT-CONT";
+const CUE_IN: &str = "EXT-X-CUE-IN";
+
+impl LegacyCue {
+    /// Parses one of `#EXT-OATCLS-SCTE35`, `#EXT-X-CUE-OUT`, `#EXT-X-CUE-OUT-CONT` or
+    /// `#EXT-X-CUE-IN`.
+    pub fn try_from_tag(tag: &str) -> Result<Self, HLSParseError> {
+        let (name, value) = match tag.trim().strip_prefix('#') {
+            Some(line) => line.split_once(':').unwrap_or((line, "")),
+            None => return Err(HLSParseError::UnexpectedTag(String::from(tag))),
+        };
+        match name {
+            OATCLS_SCTE35 => Ok(LegacyCue::OATCLSSCTE35(try_splice_info_section(
+                OATCLS_SCTE35,
+                value,
+            )?)),
+            CUE_OUT => Ok(LegacyCue::CueOut(CueOut::try_from_value(value)?)),
+            CUE_OUT_CONT => Ok(LegacyCue::CueOutCont(CueOutCont::try_from_value(value)?)),
+            CUE_IN => Ok(LegacyCue::CueIn),
+            _ => Err(HLSParseError::UnexpectedTag(String::from(tag))),
+        }
+    }
+}
+
+impl FromStr for LegacyCue {
+    type Err = HLSParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_tag(s)
+    }
+}
+
+impl CueOut {
+    /// The duration of the break in terms of the 90 kHz clock, with `auto_return` set to `true`,
+    /// as there is no guarantee of a splice in being signaled.
+    pub fn break_duration(&self) -> Option<BreakDuration> {
+        self.duration.map(|duration| BreakDuration {
+            auto_return: true,
+            duration: Pts90k((duration * 90000.0).round() as u64),
+        })
+    }
+
+    /// Synthesizes an immediate splice out `SpliceInsert` for the break, for use when the origin
+    /// has only signaled the duration.
+    pub fn to_splice_insert(&self, event_id: u32) -> Result<SpliceInsert, EncodeError> {
+        let builder = SpliceInsert::builder(event_id).cue_out().immediate();
+        match self.break_duration() {
+            Some(break_duration) => builder
+                .break_duration(break_duration.duration, break_duration.auto_return)
+                .build(),
+            None => builder.build(),
+        }
+    }
+
+    fn try_from_value(value: &str) -> Result<Self, HLSParseError> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(Self { duration: None });
+        }
+        if !value.contains('=') {
+            return Ok(Self {
+                duration: Some(try_seconds(CUE_OUT, value)?),
+            });
+        }
+        let duration = parse_attribute_list(value)?
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("DURATION"))
+            .map(|(_, value)| try_seconds(CUE_OUT, unquote(&value).unwrap_or(&value)))
+            .transpose()?;
+        Ok(Self { duration })
+    }
+}
+
+impl CueOutCont {
+    fn try_from_value(value: &str) -> Result<Self, HLSParseError> {
+        let value = value.trim();
+        if let Some((elapsed_time, duration)) =
+            value.split_once('/').filter(|_| !value.contains('='))
+        {
+            return Ok(Self {
+                elapsed_time: Some(try_seconds(CUE_OUT_CONT, elapsed_time)?),
+                duration: Some(try_seconds(CUE_OUT_CONT, duration)?),
+                splice_info_section: None,
+            });
+        }
+        let mut cue_out_cont = Self {
+            elapsed_time: None,
+            duration: None,
+            splice_info_section: None,
+        };
+        for (name, value) in parse_attribute_list(value)? {
+            let value = unquote(&value).unwrap_or(&value);
+            match name.to_ascii_uppercase().as_str() {
+                "ELAPSEDTIME" => {
+                    cue_out_cont.elapsed_time = Some(try_seconds(CUE_OUT_CONT, value)?)
+                }
+                "DURATION" => cue_out_cont.duration = Some(try_seconds(CUE_OUT_CONT, value)?),
+                "SCTE35" => {
+                    cue_out_cont.splice_info_section =
+                        Some(try_splice_info_section(CUE_OUT_CONT, value)?)
+                }
+                _ => (),
+            }
+        }
+        Ok(cue_out_cont)
+    }
+}
+
+fn try_seconds(tag: &'static str, value: &str) -> Result<f64, HLSParseError> {
+    match value.trim().parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 => Ok(seconds),
+        _ => Err(HLSParseError::InvalidTagValue {
+            tag,
+            value: String::from(value),
+        }),
+    }
+}
+
+fn try_splice_info_section(
+    tag: &'static str,
+    value: &str,
+) -> Result<SpliceInfoSection, HLSParseError> {
+    let data =
+        BASE64_STANDARD
+            .decode(value.trim())
+            .map_err(|_| HLSParseError::InvalidTagValue {
+                tag,
+                value: String::from(value),
+            })?;
+    Ok(SpliceInfoSection::try_from_bytes(&data)?)
+}
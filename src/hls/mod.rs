@@ -0,0 +1,26 @@
+//! Support for signaling SCTE-35 messages in HLS playlists.
+//!
+//! The `EXT-X-DATERANGE` tag (see [RFC 8216] Section 4.3.2.7.1) carries the binary
+//! `SpliceInfoSection` as a hexadecimal-sequence in one of the `SCTE35-OUT`, `SCTE35-IN` or
+//! `SCTE35-CMD` attributes. Tags can be created from a `SpliceInfoSection` and parsed back into
+//! a `DateRange`, with the `SCTE35-*` attributes decoded.
+//!
+//! The legacy `#EXT-OATCLS-SCTE35`, `#EXT-X-CUE-OUT`, `#EXT-X-CUE-OUT-CONT` and `#EXT-X-CUE-IN`
+//! tags, which are still produced by many live origins, can be parsed into a `LegacyCue` (when
+//! the `base64` feature is enabled, as these tags carry the section as base64).
+//!
+//! For server-guided ad insertion, a segmentation start/end pair can be mapped to an HLS
+//! interstitial `EXT-X-DATERANGE` tag (see `DateRange::try_interstitial`).
+//!
+//! [RFC 8216]: https://www.rfc-editor.org/rfc/rfc8216
+
+mod attributes;
+mod daterange;
+mod interstitial;
+#[cfg(feature = "base64")]
+mod legacy;
+
+pub use daterange::DateRange;
+pub use interstitial::INTERSTITIAL_CLASS;
+#[cfg(feature = "base64")]
+pub use legacy::{CueOut, CueOutCont, LegacyCue};
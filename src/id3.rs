@@ -0,0 +1,172 @@
+//! Extraction (and insertion) of SCTE-35 messages from ID3 `PRIV` frames, as used for timed
+//! metadata in HLS transport stream and packed audio segments, where the binary
+//! `SpliceInfoSection` is carried as the private data of a `PRIV` frame with the
+//! `urn:scte:scte35:2013:bin` owner identifier.
+//!
+//! ```text
+//! <Header for 'Private frame', ID: "PRIV">
+//! Owner identifier      <text string> $00
+//! The private data      <binary data>
+//! ```
+//!
+//! Tags are read as ID3v2.3 or ID3v2.4, and are written as ID3v2.4.
+//! ```
+//! use scte35::{id3, splice_info_section::SpliceInfoSection};
+//!
+//! let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let tag = splice_info_section.try_to_id3_tag().unwrap();
+//! assert_eq!(b"ID3", &tag[..3]);
+//!
+//! let frames = id3::find_priv_frames(&tag).unwrap();
+//! assert_eq!(id3::SCTE35_OWNER_IDENTIFIER, frames[0].owner_identifier);
+//! assert_eq!(splice_info_section, SpliceInfoSection::try_from_id3_tag(&tag).unwrap());
+//! ```
+
+use crate::{
+    error::{EncodeError, ID3Error},
+    splice_info_section::SpliceInfoSection,
+};
+
+/// The owner identifier of `PRIV` frames carrying binary `SpliceInfoSection`s.
+pub const SCTE35_OWNER_IDENTIFIER: &str = "urn:scte:scte35:2013:bin";
+
+const TAG_IDENTIFIER: &[u8; 3] = b"ID3";
+const PRIV_FRAME_ID: &[u8; 4] = b"PRIV";
+const HEADER_SIZE: usize = 10;
+const FRAME_HEADER_SIZE: usize = 10;
+const EXTENDED_HEADER_FLAG: u8 = 0x40;
+
+/// A `PRIV` (private) frame from an ID3v2 tag.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrivFrame {
+    /// Identifies the organisation responsible for the frame (`urn:scte:scte35:2013:bin` for
+    /// SCTE-35).
+    pub owner_identifier: String,
+    /// The binary data of the frame (the binary `SpliceInfoSection` for SCTE-35).
+    pub private_data: Vec<u8>,
+}
+
+impl PrivFrame {
+    /// Whether the `owner_identifier` indicates a binary SCTE-35 message.
+    pub fn is_scte35(&self) -> bool {
+        self.owner_identifier == SCTE35_OWNER_IDENTIFIER
+    }
+
+    /// Serializes the frame (including the ID3v2.4 frame header).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = self.owner_identifier.len() + 1 + self.private_data.len();
+        let mut bytes = Vec::with_capacity(FRAME_HEADER_SIZE + size);
+        bytes.extend_from_slice(PRIV_FRAME_ID);
+        bytes.extend_from_slice(&synchsafe_bytes(size as u32));
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(self.owner_identifier.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&self.private_data);
+        bytes
+    }
+}
+
+/// Finds the `PRIV` frames within an ID3v2 tag, starting at the tag header. Frames of other types
+/// are skipped.
+pub fn find_priv_frames(tag: &[u8]) -> Result<Vec<PrivFrame>, ID3Error> {
+    if tag.len() < HEADER_SIZE || &tag[..3] != TAG_IDENTIFIER {
+        return Err(ID3Error::InvalidHeader);
+    }
+    let version = tag[3];
+    if version != 3 && version != 4 {
+        return Err(ID3Error::UnsupportedVersion(version));
+    }
+    let flags = tag[5];
+    let size = synchsafe_integer(&tag[6..10]).ok_or(ID3Error::InvalidHeader)? as usize;
+    let mut frames = tag
+        .get(HEADER_SIZE..HEADER_SIZE + size)
+        .ok_or(ID3Error::UnexpectedEndOfData)?;
+    if flags & EXTENDED_HEADER_FLAG != 0 {
+        let size_bytes = frames.get(..4).ok_or(ID3Error::UnexpectedEndOfData)?;
+        // The extended header size includes itself in ID3v2.4 but not in ID3v2.3.
+        let extended_header_size = if version == 4 {
+            synchsafe_integer(size_bytes).ok_or(ID3Error::InvalidHeader)? as usize
+        } else {
+            u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]])
+                as usize
+                + 4
+        };
+        frames = frames
+            .get(extended_header_size..)
+            .ok_or(ID3Error::UnexpectedEndOfData)?;
+    }
+    let mut priv_frames = vec![];
+    // Anything after the last frame is padding (which is made up of zeros).
+    while frames.len() >= FRAME_HEADER_SIZE && frames[0] != 0 {
+        let size_bytes = &frames[4..8];
+        let size = if version == 4 {
+            synchsafe_integer(size_bytes).ok_or(ID3Error::InvalidHeader)?
+        } else {
+            u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]])
+        } as usize;
+        let body = frames
+            .get(FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + size)
+            .ok_or(ID3Error::UnexpectedEndOfData)?;
+        if &frames[..4] == PRIV_FRAME_ID {
+            let length = body
+                .iter()
+                .position(|byte| *byte == 0)
+                .ok_or(ID3Error::UnexpectedEndOfData)?;
+            priv_frames.push(PrivFrame {
+                owner_identifier: String::from_utf8_lossy(&body[..length]).into_owned(),
+                private_data: body[length + 1..].to_vec(),
+            });
+        }
+        frames = &frames[FRAME_HEADER_SIZE + size..];
+    }
+    Ok(priv_frames)
+}
+
+impl SpliceInfoSection {
+    /// Decodes the first `PRIV` frame with the SCTE-35 owner identifier within an ID3v2 tag.
+    pub fn try_from_id3_tag(tag: &[u8]) -> Result<Self, ID3Error> {
+        let frame = find_priv_frames(tag)?
+            .into_iter()
+            .find(PrivFrame::is_scte35)
+            .ok_or(ID3Error::MissingSCTE35Frame)?;
+        Ok(Self::try_from_bytes(&frame.private_data)?)
+    }
+
+    /// Creates a `PrivFrame` carrying the `SpliceInfoSection` with the SCTE-35 owner identifier.
+    pub fn try_to_id3_priv_frame(&self) -> Result<PrivFrame, EncodeError> {
+        Ok(PrivFrame {
+            owner_identifier: String::from(SCTE35_OWNER_IDENTIFIER),
+            private_data: self.try_to_bytes()?,
+        })
+    }
+
+    /// Creates a serialized ID3v2.4 tag containing a single `PRIV` frame carrying the
+    /// `SpliceInfoSection` (see `try_to_id3_priv_frame`).
+    pub fn try_to_id3_tag(&self) -> Result<Vec<u8>, EncodeError> {
+        let frame = self.try_to_id3_priv_frame()?.to_bytes();
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + frame.len());
+        bytes.extend_from_slice(TAG_IDENTIFIER);
+        bytes.extend_from_slice(&[4, 0, 0]);
+        bytes.extend_from_slice(&synchsafe_bytes(frame.len() as u32));
+        bytes.extend_from_slice(&frame);
+        Ok(bytes)
+    }
+}
+
+/// Reads a 28-bit integer stored across 4 bytes with the most significant bit of each byte unset.
+fn synchsafe_integer(bytes: &[u8]) -> Option<u32> {
+    bytes.iter().try_fold(0, |value, byte| {
+        (*byte & 0x80 == 0).then_some(value << 7 | *byte as u32)
+    })
+}
+
+fn synchsafe_bytes(value: u32) -> [u8; 4] {
+    [
+        (value >> 21) as u8 & 0x7F,
+        (value >> 14) as u8 & 0x7F,
+        (value >> 7) as u8 & 0x7F,
+        value as u8 & 0x7F,
+    ]
+}
@@ -0,0 +1,187 @@
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// An International Standard Audiovisual Number (see [ISO 15706] and [ISO 15706-2]), which is made
+/// up of a 48-bit root identifying the work, a 16-bit episode (or part) number and, for a
+/// versioned ISAN (formerly known as V-ISAN), a 32-bit version.
+///
+/// The hyphenated representation places a check character after the episode, and another after
+/// the version when one is present (e.g. `0000-0000-3A8D-0000-Z-0000-0000-6`).
+/// ```
+/// use scte35::isan::Isan;
+///
+/// let isan: Isan = "0000-0000-3A8D-0000-Z-0000-0000-6".parse().unwrap();
+/// assert_eq!(0x3A8D, isan.root);
+/// assert_eq!(0, isan.episode);
+/// assert_eq!(Some(0), isan.version);
+/// assert_eq!(('Z', Some('6')), isan.check_characters());
+/// assert_eq!(
+///     vec![0x00, 0x00, 0x00, 0x00, 0x3A, 0x8D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+///     isan.to_binary()
+/// );
+/// ```
+/**
+```text
+{
+  root     48 uimsbf
+  episode  16 uimsbf
+  version  32 uimsbf
+}
+```
+*/
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Isan {
+    /// The 48-bit root that identifies the work.
+    pub root: u64,
+    /// The episode or part of the work, which is `0` when the work is not divided.
+    pub episode: u16,
+    /// The version of the work, which is only present for a versioned ISAN.
+    pub version: Option<u32>,
+}
+
+impl Isan {
+    /// Creates an `Isan` from its binary encoding, which is 8 bytes for an ISAN without a version
+    /// and 12 bytes for a versioned ISAN. Returns `None` for any other length.
+    pub fn from_binary(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 8 && bytes.len() != 12 {
+            return None;
+        }
+        let root = bytes[..6]
+            .iter()
+            .fold(0, |root, byte| (root << 8) | *byte as u64);
+        Some(Self {
+            root,
+            episode: u16::from_be_bytes([bytes[6], bytes[7]]),
+            version: bytes.get(8..12).map(|version| {
+                u32::from_be_bytes([version[0], version[1], version[2], version[3]])
+            }),
+        })
+    }
+
+    /// The binary encoding of the ISAN, which is 8 bytes for an ISAN without a version and 12
+    /// bytes for a versioned ISAN. Only the low 48 bits of the `root` are used.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut bytes = self.root.to_be_bytes()[2..].to_vec();
+        bytes.extend_from_slice(&self.episode.to_be_bytes());
+        if let Some(version) = self.version {
+            bytes.extend_from_slice(&version.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// The check character that follows the episode, and the check character that follows the
+    /// version (when present).
+    pub fn check_characters(&self) -> (char, Option<char>) {
+        let groups = self.groups();
+        let root_check = check_char(&groups[..4]);
+        let version_check = self.version.map(|_| check_char(&groups));
+        (root_check, version_check)
+    }
+
+    pub(crate) fn try_from(bits: &mut Bits, is_versioned: bool) -> Result<Self, ParseError> {
+        Ok(Self {
+            root: bits.u64("root", 48)?,
+            episode: bits.u16("episode", 16)?,
+            version: if is_versioned {
+                Some(bits.u32("version", 32)?)
+            } else {
+                None
+            },
+        })
+    }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.validate(48, self.root, "Isan root")?;
+        bits.bytes(&self.to_binary());
+        Ok(())
+    }
+
+    fn groups(&self) -> Vec<String> {
+        self.to_binary()
+            .chunks(2)
+            .map(|group| format!("{:02X}{:02X}", group[0], group[1]))
+            .collect()
+    }
+}
+
+impl Display for Isan {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let groups = self.groups();
+        let (root_check, version_check) = self.check_characters();
+        write!(f, "{}-{}", groups[..4].join("-"), root_check)?;
+        if let Some(version_check) = version_check {
+            write!(f, "-{}-{}", groups[4..].join("-"), version_check)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Isan {
+    type Err = ParseError;
+
+    /// Parses the hyphenated representation of an ISAN, optionally preceded by `ISAN `. Check
+    /// characters may be omitted, but when present they must match the preceding groups.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::InvalidISAN(String::from(s));
+        let groups: Vec<&str> = s.strip_prefix("ISAN ").unwrap_or(s).split('-').collect();
+        let mut hex_groups = vec![];
+        let mut bytes = vec![];
+        for group in groups {
+            if group.len() == 1 && (hex_groups.len() == 4 || hex_groups.len() == 6) {
+                if !group.eq_ignore_ascii_case(&check_char(&hex_groups).to_string()) {
+                    return Err(invalid());
+                }
+                continue;
+            }
+            if group.len() != 4 || !group.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            let value = u16::from_str_radix(group, 16).map_err(|_| invalid())?;
+            bytes.extend_from_slice(&value.to_be_bytes());
+            hex_groups.push(group.to_uppercase());
+        }
+        Self::from_binary(&bytes).ok_or_else(invalid)
+    }
+}
+
+const CHAR_ARRAY: [char; 36] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
+    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+// The check calculation is taken from isan_check_digit_calculation_v2.0.pdf included
+// in the repository.
+pub(crate) fn check_char(isan: &[String]) -> char {
+    let isan: Vec<String> = isan
+        .iter()
+        .filter(|s| s.chars().count() > 1)
+        .cloned()
+        .collect();
+    let adjusted_product = isan.join("").chars().fold(36, |adjusted_sum, char| {
+        let decimal_value = u8::from_str_radix(&char.to_string(), 16)
+            .expect("Should be safe as all non-check chars in ISAN array are hexadecimal.");
+        let mut sum = adjusted_sum + decimal_value;
+        if sum > 36 {
+            sum -= 36;
+        }
+        let mut product = sum * 2;
+        if product >= 37 {
+            product -= 37;
+        }
+        product
+    });
+    if adjusted_product == 1 {
+        '0'
+    } else {
+        *CHAR_ARRAY.get((37 - adjusted_product) as usize).unwrap()
+    }
+}
@@ -20,7 +20,7 @@
 //!         SpliceDescriptor,
 //!     },
 //!     splice_info_section::{SAPType, SpliceInfoSection},
-//!     time::SpliceTime,
+//!     time::{Pts90k, SpliceTime},
 //! };
 //!
 //! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
@@ -31,11 +31,11 @@
 //!         sap_type: SAPType::Unspecified,
 //!         protocol_version: 0,
 //!         encrypted_packet: None,
-//!         pts_adjustment: 0,
+//!         pts_adjustment: Pts90k(0),
 //!         tier: 0xFFF,
 //!         splice_command: SpliceCommand::TimeSignal(TimeSignal {
 //!             splice_time: SpliceTime {
-//!                 pts_time: Some(1924989008),
+//!                 pts_time: Some(Pts90k(1924989008)),
 //!             },
 //!         }),
 //!         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -83,7 +83,7 @@
 //!         SpliceDescriptor,
 //!     },
 //!     splice_info_section::{SAPType, SpliceInfoSection},
-//!     time::SpliceTime,
+//!     time::{Pts90k, SpliceTime},
 //! };
 //!
 //! let base64_string = "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
@@ -95,11 +95,11 @@
 //!         sap_type: SAPType::Unspecified,
 //!         protocol_version: 0,
 //!         encrypted_packet: None,
-//!         pts_adjustment: 0,
+//!         pts_adjustment: Pts90k(0),
 //!         tier: 0xFFF,
 //!         splice_command: SpliceCommand::TimeSignal(TimeSignal {
 //!             splice_time: SpliceTime {
-//!                 pts_time: Some(1924989008),
+//!                 pts_time: Some(Pts90k(1924989008)),
 //!             },
 //!         }),
 //!         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -142,12 +142,104 @@
 //! let splice_info_section_from_hex = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
 //! assert_eq!(splice_info_section_from_base64, splice_info_section_from_hex);
 //! ```
+//!
+//! A base64 string can also be parsed directly with `try_from_base64_string`. For input whose encoding is not known in advance (e.g. cues pasted by an operator), `SpliceInfoSection` implements `FromStr`, which detects whether the string is hex (with or without the `0x` prefix) or base64:
+//! ```
+//! # use scte35::splice_info_section::SpliceInfoSection;
+//! # let base64_string = "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
+//! # let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! # let splice_info_section_from_hex = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! # #[cfg(feature = "base64")] {
+//! let splice_info_section: SpliceInfoSection = base64_string.parse().unwrap();
+//! assert_eq!(splice_info_section_from_hex, splice_info_section);
+//! # }
+//! ```
+//!
+//! The `hex` module's `encode_hex` and `decode_hex` are also public, for logging and reading back the bytes of messages in the same form, with a `DecodeHexError` that gives the offset of any invalid character.
+//!
+//! Similarly, the `bit_reader` module's `Bits` is public, for parsing the private bytes of custom splice descriptors (or any other big-endian bit fields) with the same bounds-checked reads and `ParseError`s that are used to parse the messages themselves.
+//!
+//! ## Encoding
+//! A `SpliceInfoSection` can be encoded back into bytes (or a hex string) via `try_to_bytes` and `try_to_hex_string`. All length fields and the `crc_32` are derived during encoding. The `SpliceInfoSectionBuilder` can be used to construct a message without having to provide the fields that are usually static:
+//! ```
+//! use scte35::{
+//!     splice_command::{time_signal::TimeSignal, SpliceCommand},
+//!     splice_info_section::SpliceInfoSection,
+//!     time::{Pts90k, SpliceTime},
+//! };
+//!
+//! let splice_info_section = SpliceInfoSection::builder()
+//!     .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+//!         splice_time: SpliceTime {
+//!             pts_time: Some(Pts90k(1924989008)),
+//!         },
+//!     }))
+//!     .build()
+//!     .unwrap();
+//! let hex_string = splice_info_section.try_to_hex_string().unwrap();
+//! assert_eq!(splice_info_section, SpliceInfoSection::try_from_hex_string(&hex_string).unwrap());
+//! ```
+//!
+//! ## Features
+//! - `base64` (enabled by default): adds `SpliceInfoSection::try_from_base64_string` (and the base64 detection of its `FromStr` implementation), the `base64` methods of the `Parser` and the legacy HLS cue tags (`hls::LegacyCue`), which carry the section as base64. It is also enabled by `xml`.
+//! - `serde`: derives `Serialize` and `Deserialize` for the public model types and errors, and adds decoding of JSON `ManagedPrivateUPID` data.
+//! - `xml`: adds `SpliceInfoSection::try_from_xml_string` and `SpliceInfoSection::to_xml_string` for parsing and emitting the SCTE-35 XML representation (see the `xml` module).
+//! - `dash`: adds the `dash` module for creating MPD `EventStream` `Event` elements (enables `xml`).
+//! - `esam`: adds the `esam` module for wrapping signals into ESAM `SignalProcessingEvent` requests and parsing `SignalProcessingNotification` responses (enables `xml`).
+//! - `tokio`: adds `transport_stream::AsyncSpliceSectionReader` for reading sections from a tokio `AsyncRead`.
+//! - `chrono`: adds conversions between the `utc_splice_time` of a `SpliceSchedule` and `chrono::DateTime` (in addition to the `SystemTime` conversions that are always available).
+//! - `crypto`: adds `encryption::DesDecryptor`, a `Decryptor` for the DES (ECB and CBC mode) and Triple DES encryption algorithms, so that encrypted messages can be parsed.
+//! - `uuid`: adds conversion of a UUID `SegmentationUPID` to `uuid::Uuid`.
+//! - `arbitrary`: derives `arbitrary::Arbitrary` for `SpliceInfoSection` and all of its nested types (excluding `SpliceDescriptor::Custom`), for use in fuzzing.
+//! - `rayon`: adds `par_parse_all` (and `Parser::par_parse_all`) for parsing a corpus of messages in parallel.
+//! - `tracing`: emits `tracing` spans and events while parsing (a span per `SpliceInfoSection` with its `tier`, `splice_command_type` and `event_id`, and events for each splice descriptor and non-fatal error), for observability of long-running cue monitors.
+//! - `test-util`: adds the `test_util` module of `proptest` strategies for generating random, valid `SpliceInfoSection` messages.
+//! - `cli`: builds the `scte35` command line tool, whose `decode` subcommand prints hex, base64 or binary messages (given as an argument or read from stdin) as text, JSON or XML (e.g. `scte35 decode --output json /DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=`), and whose `encode` subcommand encodes a message from a JSON description in the same form (e.g. `scte35 encode --output hex '{"splice_command": {"TimeSignal": {"splice_time": {"pts_time": 1924989008}}}}'`), for hand-crafting test cues. The `scan` subcommand prints the messages within a transport stream file along with the PID, packet offset and most recent PTS at which each was found, discovering the SCTE-35 PIDs from the program map tables unless a `--pid` is given (e.g. `scte35 scan recording.ts`). The `monitor` subcommand does the same for a live transport stream received over UDP (joining a multicast group, with or without RTP) or piped to stdin, printing each message as a line of JSON with the wallclock time at which it was received (e.g. `scte35 monitor --udp 239.1.1.1:5000`). The `fix-crc` and `restamp` subcommands patch messages in place with the `edit` module, recalculating the `crc_32` or setting the `pts_adjustment` (e.g. `scte35 restamp --pts-adjustment 900 0xFC30...`) while leaving the other bytes untouched (unless `--canonicalize` is given), and write them back in the encoding they were read in.
+//! - `wasm`: adds the `wasm` module of `wasm-bindgen` bindings, which exports a `parse` function that takes a base64 message and returns it as a JavaScript object in its JSON form (e.g. for a web-based cue inspector). The crate compiles to `wasm32-unknown-unknown` with any feature other than `cli`, and the bindings can be packaged with `wasm-pack build --target web --features wasm`.
 
+pub mod adi;
+pub mod annotation;
 pub mod atsc;
-mod bit_reader;
+pub mod bit_reader;
+mod bit_writer;
+mod crc;
+pub mod cue;
+#[cfg(feature = "dash")]
+pub mod dash;
+pub mod diff;
+mod display;
+pub mod edit;
+pub mod eidr;
+pub mod emsg;
+pub mod encryption;
 pub mod error;
-mod hex;
+#[cfg(feature = "esam")]
+pub mod esam;
+pub mod hex;
+pub mod hls;
+pub mod id3;
+pub mod isan;
+pub mod parser;
+pub mod preroll;
+pub mod redact;
+#[cfg(feature = "rayon")]
+pub use parser::par_parse_all;
+pub use parser::parse_all;
 pub mod splice_command;
 pub mod splice_descriptor;
 pub mod splice_info_section;
+pub mod splice_info_section_ref;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod time;
+pub mod timecode;
+pub mod timeline;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod tracker;
+pub mod transport_stream;
+pub mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "xml")]
+pub mod xml;
@@ -0,0 +1,294 @@
+//! A reusable `Parser`, for services that parse a high volume of messages (e.g. monitoring a
+//! 24/7 feed, or validating an archive of cue logs).
+//!
+//! The `Parser` holds the `ParserConfig`, so that it is not rebuilt for each message, and a
+//! scratch buffer that hex and base64 encoded messages are decoded into, so that decoding does
+//! not allocate once the buffer has grown to fit the largest message seen. Results can be either
+//! owned (`parse`), written into an existing `SpliceInfoSection` to reuse its allocations
+//! (`parse_into`), or borrowed from the input (`parse_ref`, see `SpliceInfoSectionRef`).
+//!
+//! For validating a large corpus of messages (e.g. an archive of historical cue logs), `parse_all`
+//! parses every message and aggregates the results into a `ParseReport`. With the `rayon` feature
+//! enabled, `par_parse_all` does the same in parallel.
+//! ```
+//! use scte35::{parser::Parser, splice_command::SpliceCommand};
+//!
+//! let mut parser = Parser::new();
+//! let messages = [
+//!     "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==",
+//!     "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=",
+//! ];
+//! # #[cfg(feature = "base64")]
+//! for message in messages {
+//!     let splice_info_section = parser.parse_base64(message).unwrap();
+//!     assert!(matches!(
+//!         splice_info_section.splice_command,
+//!         SpliceCommand::TimeSignal(_) | SpliceCommand::SpliceInsert(_)
+//!     ));
+//! }
+//! ```
+use crate::{
+    error::{LocatedParseError, ParseError},
+    hex,
+    splice_descriptor::SpliceDescriptor,
+    splice_info_section::{ParserConfig, SpliceInfoSection},
+    splice_info_section_ref::SpliceInfoSectionRef,
+};
+#[cfg(feature = "base64")]
+use base64::prelude::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// Parses `SpliceInfoSection` messages using a fixed `ParserConfig`, reusing its internal buffers
+/// between messages.
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    config: ParserConfig,
+    scratch: Vec<u8>,
+    splice_descriptors: Vec<SpliceDescriptor>,
+}
+
+impl Parser {
+    /// Creates a `Parser` with the default `ParserConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `Parser` that parses with the given `config`.
+    pub fn with_config(config: ParserConfig) -> Self {
+        Self {
+            config,
+            scratch: Vec::new(),
+            splice_descriptors: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
+    /// Parses the `SpliceInfoSection` from the provided bytes.
+    pub fn parse(&self, data: &[u8]) -> Result<SpliceInfoSection, ParseError> {
+        SpliceInfoSection::try_from_bytes_with_config(data, &self.config)
+    }
+
+    /// Parses the `SpliceInfoSection` from the provided bytes, where any error is located at the
+    /// bit offset and the path of the structure at which parsing failed (see
+    /// `LocatedParseError`).
+    pub fn parse_located(&self, data: &[u8]) -> Result<SpliceInfoSection, LocatedParseError> {
+        SpliceInfoSection::try_from_bytes_located_with_config(data, &self.config)
+    }
+
+    /// Parses the `SpliceInfoSection` from the provided hex encoded string (which may be prefixed
+    /// with `0x`), decoding into the scratch buffer of the `Parser`.
+    pub fn parse_hex(&mut self, hex_string: &str) -> Result<SpliceInfoSection, ParseError> {
+        self.decode_hex(hex_string)?;
+        SpliceInfoSection::try_from_bytes_with_config(&self.scratch, &self.config)
+    }
+
+    /// Parses the `SpliceInfoSection` from the provided base64 encoded string, decoding into the
+    /// scratch buffer of the `Parser`.
+    #[cfg(feature = "base64")]
+    pub fn parse_base64(&mut self, base64_string: &str) -> Result<SpliceInfoSection, ParseError> {
+        self.decode_base64(base64_string)?;
+        SpliceInfoSection::try_from_bytes_with_config(&self.scratch, &self.config)
+    }
+
+    /// Parses the `SpliceInfoSection` from the provided bytes into `splice_info_section`, reusing
+    /// the allocation of its `splice_descriptors` for later messages. When parsing fails the
+    /// `splice_info_section` is left unchanged.
+    /// ```
+    /// use scte35::{parser::Parser, splice_info_section::SpliceInfoSection};
+    ///
+    /// let mut parser = Parser::new();
+    /// let mut splice_info_section = SpliceInfoSection::builder().build().unwrap();
+    /// let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+    /// let data = parser.decode_hex(hex_string).unwrap().to_vec();
+    /// parser.parse_into(&data, &mut splice_info_section).unwrap();
+    /// assert_eq!(1, splice_info_section.splice_descriptors.len());
+    /// assert!(parser.parse_into(&data[..10], &mut splice_info_section).is_err());
+    /// assert_eq!(1, splice_info_section.splice_descriptors.len());
+    /// ```
+    pub fn parse_into(
+        &mut self,
+        data: &[u8],
+        splice_info_section: &mut SpliceInfoSection,
+    ) -> Result<(), ParseError> {
+        let splice_descriptors = std::mem::take(&mut self.splice_descriptors);
+        let parsed =
+            SpliceInfoSection::try_from_bytes_reusing(data, &self.config, splice_descriptors)?;
+        let previous = std::mem::replace(splice_info_section, parsed);
+        self.splice_descriptors = previous.splice_descriptors;
+        Ok(())
+    }
+
+    /// Parses a `SpliceInfoSectionRef` that borrows from the provided bytes. Note that the
+    /// borrowed representation does not make use of the `ParserConfig`.
+    pub fn parse_ref<'a>(&self, data: &'a [u8]) -> Result<SpliceInfoSectionRef<'a>, ParseError> {
+        SpliceInfoSectionRef::try_from_bytes(data)
+    }
+
+    /// Parses a `SpliceInfoSectionRef` from the provided hex encoded string (which may be
+    /// prefixed with `0x`), borrowing from the scratch buffer of the `Parser`.
+    pub fn parse_hex_ref(
+        &mut self,
+        hex_string: &str,
+    ) -> Result<SpliceInfoSectionRef<'_>, ParseError> {
+        let data = self.decode_hex(hex_string)?;
+        SpliceInfoSectionRef::try_from_bytes(data)
+    }
+
+    /// Parses a `SpliceInfoSectionRef` from the provided base64 encoded string, borrowing from the
+    /// scratch buffer of the `Parser`.
+    #[cfg(feature = "base64")]
+    pub fn parse_base64_ref(
+        &mut self,
+        base64_string: &str,
+    ) -> Result<SpliceInfoSectionRef<'_>, ParseError> {
+        let data = self.decode_base64(base64_string)?;
+        SpliceInfoSectionRef::try_from_bytes(data)
+    }
+
+    /// Decodes the provided hex encoded string (which may be prefixed with `0x`) into the scratch
+    /// buffer of the `Parser`, returning the decoded bytes.
+    pub fn decode_hex(&mut self, hex_string: &str) -> Result<&[u8], ParseError> {
+        hex::decode_prefixed_hex_into(hex_string, &mut self.scratch)?;
+        Ok(&self.scratch)
+    }
+
+    /// Decodes the provided base64 encoded string into the scratch buffer of the `Parser`,
+    /// returning the decoded bytes.
+    #[cfg(feature = "base64")]
+    pub fn decode_base64(&mut self, base64_string: &str) -> Result<&[u8], ParseError> {
+        self.scratch.clear();
+        BASE64_STANDARD.decode_vec(base64_string, &mut self.scratch)?;
+        Ok(&self.scratch)
+    }
+}
+
+impl Parser {
+    /// Parses every payload, aggregating the results into a `ParseReport`.
+    pub fn parse_all<I>(&self, payloads: I) -> ParseReport
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        payloads
+            .into_iter()
+            .map(|payload| self.parse(payload.as_ref()))
+            .collect()
+    }
+
+    /// Parses every payload in parallel (using the global `rayon` thread pool), aggregating the
+    /// results into a `ParseReport` in the same order as `parse_all`.
+    #[cfg(feature = "rayon")]
+    pub fn par_parse_all<T>(&self, payloads: &[T]) -> ParseReport
+    where
+        T: AsRef<[u8]> + Sync,
+    {
+        payloads
+            .par_iter()
+            .map(|payload| self.parse(payload.as_ref()))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Parses every payload with the default `ParserConfig`, aggregating the results into a
+/// `ParseReport`.
+/// ```
+/// use base64::prelude::*;
+///
+/// let payloads = [
+///     BASE64_STANDARD
+///         .decode("/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=")
+///         .unwrap(),
+///     vec![0xFC, 0x30],
+/// ];
+/// let report = scte35::parse_all(&payloads);
+/// assert_eq!(2, report.total());
+/// assert_eq!(0, report.sections[0].0);
+/// assert_eq!(1, report.errors[0].0);
+/// assert_eq!(Some(&1), report.error_counts().get("UnexpectedEndOfData"));
+/// ```
+pub fn parse_all<I>(payloads: I) -> ParseReport
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    Parser::new().parse_all(payloads)
+}
+
+/// Parses every payload in parallel with the default `ParserConfig`, aggregating the results into
+/// a `ParseReport` (see `Parser::par_parse_all`).
+#[cfg(feature = "rayon")]
+pub fn par_parse_all<T>(payloads: &[T]) -> ParseReport
+where
+    T: AsRef<[u8]> + Sync,
+{
+    Parser::new().par_parse_all(payloads)
+}
+
+/// The aggregated results of parsing a corpus of messages, where each message is identified by
+/// its index within the corpus.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct ParseReport {
+    /// The messages that were parsed successfully, in the order of the corpus.
+    pub sections: Vec<(usize, SpliceInfoSection)>,
+    /// The errors of the messages that could not be parsed, in the order of the corpus.
+    pub errors: Vec<(usize, ParseError)>,
+}
+
+impl ParseReport {
+    /// The total number of messages that were parsed.
+    pub fn total(&self) -> usize {
+        self.sections.len() + self.errors.len()
+    }
+
+    /// Whether every message was parsed successfully.
+    pub fn is_all_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The number of messages that failed with each kind of error, where the kind is the name of
+    /// the `ParseError` variant (e.g. `"CRCMismatch"`).
+    pub fn error_counts(&self) -> BTreeMap<String, usize> {
+        let mut error_counts = BTreeMap::new();
+        for (_, error) in &self.errors {
+            *error_counts.entry(error_kind(error)).or_insert(0) += 1;
+        }
+        error_counts
+    }
+
+    /// The number of messages that were parsed successfully but with `non_fatal_errors`.
+    pub fn non_fatal_error_count(&self) -> usize {
+        self.sections
+            .iter()
+            .filter(|(_, section)| !section.non_fatal_errors.is_empty())
+            .count()
+    }
+}
+
+impl FromIterator<Result<SpliceInfoSection, ParseError>> for ParseReport {
+    fn from_iter<I: IntoIterator<Item = Result<SpliceInfoSection, ParseError>>>(iter: I) -> Self {
+        let mut report = Self::default();
+        for (index, result) in iter.into_iter().enumerate() {
+            match result {
+                Ok(section) => report.sections.push((index, section)),
+                Err(error) => report.errors.push((index, error)),
+            }
+        }
+        report
+    }
+}
+
+// The name of the variant, taken from the `Debug` representation (which is derived).
+fn error_kind(error: &ParseError) -> String {
+    let debug = format!("{:?}", error);
+    match debug.find(['(', ' ', '{']) {
+        Some(end) => debug[..end].to_string(),
+        None => debug,
+    }
+}
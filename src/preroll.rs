@@ -0,0 +1,206 @@
+//! Checking that a message was received far enough in advance of the splice that it signals, for
+//! monitoring a stream of messages.
+//!
+//! A `TimeSignal` or `SpliceInsert` should be sent at least 4 seconds in advance of the signaled
+//! `SpliceTime`, and a `SpliceInfoSection` with a `DTMFDescriptor` shall be sent at least 2 seconds
+//! earlier than the `preroll` of the descriptor (which has a minimum suggested value of 4.0
+//! seconds). The `PrerollChecker` compares the `Arrival` of a message against these, reporting
+//! any shortfall as a `PrerollWarning` (as the message is still usable).
+//! ```
+//! use scte35::{
+//!     cue::AdBreak,
+//!     preroll::{PrerollChecker, PrerollWarning},
+//!     time::Pts90k,
+//!     timeline::Arrival,
+//! };
+//! use std::time::Duration;
+//!
+//! let (cue_out, _) = AdBreak::new(1, 90_000 * 10, 90_000 * 30).build().unwrap();
+//! let checker = PrerollChecker::new();
+//! assert!(checker.check(&cue_out, &Arrival::new(Pts90k(90_000 * 5))).is_empty());
+//! assert_eq!(
+//!     vec![PrerollWarning::InsufficientLeadTime {
+//!         lead_time: Duration::from_secs(2),
+//!         minimum_lead_time: Duration::from_secs(4),
+//!     }],
+//!     checker.check(&cue_out, &Arrival::new(Pts90k(90_000 * 8)))
+//! );
+//! ```
+use crate::{
+    splice_command::{
+        splice_schedule::{self, SpliceSchedule},
+        SpliceCommand,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::duration_from_ticks,
+    timeline::Arrival,
+};
+use std::{
+    fmt::{Display, Formatter},
+    time::{Duration, SystemTime},
+};
+
+/// The minimum time, before the signaled `SpliceTime`, that a message should be sent.
+pub const MINIMUM_LEAD_TIME: Duration = Duration::from_secs(4);
+
+/// The minimum time, before the `preroll` of a `DTMFDescriptor`, that the message shall be sent.
+pub const DTMF_PREROLL_LEAD_TIME: Duration = Duration::from_secs(2);
+
+/// The minimum suggested `preroll` of a `DTMFDescriptor`, in tenths of seconds.
+pub const MINIMUM_SUGGESTED_DTMF_PREROLL: u8 = 40;
+
+/// An issue with the time at which a message was received, relative to the splice that it
+/// signals.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrerollWarning {
+    /// The message was received less than the `minimum_lead_time` before its splice.
+    InsufficientLeadTime {
+        lead_time: Duration,
+        minimum_lead_time: Duration,
+    },
+    /// The message was received after its splice.
+    ReceivedAfterSplice { late_by: Duration },
+    /// The message was received less than 2 seconds earlier than the `preroll` (in tenths of
+    /// seconds) of a `DTMFDescriptor`.
+    InsufficientDTMFPreroll { preroll: u8, lead_time: Duration },
+    /// The `preroll` (in tenths of seconds) of a `DTMFDescriptor` is less than the minimum
+    /// suggested value of 4.0 seconds.
+    DTMFPrerollBelowSuggested { preroll: u8 },
+}
+
+impl Display for PrerollWarning {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            PrerollWarning::InsufficientLeadTime {
+                lead_time,
+                minimum_lead_time,
+            } => write!(
+                f,
+                "Message received {:.3}s before the splice, which is less than the minimum of {:.3}s.",
+                lead_time.as_secs_f64(),
+                minimum_lead_time.as_secs_f64()
+            ),
+            PrerollWarning::ReceivedAfterSplice { late_by } => write!(
+                f,
+                "Message received {:.3}s after the splice.",
+                late_by.as_secs_f64()
+            ),
+            PrerollWarning::InsufficientDTMFPreroll { preroll, lead_time } => write!(
+                f,
+                "Message received {:.3}s before the splice, which is less than 2s before the DTMF preroll of {:.1}s.",
+                lead_time.as_secs_f64(),
+                f64::from(*preroll) / 10.0
+            ),
+            PrerollWarning::DTMFPrerollBelowSuggested { preroll } => write!(
+                f,
+                "DTMF preroll of {:.1}s is less than the suggested minimum of 4.0s.",
+                f64::from(*preroll) / 10.0
+            ),
+        }
+    }
+}
+
+/// Checks the time at which messages are received against the time of the splice that they
+/// signal (see the `preroll` module).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct PrerollChecker {
+    minimum_lead_time: Duration,
+}
+
+impl Default for PrerollChecker {
+    fn default() -> Self {
+        Self {
+            minimum_lead_time: MINIMUM_LEAD_TIME,
+        }
+    }
+}
+
+impl PrerollChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum time before the splice that a message should be received. Defaults to
+    /// `MINIMUM_LEAD_TIME` (4 seconds).
+    pub fn minimum_lead_time(mut self, minimum_lead_time: Duration) -> Self {
+        self.minimum_lead_time = minimum_lead_time;
+        self
+    }
+
+    /// Checks the `splice_info_section`, received at the `arrival`, returning any warnings.
+    ///
+    /// The splice of a `TimeSignal` or `SpliceInsert` is its `splice_pts` (which includes the
+    /// `pts_adjustment`), compared against the PTS of the `arrival`. The splice of a
+    /// `SpliceSchedule` is its earliest `utc_splice_time`, compared against the wall clock time
+    /// of the `arrival` (and so is not checked when that is not known). Immediate and cancelled
+    /// messages have no splice time, so only the `preroll` of any `DTMFDescriptor` is checked.
+    pub fn check(
+        &self,
+        splice_info_section: &SpliceInfoSection,
+        arrival: &Arrival,
+    ) -> Vec<PrerollWarning> {
+        let mut warnings = vec![];
+        let lead_time = match &splice_info_section.splice_command {
+            SpliceCommand::SpliceSchedule(splice_schedule) => arrival
+                .wallclock
+                .zip(earliest_utc_splice_time(splice_schedule))
+                .map(|(wallclock, splice_time)| signed_duration_between(wallclock, splice_time)),
+            _ => splice_info_section.splice_pts().map(|splice_pts| {
+                let distance = arrival.pts.signed_distance_to(splice_pts);
+                let duration = duration_from_ticks(distance.unsigned_abs());
+                if distance < 0 {
+                    Err(duration)
+                } else {
+                    Ok(duration)
+                }
+            }),
+        };
+        match lead_time {
+            Some(Ok(lead_time)) if lead_time < self.minimum_lead_time => {
+                warnings.push(PrerollWarning::InsufficientLeadTime {
+                    lead_time,
+                    minimum_lead_time: self.minimum_lead_time,
+                })
+            }
+            Some(Err(late_by)) => warnings.push(PrerollWarning::ReceivedAfterSplice { late_by }),
+            _ => (),
+        }
+        for dtmf_descriptor in splice_info_section.dtmf_descriptors() {
+            let preroll = dtmf_descriptor.preroll;
+            if preroll < MINIMUM_SUGGESTED_DTMF_PREROLL {
+                warnings.push(PrerollWarning::DTMFPrerollBelowSuggested { preroll });
+            }
+            if let Some(Ok(lead_time)) = lead_time {
+                let required = dtmf_descriptor.preroll_duration() + DTMF_PREROLL_LEAD_TIME;
+                if lead_time < required {
+                    warnings.push(PrerollWarning::InsufficientDTMFPreroll { preroll, lead_time });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+// The earliest `utc_splice_time` of the events of the `splice_schedule` that are not cancelled.
+fn earliest_utc_splice_time(splice_schedule: &SpliceSchedule) -> Option<SystemTime> {
+    splice_schedule
+        .events
+        .iter()
+        .filter_map(|event| event.scheduled_event.as_ref())
+        .flat_map(|event| match &event.splice_mode {
+            splice_schedule::SpliceMode::ProgramSpliceMode(program) => {
+                vec![program.splice_system_time()]
+            }
+            splice_schedule::SpliceMode::ComponentSpliceMode(components) => components
+                .iter()
+                .map(splice_schedule::ComponentMode::splice_system_time)
+                .collect(),
+        })
+        .min()
+}
+
+// The time from `from` to `to`, as `Ok` when `to` is not before `from`, and otherwise as `Err`.
+fn signed_duration_between(from: SystemTime, to: SystemTime) -> Result<Duration, Duration> {
+    to.duration_since(from).map_err(|error| error.duration())
+}
@@ -0,0 +1,208 @@
+//! Redaction of the asset identifiers in a `SpliceInfoSection`, so that cues can be logged or
+//! shared outside of an organisation (e.g. attached to a bug report for an encoder vendor)
+//! without leaking which content they refer to.
+//!
+//! Every `SegmentationUPID`, and the private bytes of any `PrivateCommand`, `Custom` or `Unknown`
+//! descriptor, is replaced by a value of the same type and length, so the redacted section has
+//! the same structure (and encoded length) as the original.
+//! ```
+//! use scte35::{
+//!     redact::Redaction,
+//!     splice_descriptor::{segmentation_descriptor::SegmentationUPID, SpliceDescriptor},
+//!     splice_info_section::SpliceInfoSection,
+//! };
+//!
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//!
+//! let redacted = splice_info_section.redacted(&Redaction::Placeholder).unwrap();
+//! let Some(SpliceDescriptor::SegmentationDescriptor(descriptor)) =
+//!     redacted.splice_descriptors.first()
+//! else {
+//!     panic!("expected a segmentation descriptor");
+//! };
+//! assert_eq!(
+//!     SegmentationUPID::TI(String::from("0x0000000000000000")),
+//!     descriptor.scheduled_event.as_ref().unwrap().segmentation_upid
+//! );
+//! assert_eq!(
+//!     splice_info_section.try_to_bytes().unwrap().len(),
+//!     redacted.try_to_bytes().unwrap().len()
+//! );
+//! ```
+use crate::{
+    atsc::ATSCContentIdentifier,
+    eidr::Eidr,
+    error::EncodeError,
+    isan::Isan,
+    splice_command::SpliceCommand,
+    splice_descriptor::{
+        segmentation_descriptor::{hyphenated_uuid, ManagedPrivateUPID, SegmentationUPID},
+        SpliceDescriptor,
+    },
+    splice_info_section::{SpliceInfoSection, StableHasher},
+};
+use std::hash::Hasher;
+
+/// How the redacted values of a `SpliceInfoSection` are replaced.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Redaction {
+    /// Each value is replaced by one derived from a hash of the value and the `key`, so that equal
+    /// values are redacted to equal values (allowing the cues for the same asset to still be
+    /// correlated). Text is replaced by uppercase hexadecimal digits. The hash is not
+    /// cryptographic, and so only hides values from those that do not know the `key`.
+    Hash { key: u64 },
+    /// Each value is replaced by a placeholder, which is `X` characters for text and zero bytes
+    /// for binary values.
+    Placeholder,
+}
+
+impl SpliceInfoSection {
+    /// A copy of this section with the asset identifiers replaced according to the `redaction`
+    /// (see the `redact` module), and the `crc_32` recalculated (see `canonicalize`).
+    ///
+    /// A `SegmentationUPID` keeps any part that identifies the owner or structure of the
+    /// identifier, rather than the asset (i.e. the `sub_prefix` of an `Eidr`, the `tsid`,
+    /// `end_of_day` and `unique_for` of an `ATSCContentIdentifier`, and the `format_specifier` of a
+    /// `ManagedPrivateUPID`), and a `Custom` descriptor becomes an `Unknown` descriptor with the
+    /// same `tag` and `identifier`.
+    pub fn redacted(&self, redaction: &Redaction) -> Result<SpliceInfoSection, EncodeError> {
+        let mut splice_info_section = self.clone();
+        if let SpliceCommand::PrivateCommand(private_command) =
+            &mut splice_info_section.splice_command
+        {
+            private_command.private_bytes = redaction.bytes(&private_command.private_bytes);
+        }
+        for splice_descriptor in &mut splice_info_section.splice_descriptors {
+            match splice_descriptor {
+                SpliceDescriptor::SegmentationDescriptor(descriptor) => {
+                    if let Some(scheduled_event) = &mut descriptor.scheduled_event {
+                        scheduled_event.segmentation_upid =
+                            redaction.upid(&scheduled_event.segmentation_upid);
+                    }
+                }
+                SpliceDescriptor::Custom(descriptor) => {
+                    let unknown = SpliceDescriptor::Unknown {
+                        tag: descriptor.tag(),
+                        identifier: descriptor.identifier(),
+                        private_bytes: redaction.bytes(&descriptor.private_bytes()?),
+                    };
+                    *splice_descriptor = unknown;
+                }
+                SpliceDescriptor::Unknown { private_bytes, .. } => {
+                    *private_bytes = redaction.bytes(private_bytes)
+                }
+                SpliceDescriptor::AvailDescriptor(_)
+                | SpliceDescriptor::DTMFDescriptor(_)
+                | SpliceDescriptor::TimeDescriptor(_)
+                | SpliceDescriptor::AudioDescriptor(_) => (),
+            }
+        }
+        splice_info_section.canonicalize()?;
+        Ok(splice_info_section)
+    }
+}
+
+impl Redaction {
+    fn upid(&self, upid: &SegmentationUPID) -> SegmentationUPID {
+        match upid {
+            SegmentationUPID::NotUsed => SegmentationUPID::NotUsed,
+            SegmentationUPID::UserDefined(text) => SegmentationUPID::UserDefined(self.text(text)),
+            SegmentationUPID::ISCI(text) => SegmentationUPID::ISCI(self.text(text)),
+            SegmentationUPID::AdID(text) => SegmentationUPID::AdID(self.text(text)),
+            SegmentationUPID::UMID(umid) => {
+                let groups = self
+                    .bytes_of_length(umid.as_bytes(), 32)
+                    .chunks(4)
+                    .map(|group| group.iter().map(|b| format!("{:02X}", b)).collect())
+                    .collect::<Vec<String>>();
+                SegmentationUPID::UMID(groups.join("."))
+            }
+            SegmentationUPID::DeprecatedISAN(isan) => {
+                SegmentationUPID::DeprecatedISAN(self.isan(isan))
+            }
+            SegmentationUPID::ISAN(isan) => SegmentationUPID::ISAN(self.isan(isan)),
+            SegmentationUPID::TID(text) => SegmentationUPID::TID(self.text(text)),
+            SegmentationUPID::TI(ti) => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(&self.bytes_of_length(ti.as_bytes(), 8));
+                SegmentationUPID::new_ti(u64::from_be_bytes(bytes))
+            }
+            SegmentationUPID::ADI(text) => SegmentationUPID::ADI(self.text(text)),
+            SegmentationUPID::EIDR(eidr) => {
+                let mut suffix = [0; 10];
+                suffix.copy_from_slice(&self.bytes(&eidr.suffix));
+                SegmentationUPID::EIDR(Eidr { suffix, ..*eidr })
+            }
+            SegmentationUPID::ATSCContentIdentifier(atsc) => {
+                SegmentationUPID::ATSCContentIdentifier(ATSCContentIdentifier {
+                    content_id: self.text(&atsc.content_id),
+                    ..atsc.clone()
+                })
+            }
+            SegmentationUPID::MPU(mpu) => SegmentationUPID::MPU(ManagedPrivateUPID {
+                format_specifier: mpu.format_specifier.clone(),
+                private_data: self.bytes(&mpu.private_data),
+            }),
+            SegmentationUPID::MID(upids) => {
+                SegmentationUPID::MID(upids.iter().map(|upid| self.upid(upid)).collect())
+            }
+            SegmentationUPID::ADSInformation(text) => {
+                SegmentationUPID::ADSInformation(self.text(text))
+            }
+            SegmentationUPID::URI(text) => SegmentationUPID::URI(self.text(text)),
+            SegmentationUPID::UUID(uuid) => {
+                SegmentationUPID::UUID(hyphenated_uuid(&self.bytes_of_length(uuid.as_bytes(), 16)))
+            }
+            SegmentationUPID::Invalid { upid_type, bytes } => SegmentationUPID::Invalid {
+                upid_type: upid_type.clone(),
+                bytes: self.bytes(bytes),
+            },
+        }
+    }
+
+    // The 48-bit `root` identifies the work, whereas the `episode` and `version` are only
+    // meaningful alongside it.
+    fn isan(&self, isan: &Isan) -> Isan {
+        let mut root = [0; 8];
+        root[2..].copy_from_slice(&self.bytes_of_length(&isan.root.to_be_bytes(), 6));
+        Isan {
+            root: u64::from_be_bytes(root),
+            ..*isan
+        }
+    }
+
+    // Replaces text with the same number of (ASCII) characters, so that the encoded length of
+    // the text is unchanged.
+    fn text(&self, text: &str) -> String {
+        match self {
+            Redaction::Hash { .. } => self
+                .bytes(text.as_bytes())
+                .iter()
+                .map(|byte| char::from(b"0123456789ABCDEF"[usize::from(byte & 0x0F)]))
+                .collect(),
+            Redaction::Placeholder => "X".repeat(text.len()),
+        }
+    }
+
+    fn bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        self.bytes_of_length(bytes, bytes.len())
+    }
+
+    fn bytes_of_length(&self, value: &[u8], length: usize) -> Vec<u8> {
+        match self {
+            Redaction::Hash { key } => (0..length.div_ceil(8) as u64)
+                .flat_map(|block| {
+                    let mut hasher = StableHasher::default();
+                    hasher.write_u64(*key);
+                    hasher.write(value);
+                    hasher.write_u64(block);
+                    hasher.finish().to_be_bytes()
+                })
+                .take(length)
+                .collect(),
+            Redaction::Placeholder => vec![0; length],
+        }
+    }
+}
@@ -2,14 +2,27 @@ use self::{
     private_command::PrivateCommand, splice_insert::SpliceInsert, splice_schedule::SpliceSchedule,
     time_signal::TimeSignal,
 };
-use crate::{bit_reader::Bits, error::ParseError};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
+use std::fmt::{Display, Formatter};
 
 pub mod private_command;
 pub mod splice_insert;
 pub mod splice_schedule;
 pub mod time_signal;
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+/// The type of a `SpliceCommand`.
+///
+/// This is `non_exhaustive` so that command types defined by future revisions of the
+/// specification can be added without a breaking change. Until then they are represented by
+/// `Reserved` (see `from_value`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SpliceCommandType {
     SpliceNull,
     SpliceSchedule,
@@ -17,6 +30,9 @@ pub enum SpliceCommandType {
     TimeSignal,
     BandwidthReservation,
     PrivateCommand,
+    /// A `splice_command_type` that is reserved by the specification (and so is not parsed by
+    /// this library).
+    Reserved(u8),
 }
 
 impl TryFrom<u8> for SpliceCommandType {
@@ -36,6 +52,19 @@ impl TryFrom<u8> for SpliceCommandType {
 }
 
 impl SpliceCommandType {
+    /// The command type for `value`, which is `Reserved` when `value` is not defined by the
+    /// specification.
+    /// ```
+    /// use scte35::splice_command::SpliceCommandType;
+    ///
+    /// assert_eq!(SpliceCommandType::TimeSignal, SpliceCommandType::from_value(0x06));
+    /// assert_eq!(SpliceCommandType::Reserved(0x08), SpliceCommandType::from_value(0x08));
+    /// assert_eq!(0x08, SpliceCommandType::from_value(0x08).value());
+    /// ```
+    pub fn from_value(value: u8) -> Self {
+        Self::try_from(value).unwrap_or(Self::Reserved(value))
+    }
+
     pub fn value(&self) -> u8 {
         match *self {
             SpliceCommandType::SpliceNull => 0x00,
@@ -44,11 +73,34 @@ impl SpliceCommandType {
             SpliceCommandType::TimeSignal => 0x06,
             SpliceCommandType::BandwidthReservation => 0x07,
             SpliceCommandType::PrivateCommand => 0xff,
+            SpliceCommandType::Reserved(value) => value,
+        }
+    }
+
+    /// The name of the command as given in the specification (e.g. "Time Signal").
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SpliceCommandType::SpliceNull => "Splice Null",
+            SpliceCommandType::SpliceSchedule => "Splice Schedule",
+            SpliceCommandType::SpliceInsert => "Splice Insert",
+            SpliceCommandType::TimeSignal => "Time Signal",
+            SpliceCommandType::BandwidthReservation => "Bandwidth Reservation",
+            SpliceCommandType::PrivateCommand => "Private Command",
+            SpliceCommandType::Reserved(_) => "Reserved",
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+impl Display for SpliceCommandType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SpliceCommand {
     /// The `SpliceNull` command is provided for extensibility of the standard. The `SpliceNull`
     /// command allows a `SpliceInfoTable` to be sent that can carry descriptors without having to
@@ -84,9 +136,37 @@ pub enum SpliceCommand {
     PrivateCommand(PrivateCommand),
 }
 
+impl From<SpliceSchedule> for SpliceCommand {
+    fn from(splice_schedule: SpliceSchedule) -> Self {
+        Self::SpliceSchedule(splice_schedule)
+    }
+}
+
+impl From<SpliceInsert> for SpliceCommand {
+    fn from(splice_insert: SpliceInsert) -> Self {
+        Self::SpliceInsert(splice_insert)
+    }
+}
+
+impl From<TimeSignal> for SpliceCommand {
+    fn from(time_signal: TimeSignal) -> Self {
+        Self::TimeSignal(time_signal)
+    }
+}
+
+impl From<PrivateCommand> for SpliceCommand {
+    fn from(private_command: PrivateCommand) -> Self {
+        Self::PrivateCommand(private_command)
+    }
+}
+
+// The `splice_command_length` that earlier versions of the specification allowed for a length
+// that is unspecified.
+const LEGACY_SPLICE_COMMAND_LENGTH: u32 = 0xFFF;
+
 impl SpliceCommand {
     pub fn try_from(bits: &mut Bits, splice_command_length: u32) -> Result<Self, ParseError> {
-        let splice_command_type_raw_value = bits.byte();
+        let splice_command_type_raw_value = bits.byte("splice_command_type")?;
         let bits_left_before_splice_command = bits.bits_remaining() as isize;
         let expected_bits_left_at_end_of_splice_command =
             bits_left_before_splice_command - ((splice_command_length as isize) * 8);
@@ -102,22 +182,46 @@ impl SpliceCommand {
             SpliceCommandType::PrivateCommand => {
                 Self::PrivateCommand(PrivateCommand::try_from(bits, splice_command_length)?)
             }
+            SpliceCommandType::Reserved(value) => {
+                return Err(ParseError::UnrecognisedSpliceCommandType(value))
+            }
         };
 
         let bits_remaining = bits.bits_remaining() as isize;
-        if bits_remaining != expected_bits_left_at_end_of_splice_command {
-            bits.push_non_fatal_error(ParseError::UnexpectedSpliceCommandLength {
-                declared_splice_command_length_in_bits: splice_command_length * 8,
-                actual_splice_command_length_in_bits: (bits_left_before_splice_command
-                    - bits_remaining)
-                    as usize,
-                splice_command_type: command.command_type(),
-            })
+        let is_accepted_legacy_length = splice_command_length == LEGACY_SPLICE_COMMAND_LENGTH
+            && bits.config().accept_legacy_splice_command_length;
+        if bits_remaining != expected_bits_left_at_end_of_splice_command
+            && !is_accepted_legacy_length
+        {
+            let handling = bits.config().length_mismatch;
+            bits.handle_error(
+                handling,
+                ParseError::UnexpectedSpliceCommandLength {
+                    declared_splice_command_length_in_bits: splice_command_length * 8,
+                    actual_splice_command_length_in_bits: (bits_left_before_splice_command
+                        - bits_remaining)
+                        as usize,
+                    splice_command_type: command.command_type(),
+                },
+            )?;
         }
 
         Ok(command)
     }
 
+    /// Encodes the body of the splice command. The `splice_command_type` and
+    /// `splice_command_length` are written by the containing `SpliceInfoSection`.
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        match self {
+            Self::SpliceNull => Ok(()),
+            Self::SpliceSchedule(splice_schedule) => splice_schedule.encode(bits),
+            Self::SpliceInsert(splice_insert) => splice_insert.encode(bits),
+            Self::TimeSignal(time_signal) => time_signal.encode(bits),
+            Self::BandwidthReservation => Ok(()),
+            Self::PrivateCommand(private_command) => private_command.encode(bits),
+        }
+    }
+
     pub fn command_type(&self) -> SpliceCommandType {
         match *self {
             SpliceCommand::SpliceNull => SpliceCommandType::SpliceNull,
@@ -1,4 +1,9 @@
-use crate::{bit_reader::Bits, error::ParseError};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
+use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
 
 /// The `PrivateCommand` structure provides a means to distribute user-defined commands using the
 /// SCTE 35 protocol. The first bit field in each user-defined command is a 32-bit identifier,
@@ -14,7 +19,9 @@ private_command() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PrivateCommand {
     /// This 32-bit number is used to identify the owner of the command.
     ///
@@ -31,22 +38,146 @@ pub struct PrivateCommand {
 }
 
 impl PrivateCommand {
+    /// The `identifier` as the 32-bit number that it is encoded as, or `None` when it is not 4
+    /// bytes long.
+    pub fn identifier_value(&self) -> Option<u32> {
+        self.identifier
+            .as_bytes()
+            .try_into()
+            .ok()
+            .map(u32::from_be_bytes)
+    }
+
+    /// Creates the `PrivateCommand` that carries the encoded `data`.
+    pub fn try_from_data(data: &dyn PrivateCommandData) -> Result<Self, EncodeError> {
+        let identifier = data.identifier();
+        let identifier = String::from_utf8(identifier.to_be_bytes().to_vec())
+            .map_err(|_| EncodeError::InvalidPrivateCommandIdentifier(identifier))?;
+        Ok(Self {
+            identifier,
+            private_bytes: data.private_bytes()?,
+        })
+    }
+
     pub fn try_from(bits: &mut Bits, splice_command_length: u32) -> Result<Self, ParseError> {
         bits.validate(
             splice_command_length * 8,
             "PrivateCommand; validating splice_command_length",
         )?;
 
-        let identifier = bits.string(4, "Reading identifier for PrivateCommand")?;
-        let mut bytes_left = splice_command_length - 4;
-        let mut private_bytes = vec![];
-        while bytes_left > 0 {
-            bytes_left -= 1;
-            private_bytes.push(bits.byte());
-        }
+        let identifier = bits.string("identifier", 4, "Reading identifier for PrivateCommand")?;
+        let private_bytes = bits.bytes("private_bytes", (splice_command_length - 4) as usize)?;
         Ok(Self {
             identifier,
             private_bytes,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        if self.identifier.len() != 4 {
+            return Err(EncodeError::UnexpectedLength {
+                length: self.identifier.len(),
+                expected_length: 4,
+                description: "PrivateCommand identifier",
+            });
+        }
+        bits.string(&self.identifier);
+        bits.bytes(&self.private_bytes);
+        Ok(())
+    }
+}
+
+/// The typed contents of a `PrivateCommand`, as decoded by a decoder registered in a
+/// `PrivateCommandRegistry`. The concrete type can be recovered with `downcast_ref`, and a
+/// `PrivateCommand` can be created from it with `PrivateCommand::try_from_data`.
+/// ```
+/// use scte35::{
+///     error::{EncodeError, ParseError},
+///     splice_command::private_command::{
+///         PrivateCommand, PrivateCommandData, PrivateCommandRegistry,
+///     },
+/// };
+///
+/// #[derive(Debug)]
+/// struct Channel(u16);
+///
+/// impl PrivateCommandData for Channel {
+///     fn identifier(&self) -> u32 {
+///         0x41424344 // ASCII "ABCD"
+///     }
+///
+///     fn private_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+///         Ok(self.0.to_be_bytes().to_vec())
+///     }
+/// }
+///
+/// let registry = PrivateCommandRegistry::new().register(0x41424344, |private_bytes| {
+///     let channel = private_bytes
+///         .try_into()
+///         .map(u16::from_be_bytes)
+///         .map_err(|_| ParseError::InvalidPrivateData("expected a 16-bit channel"))?;
+///     Ok(Box::new(Channel(channel)))
+/// });
+/// let private_command = PrivateCommand::try_from_data(&Channel(7)).unwrap();
+/// assert_eq!("ABCD", private_command.identifier);
+/// let data = registry.decode(&private_command).unwrap().unwrap();
+/// assert_eq!(7, data.downcast_ref::<Channel>().unwrap().0);
+/// ```
+pub trait PrivateCommandData: Any + Debug + Send + Sync {
+    /// The 32-bit `identifier` of the owner of the command.
+    fn identifier(&self) -> u32;
+
+    /// The private bytes of the command (i.e. the bytes that follow the `identifier`).
+    fn private_bytes(&self) -> Result<Vec<u8>, EncodeError>;
+}
+
+impl dyn PrivateCommandData {
+    /// The concrete data, if it is of type `T`.
+    pub fn downcast_ref<T: PrivateCommandData>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
+
+type PrivateCommandDecoder =
+    Arc<dyn Fn(&[u8]) -> Result<Box<dyn PrivateCommandData>, ParseError> + Send + Sync>;
+
+/// A set of decoders for the `private_bytes` of `PrivateCommand`s, keyed by the 32-bit
+/// `identifier` of the commands that they understand (see `PrivateCommandData`).
+#[derive(Clone, Default)]
+pub struct PrivateCommandRegistry {
+    decoders: HashMap<u32, PrivateCommandDecoder>,
+}
+
+impl PrivateCommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the decoder for `identifier`, replacing any decoder that was previously
+    /// registered for it.
+    pub fn register<F>(mut self, identifier: u32, decoder: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<Box<dyn PrivateCommandData>, ParseError> + Send + Sync + 'static,
+    {
+        self.decoders.insert(identifier, Arc::new(decoder));
+        self
+    }
+
+    /// Decodes the `private_bytes` of `private_command` with the decoder registered for its
+    /// `identifier`, returning `None` when there is no such decoder.
+    pub fn decode(
+        &self,
+        private_command: &PrivateCommand,
+    ) -> Option<Result<Box<dyn PrivateCommandData>, ParseError>> {
+        let decoder = self.decoders.get(&private_command.identifier_value()?)?;
+        Some(decoder(&private_command.private_bytes))
+    }
+}
+
+impl Debug for PrivateCommandRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("PrivateCommandRegistry")
+            .field("identifiers", &self.decoders.keys())
+            .finish()
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
     bit_reader::Bits,
-    error::ParseError,
-    time::{BreakDuration, SpliceTime},
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+    time::{BreakDuration, ComponentSpliceTime, Pts90k, SpliceTime},
 };
 
 /// The `SpliceInsert` command shall be sent at least once for every splice event.
@@ -36,7 +37,9 @@ splice_insert() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SpliceInsert {
     /// A 32-bit unique splice event identifier.
     pub event_id: u32,
@@ -48,11 +51,18 @@ impl SpliceInsert {
     /// When set to `true` indicates that a previously sent splice event, identified by `event_id`,
     /// has been cancelled.
     pub fn is_cancelled(&self) -> bool {
-        self.scheduled_event == None
+        self.scheduled_event.is_none()
+    }
+
+    /// Creates a `SpliceInsertBuilder` for the given `event_id`.
+    pub fn builder(event_id: u32) -> SpliceInsertBuilder {
+        SpliceInsertBuilder::new(event_id)
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ScheduledEvent {
     /// When set to `true`, indicates that the splice event is an opportunity to exit from the
     /// network feed and that the value of `splice_time`, as modified by `pts_adjustment`, shall
@@ -91,7 +101,9 @@ pub struct ScheduledEvent {
 }
 
 /// Information on the type of splice message.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SpliceMode {
     /// Indicates that the message refers to a Program Splice Point and that the mode is the
     /// Program Splice Mode whereby all PIDs/components of the program are to be spliced.
@@ -103,7 +115,9 @@ pub enum SpliceMode {
 
 /// Indicates that the message refers to a Program Splice Point and that the mode is the Program
 /// Splice Mode whereby all PIDs/components of the program are to be spliced.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProgramMode {
     /// The `SpliceTime` structure, when modified by `pts_adjustment`, specifies the time of the
     /// splice event.
@@ -112,7 +126,9 @@ pub struct ProgramMode {
 
 /// Indicates that the mode is the Component Splice Mode whereby each component that is intended to
 /// be spliced will be listed separately by the syntax that follows.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ComponentMode {
     /// An 8-bit value that identifies the elementary PID stream containing the Splice Point
     /// specified by the value of `splice_time` that follows. The value shall be the same as the
@@ -124,11 +140,271 @@ pub struct ComponentMode {
     pub splice_time: Option<SpliceTime>,
 }
 
+impl ScheduledEvent {
+    /// The time at which the first splice of the event takes effect, with the `pts_adjustment`
+    /// added: the `splice_time` in Program Splice Mode, or the earliest `splice_time` of the
+    /// components in Component Splice Mode (taking a wrap of the clock into account). An
+    /// immediate splice takes effect at the `arrival_pts` of the message.
+    /// ```
+    /// use scte35::{splice_command::splice_insert::SpliceInsert, time::Pts90k};
+    ///
+    /// let splice_insert = SpliceInsert::builder(1)
+    ///     .component(1, Some(Pts90k(0x30)))
+    ///     .component(2, Some(Pts90k(0x20)))
+    ///     .build()
+    ///     .unwrap();
+    /// let scheduled_event = splice_insert.scheduled_event.unwrap();
+    /// assert_eq!(
+    ///     Pts90k(0x120),
+    ///     scheduled_event.earliest_splice_time(Pts90k(0x100), Pts90k(0))
+    /// );
+    /// assert_eq!(
+    ///     Some(Pts90k(0x130)),
+    ///     scheduled_event.component_splice_time(1, Pts90k(0x100), Pts90k(0))
+    /// );
+    /// ```
+    pub fn earliest_splice_time(&self, pts_adjustment: Pts90k, arrival_pts: Pts90k) -> Pts90k {
+        match &self.splice_mode {
+            SpliceMode::ProgramSpliceMode(program) => {
+                self.resolve(program.splice_time.as_ref(), pts_adjustment, arrival_pts)
+                    .0
+            }
+            SpliceMode::ComponentSpliceMode(_) => self
+                .component_splice_times(pts_adjustment, arrival_pts)
+                .into_iter()
+                .map(|component| component.pts)
+                .min_by(|a, b| a.wrapping_cmp(*b))
+                .unwrap_or(arrival_pts),
+        }
+    }
+
+    /// The time at which each component is spliced in Component Splice Mode, in the order in
+    /// which they are listed (see `ComponentSpliceTime`). This is empty in Program Splice Mode,
+    /// where all of the components are spliced at the same time (see `component_splice_time`).
+    pub fn component_splice_times(
+        &self,
+        pts_adjustment: Pts90k,
+        arrival_pts: Pts90k,
+    ) -> Vec<ComponentSpliceTime> {
+        let SpliceMode::ComponentSpliceMode(components) = &self.splice_mode else {
+            return vec![];
+        };
+        components
+            .iter()
+            .map(|component| {
+                let (pts, is_immediate) =
+                    self.resolve(component.splice_time.as_ref(), pts_adjustment, arrival_pts);
+                ComponentSpliceTime {
+                    component_tag: component.component_tag,
+                    pts,
+                    is_immediate,
+                }
+            })
+            .collect()
+    }
+
+    /// The time at which the component with the `component_tag` is spliced, with the
+    /// `pts_adjustment` added (or the `arrival_pts` for an immediate splice). In Program Splice
+    /// Mode every component is spliced at the `splice_time`, and in Component Splice Mode this is
+    /// `None` when the component is not listed (and so is not spliced).
+    pub fn component_splice_time(
+        &self,
+        component_tag: u8,
+        pts_adjustment: Pts90k,
+        arrival_pts: Pts90k,
+    ) -> Option<Pts90k> {
+        match &self.splice_mode {
+            SpliceMode::ProgramSpliceMode(_) => {
+                Some(self.earliest_splice_time(pts_adjustment, arrival_pts))
+            }
+            SpliceMode::ComponentSpliceMode(_) => self
+                .component_splice_times(pts_adjustment, arrival_pts)
+                .into_iter()
+                .find(|component| component.component_tag == component_tag)
+                .map(|component| component.pts),
+        }
+    }
+
+    // The adjusted `pts_time` of the `splice_time`, or the `arrival_pts` when the splice is
+    // immediate, along with whether it is immediate.
+    fn resolve(
+        &self,
+        splice_time: Option<&SpliceTime>,
+        pts_adjustment: Pts90k,
+        arrival_pts: Pts90k,
+    ) -> (Pts90k, bool) {
+        match splice_time.and_then(|splice_time| splice_time.pts_time) {
+            Some(pts_time) if !self.is_immediate_splice => (pts_time + pts_adjustment, false),
+            _ => (arrival_pts, true),
+        }
+    }
+}
+
+/// A fluent builder for the `SpliceInsert` command.
+///
+/// By default the builder creates a Program Splice Mode message with no `splice_time` specified.
+/// Adding a component (via `component`) switches the message to the Component Splice Mode, and
+/// setting `immediate` removes all `splice_time` values from the message.
+///
+/// ```
+/// use scte35::{splice_command::splice_insert::SpliceInsert, time::Pts90k};
+///
+/// let splice_insert = SpliceInsert::builder(1207959695)
+///     .cue_out()
+///     .splice_time(Pts90k(1936310318))
+///     .break_duration(Pts90k(5426421), true)
+///     .build()
+///     .unwrap();
+/// assert!(splice_insert.scheduled_event.unwrap().out_of_network_indicator);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SpliceInsertBuilder {
+    event_id: u32,
+    is_cancelled: bool,
+    out_of_network_indicator: bool,
+    is_immediate_splice: bool,
+    pts_time: Option<Pts90k>,
+    components: Option<Vec<(u8, Option<Pts90k>)>>,
+    break_duration: Option<BreakDuration>,
+    unique_program_id: u16,
+    avail_num: u8,
+    avails_expected: u8,
+}
+
+impl SpliceInsertBuilder {
+    pub fn new(event_id: u32) -> Self {
+        Self {
+            event_id,
+            is_cancelled: false,
+            out_of_network_indicator: false,
+            is_immediate_splice: false,
+            pts_time: None,
+            components: None,
+            break_duration: None,
+            unique_program_id: 0,
+            avail_num: 0,
+            avails_expected: 0,
+        }
+    }
+
+    /// Marks the splice event, identified by `event_id`, as cancelled. All other properties are
+    /// ignored when building a cancelled command.
+    pub fn cancel(mut self) -> Self {
+        self.is_cancelled = true;
+        self
+    }
+
+    pub fn out_of_network_indicator(mut self, out_of_network_indicator: bool) -> Self {
+        self.out_of_network_indicator = out_of_network_indicator;
+        self
+    }
+
+    /// An opportunity to exit from the network feed (`out_of_network_indicator` set to `true`).
+    pub fn cue_out(self) -> Self {
+        self.out_of_network_indicator(true)
+    }
+
+    /// An opportunity to return to the network feed (`out_of_network_indicator` set to `false`).
+    pub fn cue_in(self) -> Self {
+        self.out_of_network_indicator(false)
+    }
+
+    /// Uses the Splice Immediate Mode, whereby no `splice_time` is included in the message.
+    pub fn immediate(mut self) -> Self {
+        self.is_immediate_splice = true;
+        self
+    }
+
+    /// Sets the `pts_time` of the `splice_time` for the Program Splice Mode.
+    pub fn splice_time(mut self, pts_time: Pts90k) -> Self {
+        self.pts_time = Some(pts_time);
+        self
+    }
+
+    /// Appends a component, switching the message to the Component Splice Mode. The `pts_time` is
+    /// ignored when the splice is immediate.
+    pub fn component(mut self, component_tag: u8, pts_time: Option<Pts90k>) -> Self {
+        self.components
+            .get_or_insert_with(Vec::new)
+            .push((component_tag, pts_time));
+        self
+    }
+
+    /// Sets the `break_duration` in terms of ticks of the program's 90 kHz clock.
+    pub fn break_duration(mut self, duration: Pts90k, auto_return: bool) -> Self {
+        self.break_duration = Some(BreakDuration {
+            auto_return,
+            duration,
+        });
+        self
+    }
+
+    pub fn unique_program_id(mut self, unique_program_id: u16) -> Self {
+        self.unique_program_id = unique_program_id;
+        self
+    }
+
+    pub fn avail_num(mut self, avail_num: u8) -> Self {
+        self.avail_num = avail_num;
+        self
+    }
+
+    pub fn avails_expected(mut self, avails_expected: u8) -> Self {
+        self.avails_expected = avails_expected;
+        self
+    }
+
+    /// Builds the `SpliceInsert`, validating that it can be encoded.
+    pub fn build(self) -> Result<SpliceInsert, EncodeError> {
+        let is_immediate_splice = self.is_immediate_splice;
+        let splice_time = |pts_time: Option<Pts90k>| {
+            if is_immediate_splice {
+                None
+            } else {
+                Some(SpliceTime { pts_time })
+            }
+        };
+        let scheduled_event = if self.is_cancelled {
+            None
+        } else {
+            let splice_mode = match self.components {
+                Some(components) => SpliceMode::ComponentSpliceMode(
+                    components
+                        .into_iter()
+                        .map(|(component_tag, pts_time)| ComponentMode {
+                            component_tag,
+                            splice_time: splice_time(pts_time),
+                        })
+                        .collect(),
+                ),
+                None => SpliceMode::ProgramSpliceMode(ProgramMode {
+                    splice_time: splice_time(self.pts_time),
+                }),
+            };
+            Some(ScheduledEvent {
+                out_of_network_indicator: self.out_of_network_indicator,
+                is_immediate_splice,
+                splice_mode,
+                break_duration: self.break_duration,
+                unique_program_id: self.unique_program_id,
+                avail_num: self.avail_num,
+                avails_expected: self.avails_expected,
+            })
+        };
+        let splice_insert = SpliceInsert {
+            event_id: self.event_id,
+            scheduled_event,
+        };
+        splice_insert.encode(&mut BitWriter::new())?;
+        Ok(splice_insert)
+    }
+}
+
 impl SpliceInsert {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let event_id = bits.u32(32);
-        let is_splice_event_cancelled = bits.bool();
-        bits.consume(7);
+        let event_id = bits.u32("event_id", 32)?;
+        let is_splice_event_cancelled = bits.bool("splice_event_cancel_indicator")?;
+        bits.consume(7)?;
         if is_splice_event_cancelled {
             Ok(Self {
                 event_id,
@@ -141,48 +417,60 @@ impl SpliceInsert {
             })
         }
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.u32(32, self.event_id);
+        bits.bool(self.scheduled_event.is_none());
+        bits.reserved(7);
+        if let Some(scheduled_event) = &self.scheduled_event {
+            scheduled_event.encode(bits)?;
+        }
+        Ok(())
+    }
 }
 
 impl ScheduledEvent {
     fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let out_of_network_indicator = bits.bool();
-        let program_splice_flag = bits.bool();
-        let duration_flag = bits.bool();
-        let splice_immediate_flag = bits.bool();
-        bits.consume(4);
+        let out_of_network_indicator = bits.bool("out_of_network_indicator")?;
+        let program_splice_flag = bits.bool("program_splice_flag")?;
+        let duration_flag = bits.bool("duration_flag")?;
+        let splice_immediate_flag = bits.bool("splice_immediate_flag")?;
+        bits.consume(4)?;
         let splice_mode = if program_splice_flag {
             SpliceMode::ProgramSpliceMode(ProgramMode {
                 splice_time: if splice_immediate_flag {
                     None
                 } else {
-                    Some(SpliceTime::try_from(bits)?)
+                    Some(bits.scope("splice_time", SpliceTime::try_from)?)
                 },
             })
         } else {
-            let component_count = bits.byte();
+            let component_count = bits.byte("component_count")?;
             let mut components = vec![];
-            for _ in 0..component_count {
-                let component_tag = bits.byte();
-                let component = ComponentMode {
-                    component_tag,
-                    splice_time: if splice_immediate_flag {
-                        None
-                    } else {
-                        Some(SpliceTime::try_from(bits)?)
-                    },
-                };
+            for index in 0..component_count as usize {
+                let component = bits.scope_at("component", Some(index), |bits| {
+                    let component_tag = bits.byte("component_tag")?;
+                    Ok(ComponentMode {
+                        component_tag,
+                        splice_time: if splice_immediate_flag {
+                            None
+                        } else {
+                            Some(bits.scope("splice_time", SpliceTime::try_from)?)
+                        },
+                    })
+                })?;
                 components.push(component);
             }
             SpliceMode::ComponentSpliceMode(components)
         };
         let break_duration = if duration_flag {
-            Some(BreakDuration::try_from(bits)?)
+            Some(bits.scope("break_duration", BreakDuration::try_from)?)
         } else {
             None
         };
-        let unique_program_id = bits.u16(16);
-        let avail_num = bits.byte();
-        let avails_expected = bits.byte();
+        let unique_program_id = bits.u16("unique_program_id", 16)?;
+        let avail_num = bits.byte("avail_num")?;
+        let avails_expected = bits.byte("avails_expected")?;
 
         Ok(Self {
             out_of_network_indicator,
@@ -194,4 +482,47 @@ impl ScheduledEvent {
             avails_expected,
         })
     }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        let program_splice_flag = matches!(self.splice_mode, SpliceMode::ProgramSpliceMode(_));
+        bits.bool(self.out_of_network_indicator);
+        bits.bool(program_splice_flag);
+        bits.bool(self.break_duration.is_some());
+        bits.bool(self.is_immediate_splice);
+        bits.reserved(4);
+        match &self.splice_mode {
+            SpliceMode::ProgramSpliceMode(program_mode) => {
+                if !self.is_immediate_splice {
+                    encode_splice_time(bits, &program_mode.splice_time)?;
+                }
+            }
+            SpliceMode::ComponentSpliceMode(components) => {
+                bits.validate_length(components.len(), 255, "SpliceInsert component_count")?;
+                bits.byte(components.len() as u8);
+                for component in components {
+                    bits.byte(component.component_tag);
+                    if !self.is_immediate_splice {
+                        encode_splice_time(bits, &component.splice_time)?;
+                    }
+                }
+            }
+        }
+        if let Some(break_duration) = &self.break_duration {
+            break_duration.encode(bits)?;
+        }
+        bits.u16(16, self.unique_program_id);
+        bits.byte(self.avail_num);
+        bits.byte(self.avails_expected);
+        Ok(())
+    }
+}
+
+fn encode_splice_time(
+    bits: &mut BitWriter,
+    splice_time: &Option<SpliceTime>,
+) -> Result<(), EncodeError> {
+    match splice_time {
+        Some(splice_time) => splice_time.encode(bits),
+        None => SpliceTime { pts_time: None }.encode(bits),
+    }
 }
@@ -1,4 +1,10 @@
-use crate::{bit_reader::Bits, error::ParseError, time::BreakDuration};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+    time::{system_time_from_utc_splice_time, BreakDuration},
+};
+use std::time::SystemTime;
 
 /// The `SpliceSchedule` command is provided to allow a schedule of splice events to be conveyed
 /// in advance.
@@ -34,12 +40,16 @@ splice_schedule() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SpliceSchedule {
     pub events: Vec<Event>,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Event {
     /// A 32-bit unique splice event identifier.
     pub event_id: u32,
@@ -51,11 +61,13 @@ impl Event {
     /// When set to `true` indicates that a previously sent splice event, identified by `event_id`,
     /// has been cancelled.
     pub fn is_cancelled(&self) -> bool {
-        self.scheduled_event == None
+        self.scheduled_event.is_none()
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ScheduledEvent {
     /// When set to `true`, indicates that the splice event is an opportunity to exit from the
     /// network feed and that the value of `utc_splice_time` shall refer to an intended out point
@@ -84,7 +96,9 @@ pub struct ScheduledEvent {
 }
 
 /// Information on the type of splice message.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SpliceMode {
     /// Indicates that the message refers to a Program Splice Point and that the mode is the
     /// Program Splice Mode whereby all PIDs/components of the program are to be spliced.
@@ -96,7 +110,9 @@ pub enum SpliceMode {
 
 /// Indicates that the message refers to a Program Splice Point and that the mode is the Program
 /// Splice Mode whereby all PIDs/components of the program are to be spliced.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProgramMode {
     /// A 32-bit unsigned integer quantity representing the time of the signalled splice event as
     /// the number of seconds since 00 hours coordinated universal time (UTC), January 6th, 1980,
@@ -108,7 +124,9 @@ pub struct ProgramMode {
 
 /// Indicates that the mode is the Component Splice Mode whereby each component that is intended to
 /// be spliced will be listed separately by the syntax that follows.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ComponentMode {
     /// An 8-bit value that identifies the elementary PID stream containing the Splice Point
     /// specified by the value of `utc_splice_time` that follows. The value shall be the same as
@@ -123,22 +141,57 @@ pub struct ComponentMode {
     pub utc_splice_time: u32,
 }
 
+impl ProgramMode {
+    /// The `utc_splice_time` as a `SystemTime`.
+    pub fn splice_system_time(&self) -> SystemTime {
+        system_time_from_utc_splice_time(self.utc_splice_time)
+    }
+
+    /// The `utc_splice_time` as a `chrono::DateTime`.
+    #[cfg(feature = "chrono")]
+    pub fn splice_date_time(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::time::date_time_from_utc_splice_time(self.utc_splice_time)
+    }
+}
+
+impl ComponentMode {
+    /// The `utc_splice_time` as a `SystemTime`.
+    pub fn splice_system_time(&self) -> SystemTime {
+        system_time_from_utc_splice_time(self.utc_splice_time)
+    }
+
+    /// The `utc_splice_time` as a `chrono::DateTime`.
+    #[cfg(feature = "chrono")]
+    pub fn splice_date_time(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::time::date_time_from_utc_splice_time(self.utc_splice_time)
+    }
+}
+
 impl SpliceSchedule {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let splice_count = bits.byte();
+        let splice_count = bits.byte("splice_count")?;
         let mut events = vec![];
-        for _ in 0..splice_count {
-            events.push(Event::try_from(bits)?);
+        for index in 0..splice_count as usize {
+            events.push(bits.scope_at("event", Some(index), Event::try_from)?);
         }
         Ok(Self { events })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.validate_length(self.events.len(), 255, "SpliceSchedule splice_count")?;
+        bits.byte(self.events.len() as u8);
+        for event in &self.events {
+            event.encode(bits)?;
+        }
+        Ok(())
+    }
 }
 
 impl Event {
     fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let event_id = bits.u32(32);
-        let is_cancelled = bits.bool();
-        bits.consume(7);
+        let event_id = bits.u32("event_id", 32)?;
+        let is_cancelled = bits.bool("splice_event_cancel_indicator")?;
+        bits.consume(7)?;
         if is_cancelled {
             Ok(Self {
                 event_id,
@@ -151,24 +204,34 @@ impl Event {
             })
         }
     }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.u32(32, self.event_id);
+        bits.bool(self.scheduled_event.is_none());
+        bits.reserved(7);
+        if let Some(scheduled_event) = &self.scheduled_event {
+            scheduled_event.encode(bits)?;
+        }
+        Ok(())
+    }
 }
 
 impl ScheduledEvent {
     fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let out_of_network_indicator = bits.bool();
-        let program_splice_flag = bits.bool();
-        let duration_flag = bits.bool();
-        bits.consume(5);
+        let out_of_network_indicator = bits.bool("out_of_network_indicator")?;
+        let program_splice_flag = bits.bool("program_splice_flag")?;
+        let duration_flag = bits.bool("duration_flag")?;
+        bits.consume(5)?;
         let splice_mode = if program_splice_flag {
             SpliceMode::ProgramSpliceMode(ProgramMode {
-                utc_splice_time: bits.u32(32),
+                utc_splice_time: bits.u32("utc_splice_time", 32)?,
             })
         } else {
-            let component_count = bits.byte();
+            let component_count = bits.byte("component_count")?;
             let mut components = vec![];
             for _ in 0..component_count {
-                let component_tag = bits.byte();
-                let utc_splice_time = bits.u32(32);
+                let component_tag = bits.byte("component_tag")?;
+                let utc_splice_time = bits.u32("utc_splice_time", 32)?;
                 components.push(ComponentMode {
                     component_tag,
                     utc_splice_time,
@@ -177,13 +240,13 @@ impl ScheduledEvent {
             SpliceMode::ComponentSpliceMode(components)
         };
         let break_duration = if duration_flag {
-            Some(BreakDuration::try_from(bits)?)
+            Some(bits.scope("break_duration", BreakDuration::try_from)?)
         } else {
             None
         };
-        let unique_program_id = bits.u16(16);
-        let avail_num = bits.byte();
-        let avails_expected = bits.byte();
+        let unique_program_id = bits.u16("unique_program_id", 16)?;
+        let avail_num = bits.byte("avail_num")?;
+        let avails_expected = bits.byte("avails_expected")?;
         Ok(Self {
             out_of_network_indicator,
             splice_mode,
@@ -193,4 +256,31 @@ impl ScheduledEvent {
             avails_expected,
         })
     }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.bool(self.out_of_network_indicator);
+        bits.bool(matches!(self.splice_mode, SpliceMode::ProgramSpliceMode(_)));
+        bits.bool(self.break_duration.is_some());
+        bits.reserved(5);
+        match &self.splice_mode {
+            SpliceMode::ProgramSpliceMode(program_mode) => {
+                bits.u32(32, program_mode.utc_splice_time);
+            }
+            SpliceMode::ComponentSpliceMode(components) => {
+                bits.validate_length(components.len(), 255, "SpliceSchedule component_count")?;
+                bits.byte(components.len() as u8);
+                for component in components {
+                    bits.byte(component.component_tag);
+                    bits.u32(32, component.utc_splice_time);
+                }
+            }
+        }
+        if let Some(break_duration) = &self.break_duration {
+            break_duration.encode(bits)?;
+        }
+        bits.u16(16, self.unique_program_id);
+        bits.byte(self.avail_num);
+        bits.byte(self.avails_expected);
+        Ok(())
+    }
 }
@@ -1,4 +1,9 @@
-use crate::{bit_reader::Bits, error::ParseError, time::SpliceTime};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+    time::SpliceTime,
+};
 
 /// The `TimeSignal` provides a time synchronized data delivery mechanism. The syntax of the
 /// `TimeSignal` allows for the synchronization of the information carried in this message with the
@@ -17,7 +22,9 @@ time_signal() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TimeSignal {
     /// The `SpliceTime` structure, when modified by `pts_adjustment`, specifies the time of the
     /// splice event.
@@ -25,14 +32,18 @@ pub struct TimeSignal {
 }
 impl TimeSignal {
     pub fn is_immediate(&self) -> bool {
-        self.splice_time.pts_time == None
+        self.splice_time.pts_time.is_none()
     }
 }
 
 impl TimeSignal {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         Ok(Self {
-            splice_time: SpliceTime::try_from(bits)?,
+            splice_time: bits.scope("splice_time", SpliceTime::try_from)?,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        self.splice_time.encode(bits)
+    }
 }
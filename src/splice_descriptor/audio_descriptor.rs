@@ -1,9 +1,11 @@
-use super::DescriptorLengthExpectation;
+use super::{encode_with_descriptor_length, DescriptorLengthExpectation};
 use crate::{
     atsc::{AudioCodingMode, BitStreamMode},
     bit_reader::Bits,
-    error::ParseError,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
 };
+use std::{fmt::Display, str::FromStr};
 
 /// The `AudioDescriptor` should be used when programmers and/or MVPDs do not support dynamic
 /// signaling (e.g., signaling of audio language changes) and with legacy audio formats that do not
@@ -32,7 +34,9 @@ use crate::{
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AudioDescriptor {
     /// This 32-bit number is used to identify the owner of the descriptor. The identifier shall
     /// have a value of 0x43554549 (ASCII “CUEI”).
@@ -41,7 +45,9 @@ pub struct AudioDescriptor {
     pub components: Vec<Component>,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Component {
     /// An optional 8-bit value that identifies the elementary PID stream containing the audio
     /// channel that follows. If used, the value shall be the same as the value used in the
@@ -51,7 +57,7 @@ pub struct Component {
     pub component_tag: u8,
     /// This field is a 3-byte language code defining the language of this audio service which
     /// shall correspond to a registered language code contained in the Code column of the
-    /// [ISO 639-2] registry.
+    /// [ISO 639-2] registry (see `language`).
     pub iso_code: u32,
     /// This is a 3-bit field that is set to the same value as the bsmod field in the AC-3
     /// elementary stream.
@@ -74,18 +80,95 @@ pub struct Component {
     pub full_srvc_audio: bool,
 }
 
+/// A three letter language code from the [ISO 639-2] registry (e.g. "eng"), as carried in the
+/// `iso_code` of a `Component`. The code must consist of the lowercase letters a to z.
+/// ```
+/// use scte35::splice_descriptor::audio_descriptor::LanguageCode;
+///
+/// let language: LanguageCode = "eng".parse().unwrap();
+/// assert_eq!("eng", language.as_str());
+/// assert_eq!(0x656E67, language.iso_code());
+/// assert_eq!(Ok(language), LanguageCode::try_from(0x656E67));
+/// assert!("EN".parse::<LanguageCode>().is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct LanguageCode([u8; 3]);
+
+impl LanguageCode {
+    pub fn as_str(&self) -> &str {
+        // The bytes are validated to be ASCII letters when created.
+        std::str::from_utf8(&self.0).expect("language code should be ASCII")
+    }
+
+    /// The value of the code as a 24-bit `iso_code`.
+    pub fn iso_code(&self) -> u32 {
+        u32::from_be_bytes([0, self.0[0], self.0[1], self.0[2]])
+    }
+
+    fn try_from_bytes(bytes: [u8; 3]) -> Option<Self> {
+        if bytes.iter().all(u8::is_ascii_lowercase) {
+            Some(Self(bytes))
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<u32> for LanguageCode {
+    type Error = ParseError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        let [high, first, second, third] = value.to_be_bytes();
+        if high != 0 {
+            return Err(ParseError::InvalidISO639LanguageCode(format!(
+                "0x{:X}",
+                value
+            )));
+        }
+        Self::try_from_bytes([first, second, third])
+            .ok_or_else(|| ParseError::InvalidISO639LanguageCode(format!("0x{:06X}", value)))
+    }
+}
+
+impl From<LanguageCode> for u32 {
+    fn from(value: LanguageCode) -> Self {
+        value.iso_code()
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <[u8; 3]>::try_from(s.as_bytes())
+            .ok()
+            .and_then(Self::try_from_bytes)
+            .ok_or_else(|| ParseError::InvalidISO639LanguageCode(String::from(s)))
+    }
+}
+
+impl Display for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
 /// This is a 4-bit field that indicates the number of channels in the AC-3 elementary stream. When
 /// the MSB is 0, the lower 3 bits are set to the same value as the acmod field in the AC-3
 /// elementary stream. When the MSB field is 1, the lower 3 bits indicate the maximum number of
 /// encoded audio channels (counting the lfe channel as 1).
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum NumChannels {
     AudioCodingMode(AudioCodingMode),
     MaxNumberOfEncodedChannels(MaxNumberOfEncodedChannels),
 }
 
 /// Indicates the maximum number of encoded audio channels (counting the lfe channel as 1).
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MaxNumberOfEncodedChannels {
     One,
     Two,
@@ -96,7 +179,7 @@ pub enum MaxNumberOfEncodedChannels {
     Unknown(u8),
 }
 impl MaxNumberOfEncodedChannels {
-    fn new(value: u8) -> Self {
+    pub(crate) fn new(value: u8) -> Self {
         match value {
             0 => Self::One,
             1 => Self::Two,
@@ -107,40 +190,76 @@ impl MaxNumberOfEncodedChannels {
             x => Self::Unknown(x),
         }
     }
+
+    pub fn value(&self) -> u8 {
+        match *self {
+            Self::One => 0,
+            Self::Two => 1,
+            Self::Three => 2,
+            Self::Four => 3,
+            Self::Five => 4,
+            Self::Six => 5,
+            Self::Unknown(x) => x,
+        }
+    }
 }
 
 impl AudioDescriptor {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         let expectation = DescriptorLengthExpectation::try_from(bits, "AudioDescriptor")?;
 
-        let identifier = bits.u32(32);
-        let audio_count = bits.u8(4);
-        bits.consume(4);
+        let identifier = bits.u32("identifier", 32)?;
+        let audio_count = bits.u8("audio_count", 4)?;
+        bits.consume(4)?;
         let mut components = vec![];
-        for _ in 0..audio_count {
-            components.push(Component::try_from(bits)?);
+        for index in 0..audio_count as usize {
+            components.push(bits.scope_at("component", Some(index), Component::try_from)?);
         }
 
-        expectation.validate_non_fatal(bits, super::SpliceDescriptorTag::AudioDescriptor);
+        expectation.validate(bits, super::SpliceDescriptorTag::AudioDescriptor)?;
 
         Ok(Self {
             identifier,
             components,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        encode_with_descriptor_length(bits, "AudioDescriptor", |bits| {
+            bits.validate_length(self.components.len(), 15, "AudioDescriptor audio_count")?;
+            bits.u32(32, self.identifier);
+            bits.u8(4, self.components.len() as u8);
+            bits.reserved(4);
+            for component in &self.components {
+                component.encode(bits)?;
+            }
+            Ok(())
+        })
+    }
 }
 
 impl Component {
+    /// The language of the audio service, or `None` when the `iso_code` is not a valid
+    /// `LanguageCode`.
+    pub fn language(&self) -> Option<LanguageCode> {
+        LanguageCode::try_from(self.iso_code).ok()
+    }
+
+    /// Sets the `iso_code` to the `language`.
+    pub fn set_language(&mut self, language: LanguageCode) {
+        self.iso_code = language.iso_code();
+    }
+
     fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let component_tag = bits.byte();
-        let iso_code = bits.u32(24);
-        let bsmod = bits.u8(3);
-        if bits.bool() {
-            let acmod = bits.u8(3);
+        let component_tag = bits.byte("component_tag")?;
+        let iso_code = bits.u32("iso_code", 24)?;
+        let bsmod = bits.u8("bsmod", 3)?;
+        if bits.bool("acmod_flag")? {
+            let acmod = bits.u8("acmod", 3)?;
             let audio_coding_mode = AudioCodingMode::try_from(acmod)?;
             let bit_stream_mode = BitStreamMode::try_from(bsmod, Some(acmod))?;
             let num_channels = NumChannels::AudioCodingMode(audio_coding_mode);
-            let full_srvc_audio = bits.bool();
+            let full_srvc_audio = bits.bool("full_srvc_audio")?;
             Ok(Self {
                 component_tag,
                 iso_code,
@@ -149,11 +268,12 @@ impl Component {
                 full_srvc_audio,
             })
         } else {
-            let max_number_of_encoded_channels = MaxNumberOfEncodedChannels::new(bits.u8(3));
+            let max_number_of_encoded_channels =
+                MaxNumberOfEncodedChannels::new(bits.u8("num_channels", 3)?);
             let bit_stream_mode = BitStreamMode::try_from(bsmod, None)?;
             let num_channels =
                 NumChannels::MaxNumberOfEncodedChannels(max_number_of_encoded_channels);
-            let full_srvc_audio = bits.bool();
+            let full_srvc_audio = bits.bool("full_srvc_audio")?;
             Ok(Self {
                 component_tag,
                 iso_code,
@@ -163,4 +283,25 @@ impl Component {
             })
         }
     }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.validate(24, self.iso_code as u64, "AudioDescriptor iso_code")?;
+        bits.byte(self.component_tag);
+        bits.u32(24, self.iso_code);
+        bits.u8(3, self.bit_stream_mode.value());
+        match &self.num_channels {
+            NumChannels::AudioCodingMode(audio_coding_mode) => {
+                bits.bool(true);
+                bits.u8(3, audio_coding_mode.value());
+            }
+            NumChannels::MaxNumberOfEncodedChannels(max_number_of_encoded_channels) => {
+                let value = max_number_of_encoded_channels.value();
+                bits.validate(3, value as u64, "AudioDescriptor Num_Channels")?;
+                bits.bool(false);
+                bits.u8(3, value);
+            }
+        }
+        bits.bool(self.full_srvc_audio);
+        Ok(())
+    }
 }
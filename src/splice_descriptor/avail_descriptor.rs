@@ -1,5 +1,9 @@
-use super::DescriptorLengthExpectation;
-use crate::{bit_reader::Bits, error::ParseError};
+use super::{encode_with_descriptor_length, DescriptorLengthExpectation};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
 
 /// The `AvailDescriptor` is an implementation of a `SpliceDescriptor`. It provides an optional
 /// extension to the `SpliceInsert` command that allows an authorization identifier to be sent for
@@ -17,7 +21,9 @@ avail_descriptor() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AvailDescriptor {
     /// This 32-bit number is used to identify the owner of the descriptor. The identifier shall
     /// have a value of 0x43554549 (ASCII "CUEI").
@@ -33,14 +39,22 @@ impl AvailDescriptor {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         let expectation = DescriptorLengthExpectation::try_from(bits, "AvailDescriptor")?;
 
-        let identifier = bits.u32(32);
-        let provider_avail_id = bits.u32(32);
+        let identifier = bits.u32("identifier", 32)?;
+        let provider_avail_id = bits.u32("provider_avail_id", 32)?;
 
-        expectation.validate_non_fatal(bits, super::SpliceDescriptorTag::AvailDescriptor);
+        expectation.validate(bits, super::SpliceDescriptorTag::AvailDescriptor)?;
 
         Ok(Self {
             identifier,
             provider_avail_id,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        encode_with_descriptor_length(bits, "AvailDescriptor", |bits| {
+            bits.u32(32, self.identifier);
+            bits.u32(32, self.provider_avail_id);
+            Ok(())
+        })
+    }
 }
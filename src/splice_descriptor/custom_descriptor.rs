@@ -0,0 +1,175 @@
+use crate::error::{EncodeError, ParseError};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// A splice descriptor that is not defined by this library (e.g. a vendor descriptor with a
+/// non-CUEI `identifier`, or a CUEI descriptor with a reserved tag), parsed by a parser registered
+/// in a `SpliceDescriptorRegistry` and carried as `SpliceDescriptor::Custom`.
+///
+/// Implementations only need to derive `Clone` and `Debug` in addition to implementing this
+/// trait; the concrete type can be recovered with `downcast_ref`.
+/// ```
+/// use scte35::{
+///     error::{EncodeError, ParseError},
+///     splice_descriptor::{
+///         custom_descriptor::{CustomSpliceDescriptor, SpliceDescriptorRegistry},
+///         SpliceDescriptor,
+///     },
+///     splice_info_section::SpliceInfoSection,
+/// };
+///
+/// #[derive(Clone, Debug)]
+/// struct ChannelDescriptor {
+///     channel: u16,
+/// }
+///
+/// impl CustomSpliceDescriptor for ChannelDescriptor {
+///     fn tag(&self) -> u8 {
+///         0xF0
+///     }
+///
+///     fn identifier(&self) -> u32 {
+///         0x41424344
+///     }
+///
+///     fn private_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+///         Ok(self.channel.to_be_bytes().to_vec())
+///     }
+/// }
+///
+/// let registry = SpliceDescriptorRegistry::new().register(0xF0, 0x41424344, |private_bytes| {
+///     let channel = private_bytes
+///         .try_into()
+///         .map(u16::from_be_bytes)
+///         .map_err(|_| ParseError::InvalidPrivateData("expected a 16-bit channel"))?;
+///     Ok(Box::new(ChannelDescriptor { channel }))
+/// });
+/// let splice_info_section = SpliceInfoSection::builder()
+///     .splice_descriptor(SpliceDescriptor::Custom(Box::new(ChannelDescriptor { channel: 7 })))
+///     .build()
+///     .unwrap();
+/// let bytes = splice_info_section.try_to_bytes().unwrap();
+/// let parsed = SpliceInfoSection::try_from_bytes_with_registry(&bytes, &registry).unwrap();
+/// let SpliceDescriptor::Custom(descriptor) = &parsed.splice_descriptors[0] else {
+///     panic!("expected custom descriptor");
+/// };
+/// let descriptor = descriptor.downcast_ref::<ChannelDescriptor>().unwrap();
+/// assert_eq!(7, descriptor.channel);
+/// ```
+pub trait CustomSpliceDescriptor: CloneCustomSpliceDescriptor + Any + Debug + Send + Sync {
+    /// The `splice_descriptor_tag` of the descriptor.
+    fn tag(&self) -> u8;
+
+    /// The 32-bit `identifier` of the owner of the descriptor.
+    fn identifier(&self) -> u32;
+
+    /// The private bytes of the descriptor (i.e. the bytes that follow the `identifier`).
+    fn private_bytes(&self) -> Result<Vec<u8>, EncodeError>;
+}
+
+/// Allows a `Box<dyn CustomSpliceDescriptor>` to be cloned. This is implemented for all types
+/// that implement both `CustomSpliceDescriptor` and `Clone`.
+pub trait CloneCustomSpliceDescriptor {
+    fn clone_box(&self) -> Box<dyn CustomSpliceDescriptor>;
+}
+
+impl<T: CustomSpliceDescriptor + Clone> CloneCustomSpliceDescriptor for T {
+    fn clone_box(&self) -> Box<dyn CustomSpliceDescriptor> {
+        Box::new(self.clone())
+    }
+}
+
+impl dyn CustomSpliceDescriptor {
+    /// The concrete descriptor, if it is of type `T`.
+    pub fn downcast_ref<T: CustomSpliceDescriptor>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
+
+impl Clone for Box<dyn CustomSpliceDescriptor> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Custom descriptors are equal when they have the same tag, identifier and private bytes.
+impl PartialEq for Box<dyn CustomSpliceDescriptor> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag() == other.tag()
+            && self.identifier() == other.identifier()
+            && self.private_bytes() == other.private_bytes()
+    }
+}
+
+impl Eq for Box<dyn CustomSpliceDescriptor> {}
+
+/// Custom descriptors are hashed by the same tag, identifier and private bytes that are compared.
+impl Hash for Box<dyn CustomSpliceDescriptor> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag().hash(state);
+        self.identifier().hash(state);
+        self.private_bytes().ok().hash(state);
+    }
+}
+
+type DescriptorParser =
+    dyn Fn(&[u8]) -> Result<Box<dyn CustomSpliceDescriptor>, ParseError> + Send + Sync;
+
+/// A set of parsers for splice descriptors that are not defined by this library, keyed by the
+/// `splice_descriptor_tag` and `identifier` of the descriptors that they understand (see
+/// `SpliceInfoSection::try_from_bytes_with_registry`).
+///
+/// A registered parser takes precedence over the parsing of the descriptors defined in the
+/// specification, and is given the private bytes that follow the `identifier`.
+#[derive(Clone, Default)]
+pub struct SpliceDescriptorRegistry {
+    parsers: HashMap<(u8, u32), Arc<DescriptorParser>>,
+}
+
+impl SpliceDescriptorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the parser for descriptors with the given `tag` and `identifier`, replacing any
+    /// parser that was previously registered for them.
+    pub fn register<F>(mut self, tag: u8, identifier: u32, parser: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<Box<dyn CustomSpliceDescriptor>, ParseError> + Send + Sync + 'static,
+    {
+        self.parsers.insert((tag, identifier), Arc::new(parser));
+        self
+    }
+
+    pub(crate) fn parser(&self, tag: u8, identifier: u32) -> Option<Arc<DescriptorParser>> {
+        self.parsers.get(&(tag, identifier)).cloned()
+    }
+}
+
+impl Debug for SpliceDescriptorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SpliceDescriptorRegistry")
+            .field("keys", &self.parsers.keys())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[allow(clippy::borrowed_box)]
+pub(crate) fn serialize<S: serde::Serializer>(
+    descriptor: &Box<dyn CustomSpliceDescriptor>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::{Error, SerializeStruct};
+    let private_bytes = descriptor.private_bytes().map_err(S::Error::custom)?;
+    let mut state = serializer.serialize_struct("CustomSpliceDescriptor", 3)?;
+    state.serialize_field("tag", &descriptor.tag())?;
+    state.serialize_field("identifier", &descriptor.identifier())?;
+    state.serialize_field("private_bytes", &private_bytes)?;
+    state.end()
+}
@@ -1,5 +1,11 @@
-use super::DescriptorLengthExpectation;
-use crate::{bit_reader::Bits, error::ParseError};
+use super::{encode_with_descriptor_length, DescriptorLengthExpectation};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+    time::{Pts90k, Rounding},
+};
+use std::{fmt::Display, str::FromStr, time::Duration};
 
 /// The `DTMFDescriptor` is an implementation of a `SpliceDescriptor`. It provides an optional
 /// extension to the `SpliceInsert` command that allows a receiver device to generate a legacy
@@ -19,7 +25,9 @@ DTMF_descriptor() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DTMFDescriptor {
     /// This 32-bit number is used to identify the owner of the descriptor. The identifier shall
     /// have a value of 0x43554549 (ASCII "CUEI").
@@ -31,21 +39,270 @@ pub struct DTMFDescriptor {
     pub preroll: u8,
     /// This is a string of ASCII values from the numerals `0` to `9`, `*`, `#`. The string
     /// represents a DTMF sequence to be output on an analogue output. The string shall complete
-    /// with the last character sent being the timing mark for the `preroll`.
+    /// with the last character sent being the timing mark for the `preroll` (see `sequence`).
+    /// The characters are encoded as they are given (so that a section parsed with non-fatal
+    /// invalid characters can be re-encoded), and so should be validated with `sequence` when
+    /// that is required.
     pub dtmf_chars: String,
 }
 
+/// A character of a DTMF sequence, which is generated as a tone of two frequencies.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum DTMFChar {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    /// `*`
+    Star,
+    /// `#`
+    Pound,
+}
+
+impl DTMFChar {
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Zero => '0',
+            Self::One => '1',
+            Self::Two => '2',
+            Self::Three => '3',
+            Self::Four => '4',
+            Self::Five => '5',
+            Self::Six => '6',
+            Self::Seven => '7',
+            Self::Eight => '8',
+            Self::Nine => '9',
+            Self::Star => '*',
+            Self::Pound => '#',
+        }
+    }
+
+    /// The low (row) and high (column) frequencies of the tone, in Hz.
+    pub fn frequencies(&self) -> (u16, u16) {
+        match self {
+            Self::One => (697, 1209),
+            Self::Two => (697, 1336),
+            Self::Three => (697, 1477),
+            Self::Four => (770, 1209),
+            Self::Five => (770, 1336),
+            Self::Six => (770, 1477),
+            Self::Seven => (852, 1209),
+            Self::Eight => (852, 1336),
+            Self::Nine => (852, 1477),
+            Self::Star => (941, 1209),
+            Self::Zero => (941, 1336),
+            Self::Pound => (941, 1477),
+        }
+    }
+}
+
+impl TryFrom<char> for DTMFChar {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0' => Ok(Self::Zero),
+            '1' => Ok(Self::One),
+            '2' => Ok(Self::Two),
+            '3' => Ok(Self::Three),
+            '4' => Ok(Self::Four),
+            '5' => Ok(Self::Five),
+            '6' => Ok(Self::Six),
+            '7' => Ok(Self::Seven),
+            '8' => Ok(Self::Eight),
+            '9' => Ok(Self::Nine),
+            '*' => Ok(Self::Star),
+            '#' => Ok(Self::Pound),
+            x => Err(ParseError::InvalidDTMFChars(x.to_string())),
+        }
+    }
+}
+
+impl Display for DTMFChar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_char().fmt(f)
+    }
+}
+
+/// The `dtmf_chars` of a `DTMFDescriptor` as typed characters, in the order in which the tones
+/// are generated.
+/// ```
+/// use scte35::splice_descriptor::dtmf_descriptor::{DTMFChar, DTMFSequence};
+///
+/// let sequence: DTMFSequence = "121#".parse().unwrap();
+/// assert_eq!(
+///     &[DTMFChar::One, DTMFChar::Two, DTMFChar::One, DTMFChar::Pound],
+///     sequence.chars()
+/// );
+/// assert_eq!(Some((941, 1477)), sequence.chars().last().map(DTMFChar::frequencies));
+/// assert!("12A".parse::<DTMFSequence>().is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Default)]
+pub struct DTMFSequence(Vec<DTMFChar>);
+
+impl DTMFSequence {
+    pub fn new(chars: Vec<DTMFChar>) -> Self {
+        Self(chars)
+    }
+
+    pub fn chars(&self) -> &[DTMFChar] {
+        &self.0
+    }
+}
+
+impl FromStr for DTMFSequence {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .map(DTMFChar::try_from)
+            .collect::<Result<_, _>>()
+            .map(Self)
+            .map_err(|_| ParseError::InvalidDTMFChars(String::from(s)))
+    }
+}
+
+impl Display for DTMFSequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for dtmf_char in &self.0 {
+            dtmf_char.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<DTMFChar>> for DTMFSequence {
+    fn from(value: Vec<DTMFChar>) -> Self {
+        Self(value)
+    }
+}
+
+/// The durations of each tone of a DTMF sequence and of the silence between the tones, which are
+/// not defined by the specification. Defaults to 100 ms for each.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct DTMFToneTiming {
+    pub tone_duration: Duration,
+    pub pause_duration: Duration,
+}
+
+impl Default for DTMFToneTiming {
+    fn default() -> Self {
+        Self {
+            tone_duration: Duration::from_millis(100),
+            pause_duration: Duration::from_millis(100),
+        }
+    }
+}
+
+/// When a tone of a `DTMFSchedule` is generated.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct DTMFTone {
+    pub dtmf_char: DTMFChar,
+    pub start_pts: Pts90k,
+    pub end_pts: Pts90k,
+}
+
+/// When each tone of the DTMF sequence of a `DTMFDescriptor` is generated (see
+/// `DTMFDescriptor::schedule`). The wall clock time of each can be found with
+/// `Arrival::wallclock_at`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct DTMFSchedule {
+    /// The tones, in the order in which they are generated.
+    pub tones: Vec<DTMFTone>,
+    /// The splice PTS minus the `preroll`, which is when the last tone ends.
+    pub end_pts: Pts90k,
+}
+
+impl DTMFSchedule {
+    /// When the first tone starts (or the `end_pts` when there are no tones).
+    pub fn start_pts(&self) -> Pts90k {
+        self.tones
+            .first()
+            .map_or(self.end_pts, |tone| tone.start_pts)
+    }
+}
+
 impl DTMFDescriptor {
+    /// The `preroll` as a `Duration`.
+    pub fn preroll_duration(&self) -> Duration {
+        Duration::from_millis(u64::from(self.preroll) * 100)
+    }
+
+    /// The times at which each tone of the sequence is generated for a splice at the `splice_pts`
+    /// (e.g. the `splice_pts` of the `SpliceInfoSection`, which includes the `pts_adjustment`).
+    /// The last character is the timing mark, and so ends the `preroll` before the splice, with
+    /// the earlier tones laid out before it according to the `timing`. Fails when the `dtmf_chars`
+    /// are not a valid `DTMFSequence`.
+    /// ```
+    /// use scte35::{
+    ///     splice_descriptor::dtmf_descriptor::{DTMFChar, DTMFDescriptor, DTMFToneTiming},
+    ///     time::Pts90k,
+    /// };
+    ///
+    /// let descriptor = DTMFDescriptor {
+    ///     identifier: 0x43554549,
+    ///     preroll: 50,
+    ///     dtmf_chars: String::from("1#"),
+    /// };
+    /// let schedule = descriptor
+    ///     .schedule(Pts90k(900_000), DTMFToneTiming::default())
+    ///     .unwrap();
+    /// // 5 seconds before the splice, with 100 ms tones separated by 100 ms.
+    /// assert_eq!(Pts90k(450_000), schedule.end_pts);
+    /// assert_eq!(DTMFChar::Pound, schedule.tones[1].dtmf_char);
+    /// assert_eq!(Pts90k(441_000), schedule.tones[1].start_pts);
+    /// assert_eq!(Pts90k(423_000), schedule.start_pts());
+    /// ```
+    pub fn schedule(
+        &self,
+        splice_pts: Pts90k,
+        timing: DTMFToneTiming,
+    ) -> Result<DTMFSchedule, ParseError> {
+        let sequence = self.sequence()?;
+        let tone_ticks = Pts90k::from_duration(timing.tone_duration, Rounding::Nearest);
+        let pause_ticks = Pts90k::from_duration(timing.pause_duration, Rounding::Nearest);
+        let end_pts = splice_pts.wrapping_sub(Pts90k(u64::from(self.preroll) * 9_000));
+        let mut tones = Vec::with_capacity(sequence.chars().len());
+        let mut tone_end_pts = end_pts;
+        for dtmf_char in sequence.chars().iter().rev() {
+            let start_pts = tone_end_pts.wrapping_sub(tone_ticks);
+            tones.push(DTMFTone {
+                dtmf_char: *dtmf_char,
+                start_pts,
+                end_pts: tone_end_pts,
+            });
+            tone_end_pts = start_pts.wrapping_sub(pause_ticks);
+        }
+        tones.reverse();
+        Ok(DTMFSchedule { tones, end_pts })
+    }
+
+    /// The `dtmf_chars` as a typed sequence, which fails when they contain a character other than
+    /// `0` to `9`, `*` and `#`.
+    pub fn sequence(&self) -> Result<DTMFSequence, ParseError> {
+        self.dtmf_chars.parse()
+    }
+
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         let expectation = DescriptorLengthExpectation::try_from(bits, "DTMFDescriptor")?;
 
-        let identifier = bits.u32(32);
-        let preroll = bits.byte();
-        let dtmf_count = bits.u8(3) as usize;
-        bits.consume(5);
-        let dtmf_chars = bits.string(dtmf_count, "DTMFDescriptor dtmf_chars")?;
+        let identifier = bits.u32("identifier", 32)?;
+        let preroll = bits.byte("preroll")?;
+        let dtmf_count = bits.u8("dtmf_count", 3)? as usize;
+        bits.consume(5)?;
+        let dtmf_chars = bits.string("dtmf_chars", dtmf_count, "DTMFDescriptor dtmf_chars")?;
+        if let Err(error) = dtmf_chars.parse::<DTMFSequence>() {
+            let handling = bits.config().invalid_dtmf_chars;
+            bits.handle_error(handling, error)?;
+        }
 
-        expectation.validate_non_fatal(bits, super::SpliceDescriptorTag::DTMFDescriptor);
+        expectation.validate(bits, super::SpliceDescriptorTag::DTMFDescriptor)?;
 
         Ok(Self {
             identifier,
@@ -53,4 +310,16 @@ impl DTMFDescriptor {
             dtmf_chars,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        encode_with_descriptor_length(bits, "DTMFDescriptor", |bits| {
+            bits.validate_length(self.dtmf_chars.len(), 7, "DTMFDescriptor dtmf_count")?;
+            bits.u32(32, self.identifier);
+            bits.byte(self.preroll);
+            bits.u8(3, self.dtmf_chars.len() as u8);
+            bits.reserved(5);
+            bits.string(&self.dtmf_chars);
+            Ok(())
+        })
+    }
 }
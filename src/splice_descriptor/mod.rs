@@ -1,16 +1,27 @@
 use self::{
     audio_descriptor::AudioDescriptor, avail_descriptor::AvailDescriptor,
-    dtmf_descriptor::DTMFDescriptor, segmentation_descriptor::SegmentationDescriptor,
-    time_descriptor::TimeDescriptor,
+    custom_descriptor::CustomSpliceDescriptor, dtmf_descriptor::DTMFDescriptor,
+    segmentation_descriptor::SegmentationDescriptor, time_descriptor::TimeDescriptor,
+};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+    splice_command::SpliceCommandType,
+    splice_info_section::ErrorHandling,
 };
-use crate::{bit_reader::Bits, error::ParseError};
 
 pub mod audio_descriptor;
 pub mod avail_descriptor;
+pub mod custom_descriptor;
 pub mod dtmf_descriptor;
 pub mod segmentation_descriptor;
 pub mod time_descriptor;
 
+/// The identifier, 0x43554549 (ASCII "CUEI"), registered with SMPTE for descriptors defined in the
+/// SCTE-35 specification.
+pub const CUEI_IDENTIFIER: u32 = 0x43554549;
+
 /// The `SpliceDescriptor` is a prototype for adding new fields to the `SpliceInfoSection`. All
 /// descriptors included use the same syntax for the first six bytes. In order to allow private
 /// information to be added we have included the `identifier` code. This removes the need for a
@@ -35,7 +46,10 @@ splice_descriptor() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SpliceDescriptor {
     /// The `AvailDescriptor` provides an optional extension to the `SpliceInsert` command that
     /// allows an authorization identifier to be sent for an avail. Multiple copies of this
@@ -71,6 +85,23 @@ pub enum SpliceDescriptor {
     /// descriptor shall only be used with a `TimeSignal` command and a segmentation descriptor
     /// with the type `program_start` or `program_overlap_start`.
     AudioDescriptor(AudioDescriptor),
+    /// A descriptor that is not defined by this library, parsed by a parser registered in a
+    /// `SpliceDescriptorRegistry` (see `custom_descriptor`). This is serialized as its tag,
+    /// identifier and private bytes, and cannot be deserialized.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "custom_descriptor::serialize", skip_deserializing)
+    )]
+    #[cfg_attr(feature = "arbitrary", arbitrary(skip))]
+    Custom(Box<dyn CustomSpliceDescriptor>),
+    /// A descriptor with a tag that is not recognised, which is only produced when parsing with
+    /// `ParserConfig::unknown_enum_values` set to `NonFatal` or `Ignore`. The `private_bytes` are the bytes that
+    /// follow the `identifier`, and are encoded back unchanged.
+    Unknown {
+        tag: u8,
+        identifier: u32,
+        private_bytes: Vec<u8>,
+    },
 }
 impl SpliceDescriptor {
     /// This 8 bit number defines the syntax for the private bytes that make up the body of this
@@ -83,6 +114,8 @@ impl SpliceDescriptor {
             Self::SegmentationDescriptor(_) => SpliceDescriptorTag::SegmentationDescriptor,
             Self::TimeDescriptor(_) => SpliceDescriptorTag::TimeDescriptor,
             Self::AudioDescriptor(_) => SpliceDescriptorTag::AudioDescriptor,
+            Self::Custom(descriptor) => SpliceDescriptorTag::Private(descriptor.tag()),
+            Self::Unknown { tag, .. } => SpliceDescriptorTag::Private(*tag),
         }
     }
 
@@ -102,17 +135,100 @@ impl SpliceDescriptor {
             Self::SegmentationDescriptor(descriptor) => descriptor.identifier,
             Self::TimeDescriptor(descriptor) => descriptor.identifier,
             Self::AudioDescriptor(descriptor) => descriptor.identifier,
+            Self::Custom(descriptor) => descriptor.identifier(),
+            Self::Unknown { identifier, .. } => *identifier,
+        }
+    }
+
+    /// The `SegmentationDescriptor`, when this is one.
+    pub fn as_segmentation(&self) -> Option<&SegmentationDescriptor> {
+        match self {
+            Self::SegmentationDescriptor(descriptor) => Some(descriptor),
+            _ => None,
+        }
+    }
+
+    /// The `AvailDescriptor`, when this is one.
+    pub fn as_avail(&self) -> Option<&AvailDescriptor> {
+        match self {
+            Self::AvailDescriptor(descriptor) => Some(descriptor),
+            _ => None,
+        }
+    }
+
+    /// The `DTMFDescriptor`, when this is one.
+    pub fn as_dtmf(&self) -> Option<&DTMFDescriptor> {
+        match self {
+            Self::DTMFDescriptor(descriptor) => Some(descriptor),
+            _ => None,
+        }
+    }
+
+    /// The `TimeDescriptor`, when this is one.
+    pub fn as_time(&self) -> Option<&TimeDescriptor> {
+        match self {
+            Self::TimeDescriptor(descriptor) => Some(descriptor),
+            _ => None,
+        }
+    }
+
+    /// The `AudioDescriptor`, when this is one.
+    pub fn as_audio(&self) -> Option<&AudioDescriptor> {
+        match self {
+            Self::AudioDescriptor(descriptor) => Some(descriptor),
+            _ => None,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+impl From<SegmentationDescriptor> for SpliceDescriptor {
+    fn from(segmentation_descriptor: SegmentationDescriptor) -> Self {
+        Self::SegmentationDescriptor(segmentation_descriptor)
+    }
+}
+
+impl From<AvailDescriptor> for SpliceDescriptor {
+    fn from(avail_descriptor: AvailDescriptor) -> Self {
+        Self::AvailDescriptor(avail_descriptor)
+    }
+}
+
+impl From<DTMFDescriptor> for SpliceDescriptor {
+    fn from(dtmf_descriptor: DTMFDescriptor) -> Self {
+        Self::DTMFDescriptor(dtmf_descriptor)
+    }
+}
+
+impl From<TimeDescriptor> for SpliceDescriptor {
+    fn from(time_descriptor: TimeDescriptor) -> Self {
+        Self::TimeDescriptor(time_descriptor)
+    }
+}
+
+impl From<AudioDescriptor> for SpliceDescriptor {
+    fn from(audio_descriptor: AudioDescriptor) -> Self {
+        Self::AudioDescriptor(audio_descriptor)
+    }
+}
+
+/// The `splice_descriptor_tag` of a `SpliceDescriptor`.
+///
+/// This is `non_exhaustive` so that descriptors defined by future revisions of the specification
+/// can be added without a breaking change. Until then their tags are represented by `Private`
+/// (see `from_value`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SpliceDescriptorTag {
     AvailDescriptor,
     DTMFDescriptor,
     SegmentationDescriptor,
     TimeDescriptor,
     AudioDescriptor,
+    /// The tag of a descriptor that is not defined by this library (see
+    /// `SpliceDescriptor::Custom` and `SpliceDescriptor::Unknown`).
+    Private(u8),
 }
 
 impl TryFrom<u8> for SpliceDescriptorTag {
@@ -131,6 +247,17 @@ impl TryFrom<u8> for SpliceDescriptorTag {
 }
 
 impl SpliceDescriptorTag {
+    /// The tag for `value`, which is `Private` when `value` is not defined by the specification.
+    /// ```
+    /// use scte35::splice_descriptor::SpliceDescriptorTag;
+    ///
+    /// assert_eq!(SpliceDescriptorTag::TimeDescriptor, SpliceDescriptorTag::from_value(0x03));
+    /// assert_eq!(SpliceDescriptorTag::Private(0xF0), SpliceDescriptorTag::from_value(0xF0));
+    /// ```
+    pub fn from_value(value: u8) -> Self {
+        Self::try_from(value).unwrap_or(Self::Private(value))
+    }
+
     pub fn value(&self) -> u8 {
         match *self {
             SpliceDescriptorTag::AvailDescriptor => 0x00,
@@ -138,6 +265,29 @@ impl SpliceDescriptorTag {
             SpliceDescriptorTag::SegmentationDescriptor => 0x02,
             SpliceDescriptorTag::TimeDescriptor => 0x03,
             SpliceDescriptorTag::AudioDescriptor => 0x04,
+            SpliceDescriptorTag::Private(value) => value,
+        }
+    }
+
+    /// Indicates whether a descriptor with this tag may be carried with the given splice command,
+    /// as described in the documentation of each descriptor.
+    pub fn is_allowed_with(&self, splice_command_type: &SpliceCommandType) -> bool {
+        match self {
+            SpliceDescriptorTag::AvailDescriptor | SpliceDescriptorTag::DTMFDescriptor => {
+                *splice_command_type == SpliceCommandType::SpliceInsert
+            }
+            SpliceDescriptorTag::SegmentationDescriptor | SpliceDescriptorTag::TimeDescriptor => {
+                matches!(
+                    splice_command_type,
+                    SpliceCommandType::TimeSignal
+                        | SpliceCommandType::SpliceInsert
+                        | SpliceCommandType::SpliceNull
+                )
+            }
+            SpliceDescriptorTag::AudioDescriptor => {
+                *splice_command_type == SpliceCommandType::TimeSignal
+            }
+            SpliceDescriptorTag::Private(_) => true,
         }
     }
 }
@@ -150,7 +300,7 @@ struct DescriptorLengthExpectation {
 
 impl DescriptorLengthExpectation {
     fn try_from(bits: &mut Bits, validation_description: &'static str) -> Result<Self, ParseError> {
-        let descriptor_bits_length = bits.u32(8) * 8;
+        let descriptor_bits_length = bits.u32("descriptor_length", 8)? * 8;
         bits.validate(descriptor_bits_length, validation_description)?;
         let bits_remaining_before_descriptor = bits.bits_remaining() as isize;
         let expected_bits_remaining_after_descriptor =
@@ -163,18 +313,64 @@ impl DescriptorLengthExpectation {
         })
     }
 
-    fn validate_non_fatal(&self, bits: &mut Bits, splice_descriptor_tag: SpliceDescriptorTag) {
+    fn validate(
+        &self,
+        bits: &mut Bits,
+        splice_descriptor_tag: SpliceDescriptorTag,
+    ) -> Result<(), ParseError> {
         let bits_remaining = bits.bits_remaining();
         if self.expected_bits_remaining_after_descriptor != (bits_remaining as isize) {
-            bits.push_non_fatal_error(ParseError::UnexpectedSpliceDescriptorLength {
-                declared_splice_descriptor_length_in_bits: self.descriptor_bits_length,
-                actual_splice_descriptor_length_in_bits: (self.bits_remaining_before_descriptor
-                    as usize)
-                    - bits_remaining,
-                splice_descriptor_tag,
-            })
+            let handling = bits.config().length_mismatch;
+            bits.handle_error(
+                handling,
+                ParseError::UnexpectedSpliceDescriptorLength {
+                    declared_splice_descriptor_length_in_bits: self.descriptor_bits_length,
+                    actual_splice_descriptor_length_in_bits: (self.bits_remaining_before_descriptor
+                        as usize)
+                        - bits_remaining,
+                    splice_descriptor_tag,
+                },
+            )?;
         }
+        Ok(())
+    }
+}
+
+fn encode_with_descriptor_length<F>(
+    bits: &mut BitWriter,
+    description: &'static str,
+    encode_descriptor: F,
+) -> Result<(), EncodeError>
+where
+    F: FnOnce(&mut BitWriter) -> Result<(), EncodeError>,
+{
+    let mut descriptor_bits = BitWriter::new();
+    encode_descriptor(&mut descriptor_bits)?;
+    let descriptor_bytes = descriptor_bits.into_bytes();
+    bits.validate_length(descriptor_bytes.len(), 255, description)?;
+    bits.byte(descriptor_bytes.len() as u8);
+    bits.bytes(&descriptor_bytes);
+    Ok(())
+}
+
+// Reads the `descriptor_length`, `identifier` and private bytes of a descriptor whose syntax is not
+// known, returning the identifier and the private bytes.
+fn read_private_descriptor(
+    bits: &mut Bits,
+    description: &'static str,
+) -> Result<(u32, Vec<u8>), ParseError> {
+    let descriptor_length = bits.u32("descriptor_length", 8)?;
+    bits.validate(descriptor_length * 8, description)?;
+    if descriptor_length < 4 {
+        return Err(ParseError::UnexpectedEndOfData {
+            expected_minimum_bits_left: 32,
+            actual_bits_left: descriptor_length * 8,
+            description,
+        });
     }
+    let identifier = bits.u32("identifier", 32)?;
+    let private_bytes = bits.bytes("private_bytes", (descriptor_length - 4) as usize)?;
+    Ok((identifier, private_bytes))
 }
 
 pub fn try_splice_descriptors_from(
@@ -182,18 +378,93 @@ pub fn try_splice_descriptors_from(
     descriptor_loop_length: u32,
 ) -> Result<Vec<SpliceDescriptor>, ParseError> {
     let mut splice_descriptors = vec![];
+    try_splice_descriptors_into(bits, descriptor_loop_length, &mut splice_descriptors)?;
+    Ok(splice_descriptors)
+}
+
+// Appends the descriptors to `splice_descriptors` (which is expected to be empty), so that the
+// allocation can be reused by a `Parser`.
+pub(crate) fn try_splice_descriptors_into(
+    bits: &mut Bits,
+    descriptor_loop_length: u32,
+    splice_descriptors: &mut Vec<SpliceDescriptor>,
+) -> Result<(), ParseError> {
     bits.validate(descriptor_loop_length * 8, "SpliceDescriptor; reading loop")?;
     let bits_remaining_before_loop = bits.bits_remaining();
     let expected_end = bits_remaining_before_loop - ((descriptor_loop_length as usize) * 8);
     while bits.bits_remaining() > expected_end {
-        splice_descriptors.push(SpliceDescriptor::try_from(bits)?);
+        let limit = bits.config().limits.max_splice_descriptors;
+        if splice_descriptors.len() == limit {
+            return Err(ParseError::LimitExceeded {
+                limit,
+                description: "max_splice_descriptors",
+            });
+        }
+        let index = Some(splice_descriptors.len());
+        // The bits remaining at the end of the descriptor, as declared by its `descriptor_length`.
+        let descriptor_end = bits.peek(16).and_then(|peeked| {
+            let descriptor_length_in_bits = (2 + (peeked as usize & 0xFF)) * 8;
+            bits.bits_remaining()
+                .checked_sub(descriptor_length_in_bits)
+                .filter(|descriptor_end| *descriptor_end >= expected_end)
+        });
+        match bits.scope_at("splice_descriptors", index, SpliceDescriptor::try_from) {
+            Ok(descriptor) => {
+                #[cfg(feature = "tracing")]
+                crate::trace::splice_descriptor_parsed(splice_descriptors.len(), &descriptor);
+                splice_descriptors.push(descriptor)
+            }
+            Err(error) => skip_invalid_splice_descriptor(bits, error, descriptor_end)?,
+        }
     }
-    Ok(splice_descriptors)
+    Ok(())
+}
+
+// Skips to the `descriptor_end` of a descriptor that failed to parse, when the
+// `invalid_splice_descriptors` handling is not `Fatal`. The `error` is returned when the descriptor
+// cannot be skipped, which is when parsing has already read past the `descriptor_end` (or there is
+// none) or when a `ParserLimits` limit was exceeded.
+fn skip_invalid_splice_descriptor(
+    bits: &mut Bits,
+    error: ParseError,
+    descriptor_end: Option<usize>,
+) -> Result<(), ParseError> {
+    let handling = bits.config().invalid_splice_descriptors;
+    let Some(descriptor_end) =
+        descriptor_end.filter(|descriptor_end| bits.bits_remaining() >= *descriptor_end)
+    else {
+        return Err(error);
+    };
+    if handling == ErrorHandling::Fatal || matches!(error, ParseError::LimitExceeded { .. }) {
+        return Err(error);
+    }
+    bits.handle_error(handling, error)?;
+    let bits_to_skip = bits.bits_remaining() - descriptor_end;
+    bits.skip_bits("skipped", bits_to_skip)
 }
 
 impl SpliceDescriptor {
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        match SpliceDescriptorTag::try_from(bits.byte())? {
+        let tag = bits.byte("splice_descriptor_tag")?;
+        if let Some(descriptor) = Self::try_custom_from(bits, tag)? {
+            return Ok(Self::Custom(descriptor));
+        }
+        let splice_descriptor_tag = match SpliceDescriptorTag::try_from(tag) {
+            Ok(splice_descriptor_tag) => splice_descriptor_tag,
+            Err(error) if bits.config().unknown_enum_values != ErrorHandling::Fatal => {
+                let (identifier, private_bytes) =
+                    read_private_descriptor(bits, "SpliceDescriptor")?;
+                let handling = bits.config().unknown_enum_values;
+                bits.handle_error(handling, error)?;
+                return Ok(Self::Unknown {
+                    tag,
+                    identifier,
+                    private_bytes,
+                });
+            }
+            Err(error) => return Err(error),
+        };
+        match splice_descriptor_tag {
             SpliceDescriptorTag::AvailDescriptor => {
                 Ok(Self::AvailDescriptor(AvailDescriptor::try_from(bits)?))
             }
@@ -209,6 +480,61 @@ impl SpliceDescriptor {
             SpliceDescriptorTag::AudioDescriptor => {
                 Ok(Self::AudioDescriptor(AudioDescriptor::try_from(bits)?))
             }
+            SpliceDescriptorTag::Private(value) => {
+                Err(ParseError::UnrecognisedSpliceDescriptorTag(value))
+            }
+        }
+    }
+
+    // Parses the descriptor with a parser from the `SpliceDescriptorRegistry` of `bits`, when one
+    // is registered for the tag and identifier of the descriptor.
+    fn try_custom_from(
+        bits: &mut Bits,
+        tag: u8,
+    ) -> Result<Option<Box<dyn CustomSpliceDescriptor>>, ParseError> {
+        if bits.config().descriptor_registry.is_none() {
+            return Ok(None);
+        }
+        let Some(peeked) = bits.peek(40) else {
+            return Ok(None);
+        };
+        let identifier = peeked as u32;
+        let Some(parser) = bits
+            .config()
+            .descriptor_registry
+            .as_ref()
+            .and_then(|registry| registry.parser(tag, identifier))
+        else {
+            return Ok(None);
+        };
+        let (_, private_bytes) = read_private_descriptor(bits, "CustomSpliceDescriptor")?;
+        parser(&private_bytes).map(Some)
+    }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.byte(self.tag().value());
+        match self {
+            Self::AvailDescriptor(descriptor) => descriptor.encode(bits),
+            Self::DTMFDescriptor(descriptor) => descriptor.encode(bits),
+            Self::SegmentationDescriptor(descriptor) => descriptor.encode(bits),
+            Self::TimeDescriptor(descriptor) => descriptor.encode(bits),
+            Self::AudioDescriptor(descriptor) => descriptor.encode(bits),
+            Self::Custom(descriptor) => {
+                encode_with_descriptor_length(bits, "CustomSpliceDescriptor", |bits| {
+                    bits.u32(32, descriptor.identifier());
+                    bits.bytes(&descriptor.private_bytes()?);
+                    Ok(())
+                })
+            }
+            Self::Unknown {
+                identifier,
+                private_bytes,
+                ..
+            } => encode_with_descriptor_length(bits, "SpliceDescriptor", |bits| {
+                bits.u32(32, *identifier);
+                bits.bytes(private_bytes);
+                Ok(())
+            }),
         }
     }
 }
@@ -1,6 +1,24 @@
-use super::DescriptorLengthExpectation;
-use crate::{atsc::ATSCContentIdentifier, bit_reader::Bits, error::ParseError, hex::encode_hex};
-use ::std::fmt::Write;
+use super::{encode_with_descriptor_length, DescriptorLengthExpectation, CUEI_IDENTIFIER};
+use crate::{
+    adi::Adi,
+    atsc::ATSCContentIdentifier,
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    eidr::Eidr,
+    error::{EncodeError, ParseError},
+    hex::decode_hex,
+    isan::Isan,
+    time::{
+        duration_from_ticks, ticks_from_duration, ComponentSpliceTime, Pts90k, Rounding, SpliceTime,
+    },
+};
+use ::std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{Display, Formatter, Write},
+    sync::Arc,
+    time::Duration,
+};
 
 /// The `SegmentationDescriptor` is an implementation of a `SpliceDescriptor`. It provides an
 /// optional extension to the `TimeSignal` and `SpliceInsert` commands that allows for segmentation
@@ -58,7 +76,9 @@ segmentation_descriptor() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SegmentationDescriptor {
     /// This 32-bit number is used to identify the owner of the descriptor. The identifier shall
     /// have a value of 0x43554549 (ASCII “CUEI”).
@@ -73,11 +93,85 @@ impl SegmentationDescriptor {
     /// When set to `true` indicates that a previously sent segmentation descriptor, identified by
     /// `event_id`, has been cancelled.
     pub fn is_cancelled(&self) -> bool {
-        self.scheduled_event == None
+        self.scheduled_event.is_none()
+    }
+
+    /// Creates a `SegmentationDescriptorBuilder` for the given `event_id`.
+    pub fn builder(event_id: u32) -> SegmentationDescriptorBuilder {
+        SegmentationDescriptorBuilder::new(event_id)
+    }
+
+    /// The intended splice time of each component in Component Segmentation Mode, in the order in
+    /// which they are listed (see `ComponentSegmentation::splice_time`), for the `splice_time` of
+    /// the command that the descriptor is carried with. This is empty in Program Segmentation
+    /// Mode, and when the descriptor is cancelled.
+    /// ```
+    /// use scte35::{
+    ///     splice_descriptor::segmentation_descriptor::SegmentationDescriptor,
+    ///     time::{Pts90k, SpliceTime},
+    /// };
+    ///
+    /// let descriptor = SegmentationDescriptor::builder(1)
+    ///     .program_start()
+    ///     .component_segment(1, Pts90k(0))
+    ///     .component_segment(2, Pts90k(3_003))
+    ///     .build()
+    ///     .unwrap();
+    /// let splice_time = SpliceTime {
+    ///     pts_time: Some(Pts90k(90_000)),
+    /// };
+    /// let splice_times = descriptor.component_splice_times(
+    ///     Some(&splice_time),
+    ///     Pts90k(10),
+    ///     Pts90k(0),
+    /// );
+    /// assert_eq!(Pts90k(90_010), splice_times[0].pts);
+    /// assert_eq!(Pts90k(93_013), splice_times[1].pts);
+    /// ```
+    pub fn component_splice_times(
+        &self,
+        splice_time: Option<&SpliceTime>,
+        pts_adjustment: Pts90k,
+        arrival_pts: Pts90k,
+    ) -> Vec<ComponentSpliceTime> {
+        let Some(components) = self
+            .scheduled_event
+            .as_ref()
+            .and_then(|event| event.component_segments.as_ref())
+        else {
+            return vec![];
+        };
+        components
+            .iter()
+            .map(|component| ComponentSpliceTime {
+                component_tag: component.component_tag,
+                pts: component.splice_time(splice_time, pts_adjustment, arrival_pts),
+                is_immediate: splice_time
+                    .and_then(|splice_time| splice_time.pts_time)
+                    .is_none(),
+            })
+            .collect()
+    }
+}
+
+impl ScheduledEvent {
+    /// The `segmentation_duration` as a `Duration`.
+    pub fn segmentation_duration_as_duration(&self) -> Option<Duration> {
+        self.segmentation_duration.map(duration_from_ticks)
+    }
+
+    /// The first UPID of the given type in the `segmentation_upid`, looking within any `MID` (see
+    /// `SegmentationUPID::iter`).
+    pub fn find_upid(&self, upid_type: SegmentationUPIDType) -> Option<&SegmentationUPID> {
+        self.segmentation_upid
+            .iter()
+            .find(|upid| upid.upid_type() == upid_type)
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ScheduledEvent {
     /// This is provided to facilitate implementations that use methods that are out of scope of
     /// this standard to process and manage this Segment.
@@ -118,7 +212,9 @@ pub struct ScheduledEvent {
 
 /// This is provided to facilitate implementations that use methods that are out of scope of this
 /// standard to process and manage this Segment.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DeliveryRestrictions {
     /// This shall have the value of `true` when there are no restrictions with respect to web
     /// delivery of this Segment. This shall have the value of `false` to signal that restrictions
@@ -138,10 +234,93 @@ pub struct DeliveryRestrictions {
     pub device_restrictions: DeviceRestrictions,
 }
 
+impl DeliveryRestrictions {
+    /// The bit of `bits()` that is set when `web_delivery_allowed` is `true`.
+    pub const WEB_DELIVERY_ALLOWED: u8 = 0x10;
+    /// The bit of `bits()` that is set when `no_regional_blackout` is `true`.
+    pub const NO_REGIONAL_BLACKOUT: u8 = 0x08;
+    /// The bit of `bits()` that is set when `archive_allowed` is `true`.
+    pub const ARCHIVE_ALLOWED: u8 = 0x04;
+    /// The bits of `bits()` that hold the `device_restrictions` value.
+    pub const DEVICE_RESTRICTIONS: u8 = 0x03;
+
+    /// Restrictions where every flag allows delivery and there are no device restrictions. Note
+    /// that this is still encoded with the `delivery_not_restricted_flag` set to `false` (a
+    /// `SegmentationDescriptor` with no restrictions at all has `delivery_restrictions` of `None`).
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::DeliveryRestrictions;
+    ///
+    /// assert_eq!(0x1F, DeliveryRestrictions::unrestricted().bits());
+    /// ```
+    pub fn unrestricted() -> Self {
+        Self {
+            web_delivery_allowed: true,
+            no_regional_blackout: true,
+            archive_allowed: true,
+            device_restrictions: DeviceRestrictions::None,
+        }
+    }
+
+    /// The restrictions as the 5 bits that follow the `delivery_not_restricted_flag` in the
+    /// encoded descriptor (`web_delivery_allowed_flag`, `no_regional_blackout_flag`,
+    /// `archive_allowed_flag` and `device_restrictions`), e.g. for storing as an integer.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::{
+    ///     DeliveryRestrictions, DeviceRestrictions,
+    /// };
+    ///
+    /// let delivery_restrictions = DeliveryRestrictions {
+    ///     web_delivery_allowed: false,
+    ///     no_regional_blackout: true,
+    ///     archive_allowed: true,
+    ///     device_restrictions: DeviceRestrictions::RestrictGroup1,
+    /// };
+    /// assert_eq!(0x0D, delivery_restrictions.bits());
+    /// assert_eq!(Ok(delivery_restrictions), DeliveryRestrictions::try_from(0x0D));
+    /// ```
+    pub fn bits(&self) -> u8 {
+        let mut bits = self.device_restrictions.value();
+        if self.web_delivery_allowed {
+            bits |= Self::WEB_DELIVERY_ALLOWED;
+        }
+        if self.no_regional_blackout {
+            bits |= Self::NO_REGIONAL_BLACKOUT;
+        }
+        if self.archive_allowed {
+            bits |= Self::ARCHIVE_ALLOWED;
+        }
+        bits
+    }
+}
+
+impl From<DeliveryRestrictions> for u8 {
+    fn from(value: DeliveryRestrictions) -> Self {
+        value.bits()
+    }
+}
+
+impl TryFrom<u8> for DeliveryRestrictions {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value & 0xE0 != 0 {
+            return Err("Unexpected u8 for DeliveryRestrictions");
+        }
+        Ok(Self {
+            web_delivery_allowed: value & Self::WEB_DELIVERY_ALLOWED != 0,
+            no_regional_blackout: value & Self::NO_REGIONAL_BLACKOUT != 0,
+            archive_allowed: value & Self::ARCHIVE_ALLOWED != 0,
+            device_restrictions: DeviceRestrictions::try_from(value & Self::DEVICE_RESTRICTIONS)?,
+        })
+    }
+}
+
 /// This field signals three pre-defined groups of devices. The population of each group is
 /// independent and the groups are non-hierarchical. The delivery and format of the messaging to
 /// define the devices contained in the groups is out of the scope of this standard.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DeviceRestrictions {
     /// 00 - This Segment is restricted for a class of devices defined by an out of band message
     /// that describes which devices are excluded.
@@ -181,7 +360,9 @@ impl DeviceRestrictions {
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ComponentSegmentation {
     /// An 8-bit value that identifies the elementary PID stream containing the Segmentation Point
     /// specified by the value of `SpliceTime` that follows. The value shall be the same as the value
@@ -195,10 +376,31 @@ pub struct ComponentSegmentation {
     /// shall be used without an offset. If `SpliceTime` has no `pts_time` or if the command this
     /// descriptor is carried with does not have a `SpliceTime` field, this field shall be used to
     /// offset the derived immediate splice time.
-    pub pts_offset: u64,
+    pub pts_offset: Pts90k,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+impl ComponentSegmentation {
+    /// The intended splice time of the component, which is the `pts_time` of the `splice_time` of
+    /// the command (e.g. a `TimeSignal`) with the `pts_adjustment` and `pts_offset` added, wrapping
+    /// at 33 bits. When the `splice_time` has no `pts_time`, or the command has no `splice_time`,
+    /// the `pts_offset` is added to the derived immediate splice time, the `arrival_pts` at which
+    /// the message was received.
+    pub fn splice_time(
+        &self,
+        splice_time: Option<&SpliceTime>,
+        pts_adjustment: Pts90k,
+        arrival_pts: Pts90k,
+    ) -> Pts90k {
+        match splice_time.and_then(|splice_time| splice_time.pts_time) {
+            Some(pts_time) => pts_time + pts_adjustment + self.pts_offset,
+            None => arrival_pts + self.pts_offset,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SubSegment {
     /// If specified, this field provides identification for a specific sub-Segment within a
     /// collection of sub-Segments. This value, when utilized, is expected to be set to one for the
@@ -214,7 +416,16 @@ pub struct SubSegment {
 /// `SegmentationTypeID` is `0x01` (`ContentIdentification`), the value of `SegmentationUPIDType`
 /// shall be non-zero. If `segmentation_upid_length` is zero, then `SegmentationTypeID` shall be
 /// set to `0x00` for Not Indicated.
-#[derive(PartialEq, Eq, Debug)]
+///
+/// `SegmentationTypeID`s are ordered by their `value`.
+///
+/// This is `non_exhaustive` so that types defined by future revisions of the specification can be
+/// added without a breaking change. Until then they are represented by `Reserved` (see
+/// `from_value`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SegmentationTypeID {
     /// 0x00
     NotIndicated,
@@ -308,6 +519,10 @@ pub enum SegmentationTypeID {
     NetworkStart,
     /// 0x51
     NetworkEnd,
+    /// A value that is reserved in the version of the specification that this library implements.
+    /// The raw value is kept so that messages using types from later versions can still be parsed
+    /// and re-encoded without loss.
+    Reserved(u8),
 }
 
 impl TryFrom<u8> for SegmentationTypeID {
@@ -361,12 +576,39 @@ impl TryFrom<u8> for SegmentationTypeID {
             0x47 => Ok(SegmentationTypeID::DistributorAdBlockEnd),
             0x50 => Ok(SegmentationTypeID::NetworkStart),
             0x51 => Ok(SegmentationTypeID::NetworkEnd),
-            _ => Err(ParseError::UnrecognisedSegmentationTypeID(value)),
+            _ => Ok(SegmentationTypeID::Reserved(value)),
         }
     }
 }
 
+impl PartialOrd for SegmentationTypeID {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// A `Reserved` id holding the value of a defined id is ordered after that defined id, to stay
+// consistent with `Eq`.
+impl Ord for SegmentationTypeID {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value()
+            .cmp(&other.value())
+            .then(matches!(self, Self::Reserved(_)).cmp(&matches!(other, Self::Reserved(_))))
+    }
+}
+
 impl SegmentationTypeID {
+    /// The type for `value`, which is `Reserved` when `value` is not defined by the specification.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::SegmentationTypeID;
+    ///
+    /// assert_eq!(SegmentationTypeID::BreakStart, SegmentationTypeID::from_value(0x22));
+    /// assert_eq!(SegmentationTypeID::Reserved(0x02), SegmentationTypeID::from_value(0x02));
+    /// ```
+    pub fn from_value(value: u8) -> Self {
+        Self::try_from(value).unwrap_or(Self::Reserved(value))
+    }
+
     pub fn value(&self) -> u8 {
         match *self {
             SegmentationTypeID::NotIndicated => 0x00,
@@ -415,8 +657,219 @@ impl SegmentationTypeID {
             SegmentationTypeID::DistributorAdBlockEnd => 0x47,
             SegmentationTypeID::NetworkStart => 0x50,
             SegmentationTypeID::NetworkEnd => 0x51,
+            SegmentationTypeID::Reserved(value) => value,
+        }
+    }
+
+    /// The name of the type as given in the specification (e.g. "Provider Placement Opportunity
+    /// Start").
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SegmentationTypeID::NotIndicated => "Not Indicated",
+            SegmentationTypeID::ContentIdentification => "Content Identification",
+            SegmentationTypeID::ProgramStart => "Program Start",
+            SegmentationTypeID::ProgramEnd => "Program End",
+            SegmentationTypeID::ProgramEarlyTermination => "Program Early Termination",
+            SegmentationTypeID::ProgramBreakaway => "Program Breakaway",
+            SegmentationTypeID::ProgramResumption => "Program Resumption",
+            SegmentationTypeID::ProgramRunoverPlanned => "Program Runover Planned",
+            SegmentationTypeID::ProgramRunoverUnplanned => "Program Runover Unplanned",
+            SegmentationTypeID::ProgramOverlapStart => "Program Overlap Start",
+            SegmentationTypeID::ProgramBlackoutOverride => "Program Blackout Override",
+            SegmentationTypeID::ProgramJoin => "Program Start - In Progress",
+            SegmentationTypeID::ChapterStart => "Chapter Start",
+            SegmentationTypeID::ChapterEnd => "Chapter End",
+            SegmentationTypeID::BreakStart => "Break Start",
+            SegmentationTypeID::BreakEnd => "Break End",
+            SegmentationTypeID::OpeningCreditStart => "Opening Credit Start",
+            SegmentationTypeID::OpeningCreditEnd => "Opening Credit End",
+            SegmentationTypeID::ClosingCreditStart => "Closing Credit Start",
+            SegmentationTypeID::ClosingCreditEnd => "Closing Credit End",
+            SegmentationTypeID::ProviderAdvertisementStart => "Provider Advertisement Start",
+            SegmentationTypeID::ProviderAdvertisementEnd => "Provider Advertisement End",
+            SegmentationTypeID::DistributorAdvertisementStart => "Distributor Advertisement Start",
+            SegmentationTypeID::DistributorAdvertisementEnd => "Distributor Advertisement End",
+            SegmentationTypeID::ProviderPlacementOpportunityStart => {
+                "Provider Placement Opportunity Start"
+            }
+            SegmentationTypeID::ProviderPlacementOpportunityEnd => {
+                "Provider Placement Opportunity End"
+            }
+            SegmentationTypeID::DistributorPlacementOpportunityStart => {
+                "Distributor Placement Opportunity Start"
+            }
+            SegmentationTypeID::DistributorPlacementOpportunityEnd => {
+                "Distributor Placement Opportunity End"
+            }
+            SegmentationTypeID::ProviderOverlayPlacementOpportunityStart => {
+                "Provider Overlay Placement Opportunity Start"
+            }
+            SegmentationTypeID::ProviderOverlayPlacementOpportunityEnd => {
+                "Provider Overlay Placement Opportunity End"
+            }
+            SegmentationTypeID::DistributorOverlayPlacementOpportunityStart => {
+                "Distributor Overlay Placement Opportunity Start"
+            }
+            SegmentationTypeID::DistributorOverlayPlacementOpportunityEnd => {
+                "Distributor Overlay Placement Opportunity End"
+            }
+            SegmentationTypeID::ProviderPromoStart => "Provider Promo Start",
+            SegmentationTypeID::ProviderPromoEnd => "Provider Promo End",
+            SegmentationTypeID::DistributorPromoStart => "Distributor Promo Start",
+            SegmentationTypeID::DistributorPromoEnd => "Distributor Promo End",
+            SegmentationTypeID::UnscheduledEventStart => "Unscheduled Event Start",
+            SegmentationTypeID::UnscheduledEventEnd => "Unscheduled Event End",
+            SegmentationTypeID::AlternateContentOpportunityStart => {
+                "Alternate Content Opportunity Start"
+            }
+            SegmentationTypeID::AlternateContentOpportunityEnd => {
+                "Alternate Content Opportunity End"
+            }
+            SegmentationTypeID::ProviderAdBlockStart => "Provider Ad Block Start",
+            SegmentationTypeID::ProviderAdBlockEnd => "Provider Ad Block End",
+            SegmentationTypeID::DistributorAdBlockStart => "Distributor Ad Block Start",
+            SegmentationTypeID::DistributorAdBlockEnd => "Distributor Ad Block End",
+            SegmentationTypeID::NetworkStart => "Network Start",
+            SegmentationTypeID::NetworkEnd => "Network End",
+            SegmentationTypeID::Reserved(_) => "Reserved",
+        }
+    }
+
+    /// Indicates whether the type starts a segment (e.g. `ProviderPlacementOpportunityStart`),
+    /// which is ended by the type returned from `corresponding_end`.
+    pub fn is_out(&self) -> bool {
+        self.corresponding_end().is_some()
+    }
+
+    /// Indicates whether the type ends a segment (e.g. `ProviderPlacementOpportunityEnd`), which is
+    /// started by the type returned from `corresponding_start`. An end type should not define a
+    /// `segmentation_duration`.
+    pub fn is_in(&self) -> bool {
+        self.corresponding_start().is_some()
+    }
+
+    /// The type that ends a segment started by this type (e.g. `ProgramEnd` for `ProgramStart`),
+    /// or `None` if this type does not start a segment.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::SegmentationTypeID;
+    ///
+    /// assert_eq!(
+    ///     Some(SegmentationTypeID::ProviderPlacementOpportunityEnd),
+    ///     SegmentationTypeID::ProviderPlacementOpportunityStart.corresponding_end()
+    /// );
+    /// assert_eq!(None, SegmentationTypeID::ProgramBreakaway.corresponding_end());
+    /// ```
+    pub fn corresponding_end(&self) -> Option<SegmentationTypeID> {
+        match self {
+            SegmentationTypeID::ProgramStart => Some(SegmentationTypeID::ProgramEnd),
+            SegmentationTypeID::ChapterStart => Some(SegmentationTypeID::ChapterEnd),
+            SegmentationTypeID::BreakStart => Some(SegmentationTypeID::BreakEnd),
+            SegmentationTypeID::OpeningCreditStart => Some(SegmentationTypeID::OpeningCreditEnd),
+            SegmentationTypeID::ClosingCreditStart => Some(SegmentationTypeID::ClosingCreditEnd),
+            SegmentationTypeID::ProviderAdvertisementStart => {
+                Some(SegmentationTypeID::ProviderAdvertisementEnd)
+            }
+            SegmentationTypeID::DistributorAdvertisementStart => {
+                Some(SegmentationTypeID::DistributorAdvertisementEnd)
+            }
+            SegmentationTypeID::ProviderPlacementOpportunityStart => {
+                Some(SegmentationTypeID::ProviderPlacementOpportunityEnd)
+            }
+            SegmentationTypeID::DistributorPlacementOpportunityStart => {
+                Some(SegmentationTypeID::DistributorPlacementOpportunityEnd)
+            }
+            SegmentationTypeID::ProviderOverlayPlacementOpportunityStart => {
+                Some(SegmentationTypeID::ProviderOverlayPlacementOpportunityEnd)
+            }
+            SegmentationTypeID::DistributorOverlayPlacementOpportunityStart => {
+                Some(SegmentationTypeID::DistributorOverlayPlacementOpportunityEnd)
+            }
+            SegmentationTypeID::ProviderPromoStart => Some(SegmentationTypeID::ProviderPromoEnd),
+            SegmentationTypeID::DistributorPromoStart => {
+                Some(SegmentationTypeID::DistributorPromoEnd)
+            }
+            SegmentationTypeID::UnscheduledEventStart => {
+                Some(SegmentationTypeID::UnscheduledEventEnd)
+            }
+            SegmentationTypeID::AlternateContentOpportunityStart => {
+                Some(SegmentationTypeID::AlternateContentOpportunityEnd)
+            }
+            SegmentationTypeID::ProviderAdBlockStart => {
+                Some(SegmentationTypeID::ProviderAdBlockEnd)
+            }
+            SegmentationTypeID::DistributorAdBlockStart => {
+                Some(SegmentationTypeID::DistributorAdBlockEnd)
+            }
+            SegmentationTypeID::NetworkStart => Some(SegmentationTypeID::NetworkEnd),
+            _ => None,
         }
     }
+
+    /// The type that starts a segment ended by this type (e.g. `ProgramStart` for `ProgramEnd`),
+    /// or `None` if this type does not end a segment.
+    pub fn corresponding_start(&self) -> Option<SegmentationTypeID> {
+        match self {
+            SegmentationTypeID::ProgramEnd => Some(SegmentationTypeID::ProgramStart),
+            SegmentationTypeID::ChapterEnd => Some(SegmentationTypeID::ChapterStart),
+            SegmentationTypeID::BreakEnd => Some(SegmentationTypeID::BreakStart),
+            SegmentationTypeID::OpeningCreditEnd => Some(SegmentationTypeID::OpeningCreditStart),
+            SegmentationTypeID::ClosingCreditEnd => Some(SegmentationTypeID::ClosingCreditStart),
+            SegmentationTypeID::ProviderAdvertisementEnd => {
+                Some(SegmentationTypeID::ProviderAdvertisementStart)
+            }
+            SegmentationTypeID::DistributorAdvertisementEnd => {
+                Some(SegmentationTypeID::DistributorAdvertisementStart)
+            }
+            SegmentationTypeID::ProviderPlacementOpportunityEnd => {
+                Some(SegmentationTypeID::ProviderPlacementOpportunityStart)
+            }
+            SegmentationTypeID::DistributorPlacementOpportunityEnd => {
+                Some(SegmentationTypeID::DistributorPlacementOpportunityStart)
+            }
+            SegmentationTypeID::ProviderOverlayPlacementOpportunityEnd => {
+                Some(SegmentationTypeID::ProviderOverlayPlacementOpportunityStart)
+            }
+            SegmentationTypeID::DistributorOverlayPlacementOpportunityEnd => {
+                Some(SegmentationTypeID::DistributorOverlayPlacementOpportunityStart)
+            }
+            SegmentationTypeID::ProviderPromoEnd => Some(SegmentationTypeID::ProviderPromoStart),
+            SegmentationTypeID::DistributorPromoEnd => {
+                Some(SegmentationTypeID::DistributorPromoStart)
+            }
+            SegmentationTypeID::UnscheduledEventEnd => {
+                Some(SegmentationTypeID::UnscheduledEventStart)
+            }
+            SegmentationTypeID::AlternateContentOpportunityEnd => {
+                Some(SegmentationTypeID::AlternateContentOpportunityStart)
+            }
+            SegmentationTypeID::ProviderAdBlockEnd => {
+                Some(SegmentationTypeID::ProviderAdBlockStart)
+            }
+            SegmentationTypeID::DistributorAdBlockEnd => {
+                Some(SegmentationTypeID::DistributorAdBlockStart)
+            }
+            SegmentationTypeID::NetworkEnd => Some(SegmentationTypeID::NetworkStart),
+            _ => None,
+        }
+    }
+
+    /// Indicates whether `sub_segment_num` and `sub_segments_expected` are defined for this
+    /// segmentation type.
+    pub(crate) fn has_sub_segments(&self) -> bool {
+        matches!(
+            self,
+            SegmentationTypeID::ProviderPlacementOpportunityStart
+                | SegmentationTypeID::DistributorPlacementOpportunityStart
+                | SegmentationTypeID::ProviderOverlayPlacementOpportunityStart
+                | SegmentationTypeID::DistributorOverlayPlacementOpportunityStart
+        )
+    }
+}
+
+impl Display for SegmentationTypeID {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.name().fmt(f)
+    }
 }
 
 /// There are multiple types allowed to ensure that programmers will be able to use an id that
@@ -424,7 +877,14 @@ impl SegmentationTypeID {
 /// method of collecting other data related to these numbers and therefore they do not need to be
 /// of identical types. These ids may be in other descriptors in the Program and, where the same
 /// identifier is used (ISAN for example), it shall match between Programs.
-#[derive(PartialEq, Eq, Debug, Clone)]
+///
+/// This is `non_exhaustive` so that UPID types defined by future revisions of the specification
+/// can be added without a breaking change. Until then they are represented by `Reserved` (see
+/// `from_value`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SegmentationUPIDType {
     NotUsed,
     UserDefined,
@@ -443,6 +903,9 @@ pub enum SegmentationUPIDType {
     ADSInformation,
     URI,
     UUID,
+    /// A `segmentation_upid_type` that is reserved by the specification (and so is not parsed by
+    /// this library).
+    Reserved(u8),
 }
 
 impl TryFrom<u8> for SegmentationUPIDType {
@@ -473,6 +936,18 @@ impl TryFrom<u8> for SegmentationUPIDType {
 }
 
 impl SegmentationUPIDType {
+    /// The UPID type for `value`, which is `Reserved` when `value` is not defined by the
+    /// specification.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::SegmentationUPIDType;
+    ///
+    /// assert_eq!(SegmentationUPIDType::AdID, SegmentationUPIDType::from_value(0x03));
+    /// assert_eq!(SegmentationUPIDType::Reserved(0x11), SegmentationUPIDType::from_value(0x11));
+    /// ```
+    pub fn from_value(value: u8) -> Self {
+        Self::try_from(value).unwrap_or(Self::Reserved(value))
+    }
+
     pub fn value(&self) -> u8 {
         match *self {
             SegmentationUPIDType::NotUsed => 0x00,
@@ -492,8 +967,64 @@ impl SegmentationUPIDType {
             SegmentationUPIDType::ADSInformation => 0x0E,
             SegmentationUPIDType::URI => 0x0F,
             SegmentationUPIDType::UUID => 0x10,
+            SegmentationUPIDType::Reserved(value) => value,
         }
     }
+
+    /// The name of the type as given in the specification (e.g. "Ad-ID").
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SegmentationUPIDType::NotUsed => "Not Used",
+            SegmentationUPIDType::UserDefined => "User Defined (Deprecated)",
+            SegmentationUPIDType::ISCI => "ISCI (Deprecated)",
+            SegmentationUPIDType::AdID => "Ad-ID",
+            SegmentationUPIDType::UMID => "UMID",
+            SegmentationUPIDType::DeprecatedISAN => "ISAN (Deprecated)",
+            SegmentationUPIDType::ISAN => "ISAN",
+            SegmentationUPIDType::TID => "TID",
+            SegmentationUPIDType::TI => "TI",
+            SegmentationUPIDType::ADI => "ADI",
+            SegmentationUPIDType::EIDR => "EIDR",
+            SegmentationUPIDType::ATSCContentIdentifier => "ATSC Content Identifier",
+            SegmentationUPIDType::MPU => "MPU",
+            SegmentationUPIDType::MID => "MID",
+            SegmentationUPIDType::ADSInformation => "ADS Information",
+            SegmentationUPIDType::URI => "URI",
+            SegmentationUPIDType::UUID => "UUID",
+            SegmentationUPIDType::Reserved(_) => "Reserved",
+        }
+    }
+
+    // The `segmentation_upid_length` defined by the specification for the types that have a fixed
+    // length.
+    fn expected_length(&self) -> Option<u8> {
+        match *self {
+            SegmentationUPIDType::NotUsed => Some(0),
+            SegmentationUPIDType::ISCI => Some(8),
+            SegmentationUPIDType::AdID => Some(12),
+            SegmentationUPIDType::UMID => Some(32),
+            SegmentationUPIDType::DeprecatedISAN => Some(8),
+            SegmentationUPIDType::ISAN => Some(12),
+            SegmentationUPIDType::TID => Some(12),
+            SegmentationUPIDType::TI => Some(8),
+            SegmentationUPIDType::EIDR => Some(12),
+            SegmentationUPIDType::UUID => Some(16),
+            SegmentationUPIDType::UserDefined
+            | SegmentationUPIDType::ADI
+            | SegmentationUPIDType::ATSCContentIdentifier
+            | SegmentationUPIDType::MPU
+            | SegmentationUPIDType::MID
+            | SegmentationUPIDType::ADSInformation
+            | SegmentationUPIDType::URI
+            | SegmentationUPIDType::Reserved(_) => None,
+        }
+    }
+}
+
+impl Display for SegmentationUPIDType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.name().fmt(f)
+    }
 }
 
 /// There are multiple types allowed to ensure that programmers will be able to use an id that
@@ -501,7 +1032,10 @@ impl SegmentationUPIDType {
 /// method of collecting other data related to these numbers and therefore they do not need to be
 /// of identical types. These ids may be in other descriptors in the Program and, where the same
 /// identifier is used (ISAN for example), it shall match between Programs.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SegmentationUPID {
     /// The `SegmentationUPID` is not defined and is not present in the descriptor.
     NotUsed,
@@ -516,16 +1050,20 @@ pub enum SegmentationUPID {
     AdID(String),
     /// See [SMPTE 330]
     UMID(String),
-    /// Deprecated: use type `0x06`, ISO 15706 binary encoding.
-    DeprecatedISAN(String),
+    /// Deprecated: use type `0x06`, ISO 15706 binary encoding. The `version` of the `Isan` is
+    /// `None`.
+    DeprecatedISAN(Isan),
     /// Formerly known as V-ISAN. ISO 15706-2 binary encoding (“versioned” ISAN). See
-    /// [ISO 15706-2].
-    ISAN(String),
+    /// [ISO 15706-2]. The `version` of the `Isan` is present.
+    ISAN(Isan),
     /// Tribune Media Systems Program identifier. 12 characters; 2 alpha characters followed by 10
     /// numbers.
     TID(String),
     /// AiringID (Formerly Turner ID), used to indicate a specific airing of a Program that is
     /// unique within a network.
+    ///
+    /// The 8-byte value is represented as `0x` followed by 16 hexadecimal digits (see `ti_value`
+    /// for the integer value).
     TI(String),
     /// CableLabs metadata identifier.
     ///
@@ -541,10 +1079,13 @@ pub enum SegmentationUPID {
     ///
     /// SCTE 2362 provides compatibility with this identifier model as described in [SCTE 236]
     /// Section 7.11.1.
+    ///
+    /// The identifier is kept as it was signaled, so that non-conforming payloads are not lost
+    /// (see `adi` for the parsed identifier).
     ADI(String),
     /// An EIDR (see \[EIDR\]) represented in Compact Binary encoding as defined in Section 2.1.1
     /// in EIDR ID Format (see [EIDR ID FORMAT])
-    EIDR(String),
+    EIDR(Eidr),
     /// `ATSC_content_identifier()` structure as defined in [ATSC A/57B].
     ATSCContentIdentifier(ATSCContentIdentifier),
     /// Managed Private UPID structure.
@@ -557,10 +1098,171 @@ pub enum SegmentationUPID {
     URI(String),
     /// Universally Unique Identifier (see [RFC 4122]). This `SegmentationUPIDType` can be used
     /// instead of an URI if it is desired to transfer the UUID payload only.
+    ///
+    /// The 16 bytes of the UUID are represented in the hyphenated form (e.g.
+    /// `aa85bbb6-5c43-4b6a-bebb-ee3b13eb7999`). When encoding, the hexadecimal digits may also be
+    /// given without hyphens, or the UUID may be given as a 16 character string that holds the
+    /// bytes themselves.
     UUID(String),
+    /// A UPID whose `segmentation_upid_length` does not match the length defined for its
+    /// `upid_type`, which is kept as the raw `segmentation_upid()` bytes. This is only parsed when
+    /// the `upid_length_mismatch` of the `ParserConfig` is not `Fatal`, and is encoded as it was
+    /// received.
+    Invalid {
+        upid_type: SegmentationUPIDType,
+        bytes: Vec<u8>,
+    },
 }
 
 impl SegmentationUPID {
+    /// The `uuid::Uuid` of a `UUID` UPID, or `None` for any other type of UPID or when the UUID
+    /// is not valid.
+    #[cfg(feature = "uuid")]
+    pub fn uuid(&self) -> Option<uuid::Uuid> {
+        match self {
+            SegmentationUPID::UUID(uuid) if uuid.len() == 16 => {
+                uuid::Uuid::from_slice(uuid.as_bytes()).ok()
+            }
+            SegmentationUPID::UUID(uuid) => {
+                uuid_bytes(uuid).and_then(|bytes| uuid::Uuid::from_slice(&bytes).ok())
+            }
+            _ => None,
+        }
+    }
+
+    /// The integer value of a `TI` (AiringID) UPID, or `None` for any other type of UPID or when
+    /// the value is not a valid 8-byte hexadecimal string.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::SegmentationUPID;
+    ///
+    /// let upid = SegmentationUPID::TI(String::from("0x000000002CA0A18A"));
+    /// assert_eq!(Some(0x2CA0A18A), upid.ti_value());
+    /// ```
+    pub fn ti_value(&self) -> Option<u64> {
+        match self {
+            SegmentationUPID::TI(ti) => {
+                let hex = ti
+                    .strip_prefix("0x")
+                    .or_else(|| ti.strip_prefix("0X"))
+                    .unwrap_or(ti);
+                if hex.is_empty() || hex.len() > 16 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                u64::from_str_radix(hex, 16).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Creates an `AdID` UPID, validating that it is 4 alphabetic characters (the company
+    /// identification prefix) followed by 8 alphanumeric characters.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::SegmentationUPID;
+    ///
+    /// assert!(SegmentationUPID::new_ad_id("ABCD0123456H").is_ok());
+    /// assert!(SegmentationUPID::new_ad_id("ABC00123456H").is_err());
+    /// ```
+    pub fn new_ad_id(ad_id: &str) -> Result<Self, EncodeError> {
+        validate_characters(
+            ad_id,
+            &[
+                (4, char::is_ascii_alphabetic),
+                (8, char::is_ascii_alphanumeric),
+            ],
+            SegmentationUPIDType::AdID,
+            "Ad-ID must be 4 alphabetic characters followed by 8 alphanumeric characters",
+        )?;
+        Ok(Self::AdID(String::from(ad_id)))
+    }
+
+    /// Creates an `ISCI` UPID, validating that it is 4 alphabetic characters followed by 4
+    /// numbers.
+    pub fn new_isci(isci: &str) -> Result<Self, EncodeError> {
+        validate_characters(
+            isci,
+            &[(4, char::is_ascii_alphabetic), (4, char::is_ascii_digit)],
+            SegmentationUPIDType::ISCI,
+            "ISCI must be 4 alphabetic characters followed by 4 numbers",
+        )?;
+        Ok(Self::ISCI(String::from(isci)))
+    }
+
+    /// Creates a `TID` UPID, validating that it is 2 alphabetic characters followed by 10
+    /// numbers.
+    pub fn new_tid(tid: &str) -> Result<Self, EncodeError> {
+        validate_characters(
+            tid,
+            &[(2, char::is_ascii_alphabetic), (10, char::is_ascii_digit)],
+            SegmentationUPIDType::TID,
+            "TID must be 2 alphabetic characters followed by 10 numbers",
+        )?;
+        Ok(Self::TID(String::from(tid)))
+    }
+
+    /// Creates a `TI` UPID from the 8-byte AiringID.
+    pub fn new_ti(ti: u64) -> Self {
+        Self::TI(format!("0x{:016X}", ti))
+    }
+
+    /// Creates a `URI` UPID, validating that it has a scheme (see [RFC 3986]), contains only
+    /// printable ASCII characters other than space, and fits within `segmentation_upid_length`.
+    pub fn new_uri(uri: &str) -> Result<Self, EncodeError> {
+        let has_scheme = uri.split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        });
+        if !has_scheme || !uri.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(EncodeError::InvalidSegmentationUPID {
+                segmentation_upid_type: SegmentationUPIDType::URI,
+                description: "URI must have a scheme and contain only printable ASCII characters",
+            });
+        }
+        if uri.len() > 255 {
+            return Err(EncodeError::LengthOutOfRange {
+                length: uri.len(),
+                maximum_length: 255,
+                description: "SegmentationUPID segmentation_upid_length",
+            });
+        }
+        Ok(Self::URI(String::from(uri)))
+    }
+
+    /// Iterates over this UPID and, for a `MID`, each of the UPIDs that it contains (depth-first,
+    /// in the order that they were signaled).
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::{
+    ///     SegmentationUPID, SegmentationUPIDType,
+    /// };
+    ///
+    /// let upid = SegmentationUPID::MID(vec![
+    ///     SegmentationUPID::AdID(String::from("ABCD0123456H")),
+    ///     SegmentationUPID::TI(String::from("0x000000002CA0A18A")),
+    /// ]);
+    /// let types: Vec<_> = upid.iter().map(|upid| upid.upid_type()).collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         SegmentationUPIDType::MID,
+    ///         SegmentationUPIDType::AdID,
+    ///         SegmentationUPIDType::TI,
+    ///     ],
+    ///     types
+    /// );
+    /// ```
+    pub fn iter(&self) -> SegmentationUPIDIter<'_> {
+        SegmentationUPIDIter { stack: vec![self] }
+    }
+
+    /// The parsed identifier of an `ADI` UPID, or `None` for any other type of UPID or when the
+    /// identifier does not follow the `<element> : <identifier>` syntax.
+    pub fn adi(&self) -> Option<Adi> {
+        match self {
+            SegmentationUPID::ADI(adi) => adi.parse().ok(),
+            _ => None,
+        }
+    }
+
     pub fn upid_type(&self) -> SegmentationUPIDType {
         match *self {
             SegmentationUPID::NotUsed => SegmentationUPIDType::NotUsed,
@@ -582,30 +1284,151 @@ impl SegmentationUPID {
             SegmentationUPID::ADSInformation(_) => SegmentationUPIDType::ADSInformation,
             SegmentationUPID::URI(_) => SegmentationUPIDType::URI,
             SegmentationUPID::UUID(_) => SegmentationUPIDType::UUID,
+            SegmentationUPID::Invalid { ref upid_type, .. } => upid_type.clone(),
+        }
+    }
+}
+
+/// A depth-first iterator over a `SegmentationUPID` and the UPIDs nested within it (see
+/// `SegmentationUPID::iter`).
+#[derive(Debug, Clone)]
+pub struct SegmentationUPIDIter<'a> {
+    stack: Vec<&'a SegmentationUPID>,
+}
+
+impl<'a> Iterator for SegmentationUPIDIter<'a> {
+    type Item = &'a SegmentationUPID;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let upid = self.stack.pop()?;
+        if let SegmentationUPID::MID(mid) = upid {
+            self.stack.extend(mid.iter().rev());
         }
+        Some(upid)
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ManagedPrivateUPID {
     pub format_specifier: String,
     pub private_data: Vec<u8>,
 }
 
+impl ManagedPrivateUPID {
+    /// The `private_data` as a string, or `None` when it is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.private_data).ok()
+    }
+
+    /// Deserializes the `private_data` as JSON.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::ManagedPrivateUPID;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct AssetData {
+    ///     #[serde(rename = "assetId")]
+    ///     asset_id: String,
+    /// }
+    ///
+    /// let mpu = ManagedPrivateUPID {
+    ///     format_specifier: String::from("NBCU"),
+    ///     private_data: br#"{"assetId":"peacock_600111"}"#.to_vec(),
+    /// };
+    /// let asset_data: AssetData = mpu.as_json().unwrap();
+    /// assert_eq!("peacock_600111", asset_data.asset_id);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn as_json<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.private_data)
+    }
+}
+
+type MPUDecoder<T> = Arc<dyn Fn(&ManagedPrivateUPID) -> Option<T> + Send + Sync>;
+
+/// A set of decoders for the `private_data` of `ManagedPrivateUPID`s, keyed by the
+/// `format_specifier` of the formats that they understand.
+/// ```
+/// use scte35::splice_descriptor::segmentation_descriptor::{
+///     ManagedPrivateUPID, MPUDecoderRegistry,
+/// };
+///
+/// let registry = MPUDecoderRegistry::new()
+///     .register("ABCD", |mpu| mpu.as_str().map(String::from));
+/// let mpu = ManagedPrivateUPID {
+///     format_specifier: String::from("ABCD"),
+///     private_data: b"example".to_vec(),
+/// };
+/// assert_eq!(Some(String::from("example")), registry.decode(&mpu));
+/// ```
+pub struct MPUDecoderRegistry<T> {
+    decoders: HashMap<String, MPUDecoder<T>>,
+}
+
+impl<T> MPUDecoderRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+        }
+    }
+
+    /// Registers the decoder for `format_specifier`, replacing any decoder that was previously
+    /// registered for it.
+    pub fn register<F>(mut self, format_specifier: &str, decoder: F) -> Self
+    where
+        F: Fn(&ManagedPrivateUPID) -> Option<T> + Send + Sync + 'static,
+    {
+        self.decoders
+            .insert(String::from(format_specifier), Arc::new(decoder));
+        self
+    }
+
+    /// Decodes the `private_data` of `mpu` with the decoder registered for its
+    /// `format_specifier`, returning `None` when there is no such decoder or it fails.
+    pub fn decode(&self, mpu: &ManagedPrivateUPID) -> Option<T> {
+        self.decoders
+            .get(&mpu.format_specifier)
+            .and_then(|decoder| decoder(mpu))
+    }
+}
+
+impl<T> Default for MPUDecoderRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for MPUDecoderRegistry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            decoders: self.decoders.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for MPUDecoderRegistry<T> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("MPUDecoderRegistry")
+            .field("format_specifiers", &self.decoders.keys())
+            .finish()
+    }
+}
+
 impl SegmentationDescriptor {
     // NOTE: It is assumed that the splice_descriptor_tag has already been read.
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         let expectation = DescriptorLengthExpectation::try_from(bits, "SegmentationDescriptor")?;
 
-        let identifier = bits.u32(32);
-        if identifier != 1129661769 {
+        let identifier = bits.u32("identifier", 32)?;
+        if identifier != CUEI_IDENTIFIER {
             return Err(ParseError::InvalidSegmentationDescriptorIdentifier(
                 identifier,
             ));
         }
-        let event_id = bits.u32(32);
-        let segmentation_event_cancelled = bits.bool();
-        bits.consume(7);
+        let event_id = bits.u32("event_id", 32)?;
+        let segmentation_event_cancelled = bits.bool("segmentation_event_cancel_indicator")?;
+        bits.consume(7)?;
         let scheduled_event = if segmentation_event_cancelled {
             None
         } else {
@@ -615,7 +1438,7 @@ impl SegmentationDescriptor {
             )?)
         };
 
-        expectation.validate_non_fatal(bits, super::SpliceDescriptorTag::SegmentationDescriptor);
+        expectation.validate(bits, super::SpliceDescriptorTag::SegmentationDescriptor)?;
 
         Ok(Self {
             identifier,
@@ -623,22 +1446,309 @@ impl SegmentationDescriptor {
             scheduled_event,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        encode_with_descriptor_length(bits, "SegmentationDescriptor", |bits| {
+            bits.u32(32, self.identifier);
+            bits.u32(32, self.event_id);
+            bits.bool(self.scheduled_event.is_none());
+            bits.reserved(7);
+            if let Some(scheduled_event) = &self.scheduled_event {
+                scheduled_event.encode(bits)?;
+            }
+            Ok(())
+        })
+    }
 }
 
-impl ScheduledEvent {
-    fn try_from(bits: &mut Bits, bits_left_after_descriptor: usize) -> Result<Self, ParseError> {
-        let program_segmentation_flag = bits.bool();
-        let segmentation_duration_flag = bits.bool();
-        let delivery_not_restricted_flag = bits.bool();
+/// A fluent builder for the `SegmentationDescriptor`.
+///
+/// The `identifier` is always set to 0x43554549 (ASCII "CUEI"). When the `segmentation_type_id` is
+/// an end type (e.g. `ProviderPlacementOpportunityEnd`), the `segmentation_duration` and the
+/// `sub_segment` are omitted, and the `sub_segment` is only included for the types that define it.
+///
+/// ```
+/// use scte35::splice_descriptor::segmentation_descriptor::{
+///     SegmentationDescriptor, SegmentationTypeID,
+/// };
+///
+/// let descriptor = SegmentationDescriptor::builder(1207959694)
+///     .provider_placement_opportunity_start()
+///     .duration_90khz(27630000)
+///     .upid_ti(0x2CA0A18A)
+///     .segment_num(2)
+///     .build()
+///     .unwrap();
+/// let scheduled_event = descriptor.scheduled_event.unwrap();
+/// assert_eq!(
+///     SegmentationTypeID::ProviderPlacementOpportunityStart,
+///     scheduled_event.segmentation_type_id
+/// );
+/// assert_eq!(Some(27630000), scheduled_event.segmentation_duration);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SegmentationDescriptorBuilder {
+    event_id: u32,
+    is_cancelled: bool,
+    delivery_restrictions: Option<DeliveryRestrictions>,
+    component_segments: Option<Vec<ComponentSegmentation>>,
+    segmentation_duration: Option<u64>,
+    segmentation_upid: SegmentationUPID,
+    segmentation_type_id: SegmentationTypeID,
+    segment_num: u8,
+    segments_expected: u8,
+    sub_segment: Option<SubSegment>,
+}
+
+impl SegmentationDescriptorBuilder {
+    pub fn new(event_id: u32) -> Self {
+        Self {
+            event_id,
+            is_cancelled: false,
+            delivery_restrictions: None,
+            component_segments: None,
+            segmentation_duration: None,
+            segmentation_upid: SegmentationUPID::NotUsed,
+            segmentation_type_id: SegmentationTypeID::NotIndicated,
+            segment_num: 0,
+            segments_expected: 0,
+            sub_segment: None,
+        }
+    }
+
+    /// Marks the segmentation event, identified by `event_id`, as cancelled. All other properties
+    /// are ignored when building a cancelled descriptor.
+    pub fn cancel(mut self) -> Self {
+        self.is_cancelled = true;
+        self
+    }
+
+    pub fn segmentation_type_id(mut self, segmentation_type_id: SegmentationTypeID) -> Self {
+        self.segmentation_type_id = segmentation_type_id;
+        self
+    }
+
+    pub fn program_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProgramStart)
+    }
+
+    pub fn program_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProgramEnd)
+    }
+
+    pub fn chapter_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ChapterStart)
+    }
+
+    pub fn chapter_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ChapterEnd)
+    }
+
+    pub fn break_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::BreakStart)
+    }
+
+    pub fn break_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::BreakEnd)
+    }
+
+    pub fn provider_advertisement_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderAdvertisementStart)
+    }
+
+    pub fn provider_advertisement_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderAdvertisementEnd)
+    }
+
+    pub fn distributor_advertisement_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorAdvertisementStart)
+    }
+
+    pub fn distributor_advertisement_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorAdvertisementEnd)
+    }
+
+    pub fn provider_placement_opportunity_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderPlacementOpportunityStart)
+    }
+
+    pub fn provider_placement_opportunity_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderPlacementOpportunityEnd)
+    }
+
+    pub fn distributor_placement_opportunity_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorPlacementOpportunityStart)
+    }
+
+    pub fn distributor_placement_opportunity_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorPlacementOpportunityEnd)
+    }
+
+    pub fn provider_ad_block_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderAdBlockStart)
+    }
+
+    pub fn provider_ad_block_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::ProviderAdBlockEnd)
+    }
+
+    pub fn distributor_ad_block_start(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorAdBlockStart)
+    }
+
+    pub fn distributor_ad_block_end(self) -> Self {
+        self.segmentation_type_id(SegmentationTypeID::DistributorAdBlockEnd)
+    }
+
+    /// Sets the `segmentation_duration` in terms of ticks of the program's 90 kHz clock.
+    pub fn duration_90khz(mut self, segmentation_duration: u64) -> Self {
+        self.segmentation_duration = Some(segmentation_duration);
+        self
+    }
+
+    /// Sets the `segmentation_duration` from a `Duration`, using `rounding` when the `Duration` is
+    /// not a whole number of 90 kHz ticks.
+    pub fn duration(self, segmentation_duration: Duration, rounding: Rounding) -> Self {
+        self.duration_90khz(ticks_from_duration(segmentation_duration, rounding))
+    }
+
+    pub fn delivery_restrictions(mut self, delivery_restrictions: DeliveryRestrictions) -> Self {
+        self.delivery_restrictions = Some(delivery_restrictions);
+        self
+    }
+
+    /// Appends a component to the Component Segmentation Mode list. When no components are added
+    /// the descriptor uses the Program Segmentation Mode.
+    pub fn component_segment(mut self, component_tag: u8, pts_offset: Pts90k) -> Self {
+        self.component_segments
+            .get_or_insert_with(Vec::new)
+            .push(ComponentSegmentation {
+                component_tag,
+                pts_offset,
+            });
+        self
+    }
+
+    pub fn upid(mut self, segmentation_upid: SegmentationUPID) -> Self {
+        self.segmentation_upid = segmentation_upid;
+        self
+    }
+
+    pub fn upid_ad_id(self, ad_id: &str) -> Self {
+        self.upid(SegmentationUPID::AdID(ad_id.to_string()))
+    }
+
+    pub fn upid_isci(self, isci: &str) -> Self {
+        self.upid(SegmentationUPID::ISCI(isci.to_string()))
+    }
+
+    pub fn upid_ti(self, ti: u64) -> Self {
+        self.upid(SegmentationUPID::new_ti(ti))
+    }
+
+    pub fn upid_uri(self, uri: &str) -> Self {
+        self.upid(SegmentationUPID::URI(uri.to_string()))
+    }
+
+    pub fn upid_uuid(self, uuid: [u8; 16]) -> Self {
+        self.upid(SegmentationUPID::UUID(hyphenated_uuid(&uuid)))
+    }
+
+    pub fn upid_mpu(self, format_specifier: &str, private_data: Vec<u8>) -> Self {
+        self.upid(SegmentationUPID::MPU(ManagedPrivateUPID {
+            format_specifier: format_specifier.to_string(),
+            private_data,
+        }))
+    }
+
+    pub fn segment_num(mut self, segment_num: u8) -> Self {
+        self.segment_num = segment_num;
+        self
+    }
+
+    pub fn segments_expected(mut self, segments_expected: u8) -> Self {
+        self.segments_expected = segments_expected;
+        self
+    }
+
+    pub fn sub_segment(mut self, sub_segment_num: u8, sub_segments_expected: u8) -> Self {
+        self.sub_segment = Some(SubSegment {
+            sub_segment_num,
+            sub_segments_expected,
+        });
+        self
+    }
+
+    /// Builds the `SegmentationDescriptor`, validating that it can be encoded.
+    pub fn build(self) -> Result<SegmentationDescriptor, EncodeError> {
+        let scheduled_event = if self.is_cancelled {
+            None
+        } else {
+            let is_end = self.segmentation_type_id.is_in();
+            Some(ScheduledEvent {
+                delivery_restrictions: self.delivery_restrictions,
+                component_segments: self.component_segments,
+                segmentation_duration: if is_end {
+                    None
+                } else {
+                    self.segmentation_duration
+                },
+                segmentation_upid: self.segmentation_upid,
+                sub_segment: if !is_end && self.segmentation_type_id.has_sub_segments() {
+                    self.sub_segment
+                } else {
+                    None
+                },
+                segmentation_type_id: self.segmentation_type_id,
+                segment_num: self.segment_num,
+                segments_expected: self.segments_expected,
+            })
+        };
+        let descriptor = SegmentationDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            event_id: self.event_id,
+            scheduled_event,
+        };
+        descriptor.encode(&mut BitWriter::new())?;
+        Ok(descriptor)
+    }
+}
+
+// The fields of a `ScheduledEvent`, generic over the representation of the `segmentation_upid` so
+// that the parsing can be shared with `ScheduledEventRef`.
+pub(crate) struct ScheduledEventFields<U> {
+    pub(crate) delivery_restrictions: Option<DeliveryRestrictions>,
+    pub(crate) component_segments: Option<Vec<ComponentSegmentation>>,
+    pub(crate) segmentation_duration: Option<u64>,
+    pub(crate) segmentation_upid: U,
+    pub(crate) segmentation_type_id: SegmentationTypeID,
+    pub(crate) segment_num: u8,
+    pub(crate) segments_expected: u8,
+    pub(crate) sub_segment: Option<SubSegment>,
+}
+
+impl<U> ScheduledEventFields<U> {
+    pub(crate) fn try_from<F>(
+        bits: &mut Bits,
+        bits_left_after_descriptor: usize,
+        try_upid_from: F,
+    ) -> Result<Self, ParseError>
+    where
+        F: FnOnce(&mut Bits) -> Result<U, ParseError>,
+    {
+        let program_segmentation_flag = bits.bool("program_segmentation_flag")?;
+        let segmentation_duration_flag = bits.bool("segmentation_duration_flag")?;
+        let delivery_not_restricted_flag = bits.bool("delivery_not_restricted_flag")?;
         let delivery_restrictions = if delivery_not_restricted_flag {
-            bits.consume(5);
+            bits.consume(5)?;
             None
         } else {
-            let web_delivery_allowed = bits.bool();
-            let no_regional_blackout = bits.bool();
-            let archive_allowed = bits.bool();
+            let web_delivery_allowed = bits.bool("web_delivery_allowed")?;
+            let no_regional_blackout = bits.bool("no_regional_blackout")?;
+            let archive_allowed = bits.bool("archive_allowed")?;
             let device_restrictions =
-                DeviceRestrictions::try_from(bits.u8(2)).unwrap_or(DeviceRestrictions::None);
+                DeviceRestrictions::try_from(bits.u8("device_restrictions", 2)?)
+                    .unwrap_or(DeviceRestrictions::None);
             Some(DeliveryRestrictions {
                 web_delivery_allowed,
                 no_regional_blackout,
@@ -649,31 +1759,35 @@ impl ScheduledEvent {
         let component_segments = if program_segmentation_flag {
             None
         } else {
-            let component_count = bits.byte();
+            let component_count = bits.byte("component_count")?;
             let mut components = vec![];
-            for _ in 0..component_count {
-                let component_tag = bits.byte();
-                bits.consume(7);
-                let pts_offset = bits.u64(33);
-                components.push(ComponentSegmentation {
-                    component_tag,
-                    pts_offset,
-                })
+            for index in 0..component_count as usize {
+                let component = bits.scope_at("component", Some(index), |bits| {
+                    let component_tag = bits.byte("component_tag")?;
+                    bits.consume(7)?;
+                    let pts_offset = Pts90k(bits.u64("pts_offset", 33)?);
+                    Ok(ComponentSegmentation {
+                        component_tag,
+                        pts_offset,
+                    })
+                })?;
+                components.push(component);
             }
             Some(components)
         };
         let segmentation_duration = if segmentation_duration_flag {
-            Some(bits.u64(40))
+            Some(bits.u64("segmentation_duration", 40)?)
         } else {
             None
         };
-        let segmentation_upid = SegmentationUPID::try_from(bits)?;
-        let segmentation_type_id = SegmentationTypeID::try_from(bits.byte())?;
-        let segment_num = bits.byte();
-        let segments_expected = bits.byte();
+        let segmentation_upid = bits.scope("segmentation_upid", try_upid_from)?;
+        let segmentation_type_id =
+            SegmentationTypeID::try_from(bits.byte("segmentation_type_id")?)?;
+        let segment_num = bits.byte("segment_num")?;
+        let segments_expected = bits.byte("segments_expected")?;
         let sub_segment =
-            SubSegment::try_from(bits, &segmentation_type_id, bits_left_after_descriptor);
-        Ok(Self {
+            SubSegment::try_from(bits, &segmentation_type_id, bits_left_after_descriptor)?;
+        Ok(ScheduledEventFields {
             delivery_restrictions,
             component_segments,
             segmentation_duration,
@@ -686,41 +1800,125 @@ impl ScheduledEvent {
     }
 }
 
+impl ScheduledEvent {
+    fn try_from(bits: &mut Bits, bits_left_after_descriptor: usize) -> Result<Self, ParseError> {
+        let fields = ScheduledEventFields::try_from(
+            bits,
+            bits_left_after_descriptor,
+            SegmentationUPID::try_from,
+        )?;
+        Ok(Self {
+            delivery_restrictions: fields.delivery_restrictions,
+            component_segments: fields.component_segments,
+            segmentation_duration: fields.segmentation_duration,
+            segmentation_upid: fields.segmentation_upid,
+            segmentation_type_id: fields.segmentation_type_id,
+            segment_num: fields.segment_num,
+            segments_expected: fields.segments_expected,
+            sub_segment: fields.sub_segment,
+        })
+    }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.bool(self.component_segments.is_none());
+        bits.bool(self.segmentation_duration.is_some());
+        bits.bool(self.delivery_restrictions.is_none());
+        match &self.delivery_restrictions {
+            Some(delivery_restrictions) => {
+                bits.bool(delivery_restrictions.web_delivery_allowed);
+                bits.bool(delivery_restrictions.no_regional_blackout);
+                bits.bool(delivery_restrictions.archive_allowed);
+                bits.u8(2, delivery_restrictions.device_restrictions.value());
+            }
+            None => bits.reserved(5),
+        }
+        if let Some(component_segments) = &self.component_segments {
+            bits.validate_length(
+                component_segments.len(),
+                255,
+                "SegmentationDescriptor component_count",
+            )?;
+            bits.byte(component_segments.len() as u8);
+            for component in component_segments {
+                bits.validate(
+                    33,
+                    component.pts_offset.0,
+                    "ComponentSegmentation pts_offset",
+                )?;
+                bits.byte(component.component_tag);
+                bits.reserved(7);
+                bits.u64(33, component.pts_offset.0);
+            }
+        }
+        if let Some(segmentation_duration) = self.segmentation_duration {
+            bits.validate(
+                40,
+                segmentation_duration,
+                "SegmentationDescriptor segmentation_duration",
+            )?;
+            bits.u64(40, segmentation_duration);
+        }
+        self.segmentation_upid.encode(bits)?;
+        bits.byte(self.segmentation_type_id.value());
+        bits.byte(self.segment_num);
+        bits.byte(self.segments_expected);
+        if let Some(sub_segment) = &self.sub_segment {
+            if self.segmentation_type_id.has_sub_segments() {
+                bits.byte(sub_segment.sub_segment_num);
+                bits.byte(sub_segment.sub_segments_expected);
+            }
+        }
+        Ok(())
+    }
+}
+
 impl SubSegment {
     fn try_from(
         bits: &mut Bits,
         segmentation_type_id: &SegmentationTypeID,
         bits_left_after_descriptor: usize,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, ParseError> {
         let bits_left = bits.bits_remaining();
         if bits_left < 16 {
-            return None;
+            return Ok(None);
         }
         if bits_left - 16 < bits_left_after_descriptor {
-            return None;
+            return Ok(None);
         }
-        match segmentation_type_id {
-            SegmentationTypeID::ProviderPlacementOpportunityStart
-            | SegmentationTypeID::DistributorPlacementOpportunityStart
-            | SegmentationTypeID::ProviderOverlayPlacementOpportunityStart
-            | SegmentationTypeID::DistributorOverlayPlacementOpportunityStart => {
-                let sub_segment_num = bits.byte();
-                let sub_segments_expected = bits.byte();
-                Some(Self {
-                    sub_segment_num,
-                    sub_segments_expected,
-                })
-            }
-            _ => None,
+        if segmentation_type_id.has_sub_segments() {
+            let sub_segment_num = bits.byte("sub_segment_num")?;
+            let sub_segments_expected = bits.byte("sub_segments_expected")?;
+            Ok(Some(Self {
+                sub_segment_num,
+                sub_segments_expected,
+            }))
+        } else {
+            Ok(None)
         }
     }
 }
 
 impl SegmentationUPID {
+    /// Creates the `SegmentationUPID` from the `segmentation_upid()` bytes of the given type (i.e.
+    /// the bytes that follow `segmentation_upid_length`).
+    pub fn try_from_type_and_bytes(
+        upid_type: SegmentationUPIDType,
+        bytes: &[u8],
+    ) -> Result<Self, ParseError> {
+        let upid_length =
+            u8::try_from(bytes.len()).map_err(|_| ParseError::UnexpectedEndOfData {
+                expected_minimum_bits_left: 255 * 8,
+                actual_bits_left: (bytes.len() as u32) * 8,
+                description: "SegmentationUPID; bytes exceed maximum segmentation_upid_length",
+            })?;
+        let mut bits = Bits::new(bytes);
+        Self::try_from_with_type(&mut bits, upid_type, upid_length)
+    }
+
     fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
-        let upid_type_raw_value = bits.byte();
+        let upid_type_raw_value = bits.byte("segmentation_upid_type")?;
         let upid_type = SegmentationUPIDType::try_from(upid_type_raw_value)?;
-        let upid_length = bits.byte();
+        let upid_length = bits.byte("segmentation_upid_length")?;
         bits.validate((upid_length as u32) * 8, "SegmentationUPID; reading loop")?;
         Self::try_from_with_type(bits, upid_type, upid_length)
     }
@@ -730,75 +1928,65 @@ impl SegmentationUPID {
         upid_type: SegmentationUPIDType,
         upid_length: u8,
     ) -> Result<Self, ParseError> {
-        match upid_type {
-            SegmentationUPIDType::NotUsed => {
-                validate(upid_length, 0, upid_type)?;
-                Ok(Self::NotUsed)
+        if let Some(expected_length) = upid_type.expected_length() {
+            if upid_length != expected_length {
+                let handling = bits.config().upid_length_mismatch;
+                bits.handle_error(
+                    handling,
+                    ParseError::UnexpectedSegmentationUPIDLength {
+                        declared_segmentation_upid_length: upid_length,
+                        expected_segmentation_upid_length: expected_length,
+                        segmentation_upid_type: upid_type.clone(),
+                    },
+                )?;
+                let bytes = bits.bytes("segmentation_upid", upid_length as usize)?;
+                return Ok(Self::Invalid { upid_type, bytes });
             }
+        }
+        match upid_type {
+            SegmentationUPIDType::NotUsed => Ok(Self::NotUsed),
             SegmentationUPIDType::UserDefined => {
-                let user_defined =
-                    bits.string(upid_length as usize, "SegmentationUPIDType::UserDefined")?;
+                let user_defined = bits.string(
+                    "user_defined",
+                    upid_length as usize,
+                    "SegmentationUPIDType::UserDefined",
+                )?;
                 Ok(Self::UserDefined(user_defined))
             }
             SegmentationUPIDType::ISCI => {
-                validate(upid_length, 8, upid_type)?;
-                let isci = bits.string(upid_length as usize, "SegmentationUPIDType::ISCI")?;
+                let isci =
+                    bits.string("isci", upid_length as usize, "SegmentationUPIDType::ISCI")?;
                 Ok(Self::ISCI(isci))
             }
             SegmentationUPIDType::AdID => {
-                validate(upid_length, 12, upid_type)?;
-                let ad_id = bits.string(upid_length as usize, "SegmentationUPIDType::AdID")?;
+                let ad_id =
+                    bits.string("ad_id", upid_length as usize, "SegmentationUPIDType::AdID")?;
                 Ok(Self::AdID(ad_id))
             }
             SegmentationUPIDType::UMID => {
-                validate(upid_length, 32, upid_type)?;
-                let mut umid_vec = vec![];
-                for _ in 0..8 {
-                    let mut s = String::with_capacity(8);
-                    write!(&mut s, "{:08x}", bits.u32(32)).unwrap();
-                    umid_vec.push(s.to_uppercase());
+                let mut umid = String::with_capacity(71);
+                for i in 0..8 {
+                    if i > 0 {
+                        umid.push('.');
+                    }
+                    write!(&mut umid, "{:08X}", bits.u32("umid", 32)?).unwrap();
                 }
-                Ok(Self::UMID(umid_vec.join(".")))
+                Ok(Self::UMID(umid))
             }
             SegmentationUPIDType::DeprecatedISAN => {
-                validate(upid_length, 8, upid_type)?;
-                let check = HyphenSeparatedCheckedHex {
-                    version: HyphenSeparatedCheckedHexVersion::DeprecatedISAN,
-                };
-                Ok(Self::DeprecatedISAN(check.read(bits)))
-            }
-            SegmentationUPIDType::ISAN => {
-                validate(upid_length, 12, upid_type)?;
-                let check = HyphenSeparatedCheckedHex {
-                    version: HyphenSeparatedCheckedHexVersion::VersionedISAN,
-                };
-                Ok(Self::ISAN(check.read(bits)))
+                Ok(Self::DeprecatedISAN(Isan::try_from(bits, false)?))
             }
+            SegmentationUPIDType::ISAN => Ok(Self::ISAN(Isan::try_from(bits, true)?)),
             SegmentationUPIDType::TID => {
-                validate(upid_length, 12, upid_type)?;
-                let tid = bits.string(upid_length as usize, "SegmentationUPIDType::TID")?;
+                let tid = bits.string("tid", upid_length as usize, "SegmentationUPIDType::TID")?;
                 Ok(Self::TID(tid))
             }
-            SegmentationUPIDType::TI => {
-                validate(upid_length, 8, upid_type)?;
-                Ok(Self::TI(format!(
-                    "0x{}",
-                    encode_hex(&bits.bytes(8)).to_uppercase()
-                )))
-            }
+            SegmentationUPIDType::TI => Ok(Self::new_ti(u64::from_be_bytes(bits.array("ti")?))),
             SegmentationUPIDType::ADI => {
-                let adi = bits.string(upid_length as usize, "SegmentationUPIDType::ADI")?;
+                let adi = bits.string("adi", upid_length as usize, "SegmentationUPIDType::ADI")?;
                 Ok(Self::ADI(adi))
             }
-            SegmentationUPIDType::EIDR => {
-                validate(upid_length, 12, upid_type)?;
-                let decimal = format!("10.{}", bits.u16(16));
-                let check = HyphenSeparatedCheckedHex {
-                    version: HyphenSeparatedCheckedHexVersion::Eidr,
-                };
-                let hex_components = check.read(bits);
-                Ok(Self::EIDR(format!("{}/{}", decimal, hex_components)))
-            }
+            SegmentationUPIDType::EIDR => Ok(Self::EIDR(Eidr::try_from(bits)?)),
             SegmentationUPIDType::ATSCContentIdentifier => {
                 let atsc = ATSCContentIdentifier::try_from(bits, upid_length)?;
                 Ok(Self::ATSCContentIdentifier(atsc))
@@ -808,40 +1996,223 @@ impl SegmentationUPID {
                 Ok(Self::MPU(mpu))
             }
             SegmentationUPIDType::MID => {
+                bits.enter_nested_upid()?;
                 let mut mid = vec![];
                 let bits_remaining_after_upid =
                     bits.bits_remaining() - ((upid_length as usize) * 8);
                 while bits.bits_remaining() > bits_remaining_after_upid {
-                    mid.push(Self::try_from(bits)?);
+                    let index = Some(mid.len());
+                    mid.push(bits.scope_at("segmentation_upid", index, Self::try_from)?);
                 }
+                bits.exit_nested_upid();
                 Ok(Self::MID(mid))
             }
             SegmentationUPIDType::ADSInformation => {
-                let ads =
-                    bits.string(upid_length as usize, "SegmentationUPIDType::ADSInformation")?;
+                let ads = bits.string(
+                    "ads_information",
+                    upid_length as usize,
+                    "SegmentationUPIDType::ADSInformation",
+                )?;
                 Ok(Self::ADSInformation(ads))
             }
             SegmentationUPIDType::URI => {
-                let uri = bits.string(upid_length as usize, "SegmentationUPIDType::URI")?;
+                let uri = bits.string("uri", upid_length as usize, "SegmentationUPIDType::URI")?;
                 Ok(Self::URI(uri))
             }
             SegmentationUPIDType::UUID => {
-                validate(upid_length, 16, upid_type)?;
-                let uuid = bits.string(16, "SegmentationUPIDType::UUID")?;
-                Ok(Self::UUID(uuid))
+                Ok(Self::UUID(hyphenated_uuid(&bits.array::<16>("uuid")?)))
+            }
+            SegmentationUPIDType::Reserved(value) => {
+                Err(ParseError::UnrecognisedSegmentationUPIDType(value))
+            }
+        }
+    }
+}
+
+impl SegmentationUPID {
+    /// The `segmentation_upid()` bytes (i.e. the bytes that follow `segmentation_upid_length`),
+    /// for forwarding the UPID verbatim (e.g. to an ESAM acquisition point). Every type of UPID
+    /// is decoded without loss, so these are the same bytes that the UPID was parsed from.
+    /// ```
+    /// use scte35::splice_descriptor::segmentation_descriptor::{
+    ///     SegmentationUPID, SegmentationUPIDType,
+    /// };
+    ///
+    /// let upid = SegmentationUPID::TI(String::from("0x000000002CA0A18A"));
+    /// let bytes = upid.raw_bytes().unwrap();
+    /// assert_eq!(vec![0x00, 0x00, 0x00, 0x00, 0x2C, 0xA0, 0xA1, 0x8A], bytes);
+    /// assert_eq!(
+    ///     Ok(upid),
+    ///     SegmentationUPID::try_from_type_and_bytes(SegmentationUPIDType::TI, &bytes)
+    /// );
+    /// ```
+    pub fn raw_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut bits = BitWriter::new();
+        self.encode_value(&mut bits)?;
+        Ok(bits.into_bytes())
+    }
+
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        let mut upid_bits = BitWriter::new();
+        self.encode_value(&mut upid_bits)?;
+        let upid_bytes = upid_bits.into_bytes();
+        bits.validate_length(
+            upid_bytes.len(),
+            255,
+            "SegmentationUPID segmentation_upid_length",
+        )?;
+        bits.byte(self.upid_type().value());
+        bits.byte(upid_bytes.len() as u8);
+        bits.bytes(&upid_bytes);
+        Ok(())
+    }
+
+    fn encode_value(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        let upid_type = self.upid_type();
+        match self {
+            Self::NotUsed => {}
+            Self::UserDefined(user_defined) => bits.string(user_defined),
+            Self::ISCI(isci) => {
+                validate_encoded(isci.len(), 8, upid_type)?;
+                bits.string(isci);
+            }
+            Self::AdID(ad_id) => {
+                validate_encoded(ad_id.len(), 12, upid_type)?;
+                bits.string(ad_id);
             }
+            Self::UMID(umid) => {
+                let umid_bytes = decode_separated_hex(umid, '.', 8, upid_type.clone())?;
+                validate_encoded(umid_bytes.len(), 32, upid_type)?;
+                bits.bytes(&umid_bytes);
+            }
+            Self::DeprecatedISAN(isan) => {
+                validate_encoded(isan.to_binary().len(), 8, upid_type)?;
+                isan.encode(bits)?;
+            }
+            Self::ISAN(isan) => {
+                validate_encoded(isan.to_binary().len(), 12, upid_type)?;
+                isan.encode(bits)?;
+            }
+            Self::TID(tid) => {
+                validate_encoded(tid.len(), 12, upid_type)?;
+                bits.string(tid);
+            }
+            Self::TI(ti) => {
+                let hex = ti
+                    .strip_prefix("0x")
+                    .or_else(|| ti.strip_prefix("0X"))
+                    .unwrap_or(ti);
+                let ti_bytes =
+                    decode_hex(hex).map_err(|_| EncodeError::InvalidSegmentationUPID {
+                        segmentation_upid_type: upid_type.clone(),
+                        description: "TI is not a valid hex string",
+                    })?;
+                validate_encoded(ti_bytes.len(), 8, upid_type)?;
+                bits.bytes(&ti_bytes);
+            }
+            Self::ADI(adi) => bits.string(adi),
+            Self::EIDR(eidr) => eidr.encode(bits),
+            Self::ATSCContentIdentifier(atsc) => atsc.encode(bits)?,
+            Self::MPU(mpu) => mpu.encode(bits)?,
+            Self::MID(mid) => {
+                for upid in mid {
+                    upid.encode(bits)?;
+                }
+            }
+            Self::ADSInformation(ads) => bits.string(ads),
+            Self::URI(uri) => bits.string(uri),
+            Self::UUID(uuid) => {
+                if uuid.len() == 16 {
+                    bits.string(uuid);
+                } else {
+                    bits.bytes(
+                        &uuid_bytes(uuid).ok_or(EncodeError::InvalidSegmentationUPID {
+                            segmentation_upid_type: upid_type,
+                            description:
+                                "UUID must be 32 hexadecimal characters, optionally hyphenated",
+                        })?,
+                    );
+                }
+            }
+            Self::Invalid { bytes, .. } => {
+                // Only a UPID whose length does not match that of its type can be parsed as
+                // `Invalid`, so any other is rejected to keep the encoding consistent.
+                if upid_type
+                    .expected_length()
+                    .is_none_or(|expected_length| bytes.len() == expected_length as usize)
+                {
+                    return Err(EncodeError::InvalidSegmentationUPID {
+                        segmentation_upid_type: upid_type,
+                        description: "Invalid UPID must have a length that does not match its type",
+                    });
+                }
+                bits.bytes(bytes);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn hyphenated_uuid(bytes: &[u8]) -> String {
+    let mut uuid = String::with_capacity(bytes.len() * 2 + 4);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            uuid.push('-');
+        }
+        write!(&mut uuid, "{:02x}", byte).unwrap();
+    }
+    uuid
+}
+
+type CharacterRun = (usize, fn(&char) -> bool);
+
+// Validates that `value` is made up of consecutive runs of characters, where each run is the given
+// number of characters that each satisfy the given predicate.
+fn validate_characters(
+    value: &str,
+    runs: &[CharacterRun],
+    upid_type: SegmentationUPIDType,
+    description: &'static str,
+) -> Result<(), EncodeError> {
+    let expected_length: usize = runs.iter().map(|(length, _)| length).sum();
+    validate_encoded(value.len(), expected_length as u8, upid_type.clone())?;
+    let mut chars = value.chars();
+    for (length, predicate) in runs {
+        if !chars.by_ref().take(*length).all(|c| predicate(&c)) {
+            return Err(EncodeError::InvalidSegmentationUPID {
+                segmentation_upid_type: upid_type,
+                description,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Decodes the 16 bytes of a UUID from its hexadecimal digits, which may be hyphenated.
+fn uuid_bytes(uuid: &str) -> Option<Vec<u8>> {
+    let hex = if uuid.len() == 36 {
+        let groups: Vec<&str> = uuid.split('-').collect();
+        if groups.iter().map(|group| group.len()).collect::<Vec<_>>() != [8, 4, 4, 4, 12] {
+            return None;
         }
+        groups.concat()
+    } else {
+        String::from(uuid)
+    };
+    if hex.len() != 32 {
+        return None;
     }
+    decode_hex(&hex).ok()
 }
 
-fn validate(
-    upid_length: u8,
+fn validate_encoded(
+    upid_length: usize,
     expected_length: u8,
     upid_type: SegmentationUPIDType,
-) -> Result<(), ParseError> {
-    if upid_length != expected_length {
-        Err(ParseError::UnexpectedSegmentationUPIDLength {
-            declared_segmentation_upid_length: upid_length,
+) -> Result<(), EncodeError> {
+    if upid_length != expected_length as usize {
+        Err(EncodeError::UnexpectedSegmentationUPIDLength {
+            actual_segmentation_upid_length: upid_length,
             expected_segmentation_upid_length: expected_length,
             segmentation_upid_type: upid_type,
         })
@@ -850,87 +2221,48 @@ fn validate(
     }
 }
 
+// Decodes the hex groups of a separated string (such as the UMID representation), where
+// any group that is not exactly `group_length` characters long (i.e. check characters) is skipped.
+fn decode_separated_hex(
+    string: &str,
+    separator: char,
+    group_length: usize,
+    upid_type: SegmentationUPIDType,
+) -> Result<Vec<u8>, EncodeError> {
+    let hex: String = string
+        .split(separator)
+        .filter(|group| group.len() == group_length)
+        .collect();
+    decode_hex(&hex).map_err(|_| EncodeError::InvalidSegmentationUPID {
+        segmentation_upid_type: upid_type,
+        description: "expected groups of hexadecimal characters",
+    })
+}
+
 impl ManagedPrivateUPID {
     fn try_from(bits: &mut Bits, upid_length: u8) -> Result<Self, ParseError> {
         if upid_length < 4 {
             return Err(ParseError::InvalidMPUInSegmentationUPID { upid_length });
         }
         let private_data_length = upid_length - 4;
-        let format_specifier = bits.string(4, "ManagedPrivateUPID")?;
-        let mut private_data = vec![];
-        for _ in 0..private_data_length {
-            private_data.push(bits.byte());
-        }
+        let format_specifier = bits.string("format_specifier", 4, "ManagedPrivateUPID")?;
+        let private_data = bits.bytes("private_data", private_data_length as usize)?;
         Ok(Self {
             format_specifier,
             private_data,
         })
     }
-}
-
-enum HyphenSeparatedCheckedHexVersion {
-    DeprecatedISAN,
-    VersionedISAN,
-    Eidr,
-}
-
-struct HyphenSeparatedCheckedHex {
-    version: HyphenSeparatedCheckedHexVersion,
-}
 
-impl HyphenSeparatedCheckedHex {
-    fn read(&self, bits: &mut Bits) -> String {
-        let (check_indices, index_max) = match self.version {
-            HyphenSeparatedCheckedHexVersion::DeprecatedISAN => (vec![4], 4),
-            HyphenSeparatedCheckedHexVersion::VersionedISAN => (vec![4, 7], 7),
-            HyphenSeparatedCheckedHexVersion::Eidr => (vec![5], 5),
-        };
-        let mut sections = vec![];
-        for i in 0..=index_max {
-            if check_indices.contains(&i) {
-                sections.push(check_char(&sections).to_string());
-            } else {
-                let mut s = String::with_capacity(4);
-                write!(&mut s, "{:04x}", bits.u16(16)).unwrap();
-                sections.push(s.to_uppercase());
-            }
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        if self.format_specifier.len() != 4 {
+            return Err(EncodeError::UnexpectedLength {
+                length: self.format_specifier.len(),
+                expected_length: 4,
+                description: "ManagedPrivateUPID format_specifier",
+            });
         }
-        sections.join("-")
-    }
-}
-
-const CHAR_ARRAY: [char; 36] = [
-    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
-    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
-];
-
-// The check calculation is taken from isan_check_digit_calculation_v2.0.pdf included
-// in the repository.
-fn check_char(isan: &[String]) -> char {
-    let isan: Vec<String> = isan
-        .iter()
-        .filter(|s| s.chars().count() > 1)
-        .cloned()
-        .collect();
-    let adjusted_product = isan.join("").chars().fold(36, |adjusted_sum, char| {
-        let decimal_value = u8::from_str_radix(&char.to_string(), 16)
-            .expect("Should be safe as all non-check chars in ISAN array are hexadecimal.");
-        let mut sum = adjusted_sum + decimal_value;
-        if sum > 36 {
-            sum -= 36;
-        }
-        let mut product = sum * 2;
-        if product >= 37 {
-            product -= 37;
-        }
-        product
-    });
-    if adjusted_product == 1 {
-        '0'
-    } else {
-        CHAR_ARRAY
-            .get((37 - adjusted_product) as usize)
-            .unwrap()
-            .clone()
+        bits.string(&self.format_specifier);
+        bits.bytes(&self.private_data);
+        Ok(())
     }
 }
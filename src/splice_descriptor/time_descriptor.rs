@@ -1,5 +1,13 @@
-use super::DescriptorLengthExpectation;
-use crate::{bit_reader::Bits, error::ParseError};
+use super::{encode_with_descriptor_length, DescriptorLengthExpectation, CUEI_IDENTIFIER};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The number of seconds from the NTP epoch (00:00, January 1, 1900) to the Unix epoch.
+const NTP_UNIX_EPOCH_OFFSET_SECONDS: u64 = 2_208_988_800;
 
 /// The `TimeDescriptor` is an implementation of a `SpliceDescriptor`. It provides an optional
 /// extension to the `SpliceInsert`, `SpliceNull` and `TimeSignal` commands that allows a
@@ -40,7 +48,9 @@ use crate::{bit_reader::Bits, error::ParseError};
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TimeDescriptor {
     /// This 32-bit number is used to identify the owner of the descriptor. The identifier shall
     /// have a value of 0x43554549 (ASCII “CUEI”).
@@ -59,16 +69,57 @@ pub struct TimeDescriptor {
 }
 
 impl TimeDescriptor {
+    /// Creates a `TimeDescriptor` for a wall-clock (UTC) time, where `utc_offset` is the current
+    /// difference between TAI and UTC in seconds (37 as of 2017). Returns `None` for times before
+    /// the Unix epoch.
+    /// ```
+    /// use scte35::splice_descriptor::time_descriptor::TimeDescriptor;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let utc = UNIX_EPOCH + Duration::new(1_704_067_200, 500_000_000);
+    /// let time_descriptor = TimeDescriptor::from_utc(utc, 37).unwrap();
+    /// assert_eq!(1_704_067_237, time_descriptor.tai_seconds);
+    /// assert_eq!(500_000_000, time_descriptor.tai_ns);
+    /// assert_eq!(Some(utc), time_descriptor.utc_system_time());
+    /// assert_eq!(3_913_056_000, time_descriptor.ntp_seconds());
+    /// ```
+    pub fn from_utc(utc: SystemTime, utc_offset: u16) -> Option<Self> {
+        let since_unix_epoch = utc.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            identifier: CUEI_IDENTIFIER,
+            tai_seconds: since_unix_epoch.as_secs() + utc_offset as u64,
+            tai_ns: since_unix_epoch.subsec_nanos(),
+            utc_offset,
+        })
+    }
+
+    /// The UTC time, calculated as the TAI time minus the `utc_offset`. Returns `None` when the
+    /// result would be before the Unix epoch.
+    pub fn utc_system_time(&self) -> Option<SystemTime> {
+        let utc_seconds = self.tai_seconds.checked_sub(self.utc_offset as u64)?;
+        let since_unix_epoch = Duration::from_secs(utc_seconds)
+            .checked_add(Duration::from_nanos(self.tai_ns as u64))?;
+        UNIX_EPOCH.checked_add(since_unix_epoch)
+    }
+
+    /// The whole number of NTP seconds (since 00:00, January 1, 1900), calculated as the TAI
+    /// seconds minus the `utc_offset`, plus 2,208,988,800.
+    pub fn ntp_seconds(&self) -> u64 {
+        self.tai_seconds
+            .saturating_add(NTP_UNIX_EPOCH_OFFSET_SECONDS)
+            .saturating_sub(self.utc_offset as u64)
+    }
+
     // NOTE: It is assumed that the splice_descriptor_tag has already been read.
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         let expectation = DescriptorLengthExpectation::try_from(bits, "TimeDescriptor")?;
 
-        let identifier = bits.u32(32);
-        let tai_seconds = bits.u64(48);
-        let tai_ns = bits.u32(32);
-        let utc_offset = bits.u16(16);
+        let identifier = bits.u32("identifier", 32)?;
+        let tai_seconds = bits.u64("tai_seconds", 48)?;
+        let tai_ns = bits.u32("tai_ns", 32)?;
+        let utc_offset = bits.u16("utc_offset", 16)?;
 
-        expectation.validate_non_fatal(bits, super::SpliceDescriptorTag::TimeDescriptor);
+        expectation.validate(bits, super::SpliceDescriptorTag::TimeDescriptor)?;
 
         Ok(Self {
             identifier,
@@ -77,4 +128,15 @@ impl TimeDescriptor {
             utc_offset,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        encode_with_descriptor_length(bits, "TimeDescriptor", |bits| {
+            bits.validate(48, self.tai_seconds, "TimeDescriptor tai_seconds")?;
+            bits.u32(32, self.identifier);
+            bits.u64(48, self.tai_seconds);
+            bits.u32(32, self.tai_ns);
+            bits.u16(16, self.utc_offset);
+            Ok(())
+        })
+    }
 }
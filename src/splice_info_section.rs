@@ -1,11 +1,31 @@
+#[cfg(feature = "tracing")]
+use crate::trace;
 use crate::{
     bit_reader::Bits,
-    error::ParseError,
+    bit_writer::BitWriter,
+    crc::crc_32_mpeg_2,
+    encryption::Decryptor,
+    error::{EncodeError, LocatedParseError, ParseError},
     hex,
-    splice_command::SpliceCommand,
-    splice_descriptor::{try_splice_descriptors_from, SpliceDescriptor},
+    splice_command::{splice_insert::SpliceMode, SpliceCommand, SpliceCommandType},
+    splice_descriptor::{
+        avail_descriptor::AvailDescriptor,
+        custom_descriptor::SpliceDescriptorRegistry,
+        dtmf_descriptor::DTMFDescriptor,
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationTypeID},
+        try_splice_descriptors_into, SpliceDescriptor,
+    },
+    time::{duration_from_ticks, Pts90k, SpliceTime},
+    transport_stream::PACKET_SIZE,
+};
+#[cfg(feature = "base64")]
+use base64::prelude::*;
+use std::{
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
 };
-use bitter::BigEndianReader;
 
 /// The `SpliceInfoSection` shall be carried in transport packets whereby only one section or
 /// partial section may be in any transport packet. `SpliceInfoSection`s shall always start at the
@@ -48,7 +68,9 @@ use bitter::BigEndianReader;
   CRC_32                          32 rpchof
 }
 */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SpliceInfoSection {
     /// This is an 8-bit field. Its value shall be 0xFC.
     pub table_id: u8,
@@ -88,7 +110,7 @@ pub struct SpliceInfoSection {
     /// The `pts_adjustment` shall, at all times, be the proper value to use for conversion of the
     /// `pts_time` field to the current time-base. The conversion is done by adding the two fields.
     /// In the presence of a wrap or overflow condition, the carry shall be ignored.
-    pub pts_adjustment: u64,
+    pub pts_adjustment: Pts90k,
     /// A 12-bit value used by the SCTE 35 message provider to assign messages to authorization
     /// tiers. This field may take any value between 0x000 and 0xFFF. The value of 0xFFF provides
     /// backwards compatibility and shall be ignored by downstream equipment. When using tier, the
@@ -107,82 +129,1339 @@ pub struct SpliceInfoSection {
     /// A list of errors that have not caused the message to be un-parsable, but are inconsistent
     /// with the specification. An example of this could be a splice command who's computed length
     /// after parsing did not match the indicated length of the command.
+    ///
+    /// When the `serde` feature is enabled this list is empty when deserializing data that does
+    /// not include it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub non_fatal_errors: Vec<ParseError>,
 }
 
+/// The `non_fatal_errors` are not included in the hash, as `ParseError` does not implement `Hash`.
+impl Hash for SpliceInfoSection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.table_id.hash(state);
+        self.sap_type.hash(state);
+        self.protocol_version.hash(state);
+        self.encrypted_packet.hash(state);
+        self.pts_adjustment.hash(state);
+        self.tier.hash(state);
+        self.splice_command.hash(state);
+        self.splice_descriptors.hash(state);
+        self.crc_32.hash(state);
+    }
+}
+
+/// Parses a `SpliceInfoSection` from a string that is either hex encoded (with or without a `0x`
+/// prefix) or base64 encoded, ignoring any surrounding whitespace (e.g. from a cue pasted by an
+/// operator). A string made up only of hexadecimal digits is decoded as hex, which cannot be
+/// confused with the base64 of a section, as that always starts with `/D` (the base64 of the
+/// `table_id` 0xFC). Without the `base64` feature every string is decoded as hex.
+/// ```
+/// use scte35::splice_info_section::SpliceInfoSection;
+///
+/// let from_hex: SpliceInfoSection = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A"
+///     .parse()
+///     .unwrap();
+/// # #[cfg(feature = "base64")] {
+/// let from_base64: SpliceInfoSection =
+///     "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo="
+///         .parse()
+///         .unwrap();
+/// assert_eq!(from_hex, from_base64);
+/// # }
+/// ```
+impl FromStr for SpliceInfoSection {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let is_hex = s.starts_with("0x")
+            || s.starts_with("0X")
+            || (!s.is_empty() && s.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        #[cfg(feature = "base64")]
+        if !is_hex {
+            return Self::try_from_base64_string(s);
+        }
+        #[cfg(not(feature = "base64"))]
+        let _ = is_hex;
+        Self::try_from_hex_string(s)
+    }
+}
+
 impl SpliceInfoSection {
+    /// Creates a `SpliceInfoSectionBuilder` with defaults suitable for most messages (see
+    /// `SpliceInfoSectionBuilder` for the defaults used).
+    pub fn builder() -> SpliceInfoSectionBuilder {
+        SpliceInfoSectionBuilder::new()
+    }
+
     /// Creates a `SpliceInfoSection` using the provided hex encoded string.
     pub fn try_from_hex_string(hex_string: &str) -> Result<SpliceInfoSection, ParseError> {
-        let data = if hex_string.starts_with("0x") || hex_string.starts_with("0X") {
-            hex::decode_hex(&hex_string[2..])?
-        } else {
-            hex::decode_hex(hex_string)?
-        };
+        let mut data = Vec::new();
+        hex::decode_prefixed_hex_into(hex_string, &mut data)?;
         Self::try_from_bytes(&data)
     }
 
+    /// Creates a `SpliceInfoSection` using the provided base64 encoded string (e.g. the value of
+    /// an HLS `#EXT-OATCLS-SCTE35` tag or an ESAM `BinaryData` element).
+    #[cfg(feature = "base64")]
+    pub fn try_from_base64_string(base64_string: &str) -> Result<SpliceInfoSection, ParseError> {
+        Self::try_from_bytes(&BASE64_STANDARD.decode(base64_string)?)
+    }
+
     pub fn try_from_bytes(data: &[u8]) -> Result<SpliceInfoSection, ParseError> {
-        let mut bit_reader = BigEndianReader::new(&data);
-        let mut bits = Bits::new(&mut bit_reader);
+        Self::try_from_bytes_with_config(data, &ParserConfig::default())
+    }
+
+    /// Creates a `SpliceInfoSection` using the provided bytes, parsing any splice descriptor that
+    /// matches a parser in the `registry` as a `SpliceDescriptor::Custom` (see
+    /// `SpliceDescriptorRegistry`).
+    pub fn try_from_bytes_with_registry(
+        data: &[u8],
+        registry: &SpliceDescriptorRegistry,
+    ) -> Result<SpliceInfoSection, ParseError> {
+        let config = ParserConfig {
+            descriptor_registry: Some(registry.clone()),
+            ..Default::default()
+        };
+        Self::try_from_bytes_with_config(data, &config)
+    }
+
+    /// Creates a `SpliceInfoSection` using the provided bytes, with the parsing behaviour
+    /// controlled by the `config` (see `ParserConfig`).
+    pub fn try_from_bytes_with_config(
+        data: &[u8],
+        config: &ParserConfig,
+    ) -> Result<SpliceInfoSection, ParseError> {
+        Self::try_from_bytes_reusing(data, config, vec![])
+    }
+
+    /// Parses the `SpliceInfoSection` at the start of `data`, whose length is taken from its
+    /// `section_length`, returning it along with the number of bytes that were consumed. Any bytes
+    /// after the section (e.g. the start of another section) are not parsed, and when the
+    /// `ignore_trailing_padding` of the `config` is set, the 0xFF stuffing bytes that immediately
+    /// follow the section are also consumed.
+    /// ```
+    /// use scte35::splice_info_section::{ParserConfig, SpliceInfoSection};
+    ///
+    /// let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+    /// let section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// let mut data = section.try_to_bytes().unwrap();
+    /// data.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFC]);
+    ///
+    /// let (parsed, consumed) =
+    ///     SpliceInfoSection::try_from_bytes_prefix(&data, &ParserConfig::default()).unwrap();
+    /// assert_eq!(section, parsed);
+    /// assert_eq!(50, consumed);
+    ///
+    /// let config = ParserConfig {
+    ///     ignore_trailing_padding: true,
+    ///     ..Default::default()
+    /// };
+    /// let (_, consumed) = SpliceInfoSection::try_from_bytes_prefix(&data, &config).unwrap();
+    /// assert_eq!(53, consumed);
+    /// ```
+    pub fn try_from_bytes_prefix(
+        data: &[u8],
+        config: &ParserConfig,
+    ) -> Result<(SpliceInfoSection, usize), ParseError> {
+        // When the section is incomplete the whole of the `data` is parsed, so that the error is
+        // the same as that of `try_from_bytes_with_config`.
+        let section_size = section_size(data).unwrap_or(data.len()).min(data.len());
+        let mut consumed = section_size;
+        let splice_info_section = Self::try_from_bytes_with_config(&data[..section_size], config)?;
+        if config.ignore_trailing_padding {
+            consumed += data[consumed..]
+                .iter()
+                .take_while(|byte| **byte == 0xFF)
+                .count();
+        }
+        Ok((splice_info_section, consumed))
+    }
+
+    /// Creates a `SpliceInfoSection` using the provided bytes, where any error is located at the
+    /// bit offset and the path of the structure at which parsing failed (see
+    /// `LocatedParseError`).
+    /// ```
+    /// use base64::prelude::*;
+    /// use scte35::splice_info_section::SpliceInfoSection;
+    ///
+    /// let mut data = BASE64_STANDARD
+    ///     .decode("/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==")
+    ///     .unwrap();
+    /// // Declare a length of 7 bytes for the TI UPID, which should always be 8 bytes long.
+    /// data[39] = 7;
+    /// let error = SpliceInfoSection::try_from_bytes_located(&data).unwrap_err();
+    /// assert_eq!(320, error.bit_offset);
+    /// assert_eq!("splice_descriptors[0].segmentation_upid", error.path);
+    /// assert_eq!(
+    ///     "At bit 320 in splice_descriptors[0].segmentation_upid: Declared upid length was 7; \
+    ///      however, expected length for upid type 8 is 8.",
+    ///     error.to_string()
+    /// );
+    /// ```
+    pub fn try_from_bytes_located(data: &[u8]) -> Result<SpliceInfoSection, LocatedParseError> {
+        Self::try_from_bytes_located_with_config(data, &ParserConfig::default())
+    }
+
+    pub(crate) fn try_from_bytes_located_with_config(
+        data: &[u8],
+        config: &ParserConfig,
+    ) -> Result<SpliceInfoSection, LocatedParseError> {
+        let data = without_trailing_padding(data, config);
+        let mut bits = Bits::new(data);
+        bits.set_config(config);
+        Self::try_from_bits(&mut bits, data, vec![]).map_err(|error| bits.locate(error))
+    }
+
+    /// Parses only the fields of the `data` that are needed to decide what to do with a message
+    /// (see `SpliceInfoHeader`), which is much faster than parsing the whole message when the
+    /// splice command and descriptors are not needed. The splice descriptors are not parsed and
+    /// the `crc_32` is not checked.
+    /// ```
+    /// use scte35::{
+    ///     splice_command::SpliceCommandType, splice_info_section::SpliceInfoSection, time::Pts90k,
+    /// };
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let data = scte35::parser::Parser::new().decode_hex(hex_string).unwrap().to_vec();
+    /// let header = SpliceInfoSection::peek_header(&data).unwrap();
+    /// assert_eq!(Some(SpliceCommandType::TimeSignal), header.splice_command_type);
+    /// assert_eq!(Some(Pts90k(1924989008)), header.pts_time);
+    /// assert_eq!(0xFFF, header.tier);
+    /// assert!(!header.encrypted_packet);
+    /// ```
+    pub fn peek_header(data: &[u8]) -> Result<SpliceInfoHeader, ParseError> {
+        let config = ParserConfig::default();
+        let mut bits = Bits::new(data);
+        bits.set_config(&config);
+        let mut header = Self::try_header_from(&mut bits)?;
+        if header.encrypted_packet {
+            return Ok(header);
+        }
+        bits.validate(
+            8,
+            "SpliceInfoSection; not enough bits left to read splice_command_type",
+        )?;
+        header.splice_command_type = bits
+            .peek(8)
+            .map(|value| SpliceCommandType::from_value(value as u8));
+        if matches!(
+            header.splice_command_type,
+            Some(SpliceCommandType::TimeSignal | SpliceCommandType::SpliceInsert)
+        ) {
+            let splice_command = bits.scope("splice_command", |bits| {
+                SpliceCommand::try_from(bits, header.splice_command_length as u32)
+            })?;
+            header.pts_time = splice_time(&splice_command).and_then(|time| time.pts_time);
+        }
+        Ok(header)
+    }
+
+    // Parses with the descriptors being appended to `splice_descriptors`, which is cleared first,
+    // so that a `Parser` can reuse the allocation of a previously parsed message.
+    pub(crate) fn try_from_bytes_reusing(
+        data: &[u8],
+        config: &ParserConfig,
+        mut splice_descriptors: Vec<SpliceDescriptor>,
+    ) -> Result<SpliceInfoSection, ParseError> {
+        splice_descriptors.clear();
+        let data = without_trailing_padding(data, config);
+        let mut bits = Bits::new(data);
+        bits.set_config(config);
+        Self::try_from_bits(&mut bits, data, splice_descriptors)
+    }
+
+    pub(crate) fn try_from_bits(
+        bits: &mut Bits,
+        data: &[u8],
+        splice_descriptors: Vec<SpliceDescriptor>,
+    ) -> Result<SpliceInfoSection, ParseError> {
+        #[cfg(feature = "tracing")]
+        let _span = trace::section_span(data);
+        let result = Self::try_section_from_bits(bits, data, splice_descriptors);
+        #[cfg(feature = "tracing")]
+        trace::section_parsed(&result);
+        result
+    }
+
+    fn try_section_from_bits(
+        bits: &mut Bits,
+        data: &[u8],
+        mut splice_descriptors: Vec<SpliceDescriptor>,
+    ) -> Result<SpliceInfoSection, ParseError> {
+        let SpliceInfoHeader {
+            table_id,
+            sap_type,
+            protocol_version,
+            encrypted_packet: is_encrypted,
+            encryption_algorithm,
+            pts_adjustment,
+            cw_index,
+            tier,
+            splice_command_length,
+            ..
+        } = Self::try_header_from(bits)?;
+        #[cfg(feature = "tracing")]
+        trace::record_tier(tier);
+        let splice_command_length = splice_command_length as u32;
+        let (splice_command, encrypted_packet) = if is_encrypted {
+            let Some(decryptor) = bits.config().decryptor.clone() else {
+                return Err(ParseError::EncryptedMessageNotSupported);
+            };
+            let encrypted_bytes = (bits.bits_remaining() / 8).saturating_sub(4);
+            let encrypted_data = bits.bytes("encrypted_data", encrypted_bytes)?;
+            let data = decryptor.decrypt(&encryption_algorithm, cw_index, &encrypted_data)?;
+            let (splice_command, alignment_stuffing, e_crc_32) = Self::try_decrypted_from_bytes(
+                bits,
+                &data,
+                splice_command_length,
+                &mut splice_descriptors,
+            )?;
+            let encrypted_packet = EncryptedPacket {
+                encryption_algorithm,
+                cw_index,
+                alignment_stuffing,
+                e_crc_32,
+            };
+            (splice_command, Some(encrypted_packet))
+        } else {
+            let (splice_command, _) = Self::try_encryptable_portion_from(
+                bits,
+                splice_command_length,
+                &mut splice_descriptors,
+            )?;
+            (splice_command, None)
+        };
+        let crc_32 = bits.u32("crc_32", 32)?;
+        let calculated_crc_32 = crc_32_mpeg_2(&data[..data.len() - 4]);
+        if crc_32 != calculated_crc_32 {
+            let handling = bits.config().crc_mismatch;
+            bits.handle_error(
+                handling,
+                ParseError::CRCMismatch {
+                    crc_32,
+                    calculated_crc_32,
+                },
+            )?;
+        }
+        let non_fatal_errors = bits.take_non_fatal_errors();
+        Ok(Self {
+            table_id,
+            sap_type,
+            protocol_version,
+            encrypted_packet,
+            pts_adjustment,
+            tier,
+            splice_command,
+            splice_descriptors,
+            crc_32,
+            non_fatal_errors,
+        })
+    }
+
+    // Parses the fields that are in the clear, from the `table_id` up to the
+    // `splice_command_length`.
+    fn try_header_from(bits: &mut Bits) -> Result<SpliceInfoHeader, ParseError> {
         bits.validate(
             24,
             "SpliceInfoSection; need at least 24 bits to get to end of section_length field",
         )?;
-        let table_id = bits.byte();
-        if bits.bool() {
+        let table_id = bits.byte("table_id")?;
+        if table_id != 0xFC && !bits.config().accepted_table_ids.contains(&table_id) {
+            let handling = bits.config().table_id_mismatch;
+            bits.handle_error(handling, ParseError::UnexpectedTableID(table_id))?;
+        }
+        if bits.bool("section_syntax_indicator")? {
             return Err(ParseError::InvalidSectionSyntaxIndicator);
         }
-        if bits.bool() {
+        if bits.bool("private_indicator")? {
             return Err(ParseError::InvalidPrivateIndicator);
         }
-        let sap_type = SAPType::try_from(bits.u8(2)).unwrap_or(SAPType::Unspecified);
-        let section_length_in_bytes = bits.u32(12);
+        let sap_type = SAPType::try_from(bits.u8("sap_type", 2)?).unwrap_or(SAPType::Unspecified);
+        let section_length_in_bytes = bits.u32("section_length", 12)?;
         bits.validate(
             section_length_in_bytes * 8,
             "SpliceInfoSection; not enough bytes left to read section_length",
         )?;
-        let protocol_version = bits.byte();
-        let is_encrypted = bits.bool();
-        if is_encrypted {
-            return Err(ParseError::EncryptedMessageNotSupported);
+        let protocol_version = bits.byte("protocol_version")?;
+        if protocol_version != 0 {
+            let handling = bits.config().protocol_version_mismatch;
+            bits.handle_error(
+                handling,
+                ParseError::UnsupportedProtocolVersion(protocol_version),
+            )?;
         }
-        let _ /* encryptionAlgorithm */ = EncryptionAlgorithm::try_from(bits.u8(6)).ok();
-        let pts_adjustment = bits.u64(33);
-        let _ /* cwIndex */ = bits.byte();
-        let tier = bits.u16(12);
-        let splice_command_length = bits.u32(12);
-        let splice_command = SpliceCommand::try_from(&mut bits, splice_command_length)?;
-        let descriptor_loop_length = bits.u32(16);
-        let splice_descriptors = try_splice_descriptors_from(&mut bits, descriptor_loop_length)?;
-        let encrypted_packet: Option<EncryptedPacket> = if is_encrypted {
-            return Err(ParseError::EncryptedMessageNotSupported);
-        } else {
-            while bits.bits_remaining() >= 40 {
-                _ = bits.byte();
-            }
-            None
-        };
-        let crc_32 = bits.u32(32);
-        let non_fatal_errors = bits.get_non_fatal_errors().clone();
-        Ok(Self {
+        let encrypted_packet = bits.bool("encrypted_packet")?;
+        let encryption_algorithm =
+            EncryptionAlgorithm::from_value(bits.u8("encryption_algorithm", 6)?);
+        let pts_adjustment = Pts90k(bits.u64("pts_adjustment", 33)?);
+        let cw_index = bits.byte("cw_index")?;
+        let tier = bits.u16("tier", 12)?;
+        let splice_command_length = bits.u16("splice_command_length", 12)?;
+        Ok(SpliceInfoHeader {
             table_id,
             sap_type,
             protocol_version,
             encrypted_packet,
+            encryption_algorithm,
             pts_adjustment,
+            cw_index,
             tier,
-            splice_command,
+            splice_command_length,
+            splice_command_type: None,
+            pts_time: None,
+        })
+    }
+
+    // Parses the portion of the section that is encrypted when there is an `encrypted_packet`,
+    // from the `splice_command_type` up to the `alignment_stuffing`, which is followed by a 32-bit
+    // CRC (the `e_crc_32` or `crc_32`). Returns the number of stuffing bytes.
+    fn try_encryptable_portion_from(
+        bits: &mut Bits,
+        splice_command_length: u32,
+        splice_descriptors: &mut Vec<SpliceDescriptor>,
+    ) -> Result<(SpliceCommand, u8), ParseError> {
+        let splice_command = bits.scope("splice_command", |bits| {
+            SpliceCommand::try_from(bits, splice_command_length)
+        })?;
+        #[cfg(feature = "tracing")]
+        trace::record_splice_command(&splice_command);
+        let descriptor_loop_length = bits.u32("descriptor_loop_length", 16)?;
+        try_splice_descriptors_into(bits, descriptor_loop_length, splice_descriptors)?;
+        let mut alignment_stuffing = 0u8;
+        while bits.bits_remaining() >= 40 {
+            _ = bits.byte("alignment_stuffing")?;
+            alignment_stuffing = alignment_stuffing.saturating_add(1);
+        }
+        Ok((splice_command, alignment_stuffing))
+    }
+
+    // Parses the decrypted `data` of an encrypted section, recording any non-fatal errors in
+    // `bits`. Returns the number of stuffing bytes and the `e_crc_32`.
+    fn try_decrypted_from_bytes(
+        bits: &mut Bits,
+        data: &[u8],
+        splice_command_length: u32,
+        splice_descriptors: &mut Vec<SpliceDescriptor>,
+    ) -> Result<(SpliceCommand, u8, u32), ParseError> {
+        if data.len() >= 4 {
+            let (decrypted, e_crc_32) = data.split_at(data.len() - 4);
+            let e_crc_32 = u32::from_be_bytes(e_crc_32.try_into().unwrap_or_default());
+            let calculated_e_crc_32 = crc_32_mpeg_2(decrypted);
+            if e_crc_32 != calculated_e_crc_32 {
+                let handling = bits.config().crc_mismatch;
+                bits.handle_error(
+                    handling,
+                    ParseError::ECRCMismatch {
+                        e_crc_32,
+                        calculated_e_crc_32,
+                    },
+                )?;
+            }
+        }
+        let mut decrypted_bits = Bits::new(data);
+        decrypted_bits.set_config(bits.config());
+        let result = Self::try_encryptable_portion_from(
+            &mut decrypted_bits,
+            splice_command_length,
             splice_descriptors,
-            crc_32,
-            non_fatal_errors,
+        )
+        .and_then(|(splice_command, alignment_stuffing)| {
+            let e_crc_32 = decrypted_bits.u32("e_crc_32", 32)?;
+            Ok((splice_command, alignment_stuffing, e_crc_32))
+        });
+        let non_fatal_errors = decrypted_bits.take_non_fatal_errors();
+        for error in non_fatal_errors {
+            bits.push_non_fatal_error(error);
+        }
+        result
+    }
+
+    /// Encodes the `SpliceInfoSection` into bytes.
+    ///
+    /// All length fields are derived from the encoded content, no alignment stuffing is added, and
+    /// the `crc_32` is calculated from the encoded bytes (the value of the `crc_32` property is not
+    /// used).
+    pub fn try_to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        if self.encrypted_packet.is_some() {
+            return Err(EncodeError::EncryptedMessageNotSupported);
+        }
+        let mut command_bits = BitWriter::new();
+        self.splice_command.encode(&mut command_bits)?;
+        let command_bytes = command_bits.into_bytes();
+        let mut descriptor_bits = BitWriter::new();
+        for splice_descriptor in &self.splice_descriptors {
+            splice_descriptor.encode(&mut descriptor_bits)?;
+        }
+        let descriptor_bytes = descriptor_bits.into_bytes();
+
+        let mut bits = BitWriter::new();
+        bits.validate(
+            33,
+            self.pts_adjustment.0,
+            "SpliceInfoSection pts_adjustment",
+        )?;
+        bits.validate(12, self.tier as u64, "SpliceInfoSection tier")?;
+        bits.validate_length(
+            command_bytes.len(),
+            0xFFE,
+            "SpliceInfoSection splice_command_length",
+        )?;
+        bits.validate_length(
+            descriptor_bytes.len(),
+            0xFFFF,
+            "SpliceInfoSection descriptor_loop_length",
+        )?;
+        bits.byte(self.protocol_version);
+        bits.bool(false);
+        bits.u8(6, 0);
+        bits.u64(33, self.pts_adjustment.0);
+        bits.byte(0xFF);
+        bits.u16(12, self.tier);
+        bits.u16(12, command_bytes.len() as u16);
+        bits.byte(self.splice_command.command_type().value());
+        bits.bytes(&command_bytes);
+        bits.u16(16, descriptor_bytes.len() as u16);
+        bits.bytes(&descriptor_bytes);
+        let section_bytes = bits.into_bytes();
+
+        let section_length = section_bytes.len() + 4;
+        let mut bits = BitWriter::new();
+        bits.validate_length(section_length, 4093, "SpliceInfoSection section_length")?;
+        bits.byte(self.table_id);
+        bits.bool(false);
+        bits.bool(false);
+        bits.u8(2, self.sap_type.value());
+        bits.u16(12, section_length as u16);
+        bits.bytes(&section_bytes);
+        let mut bytes = bits.into_bytes();
+        let crc_32 = crc_32_mpeg_2(&bytes);
+        bytes.extend_from_slice(&crc_32.to_be_bytes());
+        Ok(bytes)
+    }
+
+    /// Encodes the `SpliceInfoSection` (see `try_to_bytes`), where all length fields are derived
+    /// from the content and no alignment stuffing is added, and updates the `crc_32` to that of the
+    /// encoded bytes, returning the bytes. The `non_fatal_errors` are cleared, as they describe the
+    /// bytes that were parsed (e.g. a length that did not match the content) rather than the
+    /// normalized bytes.
+    ///
+    /// This should be used after modifying a parsed section (e.g. changing the `pts_adjustment`),
+    /// so that the section and its bytes are consistent.
+    /// ```
+    /// use scte35::{splice_info_section::SpliceInfoSection, time::Pts90k};
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let mut splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// splice_info_section.pts_adjustment = Pts90k(900);
+    /// let bytes = splice_info_section.canonicalize().unwrap();
+    /// assert_ne!(0x9AC9D17E, splice_info_section.crc_32);
+    /// assert_eq!(splice_info_section, SpliceInfoSection::try_from_bytes(&bytes).unwrap());
+    /// ```
+    pub fn canonicalize(&mut self) -> Result<Vec<u8>, EncodeError> {
+        let bytes = self.try_to_bytes()?;
+        let crc_bytes = &bytes[bytes.len() - 4..];
+        self.crc_32 = u32::from_be_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+        self.non_fatal_errors.clear();
+        Ok(bytes)
+    }
+
+    /// The number of bytes of the encoded `SpliceInfoSection` (see `try_to_bytes`), from the
+    /// `table_id` through the `crc_32`.
+    pub fn encoded_length(&self) -> Result<usize, EncodeError> {
+        Ok(self.try_to_bytes()?.len())
+    }
+
+    /// Whether the encoded `SpliceInfoSection` fits in the 184-byte payload of a single transport
+    /// stream packet, along with the `pointer_field` that precedes it (so is at most 183 bytes).
+    /// When using `tier`, the message should be kept in a single packet, so authoring tools can
+    /// use this to warn before emitting an oversized message.
+    /// ```
+    /// use scte35::splice_info_section::SpliceInfoSection;
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// assert_eq!(55, splice_info_section.encoded_length().unwrap());
+    /// assert!(splice_info_section.fits_in_single_ts_packet().unwrap());
+    /// ```
+    pub fn fits_in_single_ts_packet(&self) -> Result<bool, EncodeError> {
+        // The 4-byte packet header and the 1-byte pointer_field precede the section.
+        let pointer_field_length = 1;
+        Ok(self.encoded_length()? + pointer_field_length <= PACKET_SIZE - 4)
+    }
+
+    /// Encodes the `SpliceInfoSection` into a `0x` prefixed, upper case, hex encoded string.
+    pub fn try_to_hex_string(&self) -> Result<String, EncodeError> {
+        Ok(format!(
+            "0x{}",
+            hex::encode_hex(&self.try_to_bytes()?).to_uppercase()
+        ))
+    }
+
+    /// The effective time of the splice point, which is the `pts_time` of the `TimeSignal`, or of a
+    /// `SpliceInsert` in the Program Splice Mode, plus the `pts_adjustment` (wrapped to 33 bits).
+    ///
+    /// Returns `None` for immediate splices, for the Component Splice Mode (where each component
+    /// has its own splice time) and for commands that do not carry a PTS.
+    /// ```
+    /// use scte35::{splice_info_section::SpliceInfoSection, time::Pts90k};
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let mut splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// assert_eq!(Some(Pts90k(1924989008)), splice_info_section.adjusted_splice_time());
+    ///
+    /// splice_info_section.pts_adjustment = Pts90k(0x1_0000_0000);
+    /// assert_eq!(Some(Pts90k(0x1_72BD_0050)), splice_info_section.adjusted_splice_time());
+    /// ```
+    pub fn adjusted_splice_time(&self) -> Option<Pts90k> {
+        splice_time(&self.splice_command)
+            .and_then(|splice_time| splice_time.pts_time)
+            .map(|pts_time| pts_time + self.pts_adjustment)
+    }
+
+    /// The time (including the `pts_adjustment`) at which the event signaled by the
+    /// `splice_command` takes effect, which is the `splice_time` of a `TimeSignal` or of a
+    /// `SpliceInsert` in Program Splice Mode, or the earliest `splice_time` of the components of a
+    /// `SpliceInsert` in Component Splice Mode. This is `None` for an immediate splice, and for any
+    /// other command.
+    /// ```
+    /// use scte35::{
+    ///     splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    ///     splice_info_section::SpliceInfoSection,
+    ///     time::Pts90k,
+    /// };
+    ///
+    /// let splice_info_section = SpliceInfoSection::builder()
+    ///     .pts_adjustment(Pts90k(0x100))
+    ///     .splice_command(SpliceCommand::SpliceInsert(
+    ///         SpliceInsert::builder(1)
+    ///             .component(1, Some(Pts90k(0x30)))
+    ///             .component(2, Some(Pts90k(0x20)))
+    ///             .build()
+    ///             .unwrap(),
+    ///     ))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(Some(Pts90k(0x120)), splice_info_section.splice_pts());
+    /// ```
+    pub fn splice_pts(&self) -> Option<Pts90k> {
+        let component_splice_times = match &self.splice_command {
+            SpliceCommand::SpliceInsert(splice_insert) => splice_insert
+                .scheduled_event
+                .as_ref()
+                .and_then(|event| match &event.splice_mode {
+                    SpliceMode::ComponentSpliceMode(components) => Some(components),
+                    SpliceMode::ProgramSpliceMode(_) => None,
+                }),
+            _ => None,
+        };
+        match component_splice_times {
+            Some(components) => components
+                .iter()
+                .filter_map(|component| component.splice_time.as_ref()?.pts_time)
+                .map(|pts_time| pts_time + self.pts_adjustment)
+                .min_by(|a, b| a.wrapping_cmp(*b)),
+            None => self.adjusted_splice_time(),
+        }
+    }
+
+    /// The planned duration of the event signaled by the message, which is the `break_duration`
+    /// of a `SpliceInsert`, or the first `segmentation_duration` of the `SegmentationDescriptor`s
+    /// of a `TimeSignal`. This is `None` when no duration is signaled.
+    /// ```
+    /// use scte35::splice_info_section::SpliceInfoSection;
+    /// use std::time::Duration;
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// assert_eq!(
+    ///     Some(Duration::from_secs_f64(307.0)),
+    ///     splice_info_section.planned_duration()
+    /// );
+    /// ```
+    pub fn planned_duration(&self) -> Option<Duration> {
+        let (_, duration) = self.event_id_and_duration();
+        duration.map(duration_from_ticks)
+    }
+
+    /// Folds the `pts_adjustment` into every `pts_time` of the `TimeSignal` or `SpliceInsert`
+    /// (including each component of the Component Splice Mode), wrapping at 33 bits, then sets the
+    /// `pts_adjustment` to `0` and canonicalizes the section (see `canonicalize`).
+    ///
+    /// The `SpliceInfoSection` is left unchanged if the result cannot be encoded.
+    /// ```
+    /// use scte35::{splice_info_section::SpliceInfoSection, time::Pts90k};
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let mut splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// splice_info_section.pts_adjustment = Pts90k(0x1_0000_0000);
+    /// let adjusted_splice_time = splice_info_section.adjusted_splice_time();
+    ///
+    /// splice_info_section.try_apply_pts_adjustment().unwrap();
+    /// assert_eq!(Pts90k(0), splice_info_section.pts_adjustment);
+    /// assert_eq!(adjusted_splice_time, splice_info_section.adjusted_splice_time());
+    /// ```
+    pub fn try_apply_pts_adjustment(&mut self) -> Result<(), EncodeError> {
+        let pts_adjustment = self.pts_adjustment;
+        let mut adjusted = self.clone();
+        let splice_times = match &mut adjusted.splice_command {
+            SpliceCommand::TimeSignal(time_signal) => vec![&mut time_signal.splice_time],
+            SpliceCommand::SpliceInsert(splice_insert) => match splice_insert
+                .scheduled_event
+                .as_mut()
+                .map(|event| &mut event.splice_mode)
+            {
+                Some(SpliceMode::ProgramSpliceMode(program)) => {
+                    program.splice_time.iter_mut().collect()
+                }
+                Some(SpliceMode::ComponentSpliceMode(components)) => components
+                    .iter_mut()
+                    .filter_map(|component| component.splice_time.as_mut())
+                    .collect(),
+                None => vec![],
+            },
+            _ => vec![],
+        };
+        for splice_time in splice_times {
+            splice_time.pts_time = splice_time
+                .pts_time
+                .map(|pts_time| pts_time + pts_adjustment);
+        }
+        adjusted.pts_adjustment = Pts90k(0);
+        adjusted.canonicalize()?;
+        *self = adjusted;
+        Ok(())
+    }
+
+    /// The `SegmentationDescriptor`s of the `splice_descriptors`.
+    pub fn segmentation_descriptors(
+        &self,
+    ) -> impl Iterator<Item = &SegmentationDescriptor> + Clone {
+        self.splice_descriptors
+            .iter()
+            .filter_map(SpliceDescriptor::as_segmentation)
+    }
+
+    /// The `AvailDescriptor`s of the `splice_descriptors`.
+    pub fn avail_descriptors(&self) -> impl Iterator<Item = &AvailDescriptor> + Clone {
+        self.splice_descriptors
+            .iter()
+            .filter_map(SpliceDescriptor::as_avail)
+    }
+
+    /// The `DTMFDescriptor`s of the `splice_descriptors`.
+    pub fn dtmf_descriptors(&self) -> impl Iterator<Item = &DTMFDescriptor> + Clone {
+        self.splice_descriptors
+            .iter()
+            .filter_map(SpliceDescriptor::as_dtmf)
+    }
+
+    /// The first `SegmentationDescriptor` with the `segmentation_type_id` (cancelled descriptors,
+    /// which have no `segmentation_type_id`, are never found).
+    /// ```
+    /// use scte35::{
+    ///     splice_descriptor::segmentation_descriptor::SegmentationTypeID,
+    ///     splice_info_section::SpliceInfoSection,
+    /// };
+    ///
+    /// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+    /// let descriptor = splice_info_section
+    ///     .find_segmentation(&SegmentationTypeID::ProviderPlacementOpportunityStart)
+    ///     .unwrap();
+    /// assert_eq!(0x4800008E, descriptor.event_id);
+    /// assert!(splice_info_section
+    ///     .find_segmentation(&SegmentationTypeID::ProviderPlacementOpportunityEnd)
+    ///     .is_none());
+    /// ```
+    pub fn find_segmentation(
+        &self,
+        segmentation_type_id: &SegmentationTypeID,
+    ) -> Option<&SegmentationDescriptor> {
+        self.segmentation_descriptors().find(|descriptor| {
+            descriptor
+                .scheduled_event
+                .as_ref()
+                .is_some_and(|event| &event.segmentation_type_id == segmentation_type_id)
         })
     }
+
+    /// A key that identifies the content of the message, so that the repeats of a message (which
+    /// are usually sent multiple times before the splice point) can be coalesced. The key does
+    /// not depend on the `crc_32`, the `e_crc_32`, the `alignment_stuffing`, or the
+    /// `non_fatal_errors`, and is stable across runs and platforms (for a given version of this
+    /// library), so it may be shared between processes. Two messages that are
+    /// `eq_ignoring_crc` have the same key.
+    /// ```
+    /// use scte35::splice_info_section::SpliceInfoSection;
+    ///
+    /// let hex = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    /// let message = SpliceInfoSection::try_from_hex_string(hex).unwrap();
+    /// let mut repeat = message.clone();
+    /// repeat.crc_32 = 0;
+    /// assert!(message.eq_ignoring_crc(&repeat));
+    /// assert_eq!(message.dedup_key(), repeat.dedup_key());
+    ///
+    /// repeat.tier = 0x123;
+    /// assert!(!message.eq_ignoring_crc(&repeat));
+    /// assert_ne!(message.dedup_key(), repeat.dedup_key());
+    /// ```
+    pub fn dedup_key(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        self.table_id.hash(&mut hasher);
+        self.sap_type.hash(&mut hasher);
+        self.protocol_version.hash(&mut hasher);
+        self.encrypted_packet
+            .as_ref()
+            .map(|packet| (&packet.encryption_algorithm, packet.cw_index))
+            .hash(&mut hasher);
+        self.pts_adjustment.hash(&mut hasher);
+        self.tier.hash(&mut hasher);
+        self.splice_command.hash(&mut hasher);
+        self.splice_descriptors.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether the messages are equal, other than in the `crc_32`, the `e_crc_32`, the
+    /// `alignment_stuffing` and the `non_fatal_errors` (see `dedup_key`).
+    pub fn eq_ignoring_crc(&self, other: &SpliceInfoSection) -> bool {
+        let encrypted_packet_eq = match (&self.encrypted_packet, &other.encrypted_packet) {
+            (Some(lhs), Some(rhs)) => {
+                lhs.encryption_algorithm == rhs.encryption_algorithm && lhs.cw_index == rhs.cw_index
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        self.table_id == other.table_id
+            && self.sap_type == other.sap_type
+            && self.protocol_version == other.protocol_version
+            && encrypted_packet_eq
+            && self.pts_adjustment == other.pts_adjustment
+            && self.tier == other.tier
+            && self.splice_command == other.splice_command
+            && self.splice_descriptors == other.splice_descriptors
+    }
+
+    /// The `event_id` and duration (in terms of the 90 kHz clock) of the event signaled by a
+    /// `SpliceInsert` (from its `break_duration`), or by a `TimeSignal` (from the first
+    /// `SegmentationDescriptor`, and the first `segmentation_duration` that is defined).
+    pub(crate) fn event_id_and_duration(&self) -> (Option<u32>, Option<u64>) {
+        let segmentation_descriptors = self.segmentation_descriptors();
+        match &self.splice_command {
+            SpliceCommand::SpliceInsert(splice_insert) => (
+                Some(splice_insert.event_id),
+                splice_insert
+                    .scheduled_event
+                    .as_ref()
+                    .and_then(|event| event.break_duration.as_ref())
+                    .map(|break_duration| break_duration.duration.0),
+            ),
+            SpliceCommand::TimeSignal(_) => (
+                segmentation_descriptors
+                    .clone()
+                    .next()
+                    .map(|descriptor| descriptor.event_id),
+                segmentation_descriptors.clone().find_map(|descriptor| {
+                    descriptor
+                        .scheduled_event
+                        .as_ref()
+                        .and_then(|event| event.segmentation_duration)
+                }),
+            ),
+            _ => (None, None),
+        }
+    }
+}
+
+/// Options that control how a `SpliceInfoSection` is parsed (see
+/// `SpliceInfoSection::try_from_bytes_with_config`). The default configuration matches the
+/// behaviour of `SpliceInfoSection::try_from_bytes`, where the `crc_32` and `table_id` are not
+/// checked, a non-zero `protocol_version` and length mismatches are non-fatal, and unknown enum
+/// values are fatal.
+///
+/// ```
+/// use scte35::{
+///     error::ParseError,
+///     splice_descriptor::SpliceDescriptor,
+///     splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+/// };
+///
+/// let unknown = SpliceDescriptor::Unknown {
+///     tag: 0xAA,
+///     identifier: 0x43554549,
+///     private_bytes: vec![0xDE, 0xAD, 0xBE, 0xEF],
+/// };
+/// let bytes = SpliceInfoSection::builder()
+///     .splice_descriptor(unknown.clone())
+///     .build()
+///     .unwrap()
+///     .try_to_bytes()
+///     .unwrap();
+/// assert_eq!(
+///     Err(ParseError::UnrecognisedSpliceDescriptorTag(0xAA)),
+///     SpliceInfoSection::try_from_bytes(&bytes)
+/// );
+///
+/// let config = ParserConfig {
+///     unknown_enum_values: ErrorHandling::NonFatal,
+///     ..Default::default()
+/// };
+/// let section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config).unwrap();
+/// assert_eq!(vec![unknown], section.splice_descriptors);
+/// assert_eq!(
+///     vec![ParseError::UnrecognisedSpliceDescriptorTag(0xAA)],
+///     section.non_fatal_errors
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParserConfig {
+    /// Parsers for splice descriptors that are not defined by this library, which take precedence
+    /// over the parsing of the descriptors defined in the specification (see
+    /// `SpliceDescriptorRegistry`).
+    pub descriptor_registry: Option<SpliceDescriptorRegistry>,
+    /// The handling of a `crc_32` that does not match the CRC calculated from the section
+    /// (`CRCMismatch`). Defaults to `Ignore`.
+    pub crc_mismatch: ErrorHandling,
+    /// The handling of a `table_id` that is not 0xFC or one of the `accepted_table_ids`
+    /// (`UnexpectedTableID`). Defaults to `Ignore`.
+    pub table_id_mismatch: ErrorHandling,
+    /// The `table_id` values, other than 0xFC, that are accepted without being handled as a
+    /// `table_id_mismatch` (e.g. a private `table_id` that is deliberately used within a plant,
+    /// when other values should still be rejected). Defaults to empty.
+    pub accepted_table_ids: Vec<u8>,
+    /// The handling of a `protocol_version` that is not 0 (`UnsupportedProtocolVersion`), which a
+    /// future version of the specification may use. Defaults to `NonFatal`, so that the section is
+    /// still parsed, with the version recorded in the `non_fatal_errors`.
+    pub protocol_version_mismatch: ErrorHandling,
+    /// The handling of a `splice_command_length` or `descriptor_length` that does not match the
+    /// parsed length (`UnexpectedSpliceCommandLength` and `UnexpectedSpliceDescriptorLength`).
+    /// Defaults to `NonFatal`.
+    pub length_mismatch: ErrorHandling,
+    /// Whether a `splice_command_length` of 0xFFF is accepted as the legacy value for a length
+    /// that is unspecified (which earlier versions of the specification allowed for backwards
+    /// compatibility), rather than being an `UnexpectedSpliceCommandLength`. Any other length that
+    /// does not match the parsed length is still handled according to `length_mismatch`. Defaults
+    /// to `false`.
+    ///
+    /// The length of a `PrivateCommand` can only be known from the `splice_command_length`, and
+    /// so a `PrivateCommand` with the legacy length always fails to parse.
+    pub accept_legacy_splice_command_length: bool,
+    /// The handling of an unknown `splice_descriptor_tag` (`UnrecognisedSpliceDescriptorTag`).
+    /// When this is not `Fatal` the descriptor is captured as a `SpliceDescriptor::Unknown`, so
+    /// that the rest of the section can still be parsed. Defaults to `Fatal`.
+    ///
+    /// Unknown values that have no raw representation in the model (such as an unknown
+    /// `splice_command_type` or `segmentation_upid_type`) are always fatal, and reserved
+    /// `segmentation_type_id` and `sap_type` values are always accepted.
+    pub unknown_enum_values: ErrorHandling,
+    /// The handling of a `segmentation_upid_length` that does not match the length defined for
+    /// the `segmentation_upid_type` (`UnexpectedSegmentationUPIDLength`). When this is not `Fatal`
+    /// the UPID is captured as a `SegmentationUPID::Invalid` with its raw bytes, so that the rest
+    /// of the descriptor can still be parsed. Defaults to `Fatal`.
+    pub upid_length_mismatch: ErrorHandling,
+    /// The handling of `dtmf_chars` that contain a character other than `0` to `9`, `*` and `#`
+    /// (`InvalidDTMFChars`). The `dtmf_chars` are kept as they were received when this is not
+    /// `Fatal`. Defaults to `NonFatal`.
+    pub invalid_dtmf_chars: ErrorHandling,
+    /// Whether any bytes after the section (as declared by the `section_length`) are ignored when
+    /// they are all 0xFF stuffing bytes, as is common in buffers from capture tools. When this is
+    /// `false`, such bytes are parsed as part of the section, and so the final 4 bytes are read
+    /// as the `crc_32`. Defaults to `false`.
+    pub ignore_trailing_padding: bool,
+    /// The handling of any other error when parsing a splice descriptor (such as an
+    /// `UnrecognisedSegmentationUPIDType`, or an `UnexpectedSegmentationUPIDLength` when
+    /// `upid_length_mismatch` is `Fatal`). When this is not `Fatal` the rest of the descriptor is
+    /// skipped using its `descriptor_length`, so that the remaining descriptors can still be
+    /// parsed. Exceeding a `ParserLimits` limit is always fatal. Defaults to `Fatal`.
+    pub invalid_splice_descriptors: ErrorHandling,
+    /// Limits that protect against hostile input (see `ParserLimits`).
+    pub limits: ParserLimits,
+    /// Decrypts the encrypted portion of sections that have `encrypted_packet` set (see the
+    /// `encryption` module). When this is `None` such sections fail to parse with
+    /// `EncryptedMessageNotSupported`. Defaults to `None`.
+    pub decryptor: Option<Arc<dyn Decryptor>>,
+}
+
+impl ParserConfig {
+    /// A configuration where every inconsistency with the specification is fatal.
+    pub fn strict() -> Self {
+        Self {
+            descriptor_registry: None,
+            crc_mismatch: ErrorHandling::Fatal,
+            table_id_mismatch: ErrorHandling::Fatal,
+            accepted_table_ids: vec![],
+            protocol_version_mismatch: ErrorHandling::Fatal,
+            length_mismatch: ErrorHandling::Fatal,
+            accept_legacy_splice_command_length: false,
+            unknown_enum_values: ErrorHandling::Fatal,
+            upid_length_mismatch: ErrorHandling::Fatal,
+            invalid_dtmf_chars: ErrorHandling::Fatal,
+            ignore_trailing_padding: false,
+            invalid_splice_descriptors: ErrorHandling::Fatal,
+            limits: ParserLimits::default(),
+            decryptor: None,
+        }
+    }
+
+    /// A configuration where every inconsistency with the specification that can be recovered
+    /// from is recorded in the `non_fatal_errors`, so that as much of the section as possible is
+    /// parsed (e.g. a splice descriptor with an invalid UPID is skipped, rather than failing the
+    /// parse of the whole section).
+    pub fn lenient() -> Self {
+        Self {
+            descriptor_registry: None,
+            crc_mismatch: ErrorHandling::NonFatal,
+            table_id_mismatch: ErrorHandling::NonFatal,
+            accepted_table_ids: vec![],
+            protocol_version_mismatch: ErrorHandling::NonFatal,
+            length_mismatch: ErrorHandling::NonFatal,
+            accept_legacy_splice_command_length: false,
+            unknown_enum_values: ErrorHandling::NonFatal,
+            upid_length_mismatch: ErrorHandling::NonFatal,
+            invalid_dtmf_chars: ErrorHandling::NonFatal,
+            ignore_trailing_padding: false,
+            invalid_splice_descriptors: ErrorHandling::NonFatal,
+            limits: ParserLimits::default(),
+            decryptor: None,
+        }
+    }
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            descriptor_registry: None,
+            crc_mismatch: ErrorHandling::Ignore,
+            table_id_mismatch: ErrorHandling::Ignore,
+            accepted_table_ids: vec![],
+            protocol_version_mismatch: ErrorHandling::NonFatal,
+            length_mismatch: ErrorHandling::NonFatal,
+            accept_legacy_splice_command_length: false,
+            unknown_enum_values: ErrorHandling::Fatal,
+            upid_length_mismatch: ErrorHandling::Fatal,
+            invalid_dtmf_chars: ErrorHandling::NonFatal,
+            ignore_trailing_padding: false,
+            invalid_splice_descriptors: ErrorHandling::Fatal,
+            limits: ParserLimits::default(),
+            decryptor: None,
+        }
+    }
+}
+
+/// Limits on the resources used when parsing, so that untrusted input cannot cause unbounded
+/// recursion or allocation. Exceeding a limit fails the parse with `ParseError::LimitExceeded`.
+///
+/// The defaults are high enough that no section that is consistent with the specification (which
+/// has a maximum `section_length` of 4093 bytes) exceeds them, other than one that nests `MID`
+/// UPIDs more than 8 levels deep.
+/// ```
+/// use scte35::{
+///     error::ParseError,
+///     splice_descriptor::SpliceDescriptor,
+///     splice_info_section::{ParserConfig, ParserLimits, SpliceInfoSection},
+/// };
+/// # use scte35::splice_descriptor::time_descriptor::TimeDescriptor;
+///
+/// # let time_descriptor = SpliceDescriptor::TimeDescriptor(TimeDescriptor {
+/// #     identifier: 0x43554549,
+/// #     tai_seconds: 0,
+/// #     tai_ns: 0,
+/// #     utc_offset: 37,
+/// # });
+/// let bytes = SpliceInfoSection::builder()
+///     .splice_descriptor(time_descriptor.clone())
+///     .splice_descriptor(time_descriptor)
+///     .build()
+///     .unwrap()
+///     .try_to_bytes()
+///     .unwrap();
+/// let config = ParserConfig {
+///     limits: ParserLimits {
+///         max_splice_descriptors: 1,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     Err(ParseError::LimitExceeded {
+///         limit: 1,
+///         description: "max_splice_descriptors"
+///     }),
+///     SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+/// );
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct ParserLimits {
+    /// The maximum depth that `MID` UPIDs may be nested (a `MID` that contains no other `MID` has
+    /// a depth of 1). Defaults to 8.
+    pub max_upid_nesting_depth: usize,
+    /// The maximum number of splice descriptors in the descriptor loop. Defaults to 1024.
+    pub max_splice_descriptors: usize,
+    /// The maximum total number of bytes copied out of the data into strings and byte arrays.
+    /// Defaults to 65536.
+    pub max_allocation_bytes: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        Self {
+            max_upid_nesting_depth: 8,
+            max_splice_descriptors: 1024,
+            max_allocation_bytes: 65536,
+        }
+    }
+}
+
+/// How an inconsistency with the specification is handled when parsing (see `ParserConfig`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum ErrorHandling {
+    /// The error fails the parse.
+    Fatal,
+    /// The error is added to the `non_fatal_errors` of the `SpliceInfoSection`.
+    NonFatal,
+    /// The error is discarded.
+    Ignore,
+}
+
+/// A builder for the `SpliceInfoSection`, that defaults the fields that are usually static and
+/// calculates the `crc_32` on build.
+///
+/// The defaults are:
+/// * `table_id`: `0xFC`
+/// * `sap_type`: `SAPType::Unspecified`
+/// * `protocol_version`: `0`
+/// * `pts_adjustment`: `Pts90k(0)`
+/// * `tier`: `0xFFF`
+/// * `splice_command`: `SpliceCommand::SpliceNull`
+/// * `splice_descriptors`: empty
+///
+/// ```
+/// use scte35::{
+///     splice_command::{time_signal::TimeSignal, SpliceCommand},
+///     splice_info_section::SpliceInfoSection,
+///     time::{Pts90k, SpliceTime},
+/// };
+///
+/// let splice_info_section = SpliceInfoSection::builder()
+///     .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+///         splice_time: SpliceTime {
+///             pts_time: Some(Pts90k(1924989008)),
+///         },
+///     }))
+///     .build()
+///     .unwrap();
+/// assert_eq!(0xFC, splice_info_section.table_id);
+/// assert_eq!(0xFFF, splice_info_section.tier);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SpliceInfoSectionBuilder {
+    table_id: u8,
+    sap_type: SAPType,
+    protocol_version: u8,
+    pts_adjustment: Pts90k,
+    tier: u16,
+    splice_command: SpliceCommand,
+    splice_descriptors: Vec<SpliceDescriptor>,
+}
+
+impl Default for SpliceInfoSectionBuilder {
+    fn default() -> Self {
+        Self {
+            table_id: 0xFC,
+            sap_type: SAPType::Unspecified,
+            protocol_version: 0,
+            pts_adjustment: Pts90k(0),
+            tier: 0xFFF,
+            splice_command: SpliceCommand::SpliceNull,
+            splice_descriptors: vec![],
+        }
+    }
+}
+
+impl SpliceInfoSectionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn table_id(mut self, table_id: u8) -> Self {
+        self.table_id = table_id;
+        self
+    }
+
+    pub fn sap_type(mut self, sap_type: SAPType) -> Self {
+        self.sap_type = sap_type;
+        self
+    }
+
+    pub fn protocol_version(mut self, protocol_version: u8) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    pub fn pts_adjustment(mut self, pts_adjustment: Pts90k) -> Self {
+        self.pts_adjustment = pts_adjustment;
+        self
+    }
+
+    pub fn tier(mut self, tier: u16) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    pub fn splice_command(mut self, splice_command: SpliceCommand) -> Self {
+        self.splice_command = splice_command;
+        self
+    }
+
+    /// Appends the descriptor to the list of splice descriptors.
+    pub fn splice_descriptor(mut self, splice_descriptor: SpliceDescriptor) -> Self {
+        self.splice_descriptors.push(splice_descriptor);
+        self
+    }
+
+    /// Replaces the list of splice descriptors.
+    pub fn splice_descriptors(mut self, splice_descriptors: Vec<SpliceDescriptor>) -> Self {
+        self.splice_descriptors = splice_descriptors;
+        self
+    }
+
+    /// Validates that each descriptor is allowed with the splice command, encodes the message to
+    /// calculate the `crc_32`, and returns the resulting `SpliceInfoSection`.
+    pub fn build(self) -> Result<SpliceInfoSection, EncodeError> {
+        let splice_command_type = self.splice_command.command_type();
+        for splice_descriptor in &self.splice_descriptors {
+            let splice_descriptor_tag = splice_descriptor.tag();
+            if !splice_descriptor_tag.is_allowed_with(&splice_command_type) {
+                return Err(EncodeError::InvalidSpliceDescriptorForCommand {
+                    splice_descriptor_tag,
+                    splice_command_type,
+                });
+            }
+        }
+        let mut splice_info_section = SpliceInfoSection {
+            table_id: self.table_id,
+            sap_type: self.sap_type,
+            protocol_version: self.protocol_version,
+            encrypted_packet: None,
+            pts_adjustment: self.pts_adjustment,
+            tier: self.tier,
+            splice_command: self.splice_command,
+            splice_descriptors: self.splice_descriptors,
+            crc_32: 0,
+            non_fatal_errors: vec![],
+        };
+        splice_info_section.canonicalize()?;
+        Ok(splice_info_section)
+    }
+}
+
+// A 64-bit FNV-1a hasher used for the `dedup_key` and `Redaction::Hash`. Unlike the
+// `DefaultHasher`, its output is specified, and integers are written as little-endian (with
+// lengths written as 64 bits) so that the output does not depend on the platform.
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xCBF29CE484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001B3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+// The `splice_time` of a `TimeSignal`, or of a `SpliceInsert` in Program Splice Mode.
+fn splice_time(splice_command: &SpliceCommand) -> Option<&SpliceTime> {
+    match splice_command {
+        SpliceCommand::TimeSignal(time_signal) => Some(&time_signal.splice_time),
+        SpliceCommand::SpliceInsert(splice_insert) => splice_insert
+            .scheduled_event
+            .as_ref()
+            .and_then(|event| match &event.splice_mode {
+                SpliceMode::ProgramSpliceMode(program) => program.splice_time.as_ref(),
+                SpliceMode::ComponentSpliceMode(_) => None,
+            }),
+        _ => None,
+    }
+}
+
+/// The fields at the start of a `SpliceInfoSection` (see `SpliceInfoSection::peek_header`), which
+/// are enough for a router to decide whether to forward a message without parsing all of it.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpliceInfoHeader {
+    /// See `SpliceInfoSection::table_id`.
+    pub table_id: u8,
+    /// See `SpliceInfoSection::sap_type`.
+    pub sap_type: SAPType,
+    /// See `SpliceInfoSection::protocol_version`.
+    pub protocol_version: u8,
+    /// Whether the message is encrypted, in which case the `splice_command_type` and `pts_time`
+    /// are not available.
+    pub encrypted_packet: bool,
+    /// See `EncryptedPacket::encryption_algorithm` (only meaningful when `encrypted_packet` is
+    /// set).
+    pub encryption_algorithm: EncryptionAlgorithm,
+    /// See `SpliceInfoSection::pts_adjustment`.
+    pub pts_adjustment: Pts90k,
+    /// See `EncryptedPacket::cw_index` (only meaningful when `encrypted_packet` is set).
+    pub cw_index: u8,
+    /// See `SpliceInfoSection::tier`.
+    pub tier: u16,
+    /// The length in bytes of the splice command, following the `splice_command_type`.
+    pub splice_command_length: u16,
+    /// The type of the splice command, which is `None` when the message is encrypted.
+    pub splice_command_type: Option<SpliceCommandType>,
+    /// The `pts_time` of a `TimeSignal`, or of a `SpliceInsert` in Program Splice Mode, without
+    /// the `pts_adjustment` added (see `SpliceInfoHeader::adjusted_pts_time`).
+    pub pts_time: Option<Pts90k>,
+}
+
+impl SpliceInfoHeader {
+    /// The `pts_time` with the `pts_adjustment` added (see
+    /// `SpliceInfoSection::adjusted_splice_time`).
+    pub fn adjusted_pts_time(&self) -> Option<Pts90k> {
+        self.pts_time.map(|pts_time| pts_time + self.pts_adjustment)
+    }
 }
 
 /// A two-bit field that indicates if the content preparation system has created a Stream Access
 /// Point (SAP) at the signaled point in the stream. SAP types are defined in ISO 14496-12, Annex
 /// I.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SAPType {
     /// Closed GOP with no leading pictures
     Type1,
@@ -213,7 +1492,7 @@ impl SAPType {
         match *self {
             SAPType::Type1 => 0x0,
             SAPType::Type2 => 0x1,
-            SAPType::Type3 => 0x3,
+            SAPType::Type3 => 0x2,
             SAPType::Unspecified => 0x3,
         }
     }
@@ -221,14 +1500,16 @@ impl SAPType {
 
 /// This indicates that portions of the `SpliceInfoSection`, starting with `splice_command_type`
 /// and ending with and including `e_crc_32`, are encrypted.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EncryptedPacket {
     /// The `encryption_algorithm` field of the `SpliceInfoSection` is a 6-bit value. All Data
     /// Encryption Standard variants use a 64-bit key (actually 56 bits plus a checksum) to encrypt
     /// or decrypt a block of 8 bytes. In the case of triple DES, there will need to be 3 64-bit
     /// keys, one for each of the three passes of the DES algorithm. The “standard” triple DES
     /// actually uses two keys, where the first and third keys are identical.
-    pub encryption_algorithm: Option<EncryptionAlgorithm>,
+    pub encryption_algorithm: EncryptionAlgorithm,
     /// An 8-bit unsigned integer that conveys which control word (key) is to be used to decrypt
     /// the message. The splicing device may store up to 256 keys previously provided for this
     /// purpose. When the `encrypted_packet` is `false`, this field is present but undefined.
@@ -253,7 +1534,9 @@ pub struct EncryptedPacket {
 /// decrypt a block of 8 bytes. In the case of triple DES, there will need to be 3 64-bit keys, one
 /// for each of the three passes of the DES algorithm. The “standard” triple DES actually uses two
 /// keys, where the first and third keys are identical.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum EncryptionAlgorithm {
     /// No encryption
     NoEncryption,
@@ -265,6 +1548,8 @@ pub enum EncryptionAlgorithm {
     TripleDes,
     /// User private
     UserPrivate(u8),
+    /// An `encryption_algorithm` that is reserved by the specification (4 to 31).
+    Reserved(u8),
 }
 
 impl TryFrom<u8> for EncryptionAlgorithm {
@@ -282,3 +1567,48 @@ impl TryFrom<u8> for EncryptionAlgorithm {
         }
     }
 }
+
+impl EncryptionAlgorithm {
+    /// The encryption algorithm for the 6-bit `value`, which is `Reserved` when `value` is
+    /// reserved by the specification.
+    /// ```
+    /// use scte35::splice_info_section::EncryptionAlgorithm;
+    ///
+    /// assert_eq!(EncryptionAlgorithm::DesCbcMode, EncryptionAlgorithm::from_value(2));
+    /// assert_eq!(EncryptionAlgorithm::Reserved(4), EncryptionAlgorithm::from_value(4));
+    /// assert_eq!(4, EncryptionAlgorithm::from_value(4).value());
+    /// ```
+    pub fn from_value(value: u8) -> Self {
+        Self::try_from(value).unwrap_or(Self::Reserved(value))
+    }
+
+    pub fn value(&self) -> u8 {
+        match *self {
+            EncryptionAlgorithm::NoEncryption => 0,
+            EncryptionAlgorithm::DesEcbMode => 1,
+            EncryptionAlgorithm::DesCbcMode => 2,
+            EncryptionAlgorithm::TripleDes => 3,
+            EncryptionAlgorithm::UserPrivate(value) => value,
+            EncryptionAlgorithm::Reserved(value) => value,
+        }
+    }
+}
+
+// The number of bytes in the section that starts at `data` (i.e. 3 bytes more than the
+// `section_length`), or `None` when there are not enough bytes to read the `section_length`.
+fn section_size(data: &[u8]) -> Option<usize> {
+    (data.len() >= 3).then(|| 3 + (usize::from(data[1] & 0x0F) << 8 | usize::from(data[2])))
+}
+
+// The section without any trailing 0xFF stuffing bytes, when `ignore_trailing_padding` is set.
+fn without_trailing_padding<'a>(data: &'a [u8], config: &ParserConfig) -> &'a [u8] {
+    if !config.ignore_trailing_padding {
+        return data;
+    }
+    match section_size(data) {
+        Some(size) if size < data.len() && data[size..].iter().all(|byte| *byte == 0xFF) => {
+            &data[..size]
+        }
+        _ => data,
+    }
+}
@@ -0,0 +1,392 @@
+//! A zero-copy view of a `SpliceInfoSection`, where the variable-length data (private bytes, UPID
+//! strings and `ManagedPrivateUPID` data) borrows from the input buffer instead of being copied
+//! into `String`s and `Vec<u8>`s.
+//!
+//! The view is intended for monitoring dense streams of SCTE-35 messages, where most sections only
+//! need to be inspected and not kept. Any part of the view can be converted into the owned model
+//! when needed.
+//! ```
+//! use scte35::{
+//!     splice_descriptor::segmentation_descriptor::SegmentationUPIDType,
+//!     splice_info_section::SpliceInfoSection,
+//!     splice_info_section_ref::SpliceInfoSectionRef,
+//! };
+//!
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let bytes = section.try_to_bytes().unwrap();
+//!
+//! let section_ref = SpliceInfoSectionRef::try_from_bytes(&bytes).unwrap();
+//! let segmentation_descriptor = section_ref.splice_descriptors[0]
+//!     .segmentation_descriptor()
+//!     .unwrap()
+//!     .unwrap();
+//! let upid = segmentation_descriptor.scheduled_event.unwrap().segmentation_upid;
+//! assert_eq!(SegmentationUPIDType::TI, upid.upid_type);
+//! assert_eq!(&[0x00, 0x00, 0x00, 0x00, 0x2C, 0xA0, 0xA1, 0x8A], upid.bytes);
+//! assert_eq!(section, section_ref.try_to_owned().unwrap());
+//! ```
+use crate::{
+    bit_reader::Bits,
+    error::ParseError,
+    splice_command::{private_command::PrivateCommand, SpliceCommand, SpliceCommandType},
+    splice_descriptor::{
+        segmentation_descriptor::{
+            ComponentSegmentation, DeliveryRestrictions, ManagedPrivateUPID, ScheduledEventFields,
+            SegmentationTypeID, SegmentationUPID, SegmentationUPIDType, SubSegment,
+        },
+        SpliceDescriptor, SpliceDescriptorTag, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{SAPType, SpliceInfoSection},
+    time::Pts90k,
+    visit::{parse_with_visitor, SpliceInfoSectionHeader, SpliceVisitor},
+};
+use std::ops::ControlFlow;
+
+/// A zero-copy view of a `SpliceInfoSection` (see the module documentation).
+///
+/// Unlike `SpliceInfoSection::try_from_bytes`, the splice command and splice descriptors are only
+/// split into their raw bytes when the view is created, and are parsed further on demand.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SpliceInfoSectionRef<'a> {
+    pub table_id: u8,
+    pub sap_type: SAPType,
+    pub protocol_version: u8,
+    pub pts_adjustment: Pts90k,
+    pub tier: u16,
+    pub splice_command: SpliceCommandRef<'a>,
+    pub splice_descriptors: Vec<SpliceDescriptorRef<'a>>,
+    pub crc_32: u32,
+    data: &'a [u8],
+}
+
+impl<'a> SpliceInfoSectionRef<'a> {
+    /// Creates a `SpliceInfoSectionRef` that borrows from the provided bytes.
+    pub fn try_from_bytes(data: &'a [u8]) -> Result<Self, ParseError> {
+        let mut collector = Collector::default();
+        parse_with_visitor(data, &mut collector)?;
+        // The walk only ends early on an error, and so all parts have been visited.
+        let (Some(header), Some(splice_command)) = (collector.header, collector.splice_command)
+        else {
+            unreachable!("should have visited header and splice_command");
+        };
+        Ok(Self {
+            table_id: header.table_id,
+            sap_type: header.sap_type,
+            protocol_version: header.protocol_version,
+            pts_adjustment: header.pts_adjustment,
+            tier: header.tier,
+            splice_command,
+            splice_descriptors: collector.splice_descriptors,
+            crc_32: header.crc_32,
+            data,
+        })
+    }
+
+    /// Parses the borrowed bytes into the owned `SpliceInfoSection`.
+    pub fn try_to_owned(&self) -> Result<SpliceInfoSection, ParseError> {
+        SpliceInfoSection::try_from_bytes(self.data)
+    }
+}
+
+#[derive(Default)]
+struct Collector<'a> {
+    header: Option<SpliceInfoSectionHeader>,
+    splice_command: Option<SpliceCommandRef<'a>>,
+    splice_descriptors: Vec<SpliceDescriptorRef<'a>>,
+}
+
+impl<'a> SpliceVisitor<'a> for Collector<'a> {
+    fn visit_header(&mut self, header: &SpliceInfoSectionHeader) -> ControlFlow<()> {
+        self.header = Some(header.clone());
+        ControlFlow::Continue(())
+    }
+
+    fn visit_splice_command(&mut self, splice_command: SpliceCommandRef<'a>) -> ControlFlow<()> {
+        self.splice_command = Some(splice_command);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_splice_descriptor(
+        &mut self,
+        _: usize,
+        splice_descriptor: SpliceDescriptorRef<'a>,
+    ) -> ControlFlow<()> {
+        self.splice_descriptors.push(splice_descriptor);
+        ControlFlow::Continue(())
+    }
+}
+
+/// The raw bytes of a splice command, borrowed from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SpliceCommandRef<'a> {
+    pub splice_command_type: SpliceCommandType,
+    /// The bytes of the command that follow the `splice_command_type`.
+    pub bytes: &'a [u8],
+}
+
+impl<'a> SpliceCommandRef<'a> {
+    /// The `identifier` and private bytes of a `PrivateCommand`, or `None` for any other type of
+    /// command (or when the identifier is not valid UTF-8).
+    pub fn private_command(&self) -> Option<PrivateCommandRef<'a>> {
+        if self.splice_command_type != SpliceCommandType::PrivateCommand || self.bytes.len() < 4 {
+            return None;
+        }
+        Some(PrivateCommandRef {
+            identifier: std::str::from_utf8(&self.bytes[..4]).ok()?,
+            private_bytes: &self.bytes[4..],
+        })
+    }
+
+    /// Parses the borrowed bytes into the owned `SpliceCommand`.
+    pub fn try_to_owned(&self) -> Result<SpliceCommand, ParseError> {
+        if let Some(private_command) = self.private_command() {
+            return Ok(SpliceCommand::PrivateCommand(PrivateCommand {
+                identifier: String::from(private_command.identifier),
+                private_bytes: private_command.private_bytes.to_vec(),
+            }));
+        }
+        let mut bytes = vec![self.splice_command_type.value()];
+        bytes.extend_from_slice(self.bytes);
+        let mut bits = Bits::new(&bytes);
+        SpliceCommand::try_from(&mut bits, self.bytes.len() as u32)
+    }
+}
+
+/// The `identifier` and private bytes of a `PrivateCommand`, borrowed from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct PrivateCommandRef<'a> {
+    pub identifier: &'a str,
+    pub private_bytes: &'a [u8],
+}
+
+/// The raw bytes of a splice descriptor, borrowed from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SpliceDescriptorRef<'a> {
+    pub tag: u8,
+    pub identifier: u32,
+    /// The bytes of the descriptor that follow the `identifier`.
+    pub private_bytes: &'a [u8],
+    bytes: &'a [u8],
+}
+
+impl<'a> SpliceDescriptorRef<'a> {
+    pub(crate) fn try_from_bytes(data: &'a [u8]) -> Result<Self, ParseError> {
+        let header = slice(data, 0, 2, "SpliceDescriptorRef; reading descriptor_length")?;
+        let descriptor_length = header[1] as usize;
+        let bytes = slice(
+            data,
+            0,
+            2 + descriptor_length,
+            "SpliceDescriptorRef; reading descriptor",
+        )?;
+        let identifier = slice(
+            bytes,
+            2,
+            4,
+            "SpliceDescriptorRef; need at least 32 bits for identifier",
+        )?;
+        Ok(Self {
+            tag: header[0],
+            identifier: u32::from_be_bytes([
+                identifier[0],
+                identifier[1],
+                identifier[2],
+                identifier[3],
+            ]),
+            private_bytes: &bytes[6..],
+            bytes,
+        })
+    }
+
+    // The length of the descriptor including the `splice_descriptor_tag` and `descriptor_length`.
+    pub(crate) fn byte_count(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Parses the descriptor as a `SegmentationDescriptorRef`, or returns `None` when it is not a
+    /// segmentation descriptor.
+    pub fn segmentation_descriptor(
+        &self,
+    ) -> Option<Result<SegmentationDescriptorRef<'a>, ParseError>> {
+        if self.tag != SpliceDescriptorTag::SegmentationDescriptor.value()
+            || self.identifier != CUEI_IDENTIFIER
+        {
+            return None;
+        }
+        Some(SegmentationDescriptorRef::try_from_bytes(
+            self.private_bytes,
+        ))
+    }
+
+    /// Parses the borrowed bytes into the owned `SpliceDescriptor`.
+    pub fn try_to_owned(&self) -> Result<SpliceDescriptor, ParseError> {
+        let mut bits = Bits::new(self.bytes);
+        SpliceDescriptor::try_from(&mut bits)
+    }
+}
+
+/// A `SegmentationDescriptor` whose `segmentation_upid` borrows from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SegmentationDescriptorRef<'a> {
+    pub event_id: u32,
+    /// The details of the segmentation event, which is `None` when the event is cancelled.
+    pub scheduled_event: Option<ScheduledEventRef<'a>>,
+}
+
+impl<'a> SegmentationDescriptorRef<'a> {
+    // NOTE: It is assumed that the bytes follow the identifier.
+    fn try_from_bytes(data: &'a [u8]) -> Result<Self, ParseError> {
+        let mut bits = Bits::new(data);
+        bits.validate(40, "SegmentationDescriptorRef")?;
+        let event_id = bits.u32("event_id", 32)?;
+        let segmentation_event_cancelled = bits.bool("segmentation_event_cancel_indicator")?;
+        bits.consume(7)?;
+        if segmentation_event_cancelled {
+            return Ok(Self {
+                event_id,
+                scheduled_event: None,
+            });
+        }
+        let fields = ScheduledEventFields::try_from(&mut bits, 0, |bits| {
+            let offset = data.len() - bits.bits_remaining() / 8;
+            let upid = SegmentationUPIDRef::try_from_bytes(&data[offset..])?;
+            bits.skip_bytes("segmentation_upid", 2 + upid.bytes.len())?;
+            Ok(upid)
+        })?;
+        Ok(Self {
+            event_id,
+            scheduled_event: Some(ScheduledEventRef {
+                delivery_restrictions: fields.delivery_restrictions,
+                component_segments: fields.component_segments,
+                segmentation_duration: fields.segmentation_duration,
+                segmentation_upid: fields.segmentation_upid,
+                segmentation_type_id: fields.segmentation_type_id,
+                segment_num: fields.segment_num,
+                segments_expected: fields.segments_expected,
+                sub_segment: fields.sub_segment,
+            }),
+        })
+    }
+}
+
+/// A `ScheduledEvent` whose `segmentation_upid` borrows from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct ScheduledEventRef<'a> {
+    pub delivery_restrictions: Option<DeliveryRestrictions>,
+    pub component_segments: Option<Vec<ComponentSegmentation>>,
+    pub segmentation_duration: Option<u64>,
+    pub segmentation_upid: SegmentationUPIDRef<'a>,
+    pub segmentation_type_id: SegmentationTypeID,
+    pub segment_num: u8,
+    pub segments_expected: u8,
+    pub sub_segment: Option<SubSegment>,
+}
+
+/// The raw bytes of a `SegmentationUPID`, borrowed from the input buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SegmentationUPIDRef<'a> {
+    pub upid_type: SegmentationUPIDType,
+    /// The `segmentation_upid()` bytes (i.e. the bytes that follow `segmentation_upid_length`).
+    pub bytes: &'a [u8],
+}
+
+impl<'a> SegmentationUPIDRef<'a> {
+    fn try_from_bytes(data: &'a [u8]) -> Result<Self, ParseError> {
+        let header = slice(
+            data,
+            0,
+            2,
+            "SegmentationUPIDRef; reading segmentation_upid_length",
+        )?;
+        Ok(Self {
+            upid_type: SegmentationUPIDType::try_from(header[0])?,
+            bytes: slice(
+                data,
+                2,
+                header[1] as usize,
+                "SegmentationUPIDRef; reading segmentation_upid",
+            )?,
+        })
+    }
+
+    /// The UPID as a string, for the types that carry text (`UserDefined`, `ISCI`, `AdID`, `TID`,
+    /// `ADI`, `ADSInformation` and `URI`), or `None` for any other type (or when the bytes are not
+    /// valid UTF-8).
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.upid_type {
+            SegmentationUPIDType::UserDefined
+            | SegmentationUPIDType::ISCI
+            | SegmentationUPIDType::AdID
+            | SegmentationUPIDType::TID
+            | SegmentationUPIDType::ADI
+            | SegmentationUPIDType::ADSInformation
+            | SegmentationUPIDType::URI => std::str::from_utf8(self.bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// The `format_specifier` and `private_data` of an `MPU`, or `None` for any other type (or
+    /// when the format specifier is not valid UTF-8).
+    pub fn mpu(&self) -> Option<ManagedPrivateUPIDRef<'a>> {
+        if self.upid_type != SegmentationUPIDType::MPU || self.bytes.len() < 4 {
+            return None;
+        }
+        Some(ManagedPrivateUPIDRef {
+            format_specifier: std::str::from_utf8(&self.bytes[..4]).ok()?,
+            private_data: &self.bytes[4..],
+        })
+    }
+
+    /// The UPIDs contained in a `MID`, or `None` for any other type.
+    pub fn mid(&self) -> Option<Result<Vec<SegmentationUPIDRef<'a>>, ParseError>> {
+        if self.upid_type != SegmentationUPIDType::MID {
+            return None;
+        }
+        let mut upids = vec![];
+        let mut offset = 0;
+        while offset < self.bytes.len() {
+            match Self::try_from_bytes(&self.bytes[offset..]) {
+                Ok(upid) => {
+                    offset += 2 + upid.bytes.len();
+                    upids.push(upid);
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        Some(Ok(upids))
+    }
+
+    /// Parses the borrowed bytes into the owned `SegmentationUPID`.
+    pub fn try_to_owned(&self) -> Result<SegmentationUPID, ParseError> {
+        if let Some(mpu) = self.mpu() {
+            return Ok(SegmentationUPID::MPU(ManagedPrivateUPID {
+                format_specifier: String::from(mpu.format_specifier),
+                private_data: mpu.private_data.to_vec(),
+            }));
+        }
+        SegmentationUPID::try_from_type_and_bytes(self.upid_type.clone(), self.bytes)
+    }
+}
+
+/// The `format_specifier` and `private_data` of a `ManagedPrivateUPID`, borrowed from the input
+/// buffer.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct ManagedPrivateUPIDRef<'a> {
+    pub format_specifier: &'a str,
+    pub private_data: &'a [u8],
+}
+
+pub(crate) fn slice<'a>(
+    data: &'a [u8],
+    start: usize,
+    length: usize,
+    description: &'static str,
+) -> Result<&'a [u8], ParseError> {
+    data.get(start..start + length)
+        .ok_or(ParseError::UnexpectedEndOfData {
+            expected_minimum_bits_left: (length * 8) as u32,
+            actual_bits_left: (data.len().saturating_sub(start) * 8) as u32,
+            description,
+        })
+}
@@ -0,0 +1,286 @@
+//! [`proptest`] strategies for generating random, valid SCTE-35 messages, available with the
+//! `test-util` feature.
+//!
+//! Every value generated by these strategies can be encoded, and parsing the encoded bytes
+//! results in an equal value. This makes them suitable for testing integrations (e.g. packagers or
+//! ad decision services) against a wide range of realistic cues, rather than a handful of
+//! hard-coded samples.
+//! ```
+//! use proptest::{prelude::*, test_runner::TestRunner};
+//! use scte35::{splice_info_section::SpliceInfoSection, test_util};
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&test_util::splice_info_section(), |splice_info_section| {
+//!         let bytes = splice_info_section.try_to_bytes().unwrap();
+//!         prop_assert_eq!(
+//!             splice_info_section,
+//!             SpliceInfoSection::try_from_bytes(&bytes).unwrap()
+//!         );
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+use crate::{
+    splice_command::{splice_insert::SpliceInsert, time_signal::TimeSignal, SpliceCommand},
+    splice_descriptor::{
+        avail_descriptor::AvailDescriptor,
+        dtmf_descriptor::DTMFDescriptor,
+        segmentation_descriptor::{
+            hyphenated_uuid, DeliveryRestrictions, DeviceRestrictions, ManagedPrivateUPID,
+            SegmentationDescriptor, SegmentationTypeID, SegmentationUPID,
+        },
+        time_descriptor::TimeDescriptor,
+        SpliceDescriptor, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{SAPType, SpliceInfoSection},
+    time::{Pts90k, SpliceTime},
+};
+use proptest::{collection::vec, option, prelude::*};
+
+/// A 33-bit value of the 90 kHz clock.
+pub fn pts_time() -> impl Strategy<Value = Pts90k> {
+    (0..=Pts90k::MAX.0).prop_map(Pts90k)
+}
+
+/// A `SpliceTime`, which is specified (has a `pts_time`) three times out of four.
+pub fn splice_time() -> impl Strategy<Value = SpliceTime> {
+    option::weighted(0.75, pts_time()).prop_map(|pts_time| SpliceTime { pts_time })
+}
+
+/// A `TimeSignal` command.
+pub fn time_signal() -> impl Strategy<Value = TimeSignal> {
+    splice_time().prop_map(|splice_time| TimeSignal { splice_time })
+}
+
+/// A `SpliceInsert` command, in either the Program or Component Splice Mode, which is cancelled
+/// one time in ten.
+pub fn splice_insert() -> impl Strategy<Value = SpliceInsert> {
+    (
+        any::<u32>(),
+        prop::bool::weighted(0.1),
+        any::<bool>(),
+        any::<bool>(),
+        option::of(pts_time()),
+        option::weighted(0.25, vec((any::<u8>(), option::of(pts_time())), 1..4)),
+        option::of((pts_time(), any::<bool>())),
+        (any::<u16>(), any::<u8>(), any::<u8>()),
+    )
+        .prop_map(
+            |(
+                event_id,
+                is_cancelled,
+                out_of_network_indicator,
+                is_immediate_splice,
+                pts_time,
+                components,
+                break_duration,
+                (unique_program_id, avail_num, avails_expected),
+            )| {
+                let mut builder = SpliceInsert::builder(event_id)
+                    .out_of_network_indicator(out_of_network_indicator)
+                    .unique_program_id(unique_program_id)
+                    .avail_num(avail_num)
+                    .avails_expected(avails_expected);
+                if is_cancelled {
+                    builder = builder.cancel();
+                }
+                if is_immediate_splice {
+                    builder = builder.immediate();
+                }
+                if let Some(pts_time) = pts_time {
+                    builder = builder.splice_time(pts_time);
+                }
+                for (component_tag, pts_time) in components.unwrap_or_default() {
+                    builder = builder.component(component_tag, pts_time);
+                }
+                if let Some((duration, auto_return)) = break_duration {
+                    builder = builder.break_duration(duration, auto_return);
+                }
+                builder
+                    .build()
+                    .expect("generated SpliceInsert should be valid")
+            },
+        )
+}
+
+/// A `SpliceCommand` that can carry descriptors: `SpliceNull`, `SpliceInsert` or `TimeSignal`.
+pub fn splice_command() -> impl Strategy<Value = SpliceCommand> {
+    prop_oneof![
+        1 => Just(SpliceCommand::SpliceNull),
+        2 => splice_insert().prop_map(SpliceCommand::SpliceInsert),
+        3 => time_signal().prop_map(SpliceCommand::TimeSignal),
+    ]
+}
+
+/// A `SegmentationTypeID` defined by the specification (i.e. not `Reserved`).
+pub fn segmentation_type_id() -> impl Strategy<Value = SegmentationTypeID> {
+    let segmentation_type_ids = (0..=u8::MAX)
+        .filter_map(|value| SegmentationTypeID::try_from(value).ok())
+        .filter(|type_id| !matches!(type_id, SegmentationTypeID::Reserved(_)))
+        .collect::<Vec<_>>();
+    prop::sample::select(segmentation_type_ids)
+}
+
+/// A `SegmentationUPID` of one of the commonly used types (Ad-ID, TI, URI, UUID, MPU or not
+/// used).
+pub fn segmentation_upid() -> impl Strategy<Value = SegmentationUPID> {
+    prop_oneof![
+        Just(SegmentationUPID::NotUsed),
+        "[A-Z]{4}[A-Z0-9]{8}".prop_map(SegmentationUPID::AdID),
+        any::<u64>().prop_map(SegmentationUPID::new_ti),
+        "https://example\\.com/[a-z0-9]{1,32}".prop_map(SegmentationUPID::URI),
+        any::<[u8; 16]>().prop_map(|uuid| SegmentationUPID::UUID(hyphenated_uuid(&uuid))),
+        ("[A-Z]{4}", vec(any::<u8>(), 0..32)).prop_map(|(format_specifier, private_data)| {
+            SegmentationUPID::MPU(ManagedPrivateUPID {
+                format_specifier,
+                private_data,
+            })
+        }),
+    ]
+}
+
+/// A `DeliveryRestrictions`.
+pub fn delivery_restrictions() -> impl Strategy<Value = DeliveryRestrictions> {
+    (any::<bool>(), any::<bool>(), any::<bool>(), 0..4u8).prop_map(
+        |(web_delivery_allowed, no_regional_blackout, archive_allowed, device_restrictions)| {
+            DeliveryRestrictions {
+                web_delivery_allowed,
+                no_regional_blackout,
+                archive_allowed,
+                device_restrictions: DeviceRestrictions::try_from(device_restrictions)
+                    .expect("generated DeviceRestrictions should be valid"),
+            }
+        },
+    )
+}
+
+/// A `SegmentationDescriptor`, which is cancelled one time in ten.
+pub fn segmentation_descriptor() -> impl Strategy<Value = SegmentationDescriptor> {
+    (
+        any::<u32>(),
+        prop::bool::weighted(0.1),
+        segmentation_type_id(),
+        segmentation_upid(),
+        option::of(delivery_restrictions()),
+        option::weighted(0.25, vec((any::<u8>(), pts_time()), 1..4)),
+        option::of(0..(1u64 << 40)),
+        (
+            any::<u8>(),
+            any::<u8>(),
+            option::of((any::<u8>(), any::<u8>())),
+        ),
+    )
+        .prop_map(
+            |(
+                event_id,
+                is_cancelled,
+                segmentation_type_id,
+                segmentation_upid,
+                delivery_restrictions,
+                component_segments,
+                segmentation_duration,
+                (segment_num, segments_expected, sub_segment),
+            )| {
+                let mut builder = SegmentationDescriptor::builder(event_id)
+                    .segmentation_type_id(segmentation_type_id)
+                    .upid(segmentation_upid)
+                    .segment_num(segment_num)
+                    .segments_expected(segments_expected);
+                if is_cancelled {
+                    builder = builder.cancel();
+                }
+                if let Some(delivery_restrictions) = delivery_restrictions {
+                    builder = builder.delivery_restrictions(delivery_restrictions);
+                }
+                for (component_tag, pts_offset) in component_segments.unwrap_or_default() {
+                    builder = builder.component_segment(component_tag, pts_offset);
+                }
+                if let Some(segmentation_duration) = segmentation_duration {
+                    builder = builder.duration_90khz(segmentation_duration);
+                }
+                if let Some((sub_segment_num, sub_segments_expected)) = sub_segment {
+                    builder = builder.sub_segment(sub_segment_num, sub_segments_expected);
+                }
+                builder
+                    .build()
+                    .expect("generated SegmentationDescriptor should be valid")
+            },
+        )
+}
+
+/// A `TimeDescriptor`.
+pub fn time_descriptor() -> impl Strategy<Value = TimeDescriptor> {
+    (0..(1u64 << 48), 0..1_000_000_000u32, any::<u16>()).prop_map(
+        |(tai_seconds, tai_ns, utc_offset)| TimeDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            tai_seconds,
+            tai_ns,
+            utc_offset,
+        },
+    )
+}
+
+/// An `AvailDescriptor`.
+pub fn avail_descriptor() -> impl Strategy<Value = AvailDescriptor> {
+    any::<u32>().prop_map(|provider_avail_id| AvailDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        provider_avail_id,
+    })
+}
+
+/// A `DTMFDescriptor`.
+pub fn dtmf_descriptor() -> impl Strategy<Value = DTMFDescriptor> {
+    (any::<u8>(), "[0-9*#]{0,7}").prop_map(|(preroll, dtmf_chars)| DTMFDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        preroll,
+        dtmf_chars,
+    })
+}
+
+/// A `SpliceDescriptor` that is allowed to be carried with a command of the given type.
+pub fn splice_descriptor_for(
+    splice_command: &SpliceCommand,
+) -> impl Strategy<Value = SpliceDescriptor> {
+    let splice_command_type = splice_command.command_type();
+    prop_oneof![
+        4 => segmentation_descriptor().prop_map(SpliceDescriptor::SegmentationDescriptor),
+        1 => time_descriptor().prop_map(SpliceDescriptor::TimeDescriptor),
+        1 => avail_descriptor().prop_map(SpliceDescriptor::AvailDescriptor),
+        1 => dtmf_descriptor().prop_map(SpliceDescriptor::DTMFDescriptor),
+    ]
+    .prop_filter(
+        "should be allowed with the splice command",
+        move |descriptor| descriptor.tag().is_allowed_with(&splice_command_type),
+    )
+}
+
+/// A `SpliceInfoSection` with a `SpliceNull`, `SpliceInsert` or `TimeSignal` command and up to 4
+/// descriptors that are allowed with that command.
+pub fn splice_info_section() -> impl Strategy<Value = SpliceInfoSection> {
+    splice_command()
+        .prop_flat_map(|splice_command| {
+            let splice_descriptors = vec(splice_descriptor_for(&splice_command), 0..=4);
+            (
+                Just(splice_command),
+                splice_descriptors,
+                0..4u8,
+                pts_time(),
+                0..=0xFFFu16,
+            )
+        })
+        .prop_map(
+            |(splice_command, splice_descriptors, sap_type, pts_adjustment, tier)| {
+                SpliceInfoSection::builder()
+                    .sap_type(
+                        SAPType::try_from(sap_type).expect("generated SAPType should be valid"),
+                    )
+                    .pts_adjustment(pts_adjustment)
+                    .tier(tier)
+                    .splice_command(splice_command)
+                    .splice_descriptors(splice_descriptors)
+                    .build()
+                    .expect("generated SpliceInfoSection should be valid")
+            },
+        )
+}
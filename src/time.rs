@@ -1,4 +1,252 @@
-use crate::{bit_reader::Bits, error::ParseError};
+use crate::{
+    bit_reader::Bits,
+    bit_writer::BitWriter,
+    error::{EncodeError, ParseError},
+};
+use std::{
+    cmp::Ordering,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The number of ticks per second of the program's 90 kHz clock.
+pub const TICKS_PER_SECOND: u64 = 90_000;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Specifies how a `Duration` that is not a whole number of 90 kHz ticks is converted to ticks.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Rounding {
+    /// Rounds towards zero.
+    Down,
+    /// Rounds to the nearest tick, with halfway values rounded up.
+    Nearest,
+    /// Rounds away from zero.
+    Up,
+}
+
+/// Converts ticks of the program's 90 kHz clock to a `Duration` (rounded to the nearest
+/// nanosecond).
+/// ```
+/// use scte35::time::duration_from_ticks;
+/// use std::time::Duration;
+///
+/// assert_eq!(Duration::from_millis(60_293), duration_from_ticks(5426370));
+/// assert_eq!(Duration::from_nanos(11_111), duration_from_ticks(1));
+/// ```
+pub fn duration_from_ticks(ticks: u64) -> Duration {
+    let seconds = ticks / TICKS_PER_SECOND;
+    let remainder = (ticks % TICKS_PER_SECOND) as u128;
+    let nanos =
+        (remainder * NANOS_PER_SECOND + TICKS_PER_SECOND as u128 / 2) / TICKS_PER_SECOND as u128;
+    Duration::from_secs(seconds) + Duration::from_nanos(nanos as u64)
+}
+
+/// Converts a `Duration` to ticks of the program's 90 kHz clock, using `rounding` when the
+/// `Duration` is not a whole number of ticks. The result saturates at `u64::MAX`.
+/// ```
+/// use scte35::time::{ticks_from_duration, Rounding};
+/// use std::time::Duration;
+///
+/// let duration = Duration::from_micros(60_293_999);
+/// assert_eq!(5426459, ticks_from_duration(duration, Rounding::Down));
+/// assert_eq!(5426460, ticks_from_duration(duration, Rounding::Nearest));
+/// assert_eq!(5426460, ticks_from_duration(duration, Rounding::Up));
+/// ```
+pub fn ticks_from_duration(duration: Duration, rounding: Rounding) -> u64 {
+    let numerator = duration.as_nanos() * TICKS_PER_SECOND as u128;
+    let ticks = match rounding {
+        Rounding::Down => numerator / NANOS_PER_SECOND,
+        Rounding::Nearest => (numerator + NANOS_PER_SECOND / 2) / NANOS_PER_SECOND,
+        Rounding::Up => numerator.div_ceil(NANOS_PER_SECOND),
+    };
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+/// The number of seconds from the Unix epoch to the GPS epoch (00 hours UTC, January 6th, 1980),
+/// from which the `utc_splice_time` of a `SpliceSchedule` is counted.
+pub const GPS_EPOCH_UNIX_SECONDS: u64 = 315_964_800;
+
+/// Converts a `utc_splice_time` (the number of seconds since the GPS epoch, with the count of
+/// intervening leap seconds included) to a `SystemTime`. As the leap seconds are already included,
+/// no GPS_UTC_offset is applied.
+/// ```
+/// use scte35::time::system_time_from_utc_splice_time;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let system_time = system_time_from_utc_splice_time(1_000_000_000);
+/// assert_eq!(UNIX_EPOCH + Duration::from_secs(1_315_964_800), system_time);
+/// ```
+pub fn system_time_from_utc_splice_time(utc_splice_time: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(GPS_EPOCH_UNIX_SECONDS + utc_splice_time as u64)
+}
+
+/// Converts a `SystemTime` to a `utc_splice_time` (see `system_time_from_utc_splice_time`),
+/// truncating any fraction of a second. Returns `None` when the time is before the GPS epoch or
+/// too late to be represented in 32 bits (after February 2116).
+pub fn utc_splice_time_from_system_time(system_time: SystemTime) -> Option<u32> {
+    let unix_seconds = system_time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    u32::try_from(unix_seconds.checked_sub(GPS_EPOCH_UNIX_SECONDS)?).ok()
+}
+
+/// Converts a `utc_splice_time` to a `chrono::DateTime` (see `system_time_from_utc_splice_time`).
+#[cfg(feature = "chrono")]
+pub fn date_time_from_utc_splice_time(utc_splice_time: u32) -> chrono::DateTime<chrono::Utc> {
+    system_time_from_utc_splice_time(utc_splice_time).into()
+}
+
+/// Converts a `chrono::DateTime` to a `utc_splice_time` (see
+/// `utc_splice_time_from_system_time`).
+#[cfg(feature = "chrono")]
+pub fn utc_splice_time_from_date_time<Tz: chrono::TimeZone>(
+    date_time: &chrono::DateTime<Tz>,
+) -> Option<u32> {
+    let unix_seconds = u64::try_from(date_time.timestamp()).ok()?;
+    u32::try_from(unix_seconds.checked_sub(GPS_EPOCH_UNIX_SECONDS)?).ok()
+}
+
+/// A time (or duration) in terms of ticks of the program's 33-bit 90 kHz clock.
+///
+/// The clock wraps around every 2^33 ticks (roughly 26.5 hours), so the arithmetic and comparison
+/// methods on `Pts90k` work modulo 2^33. Values above the 33-bit range can be held (as they can be
+/// parsed from other sources), but are rejected when encoded.
+/// ```
+/// use scte35::time::Pts90k;
+/// use std::cmp::Ordering;
+///
+/// let pts_time = Pts90k(0x1_FFFF_FF00);
+/// let later = pts_time + Pts90k(0x200);
+/// assert_eq!(Pts90k(0x100), later);
+/// assert_eq!(Ordering::Less, pts_time.wrapping_cmp(later));
+/// assert_eq!(0x200, pts_time.distance_to(later));
+/// assert_eq!(-0x200, later.signed_distance_to(pts_time));
+/// ```
+///
+/// `Ord` compares the number of ticks without taking a wrap of the clock into account (see
+/// `wrapping_cmp` for that).
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Pts90k(pub u64);
+
+// Generates values within the 33-bit range so that arbitrary times can be encoded.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Pts90k {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.int_in_range(0..=Self::MAX.0).map(Self)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u64::size_hint(depth)
+    }
+}
+
+impl Pts90k {
+    /// The number of ticks after which the 33-bit clock wraps around to zero.
+    pub const WRAP: u64 = 1 << 33;
+    /// The largest value that can be represented by the 33-bit clock.
+    pub const MAX: Pts90k = Pts90k(Self::WRAP - 1);
+
+    /// The number of ticks.
+    pub fn ticks(self) -> u64 {
+        self.0
+    }
+
+    /// Adds `other` (e.g. a `pts_adjustment`), wrapping around at 2^33.
+    pub fn wrapping_add(self, other: Pts90k) -> Pts90k {
+        Pts90k(self.0.wrapping_add(other.0) % Self::WRAP)
+    }
+
+    /// Subtracts `other`, wrapping around at 2^33.
+    pub fn wrapping_sub(self, other: Pts90k) -> Pts90k {
+        Pts90k(self.0.wrapping_sub(other.0) % Self::WRAP)
+    }
+
+    /// The number of ticks from `self` forward to `other`, assuming that `other` is not before
+    /// `self` (so a wrap of the clock may have occurred in between).
+    pub fn distance_to(self, other: Pts90k) -> u64 {
+        other.wrapping_sub(self).0
+    }
+
+    /// The shortest number of ticks from `self` to `other`, which is negative when `other` is
+    /// before `self`. Times that are more than half of the clock range apart are assumed to be
+    /// either side of a wrap of the clock.
+    pub fn signed_distance_to(self, other: Pts90k) -> i64 {
+        let distance = self.distance_to(other);
+        if distance > Self::WRAP / 2 {
+            distance as i64 - Self::WRAP as i64
+        } else {
+            distance as i64
+        }
+    }
+
+    /// Compares with `other`, taking a wrap of the clock into account (see `signed_distance_to`).
+    ///
+    /// This is not a total order (a time is both before and after the time half of the clock range
+    /// away), so it is not used to implement `Ord`.
+    pub fn wrapping_cmp(self, other: Pts90k) -> std::cmp::Ordering {
+        0.cmp(&self.signed_distance_to(other))
+    }
+
+    /// The number of ticks as a `Duration`.
+    pub fn as_duration(self) -> Duration {
+        duration_from_ticks(self.0)
+    }
+
+    /// Creates a `Pts90k` from a `Duration`, using `rounding` when the `Duration` is not a whole
+    /// number of ticks.
+    pub fn from_duration(duration: Duration, rounding: Rounding) -> Self {
+        Pts90k(ticks_from_duration(duration, rounding))
+    }
+}
+
+impl std::ops::Add for Pts90k {
+    type Output = Pts90k;
+
+    /// Adds, wrapping around at 2^33 (see `wrapping_add`).
+    fn add(self, other: Pts90k) -> Pts90k {
+        self.wrapping_add(other)
+    }
+}
+
+impl std::ops::Sub for Pts90k {
+    type Output = Pts90k;
+
+    /// Subtracts, wrapping around at 2^33 (see `wrapping_sub`).
+    fn sub(self, other: Pts90k) -> Pts90k {
+        self.wrapping_sub(other)
+    }
+}
+
+impl From<u64> for Pts90k {
+    fn from(ticks: u64) -> Self {
+        Pts90k(ticks)
+    }
+}
+
+impl From<Pts90k> for u64 {
+    fn from(pts: Pts90k) -> Self {
+        pts.0
+    }
+}
+
+impl std::str::FromStr for Pts90k {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Pts90k)
+    }
+}
+
+impl std::fmt::Display for Pts90k {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// The `BreakDuration` structure specifies the duration of the commercial break(s). It may
 /// be used to give the splicer an indication of when the break will be over and when the
@@ -12,7 +260,11 @@ break_duration() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+///
+/// `BreakDuration`s are ordered by their `duration`, and then by `auto_return`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BreakDuration {
     /// A flag that, when set to `true`, denotes that the `duration` shall be used by the splicing
     /// device to know when the return to the network feed (end of break) is to take place. A
@@ -23,20 +275,56 @@ pub struct BreakDuration {
     /// in the event that a `SpliceInsert` command is lost at the end of a break.
     pub auto_return: bool,
     /// A 33-bit field that indicates elapsed time in terms of ticks of the program's 90 kHz clock.
-    pub duration: u64,
+    pub duration: Pts90k,
+}
+
+impl PartialOrd for BreakDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BreakDuration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.duration
+            .cmp(&other.duration)
+            .then(self.auto_return.cmp(&other.auto_return))
+    }
 }
 
 impl BreakDuration {
+    /// Creates a `BreakDuration` from a `Duration`, using `rounding` when the `Duration` is not a
+    /// whole number of 90 kHz ticks.
+    pub fn from_duration(duration: Duration, rounding: Rounding, auto_return: bool) -> Self {
+        Self {
+            auto_return,
+            duration: Pts90k::from_duration(duration, rounding),
+        }
+    }
+
+    /// The `duration` as a `Duration`.
+    pub fn as_duration(&self) -> Duration {
+        self.duration.as_duration()
+    }
+
     pub fn try_from(bits: &mut Bits) -> Result<BreakDuration, ParseError> {
         bits.validate(40, "BreakDuration")?;
-        let auto_return = bits.bool();
-        bits.consume(6);
-        let duration = bits.u64(33);
+        let auto_return = bits.bool("auto_return")?;
+        bits.consume(6)?;
+        let duration = Pts90k(bits.u64("duration", 33)?);
         Ok(Self {
             auto_return,
             duration,
         })
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        bits.validate(33, self.duration.0, "BreakDuration duration")?;
+        bits.bool(self.auto_return);
+        bits.reserved(6);
+        bits.u64(33, self.duration.0);
+        Ok(())
+    }
 }
 
 /// The `SpliceTime` structure, when modified by `pts_adjustment`, specifies the time of the splice
@@ -53,28 +341,77 @@ splice_time() {
 }
 ```
 */
-#[derive(PartialEq, Eq, Debug)]
+///
+/// `SpliceTime`s are ordered by their `pts_time`, with an unspecified time (i.e. an immediate
+/// splice) ordered first.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SpliceTime {
     /// A 33-bit field that indicates time in terms of ticks of the program's 90 kHz clock. This
     /// field, when modified by `pts_adjustment`, represents the time of the intended splice point.
-    pub pts_time: Option<u64>,
+    pub pts_time: Option<Pts90k>,
 }
 
 impl SpliceTime {
+    /// Creates a `SpliceTime` from a `Duration` since the 90 kHz clock was at zero, using
+    /// `rounding` when the `Duration` is not a whole number of ticks.
+    pub fn from_duration(duration: Duration, rounding: Rounding) -> Self {
+        Self {
+            pts_time: Some(Pts90k::from_duration(duration, rounding)),
+        }
+    }
+
+    /// The `pts_time` as a `Duration` since the 90 kHz clock was at zero.
+    pub fn as_duration(&self) -> Option<Duration> {
+        self.pts_time.map(Pts90k::as_duration)
+    }
+
     pub fn try_from(bits: &mut Bits) -> Result<Self, ParseError> {
         bits.validate(1, "SpliceTime; reading timeSpecifiedFlag")?;
-        let time_specified_flag = bits.bool();
+        let time_specified_flag = bits.bool("time_specified_flag")?;
         if time_specified_flag {
             bits.validate(39, "SpliceTime; timeSpecifiedFlag == 1")?;
-            bits.consume(6);
-            let pts_time = bits.u64(33);
+            bits.consume(6)?;
+            let pts_time = Pts90k(bits.u64("pts_time", 33)?);
             Ok(Self {
                 pts_time: Some(pts_time),
             })
         } else {
             bits.validate(7, "SpliceTime; timeSpecifiedFlag == 0")?;
-            bits.consume(7);
+            bits.consume(7)?;
             Ok(Self { pts_time: None })
         }
     }
+
+    pub(crate) fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+        match self.pts_time {
+            Some(pts_time) => {
+                bits.validate(33, pts_time.0, "SpliceTime pts_time")?;
+                bits.bool(true);
+                bits.reserved(6);
+                bits.u64(33, pts_time.0);
+            }
+            None => {
+                bits.bool(false);
+                bits.reserved(7);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The time at which a component is spliced, as resolved from the `splice_time` of a
+/// `SpliceInsert` in Component Splice Mode (see `ScheduledEvent::component_splice_times`), or
+/// from the `pts_offset` of a `ComponentSegmentation` (see
+/// `SegmentationDescriptor::component_splice_times`).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct ComponentSpliceTime {
+    pub component_tag: u8,
+    /// The splice time with the `pts_adjustment` (and any `pts_offset`) added, which is relative
+    /// to the PTS at which the message arrived when the component is spliced immediately.
+    pub pts: Pts90k,
+    /// Whether the component is spliced immediately (i.e. at the nearest opportunity to the
+    /// `pts`), as there is no `pts_time` for it.
+    pub is_immediate: bool,
 }
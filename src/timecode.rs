@@ -0,0 +1,202 @@
+//! Conversion of 90 kHz splice times to frame indices and SMPTE timecodes, for displaying cue
+//! positions in broadcast QC tools.
+//!
+//! Frame indices are counted from a PTS of `0`, so the timecode of a splice point is the time of
+//! day of the 33-bit clock (which wraps around roughly every 26.5 hours) rather than any timecode
+//! carried in the video itself.
+//! ```
+//! use scte35::{
+//!     splice_info_section::SpliceInfoSection,
+//!     time::Rounding,
+//!     timecode::FrameRate,
+//! };
+//!
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let pts = splice_info_section.adjusted_splice_time().unwrap();
+//! let frame_rate = FrameRate::FPS_29_97_DROP_FRAME;
+//! let frame_index = frame_rate.frame_index(pts, Rounding::Nearest);
+//! assert_eq!(641022, frame_index);
+//! assert_eq!("05:56:28;24", frame_rate.timecode(frame_index).to_string());
+//! ```
+
+use crate::time::{Pts90k, Rounding, TICKS_PER_SECOND};
+use std::fmt::{Display, Formatter};
+
+/// The rate at which video frames are presented, expressed as a fraction of frames per second.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "FrameRateRepr"))]
+pub struct FrameRate {
+    numerator: u32,
+    denominator: u32,
+    is_drop_frame: bool,
+}
+
+impl FrameRate {
+    pub const FPS_23_976: FrameRate = FrameRate::non_drop_frame(24000, 1001);
+    pub const FPS_24: FrameRate = FrameRate::non_drop_frame(24, 1);
+    pub const FPS_25: FrameRate = FrameRate::non_drop_frame(25, 1);
+    /// 29.97 fps, counted with non-drop-frame timecode (which drifts from the wall clock).
+    pub const FPS_29_97: FrameRate = FrameRate::non_drop_frame(30000, 1001);
+    /// 29.97 fps, counted with drop-frame timecode (which skips frame numbers 0 and 1 at the
+    /// start of each minute, except for every tenth minute).
+    pub const FPS_29_97_DROP_FRAME: FrameRate = FrameRate {
+        numerator: 30000,
+        denominator: 1001,
+        is_drop_frame: true,
+    };
+    pub const FPS_30: FrameRate = FrameRate::non_drop_frame(30, 1);
+    pub const FPS_50: FrameRate = FrameRate::non_drop_frame(50, 1);
+    /// 59.94 fps, counted with non-drop-frame timecode (which drifts from the wall clock).
+    pub const FPS_59_94: FrameRate = FrameRate::non_drop_frame(60000, 1001);
+    /// 59.94 fps, counted with drop-frame timecode (which skips frame numbers 0 to 3 at the start
+    /// of each minute, except for every tenth minute).
+    pub const FPS_59_94_DROP_FRAME: FrameRate = FrameRate {
+        numerator: 60000,
+        denominator: 1001,
+        is_drop_frame: true,
+    };
+    pub const FPS_60: FrameRate = FrameRate::non_drop_frame(60, 1);
+
+    /// Creates a `FrameRate` of `numerator / denominator` frames per second, counted with
+    /// non-drop-frame timecode. Returns `None` when either value is `0`.
+    pub fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if numerator == 0 || denominator == 0 {
+            None
+        } else {
+            Some(Self::non_drop_frame(numerator, denominator))
+        }
+    }
+
+    const fn non_drop_frame(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+            is_drop_frame: false,
+        }
+    }
+
+    pub fn numerator(&self) -> u32 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u32 {
+        self.denominator
+    }
+
+    /// Whether timecodes are counted with drop-frame timecode.
+    pub fn is_drop_frame(&self) -> bool {
+        self.is_drop_frame
+    }
+
+    /// The number of frames counted in each second of timecode (e.g. 30 for 29.97 fps).
+    pub fn timecode_base(&self) -> u64 {
+        (self.numerator as u64).div_ceil(self.denominator as u64)
+    }
+
+    /// The index of the frame presented at `pts`, counted from a PTS of `0`, using `rounding` when
+    /// `pts` does not fall exactly on a frame boundary.
+    pub fn frame_index(&self, pts: Pts90k, rounding: Rounding) -> u64 {
+        let numerator = pts.ticks() as u128 * self.numerator as u128;
+        let denominator = TICKS_PER_SECOND as u128 * self.denominator as u128;
+        let frame_index = match rounding {
+            Rounding::Down => numerator / denominator,
+            Rounding::Nearest => (numerator + denominator / 2) / denominator,
+            Rounding::Up => numerator.div_ceil(denominator),
+        };
+        u64::try_from(frame_index).unwrap_or(u64::MAX)
+    }
+
+    /// The PTS of the frame at `frame_index` (rounded to the nearest tick), counted from a PTS of
+    /// `0` and wrapped to 33 bits.
+    pub fn pts(&self, frame_index: u64) -> Pts90k {
+        let numerator = frame_index as u128 * TICKS_PER_SECOND as u128 * self.denominator as u128;
+        let denominator = self.numerator as u128;
+        let ticks = (numerator + denominator / 2) / denominator;
+        Pts90k((ticks % Pts90k::WRAP as u128) as u64)
+    }
+
+    /// The timecode of the frame at `frame_index`, with the hours wrapping around at 24.
+    pub fn timecode(&self, frame_index: u64) -> Timecode {
+        let timecode_base = self.timecode_base();
+        let mut frame_number = frame_index;
+        if self.is_drop_frame {
+            // Frame numbers are dropped at the start of each minute, except for every tenth.
+            let dropped_per_minute = timecode_base / 15;
+            let frames_per_minute = timecode_base * 60 - dropped_per_minute;
+            let frames_per_ten_minutes = timecode_base * 600 - dropped_per_minute * 9;
+            let ten_minutes = frame_index / frames_per_ten_minutes;
+            let remainder = frame_index % frames_per_ten_minutes;
+            frame_number += dropped_per_minute * 9 * ten_minutes;
+            if remainder > dropped_per_minute {
+                frame_number +=
+                    dropped_per_minute * ((remainder - dropped_per_minute) / frames_per_minute);
+            }
+        }
+        let total_seconds = frame_number / timecode_base;
+        Timecode {
+            hours: ((total_seconds / 3600) % 24) as u8,
+            minutes: ((total_seconds / 60) % 60) as u8,
+            seconds: (total_seconds % 60) as u8,
+            frames: (frame_number % timecode_base) as u8,
+            is_drop_frame: self.is_drop_frame,
+        }
+    }
+
+    /// The timecode of the frame presented at `pts` (see `frame_index` and `timecode`).
+    pub fn timecode_at(&self, pts: Pts90k, rounding: Rounding) -> Timecode {
+        self.timecode(self.frame_index(pts, rounding))
+    }
+}
+
+/// The serialized form of a `FrameRate`, which is validated (via `FrameRate::new`) on
+/// deserialization so that a `0` numerator or denominator cannot be divided by.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct FrameRateRepr {
+    numerator: u32,
+    denominator: u32,
+    is_drop_frame: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<FrameRateRepr> for FrameRate {
+    type Error = &'static str;
+
+    fn try_from(value: FrameRateRepr) -> Result<Self, Self::Error> {
+        let frame_rate = FrameRate::new(value.numerator, value.denominator)
+            .ok_or("frame rate numerator and denominator must be non-zero")?;
+        Ok(FrameRate {
+            is_drop_frame: value.is_drop_frame,
+            ..frame_rate
+        })
+    }
+}
+
+/// A SMPTE timecode, which is displayed as `HH:MM:SS:FF` (or `HH:MM:SS;FF` for drop-frame
+/// timecode).
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timecode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+    /// Whether the timecode is counted with drop-frame timecode.
+    pub is_drop_frame: bool,
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.is_drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
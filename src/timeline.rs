@@ -0,0 +1,278 @@
+//! A timeline of the opportunities (e.g. ad breaks) signaled by a stream of messages, for
+//! manifest generators that need to know where each opportunity starts and how long it lasts.
+//!
+//! Each message is given to the `Timeline` with its `Arrival`, the PTS (and optionally the
+//! wall clock time) at which it was received, which is used as the start of an immediate
+//! message and to relate the PTS of each opportunity to the wall clock. Starts and ends are
+//! paired by an `EventTracker` (see the `tracker` module). The opportunities are ordered by their
+//! start, taking the `pts_adjustment` and a wrap of the 33-bit clock into account.
+//! ```
+//! use scte35::{
+//!     cue::AdBreak,
+//!     time::Pts90k,
+//!     timeline::{Arrival, OpportunityState, Timeline},
+//! };
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let (cue_out, cue_in) = AdBreak::new(1, 90_000 * 10, 90_000 * 30).build().unwrap();
+//! let wallclock = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+//! let mut timeline = Timeline::new();
+//! timeline.push(&cue_out, Arrival::new(Pts90k(90_000 * 6)).wallclock(wallclock));
+//!
+//! let opportunity = &timeline.opportunities()[0];
+//! assert_eq!(Pts90k(90_000 * 10), opportunity.start_pts);
+//! assert_eq!(Some(wallclock + Duration::from_secs(4)), opportunity.start_wallclock);
+//! assert_eq!(Some(90_000 * 30), opportunity.duration);
+//! assert_eq!(OpportunityState::Open, opportunity.state);
+//!
+//! timeline.push(&cue_in, Arrival::new(Pts90k(90_000 * 39)));
+//! assert_eq!(OpportunityState::Ended, timeline.opportunities()[0].state);
+//! ```
+use crate::{
+    splice_descriptor::segmentation_descriptor::{SegmentationTypeID, SegmentationUPID},
+    splice_info_section::SpliceInfoSection,
+    time::{duration_from_ticks, Pts90k},
+    tracker::{EventTracker, TrackedEvent, TrackedSegment},
+};
+use std::time::{Duration, SystemTime};
+
+/// When a message was received.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Arrival {
+    /// The PTS of the stream at which the message was received.
+    pub pts: Pts90k,
+    /// The wall clock time at which the message was received, if known.
+    pub wallclock: Option<SystemTime>,
+}
+
+impl Arrival {
+    pub fn new(pts: Pts90k) -> Self {
+        Self {
+            pts,
+            wallclock: None,
+        }
+    }
+
+    /// Sets the wall clock time at which the message was received.
+    pub fn wallclock(mut self, wallclock: SystemTime) -> Self {
+        self.wallclock = Some(wallclock);
+        self
+    }
+
+    /// The wall clock time at which the stream reaches the `pts`, relative to the `wallclock` of
+    /// the arrival (assuming that the stream plays in real time). Returns `None` when the
+    /// `wallclock` is not known.
+    pub fn wallclock_at(&self, pts: Pts90k) -> Option<SystemTime> {
+        let offset = self.pts.signed_distance_to(pts);
+        let offset_duration = duration_from_ticks(offset.unsigned_abs());
+        self.wallclock.and_then(|wallclock| {
+            if offset < 0 {
+                wallclock.checked_sub(offset_duration)
+            } else {
+                wallclock.checked_add(offset_duration)
+            }
+        })
+    }
+}
+
+/// Whether an `Opportunity` has ended.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OpportunityState {
+    /// The opportunity has started (or is scheduled to start) and has not been ended.
+    Open,
+    /// The opportunity was ended by the corresponding end type, and the `duration` is the time
+    /// between the start and the end.
+    Ended,
+    /// No end was received before the `segmentation_duration` elapsed (see
+    /// `TrackedEvent::MissingEnd`), so the `duration` is the planned one.
+    EndMissing,
+}
+
+/// An opportunity signaled by a segmentation start (see the `timeline` module).
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Opportunity {
+    /// The `event_id` of the segmentation descriptor.
+    pub event_id: u32,
+    /// The type that started the opportunity (e.g. `ProviderPlacementOpportunityStart`).
+    pub segmentation_type_id: SegmentationTypeID,
+    /// The PTS at which the opportunity starts (including the `pts_adjustment`), which is the
+    /// PTS of the `Arrival` for an immediate message.
+    pub start_pts: Pts90k,
+    /// The wall clock time at which the opportunity starts, when the wall clock time of the
+    /// `Arrival` of the start is known.
+    pub start_wallclock: Option<SystemTime>,
+    /// The duration of the opportunity, in terms of ticks of the 90 kHz clock. Until the
+    /// opportunity has ended this is the `segmentation_duration`, if any.
+    pub duration: Option<u64>,
+    /// The UPIDs of the start, with those within a `MID` listed individually (and without a
+    /// `NotUsed` UPID).
+    pub segmentation_upids: Vec<SegmentationUPID>,
+    /// Whether the opportunity has ended.
+    pub state: OpportunityState,
+}
+
+impl Opportunity {
+    /// The `duration` as a `Duration`.
+    pub fn duration_as_duration(&self) -> Option<Duration> {
+        self.duration.map(duration_from_ticks)
+    }
+
+    /// The PTS at which the opportunity ends, from the `start_pts` and `duration`.
+    pub fn end_pts(&self) -> Option<Pts90k> {
+        Some(self.start_pts + Pts90k(self.duration?))
+    }
+}
+
+/// The opportunities signaled by a stream of messages (see the `timeline` module).
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Timeline {
+    tracker: EventTracker,
+    opportunities: Vec<Opportunity>,
+    // The number of ticks from the first `Arrival` to the start of each of the `opportunities`,
+    // which orders them across a wrap of the clock.
+    positions: Vec<i64>,
+    // The PTS of the latest `Arrival`, and its number of ticks from the first `Arrival`.
+    latest_arrival: Option<(Pts90k, i64)>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `Timeline` that pairs starts and ends with the `tracker` (e.g. so that an
+    /// `end_tolerance` can be set).
+    pub fn with_tracker(tracker: EventTracker) -> Self {
+        Self {
+            tracker,
+            ..Self::default()
+        }
+    }
+
+    /// The opportunities, ordered by their start. Cancelled opportunities are removed.
+    pub fn opportunities(&self) -> &[Opportunity] {
+        &self.opportunities
+    }
+
+    /// Adds the opportunities started by the `splice_info_section`, and updates those that it
+    /// ends or cancels, returning the events of the `EventTracker`. Open opportunities that
+    /// should have ended before the `arrival` are first marked as `EndMissing` (see
+    /// `EventTracker::process_at`).
+    pub fn push(
+        &mut self,
+        splice_info_section: &SpliceInfoSection,
+        arrival: Arrival,
+    ) -> Vec<TrackedEvent> {
+        let arrival_position = self.arrive(arrival.pts);
+        let events = self.tracker.process_at(splice_info_section, arrival.pts);
+        for event in &events {
+            match event {
+                TrackedEvent::Started(segment) => {
+                    let start_pts = segment.start_pts.unwrap_or(arrival.pts);
+                    let offset = arrival.pts.signed_distance_to(start_pts);
+                    let start_wallclock = arrival.wallclock_at(start_pts);
+                    let opportunity = Opportunity {
+                        event_id: segment.event_id,
+                        segmentation_type_id: segment.segmentation_type_id.clone(),
+                        start_pts,
+                        start_wallclock,
+                        duration: segment.segmentation_duration,
+                        segmentation_upids: segment
+                            .segmentation_upid
+                            .iter()
+                            .filter(|upid| {
+                                !matches!(
+                                    upid,
+                                    SegmentationUPID::MID(_) | SegmentationUPID::NotUsed
+                                )
+                            })
+                            .cloned()
+                            .collect(),
+                        state: OpportunityState::Open,
+                    };
+                    let position = arrival_position + offset;
+                    let index = self.positions.partition_point(|other| *other <= position);
+                    self.opportunities.insert(index, opportunity);
+                    self.positions.insert(index, position);
+                }
+                TrackedEvent::Ended { segment, end_pts } => {
+                    let end_pts = end_pts.unwrap_or(arrival.pts);
+                    if let Some(index) = self.find_open(segment) {
+                        let opportunity = &mut self.opportunities[index];
+                        opportunity.duration = Some(opportunity.start_pts.distance_to(end_pts));
+                        opportunity.state = OpportunityState::Ended;
+                    }
+                }
+                TrackedEvent::Cancelled(segment) => {
+                    if let Some(index) = self.find_open(segment) {
+                        self.opportunities.remove(index);
+                        self.positions.remove(index);
+                    }
+                }
+                TrackedEvent::MissingEnd(segment) => self.end_missing(segment),
+                TrackedEvent::Duplicate(_) | TrackedEvent::UnmatchedEnd { .. } => (),
+            }
+        }
+        events
+    }
+
+    /// Marks each open opportunity that should have ended before `pts` as `EndMissing` (see
+    /// `EventTracker::advance_to`), returning the events of the `EventTracker`.
+    pub fn advance_to(&mut self, pts: Pts90k) -> Vec<TrackedEvent> {
+        self.arrive(pts);
+        let events = self.tracker.advance_to(pts);
+        for event in &events {
+            if let TrackedEvent::MissingEnd(segment) = event {
+                self.end_missing(segment);
+            }
+        }
+        events
+    }
+
+    /// Removes the opportunities that are no longer open and that ended before `pts`, so that
+    /// the timeline of a long-running stream does not grow without bound.
+    pub fn remove_ended_before(&mut self, pts: Pts90k) {
+        let mut index = 0;
+        while index < self.opportunities.len() {
+            let opportunity = &self.opportunities[index];
+            let has_ended = opportunity.state != OpportunityState::Open
+                && opportunity
+                    .end_pts()
+                    .is_some_and(|end_pts| end_pts.signed_distance_to(pts) > 0);
+            if has_ended {
+                self.opportunities.remove(index);
+                self.positions.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    // Records an arrival at `pts`, returning its number of ticks from the first arrival.
+    fn arrive(&mut self, pts: Pts90k) -> i64 {
+        let position = match self.latest_arrival {
+            Some((latest_pts, latest_position)) => {
+                latest_position + latest_pts.signed_distance_to(pts)
+            }
+            None => 0,
+        };
+        self.latest_arrival = Some((pts, position));
+        position
+    }
+
+    fn find_open(&self, segment: &TrackedSegment) -> Option<usize> {
+        self.opportunities.iter().position(|opportunity| {
+            opportunity.state == OpportunityState::Open
+                && opportunity.event_id == segment.event_id
+                && opportunity.segmentation_type_id == segment.segmentation_type_id
+        })
+    }
+
+    fn end_missing(&mut self, segment: &TrackedSegment) {
+        if let Some(index) = self.find_open(segment) {
+            self.opportunities[index].state = OpportunityState::EndMissing;
+        }
+    }
+}
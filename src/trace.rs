@@ -0,0 +1,88 @@
+//! The `tracing` instrumentation of parsing, when the `tracing` feature is enabled.
+//!
+//! Each parse of a `SpliceInfoSection` is in a `splice_info_section` span (at the `DEBUG` level),
+//! which records the `tier`, `splice_command_type` and `event_id` as they are parsed. Within the
+//! span, each parsed splice descriptor is a `TRACE` event, each non-fatal error is a `WARN` event,
+//! and the result of the parse is a `DEBUG` event.
+use crate::{
+    error::ParseError, splice_command::SpliceCommand, splice_descriptor::SpliceDescriptor,
+    splice_info_section::SpliceInfoSection,
+};
+use tracing::{field, span::EnteredSpan, Span};
+
+pub(crate) fn section_span(data: &[u8]) -> EnteredSpan {
+    tracing::debug_span!(
+        "splice_info_section",
+        length = data.len(),
+        tier = field::Empty,
+        splice_command_type = field::Empty,
+        event_id = field::Empty,
+    )
+    .entered()
+}
+
+pub(crate) fn record_tier(tier: u16) {
+    Span::current().record("tier", field::display(format_args!("0x{:03X}", tier)));
+}
+
+pub(crate) fn record_splice_command(splice_command: &SpliceCommand) {
+    let span = Span::current();
+    span.record(
+        "splice_command_type",
+        field::display(splice_command.command_type()),
+    );
+    if let SpliceCommand::SpliceInsert(splice_insert) = splice_command {
+        span.record("event_id", splice_insert.event_id);
+    }
+}
+
+pub(crate) fn splice_descriptor_parsed(index: usize, splice_descriptor: &SpliceDescriptor) {
+    match splice_descriptor {
+        SpliceDescriptor::SegmentationDescriptor(descriptor) => {
+            tracing::trace!(
+                index,
+                tag = splice_descriptor.tag().value(),
+                identifier = splice_descriptor.identifier(),
+                event_id = descriptor.event_id,
+                segmentation_type_id = descriptor
+                    .scheduled_event
+                    .as_ref()
+                    .map(|event| event.segmentation_type_id.value()),
+                "Parsed splice descriptor"
+            );
+        }
+        _ => tracing::trace!(
+            index,
+            tag = splice_descriptor.tag().value(),
+            identifier = splice_descriptor.identifier(),
+            "Parsed splice descriptor"
+        ),
+    }
+}
+
+pub(crate) fn non_fatal_error(error: &ParseError) {
+    tracing::warn!(error = %error, "Non-fatal error parsing SpliceInfoSection");
+}
+
+pub(crate) fn section_parsed(result: &Result<SpliceInfoSection, ParseError>) {
+    match result {
+        Ok(splice_info_section) => {
+            // A `TimeSignal` has no `event_id` of its own, so the span records that of the first
+            // segmentation descriptor (which usually identifies the event being signalled).
+            if !matches!(
+                splice_info_section.splice_command,
+                SpliceCommand::SpliceInsert(_)
+            ) {
+                if let Some(descriptor) = splice_info_section.segmentation_descriptors().next() {
+                    Span::current().record("event_id", descriptor.event_id);
+                }
+            }
+            tracing::debug!(
+                splice_descriptors = splice_info_section.splice_descriptors.len(),
+                non_fatal_errors = splice_info_section.non_fatal_errors.len(),
+                "Parsed SpliceInfoSection"
+            );
+        }
+        Err(error) => tracing::debug!(error = %error, "Failed to parse SpliceInfoSection"),
+    }
+}
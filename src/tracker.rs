@@ -0,0 +1,234 @@
+//! Tracking of the segmentation events signaled by a sequence of messages, pairing each start
+//! (e.g. `ProviderPlacementOpportunityStart`) with its end (e.g.
+//! `ProviderPlacementOpportunityEnd`), so that an application can act on high-level events
+//! (a break started or ended) instead of on individual messages.
+//!
+//! An end is paired with the open start that has the same `event_id` and the corresponding start
+//! type (see `SegmentationTypeID::corresponding_start`). The times of the events are the
+//! `splice_pts` of the messages (which include the `pts_adjustment`).
+//! ```
+//! use scte35::{
+//!     cue::AdBreak,
+//!     time::Pts90k,
+//!     tracker::{EventTracker, TrackedEvent},
+//! };
+//!
+//! let (cue_out, cue_in) = AdBreak::new(1, 90_000, 90_000 * 30).build().unwrap();
+//! let mut tracker = EventTracker::new();
+//!
+//! let events = tracker.process(&cue_out);
+//! let [TrackedEvent::Started(segment)] = events.as_slice() else {
+//!     panic!("expected start");
+//! };
+//! assert_eq!(Some(Pts90k(90_000)), segment.start_pts);
+//! assert_eq!(1, tracker.open_segments().len());
+//!
+//! // The out is re-sent before the in.
+//! let events = tracker.process(&cue_out);
+//! assert!(matches!(events.as_slice(), [TrackedEvent::Duplicate(_)]));
+//!
+//! let events = tracker.process(&cue_in);
+//! let [TrackedEvent::Ended { segment, end_pts }] = events.as_slice() else {
+//!     panic!("expected end");
+//! };
+//! assert_eq!(1, segment.event_id);
+//! assert_eq!(Some(Pts90k(90_000 * 31)), *end_pts);
+//! assert!(tracker.open_segments().is_empty());
+//! ```
+use crate::{
+    splice_descriptor::segmentation_descriptor::{SegmentationTypeID, SegmentationUPID},
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+};
+use std::collections::VecDeque;
+
+// The number of ended segments that are remembered so that a re-sent end is reported as a
+// `Duplicate` rather than as an `UnmatchedEnd`.
+const RECENTLY_ENDED_CAPACITY: usize = 32;
+
+/// A segment that has been started by a segmentation descriptor.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackedSegment {
+    /// The `event_id` of the segmentation descriptor.
+    pub event_id: u32,
+    /// The type that started the segment (e.g. `ProviderPlacementOpportunityStart`).
+    pub segmentation_type_id: SegmentationTypeID,
+    /// The time at which the segment starts, which is `None` for an immediate message.
+    pub start_pts: Option<Pts90k>,
+    /// The `segmentation_duration` of the start, in terms of ticks of the 90 kHz clock.
+    pub segmentation_duration: Option<u64>,
+    /// The `segmentation_upid` of the start.
+    pub segmentation_upid: SegmentationUPID,
+}
+
+impl TrackedSegment {
+    /// The time at which the segment is expected to end, from its `start_pts` and
+    /// `segmentation_duration`.
+    pub fn expected_end_pts(&self) -> Option<Pts90k> {
+        Some(self.start_pts? + Pts90k(self.segmentation_duration?))
+    }
+}
+
+/// A high-level event derived from the messages given to an `EventTracker`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrackedEvent {
+    /// A segment was started.
+    Started(TrackedSegment),
+    /// A started segment was ended by the corresponding end type.
+    Ended {
+        segment: TrackedSegment,
+        /// The time of the end message, which is `None` for an immediate message.
+        end_pts: Option<Pts90k>,
+    },
+    /// A started segment was cancelled (by a segmentation descriptor with the same `event_id`
+    /// that has the `segmentation_event_cancel_indicator` set).
+    Cancelled(TrackedSegment),
+    /// A start (or end) was received again for a segment that was already started (or ended).
+    /// This is usual, as messages are often repeated in case any are lost.
+    Duplicate(TrackedSegment),
+    /// A started segment was not ended before its `segmentation_duration` (plus the
+    /// `end_tolerance` of the `EventTracker`) elapsed, so is treated as having ended.
+    MissingEnd(TrackedSegment),
+    /// An end was received that does not correspond to any started segment.
+    UnmatchedEnd {
+        event_id: u32,
+        segmentation_type_id: SegmentationTypeID,
+        /// The time of the end message, which is `None` for an immediate message.
+        end_pts: Option<Pts90k>,
+    },
+}
+
+/// Pairs the segmentation starts and ends of a sequence of messages (see the `tracker` module).
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct EventTracker {
+    end_tolerance: u64,
+    open_segments: Vec<TrackedSegment>,
+    recently_ended: VecDeque<TrackedSegment>,
+}
+
+impl EventTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of ticks of the 90 kHz clock, after the expected end of a segment (from
+    /// its `segmentation_duration`), that the end may be late by before a `MissingEnd` is
+    /// reported. Defaults to `0`.
+    pub fn end_tolerance(mut self, end_tolerance: u64) -> Self {
+        self.end_tolerance = end_tolerance;
+        self
+    }
+
+    /// The segments that have been started and not yet ended, in the order that they started.
+    pub fn open_segments(&self) -> &[TrackedSegment] {
+        &self.open_segments
+    }
+
+    /// Processes the segmentation descriptors of the `splice_info_section`, in order, returning
+    /// the events that result. Any open segment that should have ended before the `splice_pts`
+    /// of the message is first reported as a `MissingEnd` (see `advance_to`).
+    pub fn process(&mut self, splice_info_section: &SpliceInfoSection) -> Vec<TrackedEvent> {
+        let mut events = match splice_info_section.splice_pts() {
+            Some(pts) => self.advance_to(pts),
+            None => vec![],
+        };
+        self.process_descriptors(splice_info_section, &mut events);
+        events
+    }
+
+    /// Processes the segmentation descriptors of the `splice_info_section` as with `process`,
+    /// except that missing ends are reported as of `pts` (e.g. the PTS at which the message was
+    /// received), rather than as of the `splice_pts` of the message. This should be preferred
+    /// when the current PTS is known, as messages are usually sent in advance of their
+    /// `splice_pts`.
+    pub fn process_at(
+        &mut self,
+        splice_info_section: &SpliceInfoSection,
+        pts: Pts90k,
+    ) -> Vec<TrackedEvent> {
+        let mut events = self.advance_to(pts);
+        self.process_descriptors(splice_info_section, &mut events);
+        events
+    }
+
+    fn process_descriptors(
+        &mut self,
+        splice_info_section: &SpliceInfoSection,
+        events: &mut Vec<TrackedEvent>,
+    ) {
+        let pts = splice_info_section.splice_pts();
+        for descriptor in splice_info_section.segmentation_descriptors() {
+            let event_id = descriptor.event_id;
+            let Some(scheduled_event) = &descriptor.scheduled_event else {
+                let (cancelled, open) = self
+                    .open_segments
+                    .drain(..)
+                    .partition(|segment| segment.event_id == event_id);
+                self.open_segments = open;
+                events.extend(cancelled.into_iter().map(TrackedEvent::Cancelled));
+                continue;
+            };
+            let segmentation_type_id = &scheduled_event.segmentation_type_id;
+            if segmentation_type_id.is_out() {
+                let segment = TrackedSegment {
+                    event_id,
+                    segmentation_type_id: segmentation_type_id.clone(),
+                    start_pts: pts,
+                    segmentation_duration: scheduled_event.segmentation_duration,
+                    segmentation_upid: scheduled_event.segmentation_upid.clone(),
+                };
+                if let Some(open) = self.find_open(event_id, segmentation_type_id) {
+                    events.push(TrackedEvent::Duplicate(self.open_segments[open].clone()));
+                } else {
+                    self.open_segments.push(segment.clone());
+                    events.push(TrackedEvent::Started(segment));
+                }
+            } else if let Some(start_type) = segmentation_type_id.corresponding_start() {
+                if let Some(open) = self.find_open(event_id, &start_type) {
+                    let segment = self.open_segments.remove(open);
+                    if self.recently_ended.len() == RECENTLY_ENDED_CAPACITY {
+                        self.recently_ended.pop_front();
+                    }
+                    self.recently_ended.push_back(segment.clone());
+                    events.push(TrackedEvent::Ended {
+                        segment,
+                        end_pts: pts,
+                    });
+                } else if let Some(segment) = self.recently_ended.iter().find(|segment| {
+                    segment.event_id == event_id && segment.segmentation_type_id == start_type
+                }) {
+                    events.push(TrackedEvent::Duplicate(segment.clone()));
+                } else {
+                    events.push(TrackedEvent::UnmatchedEnd {
+                        event_id,
+                        segmentation_type_id: segmentation_type_id.clone(),
+                        end_pts: pts,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Reports (and closes) each open segment whose expected end, plus the `end_tolerance`, is
+    /// before `pts` as a `MissingEnd`. This can be called as the stream progresses so that a
+    /// missing end is reported even when no further messages are received. Times are compared
+    /// taking a wrap of the 33-bit clock into account.
+    pub fn advance_to(&mut self, pts: Pts90k) -> Vec<TrackedEvent> {
+        let end_tolerance = self.end_tolerance;
+        let (missing, open) = self.open_segments.drain(..).partition(|segment| {
+            segment.expected_end_pts().is_some_and(|expected_end_pts| {
+                expected_end_pts.signed_distance_to(pts) > end_tolerance as i64
+            })
+        });
+        self.open_segments = open;
+        missing.into_iter().map(TrackedEvent::MissingEnd).collect()
+    }
+
+    fn find_open(&self, event_id: u32, segmentation_type_id: &SegmentationTypeID) -> Option<usize> {
+        self.open_segments.iter().position(|segment| {
+            segment.event_id == event_id && &segment.segmentation_type_id == segmentation_type_id
+        })
+    }
+}
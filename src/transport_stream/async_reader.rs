@@ -0,0 +1,89 @@
+use super::Feeder;
+use crate::{error::SectionReaderError, splice_info_section::SpliceInfoSection};
+use std::{collections::VecDeque, io::ErrorKind};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const READ_SIZE: usize = 4096;
+
+/// The async equivalent of `SpliceSectionReader`, which reads `SpliceInfoSection`s from a tokio
+/// `AsyncRead` one at a time.
+/// ```
+/// use scte35::transport_stream::{AsyncSpliceSectionReader, Packetizer};
+/// use scte35::splice_info_section::SpliceInfoSection;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+/// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+/// let stream = Packetizer::new(0x1F0, 0)
+///     .packetize(&splice_info_section)
+///     .unwrap()
+///     .concat();
+///
+/// let mut reader = AsyncSpliceSectionReader::new(stream.as_slice(), 0x1F0);
+/// assert_eq!(Some(Ok(splice_info_section)), reader.next_section().await);
+/// assert_eq!(None, reader.next_section().await);
+/// # });
+/// ```
+pub struct AsyncSpliceSectionReader<R: AsyncRead + Unpin> {
+    reader: R,
+    feeder: Feeder,
+    pending_results: VecDeque<Result<SpliceInfoSection, SectionReaderError>>,
+    is_finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncSpliceSectionReader<R> {
+    /// Creates a reader for the `SpliceInfoSection`s carried on `pid` within a transport stream
+    /// made up of 188-byte packets.
+    pub fn new(reader: R, pid: u16) -> Self {
+        Self::with_feeder(reader, Feeder::new(pid))
+    }
+
+    /// Creates a reader for a stream of concatenated `SpliceInfoSection`s (with no transport
+    /// stream packetization).
+    pub fn new_raw(reader: R) -> Self {
+        Self::with_feeder(reader, Feeder::new_raw())
+    }
+
+    fn with_feeder(reader: R, feeder: Feeder) -> Self {
+        Self {
+            reader,
+            feeder,
+            pending_results: VecDeque::new(),
+            is_finished: false,
+        }
+    }
+
+    /// Unwraps this `AsyncSpliceSectionReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Waits for the next section, returning `None` once the reader has reached end of file (or
+    /// reading has failed).
+    pub async fn next_section(&mut self) -> Option<Result<SpliceInfoSection, SectionReaderError>> {
+        let mut buffer = [0; READ_SIZE];
+        loop {
+            if let Some(result) = self.pending_results.pop_front() {
+                return Some(result);
+            }
+            if self.is_finished {
+                return None;
+            }
+            match self.reader.read(&mut buffer).await {
+                Ok(0) => {
+                    self.is_finished = true;
+                    return self.feeder.finish().map(Err);
+                }
+                Ok(length) => self
+                    .pending_results
+                    .extend(self.feeder.push(&buffer[..length])),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    // A failure to read cannot be recovered from.
+                    self.is_finished = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+    }
+}
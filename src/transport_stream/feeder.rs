@@ -0,0 +1,110 @@
+use super::{SectionExtractor, PACKET_SIZE, SYNC_BYTE};
+use crate::{
+    error::{SectionReaderError, TransportStreamError},
+    splice_info_section::SpliceInfoSection,
+};
+
+/// A push-based (sans-IO) parser that accumulates data across arbitrary chunk boundaries and
+/// returns each `SpliceInfoSection` as soon as it has been completed, so that it can be driven by
+/// any source of bytes (e.g. an async runtime, a packet capture, or a WASM host).
+///
+/// The data can either be a transport stream (see `Feeder::new`) or a stream of concatenated
+/// sections (see `Feeder::new_raw`). When the transport stream loses sync, the data up to the next
+/// sync byte is skipped and reported as an error.
+/// ```
+/// use scte35::transport_stream::Feeder;
+///
+/// let hex_string = "FC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+/// let data = (0..hex_string.len())
+///     .step_by(2)
+///     .map(|i| u8::from_str_radix(&hex_string[i..i + 2], 16).unwrap())
+///     .collect::<Vec<u8>>();
+///
+/// let mut feeder = Feeder::new_raw();
+/// assert!(feeder.push(&data[..10]).is_empty());
+/// let sections = feeder.push(&data[10..]);
+/// assert_eq!(1, sections.len());
+/// assert!(sections[0].is_ok());
+/// ```
+pub struct Feeder {
+    extractor: Option<SectionExtractor>,
+    buffer: Vec<u8>,
+}
+
+impl Feeder {
+    /// Creates a `Feeder` for the `SpliceInfoSection`s carried on `pid` within a transport stream.
+    pub fn new(pid: u16) -> Self {
+        Self {
+            extractor: Some(SectionExtractor::new(pid)),
+            buffer: vec![],
+        }
+    }
+
+    /// Creates a `Feeder` for a stream of concatenated `SpliceInfoSection`s (with no transport
+    /// stream packetization).
+    pub fn new_raw() -> Self {
+        Self {
+            extractor: None,
+            buffer: vec![],
+        }
+    }
+
+    /// Adds the next chunk of data, returning the results for any sections that have been
+    /// completed by it.
+    pub fn push(&mut self, data: &[u8]) -> Vec<Result<SpliceInfoSection, SectionReaderError>> {
+        self.buffer.extend_from_slice(data);
+        let mut results = vec![];
+        match self.extractor.as_mut() {
+            Some(extractor) => {
+                while self.buffer.len() >= PACKET_SIZE {
+                    if self.buffer[0] != SYNC_BYTE {
+                        let skipped = self.buffer[1..]
+                            .iter()
+                            .position(|byte| *byte == SYNC_BYTE)
+                            .map_or(self.buffer.len(), |position| position + 1);
+                        let sync_byte = self.buffer[0];
+                        self.buffer.drain(..skipped);
+                        results.push(Err(TransportStreamError::InvalidSyncByte(sync_byte).into()));
+                        continue;
+                    }
+                    let packet = self.buffer.drain(..PACKET_SIZE).collect::<Vec<u8>>();
+                    match extractor.push_packet(&packet) {
+                        Ok(sections) => results.extend(
+                            sections
+                                .into_iter()
+                                .map(|section| section.map_err(Into::into)),
+                        ),
+                        Err(e) => results.push(Err(e.into())),
+                    }
+                }
+            }
+            None => {
+                while self.buffer.len() >= 3 {
+                    let section_length =
+                        u16::from_be_bytes([self.buffer[1] & 0x0F, self.buffer[2]]) as usize;
+                    if self.buffer.len() < 3 + section_length {
+                        break;
+                    }
+                    let section = self.buffer.drain(..3 + section_length).collect::<Vec<u8>>();
+                    results.push(SpliceInfoSection::try_from_bytes(&section).map_err(Into::into));
+                }
+            }
+        }
+        results
+    }
+
+    /// Signals that there is no more data, returning an error if an incomplete packet (or
+    /// section) remains, which is then discarded.
+    pub fn finish(&mut self) -> Option<SectionReaderError> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let remaining = std::mem::take(&mut self.buffer);
+        match self.extractor {
+            Some(_) => Some(TransportStreamError::InvalidPacketLength(remaining.len()).into()),
+            None => SpliceInfoSection::try_from_bytes(&remaining)
+                .err()
+                .map(Into::into),
+        }
+    }
+}
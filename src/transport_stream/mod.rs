@@ -0,0 +1,121 @@
+//! Extraction of SCTE-35 messages from an MPEG-2 transport stream (see ISO/IEC 13818-1).
+//!
+//! The `SpliceInfoSection`s are carried as sections in the packets of a single PID, and so the
+//! packets of that PID need to be filtered, and the sections reassembled from the packet payloads
+//! (honouring the `payload_unit_start_indicator` and `pointer_field`), before they can be parsed.
+//! The PIDs that carry SCTE-35 messages can be discovered from the program map tables using a
+//! `ProgramScanner`, and sections can be packetized for muxing into a stream using a
+//! `Packetizer`. A `SpliceSectionReader` reads the sections from an `io::Read` one at a time,
+//! and a `Feeder` does the same for data that is pushed to it (without owning the IO). With the
+//! `tokio` feature, an `AsyncSpliceSectionReader` reads the sections from an `AsyncRead`. The
+//! media time around a message can be found from the PTS of the PES packets of the other streams
+//! of the program, using `pes_pts`.
+//!
+//! ```
+//! use scte35::{splice_command::SpliceCommand, transport_stream::SectionExtractor};
+//!
+//! // A single transport stream packet on PID 0x1F0 carrying a `time_signal`.
+//! let hex_string = "4741F01000FC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! let mut packet = (0..hex_string.len())
+//!     .step_by(2)
+//!     .map(|i| u8::from_str_radix(&hex_string[i..i + 2], 16).unwrap())
+//!     .collect::<Vec<u8>>();
+//! packet.resize(188, 0xFF);
+//!
+//! let mut extractor = SectionExtractor::new(0x1F0);
+//! let sections = extractor.push_packet(&packet).unwrap();
+//! assert_eq!(1, sections.len());
+//! let splice_info_section = sections[0].as_ref().unwrap();
+//! assert!(matches!(splice_info_section.splice_command, SpliceCommand::TimeSignal(_)));
+//! ```
+
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod feeder;
+mod packet;
+mod packetizer;
+mod pes;
+mod program;
+mod reader;
+mod section;
+
+use crate::{
+    error::{ParseError, TransportStreamError},
+    splice_info_section::SpliceInfoSection,
+};
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncSpliceSectionReader;
+pub use feeder::Feeder;
+use packet::Packet;
+pub use packetizer::Packetizer;
+pub use pes::pes_pts;
+pub use program::{find_scte35_streams, ProgramScanner, SCTE35Stream, SCTE35_STREAM_TYPE};
+pub use reader::SpliceSectionReader;
+use section::SectionAssembler;
+
+/// The size in bytes of a transport stream packet.
+pub const PACKET_SIZE: usize = 188;
+/// The value of the first byte of every transport stream packet.
+pub const SYNC_BYTE: u8 = 0x47;
+
+/// Filters the transport stream packets of a single PID and reassembles the
+/// `SpliceInfoSection`s that they carry, including those that span multiple packets.
+///
+/// Partially received sections are discarded when a continuity counter discontinuity is
+/// detected, and duplicate packets are ignored.
+pub struct SectionExtractor {
+    pid: u16,
+    assembler: SectionAssembler,
+}
+
+impl SectionExtractor {
+    /// Creates a `SectionExtractor` for the SCTE-35 messages carried on `pid`.
+    pub fn new(pid: u16) -> Self {
+        Self {
+            pid,
+            assembler: SectionAssembler::new(),
+        }
+    }
+
+    /// The PID that is being filtered.
+    pub fn pid(&self) -> u16 {
+        self.pid
+    }
+
+    /// Processes a single 188-byte transport stream packet, returning the parse result of each
+    /// section that has been completed by it (so that a section that cannot be parsed does not
+    /// prevent the others in the same packet from being returned). Packets for other PIDs are
+    /// ignored.
+    pub fn push_packet(
+        &mut self,
+        packet: &[u8],
+    ) -> Result<Vec<Result<SpliceInfoSection, ParseError>>, TransportStreamError> {
+        let packet = Packet::try_from(packet)?;
+        if packet.pid != self.pid {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .assembler
+            .push(&packet)?
+            .iter()
+            .map(|section| SpliceInfoSection::try_from_bytes(section))
+            .collect())
+    }
+}
+
+/// Extracts all of the `SpliceInfoSection`s carried on `pid` within `data`, which must be a
+/// sequence of whole 188-byte transport stream packets. The first section that cannot be parsed
+/// fails the extraction (use a `SectionExtractor` to receive the result of each section).
+pub fn extract_sections(
+    data: &[u8],
+    pid: u16,
+) -> Result<Vec<SpliceInfoSection>, TransportStreamError> {
+    let mut extractor = SectionExtractor::new(pid);
+    let mut sections = vec![];
+    for packet in data.chunks(PACKET_SIZE) {
+        for section in extractor.push_packet(packet)? {
+            sections.push(section?);
+        }
+    }
+    Ok(sections)
+}
@@ -0,0 +1,69 @@
+use super::{PACKET_SIZE, SYNC_BYTE};
+use crate::error::TransportStreamError;
+
+/// The fields of a transport stream packet header that are needed to reassemble sections.
+/**
+```text
+transport_packet() {
+  sync_byte                                 8 bslbf
+  transport_error_indicator                 1 bslbf
+  payload_unit_start_indicator              1 bslbf
+  transport_priority                        1 bslbf
+  PID                                      13 uimsbf
+  transport_scrambling_control              2 bslbf
+  adaptation_field_control                  2 bslbf
+  continuity_counter                        4 uimsbf
+  if(adaptation_field_control == '10' || adaptation_field_control == '11') {
+    adaptation_field()
+  }
+  if(adaptation_field_control == '01' || adaptation_field_control == '11') {
+    for (i = 0; i < N; i++) {
+      data_byte                             8 bslbf
+    }
+  }
+}
+```
+*/
+pub struct Packet<'a> {
+    pub transport_error_indicator: bool,
+    pub payload_unit_start_indicator: bool,
+    pub pid: u16,
+    pub continuity_counter: u8,
+    /// The data bytes of the packet, or `None` when the packet only carries an adaptation field.
+    pub payload: Option<&'a [u8]>,
+}
+
+impl<'a> Packet<'a> {
+    pub fn try_from(data: &'a [u8]) -> Result<Self, TransportStreamError> {
+        if data.len() != PACKET_SIZE {
+            return Err(TransportStreamError::InvalidPacketLength(data.len()));
+        }
+        if data[0] != SYNC_BYTE {
+            return Err(TransportStreamError::InvalidSyncByte(data[0]));
+        }
+        let adaptation_field_control = (data[3] >> 4) & 0b11;
+        let payload_start = if adaptation_field_control & 0b10 != 0 {
+            let adaptation_field_length = data[4];
+            let payload_start = 5 + adaptation_field_length as usize;
+            if payload_start > PACKET_SIZE {
+                return Err(TransportStreamError::InvalidAdaptationFieldLength(
+                    adaptation_field_length,
+                ));
+            }
+            payload_start
+        } else {
+            4
+        };
+        Ok(Self {
+            transport_error_indicator: data[1] & 0x80 != 0,
+            payload_unit_start_indicator: data[1] & 0x40 != 0,
+            pid: u16::from_be_bytes([data[1] & 0x1F, data[2]]),
+            continuity_counter: data[3] & 0x0F,
+            payload: if adaptation_field_control & 0b01 != 0 {
+                Some(&data[payload_start..])
+            } else {
+                None
+            },
+        })
+    }
+}
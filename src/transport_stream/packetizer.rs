@@ -0,0 +1,86 @@
+use super::{PACKET_SIZE, SYNC_BYTE};
+use crate::{error::EncodeError, splice_info_section::SpliceInfoSection};
+
+const HEADER_SIZE: usize = 4;
+const STUFFING_BYTE: u8 = 0xFF;
+
+/// Packetizes `SpliceInfoSection`s into 188-byte transport stream packets for a single PID,
+/// maintaining the continuity counter across calls.
+///
+/// Each section starts a new packet (with the `payload_unit_start_indicator` set and a
+/// `pointer_field` of 0), continues over as many packets as needed, and the remainder of the
+/// final packet is filled with stuffing bytes (0xFF).
+/// ```
+/// use scte35::{
+///     splice_info_section::SpliceInfoSection,
+///     transport_stream::{Packetizer, SectionExtractor},
+/// };
+///
+/// let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+/// let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+///
+/// let mut packetizer = Packetizer::new(0x1F0, 0);
+/// let packets = packetizer.packetize(&splice_info_section).unwrap();
+/// assert_eq!(1, packets.len());
+/// assert_eq!(1, packetizer.continuity_counter());
+///
+/// let mut extractor = SectionExtractor::new(0x1F0);
+/// assert_eq!(vec![Ok(splice_info_section)], extractor.push_packet(&packets[0]).unwrap());
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Packetizer {
+    pid: u16,
+    continuity_counter: u8,
+}
+
+impl Packetizer {
+    /// Creates a `Packetizer` for `pid` (13 bits), where the first packet produced will have the
+    /// given `continuity_counter` (4 bits).
+    pub fn new(pid: u16, continuity_counter: u8) -> Self {
+        Self {
+            pid: pid & 0x1FFF,
+            continuity_counter: continuity_counter & 0x0F,
+        }
+    }
+
+    /// The PID of the packets produced.
+    pub fn pid(&self) -> u16 {
+        self.pid
+    }
+
+    /// The continuity counter that will be used for the next packet produced.
+    pub fn continuity_counter(&self) -> u8 {
+        self.continuity_counter
+    }
+
+    /// Encodes the `SpliceInfoSection` and packetizes it.
+    pub fn packetize(
+        &mut self,
+        splice_info_section: &SpliceInfoSection,
+    ) -> Result<Vec<[u8; PACKET_SIZE]>, EncodeError> {
+        Ok(self.packetize_bytes(&splice_info_section.try_to_bytes()?))
+    }
+
+    /// Packetizes an already encoded section.
+    pub fn packetize_bytes(&mut self, section: &[u8]) -> Vec<[u8; PACKET_SIZE]> {
+        // The pointer_field precedes the section in the first packet.
+        let payload = [&[0], section].concat();
+        payload
+            .chunks(PACKET_SIZE - HEADER_SIZE)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let mut packet = [STUFFING_BYTE; PACKET_SIZE];
+                let [pid_high, pid_low] = self.pid.to_be_bytes();
+                let payload_unit_start_indicator = if index == 0 { 0x40 } else { 0x00 };
+                packet[0] = SYNC_BYTE;
+                packet[1] = payload_unit_start_indicator | pid_high;
+                packet[2] = pid_low;
+                // adaptation_field_control of '01' (payload only).
+                packet[3] = 0x10 | self.continuity_counter;
+                packet[HEADER_SIZE..HEADER_SIZE + chunk.len()].copy_from_slice(chunk);
+                self.continuity_counter = (self.continuity_counter + 1) & 0x0F;
+                packet
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+use super::packet::Packet;
+use crate::{error::TransportStreamError, time::Pts90k};
+
+const PROGRAM_STREAM_MAP: u8 = 0xBC;
+const PADDING_STREAM: u8 = 0xBE;
+const PRIVATE_STREAM_2: u8 = 0xBF;
+const ECM_STREAM: u8 = 0xF0;
+const EMM_STREAM: u8 = 0xF1;
+const DSMCC_STREAM: u8 = 0xF2;
+const H222_1_TYPE_E_STREAM: u8 = 0xF8;
+const PROGRAM_STREAM_DIRECTORY: u8 = 0xFF;
+
+/// The presentation time stamp (PTS) of the PES packet that starts in the 188-byte transport
+/// stream `packet`, which gives the media time of the stream at that point (e.g. for relating the
+/// `splice_time` of a message to the position at which it was carried).
+///
+/// Returns `None` when no PES packet starts in the `packet`, or when the PES packet header does
+/// not include a PTS. An error is only returned when the `packet` itself is invalid.
+/// ```
+/// use scte35::{time::Pts90k, transport_stream::pes_pts};
+///
+/// let mut packet = vec![
+///     0x47, 0x41, 0x00, 0x10, // PID 0x100, payload_unit_start_indicator
+///     0x00, 0x00, 0x01, 0xE0, 0x00, 0x00, // video stream, unbounded length
+///     0x80, 0x80, 0x05, // PTS only, 5 bytes of header data
+///     0x21, 0x00, 0x01, 0x78, 0x61, // PTS of 15408
+/// ];
+/// packet.resize(188, 0xFF);
+/// assert_eq!(Ok(Some(Pts90k(15408))), pes_pts(&packet));
+/// ```
+pub fn pes_pts(packet: &[u8]) -> Result<Option<Pts90k>, TransportStreamError> {
+    let packet = Packet::try_from(packet)?;
+    if !packet.payload_unit_start_indicator {
+        return Ok(None);
+    }
+    let Some(payload) = packet.payload else {
+        return Ok(None);
+    };
+    let Some(header) = payload.get(..14) else {
+        return Ok(None);
+    };
+    if header[..3] != [0x00, 0x00, 0x01] {
+        return Ok(None);
+    }
+    // These streams do not have the optional PES header that carries the PTS.
+    if matches!(
+        header[3],
+        PROGRAM_STREAM_MAP
+            | PADDING_STREAM
+            | PRIVATE_STREAM_2
+            | ECM_STREAM
+            | EMM_STREAM
+            | DSMCC_STREAM
+            | H222_1_TYPE_E_STREAM
+            | PROGRAM_STREAM_DIRECTORY
+    ) {
+        return Ok(None);
+    }
+    let has_pts = header[6] & 0xC0 == 0x80 && header[7] & 0x80 != 0;
+    if !has_pts {
+        return Ok(None);
+    }
+    let pts = ((header[9] as u64 >> 1) & 0x07) << 30
+        | (header[10] as u64) << 22
+        | (header[11] as u64 >> 1) << 15
+        | (header[12] as u64) << 7
+        | header[13] as u64 >> 1;
+    Ok(Some(Pts90k(pts)))
+}
@@ -0,0 +1,242 @@
+use super::{packet::Packet, section::SectionAssembler};
+use crate::{crc::crc_32_mpeg_2, error::TransportStreamError};
+
+/// The `stream_type` assigned to SCTE-35 splice information in the program map table.
+pub const SCTE35_STREAM_TYPE: u8 = 0x86;
+
+const PAT_PID: u16 = 0x0000;
+const PAT_TABLE_ID: u8 = 0x00;
+const PMT_TABLE_ID: u8 = 0x02;
+const REGISTRATION_DESCRIPTOR_TAG: u8 = 0x05;
+const CUEI_FORMAT_IDENTIFIER: &[u8; 4] = b"CUEI";
+
+/// An elementary stream that carries SCTE-35 messages, as discovered from the program map table
+/// of its program.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SCTE35Stream {
+    /// The `program_number` of the program that the stream belongs to.
+    pub program_number: u16,
+    /// The PID of the packets that carry the SCTE-35 messages.
+    pub pid: u16,
+    /// Whether the program (or the elementary stream) includes a registration descriptor with the
+    /// `CUEI` format identifier, as required by SCTE-35 for the `stream_type` to be interpreted
+    /// as SCTE-35.
+    pub has_cuei_registration: bool,
+}
+
+/// Scans the program association table (PAT) and program map tables (PMT) of a transport stream
+/// to discover the PIDs that carry SCTE-35 messages (those with a `stream_type` of 0x86).
+///
+/// The tables are continuously tracked, so that streams added by a later version of a PMT are
+/// also discovered.
+pub struct ProgramScanner {
+    pat_assembler: SectionAssembler,
+    program_maps: Vec<ProgramMap>,
+    streams: Vec<SCTE35Stream>,
+}
+
+struct ProgramMap {
+    program_number: u16,
+    pid: u16,
+    assembler: SectionAssembler,
+}
+
+impl ProgramScanner {
+    pub fn new() -> Self {
+        Self {
+            pat_assembler: SectionAssembler::new(),
+            program_maps: vec![],
+            streams: vec![],
+        }
+    }
+
+    /// The SCTE-35 streams discovered so far.
+    pub fn streams(&self) -> &[SCTE35Stream] {
+        &self.streams
+    }
+
+    /// Processes a single 188-byte transport stream packet, returning any SCTE-35 streams that
+    /// have been newly discovered by it.
+    pub fn push_packet(
+        &mut self,
+        packet: &[u8],
+    ) -> Result<Vec<SCTE35Stream>, TransportStreamError> {
+        let packet = Packet::try_from(packet)?;
+        if packet.pid == PAT_PID {
+            for section in self.pat_assembler.push(&packet)? {
+                self.update_program_maps(&section)?;
+            }
+            return Ok(vec![]);
+        }
+        let Some(index) = self
+            .program_maps
+            .iter()
+            .position(|program_map| program_map.pid == packet.pid)
+        else {
+            return Ok(vec![]);
+        };
+        let mut discovered_streams = vec![];
+        for section in self.program_maps[index].assembler.push(&packet)? {
+            let program_number = self.program_maps[index].program_number;
+            let Some(streams) = try_scte35_streams_from(&section, program_number)? else {
+                continue;
+            };
+            let previous_streams = self
+                .streams
+                .iter()
+                .filter(|stream| stream.program_number == program_number)
+                .cloned()
+                .collect::<Vec<_>>();
+            self.streams
+                .retain(|stream| stream.program_number != program_number);
+            for stream in streams {
+                if !previous_streams.contains(&stream) {
+                    discovered_streams.push(stream.clone());
+                }
+                self.streams.push(stream);
+            }
+        }
+        Ok(discovered_streams)
+    }
+
+    fn update_program_maps(&mut self, section: &[u8]) -> Result<(), TransportStreamError> {
+        let Some(programs) = try_table_data_from(section, PAT_TABLE_ID)? else {
+            return Ok(());
+        };
+        let programs = programs
+            .chunks_exact(4)
+            .map(|program| {
+                (
+                    u16::from_be_bytes([program[0], program[1]]),
+                    u16::from_be_bytes([program[2] & 0x1F, program[3]]),
+                )
+            })
+            // A `program_number` of 0 refers to the network PID rather than a program map PID.
+            .filter(|(program_number, _)| *program_number != 0)
+            .collect::<Vec<_>>();
+        self.program_maps.retain(|program_map| {
+            programs.contains(&(program_map.program_number, program_map.pid))
+        });
+        self.streams.retain(|stream| {
+            programs
+                .iter()
+                .any(|(program_number, _)| *program_number == stream.program_number)
+        });
+        for (program_number, pid) in programs {
+            if !self
+                .program_maps
+                .iter()
+                .any(|program_map| program_map.program_number == program_number)
+            {
+                self.program_maps.push(ProgramMap {
+                    program_number,
+                    pid,
+                    assembler: SectionAssembler::new(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ProgramScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Discovers the SCTE-35 streams declared within `data`, which must be a sequence of whole
+/// 188-byte transport stream packets.
+pub fn find_scte35_streams(data: &[u8]) -> Result<Vec<SCTE35Stream>, TransportStreamError> {
+    let mut scanner = ProgramScanner::new();
+    for packet in data.chunks(super::PACKET_SIZE) {
+        scanner.push_packet(packet)?;
+    }
+    Ok(scanner.streams)
+}
+
+/// Parses the SCTE-35 streams from a program map section, returning `None` if the section is not
+/// a currently applicable program map section for the program.
+fn try_scte35_streams_from(
+    section: &[u8],
+    program_number: u16,
+) -> Result<Option<Vec<SCTE35Stream>>, TransportStreamError> {
+    let malformed = TransportStreamError::MalformedProgramSection {
+        table_id: PMT_TABLE_ID,
+    };
+    let Some(data) = try_table_data_from(section, PMT_TABLE_ID)? else {
+        return Ok(None);
+    };
+    if u16::from_be_bytes([section[3], section[4]]) != program_number {
+        return Ok(None);
+    }
+    if data.len() < 4 {
+        return Err(malformed);
+    }
+    let program_info_length = u16::from_be_bytes([data[2] & 0x0F, data[3]]) as usize;
+    let (program_info, mut elementary_streams) = data[4..]
+        .split_at_checked(program_info_length)
+        .ok_or(malformed.clone())?;
+    let has_program_registration = has_cuei_registration(program_info);
+    let mut streams = vec![];
+    while !elementary_streams.is_empty() {
+        if elementary_streams.len() < 5 {
+            return Err(malformed);
+        }
+        let stream_type = elementary_streams[0];
+        let pid = u16::from_be_bytes([elementary_streams[1] & 0x1F, elementary_streams[2]]);
+        let es_info_length =
+            u16::from_be_bytes([elementary_streams[3] & 0x0F, elementary_streams[4]]) as usize;
+        let (es_info, remaining) = elementary_streams[5..]
+            .split_at_checked(es_info_length)
+            .ok_or(malformed.clone())?;
+        if stream_type == SCTE35_STREAM_TYPE {
+            streams.push(SCTE35Stream {
+                program_number,
+                pid,
+                has_cuei_registration: has_program_registration || has_cuei_registration(es_info),
+            });
+        }
+        elementary_streams = remaining;
+    }
+    Ok(Some(streams))
+}
+
+/// Validates the long-form section and returns the data following the
+/// `last_section_number` (up to the `CRC_32`), or `None` if the section is not for the expected
+/// table or is not yet applicable.
+fn try_table_data_from(
+    section: &[u8],
+    table_id: u8,
+) -> Result<Option<&[u8]>, TransportStreamError> {
+    if section.first() != Some(&table_id) {
+        return Ok(None);
+    }
+    if section.len() < 12 {
+        return Err(TransportStreamError::MalformedProgramSection { table_id });
+    }
+    if crc_32_mpeg_2(section) != 0 {
+        return Err(TransportStreamError::InvalidProgramSectionCRC { table_id });
+    }
+    let current_next_indicator = section[5] & 0x01 != 0;
+    if !current_next_indicator {
+        return Ok(None);
+    }
+    Ok(Some(&section[8..section.len() - 4]))
+}
+
+fn has_cuei_registration(mut descriptors: &[u8]) -> bool {
+    while descriptors.len() >= 2 {
+        let tag = descriptors[0];
+        let length = descriptors[1] as usize;
+        let Some(descriptor) = descriptors[2..].get(..length) else {
+            return false;
+        };
+        if tag == REGISTRATION_DESCRIPTOR_TAG && descriptor.starts_with(CUEI_FORMAT_IDENTIFIER) {
+            return true;
+        }
+        descriptors = &descriptors[2 + length..];
+    }
+    false
+}
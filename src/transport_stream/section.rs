@@ -0,0 +1,85 @@
+use super::packet::Packet;
+use crate::error::TransportStreamError;
+
+/// Reassembles the (raw) sections carried in the payloads of the packets of a single PID.
+pub struct SectionAssembler {
+    buffer: Vec<u8>,
+    /// Whether the start of the section in the buffer has been seen. Data received before the
+    /// first `payload_unit_start_indicator` (or after a discontinuity) cannot be used.
+    is_synchronised: bool,
+    last_continuity_counter: Option<u8>,
+}
+
+impl SectionAssembler {
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![],
+            is_synchronised: false,
+            last_continuity_counter: None,
+        }
+    }
+
+    /// Adds the payload of the packet, returning any sections that have been completed.
+    pub fn push(&mut self, packet: &Packet) -> Result<Vec<Vec<u8>>, TransportStreamError> {
+        if packet.transport_error_indicator {
+            self.reset();
+            return Ok(vec![]);
+        }
+        let Some(payload) = packet.payload else {
+            // The continuity counter does not increment for packets without payload.
+            return Ok(vec![]);
+        };
+        match self.last_continuity_counter {
+            Some(last) if last == packet.continuity_counter => return Ok(vec![]), // duplicate
+            Some(last) if (last + 1) & 0x0F != packet.continuity_counter => self.reset(),
+            _ => (),
+        }
+        self.last_continuity_counter = Some(packet.continuity_counter);
+        let mut sections = vec![];
+        if packet.payload_unit_start_indicator {
+            let Some((&pointer_field, payload)) = payload.split_first() else {
+                self.reset();
+                return Ok(vec![]);
+            };
+            if pointer_field as usize > payload.len() {
+                self.reset();
+                return Err(TransportStreamError::InvalidPointerField(pointer_field));
+            }
+            let (remainder, payload) = payload.split_at(pointer_field as usize);
+            if self.is_synchronised {
+                self.buffer.extend_from_slice(remainder);
+                self.drain_sections(&mut sections);
+            }
+            self.buffer.clear();
+            self.is_synchronised = true;
+            self.buffer.extend_from_slice(payload);
+        } else if self.is_synchronised {
+            self.buffer.extend_from_slice(payload);
+        }
+        self.drain_sections(&mut sections);
+        Ok(sections)
+    }
+
+    fn drain_sections(&mut self, sections: &mut Vec<Vec<u8>>) {
+        while self.is_synchronised && self.buffer.len() >= 3 {
+            if self.buffer[0] == 0xFF {
+                // The remainder of the packet is stuffing, and so wait for the next section start.
+                self.buffer.clear();
+                self.is_synchronised = false;
+                return;
+            }
+            let section_length = u16::from_be_bytes([self.buffer[1] & 0x0F, self.buffer[2]]);
+            let total_length = 3 + section_length as usize;
+            if self.buffer.len() < total_length {
+                return;
+            }
+            sections.push(self.buffer.drain(..total_length).collect());
+        }
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.is_synchronised = false;
+        self.last_continuity_counter = None;
+    }
+}
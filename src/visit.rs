@@ -0,0 +1,203 @@
+//! A callback-based API for inspecting a `SpliceInfoSection` without building the owned model (or
+//! even the `Vec` of descriptors of a `SpliceInfoSectionRef`).
+//!
+//! `parse_with_visitor` walks the encoded section and calls the `SpliceVisitor` with the header,
+//! the splice command and then each splice descriptor in turn, each borrowing from the input
+//! buffer. Any callback can return `ControlFlow::Break` to stop the walk early, so that e.g. a
+//! router that only needs the `segmentation_type_id` of the first segmentation descriptor does not
+//! pay for the rest of the section.
+//! ```
+//! use scte35::{
+//!     splice_descriptor::segmentation_descriptor::SegmentationTypeID,
+//!     splice_info_section_ref::SpliceDescriptorRef,
+//!     visit::{parse_with_visitor, SpliceVisitor},
+//! };
+//! use std::ops::ControlFlow;
+//!
+//! #[derive(Default)]
+//! struct FirstSegmentationType(Option<SegmentationTypeID>);
+//!
+//! impl<'a> SpliceVisitor<'a> for FirstSegmentationType {
+//!     fn visit_splice_descriptor(
+//!         &mut self,
+//!         _: usize,
+//!         splice_descriptor: SpliceDescriptorRef<'a>,
+//!     ) -> ControlFlow<()> {
+//!         let Some(Ok(descriptor)) = splice_descriptor.segmentation_descriptor() else {
+//!             return ControlFlow::Continue(());
+//!         };
+//!         self.0 = descriptor
+//!             .scheduled_event
+//!             .map(|event| event.segmentation_type_id);
+//!         ControlFlow::Break(())
+//!     }
+//! }
+//!
+//! let bytes = [
+//!     0xFC, 0x30, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xF0, 0x05, 0x06, 0xFE,
+//!     0x72, 0xBD, 0x00, 0x50, 0x00, 0x1E, 0x02, 0x1C, 0x43, 0x55, 0x45, 0x49, 0x48, 0x00, 0x00,
+//!     0x8E, 0x7F, 0xCF, 0x00, 0x01, 0xA5, 0x99, 0xB0, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x2C,
+//!     0xA0, 0xA1, 0x8A, 0x34, 0x02, 0x00, 0x9A, 0xC9, 0xD1, 0x7E,
+//! ];
+//! let mut visitor = FirstSegmentationType::default();
+//! parse_with_visitor(&bytes, &mut visitor).unwrap();
+//! assert_eq!(
+//!     Some(SegmentationTypeID::ProviderPlacementOpportunityStart),
+//!     visitor.0
+//! );
+//! ```
+use crate::{
+    bit_reader::Bits,
+    error::ParseError,
+    splice_command::SpliceCommandType,
+    splice_info_section::SAPType,
+    splice_info_section_ref::{slice, SpliceCommandRef, SpliceDescriptorRef},
+    time::Pts90k,
+};
+use std::ops::ControlFlow;
+
+/// The fields of a `SpliceInfoSection` that are not part of the splice command or the splice
+/// descriptors.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct SpliceInfoSectionHeader {
+    pub table_id: u8,
+    pub sap_type: SAPType,
+    pub protocol_version: u8,
+    pub pts_adjustment: Pts90k,
+    pub tier: u16,
+    /// The `crc_32` as read from the end of the section (which is not validated).
+    pub crc_32: u32,
+}
+
+/// The callbacks made by `parse_with_visitor`, in the order that the parts appear in the section.
+///
+/// Every callback continues the walk by default, so an implementation only needs to provide the
+/// callbacks for the parts that it inspects.
+pub trait SpliceVisitor<'a> {
+    /// Called once the header of the section has been read.
+    fn visit_header(&mut self, _header: &SpliceInfoSectionHeader) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the raw bytes of the splice command.
+    fn visit_splice_command(&mut self, _splice_command: SpliceCommandRef<'a>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called with the raw bytes of each splice descriptor, along with its index in the
+    /// descriptor loop.
+    fn visit_splice_descriptor(
+        &mut self,
+        _index: usize,
+        _splice_descriptor: SpliceDescriptorRef<'a>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Walks the encoded section, calling the `visitor` for each of its parts (see the module
+/// documentation).
+///
+/// The walk performs the same validation as `SpliceInfoSectionRef::try_from_bytes` and so does not
+/// check the `crc_32`. When the section is invalid, the callbacks for the parts before the invalid
+/// part will already have been made. Nothing after a `ControlFlow::Break` is read, and so a section
+/// that is invalid after that point returns `Ok`.
+pub fn parse_with_visitor<'a>(
+    data: &'a [u8],
+    visitor: &mut impl SpliceVisitor<'a>,
+) -> Result<(), ParseError> {
+    let mut bits = Bits::new(data);
+    bits.validate(
+        24,
+        "SpliceInfoSectionRef; need at least 24 bits to get to end of section_length field",
+    )?;
+    let table_id = bits.byte("table_id")?;
+    if bits.bool("section_syntax_indicator")? {
+        return Err(ParseError::InvalidSectionSyntaxIndicator);
+    }
+    if bits.bool("private_indicator")? {
+        return Err(ParseError::InvalidPrivateIndicator);
+    }
+    let sap_type = SAPType::try_from(bits.u8("sap_type", 2)?).unwrap_or(SAPType::Unspecified);
+    let section_length = bits.u32("section_length", 12)? as usize;
+    bits.validate(
+        (section_length as u32) * 8,
+        "SpliceInfoSectionRef; not enough bytes left to read section_length",
+    )?;
+    bits.validate(
+        14 * 8 - 24,
+        "SpliceInfoSectionRef; need at least 14 bytes to get to end of splice_command_type",
+    )?;
+    let protocol_version = bits.byte("protocol_version")?;
+    if bits.bool("encrypted_packet")? {
+        return Err(ParseError::EncryptedMessageNotSupported);
+    }
+    bits.consume(6)?;
+    let pts_adjustment = Pts90k(bits.u64("pts_adjustment", 33)?);
+    let _ /* cwIndex */ = bits.byte("cw_index")?;
+    let tier = bits.u16("tier", 12)?;
+    let splice_command_length = bits.u16("splice_command_length", 12)? as usize;
+
+    let crc_bytes = slice(
+        data,
+        data.len().saturating_sub(4),
+        4,
+        "SpliceInfoSectionRef; reading crc_32",
+    )?;
+    let crc_32 = u32::from_be_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+    let header = SpliceInfoSectionHeader {
+        table_id,
+        sap_type,
+        protocol_version,
+        pts_adjustment,
+        tier,
+        crc_32,
+    };
+    if visitor.visit_header(&header).is_break() {
+        return Ok(());
+    }
+
+    let splice_command_bytes = slice(
+        data,
+        13,
+        1 + splice_command_length,
+        "SpliceInfoSectionRef; reading splice_command",
+    )?;
+    let splice_command = SpliceCommandRef {
+        splice_command_type: SpliceCommandType::try_from(splice_command_bytes[0])?,
+        bytes: &splice_command_bytes[1..],
+    };
+    if visitor.visit_splice_command(splice_command).is_break() {
+        return Ok(());
+    }
+
+    let loop_start = 14 + splice_command_length;
+    let loop_length_bytes = slice(
+        data,
+        loop_start,
+        2,
+        "SpliceInfoSectionRef; reading descriptor_loop_length",
+    )?;
+    let descriptor_loop_length =
+        u16::from_be_bytes([loop_length_bytes[0], loop_length_bytes[1]]) as usize;
+    let descriptor_loop = slice(
+        data,
+        loop_start + 2,
+        descriptor_loop_length,
+        "SpliceInfoSectionRef; reading descriptor loop",
+    )?;
+    let mut offset = 0;
+    let mut index = 0;
+    while offset < descriptor_loop.len() {
+        let descriptor = SpliceDescriptorRef::try_from_bytes(&descriptor_loop[offset..])?;
+        offset += descriptor.byte_count();
+        if visitor
+            .visit_splice_descriptor(index, descriptor)
+            .is_break()
+        {
+            return Ok(());
+        }
+        index += 1;
+    }
+    Ok(())
+}
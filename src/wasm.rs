@@ -0,0 +1,24 @@
+//! Bindings for using the parser from JavaScript when compiled to WebAssembly (e.g. with
+//! `wasm-pack build --target web --features wasm`), so that a web page can decode messages with
+//! exactly the same parser as a Rust backend.
+//!
+//! ```js
+//! import init, { parse } from "./pkg/scte35.js";
+//!
+//! await init();
+//! const section = parse("/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=");
+//! console.log(section.splice_command.SpliceInsert.splice_event_id);
+//! ```
+use crate::splice_info_section::SpliceInfoSection;
+use wasm_bindgen::prelude::*;
+
+/// Parses a base64 encoded `SpliceInfoSection`, returning it as a JavaScript object in the same
+/// form as its `serde` JSON representation (e.g. as printed by `scte35 decode --output json`).
+///
+/// Throws an `Error` with the description of the `ParseError` when the message is invalid.
+#[wasm_bindgen]
+pub fn parse(base64: &str) -> Result<JsValue, JsError> {
+    let splice_info_section = SpliceInfoSection::try_from_base64_string(base64.trim())?;
+    let json = serde_json::to_string(&splice_info_section)?;
+    js_sys::JSON::parse(&json).map_err(|_| JsError::new("the message could not be converted."))
+}
@@ -0,0 +1,222 @@
+use crate::error::XMLParseError;
+use quick_xml::{
+    escape::escape,
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::str::FromStr;
+
+/// A minimal representation of an XML element, where namespace prefixes have been dropped from
+/// element and attribute names.
+pub struct Element {
+    pub name: String,
+    attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            attributes: vec![],
+            children: vec![],
+            text: String::new(),
+        }
+    }
+
+    pub fn with_attribute<T: ToString>(mut self, attribute: &str, value: T) -> Self {
+        self.attributes
+            .push((String::from(attribute), value.to_string()));
+        self
+    }
+
+    pub fn with_optional_attribute<T: ToString>(self, attribute: &str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.with_attribute(attribute, value),
+            None => self,
+        }
+    }
+
+    pub fn with_child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Element>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text.push_str(text);
+        self
+    }
+
+    pub fn write(&self, xml: &mut String) {
+        xml.push('<');
+        xml.push_str(&self.name);
+        for (key, value) in &self.attributes {
+            xml.push(' ');
+            xml.push_str(key);
+            xml.push_str("=\"");
+            xml.push_str(&escape(value.as_str()));
+            xml.push('"');
+        }
+        if self.children.is_empty() && self.text.is_empty() {
+            xml.push_str("/>");
+            return;
+        }
+        xml.push('>');
+        xml.push_str(&escape(self.text.as_str()));
+        for child in &self.children {
+            child.write(xml);
+        }
+        xml.push_str("</");
+        xml.push_str(&self.name);
+        xml.push('>');
+    }
+
+    pub fn try_from_str(xml: &str) -> Result<Self, XMLParseError> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+        let mut stack: Vec<Element> = vec![];
+        loop {
+            match reader.read_event().map_err(syntax_error)? {
+                Event::Start(start) => stack.push(Self::try_from_start(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::try_from_start(&start)?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::End(_) => {
+                    let element = stack
+                        .pop()
+                        .ok_or_else(|| XMLParseError::Syntax(String::from("unexpected end tag")))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element
+                            .text
+                            .push_str(&text.unescape().map_err(syntax_error)?);
+                    }
+                }
+                Event::CData(cdata) => {
+                    if let Some(element) = stack.last_mut() {
+                        element
+                            .text
+                            .push_str(&String::from_utf8_lossy(&cdata.into_inner()));
+                    }
+                }
+                Event::Eof => {
+                    return Err(XMLParseError::Syntax(String::from(
+                        "unexpected end of document",
+                    )))
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn try_from_start(start: &BytesStart) -> Result<Self, XMLParseError> {
+        let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+        let mut attributes = vec![];
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(syntax_error)?;
+            if attribute.key.as_namespace_binding().is_some() {
+                continue;
+            }
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute
+                .unescape_value()
+                .map_err(syntax_error)?
+                .into_owned();
+            attributes.push((key, value));
+        }
+        Ok(Self {
+            name,
+            attributes,
+            children: vec![],
+            text: String::new(),
+        })
+    }
+
+    pub fn attribute(&self, attribute: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == attribute)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn optional<T: FromStr>(
+        &self,
+        attribute: &'static str,
+    ) -> Result<Option<T>, XMLParseError> {
+        match self.attribute(attribute) {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| self.invalid_attribute(attribute, value)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn required<T: FromStr>(&self, attribute: &'static str) -> Result<T, XMLParseError> {
+        self.optional(attribute)?
+            .ok_or_else(|| XMLParseError::MissingAttribute {
+                element: self.name.clone(),
+                attribute,
+            })
+    }
+
+    pub fn optional_bool(&self, attribute: &'static str) -> Result<Option<bool>, XMLParseError> {
+        match self.attribute(attribute).map(str::trim) {
+            Some("true") | Some("1") => Ok(Some(true)),
+            Some("false") | Some("0") => Ok(Some(false)),
+            Some(value) => Err(self.invalid_attribute(attribute, value)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn required_bool(&self, attribute: &'static str) -> Result<bool, XMLParseError> {
+        self.optional_bool(attribute)?
+            .ok_or_else(|| XMLParseError::MissingAttribute {
+                element: self.name.clone(),
+                attribute,
+            })
+    }
+
+    pub fn invalid_attribute(&self, attribute: &'static str, value: &str) -> XMLParseError {
+        XMLParseError::InvalidAttribute {
+            element: self.name.clone(),
+            attribute,
+            value: String::from(value),
+        }
+    }
+
+    pub fn invalid_content(&self) -> XMLParseError {
+        XMLParseError::InvalidContent {
+            element: self.name.clone(),
+            content: self.text.clone(),
+        }
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn syntax_error<E: std::fmt::Display>(e: E) -> XMLParseError {
+    XMLParseError::Syntax(e.to_string())
+}
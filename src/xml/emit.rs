@@ -0,0 +1,319 @@
+use super::{element::Element, SCTE35_NAMESPACE};
+use crate::{
+    error::EncodeError,
+    hex::encode_hex,
+    splice_command::{
+        private_command::PrivateCommand, splice_insert, splice_schedule, SpliceCommand,
+    },
+    splice_descriptor::{
+        audio_descriptor::{self, NumChannels},
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationUPID},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::{BreakDuration, SpliceTime},
+};
+
+impl SpliceInfoSection {
+    /// Creates the SCTE-35 XML representation of the `SpliceInfoSection`.
+    ///
+    /// The `crc_32` is not part of the XML representation. An error is returned when a value
+    /// cannot be represented (e.g. a `SegmentationUPID` that is not valid for its type, or an
+    /// encrypted message).
+    pub fn to_xml_string(&self) -> Result<String, EncodeError> {
+        if self.encrypted_packet.is_some() {
+            return Err(EncodeError::EncryptedMessageNotSupported);
+        }
+        let element = Element::new("SpliceInfoSection")
+            .with_attribute("xmlns", SCTE35_NAMESPACE)
+            .with_attribute("sapType", self.sap_type.value())
+            .with_attribute("ptsAdjustment", self.pts_adjustment)
+            .with_attribute("protocolVersion", self.protocol_version)
+            .with_attribute("tier", self.tier)
+            .with_child(splice_command_element(&self.splice_command)?)
+            .with_children(
+                self.splice_descriptors
+                    .iter()
+                    .map(splice_descriptor_element)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        let mut xml = String::new();
+        element.write(&mut xml);
+        Ok(xml)
+    }
+}
+
+// MARK: - Splice Commands
+
+fn splice_command_element(splice_command: &SpliceCommand) -> Result<Element, EncodeError> {
+    match splice_command {
+        SpliceCommand::SpliceNull => Ok(Element::new("SpliceNull")),
+        SpliceCommand::SpliceSchedule(splice_schedule) => Ok(Element::new("SpliceSchedule")
+            .with_children(
+                splice_schedule
+                    .events
+                    .iter()
+                    .map(splice_schedule_event_element),
+            )),
+        SpliceCommand::SpliceInsert(splice_insert) => Ok(splice_insert_element(splice_insert)),
+        SpliceCommand::TimeSignal(time_signal) => Ok(
+            Element::new("TimeSignal").with_child(splice_time_element(&time_signal.splice_time))
+        ),
+        SpliceCommand::BandwidthReservation => Ok(Element::new("BandwidthReservation")),
+        SpliceCommand::PrivateCommand(private_command) => private_command_element(private_command),
+    }
+}
+
+fn splice_schedule_event_element(event: &splice_schedule::Event) -> Element {
+    let element = Element::new("Event")
+        .with_attribute("spliceEventId", event.event_id)
+        .with_attribute("spliceEventCancelIndicator", event.is_cancelled());
+    let Some(scheduled_event) = &event.scheduled_event else {
+        return element;
+    };
+    let element = element
+        .with_attribute(
+            "outOfNetworkIndicator",
+            scheduled_event.out_of_network_indicator,
+        )
+        .with_attribute("uniqueProgramId", scheduled_event.unique_program_id)
+        .with_attribute("availNum", scheduled_event.avail_num)
+        .with_attribute("availsExpected", scheduled_event.avails_expected);
+    let element = match &scheduled_event.splice_mode {
+        splice_schedule::SpliceMode::ProgramSpliceMode(program) => element.with_child(
+            Element::new("Program").with_attribute("utcSpliceTime", program.utc_splice_time),
+        ),
+        splice_schedule::SpliceMode::ComponentSpliceMode(components) => {
+            element.with_children(components.iter().map(|component| {
+                Element::new("Component")
+                    .with_attribute("componentTag", component.component_tag)
+                    .with_attribute("utcSpliceTime", component.utc_splice_time)
+            }))
+        }
+    };
+    with_break_duration(element, &scheduled_event.break_duration)
+}
+
+fn splice_insert_element(splice_insert: &splice_insert::SpliceInsert) -> Element {
+    let element = Element::new("SpliceInsert")
+        .with_attribute("spliceEventId", splice_insert.event_id)
+        .with_attribute("spliceEventCancelIndicator", splice_insert.is_cancelled());
+    let Some(scheduled_event) = &splice_insert.scheduled_event else {
+        return element;
+    };
+    let element = element
+        .with_attribute(
+            "outOfNetworkIndicator",
+            scheduled_event.out_of_network_indicator,
+        )
+        .with_attribute("spliceImmediateFlag", scheduled_event.is_immediate_splice)
+        .with_attribute("uniqueProgramId", scheduled_event.unique_program_id)
+        .with_attribute("availNum", scheduled_event.avail_num)
+        .with_attribute("availsExpected", scheduled_event.avails_expected);
+    let element = match &scheduled_event.splice_mode {
+        splice_insert::SpliceMode::ProgramSpliceMode(program) => element.with_child(
+            Element::new("Program")
+                .with_children(program.splice_time.iter().map(splice_time_element)),
+        ),
+        splice_insert::SpliceMode::ComponentSpliceMode(components) => {
+            element.with_children(components.iter().map(|component| {
+                Element::new("Component")
+                    .with_attribute("componentTag", component.component_tag)
+                    .with_children(component.splice_time.iter().map(splice_time_element))
+            }))
+        }
+    };
+    with_break_duration(element, &scheduled_event.break_duration)
+}
+
+fn private_command_element(private_command: &PrivateCommand) -> Result<Element, EncodeError> {
+    let identifier_bytes: [u8; 4] =
+        private_command
+            .identifier
+            .as_bytes()
+            .try_into()
+            .map_err(|_| EncodeError::UnexpectedLength {
+                length: private_command.identifier.len(),
+                expected_length: 4,
+                description: "PrivateCommand identifier",
+            })?;
+    Ok(Element::new("PrivateCommand")
+        .with_attribute("identifier", u32::from_be_bytes(identifier_bytes))
+        .with_child(
+            Element::new("PrivateBytes")
+                .with_text(&encode_hex(&private_command.private_bytes).to_uppercase()),
+        ))
+}
+
+fn splice_time_element(splice_time: &SpliceTime) -> Element {
+    Element::new("SpliceTime").with_optional_attribute("ptsTime", splice_time.pts_time)
+}
+
+fn with_break_duration(element: Element, break_duration: &Option<BreakDuration>) -> Element {
+    match break_duration {
+        Some(break_duration) => element.with_child(
+            Element::new("BreakDuration")
+                .with_attribute("autoReturn", break_duration.auto_return)
+                .with_attribute("duration", break_duration.duration),
+        ),
+        None => element,
+    }
+}
+
+// MARK: - Splice Descriptors
+
+fn splice_descriptor_element(splice_descriptor: &SpliceDescriptor) -> Result<Element, EncodeError> {
+    match splice_descriptor {
+        SpliceDescriptor::AvailDescriptor(descriptor) => Ok(Element::new("AvailDescriptor")
+            .with_attribute("providerAvailId", descriptor.provider_avail_id)),
+        SpliceDescriptor::DTMFDescriptor(descriptor) => Ok(Element::new("DTMFDescriptor")
+            .with_attribute("preroll", descriptor.preroll)
+            .with_attribute("chars", &descriptor.dtmf_chars)),
+        SpliceDescriptor::SegmentationDescriptor(descriptor) => {
+            segmentation_descriptor_element(descriptor)
+        }
+        SpliceDescriptor::TimeDescriptor(descriptor) => Ok(Element::new("TimeDescriptor")
+            .with_attribute("taiSeconds", descriptor.tai_seconds)
+            .with_attribute("taiNs", descriptor.tai_ns)
+            .with_attribute("utcOffset", descriptor.utc_offset)),
+        SpliceDescriptor::AudioDescriptor(descriptor) => Ok(Element::new("AudioDescriptor")
+            .with_children(descriptor.components.iter().map(audio_channel_element))),
+        SpliceDescriptor::Custom(_) | SpliceDescriptor::Unknown { .. } => {
+            Err(EncodeError::CustomSpliceDescriptorNotSupported {
+                tag: splice_descriptor.tag().value(),
+                identifier: splice_descriptor.identifier(),
+            })
+        }
+    }
+}
+
+fn audio_channel_element(component: &audio_descriptor::Component) -> Element {
+    let iso_code = component.iso_code.to_be_bytes();
+    // Mirrors the interpretation of the Num_Channels MSB used by the binary parser.
+    let num_channels = match &component.num_channels {
+        NumChannels::AudioCodingMode(audio_coding_mode) => 0x08 | audio_coding_mode.value(),
+        NumChannels::MaxNumberOfEncodedChannels(max_number_of_encoded_channels) => {
+            max_number_of_encoded_channels.value()
+        }
+    };
+    Element::new("AudioChannel")
+        .with_attribute("componentTag", component.component_tag)
+        .with_attribute("ISOCode", String::from_utf8_lossy(&iso_code[1..]))
+        .with_attribute("BitStreamMode", component.bit_stream_mode.value())
+        .with_attribute("NumChannels", num_channels)
+        .with_attribute("FullSrvcAudio", component.full_srvc_audio)
+}
+
+fn segmentation_descriptor_element(
+    descriptor: &SegmentationDescriptor,
+) -> Result<Element, EncodeError> {
+    let element = Element::new("SegmentationDescriptor")
+        .with_attribute("segmentationEventId", descriptor.event_id)
+        .with_attribute(
+            "segmentationEventCancelIndicator",
+            descriptor.is_cancelled(),
+        );
+    let Some(scheduled_event) = &descriptor.scheduled_event else {
+        return Ok(element);
+    };
+    let element = element
+        .with_optional_attribute(
+            "segmentationDuration",
+            scheduled_event.segmentation_duration,
+        )
+        .with_attribute(
+            "segmentationTypeId",
+            scheduled_event.segmentation_type_id.value(),
+        )
+        .with_attribute("segmentNum", scheduled_event.segment_num)
+        .with_attribute("segmentsExpected", scheduled_event.segments_expected)
+        .with_optional_attribute(
+            "subSegmentNum",
+            scheduled_event
+                .sub_segment
+                .as_ref()
+                .map(|sub_segment| sub_segment.sub_segment_num),
+        )
+        .with_optional_attribute(
+            "subSegmentsExpected",
+            scheduled_event
+                .sub_segment
+                .as_ref()
+                .map(|sub_segment| sub_segment.sub_segments_expected),
+        );
+    let element = match &scheduled_event.delivery_restrictions {
+        Some(delivery_restrictions) => element.with_child(
+            Element::new("DeliveryRestrictions")
+                .with_attribute(
+                    "webDeliveryAllowedFlag",
+                    delivery_restrictions.web_delivery_allowed,
+                )
+                .with_attribute(
+                    "noRegionalBlackoutFlag",
+                    delivery_restrictions.no_regional_blackout,
+                )
+                .with_attribute("archiveAllowedFlag", delivery_restrictions.archive_allowed)
+                .with_attribute(
+                    "deviceRestrictions",
+                    delivery_restrictions.device_restrictions.value(),
+                ),
+        ),
+        None => element,
+    };
+    let element = match &scheduled_event.segmentation_upid {
+        SegmentationUPID::NotUsed => element,
+        // A MID is represented by multiple SegmentationUpid elements; however, a MID containing
+        // fewer than two UPIDs would not be distinguishable from a single UPID, and so it is
+        // represented as one SegmentationUPID element containing the binary MID structure.
+        SegmentationUPID::MID(upids) if upids.len() > 1 => element.with_children(
+            upids
+                .iter()
+                .map(segmentation_upid_element)
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        upid => element.with_child(segmentation_upid_element(upid)?),
+    };
+    Ok(match &scheduled_event.component_segments {
+        Some(component_segments) => {
+            element.with_children(component_segments.iter().map(|component| {
+                Element::new("Component")
+                    .with_attribute("componentTag", component.component_tag)
+                    .with_attribute("ptsOffset", component.pts_offset)
+            }))
+        }
+        None => element,
+    })
+}
+
+fn segmentation_upid_element(upid: &SegmentationUPID) -> Result<Element, EncodeError> {
+    let element = Element::new("SegmentationUpid")
+        .with_attribute("segmentationUpidType", upid.upid_type().value());
+    match upid {
+        SegmentationUPID::UserDefined(text)
+        | SegmentationUPID::ISCI(text)
+        | SegmentationUPID::AdID(text)
+        | SegmentationUPID::TID(text)
+        | SegmentationUPID::ADI(text)
+        | SegmentationUPID::ADSInformation(text)
+        | SegmentationUPID::URI(text) => {
+            // Validates the value in the same way as the binary encoding.
+            upid.raw_bytes()?;
+            Ok(element
+                .with_attribute("segmentationUpidFormat", "text")
+                .with_text(text))
+        }
+        SegmentationUPID::MPU(mpu) => {
+            let bytes = upid.raw_bytes()?;
+            Ok(element
+                .with_attribute("segmentationUpidFormat", "hexbinary")
+                .with_attribute(
+                    "formatIdentifier",
+                    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+                )
+                .with_text(&encode_hex(&mpu.private_data).to_uppercase()))
+        }
+        _ => Ok(element
+            .with_attribute("segmentationUpidFormat", "hexbinary")
+            .with_text(&encode_hex(&upid.raw_bytes()?).to_uppercase())),
+    }
+}
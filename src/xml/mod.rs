@@ -0,0 +1,42 @@
+//! Support for the XML representation of SCTE-35 messages, as defined by the SCTE-35 XML schema
+//! (namespace `http://www.scte.org/schemas/35`), which is used by ESAM and POIS integrations.
+//!
+//! Parsing yields the same `SpliceInfoSection` model as the binary parser:
+//! ```
+//! use scte35::splice_info_section::SpliceInfoSection;
+//!
+//! let xml = r#"
+//! <SpliceInfoSection xmlns="http://www.scte.org/schemas/35" ptsAdjustment="0" tier="4095">
+//!     <TimeSignal>
+//!         <SpliceTime ptsTime="1924989008"/>
+//!     </TimeSignal>
+//!     <SegmentationDescriptor segmentationEventId="1207959694" segmentationDuration="27630000" segmentationTypeId="52" segmentNum="2" segmentsExpected="0">
+//!         <DeliveryRestrictions webDeliveryAllowedFlag="false" noRegionalBlackoutFlag="true" archiveAllowedFlag="true" deviceRestrictions="3"/>
+//!         <SegmentationUpid segmentationUpidType="8" segmentationUpidFormat="hexbinary">000000002CA0A18A</SegmentationUpid>
+//!     </SegmentationDescriptor>
+//! </SpliceInfoSection>
+//! "#;
+//! let hex_string = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+//! assert_eq!(
+//!     SpliceInfoSection::try_from_hex_string(hex_string).unwrap(),
+//!     SpliceInfoSection::try_from_xml_string(xml).unwrap()
+//! );
+//! ```
+//!
+//! A `SpliceInfoSection` (e.g. one parsed from binary) can be converted into XML via
+//! `to_xml_string`:
+//! ```
+//! use scte35::splice_info_section::SpliceInfoSection;
+//!
+//! let hex_string = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+//! let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string).unwrap();
+//! let xml = splice_info_section.to_xml_string().unwrap();
+//! assert_eq!(splice_info_section, SpliceInfoSection::try_from_xml_string(&xml).unwrap());
+//! ```
+
+pub(crate) mod element;
+mod emit;
+pub(crate) mod parse;
+
+/// The namespace of the SCTE-35 XML schema.
+pub const SCTE35_NAMESPACE: &str = "http://www.scte.org/schemas/35";
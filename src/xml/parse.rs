@@ -0,0 +1,454 @@
+use super::element::Element;
+use crate::{
+    atsc::{AudioCodingMode, BitStreamMode},
+    error::{ParseError, XMLParseError},
+    hex::decode_hex,
+    splice_command::{
+        private_command::PrivateCommand, splice_insert, splice_schedule, time_signal::TimeSignal,
+        SpliceCommand,
+    },
+    splice_descriptor::{
+        audio_descriptor::{self, AudioDescriptor, MaxNumberOfEncodedChannels, NumChannels},
+        avail_descriptor::AvailDescriptor,
+        dtmf_descriptor::DTMFDescriptor,
+        segmentation_descriptor::{
+            ComponentSegmentation, DeliveryRestrictions, DeviceRestrictions, ScheduledEvent,
+            SegmentationDescriptor, SegmentationTypeID, SegmentationUPID, SegmentationUPIDType,
+            SubSegment,
+        },
+        time_descriptor::TimeDescriptor,
+        SpliceDescriptor, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{SAPType, SpliceInfoSection},
+    time::{BreakDuration, SpliceTime},
+};
+use base64::prelude::*;
+
+impl SpliceInfoSection {
+    /// Creates a `SpliceInfoSection` from the SCTE-35 XML representation.
+    ///
+    /// The root element may either be a `SpliceInfoSection` or a `Binary` element (containing the
+    /// base64 encoded binary message). As the XML representation does not carry the `crc_32`, it
+    /// is calculated by encoding the parsed message.
+    pub fn try_from_xml_string(xml: &str) -> Result<SpliceInfoSection, XMLParseError> {
+        let root = Element::try_from_str(xml)?;
+        match root.name.as_str() {
+            "SpliceInfoSection" => try_splice_info_section_from(&root),
+            "Binary" => {
+                let data = BASE64_STANDARD
+                    .decode(root.text.trim())
+                    .map_err(|_| root.invalid_content())?;
+                Ok(SpliceInfoSection::try_from_bytes(&data)?)
+            }
+            _ => Err(XMLParseError::UnexpectedRootElement(root.name)),
+        }
+    }
+}
+
+pub(crate) fn try_splice_info_section_from(
+    element: &Element,
+) -> Result<SpliceInfoSection, XMLParseError> {
+    if element.child("EncryptedPacket").is_some() {
+        return Err(ParseError::EncryptedMessageNotSupported.into());
+    }
+    let sap_type = match element.optional::<u8>("sapType")? {
+        Some(value) => SAPType::try_from(value)
+            .map_err(|_| element.invalid_attribute("sapType", &value.to_string()))?,
+        None => SAPType::Unspecified,
+    };
+    let mut splice_command = None;
+    let mut splice_descriptors = vec![];
+    for child in &element.children {
+        match child.name.as_str() {
+            "EncryptedPacket" => (),
+            "SpliceNull"
+            | "SpliceSchedule"
+            | "SpliceInsert"
+            | "TimeSignal"
+            | "BandwidthReservation"
+            | "PrivateCommand"
+                if splice_command.is_none() =>
+            {
+                splice_command = Some(try_splice_command_from(child)?)
+            }
+            "AvailDescriptor"
+            | "DTMFDescriptor"
+            | "SegmentationDescriptor"
+            | "TimeDescriptor"
+            | "AudioDescriptor" => splice_descriptors.push(try_splice_descriptor_from(child)?),
+            _ => {
+                return Err(XMLParseError::UnexpectedElement {
+                    element: child.name.clone(),
+                    parent: element.name.clone(),
+                })
+            }
+        }
+    }
+    let splice_command = splice_command.ok_or_else(|| XMLParseError::MissingElement {
+        element: "SpliceCommand",
+        parent: element.name.clone(),
+    })?;
+    let mut splice_info_section = SpliceInfoSection {
+        table_id: 0xFC,
+        sap_type,
+        protocol_version: element.optional("protocolVersion")?.unwrap_or(0),
+        encrypted_packet: None,
+        pts_adjustment: element.optional("ptsAdjustment")?.unwrap_or_default(),
+        tier: element.optional("tier")?.unwrap_or(0xFFF),
+        splice_command,
+        splice_descriptors,
+        crc_32: 0,
+        non_fatal_errors: vec![],
+    };
+    splice_info_section.canonicalize()?;
+    Ok(splice_info_section)
+}
+
+// MARK: - Splice Commands
+
+fn try_splice_command_from(element: &Element) -> Result<SpliceCommand, XMLParseError> {
+    match element.name.as_str() {
+        "SpliceNull" => Ok(SpliceCommand::SpliceNull),
+        "SpliceSchedule" => Ok(SpliceCommand::SpliceSchedule(
+            splice_schedule::SpliceSchedule {
+                events: element
+                    .children("Event")
+                    .map(try_splice_schedule_event_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        )),
+        "SpliceInsert" => Ok(SpliceCommand::SpliceInsert(try_splice_insert_from(
+            element,
+        )?)),
+        "TimeSignal" => Ok(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: try_splice_time_from(element)?.unwrap_or(SpliceTime { pts_time: None }),
+        })),
+        "BandwidthReservation" => Ok(SpliceCommand::BandwidthReservation),
+        _ => Ok(SpliceCommand::PrivateCommand(try_private_command_from(
+            element,
+        )?)),
+    }
+}
+
+fn try_splice_schedule_event_from(
+    element: &Element,
+) -> Result<splice_schedule::Event, XMLParseError> {
+    let event_id = element.required("spliceEventId")?;
+    if element
+        .optional_bool("spliceEventCancelIndicator")?
+        .unwrap_or(false)
+    {
+        return Ok(splice_schedule::Event {
+            event_id,
+            scheduled_event: None,
+        });
+    }
+    let splice_mode = match element.child("Program") {
+        Some(program) => {
+            splice_schedule::SpliceMode::ProgramSpliceMode(splice_schedule::ProgramMode {
+                utc_splice_time: program.required("utcSpliceTime")?,
+            })
+        }
+        None => splice_schedule::SpliceMode::ComponentSpliceMode(
+            element
+                .children("Component")
+                .map(|component| {
+                    Ok(splice_schedule::ComponentMode {
+                        component_tag: component.required("componentTag")?,
+                        utc_splice_time: component.required("utcSpliceTime")?,
+                    })
+                })
+                .collect::<Result<_, XMLParseError>>()?,
+        ),
+    };
+    Ok(splice_schedule::Event {
+        event_id,
+        scheduled_event: Some(splice_schedule::ScheduledEvent {
+            out_of_network_indicator: element
+                .optional_bool("outOfNetworkIndicator")?
+                .unwrap_or(false),
+            splice_mode,
+            break_duration: try_break_duration_from(element)?,
+            unique_program_id: element.optional("uniqueProgramId")?.unwrap_or(0),
+            avail_num: element.optional("availNum")?.unwrap_or(0),
+            avails_expected: element.optional("availsExpected")?.unwrap_or(0),
+        }),
+    })
+}
+
+fn try_splice_insert_from(element: &Element) -> Result<splice_insert::SpliceInsert, XMLParseError> {
+    let event_id = element.required("spliceEventId")?;
+    if element
+        .optional_bool("spliceEventCancelIndicator")?
+        .unwrap_or(false)
+    {
+        return Ok(splice_insert::SpliceInsert {
+            event_id,
+            scheduled_event: None,
+        });
+    }
+    let is_immediate_splice = element
+        .optional_bool("spliceImmediateFlag")?
+        .unwrap_or(false);
+    let splice_time = |element: &Element| -> Result<Option<SpliceTime>, XMLParseError> {
+        if is_immediate_splice {
+            Ok(None)
+        } else {
+            Ok(Some(
+                try_splice_time_from(element)?.unwrap_or(SpliceTime { pts_time: None }),
+            ))
+        }
+    };
+    let components = element.children("Component").collect::<Vec<_>>();
+    let splice_mode = if element.child("Program").is_none() && !components.is_empty() {
+        splice_insert::SpliceMode::ComponentSpliceMode(
+            components
+                .into_iter()
+                .map(|component| {
+                    Ok(splice_insert::ComponentMode {
+                        component_tag: component.required("componentTag")?,
+                        splice_time: splice_time(component)?,
+                    })
+                })
+                .collect::<Result<_, XMLParseError>>()?,
+        )
+    } else {
+        splice_insert::SpliceMode::ProgramSpliceMode(splice_insert::ProgramMode {
+            splice_time: match element.child("Program") {
+                Some(program) => splice_time(program)?,
+                None => splice_time(element)?,
+            },
+        })
+    };
+    Ok(splice_insert::SpliceInsert {
+        event_id,
+        scheduled_event: Some(splice_insert::ScheduledEvent {
+            out_of_network_indicator: element
+                .optional_bool("outOfNetworkIndicator")?
+                .unwrap_or(false),
+            is_immediate_splice,
+            splice_mode,
+            break_duration: try_break_duration_from(element)?,
+            unique_program_id: element.optional("uniqueProgramId")?.unwrap_or(0),
+            avail_num: element.optional("availNum")?.unwrap_or(0),
+            avails_expected: element.optional("availsExpected")?.unwrap_or(0),
+        }),
+    })
+}
+
+fn try_private_command_from(element: &Element) -> Result<PrivateCommand, XMLParseError> {
+    let identifier: u32 = element.required("identifier")?;
+    let identifier = String::from_utf8(identifier.to_be_bytes().to_vec())
+        .map_err(|_| element.invalid_attribute("identifier", &identifier.to_string()))?;
+    let private_bytes = match element.child("PrivateBytes") {
+        Some(private_bytes) => {
+            decode_hex(private_bytes.text.trim()).map_err(|_| private_bytes.invalid_content())?
+        }
+        None => vec![],
+    };
+    Ok(PrivateCommand {
+        identifier,
+        private_bytes,
+    })
+}
+
+/// Reads the optional `SpliceTime` child of the element.
+fn try_splice_time_from(element: &Element) -> Result<Option<SpliceTime>, XMLParseError> {
+    match element.child("SpliceTime") {
+        Some(splice_time) => Ok(Some(SpliceTime {
+            pts_time: splice_time.optional("ptsTime")?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Reads the optional `BreakDuration` child of the element.
+fn try_break_duration_from(element: &Element) -> Result<Option<BreakDuration>, XMLParseError> {
+    match element.child("BreakDuration") {
+        Some(break_duration) => Ok(Some(BreakDuration {
+            auto_return: break_duration.optional_bool("autoReturn")?.unwrap_or(false),
+            duration: break_duration.required("duration")?,
+        })),
+        None => Ok(None),
+    }
+}
+
+// MARK: - Splice Descriptors
+
+fn try_splice_descriptor_from(element: &Element) -> Result<SpliceDescriptor, XMLParseError> {
+    match element.name.as_str() {
+        "AvailDescriptor" => Ok(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            provider_avail_id: element.required("providerAvailId")?,
+        })),
+        "DTMFDescriptor" => Ok(SpliceDescriptor::DTMFDescriptor(DTMFDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            preroll: element.required("preroll")?,
+            dtmf_chars: element.attribute("chars").unwrap_or_default().to_string(),
+        })),
+        "TimeDescriptor" => Ok(SpliceDescriptor::TimeDescriptor(TimeDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            tai_seconds: element.required("taiSeconds")?,
+            tai_ns: element.required("taiNs")?,
+            utc_offset: element.required("utcOffset")?,
+        })),
+        "AudioDescriptor" => Ok(SpliceDescriptor::AudioDescriptor(AudioDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            components: element
+                .children("AudioChannel")
+                .map(try_audio_component_from)
+                .collect::<Result<_, _>>()?,
+        })),
+        _ => Ok(SpliceDescriptor::SegmentationDescriptor(
+            try_segmentation_descriptor_from(element)?,
+        )),
+    }
+}
+
+fn try_audio_component_from(
+    element: &Element,
+) -> Result<audio_descriptor::Component, XMLParseError> {
+    let iso_code_string = element.attribute("ISOCode").unwrap_or_default();
+    if iso_code_string.len() != 3 {
+        return Err(element.invalid_attribute("ISOCode", iso_code_string));
+    }
+    let iso_code = iso_code_string
+        .bytes()
+        .fold(0u32, |iso_code, byte| (iso_code << 8) | byte as u32);
+    let bsmod: u8 = element.required("BitStreamMode")?;
+    let num_channels: u8 = element.required("NumChannels")?;
+    // Mirrors the interpretation of the Num_Channels MSB used by the binary parser.
+    let (num_channels, acmod) = if num_channels & 0x08 != 0 {
+        let acmod = num_channels & 0x07;
+        (
+            NumChannels::AudioCodingMode(AudioCodingMode::try_from(acmod)?),
+            Some(acmod),
+        )
+    } else {
+        (
+            NumChannels::MaxNumberOfEncodedChannels(MaxNumberOfEncodedChannels::new(
+                num_channels & 0x07,
+            )),
+            None,
+        )
+    };
+    Ok(audio_descriptor::Component {
+        component_tag: element.optional("componentTag")?.unwrap_or(0xFF),
+        iso_code,
+        bit_stream_mode: BitStreamMode::try_from(bsmod, acmod)?,
+        num_channels,
+        full_srvc_audio: element.optional_bool("FullSrvcAudio")?.unwrap_or(false),
+    })
+}
+
+fn try_segmentation_descriptor_from(
+    element: &Element,
+) -> Result<SegmentationDescriptor, XMLParseError> {
+    let event_id = element.required("segmentationEventId")?;
+    if element
+        .optional_bool("segmentationEventCancelIndicator")?
+        .unwrap_or(false)
+    {
+        return Ok(SegmentationDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            event_id,
+            scheduled_event: None,
+        });
+    }
+    let delivery_restrictions = match element.child("DeliveryRestrictions") {
+        Some(delivery_restrictions) => {
+            let device_restrictions: u8 = delivery_restrictions.required("deviceRestrictions")?;
+            Some(DeliveryRestrictions {
+                web_delivery_allowed: delivery_restrictions
+                    .required_bool("webDeliveryAllowedFlag")?,
+                no_regional_blackout: delivery_restrictions
+                    .required_bool("noRegionalBlackoutFlag")?,
+                archive_allowed: delivery_restrictions.required_bool("archiveAllowedFlag")?,
+                device_restrictions: DeviceRestrictions::try_from(device_restrictions).map_err(
+                    |_| {
+                        delivery_restrictions.invalid_attribute(
+                            "deviceRestrictions",
+                            &device_restrictions.to_string(),
+                        )
+                    },
+                )?,
+            })
+        }
+        None => None,
+    };
+    let component_segments = element
+        .children("Component")
+        .map(|component| {
+            Ok(ComponentSegmentation {
+                component_tag: component.required("componentTag")?,
+                pts_offset: component.required("ptsOffset")?,
+            })
+        })
+        .collect::<Result<Vec<_>, XMLParseError>>()?;
+    let mut upids = element
+        .children("SegmentationUpid")
+        .map(try_segmentation_upid_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    let segmentation_upid = match upids.len() {
+        0 => SegmentationUPID::NotUsed,
+        1 => upids.remove(0),
+        _ => SegmentationUPID::MID(upids),
+    };
+    let segmentation_type_id_value: u8 = element.required("segmentationTypeId")?;
+    let segmentation_type_id = SegmentationTypeID::try_from(segmentation_type_id_value)?;
+    let sub_segment = match element.optional("subSegmentNum")? {
+        Some(sub_segment_num) if segmentation_type_id.has_sub_segments() => Some(SubSegment {
+            sub_segment_num,
+            sub_segments_expected: element.optional("subSegmentsExpected")?.unwrap_or(0),
+        }),
+        _ => None,
+    };
+    Ok(SegmentationDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        event_id,
+        scheduled_event: Some(ScheduledEvent {
+            delivery_restrictions,
+            component_segments: if component_segments.is_empty() {
+                None
+            } else {
+                Some(component_segments)
+            },
+            segmentation_duration: element.optional("segmentationDuration")?,
+            segmentation_upid,
+            segmentation_type_id,
+            segment_num: element.optional("segmentNum")?.unwrap_or(0),
+            segments_expected: element.optional("segmentsExpected")?.unwrap_or(0),
+            sub_segment,
+        }),
+    })
+}
+
+fn try_segmentation_upid_from(element: &Element) -> Result<SegmentationUPID, XMLParseError> {
+    let upid_type_value: u8 = element.required("segmentationUpidType")?;
+    let upid_type = SegmentationUPIDType::try_from(upid_type_value)?;
+    let content = element.text.trim();
+    let mut bytes = match element.attribute("segmentationUpidFormat") {
+        Some("text") => content.as_bytes().to_vec(),
+        Some("base-64") | Some("base64") => BASE64_STANDARD
+            .decode(content)
+            .map_err(|_| element.invalid_content())?,
+        Some("hexbinary") | Some("hexBinary") | None => {
+            decode_hex(content).map_err(|_| element.invalid_content())?
+        }
+        Some(format) => {
+            return Err(XMLParseError::UnsupportedSegmentationUPIDFormat(
+                String::from(format),
+            ))
+        }
+    };
+    if let Some(format_identifier) = element.optional::<u32>("formatIdentifier")? {
+        if upid_type == SegmentationUPIDType::MPU {
+            let mut mpu_bytes = format_identifier.to_be_bytes().to_vec();
+            mpu_bytes.append(&mut bytes);
+            bytes = mpu_bytes;
+        }
+    }
+    Ok(SegmentationUPID::try_from_type_and_bytes(
+        upid_type, &bytes,
+    )?)
+}
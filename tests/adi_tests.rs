@@ -0,0 +1,86 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    adi::{Adi, AdiElement},
+    error::ParseError,
+    splice_descriptor::segmentation_descriptor::SegmentationUPID,
+};
+
+// MARK: - Parsing
+
+#[test]
+fn test_adi_elements() {
+    for (string, element) in [
+        ("PREVIEW", AdiElement::Preview),
+        ("MPEG2HD", AdiElement::Mpeg2Hd),
+        ("MPEG2SD", AdiElement::Mpeg2Sd),
+        ("AVCHD", AdiElement::AvcHd),
+        ("AVCSD", AdiElement::AvcSd),
+        ("HEVCSD", AdiElement::HevcSd),
+        ("HEVCHD", AdiElement::HevcHd),
+        ("SIGNAL", AdiElement::Signal),
+        ("PO", AdiElement::PlacementOpportunity),
+        ("BLACKOUT", AdiElement::Blackout),
+        ("OTHER", AdiElement::Other),
+    ] {
+        assert_eq!(Ok(element), string.parse());
+        assert_eq!(string, element.value());
+    }
+}
+
+#[test]
+fn test_adi_content_identifier() {
+    let adi: Adi = "AVCHD : example.com/ABCD1234567890123456"
+        .parse()
+        .expect("should be valid ADI");
+    assert_eq!(
+        Adi {
+            element: AdiElement::AvcHd,
+            identifier: String::from("example.com/ABCD1234567890123456"),
+        },
+        adi
+    );
+    assert_eq!(Some("example.com"), adi.provider_id());
+    assert_eq!(Some("ABCD1234567890123456"), adi.asset_id());
+    assert_eq!("AVCHD:example.com/ABCD1234567890123456", adi.to_string());
+}
+
+#[test]
+fn test_adi_signal_has_no_content_ids() {
+    let adi: Adi = "PO:abc/def".parse().expect("should be valid ADI");
+    assert_eq!(AdiElement::PlacementOpportunity, adi.element);
+    assert_eq!(None, adi.provider_id());
+    assert_eq!(None, adi.asset_id());
+}
+
+#[test]
+fn test_invalid_adi() {
+    for string in ["SIGNAL", "SIGNAL:", "signal:abc", "UNKNOWN:abc"] {
+        assert_eq!(
+            Err(ParseError::InvalidADI(String::from(string))),
+            string.parse::<Adi>(),
+            "{}",
+            string
+        );
+    }
+}
+
+// MARK: - SegmentationUPID
+
+#[test]
+fn test_segmentation_upid_adi() {
+    assert_eq!(
+        Some(Adi {
+            element: AdiElement::Signal,
+            identifier: String::from("Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0"),
+        }),
+        SegmentationUPID::ADI(String::from("SIGNAL:Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0")).adi()
+    );
+    assert_eq!(
+        None,
+        SegmentationUPID::ADI(String::from("non-conforming")).adi()
+    );
+    assert_eq!(
+        None,
+        SegmentationUPID::URI(String::from("SIGNAL:abc")).adi()
+    );
+}
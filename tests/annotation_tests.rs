@@ -0,0 +1,150 @@
+use base64::prelude::*;
+use pretty_assertions::assert_eq;
+use scte35::{annotation::Annotation, splice_info_section::SpliceInfoSection};
+
+const TIME_SIGNAL_WITH_TI_UPID: &str =
+    "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
+
+fn decode(base64: &str) -> Vec<u8> {
+    BASE64_STANDARD
+        .decode(base64)
+        .expect("should be valid base64")
+}
+
+fn leaves(annotations: &[Annotation]) -> Vec<&Annotation> {
+    annotations
+        .iter()
+        .flat_map(Annotation::iter)
+        .filter(|annotation| annotation.children.is_empty())
+        .collect()
+}
+
+// MARK: - Hex dump
+
+#[test]
+fn test_hex_dump_time_signal_with_segmentation_descriptor() {
+    let annotated_section = SpliceInfoSection::annotate(&decode(TIME_SIGNAL_WITH_TI_UPID));
+    assert!(annotated_section.result.is_ok());
+    assert_eq!(
+        "\
+offset  width  bytes                    field
+     0      8  FC                       table_id = 252 (0xFC)
+     8      1  30                       section_syntax_indicator = 0
+     9      1  30                       private_indicator = 0
+    10      2  30                       sap_type = 3 (0x3)
+    12     12  30 34                    section_length = 52 (0x34)
+    24      8  00                       protocol_version = 0 (0x0)
+    32      1  00                       encrypted_packet = 0
+    33      6  00                       encryption_algorithm = 0 (0x0)
+    39     33  00 00 00 00 00           pts_adjustment = 0 (0x0)
+    72      8  FF                       cw_index = 255 (0xFF)
+    80     12  FF F0                    tier = 4095 (0xFFF)
+    92     12  F0 05                    splice_command_length = 5 (0x5)
+   104     48                           splice_command
+   104      8  06                         splice_command_type = 6 (0x6)
+   112     40                             splice_time
+   112      1  FE                           time_specified_flag = 1
+   113      6  FE                           reserved = 63 (0x3F)
+   119     33  FE 72 BD 00 50               pts_time = 1924989008 (0x72BD0050)
+   152     16  00 1E                    descriptor_loop_length = 30 (0x1E)
+   168    240                           splice_descriptors[0]
+   168      8  02                         splice_descriptor_tag = 2 (0x2)
+   176      8  1C                         descriptor_length = 28 (0x1C)
+   184     32  43 55 45 49                identifier = 1129661769 (0x43554549)
+   216     32  48 00 00 8E                event_id = 1207959694 (0x4800008E)
+   248      1  7F                         segmentation_event_cancel_indicator = 0
+   249      7  7F                         reserved = 127 (0x7F)
+   256      1  CF                         program_segmentation_flag = 1
+   257      1  CF                         segmentation_duration_flag = 1
+   258      1  CF                         delivery_not_restricted_flag = 0
+   259      1  CF                         web_delivery_allowed = 0
+   260      1  CF                         no_regional_blackout = 1
+   261      1  CF                         archive_allowed = 1
+   262      2  CF                         device_restrictions = 3 (0x3)
+   264     40  00 01 A5 99 B0             segmentation_duration = 27630000 (0x1A599B0)
+   304     80                             segmentation_upid
+   304      8  08                           segmentation_upid_type = 8 (0x8)
+   312      8  08                           segmentation_upid_length = 8 (0x8)
+   320     64  00 00 00 00 2C A0 A1 8A      ti
+   384      8  34                         segmentation_type_id = 52 (0x34)
+   392      8  02                         segment_num = 2 (0x2)
+   400      8  00                         segments_expected = 0 (0x0)
+   408     32  9A C9 D1 7E              crc_32 = 2596917630 (0x9AC9D17E)
+",
+        annotated_section.hex_dump()
+    );
+}
+
+#[test]
+fn test_hex_dump_points_at_malformed_upid_length() {
+    let mut data = decode(TIME_SIGNAL_WITH_TI_UPID);
+    // A TI UPID must be 8 bytes.
+    data[39] = 0x07;
+    let annotated_section = SpliceInfoSection::annotate(&data);
+    let error = annotated_section
+        .result
+        .as_ref()
+        .expect_err("should fail to parse");
+    assert_eq!(
+        (320, "splice_descriptors[0].segmentation_upid"),
+        (error.bit_offset, error.path.as_str())
+    );
+    let last_field = annotated_section
+        .iter()
+        .last()
+        .expect("should have annotations");
+    assert_eq!(
+        ("segmentation_upid_length", 312, 8, Some(7)),
+        (
+            last_field.name,
+            last_field.bit_offset,
+            last_field.bit_width,
+            last_field.value
+        )
+    );
+    assert!(annotated_section.hex_dump().ends_with(
+        "   312      8  07                           segmentation_upid_length = 7 (0x7)\n\
+         At bit 320 in splice_descriptors[0].segmentation_upid: Declared upid length was 7; \
+         however, expected length for upid type 8 is 8.\n"
+    ));
+}
+
+// MARK: - Coverage
+
+#[test]
+fn test_annotations_cover_every_bit_of_valid_sections() {
+    let samples = [
+        TIME_SIGNAL_WITH_TI_UPID,
+        "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=",
+        "/DBIAAAAAAAA///wBQb+ek2ItgAyAhdDVUVJSAAAGH+fCAgAAAAALMvDRBEAAAIXQ1VFSUgAABl/nwgIAAAAACyk26AQAACZcuND",
+        "/DAgAAAAAAAAAP/wDwUAAABkf0/+AKTLgAAAAAAAAGm9Z0E=",
+    ];
+    for sample in samples {
+        let data = decode(sample);
+        let annotated_section = SpliceInfoSection::annotate(&data);
+        assert!(annotated_section.result.is_ok(), "{}", sample);
+        let mut bit_offset = 0;
+        for leaf in leaves(&annotated_section.annotations) {
+            assert_eq!(bit_offset, leaf.bit_offset, "{} {}", sample, leaf.name);
+            bit_offset += leaf.bit_width;
+        }
+        assert_eq!(data.len() * 8, bit_offset, "{}", sample);
+        let top_level_width: usize = annotated_section
+            .annotations
+            .iter()
+            .map(|annotation| annotation.bit_width)
+            .sum();
+        assert_eq!(data.len() * 8, top_level_width, "{}", sample);
+    }
+}
+
+#[test]
+fn test_annotate_matches_parse_result() {
+    let data = decode(TIME_SIGNAL_WITH_TI_UPID);
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&data).expect("should parse sample"),
+        SpliceInfoSection::annotate(&data)
+            .result
+            .expect("should parse annotated sample")
+    );
+}
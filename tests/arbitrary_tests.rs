@@ -0,0 +1,46 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use pretty_assertions::assert_eq;
+use scte35::splice_info_section::{ParserConfig, SpliceInfoSection};
+
+// A small deterministic generator, so that failures are reproducible.
+fn pseudo_random_bytes(seed: u64, length: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+// MARK: - Round trip
+
+#[test]
+fn test_arbitrary_sections_round_trip_when_encodable() {
+    let mut encoded_count = 0;
+    for seed in 0..2000 {
+        let data = pseudo_random_bytes(seed, 512);
+        let mut unstructured = Unstructured::new(&data);
+        let Ok(section) = SpliceInfoSection::arbitrary(&mut unstructured) else {
+            continue;
+        };
+        let Ok(bytes) = section.try_to_bytes() else {
+            continue;
+        };
+        encoded_count += 1;
+        let parsed =
+            SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::lenient())
+                .unwrap_or_else(|e| panic!("should parse encoded section (seed {}): {}", seed, e));
+        assert_eq!(
+            bytes,
+            parsed.try_to_bytes().expect("should encode parsed section"),
+            "seed {}",
+            seed
+        );
+    }
+    assert!(encoded_count > 0, "should generate some encodable sections");
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::{splice_info_section, SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{SectionReaderError, TransportStreamError},
+    splice_info_section::SpliceInfoSection,
+    transport_stream::{AsyncSpliceSectionReader, Packetizer},
+};
+use tokio::io::AsyncWriteExt;
+
+const PID: u16 = 0x1F0;
+
+#[tokio::test]
+async fn test_async_reader_transport_stream_across_writes() {
+    let mut packetizer = Packetizer::new(PID, 0);
+    let stream = [
+        packetizer
+            .packetize(&splice_info_section(TIME_SIGNAL_HEX))
+            .expect("should packetize"),
+        packetizer
+            .packetize(&splice_info_section(SPLICE_INSERT_HEX))
+            .expect("should packetize"),
+    ]
+    .concat()
+    .concat();
+    let (mut writer, reader) = tokio::io::duplex(64);
+    let write = tokio::spawn(async move {
+        for chunk in stream.chunks(50) {
+            writer.write_all(chunk).await.expect("should write");
+        }
+    });
+    let mut reader = AsyncSpliceSectionReader::new(reader, PID);
+    assert_eq!(
+        Some(Ok(splice_info_section(TIME_SIGNAL_HEX))),
+        reader.next_section().await
+    );
+    assert_eq!(
+        Some(Ok(splice_info_section(SPLICE_INSERT_HEX))),
+        reader.next_section().await
+    );
+    write.await.expect("should finish writing");
+    assert_eq!(None, reader.next_section().await);
+}
+
+#[tokio::test]
+async fn test_async_reader_raw_sections_truncated() {
+    let section = SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX)
+        .expect("should be valid splice info section from hex")
+        .try_to_bytes()
+        .expect("should encode");
+    let stream = [&section[..], &section[..20]].concat();
+    let mut reader = AsyncSpliceSectionReader::new_raw(stream.as_slice());
+    assert_eq!(
+        Some(Ok(splice_info_section(SPLICE_INSERT_HEX))),
+        reader.next_section().await
+    );
+    assert!(matches!(
+        reader.next_section().await,
+        Some(Err(SectionReaderError::ParseError(_)))
+    ));
+    assert_eq!(None, reader.next_section().await);
+}
+
+#[tokio::test]
+async fn test_async_reader_truncated_packet() {
+    let packets = Packetizer::new(PID, 0)
+        .packetize(&splice_info_section(TIME_SIGNAL_HEX))
+        .expect("should packetize");
+    let mut reader = AsyncSpliceSectionReader::new(&packets[0][..100], PID);
+    assert_eq!(
+        Some(Err(SectionReaderError::TransportStreamError(
+            TransportStreamError::InvalidPacketLength(100)
+        ))),
+        reader.next_section().await
+    );
+    assert_eq!(None, reader.next_section().await);
+}
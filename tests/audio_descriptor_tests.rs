@@ -0,0 +1,107 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    atsc::{AudioCodingMode, BitStreamMode},
+    error::ParseError,
+    splice_command::{time_signal::TimeSignal, SpliceCommand},
+    splice_descriptor::{
+        audio_descriptor::{AudioDescriptor, Component, LanguageCode, NumChannels},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::SpliceTime,
+};
+
+fn component(iso_code: u32) -> Component {
+    Component {
+        component_tag: 0xFF,
+        iso_code,
+        bit_stream_mode: BitStreamMode::CompleteMain,
+        num_channels: NumChannels::AudioCodingMode(AudioCodingMode::TwoZero),
+        full_srvc_audio: true,
+    }
+}
+
+// MARK: - LanguageCode
+
+#[test]
+fn test_language_code_from_str() {
+    let language: LanguageCode = "eng".parse().expect("should be valid language code");
+    assert_eq!("eng", language.as_str());
+    assert_eq!("eng", language.to_string());
+    assert_eq!(0x656E67, language.iso_code());
+    assert_eq!(0x656E67, u32::from(language));
+}
+
+#[test]
+fn test_language_code_rejects_invalid_strings() {
+    for string in ["", "en", "engl", "ENG", "Eng", "en1", "en ", "é"] {
+        assert_eq!(
+            Err(ParseError::InvalidISO639LanguageCode(String::from(string))),
+            string.parse::<LanguageCode>(),
+            "{}",
+            string
+        );
+    }
+}
+
+#[test]
+fn test_language_code_try_from_iso_code() {
+    assert_eq!(
+        Ok("spa".parse::<LanguageCode>().expect("should be valid")),
+        LanguageCode::try_from(0x737061)
+    );
+    assert_eq!(
+        Err(ParseError::InvalidISO639LanguageCode(String::from(
+            "0x454E47"
+        ))),
+        LanguageCode::try_from(0x454E47)
+    );
+    assert_eq!(
+        Err(ParseError::InvalidISO639LanguageCode(String::from(
+            "0x000000"
+        ))),
+        LanguageCode::try_from(0)
+    );
+    assert_eq!(
+        Err(ParseError::InvalidISO639LanguageCode(String::from(
+            "0x61656E67"
+        ))),
+        LanguageCode::try_from(0x61656E67)
+    );
+}
+
+// MARK: - Component language
+
+#[test]
+fn test_component_language() {
+    let mut component = component(0);
+    assert_eq!(None, component.language());
+    let language: LanguageCode = "fra".parse().expect("should be valid language code");
+    component.set_language(language);
+    assert_eq!(0x667261, component.iso_code);
+    assert_eq!(Some(language), component.language());
+}
+
+#[test]
+fn test_component_language_round_trip() {
+    let language: LanguageCode = "deu".parse().expect("should be valid language code");
+    let audio_descriptor = AudioDescriptor {
+        identifier: 0x43554549,
+        components: vec![component(language.into())],
+    };
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime { pts_time: None },
+        }))
+        .splice_descriptors(vec![SpliceDescriptor::AudioDescriptor(audio_descriptor)])
+        .build()
+        .expect("should build section");
+    let bytes = splice_info_section
+        .try_to_bytes()
+        .expect("should encode section");
+    let parsed = SpliceInfoSection::try_from_bytes(&bytes).expect("should parse section");
+    let audio_descriptor = parsed.splice_descriptors[0]
+        .as_audio()
+        .expect("should be audio descriptor");
+    assert_eq!(Some(language), audio_descriptor.components[0].language());
+}
@@ -0,0 +1,205 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    bit_reader::{Bits, MAX_PEEK_BITS},
+    error::{EncodeError, ParseError},
+    splice_descriptor::{
+        custom_descriptor::{CustomSpliceDescriptor, SpliceDescriptorRegistry},
+        SpliceDescriptor,
+    },
+    splice_info_section::{ErrorHandling, ParserConfig, ParserLimits, SpliceInfoSection},
+};
+
+// MARK: - Reads
+
+#[test]
+fn test_reads_fields_that_are_not_byte_aligned() {
+    let data = [0b1010_1100, 0xFF, 0x00, 0x01];
+    let mut bits = Bits::new(&data);
+    assert_eq!(Ok(true), bits.bool("flag"));
+    assert_eq!(Ok(0b010), bits.u8("three_bits", 3));
+    bits.consume(4).expect("should consume reserved bits");
+    assert_eq!(8, bits.bit_offset());
+    assert_eq!(Ok(0xFF0001), bits.u32("rest", 24));
+    assert_eq!(0, bits.bits_remaining());
+}
+
+#[test]
+fn test_reads_up_to_64_bits() {
+    let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+    let mut bits = Bits::new(&data);
+    assert_eq!(Ok(0x0123456789ABCDEF), bits.u64("value", 64));
+
+    let mut bits = Bits::new(&data);
+    bits.consume(1).expect("should consume reserved bit");
+    assert_eq!(Ok(0x0123456789ABCDEF >> 1), bits.u64("value", 62));
+    assert_eq!(Ok(1), bits.u8("last", 1));
+}
+
+#[test]
+fn test_reads_bytes_arrays_and_strings() {
+    let data = [0x43, 0x55, 0x45, 0x49, 0x01, 0x02, 0xFF, 0xFE];
+    let mut bits = Bits::new(&data);
+    assert_eq!(
+        Ok("CUEI".to_string()),
+        bits.string("identifier", 4, "reading identifier")
+    );
+    assert_eq!(Ok([0x01, 0x02]), bits.array::<2>("pair"));
+    assert!(matches!(
+        bits.string("name", 2, "reading name"),
+        Err(ParseError::Utf8ConversionError {
+            description: "reading name",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_peek_does_not_consume() {
+    let data = [0xFC, 0x30];
+    let mut bits = Bits::new(&data);
+    assert_eq!(Some(0xFC), bits.peek(8));
+    assert_eq!(Some(0xFC30), bits.peek(16));
+    assert_eq!(None, bits.peek(17));
+    assert_eq!(None, bits.peek(0));
+    assert_eq!(None, bits.peek(MAX_PEEK_BITS + 1));
+    assert_eq!(Ok(0xFC), bits.byte("table_id"));
+}
+
+// MARK: - Errors
+
+#[test]
+fn test_reading_past_end_of_data_fails() {
+    let data = [0xFF];
+    let mut bits = Bits::new(&data);
+    assert_eq!(
+        Err(ParseError::UnexpectedEndOfData {
+            expected_minimum_bits_left: 16,
+            actual_bits_left: 8,
+            description: "reading bits",
+        }),
+        bits.u16("value", 16)
+    );
+    assert_eq!(
+        Err(ParseError::UnexpectedEndOfData {
+            expected_minimum_bits_left: 9,
+            actual_bits_left: 8,
+            description: "VendorDescriptor",
+        }),
+        bits.validate(9, "VendorDescriptor")
+    );
+    // Nothing was consumed by the failed reads.
+    assert_eq!(Ok(0xFF), bits.byte("value"));
+}
+
+#[test]
+fn test_bytes_respect_allocation_limit() {
+    let data = [0; 16];
+    let config = ParserConfig {
+        limits: ParserLimits {
+            max_allocation_bytes: 8,
+            ..ParserLimits::default()
+        },
+        ..ParserConfig::default()
+    };
+    let mut bits = Bits::new(&data);
+    bits.set_config(&config);
+    bits.bytes("first", 8).expect("should read within limit");
+    assert_eq!(
+        Err(ParseError::LimitExceeded {
+            limit: 8,
+            description: "max_allocation_bytes",
+        }),
+        bits.bytes("second", 1)
+    );
+}
+
+#[test]
+fn test_handle_error_records_non_fatal_errors() {
+    let mut bits = Bits::new(&[]);
+    let error = ParseError::InvalidPrivateData("unexpected version");
+    assert_eq!(
+        Err(error.clone()),
+        bits.handle_error(ErrorHandling::Fatal, error.clone())
+    );
+    assert_eq!(
+        Ok(()),
+        bits.handle_error(ErrorHandling::Ignore, error.clone())
+    );
+    assert_eq!(
+        Ok(()),
+        bits.handle_error(ErrorHandling::NonFatal, error.clone())
+    );
+    assert_eq!(vec![error], bits.take_non_fatal_errors());
+    assert!(bits.get_non_fatal_errors().is_empty());
+}
+
+// MARK: - Custom descriptors
+
+#[derive(Clone, Debug)]
+struct ProgramDescriptor {
+    is_live: bool,
+    program_number: u16,
+    title: String,
+}
+
+impl CustomSpliceDescriptor for ProgramDescriptor {
+    fn tag(&self) -> u8 {
+        0xF0
+    }
+
+    fn identifier(&self) -> u32 {
+        0x41424344
+    }
+
+    fn private_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = ((self.is_live as u16) << 15 | self.program_number)
+            .to_be_bytes()
+            .to_vec();
+        bytes.push(self.title.len() as u8);
+        bytes.extend(self.title.as_bytes());
+        Ok(bytes)
+    }
+}
+
+fn parse_program_descriptor(private_bytes: &[u8]) -> Result<ProgramDescriptor, ParseError> {
+    let mut bits = Bits::new(private_bytes);
+    bits.validate(24, "ProgramDescriptor")?;
+    let is_live = bits.bool("is_live")?;
+    let program_number = bits.u16("program_number", 15)?;
+    let title_length = bits.byte("title_length")? as usize;
+    let title = bits.string("title", title_length, "ProgramDescriptor; reading title")?;
+    Ok(ProgramDescriptor {
+        is_live,
+        program_number,
+        title,
+    })
+}
+
+#[test]
+fn test_custom_descriptor_parsed_with_bits() {
+    let descriptor = ProgramDescriptor {
+        is_live: true,
+        program_number: 0x1234,
+        title: "News".to_string(),
+    };
+    let registry = SpliceDescriptorRegistry::new().register(0xF0, 0x41424344, |private_bytes| {
+        Ok(Box::new(parse_program_descriptor(private_bytes)?))
+    });
+    let bytes = SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::Custom(Box::new(descriptor)))
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section");
+    let parsed = SpliceInfoSection::try_from_bytes_with_registry(&bytes, &registry)
+        .expect("should parse section");
+    let SpliceDescriptor::Custom(descriptor) = &parsed.splice_descriptors[0] else {
+        panic!("expected custom descriptor");
+    };
+    let descriptor = descriptor
+        .downcast_ref::<ProgramDescriptor>()
+        .expect("should be program descriptor");
+    assert!(descriptor.is_live);
+    assert_eq!(0x1234, descriptor.program_number);
+    assert_eq!("News", descriptor.title);
+}
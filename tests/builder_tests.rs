@@ -0,0 +1,1386 @@
+mod common;
+
+use base64::prelude::*;
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    cue::{splice_insert_to_time_signal, time_signal_to_splice_insert, AdBreak, Cue, CueDetails},
+    error::{CueConversionError, EncodeError, ParseError},
+    splice_command::{
+        splice_insert::{self, SpliceInsert},
+        time_signal::TimeSignal,
+        SpliceCommand, SpliceCommandType,
+    },
+    splice_descriptor::{
+        audio_descriptor::AudioDescriptor,
+        avail_descriptor::AvailDescriptor,
+        dtmf_descriptor::DTMFDescriptor,
+        segmentation_descriptor::{
+            DeliveryRestrictions, DeviceRestrictions, ScheduledEvent, SegmentationDescriptor,
+            SegmentationTypeID, SegmentationUPID, SegmentationUPIDType,
+        },
+        time_descriptor::TimeDescriptor,
+        SpliceDescriptor, SpliceDescriptorTag, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{
+        EncryptedPacket, EncryptionAlgorithm, ErrorHandling, ParserConfig, SAPType,
+        SpliceInfoSection,
+    },
+    time::{BreakDuration, Pts90k, SpliceTime},
+    transport_stream::Packetizer,
+};
+
+// MARK: - SpliceInfoSectionBuilder
+
+#[test]
+fn test_splice_info_section_builder_defaults() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice null");
+    assert_eq!(
+        SpliceInfoSection {
+            table_id: 0xFC,
+            sap_type: SAPType::Unspecified,
+            protocol_version: 0,
+            encrypted_packet: None,
+            pts_adjustment: Pts90k(0),
+            tier: 0xFFF,
+            splice_command: SpliceCommand::SpliceNull,
+            splice_descriptors: vec![],
+            crc_32: 0x761DD3B6,
+            non_fatal_errors: vec![],
+        },
+        splice_info_section
+    );
+    assert_eq!(
+        "0xFC3011000000000000FFFFF000000000761DD3B6",
+        splice_info_section
+            .try_to_hex_string()
+            .expect("should encode splice null")
+    );
+}
+
+// 14.1. time_signal – Placement Opportunity Start
+#[test]
+fn test_splice_info_section_builder_time_signal_placement_opportunity_start() {
+    let base64_string =
+        "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(1924989008)),
+            },
+        }))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor {
+                identifier: 1129661769,
+                event_id: 1207959694,
+                scheduled_event: Some(ScheduledEvent {
+                    delivery_restrictions: Some(DeliveryRestrictions {
+                        web_delivery_allowed: false,
+                        no_regional_blackout: true,
+                        archive_allowed: true,
+                        device_restrictions: DeviceRestrictions::None,
+                    }),
+                    component_segments: None,
+                    segmentation_duration: Some(27630000),
+                    segmentation_upid: SegmentationUPID::TI(String::from("0x000000002CA0A18A")),
+                    segmentation_type_id: SegmentationTypeID::ProviderPlacementOpportunityStart,
+                    segment_num: 2,
+                    segments_expected: 0,
+                    sub_segment: None,
+                }),
+            },
+        ))
+        .build()
+        .expect("should build time signal");
+    assert_eq!(0x9AC9D17E, splice_info_section.crc_32);
+    assert_eq!(
+        BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64"),
+        splice_info_section
+            .try_to_bytes()
+            .expect("should encode time signal")
+    );
+}
+
+#[test]
+fn test_splice_info_section_builder_invalid_descriptor_for_command() {
+    let result = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime { pts_time: None },
+        }))
+        .splice_descriptor(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: 1129661769,
+            provider_avail_id: 309,
+        }))
+        .build();
+    assert_eq!(
+        Err(EncodeError::InvalidSpliceDescriptorForCommand {
+            splice_descriptor_tag: SpliceDescriptorTag::AvailDescriptor,
+            splice_command_type: SpliceCommandType::TimeSignal,
+        }),
+        result
+    );
+}
+
+#[test]
+fn test_splice_info_section_builder_pts_adjustment_out_of_range() {
+    let result = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(1 << 33))
+        .build();
+    assert_eq!(
+        Err(EncodeError::ValueOutOfRange {
+            value: 1 << 33,
+            maximum_bits: 33,
+            description: "SpliceInfoSection pts_adjustment",
+        }),
+        result
+    );
+}
+
+// MARK: - SegmentationDescriptorBuilder
+
+#[test]
+fn test_segmentation_descriptor_builder_placement_opportunity_start() {
+    let descriptor = SegmentationDescriptor::builder(1207959694)
+        .provider_placement_opportunity_start()
+        .delivery_restrictions(DeliveryRestrictions {
+            web_delivery_allowed: false,
+            no_regional_blackout: true,
+            archive_allowed: true,
+            device_restrictions: DeviceRestrictions::None,
+        })
+        .duration_90khz(27630000)
+        .upid_ti(0x2CA0A18A)
+        .segment_num(2)
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        SegmentationDescriptor {
+            identifier: 1129661769,
+            event_id: 1207959694,
+            scheduled_event: Some(ScheduledEvent {
+                delivery_restrictions: Some(DeliveryRestrictions {
+                    web_delivery_allowed: false,
+                    no_regional_blackout: true,
+                    archive_allowed: true,
+                    device_restrictions: DeviceRestrictions::None,
+                }),
+                component_segments: None,
+                segmentation_duration: Some(27630000),
+                segmentation_upid: SegmentationUPID::TI(String::from("0x000000002CA0A18A")),
+                segmentation_type_id: SegmentationTypeID::ProviderPlacementOpportunityStart,
+                segment_num: 2,
+                segments_expected: 0,
+                sub_segment: None,
+            }),
+        },
+        descriptor
+    );
+}
+
+#[test]
+fn test_segmentation_upid_ti_value() {
+    let descriptor = SegmentationDescriptor::builder(10)
+        .upid_ti(0x2CA0A18A)
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        Some(0x2CA0A18A),
+        descriptor
+            .scheduled_event
+            .expect("should have scheduled event")
+            .segmentation_upid
+            .ti_value()
+    );
+    assert_eq!(
+        Some(u64::MAX),
+        SegmentationUPID::TI(String::from("0xFFFFFFFFFFFFFFFF")).ti_value()
+    );
+    assert_eq!(None, SegmentationUPID::TI(String::from("0x")).ti_value());
+    assert_eq!(None, SegmentationUPID::TI(String::from("0x+1")).ti_value());
+    assert_eq!(
+        None,
+        SegmentationUPID::ADI(String::from("SIGNAL:1")).ti_value()
+    );
+}
+
+#[test]
+fn test_segmentation_descriptor_builder_end_omits_duration_and_sub_segment() {
+    let descriptor = SegmentationDescriptor::builder(10)
+        .provider_placement_opportunity_end()
+        .duration_90khz(27630000)
+        .sub_segment(1, 2)
+        .upid_ad_id("ABCD0123456H")
+        .build()
+        .expect("should build segmentation descriptor");
+    let scheduled_event = descriptor
+        .scheduled_event
+        .expect("should have scheduled event");
+    assert_eq!(None, scheduled_event.segmentation_duration);
+    assert_eq!(None, scheduled_event.sub_segment);
+    assert_eq!(
+        SegmentationUPID::AdID(String::from("ABCD0123456H")),
+        scheduled_event.segmentation_upid
+    );
+}
+
+#[test]
+fn test_segmentation_descriptor_builder_cancel() {
+    let descriptor = SegmentationDescriptor::builder(10)
+        .provider_placement_opportunity_start()
+        .cancel()
+        .build()
+        .expect("should build segmentation descriptor");
+    assert!(descriptor.is_cancelled());
+}
+
+#[test]
+fn test_segmentation_descriptor_builder_invalid_upid() {
+    let result = SegmentationDescriptor::builder(10)
+        .program_start()
+        .upid_ad_id("ABCD")
+        .build();
+    assert_eq!(
+        Err(EncodeError::UnexpectedSegmentationUPIDLength {
+            actual_segmentation_upid_length: 4,
+            expected_segmentation_upid_length: 12,
+            segmentation_upid_type: SegmentationUPIDType::AdID,
+        }),
+        result
+    );
+}
+
+// MARK: - SpliceInsertBuilder
+
+// 14.2. splice_insert
+#[test]
+fn test_splice_insert_builder_program_out() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let splice_insert = SpliceInsert::builder(1207959695)
+        .cue_out()
+        .splice_time(Pts90k(1936310318))
+        .break_duration(Pts90k(5426421), true)
+        .build()
+        .expect("should build splice insert");
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(splice_insert.into())
+        .splice_descriptor(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: 1129661769,
+            provider_avail_id: 309,
+        }))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(
+        hex_string,
+        splice_info_section
+            .try_to_hex_string()
+            .expect("should encode splice insert")
+    );
+}
+
+#[test]
+fn test_splice_insert_builder_immediate_component_in() {
+    let splice_insert = SpliceInsert::builder(42)
+        .cue_out()
+        .cue_in()
+        .immediate()
+        .component(1, Some(Pts90k(1000)))
+        .component(2, None)
+        .unique_program_id(7)
+        .avail_num(1)
+        .avails_expected(2)
+        .build()
+        .expect("should build splice insert");
+    assert_eq!(
+        SpliceInsert {
+            event_id: 42,
+            scheduled_event: Some(splice_insert::ScheduledEvent {
+                out_of_network_indicator: false,
+                is_immediate_splice: true,
+                splice_mode: splice_insert::SpliceMode::ComponentSpliceMode(vec![
+                    splice_insert::ComponentMode {
+                        component_tag: 1,
+                        splice_time: None,
+                    },
+                    splice_insert::ComponentMode {
+                        component_tag: 2,
+                        splice_time: None,
+                    },
+                ]),
+                break_duration: None,
+                unique_program_id: 7,
+                avail_num: 1,
+                avails_expected: 2,
+            }),
+        },
+        splice_insert
+    );
+}
+
+#[test]
+fn test_splice_insert_builder_component_round_trip() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(
+            SpliceInsert::builder(42)
+                .cue_out()
+                .component(1, Some(Pts90k(1000)))
+                .component(2, None)
+                .build()
+                .expect("should build splice insert")
+                .into(),
+        )
+        .build()
+        .expect("should build splice info section");
+    let encoded = splice_info_section
+        .try_to_bytes()
+        .expect("should encode splice insert");
+    assert_eq!(
+        splice_info_section,
+        SpliceInfoSection::try_from_bytes(&encoded).expect("should parse encoded bytes")
+    );
+}
+
+#[test]
+fn test_splice_insert_builder_cancel() {
+    let splice_insert = SpliceInsert::builder(42)
+        .cue_out()
+        .splice_time(Pts90k(1000))
+        .cancel()
+        .build()
+        .expect("should build splice insert");
+    assert_eq!(
+        SpliceInsert {
+            event_id: 42,
+            scheduled_event: None,
+        },
+        splice_insert
+    );
+    assert!(splice_insert.is_cancelled());
+}
+
+#[test]
+fn test_splice_insert_builder_splice_time_out_of_range() {
+    let result = SpliceInsert::builder(42)
+        .splice_time(Pts90k(1 << 33))
+        .build();
+    assert!(matches!(
+        result,
+        Err(EncodeError::ValueOutOfRange {
+            value: 0x200000000,
+            maximum_bits: 33,
+            ..
+        })
+    ));
+}
+
+// MARK: - AdBreak
+
+#[test]
+fn test_ad_break_out_is_placement_opportunity_start_with_duration() {
+    let (cue_out, _) = AdBreak::new(1207959694, 1924989008, 27630000)
+        .upid_ti(0x2CA0A18A)
+        .build()
+        .expect("should build ad break");
+    let SpliceCommand::TimeSignal(ref time_signal) = cue_out.splice_command else {
+        panic!("cue out should be time signal");
+    };
+    assert_eq!(Some(Pts90k(1924989008)), time_signal.splice_time.pts_time);
+    let SpliceDescriptor::SegmentationDescriptor(ref descriptor) = cue_out.splice_descriptors[0]
+    else {
+        panic!("cue out should have segmentation descriptor");
+    };
+    let scheduled_event = descriptor
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert_eq!(
+        SegmentationTypeID::ProviderPlacementOpportunityStart,
+        scheduled_event.segmentation_type_id
+    );
+    assert_eq!(Some(27630000), scheduled_event.segmentation_duration);
+}
+
+#[test]
+fn test_ad_break_in_is_placement_opportunity_end_at_break_end() {
+    let (cue_out, cue_in) = AdBreak::new(10, 0x1FFFFFFF0, 0x20)
+        .build()
+        .expect("should build ad break");
+    let SpliceCommand::TimeSignal(ref time_signal) = cue_in.splice_command else {
+        panic!("cue in should be time signal");
+    };
+    assert_eq!(Some(Pts90k(0x10)), time_signal.splice_time.pts_time);
+    let SpliceDescriptor::SegmentationDescriptor(ref descriptor) = cue_in.splice_descriptors[0]
+    else {
+        panic!("cue in should have segmentation descriptor");
+    };
+    let scheduled_event = descriptor
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert_eq!(10, descriptor.event_id);
+    assert_eq!(
+        SegmentationTypeID::ProviderPlacementOpportunityEnd,
+        scheduled_event.segmentation_type_id
+    );
+    assert_eq!(None, scheduled_event.segmentation_duration);
+    assert_eq!(SegmentationUPID::NotUsed, scheduled_event.segmentation_upid);
+    for section in [cue_out, cue_in] {
+        let encoded = section.try_to_bytes().expect("should encode cue");
+        assert_eq!(
+            section,
+            SpliceInfoSection::try_from_bytes(&encoded).expect("should parse encoded cue")
+        );
+    }
+}
+
+// MARK: - Cue summary
+
+fn time_signal_with(descriptors: Vec<SegmentationDescriptor>) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(10))
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(90)),
+            },
+        }))
+        .splice_descriptors(descriptors.into_iter().map(Into::into).collect())
+        .build()
+        .expect("should build section")
+}
+
+#[test]
+fn test_cue_from_splice_insert() {
+    let cue_out = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(5)
+                .cue_out()
+                .splice_time(Pts90k(900))
+                .break_duration(Pts90k(90_000 * 30), true)
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Cue::AdBreakStart(CueDetails {
+            pts: Some(Pts90k(900)),
+            duration: Some(std::time::Duration::from_secs(30)),
+            event_id: Some(5),
+            segmentation_type_id: None,
+            upid: None,
+        }),
+        Cue::from(&cue_out)
+    );
+
+    let cue_in = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(5)
+                .cue_in()
+                .immediate()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Cue::AdBreakEnd(CueDetails {
+            pts: None,
+            duration: None,
+            event_id: Some(5),
+            segmentation_type_id: None,
+            upid: None,
+        }),
+        Cue::from(&cue_in)
+    );
+
+    let cancel = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(5)
+                .cancel()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    assert!(matches!(Cue::from(&cancel), Cue::Cancelled(_)));
+    assert_eq!(Some(5), Cue::from(&cancel).details().event_id);
+}
+
+#[test]
+fn test_cue_from_time_signal() {
+    let (cue_out, cue_in) = AdBreak::new(1, 100, 90_000)
+        .upid_ti(0x2CA0A18A)
+        .build()
+        .expect("should build ad break");
+    assert_eq!(
+        Cue::AdBreakStart(CueDetails {
+            pts: Some(Pts90k(100)),
+            duration: Some(std::time::Duration::from_secs(1)),
+            event_id: Some(1),
+            segmentation_type_id: Some(SegmentationTypeID::ProviderPlacementOpportunityStart),
+            upid: Some(SegmentationUPID::new_ti(0x2CA0A18A)),
+        }),
+        Cue::from(&cue_out)
+    );
+    assert!(matches!(Cue::from(&cue_in), Cue::AdBreakEnd(_)));
+
+    let program_start = time_signal_with(vec![SegmentationDescriptor::builder(2)
+        .program_start()
+        .build()
+        .expect("should build segmentation descriptor")]);
+    assert_eq!(
+        Cue::ProgramBoundary(CueDetails {
+            pts: Some(Pts90k(100)),
+            duration: None,
+            event_id: Some(2),
+            segmentation_type_id: Some(SegmentationTypeID::ProgramStart),
+            upid: None,
+        }),
+        Cue::from(&program_start)
+    );
+
+    let chapter_end = time_signal_with(vec![SegmentationDescriptor::builder(3)
+        .chapter_end()
+        .build()
+        .expect("should build segmentation descriptor")]);
+    assert!(matches!(Cue::from(&chapter_end), Cue::Chapter(_)));
+
+    let cancel = time_signal_with(vec![SegmentationDescriptor::builder(4)
+        .cancel()
+        .build()
+        .expect("should build segmentation descriptor")]);
+    assert_eq!(
+        Cue::Cancelled(CueDetails {
+            pts: Some(Pts90k(100)),
+            duration: None,
+            event_id: Some(4),
+            segmentation_type_id: None,
+            upid: None,
+        }),
+        Cue::from(&cancel)
+    );
+}
+
+#[test]
+fn test_cue_uses_first_segmentation_descriptor() {
+    let section = time_signal_with(vec![
+        SegmentationDescriptor::builder(1)
+            .chapter_start()
+            .build()
+            .expect("should build segmentation descriptor"),
+        SegmentationDescriptor::builder(2)
+            .break_start()
+            .build()
+            .expect("should build segmentation descriptor"),
+    ]);
+    assert!(matches!(Cue::from(&section), Cue::Chapter(_)));
+}
+
+#[test]
+fn test_unknown_cue() {
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Cue::Unknown(CueDetails {
+            pts: None,
+            duration: None,
+            event_id: None,
+            segmentation_type_id: None,
+            upid: None,
+        }),
+        Cue::from(&splice_null)
+    );
+    assert!(matches!(
+        Cue::from(&time_signal_with(vec![])),
+        Cue::Unknown(_)
+    ));
+    let content_identification = time_signal_with(vec![SegmentationDescriptor::builder(1)
+        .segmentation_type_id(SegmentationTypeID::ContentIdentification)
+        .build()
+        .expect("should build segmentation descriptor")]);
+    assert!(matches!(
+        Cue::from(&content_identification),
+        Cue::Unknown(_)
+    ));
+}
+
+// MARK: - Dialect conversion
+
+#[test]
+fn test_splice_insert_out_converts_to_placement_opportunity_start() {
+    let time_descriptor = TimeDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        tai_seconds: 1,
+        tai_ns: 2,
+        utc_offset: 37,
+    };
+    let splice_insert = SpliceInsert::builder(1207959695)
+        .cue_out()
+        .splice_time(Pts90k(1936310318))
+        .break_duration(Pts90k(5426421), true)
+        .avail_num(1)
+        .avails_expected(2)
+        .build()
+        .expect("should build splice insert");
+    let cue_out = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(1000))
+        .tier(0x123)
+        .splice_command(SpliceCommand::SpliceInsert(splice_insert))
+        .splice_descriptor(
+            AvailDescriptor {
+                identifier: CUEI_IDENTIFIER,
+                provider_avail_id: 309,
+            }
+            .into(),
+        )
+        .splice_descriptor(time_descriptor.clone().into())
+        .build()
+        .expect("should build section");
+    let time_signal = splice_insert_to_time_signal(&cue_out).expect("should convert");
+    let expected = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(1000))
+        .tier(0x123)
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(1936310318)),
+            },
+        }))
+        .splice_descriptor(
+            SegmentationDescriptor::builder(1207959695)
+                .provider_placement_opportunity_start()
+                .duration_90khz(5426421)
+                .segment_num(1)
+                .segments_expected(2)
+                .build()
+                .expect("should build segmentation descriptor")
+                .into(),
+        )
+        .splice_descriptor(time_descriptor.into())
+        .build()
+        .expect("should build section");
+    assert_eq!(expected, time_signal);
+
+    // The auto_return is not kept, and the avail descriptor is not allowed with a time signal.
+    let splice_insert = time_signal_to_splice_insert(&time_signal).expect("should convert back");
+    let SpliceCommand::SpliceInsert(splice_insert) = &splice_insert.splice_command else {
+        panic!("should be splice insert");
+    };
+    let scheduled_event = splice_insert
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert!(scheduled_event.out_of_network_indicator);
+    assert_eq!(
+        Some(BreakDuration {
+            auto_return: false,
+            duration: Pts90k(5426421),
+        }),
+        scheduled_event.break_duration
+    );
+    assert_eq!(
+        (1, 2),
+        (scheduled_event.avail_num, scheduled_event.avails_expected)
+    );
+}
+
+#[test]
+fn test_splice_insert_in_and_cancel_convert_to_time_signal() {
+    let cue_in = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(7)
+                .cue_in()
+                .immediate()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    let time_signal = splice_insert_to_time_signal(&cue_in).expect("should convert");
+    assert_eq!(None, time_signal.splice_pts());
+    let descriptor = time_signal
+        .find_segmentation(&SegmentationTypeID::ProviderPlacementOpportunityEnd)
+        .expect("should have placement opportunity end");
+    assert_eq!(7, descriptor.event_id);
+    assert_eq!(
+        cue_in,
+        time_signal_to_splice_insert(&time_signal).expect("should convert back")
+    );
+
+    let cancel = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(7)
+                .cancel()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    let time_signal = splice_insert_to_time_signal(&cancel).expect("should convert");
+    let descriptors = time_signal.segmentation_descriptors().collect::<Vec<_>>();
+    assert_eq!(1, descriptors.len());
+    assert!(descriptors[0].is_cancelled());
+    assert_eq!(7, descriptors[0].event_id);
+    assert_eq!(
+        cancel,
+        time_signal_to_splice_insert(&time_signal).expect("should convert back")
+    );
+}
+
+#[test]
+fn test_time_signal_converts_to_splice_insert() {
+    let time_signal =
+        SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse");
+    let splice_insert = time_signal_to_splice_insert(&time_signal).expect("should convert");
+    let expected = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(0x4800008E)
+                .cue_out()
+                .splice_time(Pts90k(1924989008))
+                .break_duration(Pts90k(27630000), false)
+                .avail_num(2)
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    assert_eq!(expected, splice_insert);
+}
+
+#[test]
+fn test_time_signal_conversion_uses_first_placement_opportunity() {
+    let time_signal = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(100)),
+            },
+        }))
+        .splice_descriptor(
+            SegmentationDescriptor::builder(1)
+                .program_start()
+                .build()
+                .expect("should build segmentation descriptor")
+                .into(),
+        )
+        .splice_descriptor(
+            SegmentationDescriptor::builder(2)
+                .provider_placement_opportunity_end()
+                .build()
+                .expect("should build segmentation descriptor")
+                .into(),
+        )
+        .splice_descriptor(
+            AudioDescriptor {
+                identifier: CUEI_IDENTIFIER,
+                components: vec![],
+            }
+            .into(),
+        )
+        .build()
+        .expect("should build section");
+    let splice_insert = time_signal_to_splice_insert(&time_signal).expect("should convert");
+    let expected = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(2)
+                .cue_in()
+                .splice_time(Pts90k(100))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .splice_descriptor(time_signal.splice_descriptors[0].clone())
+        .build()
+        .expect("should build section");
+    assert_eq!(expected, splice_insert);
+}
+
+#[test]
+fn test_dialect_conversion_errors() {
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Err(CueConversionError::UnexpectedSpliceCommand(
+            SpliceCommandType::SpliceNull
+        )),
+        splice_insert_to_time_signal(&splice_null)
+    );
+    assert_eq!(
+        Err(CueConversionError::UnexpectedSpliceCommand(
+            SpliceCommandType::SpliceNull
+        )),
+        time_signal_to_splice_insert(&splice_null)
+    );
+
+    let component_splice = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .cue_out()
+                .component(1, Some(Pts90k(100)))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Err(CueConversionError::ComponentSpliceMode),
+        splice_insert_to_time_signal(&component_splice)
+    );
+
+    let break_start = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime { pts_time: None },
+        }))
+        .splice_descriptor(
+            SegmentationDescriptor::builder(1)
+                .break_start()
+                .build()
+                .expect("should build segmentation descriptor")
+                .into(),
+        )
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        Err(CueConversionError::MissingSegmentationDescriptor),
+        time_signal_to_splice_insert(&break_start)
+    );
+}
+
+// MARK: - Encoding
+
+#[test]
+fn test_encode_round_trip_splice_insert() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    assert_eq!(
+        hex_string,
+        splice_info_section
+            .try_to_hex_string()
+            .expect("should encode splice insert")
+    );
+}
+
+#[test]
+fn test_encode_round_trip_sap_types() {
+    for sap_type in [
+        SAPType::Type1,
+        SAPType::Type2,
+        SAPType::Type3,
+        SAPType::Unspecified,
+    ] {
+        let splice_info_section = SpliceInfoSection::builder()
+            .sap_type(sap_type.clone())
+            .build()
+            .expect("should build splice info section");
+        let bytes = splice_info_section
+            .try_to_bytes()
+            .expect("should encode splice info section");
+        assert_eq!(
+            sap_type,
+            SpliceInfoSection::try_from_bytes(&bytes)
+                .expect("should parse splice info section")
+                .sap_type
+        );
+    }
+}
+
+#[test]
+fn test_sap_type_raw_values() {
+    for value in 0..=3u8 {
+        let sap_type = SAPType::try_from(value).expect("should be valid sap_type");
+        assert_eq!(value, sap_type.value());
+        let bytes = SpliceInfoSection::builder()
+            .sap_type(sap_type)
+            .build()
+            .expect("should build splice info section")
+            .try_to_bytes()
+            .expect("should encode splice info section");
+        // The sap_type is the 2 bits after the section_syntax_indicator and private_indicator.
+        assert_eq!(value, (bytes[1] >> 4) & 0x03);
+    }
+    assert!(SAPType::try_from(4).is_err());
+}
+
+#[test]
+fn test_encode_round_trip_mid_upid() {
+    let base64_string = "/DBwAAAAAAAAAP/wBQb/AAAAAABaAlhDVUVJAAAAAn//AABSZcANRAoMFHeL5eP2AAAAAAAACgwUd4vl4/YAAAAAAAAJJlNJR05BTDpMeTlFTUd4S1IwaEZaVXRwTUhkQ1VWWm5SVUZuWnowNgEB1Dao2g==";
+    let splice_info_section = SpliceInfoSection::try_from_bytes(
+        &BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64"),
+    )
+    .expect("should be valid splice info section from base64");
+    let encoded = splice_info_section
+        .try_to_bytes()
+        .expect("should encode mid upid");
+    assert_eq!(
+        SpliceInfoSection {
+            crc_32: splice_info_section.crc_32,
+            ..SpliceInfoSection::try_from_bytes(&encoded).expect("should parse encoded bytes")
+        },
+        splice_info_section
+    );
+}
+
+#[test]
+fn test_encode_round_trip_uuid_upid() {
+    let uuid = [
+        0xAA, 0x85, 0xBB, 0xB6, 0x5C, 0x43, 0x4B, 0x6A, 0xBE, 0xBB, 0xEE, 0x3B, 0x13, 0xEB, 0x79,
+        0x99,
+    ];
+    let encode = |segmentation_upid: SegmentationUPID| {
+        let descriptor = SegmentationDescriptor::builder(10)
+            .program_start()
+            .upid(segmentation_upid)
+            .build()
+            .expect("should build segmentation descriptor");
+        SpliceInfoSection::builder()
+            .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+                splice_time: SpliceTime { pts_time: None },
+            }))
+            .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(descriptor))
+            .build()
+            .expect("should build time signal")
+            .try_to_bytes()
+            .expect("should encode uuid upid")
+    };
+    let encoded = encode(
+        SegmentationDescriptor::builder(10)
+            .upid_uuid(uuid)
+            .build()
+            .expect("should build segmentation descriptor")
+            .scheduled_event
+            .expect("should have scheduled event")
+            .segmentation_upid,
+    );
+    assert_eq!(
+        Some(SegmentationUPID::UUID(String::from(
+            "aa85bbb6-5c43-4b6a-bebb-ee3b13eb7999"
+        ))),
+        SpliceInfoSection::try_from_bytes(&encoded)
+            .expect("should parse non-UTF-8 uuid upid")
+            .splice_descriptors
+            .into_iter()
+            .find_map(|descriptor| match descriptor {
+                SpliceDescriptor::SegmentationDescriptor(descriptor) => descriptor
+                    .scheduled_event
+                    .map(|event| event.segmentation_upid),
+                _ => None,
+            })
+    );
+    for uuid in [
+        "aa85bbb65c434b6abebbee3b13eb7999",
+        "AA85BBB6-5C43-4B6A-BEBB-EE3B13EB7999",
+    ] {
+        assert_eq!(encoded, encode(SegmentationUPID::UUID(String::from(uuid))));
+    }
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_upid_as_uuid() {
+    let expected = uuid::Uuid::from_u128(0xAA85BBB65C434B6ABEBBEE3B13EB7999);
+    assert_eq!(
+        Some(expected),
+        SegmentationUPID::UUID(String::from("aa85bbb6-5c43-4b6a-bebb-ee3b13eb7999")).uuid()
+    );
+    assert_eq!(None, SegmentationUPID::UUID(String::from("invalid")).uuid());
+    assert_eq!(
+        None,
+        SegmentationUPID::AdID(String::from("ABCD0123456H")).uuid()
+    );
+}
+
+#[test]
+fn test_segmentation_upid_raw_bytes_are_verbatim() {
+    for base64_string in [
+        // EIDR
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAAA3//AAApPWwKDBR4+FrhALBoW4+xyBAAAGij1lQ=",
+        // ISAN
+        "/DAzAAAAAAAA///wBQb+AAAAAAAdAhtDVUVJAAAABn+/BgwAAAAAOo0AAAAAAAARAAAT5alN",
+        // MID
+        "/DBwAAAAAAAAAP/wBQb/AAAAAABaAlhDVUVJAAAAAn//AABSZcANRAoMFHeL5eP2AAAAAAAACgwUd4vl4/YAAAAAAAAJJlNJR05BTDpMeTlFTUd4S1IwaEZaVXRwTUhkQ1VWWm5SVUZuWnowNgEB1Dao2g==",
+    ] {
+        let bytes = BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64");
+        let splice_info_section = SpliceInfoSection::try_from_bytes(&bytes)
+            .expect("should be valid splice info section from base64");
+        let Some(SpliceDescriptor::SegmentationDescriptor(descriptor)) =
+            splice_info_section.splice_descriptors.first()
+        else {
+            panic!("should have segmentation descriptor");
+        };
+        let upid = &descriptor
+            .scheduled_event
+            .as_ref()
+            .expect("should have scheduled event")
+            .segmentation_upid;
+        let raw_bytes = upid.raw_bytes().expect("should encode upid");
+        let upid_bytes = [&[upid.upid_type().value(), raw_bytes.len() as u8], &raw_bytes[..]].concat();
+        assert!(
+            bytes
+                .windows(upid_bytes.len())
+                .any(|window| window == upid_bytes),
+            "{:?}",
+            upid
+        );
+        assert_eq!(
+            Ok(upid.clone()),
+            SegmentationUPID::try_from_type_and_bytes(upid.upid_type(), &raw_bytes)
+        );
+    }
+}
+
+// MARK: - Section size
+
+fn section_with_private_bytes(length: usize) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::Unknown {
+            tag: 0xF0,
+            identifier: CUEI_IDENTIFIER,
+            private_bytes: vec![0xAB; length],
+        })
+        .build()
+        .expect("should build section")
+}
+
+#[test]
+fn test_encoded_length_matches_encoded_bytes() {
+    let splice_info_section = section_with_private_bytes(10);
+    let bytes = splice_info_section
+        .try_to_bytes()
+        .expect("should encode section");
+    assert_eq!(
+        bytes.len(),
+        splice_info_section
+            .encoded_length()
+            .expect("should encode section")
+    );
+}
+
+#[test]
+fn test_fits_in_single_ts_packet() {
+    let base_length = section_with_private_bytes(0)
+        .encoded_length()
+        .expect("should encode section");
+    let largest = section_with_private_bytes(183 - base_length);
+    assert_eq!(Ok(183), largest.encoded_length());
+    assert_eq!(Ok(true), largest.fits_in_single_ts_packet());
+    assert_eq!(
+        1,
+        Packetizer::new(0x1F0, 0)
+            .packetize(&largest)
+            .expect("should packetize")
+            .len()
+    );
+
+    let oversized = section_with_private_bytes(184 - base_length);
+    assert_eq!(Ok(false), oversized.fits_in_single_ts_packet());
+    assert_eq!(
+        2,
+        Packetizer::new(0x1F0, 0)
+            .packetize(&oversized)
+            .expect("should packetize")
+            .len()
+    );
+}
+
+#[test]
+fn test_encoded_length_of_encrypted_section_fails() {
+    let mut splice_info_section = section_with_private_bytes(0);
+    splice_info_section.encrypted_packet = Some(EncryptedPacket {
+        encryption_algorithm: EncryptionAlgorithm::DesEcbMode,
+        cw_index: 0,
+        alignment_stuffing: 0,
+        e_crc_32: 0,
+    });
+    assert_eq!(
+        Err(EncodeError::EncryptedMessageNotSupported),
+        splice_info_section.fits_in_single_ts_packet()
+    );
+}
+
+// MARK: - Canonicalization
+
+#[test]
+fn test_canonicalize_rederives_lengths_and_crc() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let canonical_bytes = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should parse")
+        .try_to_bytes()
+        .expect("should encode");
+    // The splice_command_length is increased by one byte (taken from the descriptor_loop_length).
+    let mut bytes = canonical_bytes.clone();
+    bytes[12] += 1;
+    let mut splice_info_section =
+        SpliceInfoSection::try_from_bytes(&bytes).expect("should parse with non-fatal errors");
+    assert!(matches!(
+        splice_info_section.non_fatal_errors.as_slice(),
+        [ParseError::UnexpectedSpliceCommandLength { .. }]
+    ));
+    assert_eq!(
+        Ok(canonical_bytes.clone()),
+        splice_info_section.canonicalize()
+    );
+    assert!(splice_info_section.non_fatal_errors.is_empty());
+    assert_eq!(0x62DBA30A, splice_info_section.crc_32);
+
+    splice_info_section.pts_adjustment = Pts90k(1);
+    let bytes = splice_info_section
+        .canonicalize()
+        .expect("should canonicalize");
+    assert_eq!(
+        splice_info_section,
+        SpliceInfoSection::try_from_bytes_with_config(
+            &bytes,
+            &ParserConfig {
+                crc_mismatch: ErrorHandling::Fatal,
+                ..Default::default()
+            }
+        )
+        .expect("should parse with valid crc")
+    );
+}
+
+#[test]
+fn test_canonicalize_encrypted_section_fails() {
+    let mut splice_info_section = section_with_private_bytes(0);
+    splice_info_section.encrypted_packet = Some(EncryptedPacket {
+        encryption_algorithm: EncryptionAlgorithm::DesEcbMode,
+        cw_index: 0,
+        alignment_stuffing: 0,
+        e_crc_32: 0,
+    });
+    assert_eq!(
+        Err(EncodeError::EncryptedMessageNotSupported),
+        splice_info_section.canonicalize()
+    );
+}
+
+// MARK: - SpliceDescriptor conversions
+
+#[test]
+fn test_splice_descriptor_from_and_as() {
+    let avail_descriptor = AvailDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        provider_avail_id: 309,
+    };
+    let time_descriptor = TimeDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        tai_seconds: 1,
+        tai_ns: 2,
+        utc_offset: 37,
+    };
+    let audio_descriptor = AudioDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        components: vec![],
+    };
+    let segmentation_descriptor = SegmentationDescriptor::builder(1)
+        .break_start()
+        .build()
+        .expect("should build segmentation descriptor");
+    let descriptors: Vec<SpliceDescriptor> = vec![
+        avail_descriptor.clone().into(),
+        time_descriptor.clone().into(),
+        audio_descriptor.clone().into(),
+        segmentation_descriptor.clone().into(),
+    ];
+    assert_eq!(
+        SpliceDescriptor::AvailDescriptor(avail_descriptor.clone()),
+        descriptors[0]
+    );
+    assert_eq!(Some(&avail_descriptor), descriptors[0].as_avail());
+    assert_eq!(Some(&time_descriptor), descriptors[1].as_time());
+    assert_eq!(Some(&audio_descriptor), descriptors[2].as_audio());
+    assert_eq!(
+        Some(&segmentation_descriptor),
+        descriptors[3].as_segmentation()
+    );
+    assert_eq!(None, descriptors[0].as_segmentation());
+    assert_eq!(None, descriptors[3].as_avail());
+    assert_eq!(None, descriptors[3].as_dtmf());
+}
+
+#[test]
+fn test_splice_descriptor_from_dtmf_descriptor() {
+    let dtmf_descriptor = DTMFDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        preroll: 177,
+        dtmf_chars: String::from("121#"),
+    };
+    let splice_descriptor = SpliceDescriptor::from(dtmf_descriptor.clone());
+    assert_eq!(SpliceDescriptorTag::DTMFDescriptor, splice_descriptor.tag());
+    assert_eq!(Some(&dtmf_descriptor), splice_descriptor.as_dtmf());
+    assert_eq!(None, splice_descriptor.as_time());
+    assert_eq!(None, splice_descriptor.as_audio());
+}
+
+// MARK: - UPID lookup
+
+#[test]
+fn test_segmentation_upid_iter_is_depth_first() {
+    let upid = SegmentationUPID::MID(vec![
+        SegmentationUPID::AdID(String::from("ABCD0123456H")),
+        SegmentationUPID::MID(vec![
+            SegmentationUPID::TI(String::from("0x000000002CA0A18A")),
+            SegmentationUPID::URI(String::from("urn:example")),
+        ]),
+        SegmentationUPID::ADI(String::from("SIGNAL:abc")),
+    ]);
+    assert_eq!(
+        vec![
+            SegmentationUPIDType::MID,
+            SegmentationUPIDType::AdID,
+            SegmentationUPIDType::MID,
+            SegmentationUPIDType::TI,
+            SegmentationUPIDType::URI,
+            SegmentationUPIDType::ADI,
+        ],
+        upid.iter().map(|upid| upid.upid_type()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&SegmentationUPID::NotUsed],
+        SegmentationUPID::NotUsed.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_scheduled_event_find_upid() {
+    let base64_string = "/DBwAAAAAAAAAP/wBQb/AAAAAABaAlhDVUVJAAAAAn//AABSZcANRAoMFHeL5eP2AAAAAAAACgwUd4vl4/YAAAAAAAAJJlNJR05BTDpMeTlFTUd4S1IwaEZaVXRwTUhkQ1VWWm5SVUZuWnowNgEB1Dao2g==";
+    let splice_info_section = SpliceInfoSection::try_from_bytes(
+        &BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64"),
+    )
+    .expect("should be valid splice info section from base64");
+    let Some(SpliceDescriptor::SegmentationDescriptor(descriptor)) =
+        splice_info_section.splice_descriptors.first()
+    else {
+        panic!("should have segmentation descriptor");
+    };
+    let scheduled_event = descriptor
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert_eq!(
+        Some(&SegmentationUPID::ADI(String::from(
+            "SIGNAL:Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0"
+        ))),
+        scheduled_event.find_upid(SegmentationUPIDType::ADI)
+    );
+    assert_eq!(
+        Some(SegmentationUPIDType::EIDR),
+        scheduled_event
+            .find_upid(SegmentationUPIDType::EIDR)
+            .map(|upid| upid.upid_type())
+    );
+    assert_eq!(None, scheduled_event.find_upid(SegmentationUPIDType::TI));
+}
+
+// MARK: - UPID constructors
+
+#[test]
+fn test_segmentation_upid_constructors() {
+    assert_eq!(
+        Ok(SegmentationUPID::AdID(String::from("ABCD0123456H"))),
+        SegmentationUPID::new_ad_id("ABCD0123456H")
+    );
+    assert_eq!(
+        Ok(SegmentationUPID::ISCI(String::from("ABCD1234"))),
+        SegmentationUPID::new_isci("ABCD1234")
+    );
+    assert_eq!(
+        Ok(SegmentationUPID::TID(String::from("MV0004146400"))),
+        SegmentationUPID::new_tid("MV0004146400")
+    );
+    assert_eq!(
+        SegmentationUPID::TI(String::from("0x000000002CA0A18A")),
+        SegmentationUPID::new_ti(0x2CA0A18A)
+    );
+    assert_eq!(
+        Ok(SegmentationUPID::URI(String::from(
+            "urn:uuid:aa85bbb6-5c43-4b6a-bebb-ee3b13eb7999"
+        ))),
+        SegmentationUPID::new_uri("urn:uuid:aa85bbb6-5c43-4b6a-bebb-ee3b13eb7999")
+    );
+}
+
+#[test]
+fn test_segmentation_upid_constructors_validate() {
+    assert_eq!(
+        Err(EncodeError::UnexpectedSegmentationUPIDLength {
+            actual_segmentation_upid_length: 4,
+            expected_segmentation_upid_length: 12,
+            segmentation_upid_type: SegmentationUPIDType::AdID,
+        }),
+        SegmentationUPID::new_ad_id("ABCD")
+    );
+    for ad_id in ["ABC10123456H", "ABCD0123456-", "ABCD012345é"] {
+        assert!(
+            matches!(
+                SegmentationUPID::new_ad_id(ad_id),
+                Err(EncodeError::InvalidSegmentationUPID {
+                    segmentation_upid_type: SegmentationUPIDType::AdID,
+                    ..
+                })
+            ),
+            "{}",
+            ad_id
+        );
+    }
+    assert!(SegmentationUPID::new_isci("ABCD123A").is_err());
+    assert!(SegmentationUPID::new_tid("M10004146400").is_err());
+    for uri in [
+        "",
+        "no-scheme",
+        "1http://example.com",
+        "http://exa mple.com",
+    ] {
+        assert!(SegmentationUPID::new_uri(uri).is_err(), "{}", uri);
+    }
+    assert_eq!(
+        Err(EncodeError::LengthOutOfRange {
+            length: 256,
+            maximum_length: 255,
+            description: "SegmentationUPID segmentation_upid_length",
+        }),
+        SegmentationUPID::new_uri(&format!("urn:{}", "a".repeat(252)))
+    );
+}
+
+// MARK: - Delivery restrictions
+
+#[test]
+fn test_delivery_restrictions_bits() {
+    assert_eq!(
+        DeliveryRestrictions {
+            web_delivery_allowed: true,
+            no_regional_blackout: true,
+            archive_allowed: true,
+            device_restrictions: DeviceRestrictions::None,
+        },
+        DeliveryRestrictions::unrestricted()
+    );
+    let delivery_restrictions = DeliveryRestrictions {
+        web_delivery_allowed: true,
+        no_regional_blackout: false,
+        archive_allowed: false,
+        device_restrictions: DeviceRestrictions::RestrictGroup2,
+    };
+    assert_eq!(0x12, u8::from(delivery_restrictions.clone()));
+    assert_eq!(
+        Ok(delivery_restrictions),
+        DeliveryRestrictions::try_from(0x12)
+    );
+    for value in 0..=0x1F {
+        let delivery_restrictions =
+            DeliveryRestrictions::try_from(value).expect("should convert 5 bits");
+        assert_eq!(value, delivery_restrictions.bits());
+    }
+    assert!(DeliveryRestrictions::try_from(0x20).is_err());
+    assert!(DeliveryRestrictions::try_from(0xFF).is_err());
+}
@@ -0,0 +1,482 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{splice_info_section::SpliceInfoSection, time::Pts90k, transport_stream::Packetizer};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::UdpSocket,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const SPLICE_INSERT_BASE64: &str =
+    "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=";
+
+fn scte35(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scte35"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("should spawn scte35");
+    child
+        .stdin
+        .take()
+        .expect("should have stdin")
+        .write_all(stdin)
+        .expect("should write stdin");
+    child.wait_with_output().expect("should run scte35")
+}
+
+// A transport stream with a PAT and PMT for program 1, a video PES packet with a PTS 2 seconds
+// before the splice time, and the time_signal on PID 0x1F0.
+fn transport_stream() -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend(section_packet(
+        0x0000,
+        &long_section(0x00, 1, &[0x00, 0x01, 0xF0, 0x00]),
+    ));
+    let pmt = [
+        0xE1, 0x00, 0xF0, 0x06, 0x05, 0x04, b'C', b'U', b'E', b'I', // CUEI registration
+        0x1B, 0xE1, 0x00, 0xF0, 0x00, // video on PID 0x100
+        0x86, 0xE1, 0xF0, 0xF0, 0x00, // SCTE-35 on PID 0x1F0
+    ];
+    data.extend(section_packet(0x1000, &long_section(0x02, 1, &pmt)));
+    // PTS of 1924809008 (i.e. 180000 ticks before the splice time of 1924989008).
+    let mut pes = vec![
+        0x47, 0x41, 0x00, 0x10, 0x00, 0x00, 0x01, 0xE0, 0x00, 0x00, 0x80, 0x80,
+    ];
+    pes.extend([0x05, 0x23, 0xCA, 0xE9, 0x82, 0x61]);
+    pes.resize(188, 0xFF);
+    data.extend(pes);
+    let section = SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse");
+    for packet in Packetizer::new(0x1F0, 0)
+        .packetize(&section)
+        .expect("should packetize")
+    {
+        data.extend(packet);
+    }
+    data
+}
+
+fn transport_stream_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("scte35_cli_{}_{}.ts", std::process::id(), name));
+    std::fs::write(&path, transport_stream()).expect("should write transport stream");
+    path
+}
+
+fn section_packet(pid: u16, section: &[u8]) -> Vec<u8> {
+    let [pid_high, pid_low] = pid.to_be_bytes();
+    let mut packet = vec![0x47, 0x40 | pid_high, pid_low, 0x10, 0x00];
+    packet.extend_from_slice(section);
+    packet.resize(188, 0xFF);
+    packet
+}
+
+fn long_section(table_id: u8, table_id_extension: u16, data: &[u8]) -> Vec<u8> {
+    let [length_high, length_low] = ((5 + data.len() + 4) as u16).to_be_bytes();
+    let [extension_high, extension_low] = table_id_extension.to_be_bytes();
+    let mut section = vec![
+        table_id,
+        0xB0 | length_high,
+        length_low,
+        extension_high,
+        extension_low,
+        0xC1,
+        0x00,
+        0x00,
+    ];
+    section.extend_from_slice(data);
+    let crc = section.iter().fold(0xFFFFFFFF_u32, |mut crc, byte| {
+        crc ^= (*byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x80000000 != 0 {
+                (crc << 1) ^ 0x04C11DB7
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    });
+    section.extend_from_slice(&crc.to_be_bytes());
+    section
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("should be UTF-8")
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).expect("should be UTF-8")
+}
+
+// MARK: - decode
+
+#[test]
+fn test_decode_argument_as_text() {
+    let expected = SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX)
+        .expect("should parse hex")
+        .to_string();
+    for message in [SPLICE_INSERT_HEX, SPLICE_INSERT_BASE64] {
+        let output = scte35(&["decode", message], &[]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(expected, stdout(&output));
+    }
+}
+
+#[test]
+fn test_decode_as_json() {
+    let output = scte35(&["decode", "--output", "json", SPLICE_INSERT_BASE64], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let decoded: SpliceInfoSection =
+        serde_json::from_str(&stdout(&output)).expect("should deserialize JSON");
+    let expected =
+        SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX).expect("should parse hex");
+    assert_eq!(expected, decoded);
+}
+
+#[test]
+fn test_decode_as_xml() {
+    let output = scte35(&["decode", "-o", "xml", SPLICE_INSERT_HEX], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let decoded =
+        SpliceInfoSection::try_from_xml_string(stdout(&output).trim()).expect("should parse XML");
+    let expected =
+        SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX).expect("should parse hex");
+    assert_eq!(expected.splice_command, decoded.splice_command);
+    assert_eq!(expected.splice_descriptors, decoded.splice_descriptors);
+}
+
+#[test]
+fn test_decode_stdin_lines() {
+    let stdin = format!("{}\n\n{}\n", SPLICE_INSERT_HEX, SPLICE_INSERT_BASE64);
+    let output = scte35(&["decode"], stdin.as_bytes());
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(2, stdout(&output).matches("Splice Info Section").count());
+}
+
+#[test]
+fn test_decode_stdin_binary_sections() {
+    let bytes = SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX)
+        .expect("should parse hex")
+        .try_to_bytes()
+        .expect("should encode");
+    let stdin = [bytes.as_slice(), bytes.as_slice()].concat();
+    let output = scte35(&["decode", "--input", "binary"], &stdin);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(2, stdout(&output).matches("Splice Info Section").count());
+
+    // Binary data is detected when it is not valid UTF-8.
+    let output = scte35(&["decode"], &stdin);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(2, stdout(&output).matches("Splice Info Section").count());
+}
+
+#[test]
+fn test_decode_reports_failures() {
+    let stdin = format!("{}\nFC30\n", SPLICE_INSERT_HEX);
+    let output = scte35(&["decode"], stdin.as_bytes());
+    assert!(!output.status.success());
+    assert_eq!(1, stdout(&output).matches("Splice Info Section").count());
+    let stderr = stderr(&output);
+    assert!(stderr.contains("error: line 2: "), "{}", stderr);
+    assert!(
+        stderr.ends_with("error: 1 message could not be decoded.\n"),
+        "{}",
+        stderr
+    );
+}
+
+// MARK: - encode
+
+#[test]
+fn test_encode_decoded_json() {
+    let decoded = scte35(&["decode", "--output", "json", SPLICE_INSERT_HEX], &[]);
+    assert!(decoded.status.success(), "{}", stderr(&decoded));
+
+    let output = scte35(&["encode"], &decoded.stdout);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(format!("{}\n", SPLICE_INSERT_BASE64), stdout(&output));
+
+    let output = scte35(&["encode", "--output", "hex", &stdout(&decoded)], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(format!("{}\n", SPLICE_INSERT_HEX), stdout(&output));
+}
+
+#[test]
+fn test_encode_fills_omitted_fields_with_defaults() {
+    let spec = r#"{
+        "pts_adjustment": 90000,
+        "splice_command": { "TimeSignal": { "splice_time": { "pts_time": 1924989008 } } },
+        "crc_32": 0
+    }"#;
+    let output = scte35(&["encode", "-o", "binary", spec], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let encoded = SpliceInfoSection::try_from_bytes(&output.stdout).expect("should parse");
+    let expected = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(90000))
+        .splice_command(
+            serde_json::from_str(
+                r#"{ "TimeSignal": { "splice_time": { "pts_time": 1924989008 } } }"#,
+            )
+            .expect("should deserialize command"),
+        )
+        .build()
+        .expect("should build");
+    assert_eq!(expected, encoded);
+}
+
+#[test]
+fn test_encode_reports_invalid_spec() {
+    let output = scte35(&["encode", "[]"], &[]);
+    assert!(!output.status.success());
+    assert_eq!(
+        "error: the description must be a JSON object.\n",
+        stderr(&output)
+    );
+
+    // The descriptors are validated against the command.
+    let spec = r#"{ "splice_descriptors": [{ "AvailDescriptor": { "identifier": 1129661769, "provider_avail_id": 309 } }] }"#;
+    let output = scte35(&["encode", spec], &[]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+}
+
+// MARK: - scan
+
+#[test]
+fn test_scan_discovers_pids() {
+    let path = transport_stream_file("discovers");
+    let output = scte35(&["scan", path.to_str().expect("should be UTF-8")], &[]);
+    std::fs::remove_file(&path).expect("should remove transport stream");
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let first_line = stdout.lines().next().expect("should print cue");
+    assert_eq!(
+        "PID 0x01F0 (program 1), packet 3 (offset 564), last PTS 1924809008 (21386.766756s), \
+         splice PTS 1924989008 (+2.000000s)",
+        first_line
+    );
+    assert_eq!(1, stdout.matches("Splice Info Section").count());
+    assert_eq!("Messages: 1 (in 4 packets).\n", stderr(&output));
+}
+
+#[test]
+fn test_scan_pid_as_json() {
+    let path = transport_stream_file("pid_as_json");
+    let output = scte35(
+        &[
+            "scan",
+            path.to_str().expect("should be UTF-8"),
+            "--pid",
+            "0x1F0",
+            "-o",
+            "json",
+        ],
+        &[],
+    );
+    std::fs::remove_file(&path).expect("should remove transport stream");
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cue: serde_json::Value =
+        serde_json::from_str(&stdout(&output)).expect("should deserialize JSON");
+    assert_eq!(0x1F0, cue["pid"]);
+    assert_eq!(serde_json::Value::Null, cue["program_number"]);
+    assert_eq!(564, cue["offset"]);
+    assert_eq!(1924809008_u64, cue["last_pts"]);
+    let section: SpliceInfoSection = serde_json::from_value(cue["splice_info_section"].clone())
+        .expect("should deserialize section");
+    assert_eq!(
+        SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse"),
+        section
+    );
+}
+
+#[test]
+fn test_scan_without_scte35_streams_fails() {
+    let path = transport_stream_file("without_streams");
+    let output = scte35(
+        &[
+            "scan",
+            path.to_str().expect("should be UTF-8"),
+            "--pid",
+            "8191",
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!("Messages: 0 (in 4 packets).\n", stderr(&output));
+
+    let data = std::fs::read(&path).expect("should read transport stream");
+    // Only the video packet.
+    std::fs::write(&path, &data[376..564]).expect("should write transport stream");
+    let output = scte35(&["scan", path.to_str().expect("should be UTF-8")], &[]);
+    std::fs::remove_file(&path).expect("should remove transport stream");
+    assert!(!output.status.success());
+    assert_eq!(
+        "error: no SCTE-35 streams were found (use --pid to scan a known PID).\n",
+        stderr(&output)
+    );
+}
+
+// MARK: - monitor
+
+#[test]
+fn test_monitor_stdin_prints_json_lines() {
+    let output = scte35(&["monitor"], &transport_stream());
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(1, lines.len());
+    let cue: serde_json::Value = serde_json::from_str(lines[0]).expect("should be JSON line");
+    let received_at = cue["received_at"]
+        .as_str()
+        .expect("should have received_at");
+    assert!(received_at.ends_with('Z'), "{}", received_at);
+    assert_eq!(0x1F0, cue["pid"]);
+    assert_eq!(1, cue["program_number"]);
+    assert_eq!(1924809008_u64, cue["last_pts"]);
+}
+
+#[test]
+fn test_monitor_udp_with_rtp() {
+    let port = UdpSocket::bind("127.0.0.1:0")
+        .and_then(|socket| socket.local_addr())
+        .expect("should find free port")
+        .port();
+    let address = format!("127.0.0.1:{}", port);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scte35"))
+        .args(["monitor", "--udp", &address, "--pid", "0x1F0"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("should spawn scte35");
+
+    // The datagrams are sent until the monitor has started and printed a cue.
+    let received = Arc::new(AtomicBool::new(false));
+    let sender = {
+        let received = received.clone();
+        thread::spawn(move || {
+            let socket = UdpSocket::bind("127.0.0.1:0").expect("should bind");
+            let mut datagram = vec![0x80, 0x21, 0x00, 0x01, 0, 0, 0, 0, 0, 0, 0, 1];
+            datagram.extend(transport_stream());
+            while !received.load(Ordering::SeqCst) {
+                socket
+                    .send_to(&datagram, ("127.0.0.1", port))
+                    .expect("should send");
+                thread::sleep(Duration::from_millis(20));
+            }
+        })
+    };
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().expect("should have stdout"))
+        .read_line(&mut line)
+        .expect("should read line");
+    received.store(true, Ordering::SeqCst);
+    child.kill().expect("should kill monitor");
+    child.wait().expect("should wait for monitor");
+    sender.join().expect("should join sender");
+
+    let cue: serde_json::Value = serde_json::from_str(&line).expect("should be JSON line");
+    assert_eq!(0x1F0, cue["pid"]);
+    let section: SpliceInfoSection = serde_json::from_value(cue["splice_info_section"].clone())
+        .expect("should deserialize section");
+    assert_eq!(
+        SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse"),
+        section
+    );
+}
+
+// MARK: - fix-crc
+
+#[test]
+fn test_fix_crc_preserves_encoding_and_other_bytes() {
+    let corrupted = format!(
+        "{}00000000",
+        &SPLICE_INSERT_HEX[..SPLICE_INSERT_HEX.len() - 8]
+    );
+    let output = scte35(&["fix-crc", &corrupted], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(format!("{}\n", SPLICE_INSERT_HEX), stdout(&output));
+    assert_eq!("", stderr(&output));
+
+    let output = scte35(&["fix-crc", "--output", "base64", SPLICE_INSERT_HEX], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(format!("{}\n", SPLICE_INSERT_BASE64), stdout(&output));
+    assert_eq!("warning: the crc_32 was already valid.\n", stderr(&output));
+}
+
+#[test]
+fn test_fix_crc_stdin_binary_sections() {
+    let bytes = SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX)
+        .expect("should parse hex")
+        .try_to_bytes()
+        .expect("should encode");
+    let mut corrupted = bytes.clone();
+    let crc_offset = corrupted.len() - 4;
+    corrupted[crc_offset..].copy_from_slice(&[0; 4]);
+    let stdin = [corrupted.as_slice(), corrupted.as_slice()].concat();
+    let output = scte35(&["fix-crc", "--input", "binary"], &stdin);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!([bytes.as_slice(), bytes.as_slice()].concat(), output.stdout);
+}
+
+// MARK: - restamp
+
+#[test]
+fn test_restamp_sets_pts_adjustment() {
+    let output = scte35(
+        &["restamp", "--pts-adjustment", "900", SPLICE_INSERT_BASE64],
+        &[],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.ends_with('\n'));
+    let splice_info_section = SpliceInfoSection::try_from_base64_string(stdout.trim())
+        .expect("should parse restamped message");
+    let mut expected = SpliceInfoSection::try_from_base64_string(SPLICE_INSERT_BASE64)
+        .expect("should parse base64");
+    expected.pts_adjustment = Pts90k(900);
+    expected.canonicalize().expect("should canonicalize");
+    assert_eq!(expected, splice_info_section);
+}
+
+#[test]
+fn test_restamp_adds_to_pts_adjustment_of_each_line() {
+    let stdin = format!("{}\nFC30\n{}\n", SPLICE_INSERT_HEX, SPLICE_INSERT_HEX);
+    let output = scte35(
+        &[
+            "restamp",
+            "--add-pts-adjustment",
+            "8589934591",
+            "--canonicalize",
+        ],
+        stdin.as_bytes(),
+    );
+    assert!(!output.status.success());
+    let lines = stdout(&output)
+        .lines()
+        .map(|line| {
+            SpliceInfoSection::try_from_hex_string(line)
+                .expect("should parse restamped message")
+                .pts_adjustment
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Pts90k(0x1_FFFF_FFFF), Pts90k(0x1_FFFF_FFFF)], lines);
+    let stderr = stderr(&output);
+    assert!(stderr.contains("error: line 2: "), "{}", stderr);
+    assert!(
+        stderr.ends_with("error: 1 message could not be patched.\n"),
+        "{}",
+        stderr
+    );
+}
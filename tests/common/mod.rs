@@ -0,0 +1,68 @@
+//! Fixtures shared by the integration tests (each test crate uses a different subset of them).
+#![allow(dead_code)]
+
+use scte35::{
+    hex::decode_hex,
+    splice_command::{time_signal::TimeSignal, SpliceCommand},
+    splice_descriptor::{
+        segmentation_descriptor::{SegmentationDescriptor, SegmentationTypeID},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::{Pts90k, SpliceTime},
+};
+
+// 14.1. time_signal – Placement Opportunity Start
+pub const TIME_SIGNAL_HEX: &str = "0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+// 14.2. splice_insert
+pub const SPLICE_INSERT_HEX: &str = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+
+pub fn splice_info_section(hex_string: &str) -> SpliceInfoSection {
+    SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex")
+}
+
+/// The bytes of a hex string that may be prefixed with `0x`.
+pub fn bytes(hex_string: &str) -> Vec<u8> {
+    decode_hex(hex_string.trim_start_matches("0x")).expect("should be valid hex")
+}
+
+/// A `time_signal` section that carries the segmentation descriptors.
+pub fn time_signal(
+    pts_adjustment: u64,
+    pts_time: Option<u64>,
+    descriptors: Vec<SegmentationDescriptor>,
+) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(pts_adjustment))
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: pts_time.map(Pts90k),
+            },
+        }))
+        .splice_descriptors(
+            descriptors
+                .into_iter()
+                .map(SpliceDescriptor::SegmentationDescriptor)
+                .collect(),
+        )
+        .build()
+        .expect("should build section")
+}
+
+/// A segmentation descriptor of the type, with a `segmentation_duration` when one is given (which
+/// is only meaningful for the types that start a segment).
+pub fn segmentation_descriptor(
+    event_id: u32,
+    segmentation_type_id: SegmentationTypeID,
+    duration: Option<u64>,
+) -> SegmentationDescriptor {
+    let builder =
+        SegmentationDescriptor::builder(event_id).segmentation_type_id(segmentation_type_id);
+    match duration {
+        Some(duration) => builder.duration_90khz(duration),
+        None => builder,
+    }
+    .build()
+    .expect("should build segmentation descriptor")
+}
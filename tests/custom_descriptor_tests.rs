@@ -0,0 +1,231 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{EncodeError, ParseError},
+    splice_descriptor::{
+        custom_descriptor::{CustomSpliceDescriptor, SpliceDescriptorRegistry},
+        time_descriptor::TimeDescriptor,
+        SpliceDescriptor, SpliceDescriptorTag, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+};
+
+const ABCD: u32 = 0x41424344;
+
+#[derive(Clone, Debug)]
+struct ChannelDescriptor {
+    tag: u8,
+    channel: u16,
+}
+
+impl CustomSpliceDescriptor for ChannelDescriptor {
+    fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    fn identifier(&self) -> u32 {
+        ABCD
+    }
+
+    fn private_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        Ok(self.channel.to_be_bytes().to_vec())
+    }
+}
+
+fn registry(tag: u8) -> SpliceDescriptorRegistry {
+    SpliceDescriptorRegistry::new().register(tag, ABCD, move |private_bytes| {
+        let channel = private_bytes
+            .try_into()
+            .map(u16::from_be_bytes)
+            .map_err(|_| ParseError::InvalidPrivateData("expected a 16-bit channel"))?;
+        Ok(Box::new(ChannelDescriptor { tag, channel }))
+    })
+}
+
+fn section_bytes(tag: u8, channel: u16) -> Vec<u8> {
+    SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::Custom(Box::new(ChannelDescriptor {
+            tag,
+            channel,
+        })))
+        .build()
+        .expect("should build")
+        .try_to_bytes()
+        .expect("should encode")
+}
+
+// MARK: - Parsing
+
+#[test]
+fn test_custom_descriptor_round_trip_with_registry() {
+    let bytes = section_bytes(0xF0, 513);
+    let section = SpliceInfoSection::try_from_bytes_with_registry(&bytes, &registry(0xF0))
+        .expect("should parse with registry");
+    assert_eq!(1, section.splice_descriptors.len());
+    let descriptor = &section.splice_descriptors[0];
+    assert_eq!(SpliceDescriptorTag::Private(0xF0), descriptor.tag());
+    assert_eq!(ABCD, descriptor.identifier());
+    let SpliceDescriptor::Custom(descriptor) = descriptor else {
+        panic!("should be custom descriptor");
+    };
+    let descriptor = descriptor
+        .downcast_ref::<ChannelDescriptor>()
+        .expect("should downcast to ChannelDescriptor");
+    assert_eq!(513, descriptor.channel);
+    assert_eq!(
+        bytes,
+        section
+            .try_to_bytes()
+            .expect("should encode parsed section")
+    );
+    assert!(section.non_fatal_errors.is_empty());
+}
+
+#[test]
+fn test_custom_descriptor_without_registry_is_unrecognised() {
+    let bytes = section_bytes(0xF0, 1);
+    assert_eq!(
+        Err(ParseError::UnrecognisedSpliceDescriptorTag(0xF0)),
+        SpliceInfoSection::try_from_bytes(&bytes)
+    );
+}
+
+#[test]
+fn test_custom_descriptor_registry_overrides_spec_tag() {
+    let bytes = section_bytes(0x02, 7);
+    let section = SpliceInfoSection::try_from_bytes_with_registry(&bytes, &registry(0x02))
+        .expect("should parse with registry");
+    let SpliceDescriptor::Custom(descriptor) = &section.splice_descriptors[0] else {
+        panic!("should be custom descriptor");
+    };
+    assert_eq!(
+        7,
+        descriptor
+            .downcast_ref::<ChannelDescriptor>()
+            .expect("should downcast to ChannelDescriptor")
+            .channel
+    );
+}
+
+#[test]
+fn test_custom_descriptor_parser_error_is_returned() {
+    let bytes = section_bytes(0xF0, 1);
+    let registry = SpliceDescriptorRegistry::new().register(0xF0, ABCD, |_| {
+        Err(ParseError::InvalidPrivateData("always fails"))
+    });
+    assert_eq!(
+        Err(ParseError::InvalidPrivateData("always fails")),
+        SpliceInfoSection::try_from_bytes_with_registry(&bytes, &registry)
+    );
+}
+
+// MARK: - Equality
+
+#[test]
+fn test_custom_descriptor_clone_and_eq() {
+    let descriptor = SpliceDescriptor::Custom(Box::new(ChannelDescriptor {
+        tag: 0xF0,
+        channel: 1,
+    }));
+    assert_eq!(descriptor, descriptor.clone());
+    assert_ne!(
+        descriptor,
+        SpliceDescriptor::Custom(Box::new(ChannelDescriptor {
+            tag: 0xF0,
+            channel: 2,
+        }))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_custom_descriptor_serialize() {
+    let descriptor = SpliceDescriptor::Custom(Box::new(ChannelDescriptor {
+        tag: 0xF0,
+        channel: 258,
+    }));
+    assert_eq!(
+        serde_json::json!({
+            "Custom": { "tag": 240, "identifier": ABCD, "private_bytes": [1, 2] }
+        }),
+        serde_json::to_value(&descriptor).expect("should serialize")
+    );
+}
+
+// MARK: - Unknown descriptors
+
+#[test]
+fn test_unknown_descriptor_preserved_in_lenient_mode() {
+    let unknown = SpliceDescriptor::Unknown {
+        tag: 0xF0,
+        identifier: ABCD,
+        private_bytes: vec![0x01, 0x02],
+    };
+    let time = SpliceDescriptor::TimeDescriptor(TimeDescriptor {
+        identifier: CUEI_IDENTIFIER,
+        tai_seconds: 1_700_000_000,
+        tai_ns: 0,
+        utc_offset: 37,
+    });
+    let bytes = SpliceInfoSection::builder()
+        .splice_descriptor(unknown.clone())
+        .splice_descriptor(time.clone())
+        .build()
+        .expect("should build")
+        .try_to_bytes()
+        .expect("should encode");
+    assert_eq!(
+        bytes,
+        section_bytes_with_time_descriptor(0xF0, 0x0102),
+        "Unknown should encode the same as the equivalent custom descriptor"
+    );
+    let config = ParserConfig {
+        unknown_enum_values: ErrorHandling::NonFatal,
+        ..Default::default()
+    };
+    let section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse in lenient mode");
+    assert_eq!(vec![unknown, time], section.splice_descriptors);
+    assert_eq!(
+        vec![ParseError::UnrecognisedSpliceDescriptorTag(0xF0)],
+        section.non_fatal_errors
+    );
+    assert_eq!(
+        bytes,
+        section
+            .try_to_bytes()
+            .expect("should encode parsed section")
+    );
+}
+
+#[test]
+fn test_registry_takes_precedence_over_unknown_descriptor() {
+    let config = ParserConfig {
+        descriptor_registry: Some(registry(0xF0)),
+        ..ParserConfig::lenient()
+    };
+    let section = SpliceInfoSection::try_from_bytes_with_config(&section_bytes(0xF0, 1), &config)
+        .expect("should parse with config");
+    assert!(matches!(
+        section.splice_descriptors[0],
+        SpliceDescriptor::Custom(_)
+    ));
+    assert!(section.non_fatal_errors.is_empty());
+}
+
+fn section_bytes_with_time_descriptor(tag: u8, channel: u16) -> Vec<u8> {
+    SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::Custom(Box::new(ChannelDescriptor {
+            tag,
+            channel,
+        })))
+        .splice_descriptor(SpliceDescriptor::TimeDescriptor(TimeDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            tai_seconds: 1_700_000_000,
+            tai_ns: 0,
+            utc_offset: 37,
+        }))
+        .build()
+        .expect("should build")
+        .try_to_bytes()
+        .expect("should encode")
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "dash")]
+
+use base64::prelude::*;
+use pretty_assertions::assert_eq;
+use scte35::{
+    dash::{Event, EventStream},
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+};
+
+// 14.1. time_signal – Placement Opportunity Start
+#[test]
+fn test_dash_event_time_signal() {
+    let base64_string =
+        "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
+    let splice_info_section = SpliceInfoSection::try_from_bytes(
+        &BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64"),
+    )
+    .expect("should be valid splice info section");
+    let event = splice_info_section
+        .try_to_dash_event()
+        .expect("should create dash event");
+    assert_eq!(
+        Event {
+            presentation_time: Some(1924989008),
+            duration: Some(27630000),
+            id: Some(1207959694),
+            message_data: String::from(base64_string),
+        },
+        event
+    );
+    assert_eq!(
+        concat!(
+            r#"<EventStream schemeIdUri="urn:scte:scte35:2014:xml+bin" timescale="90000">"#,
+            r#"<Event presentationTime="1924989008" duration="27630000" id="1207959694" messageData="/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg=="/>"#,
+            r#"</EventStream>"#,
+        ),
+        EventStream::new(vec![event]).to_xml_string()
+    );
+}
+
+#[test]
+fn test_dash_event_applies_pts_adjustment_and_wraps() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x1FFFFFFF0))
+        .splice_command(
+            scte35::splice_command::splice_insert::SpliceInsert::builder(7)
+                .cue_out()
+                .splice_time(Pts90k(0x20))
+                .break_duration(Pts90k(2700000), true)
+                .build()
+                .expect("should build splice insert")
+                .into(),
+        )
+        .build()
+        .expect("should build splice info section");
+    let event = splice_info_section
+        .try_to_dash_event()
+        .expect("should create dash event");
+    assert_eq!(Some(0x10), event.presentation_time);
+    assert_eq!(Some(2700000), event.duration);
+    assert_eq!(Some(7), event.id);
+}
+
+#[test]
+fn test_dash_event_splice_null() {
+    let event = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice null")
+        .try_to_dash_event()
+        .expect("should create dash event");
+    assert_eq!(
+        r#"<Event messageData="/DARAAAAAAAA///wAAAAAHYd07Y="/>"#,
+        event.to_xml_string()
+    );
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    diff::FieldDifference,
+    splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    splice_descriptor::{
+        avail_descriptor::AvailDescriptor, segmentation_descriptor::SegmentationUPID,
+        SpliceDescriptor, CUEI_IDENTIFIER,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+};
+
+fn difference(path: &str, before: Option<&str>, after: Option<&str>) -> FieldDifference {
+    FieldDifference {
+        path: String::from(path),
+        before: before.map(String::from),
+        after: after.map(String::from),
+    }
+}
+
+fn time_signal() -> SpliceInfoSection {
+    SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse time signal")
+}
+
+fn splice_insert() -> SpliceInfoSection {
+    SpliceInfoSection::try_from_hex_string(SPLICE_INSERT_HEX).expect("should parse splice insert")
+}
+
+// MARK: - Diff
+
+#[test]
+fn test_identical_sections_have_no_differences() {
+    assert_eq!(
+        Vec::<FieldDifference>::new(),
+        time_signal().diff(&time_signal())
+    );
+    assert_eq!(
+        Vec::<FieldDifference>::new(),
+        splice_insert().diff(&splice_insert())
+    );
+}
+
+#[test]
+fn test_diff_of_nested_fields() {
+    let before = splice_insert();
+    let mut after = before.clone();
+    after.tier = 0x123;
+    if let SpliceCommand::SpliceInsert(SpliceInsert {
+        scheduled_event: Some(scheduled_event),
+        ..
+    }) = &mut after.splice_command
+    {
+        scheduled_event.break_duration = None;
+        scheduled_event.avail_num = 1;
+    }
+    assert_eq!(
+        vec![
+            difference("tier", Some("0xFFF"), Some("0x123")),
+            difference(
+                "splice_command.scheduled_event.break_duration.auto_return",
+                Some("true"),
+                None
+            ),
+            difference(
+                "splice_command.scheduled_event.break_duration.duration",
+                Some("5426421 (60.293567s)"),
+                None
+            ),
+            difference(
+                "splice_command.scheduled_event.avail_num",
+                Some("0"),
+                Some("1")
+            ),
+            difference(
+                "splice_command.scheduled_event.break_duration",
+                None,
+                Some("None")
+            ),
+        ],
+        before.diff(&after)
+    );
+}
+
+#[test]
+fn test_diff_of_different_commands() {
+    let differences = splice_insert().diff(&time_signal());
+    assert_eq!(
+        Some(&difference(
+            "splice_command",
+            Some("Splice Insert (0x05)"),
+            Some("Time Signal (0x06)")
+        )),
+        differences.iter().find(|d| d.path == "splice_command")
+    );
+    assert_eq!(
+        Some(&difference(
+            "splice_command.splice_time.pts_time",
+            None,
+            Some("1924989008 (21388.766756s)")
+        )),
+        differences
+            .iter()
+            .find(|d| d.path == "splice_command.splice_time.pts_time")
+    );
+    assert!(differences
+        .iter()
+        .filter(|d| d.path.starts_with("splice_command.scheduled_event"))
+        .all(|d| d.before.is_some() && d.after.is_none()));
+}
+
+#[test]
+fn test_diff_of_descriptors() {
+    let before = time_signal();
+    let mut after = before.clone();
+    if let SpliceDescriptor::SegmentationDescriptor(descriptor) = &mut after.splice_descriptors[0] {
+        descriptor
+            .scheduled_event
+            .as_mut()
+            .expect("should have scheduled event")
+            .segmentation_upid = SegmentationUPID::AdID(String::from("ABCD0123456H"));
+    }
+    after
+        .splice_descriptors
+        .push(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            provider_avail_id: 7,
+        }));
+    assert_eq!(
+        vec![
+            difference(
+                "splice_descriptors[0].scheduled_event.segmentation_upid",
+                Some("TI (0x08): 0x000000002CA0A18A"),
+                Some("Ad-ID (0x03): ABCD0123456H")
+            ),
+            difference(
+                "splice_descriptors[1]",
+                None,
+                Some("AvailDescriptor (0x00)")
+            ),
+            difference(
+                "splice_descriptors[1].identifier",
+                None,
+                Some("CUEI (0x43554549)")
+            ),
+            difference("splice_descriptors[1].provider_avail_id", None, Some("7")),
+        ],
+        before.diff(&after)
+    );
+}
+
+#[test]
+fn test_diff_includes_crc_32() {
+    let before = time_signal();
+    let mut after = before.clone();
+    after.pts_adjustment = Pts90k(1);
+    after.canonicalize().expect("should canonicalize");
+    let differences = before.diff(&after);
+    assert_eq!(2, differences.len());
+    assert_eq!("crc_32", differences[1].path);
+}
+
+#[test]
+fn test_field_difference_display() {
+    assert_eq!(
+        "splice_descriptors[1].provider_avail_id: (absent) -> 7",
+        difference("splice_descriptors[1].provider_avail_id", None, Some("7")).to_string()
+    );
+}
@@ -0,0 +1,231 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::ParseError,
+    splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    splice_descriptor::{
+        dtmf_descriptor::{DTMFChar, DTMFDescriptor, DTMFSequence, DTMFTone, DTMFToneTiming},
+        SpliceDescriptor,
+    },
+    splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+    time::Pts90k,
+    timeline::Arrival,
+};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn dtmf_descriptor(dtmf_chars: &str) -> DTMFDescriptor {
+    DTMFDescriptor {
+        identifier: 0x43554549,
+        preroll: 50,
+        dtmf_chars: String::from(dtmf_chars),
+    }
+}
+
+fn section_with(dtmf_descriptor: DTMFDescriptor) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(SpliceInsert {
+            event_id: 1,
+            scheduled_event: None,
+        }))
+        .splice_descriptor(SpliceDescriptor::DTMFDescriptor(dtmf_descriptor))
+        .build()
+        .expect("should build section")
+}
+
+// The encoded section with the `dtmf_chars` of "121#" replaced by "12A#".
+fn bytes_with_invalid_dtmf_chars() -> Vec<u8> {
+    let mut bytes = section_with(dtmf_descriptor("121#"))
+        .try_to_bytes()
+        .expect("should encode section");
+    let offset = bytes
+        .windows(4)
+        .position(|window| window == b"121#")
+        .expect("should contain dtmf_chars");
+    bytes[offset + 2] = b'A';
+    bytes
+}
+
+// MARK: - DTMFSequence
+
+#[test]
+fn test_dtmf_sequence_from_str() {
+    let sequence: DTMFSequence = "0123456789*#".parse().expect("should be valid sequence");
+    assert_eq!(
+        &[
+            DTMFChar::Zero,
+            DTMFChar::One,
+            DTMFChar::Two,
+            DTMFChar::Three,
+            DTMFChar::Four,
+            DTMFChar::Five,
+            DTMFChar::Six,
+            DTMFChar::Seven,
+            DTMFChar::Eight,
+            DTMFChar::Nine,
+            DTMFChar::Star,
+            DTMFChar::Pound,
+        ],
+        sequence.chars()
+    );
+    assert_eq!("0123456789*#", sequence.to_string());
+    assert_eq!(
+        DTMFSequence::default(),
+        "".parse().expect("should be valid")
+    );
+    assert_eq!(
+        Err(ParseError::InvalidDTMFChars(String::from("12A#"))),
+        "12A#".parse::<DTMFSequence>()
+    );
+}
+
+#[test]
+fn test_dtmf_char_frequencies() {
+    assert_eq!((697, 1209), DTMFChar::One.frequencies());
+    assert_eq!((770, 1336), DTMFChar::Five.frequencies());
+    assert_eq!((852, 1477), DTMFChar::Nine.frequencies());
+    assert_eq!((941, 1209), DTMFChar::Star.frequencies());
+    assert_eq!((941, 1336), DTMFChar::Zero.frequencies());
+    assert_eq!((941, 1477), DTMFChar::Pound.frequencies());
+    assert_eq!(Ok(DTMFChar::Pound), DTMFChar::try_from('#'));
+    assert_eq!(
+        Err(ParseError::InvalidDTMFChars(String::from("D"))),
+        DTMFChar::try_from('D')
+    );
+}
+
+#[test]
+fn test_dtmf_descriptor_sequence() {
+    assert_eq!(
+        Ok(DTMFSequence::new(vec![
+            DTMFChar::One,
+            DTMFChar::Two,
+            DTMFChar::One,
+            DTMFChar::Pound
+        ])),
+        dtmf_descriptor("121#").sequence()
+    );
+    assert!(dtmf_descriptor("12 #").sequence().is_err());
+}
+
+// MARK: - Validation
+
+#[test]
+fn test_invalid_dtmf_chars_are_non_fatal_by_default() {
+    let splice_info_section = SpliceInfoSection::try_from_bytes(&bytes_with_invalid_dtmf_chars())
+        .expect("should parse section");
+    assert_eq!(
+        Some("12A#"),
+        splice_info_section
+            .dtmf_descriptors()
+            .next()
+            .map(|descriptor| descriptor.dtmf_chars.as_str())
+    );
+    assert_eq!(
+        vec![ParseError::InvalidDTMFChars(String::from("12A#"))],
+        splice_info_section.non_fatal_errors
+    );
+}
+
+#[test]
+fn test_invalid_dtmf_chars_handling() {
+    let bytes = bytes_with_invalid_dtmf_chars();
+    assert_eq!(
+        Err(ParseError::InvalidDTMFChars(String::from("12A#"))),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::strict()).map(|_| ())
+    );
+    let config = ParserConfig {
+        invalid_dtmf_chars: ErrorHandling::Ignore,
+        ..ParserConfig::default()
+    };
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse section");
+    assert_eq!(
+        Vec::<ParseError>::new(),
+        splice_info_section.non_fatal_errors
+    );
+}
+
+#[test]
+fn test_invalid_dtmf_chars_round_trip() {
+    let bytes = bytes_with_invalid_dtmf_chars();
+    let splice_info_section =
+        SpliceInfoSection::try_from_bytes(&bytes).expect("should parse section");
+    let encoded = splice_info_section
+        .try_to_bytes()
+        .expect("should encode the dtmf_chars that were accepted on parse");
+    // Only the crc_32 differs, as it was not updated when the dtmf_chars were replaced.
+    assert_eq!(bytes[..bytes.len() - 4], encoded[..encoded.len() - 4]);
+}
+
+// MARK: - Schedule
+
+#[test]
+fn test_schedule_ends_preroll_before_splice() {
+    let timing = DTMFToneTiming {
+        tone_duration: Duration::from_millis(50),
+        pause_duration: Duration::from_millis(20),
+    };
+    let schedule = dtmf_descriptor("121#")
+        .schedule(Pts90k(1_000_000), timing)
+        .expect("should schedule");
+    assert_eq!(Pts90k(550_000), schedule.end_pts);
+    assert_eq!(
+        vec![
+            DTMFTone {
+                dtmf_char: DTMFChar::One,
+                start_pts: Pts90k(526_600),
+                end_pts: Pts90k(531_100),
+            },
+            DTMFTone {
+                dtmf_char: DTMFChar::Two,
+                start_pts: Pts90k(532_900),
+                end_pts: Pts90k(537_400),
+            },
+            DTMFTone {
+                dtmf_char: DTMFChar::One,
+                start_pts: Pts90k(539_200),
+                end_pts: Pts90k(543_700),
+            },
+            DTMFTone {
+                dtmf_char: DTMFChar::Pound,
+                start_pts: Pts90k(545_500),
+                end_pts: Pts90k(550_000),
+            },
+        ],
+        schedule.tones
+    );
+    assert_eq!(Pts90k(526_600), schedule.start_pts());
+}
+
+#[test]
+fn test_schedule_wraps_and_relates_to_wallclock() {
+    let schedule = dtmf_descriptor("5")
+        .schedule(Pts90k(90_000), DTMFToneTiming::default())
+        .expect("should schedule");
+    assert_eq!(Pts90k(Pts90k::WRAP - 360_000), schedule.end_pts);
+    assert_eq!(Pts90k(Pts90k::WRAP - 369_000), schedule.start_pts());
+
+    let wallclock = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let arrival = Arrival::new(Pts90k(Pts90k::WRAP - 900_000)).wallclock(wallclock);
+    assert_eq!(
+        Some(wallclock + Duration::from_millis(5_900)),
+        arrival.wallclock_at(schedule.start_pts())
+    );
+    assert_eq!(
+        Some(wallclock + Duration::from_secs(6)),
+        arrival.wallclock_at(schedule.end_pts)
+    );
+    assert_eq!(None, Arrival::new(Pts90k(0)).wallclock_at(schedule.end_pts));
+}
+
+#[test]
+fn test_schedule_of_empty_and_invalid_sequences() {
+    let schedule = dtmf_descriptor("")
+        .schedule(Pts90k(900_000), DTMFToneTiming::default())
+        .expect("should schedule");
+    assert_eq!(Vec::<DTMFTone>::new(), schedule.tones);
+    assert_eq!(Pts90k(450_000), schedule.start_pts());
+    assert_eq!(
+        Err(ParseError::InvalidDTMFChars(String::from("12A#"))),
+        dtmf_descriptor("12A#").schedule(Pts90k(900_000), DTMFToneTiming::default())
+    );
+}
@@ -0,0 +1,273 @@
+mod common;
+
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    edit::SectionEditor,
+    error::{EncodeError, ParseError, SectionEditError},
+    splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+    time::Pts90k,
+};
+
+fn bytes_from_hex(hex_string: &str) -> Vec<u8> {
+    SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should parse section")
+        .try_to_bytes()
+        .expect("should encode section")
+}
+
+fn parse_validating_crc(bytes: &[u8]) -> SpliceInfoSection {
+    let config = ParserConfig {
+        crc_mismatch: ErrorHandling::Fatal,
+        ..ParserConfig::default()
+    };
+    SpliceInfoSection::try_from_bytes_with_config(bytes, &config)
+        .expect("should parse section with valid crc")
+}
+
+// MARK: - pts_adjustment
+
+#[test]
+fn test_set_pts_adjustment_only_changes_pts_adjustment_and_crc() {
+    let original = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut bytes = original.clone();
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert_eq!(Pts90k(0), editor.pts_adjustment());
+    editor
+        .set_pts_adjustment(Pts90k(0x1_2345_6789))
+        .expect("should set pts_adjustment");
+    assert_eq!(Pts90k(0x1_2345_6789), editor.pts_adjustment());
+
+    let changed_indices = original
+        .iter()
+        .zip(bytes.iter())
+        .enumerate()
+        .filter(|(_, (original, edited))| original != edited)
+        .map(|(index, _)| index)
+        .filter(|index| *index < original.len() - 4)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![4, 5, 6, 7, 8], changed_indices);
+
+    let mut expected = SpliceInfoSection::try_from_bytes(&original).expect("should parse");
+    expected.pts_adjustment = Pts90k(0x1_2345_6789);
+    let expected_bytes = expected.canonicalize().expect("should canonicalize");
+    assert_eq!(expected_bytes, bytes);
+    assert_eq!(expected, parse_validating_crc(&bytes));
+}
+
+#[test]
+fn test_add_pts_adjustment_wraps_at_33_bits() {
+    let mut bytes = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    editor
+        .set_pts_adjustment(Pts90k::MAX)
+        .expect("should set pts_adjustment");
+    editor
+        .add_pts_adjustment(Pts90k(10))
+        .expect("should add to pts_adjustment");
+    assert_eq!(Pts90k(9), editor.pts_adjustment());
+    assert_eq!(Pts90k(9), parse_validating_crc(&bytes).pts_adjustment);
+}
+
+#[test]
+fn test_set_pts_adjustment_out_of_range() {
+    let original = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut bytes = original.clone();
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert_eq!(
+        Err(SectionEditError::EncodeError(
+            EncodeError::ValueOutOfRange {
+                value: 1 << 33,
+                maximum_bits: 33,
+                description: "SpliceInfoSection pts_adjustment",
+            }
+        )),
+        editor.set_pts_adjustment(Pts90k(1 << 33))
+    );
+    assert_eq!(original, bytes);
+}
+
+// MARK: - tier
+
+#[test]
+fn test_set_tier() {
+    let mut bytes = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert_eq!(0xFFF, editor.tier());
+    editor.set_tier(0x123).expect("should set tier");
+    assert_eq!(0x123, editor.tier());
+    let section = parse_validating_crc(&bytes);
+    assert_eq!(0x123, section.tier);
+    assert_eq!(
+        Err(SectionEditError::EncodeError(
+            EncodeError::ValueOutOfRange {
+                value: 0x1000,
+                maximum_bits: 12,
+                description: "SpliceInfoSection tier",
+            }
+        )),
+        SectionEditor::new(&mut bytes)
+            .expect("should create editor")
+            .set_tier(0x1000)
+    );
+}
+
+// MARK: - crc_32
+
+#[test]
+fn test_recalculate_crc_32() {
+    let original = bytes_from_hex(SPLICE_INSERT_HEX);
+    let mut bytes = original.clone();
+    let crc_offset = bytes.len() - 4;
+    bytes[crc_offset..].copy_from_slice(&[0; 4]);
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert_eq!(0, editor.crc_32());
+    assert!(editor.recalculate_crc_32());
+    assert_eq!(0x62DBA30A, editor.crc_32());
+    assert!(!editor.recalculate_crc_32());
+    assert_eq!(original, editor.as_bytes());
+    assert_eq!(original, bytes);
+}
+
+// MARK: - pts_time
+
+#[test]
+fn test_set_pts_time_of_time_signal() {
+    let mut bytes = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert_eq!(Some(Pts90k(1924989008)), editor.pts_time());
+    editor
+        .set_pts_time(Pts90k(0x1_0000_0001))
+        .expect("should set pts_time");
+    assert_eq!(Some(Pts90k(0x1_0000_0001)), editor.pts_time());
+    assert_eq!(
+        Some(Pts90k(0x1_0000_0001)),
+        parse_validating_crc(&bytes).splice_pts()
+    );
+}
+
+#[test]
+fn test_set_pts_time_of_splice_insert() {
+    let original = bytes_from_hex(SPLICE_INSERT_HEX);
+    let mut bytes = original.clone();
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    let original_pts_time = editor.pts_time().expect("should have pts_time");
+    editor
+        .set_pts_time(original_pts_time + Pts90k(90_000))
+        .expect("should set pts_time");
+
+    let edited = parse_validating_crc(&bytes);
+    assert_eq!(
+        Some(original_pts_time + Pts90k(90_000)),
+        edited.splice_pts()
+    );
+    let mut expected = SpliceInfoSection::try_from_bytes(&original).expect("should parse");
+    expected.splice_command = edited.splice_command.clone();
+    expected.crc_32 = edited.crc_32;
+    assert_eq!(expected, edited);
+    assert_eq!(original[..20], bytes[..20]);
+    assert_eq!(original[25..original.len() - 4], bytes[25..bytes.len() - 4]);
+}
+
+#[test]
+fn test_set_pts_time_without_pts_time() {
+    let immediate = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .cue_out()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section");
+    let cancelled = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .cancel()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section");
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section");
+    for mut bytes in [immediate, cancelled, splice_null] {
+        let original = bytes.clone();
+        let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+        assert_eq!(None, editor.pts_time());
+        assert_eq!(
+            Err(SectionEditError::MissingPTSTime),
+            editor.set_pts_time(Pts90k(0))
+        );
+        assert_eq!(original, bytes);
+    }
+}
+
+#[test]
+fn test_set_pts_time_of_encrypted_section() {
+    let mut bytes = bytes_from_hex(TIME_SIGNAL_HEX);
+    bytes[4] |= 0x80;
+    let mut editor = SectionEditor::new(&mut bytes).expect("should create editor");
+    assert!(editor.is_encrypted());
+    assert_eq!(None, editor.pts_time());
+    assert_eq!(
+        Err(SectionEditError::EncryptedPacket),
+        editor.set_pts_time(Pts90k(0))
+    );
+    editor
+        .set_pts_adjustment(Pts90k(1))
+        .expect("should set pts_adjustment of encrypted section");
+}
+
+// MARK: - Section bounds
+
+#[test]
+fn test_bytes_after_section_are_not_edited() {
+    let section = bytes_from_hex(TIME_SIGNAL_HEX);
+    let mut bytes = section.clone();
+    bytes.extend_from_slice(&[0xFF; 8]);
+    SectionEditor::new(&mut bytes)
+        .expect("should create editor")
+        .set_pts_adjustment(Pts90k(1))
+        .expect("should set pts_adjustment");
+    assert_eq!(vec![0xFF; 8], bytes[section.len()..]);
+    assert_eq!(
+        Pts90k(1),
+        parse_validating_crc(&bytes[..section.len()]).pts_adjustment
+    );
+}
+
+#[test]
+fn test_truncated_section() {
+    let mut bytes = bytes_from_hex(TIME_SIGNAL_HEX);
+    let length = bytes.len();
+    bytes.truncate(length - 1);
+    assert_eq!(
+        Err(SectionEditError::ParseError(
+            ParseError::UnexpectedEndOfData {
+                expected_minimum_bits_left: length as u32 * 8,
+                actual_bits_left: (length as u32 - 1) * 8,
+                description: "section",
+            }
+        )),
+        SectionEditor::new(&mut bytes)
+    );
+    assert_eq!(
+        Err(SectionEditError::ParseError(
+            ParseError::UnexpectedEndOfData {
+                expected_minimum_bits_left: 24,
+                actual_bits_left: 16,
+                description: "section_length",
+            }
+        )),
+        SectionEditor::new(&mut [0xFC, 0x30])
+    );
+}
@@ -0,0 +1,74 @@
+use pretty_assertions::assert_eq;
+use scte35::{eidr::Eidr, error::ParseError};
+
+// MARK: - Canonical representation
+
+#[test]
+fn test_content_id_from_canonical() {
+    let eidr: Eidr = "10.5240/F85A-E100-B068-5B8F-B1C8-T"
+        .parse()
+        .expect("should be valid EIDR");
+    assert_eq!(
+        Eidr {
+            sub_prefix: 5240,
+            suffix: [0xF8, 0x5A, 0xE1, 0x00, 0xB0, 0x68, 0x5B, 0x8F, 0xB1, 0xC8],
+        },
+        eidr
+    );
+    assert!(eidr.is_full_suffix());
+    assert_eq!(Some('T'), eidr.check_character());
+    assert_eq!("10.5240/F85A-E100-B068-5B8F-B1C8-T", eidr.to_string());
+}
+
+#[test]
+fn test_check_character_is_optional_and_case_insensitive() {
+    let expected = Eidr {
+        sub_prefix: 5240,
+        suffix: [0xF8, 0x5A, 0xE1, 0x00, 0xB0, 0x68, 0x5B, 0x8F, 0xB1, 0xC8],
+    };
+    assert_eq!(Ok(expected), "10.5240/F85A-E100-B068-5B8F-B1C8".parse());
+    assert_eq!(Ok(expected), "10.5240/f85a-e100-b068-5b8f-b1c8-t".parse());
+}
+
+#[test]
+fn test_short_suffix_is_not_padded() {
+    let eidr: Eidr = "10.5239/8BE5-E3F6".parse().expect("should be valid EIDR");
+    assert!(!eidr.is_full_suffix());
+    assert_eq!(None, eidr.check_character());
+    assert_eq!("10.5239/8BE5-E3F6", eidr.to_string());
+    // The padded form written by earlier versions is still accepted.
+    assert_eq!(Ok(eidr), "10.5239/8BE5-E3F6-0000-0000-0000-B".parse());
+}
+
+#[test]
+fn test_invalid_canonical_representations() {
+    for string in [
+        "5240/F85A-E100-B068-5B8F-B1C8-T",
+        "10.5240",
+        "10.70000/8BE5-E3F6",
+        "10.5240/F85A-E100-B068-5B8F-B1C8-A",
+        "10.5240/F85A-E100",
+        "10.5239/8BE5-E3F6-0000",
+        "10.5239/8BE5-E3G6",
+        "10.5239/8BE5-E3F6-B",
+    ] {
+        assert_eq!(
+            Err(ParseError::InvalidEIDR(String::from(string))),
+            string.parse::<Eidr>(),
+            "{}",
+            string
+        );
+    }
+}
+
+// MARK: - Compact binary
+
+#[test]
+fn test_compact_binary_round_trip() {
+    let bytes = [
+        0x14, 0x78, 0xF8, 0x5A, 0xE1, 0x00, 0xB0, 0x68, 0x5B, 0x8F, 0xB1, 0xC8,
+    ];
+    let eidr = Eidr::from_compact_binary(bytes);
+    assert_eq!("10.5240/F85A-E100-B068-5B8F-B1C8-T", eidr.to_string());
+    assert_eq!(bytes, eidr.to_compact_binary());
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use common::{bytes, splice_info_section, SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    emsg::{find_event_messages, EventMessage, PresentationTime},
+    error::EmsgError,
+    splice_info_section::SpliceInfoSection,
+};
+
+// MARK: - emsg parsing
+
+#[test]
+fn test_emsg_v0() {
+    let message_data = bytes(SPLICE_INSERT_HEX);
+    let data = emsg_v0(
+        "urn:scte:scte35:2013:bin",
+        "",
+        90000,
+        2700,
+        5426421,
+        1,
+        &message_data,
+    );
+    let event_message = EventMessage::try_from_bytes(&data).expect("should parse emsg");
+    assert_eq!(
+        EventMessage {
+            scheme_id_uri: String::from("urn:scte:scte35:2013:bin"),
+            value: String::new(),
+            timescale: 90000,
+            presentation_time: PresentationTime::Delta(2700),
+            event_duration: 5426421,
+            id: 1,
+            message_data,
+        },
+        event_message
+    );
+    assert_eq!(
+        Ok(splice_info_section(SPLICE_INSERT_HEX)),
+        event_message.try_splice_info_section()
+    );
+}
+
+#[test]
+fn test_emsg_v1() {
+    let data = emsg_v1(
+        "urn:scte:scte35:2013:bin",
+        "1",
+        1000,
+        0x1_0000_0000,
+        60294,
+        1207959695,
+        &bytes(SPLICE_INSERT_HEX),
+    );
+    let event_message = EventMessage::try_from_bytes(&data).expect("should parse emsg");
+    assert_eq!("1", event_message.value);
+    assert_eq!(1000, event_message.timescale);
+    assert_eq!(
+        PresentationTime::Absolute(0x1_0000_0000),
+        event_message.presentation_time
+    );
+    assert_eq!(60294, event_message.event_duration);
+    assert_eq!(1207959695, event_message.id);
+    assert_eq!(
+        Ok(splice_info_section(SPLICE_INSERT_HEX)),
+        event_message.try_splice_info_section()
+    );
+}
+
+#[test]
+fn test_find_event_messages_in_chunk() {
+    let scte35 = emsg_v1(
+        "urn:scte:scte35:2013:bin",
+        "",
+        90000,
+        1936310318,
+        5426421,
+        1,
+        &bytes(SPLICE_INSERT_HEX),
+    );
+    let id3 = emsg_v1("https://aomedia.org/emsg/ID3", "", 90000, 0, 0, 2, b"ID3");
+    let data = [
+        iso_box(b"styp", b"cmfc"),
+        scte35.clone(),
+        id3.clone(),
+        iso_box(b"moof", &[0; 16]),
+    ]
+    .concat();
+    let event_messages = find_event_messages(&data).expect("should find emsg boxes");
+    assert_eq!(
+        vec![
+            EventMessage::try_from_bytes(&scte35).expect("should parse emsg"),
+            EventMessage::try_from_bytes(&id3).expect("should parse emsg")
+        ],
+        event_messages
+    );
+    assert!(event_messages[0].is_scte35());
+    assert!(!event_messages[1].is_scte35());
+    assert_eq!(
+        Err(EmsgError::UnexpectedScheme(String::from(
+            "https://aomedia.org/emsg/ID3"
+        ))),
+        event_messages[1].try_splice_info_section()
+    );
+}
+
+#[test]
+fn test_emsg_errors() {
+    assert_eq!(
+        Err(EmsgError::UnexpectedBoxType(String::from("moof"))),
+        EventMessage::try_from_bytes(&iso_box(b"moof", &[0; 16]))
+    );
+    assert_eq!(
+        Err(EmsgError::UnsupportedVersion(2)),
+        EventMessage::try_from_bytes(&iso_box(b"emsg", &[2, 0, 0, 0]))
+    );
+    let data = emsg_v1("urn:scte:scte35:2013:bin", "", 90000, 0, 0, 1, &[]);
+    assert_eq!(
+        Err(EmsgError::InvalidBoxSize(data.len() as u64)),
+        EventMessage::try_from_bytes(&data[..data.len() - 1])
+    );
+    assert_eq!(
+        Err(EmsgError::UnexpectedEndOfData {
+            description: "timescale"
+        }),
+        EventMessage::try_from_bytes(&iso_box(b"emsg", &[1, 0, 0, 0, 0, 1]))
+    );
+    assert_eq!(
+        Err(EmsgError::InvalidString {
+            field: "scheme_id_uri"
+        }),
+        EventMessage::try_from_bytes(&iso_box(b"emsg", &[0, 0, 0, 0, b'u', b'r', b'n']))
+    );
+}
+
+// MARK: - emsg generation
+
+#[test]
+fn test_emsg_to_bytes() {
+    let v0 = emsg_v0(
+        "urn:scte:scte35:2013:bin",
+        "",
+        90000,
+        2700,
+        5426421,
+        1,
+        &bytes(SPLICE_INSERT_HEX),
+    );
+    let v1 = emsg_v1(
+        "urn:scte:scte35:2013:bin",
+        "1",
+        1000,
+        0x1_0000_0000,
+        60294,
+        1207959695,
+        &bytes(SPLICE_INSERT_HEX),
+    );
+    for data in [v0, v1] {
+        assert_eq!(
+            data,
+            EventMessage::try_from_bytes(&data)
+                .expect("should parse emsg")
+                .to_bytes()
+        );
+    }
+}
+
+#[test]
+fn test_splice_info_section_to_emsg() {
+    let time_signal = splice_info_section(TIME_SIGNAL_HEX);
+    assert_eq!(
+        emsg_v1(
+            "urn:scte:scte35:2013:bin",
+            "",
+            48000,
+            1026660804,
+            14736000,
+            1207959694,
+            &bytes(TIME_SIGNAL_HEX)
+        ),
+        time_signal
+            .try_to_emsg(48000, 1026660804)
+            .expect("should create emsg")
+    );
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice null");
+    let event_message = splice_null
+        .try_to_event_message(90000, 0)
+        .expect("should create emsg");
+    assert_eq!(0xFFFFFFFF, event_message.event_duration);
+    assert_eq!(0, event_message.id);
+}
+
+// MARK: - Helpers
+
+fn iso_box(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    [
+        &((8 + payload.len()) as u32).to_be_bytes()[..],
+        box_type,
+        payload,
+    ]
+    .concat()
+}
+
+fn emsg_v0(
+    scheme_id_uri: &str,
+    value: &str,
+    timescale: u32,
+    presentation_time_delta: u32,
+    event_duration: u32,
+    id: u32,
+    message_data: &[u8],
+) -> Vec<u8> {
+    let payload = [
+        &[0, 0, 0, 0][..],
+        scheme_id_uri.as_bytes(),
+        &[0],
+        value.as_bytes(),
+        &[0],
+        &timescale.to_be_bytes(),
+        &presentation_time_delta.to_be_bytes(),
+        &event_duration.to_be_bytes(),
+        &id.to_be_bytes(),
+        message_data,
+    ]
+    .concat();
+    iso_box(b"emsg", &payload)
+}
+
+fn emsg_v1(
+    scheme_id_uri: &str,
+    value: &str,
+    timescale: u32,
+    presentation_time: u64,
+    event_duration: u32,
+    id: u32,
+    message_data: &[u8],
+) -> Vec<u8> {
+    let payload = [
+        &[1, 0, 0, 0][..],
+        &timescale.to_be_bytes(),
+        &presentation_time.to_be_bytes(),
+        &event_duration.to_be_bytes(),
+        &id.to_be_bytes(),
+        scheme_id_uri.as_bytes(),
+        &[0],
+        value.as_bytes(),
+        &[0],
+        message_data,
+    ]
+    .concat();
+    iso_box(b"emsg", &payload)
+}
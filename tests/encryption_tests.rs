@@ -0,0 +1,363 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    encryption::Decryptor,
+    error::ParseError,
+    splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    splice_descriptor::{avail_descriptor::AvailDescriptor, SpliceDescriptor, CUEI_IDENTIFIER},
+    splice_info_section::{
+        EncryptedPacket, EncryptionAlgorithm, ErrorHandling, ParserConfig, SpliceInfoSection,
+    },
+    time::Pts90k,
+};
+use std::sync::Arc;
+
+const CW_INDEX: u8 = 3;
+const CONTROL_WORD: u8 = 0xA5;
+const USER_PRIVATE_ALGORITHM: u8 = 40;
+
+#[derive(Debug)]
+struct XorDecryptor {
+    control_word: u8,
+}
+
+impl Decryptor for XorDecryptor {
+    fn decrypt(
+        &self,
+        encryption_algorithm: &EncryptionAlgorithm,
+        cw_index: u8,
+        data: &[u8],
+    ) -> Result<Vec<u8>, ParseError> {
+        if encryption_algorithm != &EncryptionAlgorithm::UserPrivate(USER_PRIVATE_ALGORITHM) {
+            return Err(ParseError::DecryptionFailed(
+                "unsupported encryption_algorithm",
+            ));
+        }
+        if cw_index != CW_INDEX {
+            return Err(ParseError::DecryptionFailed("unknown cw_index"));
+        }
+        Ok(data.iter().map(|byte| byte ^ self.control_word).collect())
+    }
+}
+
+fn config(control_word: u8) -> ParserConfig {
+    ParserConfig {
+        decryptor: Some(Arc::new(XorDecryptor { control_word })),
+        ..ParserConfig::strict()
+    }
+}
+
+fn crc_32_mpeg_2(data: &[u8]) -> u32 {
+    data.iter().fold(0xFFFFFFFF, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u32) << 24), |crc, _| {
+            if crc & 0x80000000 != 0 {
+                (crc << 1) ^ 0x04C11DB7
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+// Encrypts the bytes of a clear section with the user private XOR algorithm (see `encrypt_with`).
+fn encrypt(clear: &[u8]) -> (Vec<u8>, EncryptedPacket) {
+    encrypt_with(clear, USER_PRIVATE_ALGORITHM, |data| {
+        data.iter_mut().for_each(|byte| *byte ^= CONTROL_WORD)
+    })
+}
+
+// Encrypts the bytes of a clear section with `encrypt_data`, with alignment stuffing so that the
+// encrypted portion is a multiple of 8 bytes (as DES requires). Returns the encrypted bytes and
+// the `EncryptedPacket`.
+fn encrypt_with(
+    clear: &[u8],
+    encryption_algorithm: u8,
+    encrypt_data: impl Fn(&mut [u8]),
+) -> (Vec<u8>, EncryptedPacket) {
+    let (header, rest) = clear.split_at(13);
+    let mut encrypted = rest[..rest.len() - 4].to_vec();
+    let mut alignment_stuffing = 0;
+    while !(encrypted.len() + 4).is_multiple_of(8) {
+        encrypted.push(0xFF);
+        alignment_stuffing += 1;
+    }
+    let e_crc_32 = crc_32_mpeg_2(&encrypted);
+    encrypted.extend(e_crc_32.to_be_bytes());
+    encrypt_data(&mut encrypted);
+    let mut bytes = header.to_vec();
+    bytes.extend(encrypted);
+    let section_length = (bytes.len() + 4 - 3) as u16;
+    bytes[1] = (bytes[1] & 0xF0) | (section_length >> 8) as u8;
+    bytes[2] = section_length as u8;
+    bytes[4] |= 0x80 | (encryption_algorithm << 1);
+    bytes[9] = CW_INDEX;
+    let crc_32 = crc_32_mpeg_2(&bytes);
+    bytes.extend(crc_32.to_be_bytes());
+    let encrypted_packet = EncryptedPacket {
+        encryption_algorithm: EncryptionAlgorithm::from_value(encryption_algorithm),
+        cw_index: CW_INDEX,
+        alignment_stuffing,
+        e_crc_32,
+    };
+    (bytes, encrypted_packet)
+}
+
+fn splice_insert_bytes() -> Vec<u8> {
+    SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(0x4800008F)
+                .out_of_network_indicator(true)
+                .splice_time(Pts90k(0x07369C02E))
+                .break_duration(Pts90k(0x00052CCF5), true)
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .splice_descriptor(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            provider_avail_id: 309,
+        }))
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section")
+}
+
+// MARK: - Decryption
+
+#[test]
+fn test_encrypted_section_is_decrypted() {
+    let clear_bytes = splice_insert_bytes();
+    let clear_section =
+        SpliceInfoSection::try_from_bytes(&clear_bytes).expect("should parse clear section");
+    let (bytes, encrypted_packet) = encrypt(&clear_bytes);
+    assert_ne!(0, encrypted_packet.alignment_stuffing);
+    assert_eq!(
+        SpliceInfoSection {
+            encrypted_packet: Some(encrypted_packet),
+            crc_32: u32::from_be_bytes(
+                bytes[bytes.len() - 4..]
+                    .try_into()
+                    .expect("should have crc_32")
+            ),
+            ..clear_section
+        },
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config(CONTROL_WORD))
+            .expect("should parse encrypted section")
+    );
+}
+
+#[test]
+fn test_encrypted_section_without_decryptor_is_not_supported() {
+    let (bytes, _) = encrypt(&splice_insert_bytes());
+    assert_eq!(
+        Err(ParseError::EncryptedMessageNotSupported),
+        SpliceInfoSection::try_from_bytes(&bytes)
+    );
+}
+
+#[test]
+fn test_decryptor_error_fails_parse() {
+    let (mut bytes, _) = encrypt(&splice_insert_bytes());
+    bytes[9] = CW_INDEX + 1;
+    let config = ParserConfig {
+        crc_mismatch: ErrorHandling::Ignore,
+        ..config(CONTROL_WORD)
+    };
+    assert_eq!(
+        Err(ParseError::DecryptionFailed("unknown cw_index")),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+    );
+}
+
+#[test]
+fn test_wrong_control_word_is_e_crc_mismatch() {
+    let (bytes, _) = encrypt(&splice_insert_bytes());
+    assert!(matches!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config(0x5A)),
+        Err(ParseError::ECRCMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_decrypted_non_fatal_errors_are_recorded() {
+    let mut clear_bytes = splice_insert_bytes();
+    // The splice_command_length is increased by one byte (taken from the descriptor_loop_length).
+    clear_bytes[12] += 1;
+    let clear_section =
+        SpliceInfoSection::try_from_bytes(&clear_bytes).expect("should parse clear section");
+    assert!(matches!(
+        clear_section.non_fatal_errors.as_slice(),
+        [ParseError::UnexpectedSpliceCommandLength { .. }]
+    ));
+    let (bytes, _) = encrypt(&clear_bytes);
+    let config = ParserConfig {
+        length_mismatch: ErrorHandling::NonFatal,
+        ..config(CONTROL_WORD)
+    };
+    let section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse encrypted section");
+    assert_eq!(clear_section.non_fatal_errors, section.non_fatal_errors);
+}
+
+#[test]
+fn test_peek_header_of_encrypted_section() {
+    let (bytes, _) = encrypt(&splice_insert_bytes());
+    let header = SpliceInfoSection::peek_header(&bytes).expect("should peek header");
+    assert!(header.encrypted_packet);
+    assert_eq!(
+        EncryptionAlgorithm::UserPrivate(USER_PRIVATE_ALGORITHM),
+        header.encryption_algorithm
+    );
+    assert_eq!(CW_INDEX, header.cw_index);
+    assert_eq!(None, header.splice_command_type);
+    assert_eq!(None, header.pts_time);
+}
+
+#[test]
+fn test_reserved_encryption_algorithm_is_preserved() {
+    let (bytes, _) = encrypt_with(&splice_insert_bytes(), 17, |data| {
+        data.iter_mut().for_each(|byte| *byte ^= CONTROL_WORD)
+    });
+    assert_eq!(
+        Err(ParseError::DecryptionFailed(
+            "unsupported encryption_algorithm"
+        )),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config(CONTROL_WORD))
+    );
+
+    #[derive(Debug)]
+    struct ReservedDecryptor;
+
+    impl Decryptor for ReservedDecryptor {
+        fn decrypt(
+            &self,
+            encryption_algorithm: &EncryptionAlgorithm,
+            _: u8,
+            data: &[u8],
+        ) -> Result<Vec<u8>, ParseError> {
+            assert_eq!(&EncryptionAlgorithm::Reserved(17), encryption_algorithm);
+            Ok(data.iter().map(|byte| byte ^ CONTROL_WORD).collect())
+        }
+    }
+
+    let config = ParserConfig {
+        decryptor: Some(Arc::new(ReservedDecryptor)),
+        ..ParserConfig::strict()
+    };
+    let encrypted_packet = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse encrypted section")
+        .encrypted_packet
+        .expect("should have encrypted packet");
+    assert_eq!(
+        EncryptionAlgorithm::Reserved(17),
+        encrypted_packet.encryption_algorithm
+    );
+    assert_eq!(17, encrypted_packet.encryption_algorithm.value());
+}
+
+// MARK: - DES
+
+#[cfg(feature = "crypto")]
+const DES_KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+
+#[cfg(feature = "crypto")]
+const TRIPLE_DES_KEY: [u8; 24] = [
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10,
+    0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67,
+];
+
+#[cfg(feature = "crypto")]
+fn des_config() -> ParserConfig {
+    ParserConfig {
+        decryptor: Some(Arc::new(
+            scte35::encryption::DesDecryptor::new()
+                .des_key(CW_INDEX, DES_KEY)
+                .triple_des_key(CW_INDEX, TRIPLE_DES_KEY),
+        )),
+        ..ParserConfig::strict()
+    }
+}
+
+#[cfg(feature = "crypto")]
+fn assert_decrypts(bytes: &[u8], encrypted_packet: EncryptedPacket) {
+    let clear_section =
+        SpliceInfoSection::try_from_bytes(&splice_insert_bytes()).expect("should parse section");
+    let section = SpliceInfoSection::try_from_bytes_with_config(bytes, &des_config())
+        .expect("should parse encrypted section");
+    assert_eq!(Some(encrypted_packet), section.encrypted_packet);
+    assert_eq!(clear_section.splice_command, section.splice_command);
+    assert_eq!(clear_section.splice_descriptors, section.splice_descriptors);
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn test_des_ecb_mode() {
+    use des::cipher::{BlockEncrypt, KeyInit};
+    let cipher = des::Des::new(&DES_KEY.into());
+    let (bytes, encrypted_packet) = encrypt_with(&splice_insert_bytes(), 1, |data| {
+        for block in data.chunks_exact_mut(8) {
+            cipher.encrypt_block(block.into());
+        }
+    });
+    assert_decrypts(&bytes, encrypted_packet);
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn test_des_cbc_mode() {
+    use des::cipher::{BlockEncrypt, KeyInit};
+    let cipher = des::Des::new(&DES_KEY.into());
+    let (bytes, encrypted_packet) = encrypt_with(&splice_insert_bytes(), 2, |data| {
+        let mut previous = [0; 8];
+        for block in data.chunks_exact_mut(8) {
+            block
+                .iter_mut()
+                .zip(previous)
+                .for_each(|(byte, previous_byte)| *byte ^= previous_byte);
+            cipher.encrypt_block(block.into());
+            previous.copy_from_slice(block);
+        }
+    });
+    assert_decrypts(&bytes, encrypted_packet);
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn test_triple_des() {
+    use des::cipher::{BlockEncrypt, KeyInit};
+    let cipher = des::TdesEde3::new(&TRIPLE_DES_KEY.into());
+    let (bytes, encrypted_packet) = encrypt_with(&splice_insert_bytes(), 3, |data| {
+        for block in data.chunks_exact_mut(8) {
+            cipher.encrypt_block(block.into());
+        }
+    });
+    assert_decrypts(&bytes, encrypted_packet);
+}
+
+#[cfg(feature = "crypto")]
+#[test]
+fn test_des_decryptor_errors() {
+    use scte35::encryption::DesDecryptor;
+    let decryptor = DesDecryptor::new().des_key(CW_INDEX, DES_KEY);
+    assert_eq!(
+        Err(ParseError::DecryptionFailed("no DES key for cw_index")),
+        decryptor.decrypt(&EncryptionAlgorithm::DesEcbMode, 0, &[0; 8])
+    );
+    assert_eq!(
+        Err(ParseError::DecryptionFailed(
+            "no Triple DES key for cw_index"
+        )),
+        decryptor.decrypt(&EncryptionAlgorithm::TripleDes, CW_INDEX, &[0; 8])
+    );
+    assert_eq!(
+        Err(ParseError::DecryptionFailed(
+            "encrypted data is not a multiple of 8 bytes"
+        )),
+        decryptor.decrypt(&EncryptionAlgorithm::DesEcbMode, CW_INDEX, &[0; 7])
+    );
+    assert_eq!(
+        Err(ParseError::DecryptionFailed(
+            "unsupported encryption_algorithm"
+        )),
+        decryptor.decrypt(&EncryptionAlgorithm::Reserved(4), CW_INDEX, &[0; 8])
+    );
+}
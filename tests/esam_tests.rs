@@ -0,0 +1,117 @@
+#![cfg(feature = "esam")]
+
+mod common;
+
+use common::TIME_SIGNAL_HEX;
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::XMLParseError,
+    esam::{
+        AcquiredSignal, Action, ResponseSignal, SignalProcessingEvent,
+        SignalProcessingNotification, StreamTime,
+    },
+    splice_command::SpliceCommand,
+    splice_info_section::SpliceInfoSection,
+};
+
+#[test]
+fn test_signal_processing_event_to_xml() {
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX)
+        .expect("should be valid splice info section from hex");
+    let event = SignalProcessingEvent {
+        acquired_signals: vec![AcquiredSignal {
+            acquisition_time: Some(String::from("2012-09-18T10:14:26Z")),
+            stream_times: vec![StreamTime {
+                time_type: String::from("PTS"),
+                time_value: String::from("1924989008"),
+            }],
+            ..AcquiredSignal::new(
+                "ESAM_POC",
+                "signal-1",
+                "2012-09-18T10:14:34Z",
+                splice_info_section,
+            )
+        }],
+    };
+    let xml = event.try_to_xml_string().expect("should emit xml");
+    assert_eq!(
+        concat!(
+            r#"<SignalProcessingEvent xmlns="urn:cablelabs:iptvservices:esam:xsd:signal:1" xmlns:sig="urn:cablelabs:md:xsd:signaling:3.0">"#,
+            r#"<AcquiredSignal acquisitionPointIdentity="ESAM_POC" acquisitionSignalID="signal-1" acquisitionTime="2012-09-18T10:14:26Z">"#,
+            r#"<sig:UTCPoint utcPoint="2012-09-18T10:14:34Z"/>"#,
+            r#"<sig:BinaryData signalType="SCTE35">/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==</sig:BinaryData>"#,
+            r#"<sig:StreamTimes><sig:StreamTime timeType="PTS" timeValue="1924989008"/></sig:StreamTimes>"#,
+            r#"</AcquiredSignal>"#,
+            r#"</SignalProcessingEvent>"#,
+        ),
+        xml
+    );
+    assert_eq!(
+        event,
+        SignalProcessingEvent::try_from_xml_string(&xml).expect("should parse emitted xml")
+    );
+}
+
+#[test]
+fn test_signal_processing_notification_from_xml() {
+    let xml = r#"
+        <SignalProcessingNotification xmlns="urn:cablelabs:iptvservices:esam:xsd:signal:1" xmlns:sig="urn:cablelabs:md:xsd:signaling:3.0" xmlns:scte35="http://www.scte.org/schemas/35" acquisitionPointIdentity="ESAM_POC">
+            <ResponseSignal action="replace" acquisitionPointIdentity="ESAM_POC" acquisitionSignalID="signal-1" signalPointID="point-1">
+                <sig:UTCPoint utcPoint="2012-09-18T10:14:34Z"/>
+                <sig:BinaryData signalType="SCTE35">/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==</sig:BinaryData>
+            </ResponseSignal>
+            <ResponseSignal action="create" acquisitionPointIdentity="ESAM_POC" acquisitionSignalID="signal-2">
+                <sig:UTCPoint utcPoint="2012-09-18T10:15:34Z"/>
+                <scte35:SpliceInfoSection>
+                    <scte35:SpliceNull/>
+                </scte35:SpliceInfoSection>
+            </ResponseSignal>
+            <ResponseSignal action="delete" acquisitionSignalID="signal-3"/>
+        </SignalProcessingNotification>
+    "#;
+    let notification =
+        SignalProcessingNotification::try_from_xml_string(xml).expect("should parse xml");
+    assert_eq!(
+        Some(String::from("ESAM_POC")),
+        notification.acquisition_point_identity
+    );
+    assert_eq!(3, notification.response_signals.len());
+    assert_eq!(
+        ResponseSignal {
+            action: Action::Replace,
+            acquisition_point_identity: Some(String::from("ESAM_POC")),
+            acquisition_signal_id: Some(String::from("signal-1")),
+            signal_point_id: Some(String::from("point-1")),
+            utc_point: Some(String::from("2012-09-18T10:14:34Z")),
+            splice_info_section: Some(
+                SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX)
+                    .expect("should be valid splice info section from hex")
+            ),
+        },
+        notification.response_signals[0]
+    );
+    assert_eq!(Action::Create, notification.response_signals[1].action);
+    assert_eq!(
+        Some(&SpliceCommand::SpliceNull),
+        notification.response_signals[1]
+            .splice_info_section
+            .as_ref()
+            .map(|splice_info_section| &splice_info_section.splice_command)
+    );
+    assert_eq!(Action::Delete, notification.response_signals[2].action);
+    assert_eq!(None, notification.response_signals[2].splice_info_section);
+}
+
+#[test]
+fn test_signal_processing_notification_invalid_action() {
+    assert_eq!(
+        Err(XMLParseError::InvalidAttribute {
+            element: String::from("ResponseSignal"),
+            attribute: "action",
+            value: String::from("skip"),
+        }),
+        SignalProcessingNotification::try_from_xml_string(
+            r#"<SignalProcessingNotification><ResponseSignal action="skip"/></SignalProcessingNotification>"#
+        )
+    );
+}
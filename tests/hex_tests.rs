@@ -0,0 +1,84 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::ParseError,
+    hex::{decode_hex, decode_hex_into, encode_hex, DecodeHexError},
+    parser::Parser,
+    splice_info_section::SpliceInfoSection,
+};
+
+// MARK: - Round trip
+
+#[test]
+fn test_hex_round_trip() {
+    let bytes = (0..=255).collect::<Vec<u8>>();
+    let hex = encode_hex(&bytes);
+    assert_eq!(512, hex.len());
+    assert_eq!(Ok(bytes.clone()), decode_hex(&hex));
+    assert_eq!(Ok(bytes), decode_hex(&hex.to_uppercase()));
+    assert_eq!(Ok(vec![]), decode_hex(""));
+}
+
+#[test]
+fn test_decode_hex_into_reuses_buffer() {
+    let mut buf = vec![1, 2, 3, 4];
+    decode_hex_into("FC30", &mut buf).expect("should decode hex");
+    assert_eq!(vec![0xFC, 0x30], buf);
+}
+
+// MARK: - Errors
+
+#[test]
+fn test_decode_hex_errors() {
+    assert_eq!(Err(DecodeHexError::OddLength), decode_hex("FC3"));
+    assert_eq!(
+        Err(DecodeHexError::InvalidCharacter {
+            character: ' ',
+            offset: 2,
+        }),
+        decode_hex("FC 30")
+    );
+    // The invalid character is reported before the odd length.
+    assert_eq!(
+        Err(DecodeHexError::InvalidCharacter {
+            character: 'x',
+            offset: 1,
+        }),
+        decode_hex("0xFC3")
+    );
+    assert_eq!(
+        Err(DecodeHexError::InvalidCharacter {
+            character: 'é',
+            offset: 2,
+        }),
+        decode_hex("FCé0")
+    );
+    assert_eq!(
+        "The hex string has an invalid character 'é' at offset 2.",
+        decode_hex("FCé0").unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_section_hex_errors_are_offset_from_start_of_string() {
+    let error = DecodeHexError::InvalidCharacter {
+        character: 'Z',
+        offset: 4,
+    };
+    assert_eq!(
+        Err(ParseError::DecodeHexError(error.clone())),
+        SpliceInfoSection::try_from_hex_string("0xFCZ0")
+    );
+    assert_eq!(
+        Err(ParseError::DecodeHexError(error)),
+        Parser::new().parse_hex("0XFCZ0")
+    );
+    assert_eq!(
+        Err(ParseError::DecodeHexError(
+            DecodeHexError::InvalidCharacter {
+                character: 'Z',
+                offset: 2,
+            }
+        )),
+        SpliceInfoSection::try_from_hex_string("FCZ0")
+    );
+}
@@ -0,0 +1,349 @@
+mod common;
+
+#[cfg(feature = "base64")]
+use base64::prelude::*;
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    cue::AdBreak,
+    error::{HLSInterstitialError, HLSParseError},
+    hls::DateRange,
+    splice_command::{splice_insert::SpliceInsert, SpliceCommand},
+    splice_descriptor::segmentation_descriptor::SegmentationTypeID,
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+};
+#[cfg(feature = "base64")]
+use scte35::{
+    hls::{CueOut, CueOutCont, LegacyCue},
+    time::BreakDuration,
+};
+
+// MARK: - EXT-X-DATERANGE generation
+
+// 14.1. time_signal – Placement Opportunity Start
+#[test]
+fn test_to_daterange_time_signal() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    let daterange = splice_info_section
+        .to_daterange("2014-03-05T11:15:00Z")
+        .expect("should create daterange");
+    assert_eq!(
+        DateRange {
+            id: String::from("1207959694"),
+            class: None,
+            start_date: String::from("2014-03-05T11:15:00Z"),
+            end_date: None,
+            duration: None,
+            planned_duration: Some(307.0),
+            scte35_cmd: Some(splice_info_section),
+            scte35_out: None,
+            scte35_in: None,
+            end_on_next: false,
+            client_attributes: vec![],
+        },
+        daterange
+    );
+    assert_eq!(
+        format!(
+            "#EXT-X-DATERANGE:ID=\"1207959694\",START-DATE=\"2014-03-05T11:15:00Z\",PLANNED-DURATION=307,SCTE35-CMD={}",
+            hex_string
+        ),
+        daterange.to_string()
+    );
+}
+
+#[test]
+fn test_to_daterange_splice_insert_in() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(
+            SpliceInsert::builder(10)
+                .cue_in()
+                .splice_time(Pts90k(900000))
+                .build()
+                .expect("should build splice insert")
+                .into(),
+        )
+        .build()
+        .expect("should build splice info section");
+    let daterange = splice_info_section
+        .to_daterange("2014-03-05T11:16:00.5Z")
+        .expect("should create daterange");
+    assert_eq!(Some(&splice_info_section), daterange.scte35_in.as_ref());
+    assert_eq!(None, daterange.scte35_out);
+    assert_eq!(None, daterange.planned_duration);
+    assert_eq!(
+        format!(
+            "#EXT-X-DATERANGE:ID=\"10\",START-DATE=\"2014-03-05T11:16:00.5Z\",SCTE35-IN={}",
+            splice_info_section
+                .try_to_hex_string()
+                .expect("should encode splice insert")
+        ),
+        daterange.to_string()
+    );
+}
+
+#[test]
+fn test_daterange_display_all_attributes() {
+    let daterange = DateRange {
+        class: Some(String::from("com.example.ad")),
+        end_date: Some(String::from("2014-03-05T11:16:00Z")),
+        duration: Some(60.5),
+        planned_duration: Some(60.0),
+        end_on_next: true,
+        client_attributes: vec![(
+            String::from("X-COM-EXAMPLE-AD-ID"),
+            String::from("\"XYZ123\""),
+        )],
+        ..DateRange::new("splice-6FFFFFF0", "2014-03-05T11:15:00Z")
+    };
+    assert_eq!(
+        "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",CLASS=\"com.example.ad\",START-DATE=\"2014-03-05T11:15:00Z\",END-DATE=\"2014-03-05T11:16:00Z\",DURATION=60.5,PLANNED-DURATION=60,X-COM-EXAMPLE-AD-ID=\"XYZ123\",END-ON-NEXT=YES",
+        daterange.to_string()
+    );
+}
+
+// MARK: - EXT-X-DATERANGE parsing
+
+#[test]
+fn test_daterange_round_trip() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    let daterange = splice_info_section
+        .to_daterange("2014-03-05T11:15:00Z")
+        .expect("should create daterange");
+    let parsed_daterange =
+        DateRange::try_from_tag(&daterange.to_string()).expect("should parse daterange");
+    // PLANNED-DURATION is written with millisecond precision.
+    assert_eq!(Some(60.294), parsed_daterange.planned_duration);
+    assert_eq!(daterange.scte35_out, parsed_daterange.scte35_out);
+    assert_eq!(daterange.to_string(), parsed_daterange.to_string());
+}
+
+#[test]
+fn test_daterange_from_tag_all_attributes() {
+    let tag = "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",CLASS=\"com.example.ad,with-comma\",START-DATE=\"2014-03-05T11:15:00Z\",END-DATE=\"2014-03-05T11:16:00Z\",DURATION=59.993,PLANNED-DURATION=60,X-COM-EXAMPLE-AD-ID=\"XYZ123\",X-ASSET-COUNT=3,END-ON-NEXT=YES,SCTE35-IN=0xFC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17E";
+    let daterange: DateRange = tag.parse().expect("should parse daterange");
+    assert_eq!("splice-6FFFFFF0", daterange.id);
+    assert_eq!(
+        Some("com.example.ad,with-comma"),
+        daterange.class.as_deref()
+    );
+    assert_eq!("2014-03-05T11:15:00Z", daterange.start_date);
+    assert_eq!(Some("2014-03-05T11:16:00Z"), daterange.end_date.as_deref());
+    assert_eq!(Some(59.993), daterange.duration);
+    assert_eq!(Some(60.0), daterange.planned_duration);
+    assert_eq!(
+        vec![
+            (
+                String::from("X-COM-EXAMPLE-AD-ID"),
+                String::from("\"XYZ123\"")
+            ),
+            (String::from("X-ASSET-COUNT"), String::from("3")),
+        ],
+        daterange.client_attributes
+    );
+    assert!(daterange.end_on_next);
+    assert_eq!(None, daterange.scte35_cmd);
+    assert_eq!(None, daterange.scte35_out);
+    assert!(matches!(
+        daterange.scte35_in.map(|section| section.splice_command),
+        Some(SpliceCommand::TimeSignal(_))
+    ));
+}
+
+#[test]
+fn test_daterange_from_tag_errors() {
+    assert_eq!(
+        Err(HLSParseError::UnexpectedTag(String::from(
+            "#EXT-X-CUE-OUT:DURATION=30"
+        ))),
+        DateRange::try_from_tag("#EXT-X-CUE-OUT:DURATION=30")
+    );
+    assert_eq!(
+        Err(HLSParseError::MissingAttribute("START-DATE")),
+        DateRange::try_from_tag("#EXT-X-DATERANGE:ID=\"1\"")
+    );
+    assert_eq!(
+        Err(HLSParseError::InvalidAttributeList(String::from(
+            "ID=\"1,START-DATE=\"2014-03-05T11:15:00Z\""
+        ))),
+        DateRange::try_from_tag("#EXT-X-DATERANGE:ID=\"1,START-DATE=\"2014-03-05T11:15:00Z\"")
+    );
+    assert_eq!(
+        Err(HLSParseError::InvalidAttribute {
+            attribute: String::from("PLANNED-DURATION"),
+            value: String::from("abc")
+        }),
+        DateRange::try_from_tag(
+            "#EXT-X-DATERANGE:ID=\"1\",START-DATE=\"2014-03-05T11:15:00Z\",PLANNED-DURATION=abc"
+        )
+    );
+    assert!(matches!(
+        DateRange::try_from_tag(
+            "#EXT-X-DATERANGE:ID=\"1\",START-DATE=\"2014-03-05T11:15:00Z\",SCTE35-CMD=0xFC30"
+        ),
+        Err(HLSParseError::ParseError(_))
+    ));
+}
+
+// MARK: - Legacy cue tags
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_legacy_cue_oatcls_scte35() {
+    let base64_string =
+        "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==";
+    let expected = SpliceInfoSection::try_from_bytes(
+        &BASE64_STANDARD
+            .decode(base64_string)
+            .expect("should be valid base64"),
+    )
+    .expect("should be valid splice info section");
+    assert_eq!(
+        Ok(LegacyCue::OATCLSSCTE35(expected)),
+        LegacyCue::try_from_tag(&format!("#EXT-OATCLS-SCTE35:{}", base64_string))
+    );
+    assert_eq!(
+        Err(HLSParseError::InvalidTagValue {
+            tag: "EXT-OATCLS-SCTE35",
+            value: String::from("not base64!")
+        }),
+        LegacyCue::try_from_tag("#EXT-OATCLS-SCTE35:not base64!")
+    );
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_legacy_cue_out_forms() {
+    let expected = Ok(LegacyCue::CueOut(CueOut {
+        duration: Some(30.0),
+    }));
+    assert_eq!(expected, LegacyCue::try_from_tag("#EXT-X-CUE-OUT:30"));
+    assert_eq!(
+        expected,
+        LegacyCue::try_from_tag("#EXT-X-CUE-OUT:DURATION=30")
+    );
+    assert_eq!(
+        expected,
+        LegacyCue::try_from_tag("#EXT-X-CUE-OUT:DURATION=\"30\",BREAKID=\"1\"")
+    );
+    assert_eq!(
+        Ok(LegacyCue::CueOut(CueOut { duration: None })),
+        LegacyCue::try_from_tag("#EXT-X-CUE-OUT")
+    );
+    assert_eq!(Ok(LegacyCue::CueIn), "#EXT-X-CUE-IN".parse());
+    assert_eq!(
+        Err(HLSParseError::UnexpectedTag(String::from("#EXTINF:6.006,"))),
+        LegacyCue::try_from_tag("#EXTINF:6.006,")
+    );
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_legacy_cue_out_to_splice_insert() {
+    let cue_out = CueOut {
+        duration: Some(60.293),
+    };
+    let splice_insert = cue_out
+        .to_splice_insert(1)
+        .expect("should build splice insert");
+    let scheduled_event = splice_insert
+        .scheduled_event
+        .expect("should have scheduled event");
+    assert!(scheduled_event.out_of_network_indicator);
+    assert_eq!(
+        Some(BreakDuration {
+            auto_return: true,
+            duration: Pts90k(5426370)
+        }),
+        scheduled_event.break_duration
+    );
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_legacy_cue_out_cont_forms() {
+    assert_eq!(
+        Ok(LegacyCue::CueOutCont(CueOutCont {
+            elapsed_time: Some(12.012),
+            duration: Some(30.0),
+            splice_info_section: None,
+        })),
+        LegacyCue::try_from_tag("#EXT-X-CUE-OUT-CONT:12.012/30")
+    );
+    let cue = LegacyCue::try_from_tag(
+        "#EXT-X-CUE-OUT-CONT:ElapsedTime=12.012,Duration=30,SCTE35=/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==",
+    )
+    .expect("should parse cue out cont");
+    let LegacyCue::CueOutCont(cue_out_cont) = cue else {
+        panic!("should be EXT-X-CUE-OUT-CONT");
+    };
+    assert_eq!(Some(12.012), cue_out_cont.elapsed_time);
+    assert_eq!(Some(30.0), cue_out_cont.duration);
+    assert!(cue_out_cont.splice_info_section.is_some());
+}
+
+// MARK: - HLS interstitials
+
+#[test]
+fn test_interstitial_from_ad_break() {
+    let (cue_out, cue_in) = AdBreak::new(1207959694, 1924989008, 27630000)
+        .upid_ti(0x2CA0A18A)
+        .build()
+        .expect("should build ad break");
+    let daterange = DateRange::try_interstitial(
+        "2014-03-05T11:15:00Z",
+        &cue_out,
+        &cue_in,
+        "https://example.com/asset-list.json",
+    )
+    .expect("should create interstitial");
+    assert_eq!(
+        format!(
+            "#EXT-X-DATERANGE:ID=\"1207959694\",CLASS=\"com.apple.hls.interstitial\",START-DATE=\"2014-03-05T11:15:00Z\",DURATION=307,X-ASSET-LIST=\"https://example.com/asset-list.json\",X-RESUME-OFFSET=307,SCTE35-OUT={},SCTE35-IN={}",
+            cue_out.try_to_hex_string().expect("should encode cue out"),
+            cue_in.try_to_hex_string().expect("should encode cue in")
+        ),
+        daterange.to_string()
+    );
+}
+
+#[test]
+fn test_interstitial_duration_wraps_pts() {
+    let (cue_out, cue_in) = AdBreak::new(1, 0x1FFFFFFFF - 89999, 180000)
+        .build()
+        .expect("should build ad break");
+    let daterange = DateRange::try_interstitial(
+        "2014-03-05T11:15:00Z",
+        &cue_out,
+        &cue_in,
+        "https://example.com/asset-list.json",
+    )
+    .expect("should create interstitial");
+    assert_eq!(Some(2.0), daterange.duration);
+}
+
+#[test]
+fn test_interstitial_errors() {
+    let (cue_out, cue_in) = AdBreak::new(1, 0, 180000)
+        .build()
+        .expect("should build ad break");
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice null");
+    assert_eq!(
+        Err(HLSInterstitialError::MissingSegmentationDescriptor),
+        DateRange::try_interstitial("2014-03-05T11:15:00Z", &cue_out, &splice_null, "")
+    );
+    assert_eq!(
+        Err(HLSInterstitialError::MismatchedSegmentationTypes {
+            start: SegmentationTypeID::ProviderPlacementOpportunityEnd,
+            end: SegmentationTypeID::ProviderPlacementOpportunityStart,
+        }),
+        DateRange::try_interstitial("2014-03-05T11:15:00Z", &cue_in, &cue_out, "")
+    );
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::{bytes, splice_info_section, SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{ID3Error, ParseError},
+    id3::{find_priv_frames, PrivFrame, SCTE35_OWNER_IDENTIFIER},
+    splice_info_section::SpliceInfoSection,
+};
+
+// MARK: - ID3 parsing
+
+#[test]
+fn test_priv_frames_from_v4_tag() {
+    let tag = id3_tag(
+        4,
+        &[
+            frame(4, b"TXXX", b"\x03desc\x00value"),
+            priv_frame(4, "com.apple.streaming.transportStreamTimestamp", &[0; 8]),
+            priv_frame(4, SCTE35_OWNER_IDENTIFIER, &bytes(SPLICE_INSERT_HEX)),
+        ]
+        .concat(),
+        16,
+    );
+    assert_eq!(
+        vec![
+            PrivFrame {
+                owner_identifier: String::from("com.apple.streaming.transportStreamTimestamp"),
+                private_data: vec![0; 8],
+            },
+            PrivFrame {
+                owner_identifier: String::from(SCTE35_OWNER_IDENTIFIER),
+                private_data: bytes(SPLICE_INSERT_HEX),
+            },
+        ],
+        find_priv_frames(&tag).expect("should find PRIV frames")
+    );
+    assert_eq!(
+        Ok(splice_info_section(SPLICE_INSERT_HEX)),
+        SpliceInfoSection::try_from_id3_tag(&tag)
+    );
+}
+
+#[test]
+fn test_splice_info_section_from_v3_tag() {
+    let mut tag = id3_tag(
+        3,
+        &[
+            // Extended header (size excluding itself, flags and padding size).
+            &[0, 0, 0, 6, 0, 0, 0, 0, 0, 0][..],
+            &priv_frame(3, SCTE35_OWNER_IDENTIFIER, &bytes(TIME_SIGNAL_HEX)),
+        ]
+        .concat(),
+        0,
+    );
+    tag[5] = 0x40;
+    assert_eq!(
+        Ok(splice_info_section(TIME_SIGNAL_HEX)),
+        SpliceInfoSection::try_from_id3_tag(&tag)
+    );
+}
+
+#[test]
+fn test_id3_errors() {
+    assert_eq!(Err(ID3Error::InvalidHeader), find_priv_frames(b"ID3"));
+    assert_eq!(
+        Err(ID3Error::UnsupportedVersion(2)),
+        find_priv_frames(&id3_tag(2, &[], 0))
+    );
+    let tag = id3_tag(4, &priv_frame(4, SCTE35_OWNER_IDENTIFIER, &[0xFC]), 0);
+    assert_eq!(
+        Err(ID3Error::UnexpectedEndOfData),
+        find_priv_frames(&tag[..tag.len() - 1])
+    );
+    assert_eq!(
+        Err(ID3Error::MissingSCTE35Frame),
+        SpliceInfoSection::try_from_id3_tag(&id3_tag(
+            4,
+            &priv_frame(4, "com.example", &bytes(TIME_SIGNAL_HEX)),
+            0
+        ))
+    );
+    assert!(matches!(
+        SpliceInfoSection::try_from_id3_tag(&tag),
+        Err(ID3Error::ParseError(ParseError::UnexpectedEndOfData { .. }))
+    ));
+}
+
+// MARK: - ID3 generation
+
+#[test]
+fn test_splice_info_section_to_id3_tag() {
+    let splice_insert = splice_info_section(SPLICE_INSERT_HEX);
+    assert_eq!(
+        id3_tag(
+            4,
+            &priv_frame(4, SCTE35_OWNER_IDENTIFIER, &bytes(SPLICE_INSERT_HEX)),
+            0
+        ),
+        splice_insert
+            .try_to_id3_tag()
+            .expect("should create ID3 tag")
+    );
+    let frame = splice_insert
+        .try_to_id3_priv_frame()
+        .expect("should create PRIV frame");
+    assert!(frame.is_scte35());
+    assert_eq!(
+        priv_frame(4, SCTE35_OWNER_IDENTIFIER, &bytes(SPLICE_INSERT_HEX)),
+        frame.to_bytes()
+    );
+}
+
+// MARK: - Helpers
+
+fn synchsafe(value: usize) -> [u8; 4] {
+    [
+        (value >> 21) as u8 & 0x7F,
+        (value >> 14) as u8 & 0x7F,
+        (value >> 7) as u8 & 0x7F,
+        value as u8 & 0x7F,
+    ]
+}
+
+fn id3_tag(version: u8, frames: &[u8], padding: usize) -> Vec<u8> {
+    [
+        &b"ID3"[..],
+        &[version, 0, 0],
+        &synchsafe(frames.len() + padding),
+        frames,
+        &vec![0; padding],
+    ]
+    .concat()
+}
+
+fn frame(version: u8, frame_id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let size = if version == 4 {
+        synchsafe(body.len())
+    } else {
+        (body.len() as u32).to_be_bytes()
+    };
+    [&frame_id[..], &size, &[0, 0], body].concat()
+}
+
+fn priv_frame(version: u8, owner_identifier: &str, private_data: &[u8]) -> Vec<u8> {
+    frame(
+        version,
+        b"PRIV",
+        &[owner_identifier.as_bytes(), &[0], private_data].concat(),
+    )
+}
@@ -0,0 +1,80 @@
+use pretty_assertions::assert_eq;
+use scte35::{error::ParseError, isan::Isan};
+
+// MARK: - Hyphenated representation
+
+#[test]
+fn test_versioned_isan_from_hyphenated() {
+    let isan: Isan = "0000-0000-3A8D-0000-Z-0000-0000-6"
+        .parse()
+        .expect("should be valid ISAN");
+    assert_eq!(
+        Isan {
+            root: 0x3A8D,
+            episode: 0,
+            version: Some(0),
+        },
+        isan
+    );
+    assert_eq!("0000-0000-3A8D-0000-Z-0000-0000-6", isan.to_string());
+}
+
+#[test]
+fn test_isan_without_version() {
+    let isan = Isan {
+        root: 0x3A8D,
+        episode: 0,
+        version: None,
+    };
+    assert_eq!(('Z', None), isan.check_characters());
+    assert_eq!("0000-0000-3A8D-0000-Z", isan.to_string());
+    assert_eq!(Ok(isan), "0000-0000-3A8D-0000-Z".parse());
+    assert_eq!(Ok(isan), "ISAN 0000-0000-3a8d-0000".parse());
+}
+
+#[test]
+fn test_invalid_isan_representations() {
+    for string in [
+        "0000-0000-3A8D-0000-A-0000-0000-6",
+        "0000-0000-3A8D-0000-Z-0000-0000-7",
+        "0000-0000-3A8D",
+        "0000-0000-3A8D-0000-Z-0000",
+        "0000-0000-3A8D-000G-Z",
+        "0000-0000-3A8D-00000",
+    ] {
+        assert_eq!(
+            Err(ParseError::InvalidISAN(String::from(string))),
+            string.parse::<Isan>(),
+            "{}",
+            string
+        );
+    }
+}
+
+// MARK: - Binary
+
+#[test]
+fn test_binary_round_trip() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x00, 0x3A, 0x8D, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+    ];
+    let isan = Isan::from_binary(&bytes).expect("should be valid ISAN binary");
+    assert_eq!(
+        Isan {
+            root: 0x3A8D,
+            episode: 1,
+            version: Some(2),
+        },
+        isan
+    );
+    assert_eq!(bytes.to_vec(), isan.to_binary());
+    assert_eq!(
+        Some(Isan {
+            root: 0x3A8D,
+            episode: 1,
+            version: None,
+        }),
+        Isan::from_binary(&bytes[..8])
+    );
+    assert_eq!(None, Isan::from_binary(&bytes[..10]));
+}
@@ -0,0 +1,82 @@
+use base64::prelude::*;
+use pretty_assertions::assert_eq;
+use scte35::splice_descriptor::segmentation_descriptor::{MPUDecoderRegistry, ManagedPrivateUPID};
+
+// MARK: - Private data
+
+#[test]
+fn test_mpu_as_str() {
+    assert_eq!(
+        Some(
+            r#"{"assetId":"peacock_600111","cueData":{"cueType":"standard_break","key":"pb","value":"standard"}}"#
+        ),
+        nbcu_mpu().as_str()
+    );
+    assert_eq!(
+        None,
+        ManagedPrivateUPID {
+            format_specifier: String::from("ABCD"),
+            private_data: vec![0xFF, 0xFE],
+        }
+        .as_str()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_mpu_as_json() {
+    let json: serde_json::Value = nbcu_mpu().as_json().expect("should be valid JSON");
+    assert_eq!("peacock_600111", json["assetId"]);
+    assert_eq!("standard_break", json["cueData"]["cueType"]);
+    assert!(rtln_mpu().as_json::<serde_json::Value>().is_err());
+}
+
+// MARK: - Registry
+
+#[test]
+fn test_mpu_decoder_registry() {
+    #[derive(PartialEq, Debug)]
+    enum Decoded {
+        Text(String),
+        Length(usize),
+    }
+    let registry = MPUDecoderRegistry::new()
+        .register("NBCU", |mpu| {
+            mpu.as_str().map(|s| Decoded::Text(String::from(s)))
+        })
+        .register("RTLN", |mpu| Some(Decoded::Length(mpu.private_data.len())));
+    assert_eq!(
+        Some(Decoded::Text(String::from(
+            nbcu_mpu().as_str().expect("should be valid UTF-8")
+        ))),
+        registry.decode(&nbcu_mpu())
+    );
+    assert_eq!(Some(Decoded::Length(23)), registry.decode(&rtln_mpu()));
+    assert_eq!(
+        None,
+        registry.decode(&ManagedPrivateUPID {
+            format_specifier: String::from("ABCD"),
+            private_data: vec![],
+        })
+    );
+}
+
+// MARK: - Helpers
+
+fn nbcu_mpu() -> ManagedPrivateUPID {
+    ManagedPrivateUPID {
+        format_specifier: String::from("NBCU"),
+        private_data: BASE64_STANDARD
+            .decode("eyJhc3NldElkIjoicGVhY29ja182MDAxMTEiLCJjdWVEYXRhIjp7ImN1ZVR5cGUiOiJzdGFuZGFyZF9icmVhayIsImtleSI6InBiIiwidmFsdWUiOiJzdGFuZGFyZCJ9fQ==")
+            .expect("should be valid base64"),
+    }
+}
+
+fn rtln_mpu() -> ManagedPrivateUPID {
+    ManagedPrivateUPID {
+        format_specifier: String::from("RTLN"),
+        private_data: BASE64_STANDARD
+            .decode("MUgBAAAAADEzNTYxNjYyNDU1NTFCMQA=")
+            .expect("should be valid base64"),
+    }
+}
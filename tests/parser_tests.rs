@@ -2,7 +2,9 @@ use base64::prelude::*;
 use pretty_assertions::assert_eq;
 use scte35::{
     atsc::ATSCContentIdentifier,
-    error::ParseError,
+    edit::SectionEditor,
+    error::{LocatedParseError, ParseError},
+    parser::Parser,
     splice_command::{
         splice_insert::{self, SpliceInsert},
         time_signal::TimeSignal,
@@ -17,8 +19,11 @@ use scte35::{
         },
         SpliceDescriptor,
     },
-    splice_info_section::{SAPType, SpliceInfoSection},
-    time::{BreakDuration, SpliceTime},
+    splice_info_section::{
+        EncryptedPacket, EncryptionAlgorithm, ErrorHandling, ParserConfig, ParserLimits, SAPType,
+        SpliceInfoHeader, SpliceInfoSection,
+    },
+    time::{BreakDuration, Pts90k, SpliceTime},
 };
 
 // MARK: - SCTE-35 2020 - 14. Sample SCTE 35 Messages (Informative)
@@ -34,11 +39,11 @@ fn test_time_signal_placement_opportunity_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(1924989008),
+                pts_time: Some(Pts90k(1924989008)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -93,7 +98,7 @@ fn test_splice_insert() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 1207959695,
@@ -103,13 +108,13 @@ fn test_splice_insert() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(1936310318),
+                            pts_time: Some(Pts90k(1936310318)),
                         }),
                     },
                 ),
                 break_duration: Some(BreakDuration {
                     auto_return: true,
-                    duration: 5426421,
+                    duration: Pts90k(5426421),
                 }),
                 unique_program_id: 0,
                 avail_num: 0,
@@ -151,11 +156,11 @@ fn test_time_signal_placement_opportunity_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(1952616608),
+                pts_time: Some(Pts90k(1952616608)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -210,11 +215,11 @@ fn test_time_signal_program_start_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(2051901622),
+                pts_time: Some(Pts90k(2051901622)),
             },
         }),
         splice_descriptors: vec![
@@ -288,11 +293,11 @@ fn test_time_signal_program_overlap_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(2931818340),
+                pts_time: Some(Pts90k(2931818340)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -347,11 +352,11 @@ fn test_time_signal_program_blackoutoverride_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(2469279755),
+                pts_time: Some(Pts90k(2469279755)),
             },
         }),
         splice_descriptors: vec![
@@ -425,11 +430,11 @@ fn test_time_signal_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(2935061580),
+                pts_time: Some(Pts90k(2935061580)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -484,11 +489,11 @@ fn test_time_signal_program_start_end_placement_opportunity_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(2832024813),
+                pts_time: Some(Pts90k(2832024813)),
             },
         }),
         splice_descriptors: vec![
@@ -582,10 +587,12 @@ fn test_time_signal_segmentation_descriptor_ad_id() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -626,10 +633,12 @@ fn test_time_signal_segmentation_descriptor_umid() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -673,10 +682,12 @@ fn test_time_signal_segmentation_descriptor_isan_program_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -686,9 +697,11 @@ fn test_time_signal_segmentation_descriptor_isan_program_start() {
                     delivery_restrictions: None,
                     component_segments: None,
                     segmentation_duration: Some(2702700),
-                    segmentation_upid: SegmentationUPID::ISAN(String::from(
-                        "0000-0000-3A8D-0000-Z-0000-0000-6",
-                    )),
+                    segmentation_upid: SegmentationUPID::ISAN(
+                        "0000-0000-3A8D-0000-Z-0000-0000-6"
+                            .parse()
+                            .expect("should be valid ISAN"),
+                    ),
                     segmentation_type_id: SegmentationTypeID::ProgramStart,
                     segment_num: 0,
                     segments_expected: 0,
@@ -719,10 +732,12 @@ fn test_time_signal_segmentation_descriptor_isan_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -732,9 +747,11 @@ fn test_time_signal_segmentation_descriptor_isan_program_end() {
                     delivery_restrictions: None,
                     component_segments: None,
                     segmentation_duration: None,
-                    segmentation_upid: SegmentationUPID::ISAN(String::from(
-                        "0000-0000-3A8D-0000-Z-0000-0000-6",
-                    )),
+                    segmentation_upid: SegmentationUPID::ISAN(
+                        "0000-0000-3A8D-0000-Z-0000-0000-6"
+                            .parse()
+                            .expect("should be valid ISAN"),
+                    ),
                     segmentation_type_id: SegmentationTypeID::ProgramEnd,
                     segment_num: 0,
                     segments_expected: 0,
@@ -766,10 +783,12 @@ fn test_time_signal_segmentation_descriptor_tid_program_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -810,10 +829,12 @@ fn test_time_signal_segmentation_descriptor_tid_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -854,10 +875,12 @@ fn test_time_signal_segmentation_descriptor_adi_ppo_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -900,11 +923,11 @@ fn test_time_signal_segmentation_descriptor_adi_ppo_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(5400000),
+                pts_time: Some(Pts90k(5400000)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -949,10 +972,12 @@ fn test_time_signal_segmentation_descriptor_eidr_program_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -962,9 +987,11 @@ fn test_time_signal_segmentation_descriptor_eidr_program_start() {
                     delivery_restrictions: None,
                     component_segments: None,
                     segmentation_duration: Some(2702700),
-                    segmentation_upid: SegmentationUPID::EIDR(String::from(
-                        "10.5240/F85A-E100-B068-5B8F-B1C8-T",
-                    )),
+                    segmentation_upid: SegmentationUPID::EIDR(
+                        "10.5240/F85A-E100-B068-5B8F-B1C8-T"
+                            .parse()
+                            .expect("should be valid EIDR"),
+                    ),
                     segmentation_type_id: SegmentationTypeID::ProgramStart,
                     segment_num: 0,
                     segments_expected: 0,
@@ -1008,6 +1035,71 @@ fn test_time_signal_segmentation_descriptor_invalid_eidr() {
     }
 }
 
+#[test]
+fn test_time_signal_segmentation_descriptor_invalid_eidr_with_upid_length_mismatch_non_fatal() {
+    let hex_string =
+        "0xFC30280000000000000000700506FF1252E9220012021043554549000000007F9F0A013050000015871049";
+    let bytes = [
+        0xFC, 0x30, 0x28, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x70, 0x05, 0x06, 0xFF,
+        0x12, 0x52, 0xE9, 0x22, 0x00, 0x12, 0x02, 0x10, 0x43, 0x55, 0x45, 0x49, 0x00, 0x00, 0x00,
+        0x00, 0x7F, 0x9F, 0x0A, 0x01, 0x30, 0x50, 0x00, 0x00, 0x15, 0x87, 0x10, 0x49,
+    ];
+    let config = ParserConfig {
+        upid_length_mismatch: ErrorHandling::NonFatal,
+        ..Default::default()
+    };
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should capture invalid upid");
+    let SpliceDescriptor::SegmentationDescriptor(descriptor) =
+        &splice_info_section.splice_descriptors[0]
+    else {
+        panic!("should be segmentation descriptor");
+    };
+    let scheduled_event = descriptor
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert_eq!(
+        SegmentationUPID::Invalid {
+            upid_type: SegmentationUPIDType::EIDR,
+            bytes: vec![0x30],
+        },
+        scheduled_event.segmentation_upid
+    );
+    assert_eq!(
+        SegmentationTypeID::NetworkStart,
+        scheduled_event.segmentation_type_id
+    );
+    assert_eq!(
+        vec![ParseError::UnexpectedSegmentationUPIDLength {
+            declared_segmentation_upid_length: 1,
+            expected_segmentation_upid_length: 12,
+            segmentation_upid_type: SegmentationUPIDType::EIDR,
+        }],
+        splice_info_section.non_fatal_errors
+    );
+    let encoded = splice_info_section
+        .try_to_bytes()
+        .expect("should encode invalid upid as received");
+    assert_eq!(
+        splice_info_section.splice_descriptors,
+        SpliceInfoSection::try_from_bytes_with_config(&encoded, &config)
+            .expect("should capture invalid upid")
+            .splice_descriptors
+    );
+    let config = ParserConfig {
+        upid_length_mismatch: ErrorHandling::Ignore,
+        ..Default::default()
+    };
+    assert!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+            .expect("should capture invalid upid")
+            .non_fatal_errors
+            .is_empty()
+    );
+    assert!(SpliceInfoSection::try_from_hex_string(hex_string).is_err());
+}
+
 #[test]
 fn test_time_signal_segmentation_descriptor_atsc_content_identifier_program_start() {
     let base64_string =
@@ -1017,10 +1109,12 @@ fn test_time_signal_segmentation_descriptor_atsc_content_identifier_program_star
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1068,10 +1162,12 @@ fn test_time_signal_segmentation_descriptor_atsc_content_identifier_program_end(
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1119,11 +1215,11 @@ fn test_time_signal_segmentation_descriptor_ti_mpu() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(4534560420),
+                pts_time: Some(Pts90k(4534560420)),
             },
         }),
         splice_descriptors: vec![
@@ -1199,11 +1295,11 @@ fn test_time_signal_segmentation_descriptor_mid_ads_ti() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0x8,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(3522714355),
+                pts_time: Some(Pts90k(3522714355)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -1253,10 +1349,12 @@ fn test_time_signal_segmentation_descriptor_ads_program_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1299,10 +1397,12 @@ fn test_time_signal_segmentation_descriptor_ads_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1345,10 +1445,12 @@ fn test_time_signal_segmentation_descriptor_uri_program_start() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1391,10 +1493,12 @@ fn test_time_signal_segmentation_descriptor_uri_program_end() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
-            splice_time: SpliceTime { pts_time: Some(0) },
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0)),
+            },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
             SegmentationDescriptor {
@@ -1437,7 +1541,7 @@ fn test_splice_insert_avail_descriptor_hex() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 1207959695,
@@ -1447,13 +1551,13 @@ fn test_splice_insert_avail_descriptor_hex() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(1936310318),
+                            pts_time: Some(Pts90k(1936310318)),
                         }),
                     },
                 ),
                 break_duration: Some(BreakDuration {
                     auto_return: true,
-                    duration: 5426421,
+                    duration: Pts90k(5426421),
                 }),
                 unique_program_id: 0,
                 avail_num: 0,
@@ -1483,7 +1587,7 @@ fn test_splice_insert_avail_descriptor_base64() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 1644168586,
@@ -1493,13 +1597,13 @@ fn test_splice_insert_avail_descriptor_base64() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(4453646850),
+                            pts_time: Some(Pts90k(4453646850)),
                         }),
                     },
                 ),
                 break_duration: Some(BreakDuration {
                     auto_return: true,
-                    duration: 5400000,
+                    duration: Pts90k(5400000),
                 }),
                 unique_program_id: 821,
                 avail_num: 0,
@@ -1537,7 +1641,7 @@ fn test_splice_insert_hex() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 987,
@@ -1551,7 +1655,7 @@ fn test_splice_insert_hex() {
                 ),
                 break_duration: Some(BreakDuration {
                     auto_return: false,
-                    duration: 2160000,
+                    duration: Pts90k(2160000),
                 }),
                 unique_program_id: 49152,
                 avail_num: 0,
@@ -1578,7 +1682,7 @@ fn test_splice_insert_hex_with_no0x() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 4000,
@@ -1588,7 +1692,7 @@ fn test_splice_insert_hex_with_no0x() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(531582484),
+                            pts_time: Some(Pts90k(531582484)),
                         }),
                     },
                 ),
@@ -1618,7 +1722,7 @@ fn test_splice_insert_out() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 1007,
@@ -1628,13 +1732,13 @@ fn test_splice_insert_out() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(6070663743),
+                            pts_time: Some(Pts90k(6070663743)),
                         }),
                     },
                 ),
                 break_duration: Some(BreakDuration {
                     auto_return: true,
-                    duration: 5400000,
+                    duration: Pts90k(5400000),
                 }),
                 unique_program_id: 0,
                 avail_num: 0,
@@ -1665,7 +1769,7 @@ fn test_splice_insert_in() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 1007,
@@ -1675,7 +1779,7 @@ fn test_splice_insert_in() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(6074713743),
+                            pts_time: Some(Pts90k(6074713743)),
                         }),
                     },
                 ),
@@ -1710,7 +1814,7 @@ fn test_dtmf_with_alignment_stuffing() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceInsert(SpliceInsert {
             event_id: 94,
@@ -1720,7 +1824,7 @@ fn test_dtmf_with_alignment_stuffing() {
                 splice_mode: splice_insert::SpliceMode::ProgramSpliceMode(
                     splice_insert::ProgramMode {
                         splice_time: Some(SpliceTime {
-                            pts_time: Some(3438281293),
+                            pts_time: Some(Pts90k(3438281293)),
                         }),
                     },
                 ),
@@ -1759,7 +1863,7 @@ fn test_splice_null() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::SpliceNull,
         splice_descriptors: vec![],
@@ -1788,11 +1892,11 @@ fn test_time_signal_segmentation_descriptor_mid() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(4294967296),
+                pts_time: Some(Pts90k(4294967296)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -1804,11 +1908,12 @@ fn test_time_signal_segmentation_descriptor_mid() {
                     component_segments: None,
                     segmentation_duration: Some(5400000),
                     segmentation_upid: SegmentationUPID::MID(vec![
-                        // TODO - EIDR DOI suffix is not always ISAN, as demonstrated here.
-                        // It may be worth creating a struct for the EIDR so as not to force
-                        // an unexpected format (the below examples should be "10.5239/8BE5-E3F6").
-                        SegmentationUPID::EIDR(String::from("10.5239/8BE5-E3F6-0000-0000-0000-B")),
-                        SegmentationUPID::EIDR(String::from("10.5239/8BE5-E3F6-0000-0000-0000-B")),
+                        SegmentationUPID::EIDR(
+                            "10.5239/8BE5-E3F6".parse().expect("should be valid EIDR"),
+                        ),
+                        SegmentationUPID::EIDR(
+                            "10.5239/8BE5-E3F6".parse().expect("should be valid EIDR"),
+                        ),
                         SegmentationUPID::ADI(String::from(
                             "SIGNAL:Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0",
                         )),
@@ -1843,11 +1948,11 @@ fn test_time_signal_provider_ad_start_mpu() {
         sap_type: SAPType::Unspecified,
         protocol_version: 0,
         encrypted_packet: None,
-        pts_adjustment: 0,
+        pts_adjustment: Pts90k(0),
         tier: 0xFFF,
         splice_command: SpliceCommand::TimeSignal(TimeSignal {
             splice_time: SpliceTime {
-                pts_time: Some(5971536646),
+                pts_time: Some(Pts90k(5971536646)),
             },
         }),
         splice_descriptors: vec![SpliceDescriptor::SegmentationDescriptor(
@@ -1884,3 +1989,1080 @@ fn test_time_signal_provider_ad_start_mpu() {
         "unexpected splice info section from hex"
     );
 }
+
+// MARK: - String encodings
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_from_str_detects_encoding() {
+    let expected = SpliceInfoSection::try_from_base64_string(SPLICE_INSERT_BASE64)
+        .expect("should parse base64");
+    let hex_string = expected.try_to_hex_string().expect("should encode section");
+    let bare_hex = hex_string.trim_start_matches("0x");
+    for string in [
+        String::from(SPLICE_INSERT_BASE64),
+        format!("  {}\n", SPLICE_INSERT_BASE64),
+        hex_string.clone(),
+        hex_string.replacen("0x", "0X", 1),
+        String::from(bare_hex),
+        bare_hex.to_lowercase(),
+    ] {
+        assert_eq!(
+            Ok(&expected),
+            string.parse::<SpliceInfoSection>().as_ref(),
+            "{}",
+            string
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_from_str_invalid_strings() {
+    assert!(matches!(
+        "".parse::<SpliceInfoSection>(),
+        Err(ParseError::UnexpectedEndOfData { .. })
+    ));
+    assert!(matches!(
+        "0xFC3".parse::<SpliceInfoSection>(),
+        Err(ParseError::DecodeHexError(_))
+    ));
+    assert!(matches!(
+        "/DAv!".parse::<SpliceInfoSection>(),
+        Err(ParseError::DecodeBase64Error(_))
+    ));
+}
+
+// MARK: - Parser config
+
+const SPLICE_INSERT_BASE64: &str =
+    "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=";
+
+#[test]
+fn test_parser_config_crc_mismatch() {
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    let crc_mismatch = ParseError::CRCMismatch {
+        crc_32: 0x62DBA3F5,
+        calculated_crc_32: 0x62DBA30A,
+    };
+    assert!(SpliceInfoSection::try_from_bytes(&bytes)
+        .expect("should ignore CRC by default")
+        .non_fatal_errors
+        .is_empty());
+    assert_eq!(
+        vec![crc_mismatch.clone()],
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::lenient())
+            .expect("should record CRC mismatch as non-fatal")
+            .non_fatal_errors
+    );
+    assert_eq!(
+        Err(crc_mismatch),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::strict())
+    );
+}
+
+#[test]
+fn test_parser_config_strict_accepts_valid_section() {
+    let bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&bytes),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::strict())
+    );
+}
+
+#[test]
+fn test_parser_config_table_id_and_protocol_version() {
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    bytes[0] = 0xFD;
+    bytes[3] = 0x01;
+    let config = ParserConfig {
+        table_id_mismatch: ErrorHandling::NonFatal,
+        protocol_version_mismatch: ErrorHandling::NonFatal,
+        ..Default::default()
+    };
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse with non-fatal errors");
+    assert_eq!(0xFD, splice_info_section.table_id);
+    assert_eq!(1, splice_info_section.protocol_version);
+    assert_eq!(
+        vec![
+            ParseError::UnexpectedTableID(0xFD),
+            ParseError::UnsupportedProtocolVersion(1)
+        ],
+        splice_info_section.non_fatal_errors
+    );
+    let config = ParserConfig {
+        protocol_version_mismatch: ErrorHandling::Fatal,
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(ParseError::UnsupportedProtocolVersion(1)),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+    );
+}
+
+#[test]
+fn test_non_zero_protocol_version_is_non_fatal_by_default() {
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    bytes[3] = 0x01;
+    let splice_info_section =
+        SpliceInfoSection::try_from_bytes(&bytes).expect("should parse with non-fatal errors");
+    assert_eq!(1, splice_info_section.protocol_version);
+    assert_eq!(
+        vec![ParseError::UnsupportedProtocolVersion(1)],
+        splice_info_section.non_fatal_errors
+    );
+}
+
+#[test]
+fn test_parser_config_accepted_table_ids() {
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    bytes[0] = 0xFD;
+    SectionEditor::new(&mut bytes)
+        .expect("should create editor")
+        .recalculate_crc_32();
+    let config = ParserConfig {
+        accepted_table_ids: vec![0xFD],
+        ..ParserConfig::strict()
+    };
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should accept private table_id");
+    assert_eq!(0xFD, splice_info_section.table_id);
+    assert_eq!(
+        Vec::<ParseError>::new(),
+        splice_info_section.non_fatal_errors
+    );
+    assert_eq!(
+        Err(ParseError::UnexpectedTableID(0xFD)),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::strict())
+    );
+    bytes[0] = 0xFE;
+    SectionEditor::new(&mut bytes)
+        .expect("should create editor")
+        .recalculate_crc_32();
+    assert_eq!(
+        Err(ParseError::UnexpectedTableID(0xFE)),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+    );
+}
+
+#[test]
+fn test_parser_config_length_mismatch() {
+    // The splice_command_length of the splice_insert is increased by one byte (taken from the
+    // descriptor_loop_length), which is a non-fatal error by default.
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    bytes[13] += 1;
+    let default_parse =
+        SpliceInfoSection::try_from_bytes(&bytes).expect("should parse with non-fatal errors");
+    assert!(matches!(
+        default_parse.non_fatal_errors.as_slice(),
+        [ParseError::UnexpectedSpliceCommandLength { .. }]
+    ));
+    let config = ParserConfig {
+        length_mismatch: ErrorHandling::Ignore,
+        ..Default::default()
+    };
+    assert!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+            .map(|section| section.non_fatal_errors.is_empty())
+            .unwrap_or(false)
+    );
+}
+
+#[test]
+fn test_parser_config_accept_legacy_splice_command_length() {
+    // The splice_null example, which uses the legacy splice_command_length of 0xFFF.
+    let legacy_bytes = [
+        0xFC, 0x30, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00, 0x00,
+        0x00, 0x4F, 0x25, 0x33, 0x96,
+    ];
+    let config = ParserConfig {
+        accept_legacy_splice_command_length: true,
+        ..ParserConfig::strict()
+    };
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&legacy_bytes, &config)
+        .expect("should accept legacy splice_command_length");
+    assert_eq!(
+        SpliceCommand::SpliceNull,
+        splice_info_section.splice_command
+    );
+    assert!(splice_info_section.non_fatal_errors.is_empty());
+
+    // A length that is genuinely wrong is still an error.
+    let mut bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    bytes[13] += 1;
+    assert!(matches!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config),
+        Err(ParseError::UnexpectedSpliceCommandLength { .. })
+    ));
+}
+
+#[test]
+fn test_parser_config_ignore_trailing_padding() {
+    let bytes = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    let splice_info_section =
+        SpliceInfoSection::try_from_bytes(&bytes).expect("should parse valid section");
+    let mut padded_bytes = bytes.clone();
+    padded_bytes.extend_from_slice(&[0xFF; 8]);
+    assert_ne!(
+        Ok(splice_info_section.clone()),
+        SpliceInfoSection::try_from_bytes(&padded_bytes)
+    );
+    let config = ParserConfig {
+        ignore_trailing_padding: true,
+        crc_mismatch: ErrorHandling::Fatal,
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(splice_info_section.clone()),
+        SpliceInfoSection::try_from_bytes_with_config(&padded_bytes, &config)
+    );
+    assert_eq!(
+        Ok(splice_info_section),
+        Parser::with_config(config.clone()).parse(&padded_bytes)
+    );
+    // Trailing bytes that are not padding are still parsed as part of the section.
+    padded_bytes.push(0x00);
+    assert!(matches!(
+        SpliceInfoSection::try_from_bytes_with_config(&padded_bytes, &config),
+        Err(ParseError::CRCMismatch {
+            crc_32: 0xFFFFFF00,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_try_from_bytes_prefix() {
+    let first = BASE64_STANDARD
+        .decode(SPLICE_INSERT_BASE64)
+        .expect("should be valid base64");
+    let second = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    let mut data = first.clone();
+    data.extend_from_slice(&[0xFF, 0xFF]);
+    data.extend_from_slice(&second);
+    let config = ParserConfig {
+        ignore_trailing_padding: true,
+        ..Default::default()
+    };
+    let (splice_info_section, consumed) =
+        SpliceInfoSection::try_from_bytes_prefix(&data, &config).expect("should parse first");
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&first).expect("should parse first"),
+        splice_info_section
+    );
+    assert_eq!(first.len() + 2, consumed);
+    let (splice_info_section, consumed) =
+        SpliceInfoSection::try_from_bytes_prefix(&data[consumed..], &config)
+            .expect("should parse second");
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&second).expect("should parse second"),
+        splice_info_section
+    );
+    assert_eq!(second.len(), consumed);
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&first[..20]),
+        SpliceInfoSection::try_from_bytes_prefix(&first[..20], &config).map(|(section, _)| section)
+    );
+    assert!(SpliceInfoSection::try_from_bytes_prefix(&[0xFC], &config).is_err());
+}
+
+#[test]
+fn test_parser_config_invalid_splice_descriptors() {
+    let bytes = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    let second_descriptor = SpliceInfoSection::try_from_bytes(&bytes)
+        .expect("should parse valid section")
+        .splice_descriptors[1]
+        .clone();
+    // The segmentation_upid_length and segmentation_upid_type of the first descriptor.
+    for (index, value, error) in [
+        (
+            34,
+            9,
+            ParseError::UnexpectedSegmentationUPIDLength {
+                declared_segmentation_upid_length: 9,
+                expected_segmentation_upid_length: 8,
+                segmentation_upid_type: SegmentationUPIDType::TI,
+            },
+        ),
+        (33, 0x30, ParseError::UnrecognisedSegmentationUPIDType(0x30)),
+    ] {
+        let mut bytes = bytes.clone();
+        bytes[index] = value;
+        assert_eq!(
+            Err(error.clone()),
+            SpliceInfoSection::try_from_bytes(&bytes)
+        );
+        let config = ParserConfig {
+            invalid_splice_descriptors: ErrorHandling::NonFatal,
+            ..Default::default()
+        };
+        let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+            .expect("should skip invalid descriptor");
+        assert_eq!(
+            vec![second_descriptor.clone()],
+            splice_info_section.splice_descriptors
+        );
+        assert_eq!(vec![error.clone()], splice_info_section.non_fatal_errors);
+        let lenient_config = ParserConfig {
+            upid_length_mismatch: ErrorHandling::Fatal,
+            ..ParserConfig::lenient()
+        };
+        let lenient_section =
+            SpliceInfoSection::try_from_bytes_with_config(&bytes, &lenient_config)
+                .expect("should skip invalid descriptor in lenient mode");
+        assert_eq!(
+            splice_info_section.splice_descriptors,
+            lenient_section.splice_descriptors
+        );
+        assert!(lenient_section.non_fatal_errors.contains(&error));
+        let config = ParserConfig {
+            invalid_splice_descriptors: ErrorHandling::Ignore,
+            ..Default::default()
+        };
+        assert!(
+            SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+                .expect("should skip invalid descriptor")
+                .non_fatal_errors
+                .is_empty()
+        );
+    }
+}
+
+#[test]
+fn test_parser_config_invalid_splice_descriptor_past_loop_end_is_fatal() {
+    let mut bytes = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    // The descriptor_length of the second descriptor extends past the descriptor loop.
+    bytes[47] = 0xFF;
+    assert!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::lenient()).is_err()
+    );
+}
+
+// MARK: - Parser limits
+
+fn nested_mid_section_bytes(depth: usize) -> Vec<u8> {
+    let mut upid = SegmentationUPID::AdID(String::from("ABCD0001000H"));
+    for _ in 0..depth {
+        upid = SegmentationUPID::MID(vec![upid]);
+    }
+    let descriptor = SegmentationDescriptor::builder(1)
+        .provider_placement_opportunity_start()
+        .upid(upid)
+        .build()
+        .expect("should build segmentation descriptor");
+    SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(descriptor))
+        .build()
+        .expect("should build splice info section")
+        .try_to_bytes()
+        .expect("should encode splice info section")
+}
+
+#[test]
+fn test_parser_limits_upid_nesting_depth() {
+    assert!(SpliceInfoSection::try_from_bytes(&nested_mid_section_bytes(8)).is_ok());
+    assert_eq!(
+        Err(ParseError::LimitExceeded {
+            limit: 8,
+            description: "max_upid_nesting_depth"
+        }),
+        SpliceInfoSection::try_from_bytes(&nested_mid_section_bytes(9))
+    );
+}
+
+#[test]
+fn test_parser_limits_allocation() {
+    let bytes = nested_mid_section_bytes(1);
+    let config = ParserConfig {
+        limits: ParserLimits {
+            max_allocation_bytes: 11,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(ParseError::LimitExceeded {
+            limit: 11,
+            description: "max_allocation_bytes"
+        }),
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+    );
+    let config = ParserConfig {
+        limits: ParserLimits {
+            max_allocation_bytes: 12,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(SpliceInfoSection::try_from_bytes_with_config(&bytes, &config).is_ok());
+}
+
+// MARK: - Malformed data
+
+const MALFORMED_DATA_BASE64: &str = "/DB5AAAAAAAAAP/wBQb/DkfmpABjAhdDVUVJhPHPYH+/CAgAAAAABy4QajEBGAIcQ1VFSYTx71B//wAAK3NwCAgAAAAABy1cxzACGAIqQ1VFSYTx751/vwwbUlRMTjFIAQAAAAAxMzU2MTY2MjQ1NTUxQjEAAQAALL95dg==";
+
+#[test]
+fn test_truncated_data_is_error_in_lenient_mode() {
+    let bytes = BASE64_STANDARD
+        .decode(MALFORMED_DATA_BASE64)
+        .expect("should be valid base64");
+    for length in 0..bytes.len() {
+        assert!(
+            SpliceInfoSection::try_from_bytes_with_config(
+                &bytes[..length],
+                &ParserConfig::lenient()
+            )
+            .is_err(),
+            "should fail for truncated length {}",
+            length
+        );
+    }
+}
+
+#[test]
+fn test_corrupted_data_does_not_panic_in_lenient_mode() {
+    let bytes = BASE64_STANDARD
+        .decode(MALFORMED_DATA_BASE64)
+        .expect("should be valid base64");
+    for index in 0..bytes.len() {
+        for value in [0x00, 0x7F, 0xFF] {
+            let mut corrupted = bytes.clone();
+            corrupted[index] = value;
+            let _ =
+                SpliceInfoSection::try_from_bytes_with_config(&corrupted, &ParserConfig::lenient());
+        }
+    }
+}
+
+// MARK: - Error location
+
+const TWO_SEGMENTATION_DESCRIPTORS_BASE64: &str = "/DBIAAAAAAAA///wBQb+ek2ItgAyAhdDVUVJSAAAGH+fCAgAAAAALMvDRBEAAAIXQ1VFSUgAABl/nwgIAAAAACyk26AQAACZcuND";
+
+#[test]
+fn test_error_is_located_in_innermost_structure() {
+    let mut bytes = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    // The segmentation_upid_length of the second descriptor (a TI, which must be 8 bytes).
+    bytes[59] = 9;
+    let error =
+        SpliceInfoSection::try_from_bytes_located(&bytes).expect_err("should fail on upid length");
+    assert_eq!(
+        LocatedParseError {
+            error: ParseError::UnexpectedSegmentationUPIDLength {
+                declared_segmentation_upid_length: 9,
+                expected_segmentation_upid_length: 8,
+                segmentation_upid_type: SegmentationUPIDType::TI,
+            },
+            bit_offset: 480,
+            path: String::from("splice_descriptors[1].segmentation_upid"),
+        },
+        error
+    );
+    assert_eq!(Err(error.error), SpliceInfoSection::try_from_bytes(&bytes));
+}
+
+#[test]
+fn test_top_level_error_has_empty_path() {
+    let bytes = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    let error = SpliceInfoSection::try_from_bytes_located(&bytes[..10])
+        .expect_err("should fail on truncated data");
+    assert!(matches!(
+        error.error,
+        ParseError::UnexpectedEndOfData { .. }
+    ));
+    assert_eq!((24, ""), (error.bit_offset, error.path.as_str()));
+    assert!(error.to_string().starts_with("At bit 24: "));
+}
+
+#[test]
+fn test_parser_error_is_located_in_splice_command() {
+    let mut bytes = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should be valid base64");
+    // The time_specified_flag is set, and so the splice_command_length of 1 is too short.
+    bytes[12] = 1;
+    let error = Parser::with_config(ParserConfig::strict())
+        .parse_located(&bytes)
+        .expect_err("should fail on splice command length");
+    assert!(matches!(
+        error.error,
+        ParseError::UnexpectedSpliceCommandLength { .. }
+    ));
+    assert_eq!("splice_command", error.path);
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes_with_config(&bytes, &ParserConfig::strict()),
+        Err(ParseError::from(error))
+    );
+}
+
+// MARK: - Parser
+
+#[cfg(feature = "base64")]
+const PARSER_HEX: &str = "0xFC302F000000000000FFFFF014054800008F7FEFFE7369C02EFE0052CCF500000000000A0008435545490000013562DBA30A";
+const PARSER_BASE64: &str = "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=";
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_parser_matches_section_constructors() {
+    let mut parser = Parser::new();
+    let expected =
+        SpliceInfoSection::try_from_hex_string(PARSER_HEX).expect("should parse from hex");
+    for _ in 0..2 {
+        assert_eq!(
+            expected,
+            parser.parse_hex(PARSER_HEX).expect("should parse hex")
+        );
+        assert_eq!(
+            expected,
+            parser
+                .parse_hex(&PARSER_HEX[2..].to_lowercase())
+                .expect("should parse lowercase hex without prefix")
+        );
+        assert_eq!(
+            expected,
+            parser
+                .parse_base64(PARSER_BASE64)
+                .expect("should parse base64")
+        );
+        let bytes = BASE64_STANDARD
+            .decode(PARSER_BASE64)
+            .expect("should be valid base64");
+        assert_eq!(expected, parser.parse(&bytes).expect("should parse bytes"));
+    }
+}
+
+#[test]
+fn test_parser_uses_config() {
+    let mut bytes = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should be valid base64");
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    assert!(Parser::new().parse(&bytes).is_ok());
+    assert!(matches!(
+        Parser::with_config(ParserConfig::strict()).parse(&bytes),
+        Err(ParseError::CRCMismatch { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_parser_invalid_encoding_is_error() {
+    let mut parser = Parser::new();
+    assert!(matches!(
+        parser.parse_base64("not base64!"),
+        Err(ParseError::DecodeBase64Error(_))
+    ));
+    assert!(matches!(
+        parser.parse_hex("FC3"),
+        Err(ParseError::DecodeHexError(_))
+    ));
+    assert!(matches!(
+        parser.parse_hex("FCé0"),
+        Err(ParseError::DecodeHexError(_))
+    ));
+    assert!(matches!(
+        SpliceInfoSection::try_from_hex_string("0xé0"),
+        Err(ParseError::DecodeHexError(_))
+    ));
+}
+
+#[test]
+fn test_parser_parse_into_reuses_section() {
+    let mut parser = Parser::new();
+    let bytes = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should be valid base64");
+    let expected = parser.parse(&bytes).expect("should parse bytes");
+    let mut splice_info_section = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice info section");
+    for _ in 0..2 {
+        parser
+            .parse_into(&bytes, &mut splice_info_section)
+            .expect("should parse into section");
+        assert_eq!(expected, splice_info_section);
+    }
+    assert!(parser
+        .parse_into(&bytes[..bytes.len() - 1], &mut splice_info_section)
+        .is_err());
+    assert_eq!(expected, splice_info_section);
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_parser_borrowed_results() {
+    let mut parser = Parser::new();
+    let expected = parser
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse base64");
+    let bytes = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should be valid base64");
+    assert_eq!(
+        expected,
+        parser
+            .parse_ref(&bytes)
+            .expect("should parse ref")
+            .try_to_owned()
+            .expect("should convert ref")
+    );
+    assert_eq!(
+        expected,
+        parser
+            .parse_hex_ref(PARSER_HEX)
+            .expect("should parse hex ref")
+            .try_to_owned()
+            .expect("should convert hex ref")
+    );
+    let section_ref = parser
+        .parse_base64_ref(PARSER_BASE64)
+        .expect("should parse base64 ref");
+    assert_eq!(expected.crc_32, section_ref.crc_32);
+    assert_eq!(
+        expected.splice_descriptors.len(),
+        section_ref.splice_descriptors.len()
+    );
+}
+
+// MARK: - Corpus parsing
+
+fn corpus() -> Vec<Vec<u8>> {
+    let bytes = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should be valid base64");
+    let mut crc_mismatch = bytes.clone();
+    let last = crc_mismatch.len() - 1;
+    crc_mismatch[last] ^= 0xFF;
+    vec![
+        bytes.clone(),
+        bytes[..10].to_vec(),
+        crc_mismatch,
+        vec![0xFD, 0x30, 0x00],
+        bytes,
+    ]
+}
+
+#[test]
+fn test_parse_all_aggregates_results() {
+    let report = Parser::with_config(ParserConfig::strict()).parse_all(corpus());
+    assert_eq!(5, report.total());
+    assert!(!report.is_all_ok());
+    assert_eq!(
+        vec![0, 4],
+        report
+            .sections
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![1, 2, 3],
+        report
+            .errors
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            (String::from("CRCMismatch"), 1),
+            (String::from("UnexpectedEndOfData"), 1),
+            (String::from("UnexpectedTableID"), 1),
+        ],
+        report.error_counts().into_iter().collect::<Vec<_>>()
+    );
+    assert_eq!(0, report.non_fatal_error_count());
+}
+
+#[test]
+fn test_parse_all_with_default_config() {
+    let report = scte35::parse_all(corpus());
+    assert_eq!(
+        vec![0, 2, 4],
+        report
+            .sections
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![(String::from("UnexpectedEndOfData"), 2)],
+        report.error_counts().into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_parse_all_matches_parse_all() {
+    let corpus = corpus().into_iter().cycle().take(500).collect::<Vec<_>>();
+    assert_eq!(scte35::parse_all(&corpus), scte35::par_parse_all(&corpus));
+    let parser = Parser::with_config(ParserConfig::strict());
+    assert_eq!(parser.parse_all(&corpus), parser.par_parse_all(&corpus));
+}
+
+// MARK: - Header peek
+
+#[test]
+fn test_peek_header_of_splice_insert() {
+    let data = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should decode base64");
+    let splice_info_section = SpliceInfoSection::try_from_bytes(&data).expect("should parse");
+    let header = SpliceInfoSection::peek_header(&data).expect("should peek header");
+    assert_eq!(
+        SpliceInfoHeader {
+            table_id: 0xFC,
+            sap_type: SAPType::Unspecified,
+            protocol_version: 0,
+            encrypted_packet: false,
+            encryption_algorithm: EncryptionAlgorithm::NoEncryption,
+            pts_adjustment: Pts90k(0),
+            cw_index: 0xFF,
+            tier: 0xFFF,
+            splice_command_length: 20,
+            splice_command_type: Some(SpliceCommandType::SpliceInsert),
+            pts_time: Some(Pts90k(0x07369C02E)),
+        },
+        header
+    );
+    assert_eq!(
+        splice_info_section.adjusted_splice_time(),
+        header.adjusted_pts_time()
+    );
+}
+
+#[test]
+fn test_peek_header_of_splice_null_has_no_pts_time() {
+    let data = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(10))
+        .tier(0x123)
+        .build()
+        .expect("should build section")
+        .try_to_bytes()
+        .expect("should encode section");
+    let header = SpliceInfoSection::peek_header(&data).expect("should peek header");
+    assert_eq!(
+        Some(SpliceCommandType::SpliceNull),
+        header.splice_command_type
+    );
+    assert_eq!(Pts90k(10), header.pts_adjustment);
+    assert_eq!(0x123, header.tier);
+    assert_eq!(None, header.pts_time);
+    assert_eq!(None, header.adjusted_pts_time());
+}
+
+#[test]
+fn test_peek_header_does_not_parse_splice_descriptors() {
+    let mut data = BASE64_STANDARD
+        .decode(TWO_SEGMENTATION_DESCRIPTORS_BASE64)
+        .expect("should decode base64");
+    data[59] = 9;
+    assert!(SpliceInfoSection::try_from_bytes(&data).is_err());
+    let header = SpliceInfoSection::peek_header(&data).expect("should peek header");
+    assert_eq!(
+        Some(SpliceCommandType::TimeSignal),
+        header.splice_command_type
+    );
+    assert_eq!(Some(Pts90k(0x7A4D88B6)), header.pts_time);
+}
+
+#[test]
+fn test_peek_header_errors() {
+    let data = BASE64_STANDARD
+        .decode(PARSER_BASE64)
+        .expect("should decode base64");
+    assert!(matches!(
+        SpliceInfoSection::peek_header(&data[..10]),
+        Err(ParseError::UnexpectedEndOfData { .. })
+    ));
+    let mut data = data;
+    data[1] |= 0x80;
+    assert_eq!(
+        Err(ParseError::InvalidSectionSyntaxIndicator),
+        SpliceInfoSection::peek_header(&data)
+    );
+}
+
+// MARK: - Descriptor queries
+
+#[test]
+fn test_descriptor_queries() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .splice_time(Pts90k(0x20))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .splice_descriptor(SpliceDescriptor::AvailDescriptor(AvailDescriptor {
+            identifier: 0x43554549,
+            provider_avail_id: 309,
+        }))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(2)
+                .cancel()
+                .build()
+                .expect("should build"),
+        ))
+        .splice_descriptor(SpliceDescriptor::DTMFDescriptor(DTMFDescriptor {
+            identifier: 0x43554549,
+            preroll: 177,
+            dtmf_chars: String::from("121#"),
+        }))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(3)
+                .break_start()
+                .build()
+                .expect("should build"),
+        ))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(4)
+                .break_start()
+                .build()
+                .expect("should build"),
+        ))
+        .build()
+        .expect("should build section");
+    assert_eq!(
+        vec![2, 3, 4],
+        splice_info_section
+            .segmentation_descriptors()
+            .map(|descriptor| descriptor.event_id)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![309],
+        splice_info_section
+            .avail_descriptors()
+            .map(|descriptor| descriptor.provider_avail_id)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["121#"],
+        splice_info_section
+            .dtmf_descriptors()
+            .map(|descriptor| descriptor.dtmf_chars.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(3),
+        splice_info_section
+            .find_segmentation(&SegmentationTypeID::BreakStart)
+            .map(|descriptor| descriptor.event_id)
+    );
+    assert_eq!(
+        None,
+        splice_info_section.find_segmentation(&SegmentationTypeID::BreakEnd)
+    );
+}
+
+#[test]
+fn test_descriptor_queries_without_descriptors() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .build()
+        .expect("should build section");
+    assert_eq!(0, splice_info_section.segmentation_descriptors().count());
+    assert_eq!(0, splice_info_section.avail_descriptors().count());
+    assert_eq!(0, splice_info_section.dtmf_descriptors().count());
+    assert_eq!(
+        None,
+        splice_info_section.find_segmentation(&SegmentationTypeID::ProgramStart)
+    );
+}
+
+// MARK: - Clone and Hash
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_parsed_values_can_be_cloned_and_deduplicated() {
+    let splice_info_section = Parser::new()
+        .parse_base64(
+            "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==",
+        )
+        .expect("should parse");
+    let cloned = splice_info_section.clone();
+    assert_eq!(splice_info_section, cloned);
+
+    let commands = std::collections::HashSet::from([
+        splice_info_section.splice_command.clone(),
+        cloned.splice_command,
+        SpliceCommand::SpliceNull,
+    ]);
+    assert_eq!(2, commands.len());
+
+    let segmentation_descriptors = splice_info_section
+        .splice_descriptors
+        .iter()
+        .chain(&splice_info_section.splice_descriptors)
+        .filter_map(|descriptor| match descriptor {
+            SpliceDescriptor::SegmentationDescriptor(descriptor) => Some(descriptor.clone()),
+            _ => None,
+        })
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(1, segmentation_descriptors.len());
+
+    let mut with_non_fatal_error = splice_info_section.clone();
+    with_non_fatal_error
+        .non_fatal_errors
+        .push(ParseError::InvalidPrivateIndicator);
+    let sections = std::collections::HashSet::from([splice_info_section, with_non_fatal_error]);
+    assert_eq!(2, sections.len());
+}
+
+// MARK: - Deduplication
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_repeated_message_has_same_dedup_key() {
+    let splice_info_section = Parser::new()
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse");
+    let mut repeat = splice_info_section.clone();
+    repeat.crc_32 = 0;
+    repeat
+        .non_fatal_errors
+        .push(ParseError::InvalidPrivateIndicator);
+    assert!(splice_info_section.eq_ignoring_crc(&repeat));
+    assert!(repeat.eq_ignoring_crc(&splice_info_section));
+    assert_eq!(splice_info_section.dedup_key(), repeat.dedup_key());
+    assert_ne!(splice_info_section, repeat);
+}
+
+#[test]
+fn test_dedup_key_ignores_encryption_checks() {
+    let mut splice_info_section = SpliceInfoSection::builder()
+        .build()
+        .expect("should build section");
+    splice_info_section.encrypted_packet = Some(EncryptedPacket {
+        encryption_algorithm: EncryptionAlgorithm::DesEcbMode,
+        cw_index: 3,
+        alignment_stuffing: 0,
+        e_crc_32: 0x12345678,
+    });
+    let mut repeat = splice_info_section.clone();
+    let encrypted_packet = repeat
+        .encrypted_packet
+        .as_mut()
+        .expect("should be encrypted");
+    encrypted_packet.alignment_stuffing = 4;
+    encrypted_packet.e_crc_32 = 0x87654321;
+    assert!(splice_info_section.eq_ignoring_crc(&repeat));
+    assert_eq!(splice_info_section.dedup_key(), repeat.dedup_key());
+
+    repeat
+        .encrypted_packet
+        .as_mut()
+        .expect("should be encrypted")
+        .cw_index = 4;
+    assert!(!splice_info_section.eq_ignoring_crc(&repeat));
+    assert_ne!(splice_info_section.dedup_key(), repeat.dedup_key());
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_different_messages_have_different_dedup_keys() {
+    let splice_info_section = Parser::new()
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse");
+    let mut adjusted = splice_info_section.clone();
+    adjusted.pts_adjustment = Pts90k(1);
+    let mut without_descriptors = splice_info_section.clone();
+    without_descriptors.splice_descriptors.clear();
+    let keys = std::collections::HashSet::from([
+        splice_info_section.dedup_key(),
+        adjusted.dedup_key(),
+        without_descriptors.dedup_key(),
+    ]);
+    assert_eq!(3, keys.len());
+    assert!(!splice_info_section.eq_ignoring_crc(&adjusted));
+    assert!(!splice_info_section.eq_ignoring_crc(&without_descriptors));
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_dedup_key_is_stable() {
+    let splice_info_section = Parser::new()
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse");
+    // The key may be shared between processes, so must not change between runs or platforms.
+    assert_eq!(8479460991819606481, splice_info_section.dedup_key());
+}
+
+// MARK: - Display
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_display_splice_insert() {
+    let splice_info_section = Parser::new()
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse");
+    assert_eq!(
+        "\
+Splice Info Section
+  Table ID: 0xFC
+  SAP Type: Unspecified
+  Protocol Version: 0
+  Encrypted: false
+  PTS Adjustment: 0 (0.000000s)
+  Tier: 0xFFF
+  Splice Command: Splice Insert (0x05)
+    Event ID: 1207959695
+    Out Of Network: true
+    Immediate: false
+    PTS Time: 1936310318 (21514.559089s)
+    Break Duration: 5426421 (60.293567s)
+      Auto Return: true
+    Unique Program ID: 0
+    Avail: 0 of 0
+  Splice Descriptors: 1
+    [0] Avail Descriptor (0x00)
+      Identifier: CUEI (0x43554549)
+      Provider Avail ID: 309
+  CRC-32: 0x62DBA30A
+",
+        splice_info_section.to_string()
+    );
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn test_display_includes_non_fatal_errors() {
+    let mut splice_info_section = Parser::new()
+        .parse_base64(PARSER_BASE64)
+        .expect("should parse");
+    splice_info_section
+        .non_fatal_errors
+        .push(ParseError::InvalidPrivateIndicator);
+    assert!(splice_info_section
+        .to_string()
+        .ends_with("  Non-fatal Errors: 1\n    The 1-bit private indicator was not 0.\n"));
+}
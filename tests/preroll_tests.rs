@@ -0,0 +1,216 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    preroll::{PrerollChecker, PrerollWarning},
+    splice_command::{
+        splice_insert::SpliceInsert,
+        splice_schedule::{self, SpliceSchedule},
+        time_signal::TimeSignal,
+        SpliceCommand,
+    },
+    splice_descriptor::{dtmf_descriptor::DTMFDescriptor, SpliceDescriptor, CUEI_IDENTIFIER},
+    splice_info_section::SpliceInfoSection,
+    time::{system_time_from_utc_splice_time, Pts90k, SpliceTime},
+    timeline::Arrival,
+};
+use std::time::Duration;
+
+fn time_signal(pts_adjustment: u64, pts_time: Option<u64>) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(pts_adjustment))
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: pts_time.map(Pts90k),
+            },
+        }))
+        .build()
+        .expect("should build section")
+}
+
+fn splice_insert_with_dtmf(pts_time: u64, preroll: u8) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .cue_out()
+                .splice_time(Pts90k(pts_time))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .splice_descriptor(SpliceDescriptor::DTMFDescriptor(DTMFDescriptor {
+            identifier: CUEI_IDENTIFIER,
+            preroll,
+            dtmf_chars: String::from("123*"),
+        }))
+        .build()
+        .expect("should build section")
+}
+
+fn splice_schedule(utc_splice_times: &[u32]) -> SpliceInfoSection {
+    let events = utc_splice_times
+        .iter()
+        .enumerate()
+        .map(|(index, utc_splice_time)| splice_schedule::Event {
+            event_id: index as u32,
+            scheduled_event: Some(splice_schedule::ScheduledEvent {
+                out_of_network_indicator: true,
+                splice_mode: splice_schedule::SpliceMode::ProgramSpliceMode(
+                    splice_schedule::ProgramMode {
+                        utc_splice_time: *utc_splice_time,
+                    },
+                ),
+                break_duration: None,
+                unique_program_id: 0,
+                avail_num: 0,
+                avails_expected: 0,
+            }),
+        })
+        .collect();
+    SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceSchedule(SpliceSchedule { events }))
+        .build()
+        .expect("should build section")
+}
+
+// MARK: - Lead time
+
+#[test]
+fn test_sufficient_lead_time_has_no_warnings() {
+    let checker = PrerollChecker::new();
+    let section = time_signal(0, Some(90_000 * 10));
+    assert_eq!(
+        Vec::<PrerollWarning>::new(),
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 6)))
+    );
+}
+
+#[test]
+fn test_insufficient_lead_time_includes_pts_adjustment() {
+    let checker = PrerollChecker::new();
+    let section = time_signal(90_000, Some(90_000 * 10));
+    assert_eq!(
+        vec![PrerollWarning::InsufficientLeadTime {
+            lead_time: Duration::from_secs(3),
+            minimum_lead_time: Duration::from_secs(4),
+        }],
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 8)))
+    );
+}
+
+#[test]
+fn test_minimum_lead_time_can_be_changed() {
+    let checker = PrerollChecker::new().minimum_lead_time(Duration::from_secs(8));
+    let section = time_signal(0, Some(90_000 * 10));
+    assert_eq!(
+        vec![PrerollWarning::InsufficientLeadTime {
+            lead_time: Duration::from_secs(5),
+            minimum_lead_time: Duration::from_secs(8),
+        }],
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 5)))
+    );
+}
+
+#[test]
+fn test_message_received_after_splice() {
+    let checker = PrerollChecker::new();
+    let section = time_signal(0, Some(90_000 * 10));
+    assert_eq!(
+        vec![PrerollWarning::ReceivedAfterSplice {
+            late_by: Duration::from_secs(1),
+        }],
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 11)))
+    );
+}
+
+#[test]
+fn test_lead_time_across_clock_wrap() {
+    let checker = PrerollChecker::new();
+    let section = time_signal(0, Some(90_000 * 5));
+    let arrival = Arrival::new(Pts90k(Pts90k::MAX.ticks() + 1 - 90_000));
+    assert_eq!(
+        Vec::<PrerollWarning>::new(),
+        checker.check(&section, &arrival)
+    );
+}
+
+#[test]
+fn test_immediate_message_is_not_checked() {
+    let checker = PrerollChecker::new();
+    assert_eq!(
+        Vec::<PrerollWarning>::new(),
+        checker.check(&time_signal(0, None), &Arrival::new(Pts90k(100)))
+    );
+}
+
+// MARK: - Splice schedule
+
+#[test]
+fn test_splice_schedule_is_checked_against_wallclock() {
+    let checker = PrerollChecker::new();
+    let section = splice_schedule(&[1_000_010, 1_000_002]);
+    let arrival = Arrival::new(Pts90k(0));
+    assert_eq!(
+        Vec::<PrerollWarning>::new(),
+        checker.check(&section, &arrival)
+    );
+
+    let arrival = arrival.wallclock(system_time_from_utc_splice_time(1_000_000));
+    assert_eq!(
+        vec![PrerollWarning::InsufficientLeadTime {
+            lead_time: Duration::from_secs(2),
+            minimum_lead_time: Duration::from_secs(4),
+        }],
+        checker.check(&section, &arrival)
+    );
+
+    let arrival = arrival.wallclock(system_time_from_utc_splice_time(1_000_003));
+    assert_eq!(
+        vec![PrerollWarning::ReceivedAfterSplice {
+            late_by: Duration::from_secs(1),
+        }],
+        checker.check(&section, &arrival)
+    );
+}
+
+// MARK: - DTMF preroll
+
+#[test]
+fn test_dtmf_preroll_requires_two_seconds_more_lead_time() {
+    let checker = PrerollChecker::new();
+    let section = splice_insert_with_dtmf(90_000 * 20, 50);
+    assert_eq!(
+        Vec::<PrerollWarning>::new(),
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 13)))
+    );
+    assert_eq!(
+        vec![PrerollWarning::InsufficientDTMFPreroll {
+            preroll: 50,
+            lead_time: Duration::from_secs(6),
+        }],
+        checker.check(&section, &Arrival::new(Pts90k(90_000 * 14)))
+    );
+}
+
+#[test]
+fn test_dtmf_preroll_below_suggested_minimum() {
+    let checker = PrerollChecker::new();
+    let section = splice_insert_with_dtmf(90_000 * 20, 39);
+    assert_eq!(
+        vec![PrerollWarning::DTMFPrerollBelowSuggested { preroll: 39 }],
+        checker.check(&section, &Arrival::new(Pts90k(0)))
+    );
+}
+
+#[test]
+fn test_warning_messages() {
+    assert_eq!(
+        "Message received 3.000s before the splice, which is less than the minimum of 4.000s.",
+        PrerollWarning::InsufficientLeadTime {
+            lead_time: Duration::from_secs(3),
+            minimum_lead_time: Duration::from_secs(4),
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "DTMF preroll of 3.9s is less than the suggested minimum of 4.0s.",
+        PrerollWarning::DTMFPrerollBelowSuggested { preroll: 39 }.to_string()
+    );
+}
@@ -0,0 +1,127 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{EncodeError, ParseError},
+    splice_command::{
+        private_command::{PrivateCommand, PrivateCommandData, PrivateCommandRegistry},
+        SpliceCommand,
+    },
+    splice_info_section::SpliceInfoSection,
+};
+
+const ABCD: u32 = 0x41424344;
+
+#[derive(Debug)]
+struct Channel {
+    identifier: u32,
+    channel: u16,
+}
+
+impl PrivateCommandData for Channel {
+    fn identifier(&self) -> u32 {
+        self.identifier
+    }
+
+    fn private_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        Ok(self.channel.to_be_bytes().to_vec())
+    }
+}
+
+fn registry() -> PrivateCommandRegistry {
+    PrivateCommandRegistry::new().register(ABCD, |private_bytes| {
+        let channel = private_bytes
+            .try_into()
+            .map(u16::from_be_bytes)
+            .map_err(|_| ParseError::InvalidPrivateData("expected a 16-bit channel"))?;
+        Ok(Box::new(Channel {
+            identifier: ABCD,
+            channel,
+        }))
+    })
+}
+
+// MARK: - Decoding
+
+#[test]
+fn test_private_command_decode_round_trip() {
+    let private_command = PrivateCommand::try_from_data(&Channel {
+        identifier: ABCD,
+        channel: 513,
+    })
+    .expect("should create private command");
+    assert_eq!(
+        PrivateCommand {
+            identifier: String::from("ABCD"),
+            private_bytes: vec![0x02, 0x01],
+        },
+        private_command
+    );
+    let bytes = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::PrivateCommand(private_command))
+        .build()
+        .expect("should build")
+        .try_to_bytes()
+        .expect("should encode");
+    let section = SpliceInfoSection::try_from_bytes(&bytes).expect("should parse");
+    let SpliceCommand::PrivateCommand(private_command) = &section.splice_command else {
+        panic!("should be private command");
+    };
+    let data = registry()
+        .decode(private_command)
+        .expect("should have decoder")
+        .expect("should decode");
+    assert_eq!(ABCD, data.identifier());
+    assert_eq!(
+        513,
+        data.downcast_ref::<Channel>()
+            .expect("should downcast to Channel")
+            .channel
+    );
+}
+
+#[test]
+fn test_private_command_decode_without_decoder() {
+    let private_command = PrivateCommand {
+        identifier: String::from("WXYZ"),
+        private_bytes: vec![0x01, 0x02],
+    };
+    assert!(registry().decode(&private_command).is_none());
+}
+
+#[test]
+fn test_private_command_decode_error() {
+    let private_command = PrivateCommand {
+        identifier: String::from("ABCD"),
+        private_bytes: vec![0x01],
+    };
+    assert_eq!(
+        ParseError::InvalidPrivateData("expected a 16-bit channel"),
+        registry()
+            .decode(&private_command)
+            .expect("should have decoder")
+            .expect_err("should fail to decode")
+    );
+}
+
+// MARK: - Identifier
+
+#[test]
+fn test_private_command_identifier_value() {
+    let mut private_command = PrivateCommand {
+        identifier: String::from("ABCD"),
+        private_bytes: vec![],
+    };
+    assert_eq!(Some(ABCD), private_command.identifier_value());
+    private_command.identifier = String::from("ABC");
+    assert_eq!(None, private_command.identifier_value());
+}
+
+#[test]
+fn test_private_command_from_data_with_invalid_identifier() {
+    assert_eq!(
+        Err(EncodeError::InvalidPrivateCommandIdentifier(0xFFFFFFFF)),
+        PrivateCommand::try_from_data(&Channel {
+            identifier: 0xFFFFFFFF,
+            channel: 1,
+        })
+    );
+}
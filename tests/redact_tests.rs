@@ -0,0 +1,219 @@
+mod common;
+
+use common::TIME_SIGNAL_HEX;
+use pretty_assertions::assert_eq;
+use scte35::{
+    atsc::ATSCContentIdentifier,
+    eidr::Eidr,
+    redact::Redaction,
+    splice_command::{private_command::PrivateCommand, SpliceCommand},
+    splice_descriptor::{
+        segmentation_descriptor::{ManagedPrivateUPID, SegmentationDescriptor, SegmentationUPID},
+        SpliceDescriptor, CUEI_IDENTIFIER,
+    },
+    splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+};
+
+fn section_with_upid(upid: SegmentationUPID) -> SpliceInfoSection {
+    SpliceInfoSection::builder()
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(1)
+                .provider_placement_opportunity_start()
+                .upid(upid)
+                .build()
+                .expect("should build segmentation descriptor"),
+        ))
+        .build()
+        .expect("should build section")
+}
+
+fn upid(splice_info_section: &SpliceInfoSection) -> &SegmentationUPID {
+    match splice_info_section.splice_descriptors.first() {
+        Some(SpliceDescriptor::SegmentationDescriptor(SegmentationDescriptor {
+            scheduled_event: Some(scheduled_event),
+            ..
+        })) => &scheduled_event.segmentation_upid,
+        _ => panic!("should have segmentation descriptor"),
+    }
+}
+
+fn redacted_upid(upid_to_redact: SegmentationUPID, redaction: &Redaction) -> SegmentationUPID {
+    let redacted = section_with_upid(upid_to_redact)
+        .redacted(redaction)
+        .expect("should redact section");
+    upid(&redacted).clone()
+}
+
+// MARK: - Placeholder
+
+#[test]
+fn test_placeholder_redaction_of_upids() {
+    let redaction = Redaction::Placeholder;
+    assert_eq!(
+        SegmentationUPID::AdID(String::from("XXXXXXXXXXXX")),
+        redacted_upid(
+            SegmentationUPID::AdID(String::from("ABCD0123456H")),
+            &redaction
+        )
+    );
+    assert_eq!(
+        SegmentationUPID::UUID(String::from("00000000-0000-0000-0000-000000000000")),
+        redacted_upid(
+            SegmentationUPID::UUID(String::from("12345678-9abc-def0-1234-56789abcdef0")),
+            &redaction
+        )
+    );
+    assert_eq!(
+        SegmentationUPID::MPU(ManagedPrivateUPID {
+            format_specifier: String::from("ABCD"),
+            private_data: vec![0, 0, 0],
+        }),
+        redacted_upid(
+            SegmentationUPID::MPU(ManagedPrivateUPID {
+                format_specifier: String::from("ABCD"),
+                private_data: vec![1, 2, 3],
+            }),
+            &redaction
+        )
+    );
+    assert_eq!(
+        SegmentationUPID::MID(vec![
+            SegmentationUPID::TI(String::from("0x0000000000000000")),
+            SegmentationUPID::URI(String::from("XXXXXXXXXXXXXX")),
+        ]),
+        redacted_upid(
+            SegmentationUPID::MID(vec![
+                SegmentationUPID::new_ti(0x2CA0A18A),
+                SegmentationUPID::URI(String::from("urn:example:42")),
+            ]),
+            &redaction
+        )
+    );
+}
+
+#[test]
+fn test_redaction_keeps_owner_of_identifier() {
+    let redaction = Redaction::Placeholder;
+    let eidr: Eidr = "10.5240/F85A-E100-B068-5B8F-B1C8-T"
+        .parse()
+        .expect("should parse eidr");
+    assert_eq!(
+        SegmentationUPID::EIDR(Eidr {
+            sub_prefix: 5240,
+            suffix: [0; 10],
+        }),
+        redacted_upid(SegmentationUPID::EIDR(eidr), &redaction)
+    );
+    assert_eq!(
+        SegmentationUPID::ATSCContentIdentifier(ATSCContentIdentifier {
+            tsid: 1,
+            end_of_day: 2,
+            unique_for: 3,
+            content_id: String::from("XXXXX"),
+        }),
+        redacted_upid(
+            SegmentationUPID::ATSCContentIdentifier(ATSCContentIdentifier {
+                tsid: 1,
+                end_of_day: 2,
+                unique_for: 3,
+                content_id: String::from("HOUSE"),
+            }),
+            &redaction
+        )
+    );
+}
+
+#[test]
+fn test_redaction_of_private_bytes() {
+    let splice_info_section = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::PrivateCommand(PrivateCommand {
+            identifier: String::from("ABCD"),
+            private_bytes: vec![1, 2, 3],
+        }))
+        .splice_descriptor(SpliceDescriptor::Unknown {
+            tag: 0xF0,
+            identifier: CUEI_IDENTIFIER,
+            private_bytes: vec![4, 5],
+        })
+        .build()
+        .expect("should build section");
+    let redacted = splice_info_section
+        .redacted(&Redaction::Placeholder)
+        .expect("should redact section");
+    assert_eq!(
+        SpliceCommand::PrivateCommand(PrivateCommand {
+            identifier: String::from("ABCD"),
+            private_bytes: vec![0, 0, 0],
+        }),
+        redacted.splice_command
+    );
+    assert_eq!(
+        vec![SpliceDescriptor::Unknown {
+            tag: 0xF0,
+            identifier: CUEI_IDENTIFIER,
+            private_bytes: vec![0, 0],
+        }],
+        redacted.splice_descriptors
+    );
+}
+
+// MARK: - Hash
+
+#[test]
+fn test_hash_redaction_is_consistent_per_value_and_key() {
+    let ad_id = || SegmentationUPID::AdID(String::from("ABCD0123456H"));
+    let redacted = redacted_upid(ad_id(), &Redaction::Hash { key: 1 });
+    assert_eq!(
+        redacted,
+        redacted_upid(ad_id(), &Redaction::Hash { key: 1 })
+    );
+    assert_ne!(ad_id(), redacted);
+    assert_ne!(
+        redacted,
+        redacted_upid(ad_id(), &Redaction::Hash { key: 2 })
+    );
+    assert_ne!(
+        redacted,
+        redacted_upid(
+            SegmentationUPID::AdID(String::from("ABCD0123457H")),
+            &Redaction::Hash { key: 1 }
+        )
+    );
+    let SegmentationUPID::AdID(text) = redacted else {
+        panic!("should be ad-id");
+    };
+    assert_eq!(12, text.len());
+    assert!(text.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+// MARK: - Encoding
+
+#[test]
+fn test_redacted_section_has_valid_crc_and_same_length() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let splice_info_section =
+        SpliceInfoSection::try_from_hex_string(hex_string).expect("should parse section");
+    let redacted = splice_info_section
+        .redacted(&Redaction::Hash { key: 0 })
+        .expect("should redact section");
+    let bytes = redacted.try_to_bytes().expect("should encode section");
+    assert_eq!(
+        splice_info_section
+            .try_to_bytes()
+            .expect("should encode section")
+            .len(),
+        bytes.len()
+    );
+    assert_eq!(
+        redacted,
+        SpliceInfoSection::try_from_bytes_with_config(
+            &bytes,
+            &ParserConfig {
+                crc_mismatch: ErrorHandling::Fatal,
+                ..Default::default()
+            }
+        )
+        .expect("should parse with valid crc")
+    );
+    assert_ne!(splice_info_section.crc_32, redacted.crc_32);
+}
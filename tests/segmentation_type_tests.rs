@@ -0,0 +1,245 @@
+mod common;
+
+use common::TIME_SIGNAL_HEX;
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::ParseError,
+    splice_command::SpliceCommandType,
+    splice_descriptor::{
+        segmentation_descriptor::{SegmentationTypeID, SegmentationUPIDType},
+        SpliceDescriptor, SpliceDescriptorTag,
+    },
+    splice_info_section::SpliceInfoSection,
+};
+
+// MARK: - Out/in classification
+
+#[test]
+fn test_start_types_are_out() {
+    for segmentation_type_id in [
+        SegmentationTypeID::ProgramStart,
+        SegmentationTypeID::BreakStart,
+        SegmentationTypeID::ProviderAdvertisementStart,
+        SegmentationTypeID::ProviderPlacementOpportunityStart,
+        SegmentationTypeID::DistributorOverlayPlacementOpportunityStart,
+        SegmentationTypeID::ProviderAdBlockStart,
+        SegmentationTypeID::NetworkStart,
+    ] {
+        assert!(segmentation_type_id.is_out(), "{:?}", segmentation_type_id);
+        assert!(!segmentation_type_id.is_in(), "{:?}", segmentation_type_id);
+    }
+}
+
+#[test]
+fn test_end_types_are_in() {
+    for segmentation_type_id in [
+        SegmentationTypeID::ProgramEnd,
+        SegmentationTypeID::BreakEnd,
+        SegmentationTypeID::ProviderAdvertisementEnd,
+        SegmentationTypeID::ProviderPlacementOpportunityEnd,
+        SegmentationTypeID::DistributorOverlayPlacementOpportunityEnd,
+        SegmentationTypeID::ProviderAdBlockEnd,
+        SegmentationTypeID::NetworkEnd,
+    ] {
+        assert!(segmentation_type_id.is_in(), "{:?}", segmentation_type_id);
+        assert!(!segmentation_type_id.is_out(), "{:?}", segmentation_type_id);
+    }
+}
+
+#[test]
+fn test_unpaired_types_are_neither_out_nor_in() {
+    for segmentation_type_id in [
+        SegmentationTypeID::NotIndicated,
+        SegmentationTypeID::ContentIdentification,
+        SegmentationTypeID::ProgramEarlyTermination,
+        SegmentationTypeID::ProgramBreakaway,
+        SegmentationTypeID::ProgramResumption,
+        SegmentationTypeID::ProgramRunoverPlanned,
+        SegmentationTypeID::ProgramRunoverUnplanned,
+        SegmentationTypeID::ProgramOverlapStart,
+        SegmentationTypeID::ProgramBlackoutOverride,
+        SegmentationTypeID::ProgramJoin,
+    ] {
+        assert!(!segmentation_type_id.is_out(), "{:?}", segmentation_type_id);
+        assert!(!segmentation_type_id.is_in(), "{:?}", segmentation_type_id);
+        assert_eq!(None, segmentation_type_id.corresponding_end());
+        assert_eq!(None, segmentation_type_id.corresponding_start());
+    }
+}
+
+// MARK: - Pairing
+
+#[test]
+fn test_corresponding_start_and_end() {
+    assert_eq!(
+        Some(SegmentationTypeID::ProgramEnd),
+        SegmentationTypeID::ProgramStart.corresponding_end()
+    );
+    assert_eq!(
+        Some(SegmentationTypeID::ProviderPlacementOpportunityStart),
+        SegmentationTypeID::ProviderPlacementOpportunityEnd.corresponding_start()
+    );
+    assert_eq!(None, SegmentationTypeID::ProgramEnd.corresponding_end());
+    assert_eq!(None, SegmentationTypeID::ProgramStart.corresponding_start());
+}
+
+#[test]
+fn test_pairs_are_consistent_for_all_types() {
+    for value in 0..=u8::MAX {
+        let Ok(segmentation_type_id) = SegmentationTypeID::try_from(value) else {
+            continue;
+        };
+        if let Some(end) = segmentation_type_id.corresponding_end() {
+            assert_eq!(value + 1, end.value());
+            assert_eq!(
+                Some(segmentation_type_id.clone()),
+                end.corresponding_start()
+            );
+        }
+        if let Some(start) = segmentation_type_id.corresponding_start() {
+            assert_eq!(value - 1, start.value());
+            assert_eq!(
+                Some(segmentation_type_id.clone()),
+                start.corresponding_end()
+            );
+        }
+    }
+}
+
+// MARK: - Names
+
+#[test]
+fn test_segmentation_type_id_names() {
+    assert_eq!(
+        "Provider Placement Opportunity Start",
+        SegmentationTypeID::ProviderPlacementOpportunityStart.name()
+    );
+    assert_eq!(
+        "Program Start - In Progress",
+        SegmentationTypeID::ProgramJoin.to_string()
+    );
+    assert_eq!("Network End", format!("{}", SegmentationTypeID::NetworkEnd));
+}
+
+#[test]
+fn test_splice_command_type_names() {
+    assert_eq!("Time Signal", SpliceCommandType::TimeSignal.name());
+    assert_eq!("Splice Insert", SpliceCommandType::SpliceInsert.to_string());
+    assert_eq!(
+        "Bandwidth Reservation",
+        SpliceCommandType::BandwidthReservation.to_string()
+    );
+}
+
+#[test]
+fn test_segmentation_upid_type_names() {
+    assert_eq!("Ad-ID", SegmentationUPIDType::AdID.name());
+    assert_eq!(
+        "ISAN (Deprecated)",
+        SegmentationUPIDType::DeprecatedISAN.to_string()
+    );
+    assert_eq!(
+        "ATSC Content Identifier",
+        SegmentationUPIDType::ATSCContentIdentifier.to_string()
+    );
+}
+
+// MARK: - Reserved types
+
+#[test]
+fn test_reserved_type_is_preserved() {
+    assert_eq!(
+        Ok(SegmentationTypeID::Reserved(0x60)),
+        SegmentationTypeID::try_from(0x60)
+    );
+    assert_eq!(0x60, SegmentationTypeID::Reserved(0x60).value());
+    assert_eq!("Reserved", SegmentationTypeID::Reserved(0x60).to_string());
+    assert!(!SegmentationTypeID::Reserved(0x60).is_out());
+    assert!(!SegmentationTypeID::Reserved(0x60).is_in());
+}
+
+#[test]
+fn test_reserved_type_round_trips() {
+    // 14.1. time_signal – Placement Opportunity Start
+    let hex_string = TIME_SIGNAL_HEX;
+    let mut splice_info_section =
+        SpliceInfoSection::try_from_hex_string(hex_string).expect("should parse time_signal");
+    let Some(SpliceDescriptor::SegmentationDescriptor(descriptor)) =
+        splice_info_section.splice_descriptors.first_mut()
+    else {
+        panic!("should have segmentation descriptor");
+    };
+    descriptor
+        .scheduled_event
+        .as_mut()
+        .expect("should have scheduled event")
+        .segmentation_type_id = SegmentationTypeID::Reserved(0x60);
+    let encoded = splice_info_section
+        .try_to_hex_string()
+        .expect("should encode reserved type");
+    let reparsed =
+        SpliceInfoSection::try_from_hex_string(&encoded).expect("should parse reserved type");
+    assert_eq!(
+        splice_info_section.splice_descriptors,
+        reparsed.splice_descriptors
+    );
+}
+
+#[test]
+fn test_reserved_raw_values() {
+    assert_eq!(
+        SegmentationUPIDType::Reserved(0x11),
+        SegmentationUPIDType::from_value(0x11)
+    );
+    assert_eq!(0x11, SegmentationUPIDType::Reserved(0x11).value());
+    assert_eq!("Reserved", SegmentationUPIDType::Reserved(0x11).to_string());
+    assert_eq!(
+        Err(ParseError::UnrecognisedSegmentationUPIDType(0x11)),
+        SegmentationUPIDType::try_from(0x11)
+    );
+    assert_eq!(
+        SpliceCommandType::Reserved(0x08),
+        SpliceCommandType::from_value(0x08)
+    );
+    assert_eq!(0x08, SpliceCommandType::Reserved(0x08).value());
+    assert_eq!("Reserved", SpliceCommandType::Reserved(0x08).to_string());
+    assert_eq!(
+        SpliceDescriptorTag::Private(0x05),
+        SpliceDescriptorTag::from_value(0x05)
+    );
+    for value in 0..=u8::MAX {
+        assert_eq!(value, SegmentationTypeID::from_value(value).value());
+        assert_eq!(value, SegmentationUPIDType::from_value(value).value());
+        assert_eq!(value, SpliceCommandType::from_value(value).value());
+        assert_eq!(value, SpliceDescriptorTag::from_value(value).value());
+    }
+}
+
+// MARK: - Ordering
+
+#[test]
+fn test_segmentation_type_ids_are_ordered_by_value() {
+    let mut segmentation_type_ids = vec![
+        SegmentationTypeID::NetworkEnd,
+        SegmentationTypeID::Reserved(0x10),
+        SegmentationTypeID::BreakStart,
+        SegmentationTypeID::ProgramStart,
+        SegmentationTypeID::NotIndicated,
+    ];
+    segmentation_type_ids.sort();
+    assert_eq!(
+        vec![
+            SegmentationTypeID::NotIndicated,
+            SegmentationTypeID::ProgramStart,
+            SegmentationTypeID::Reserved(0x10),
+            SegmentationTypeID::BreakStart,
+            SegmentationTypeID::NetworkEnd,
+        ],
+        segmentation_type_ids
+    );
+    let by_type = std::collections::BTreeMap::from([
+        (SegmentationTypeID::BreakEnd, "in"),
+        (SegmentationTypeID::BreakStart, "out"),
+    ]);
+    assert_eq!(vec!["out", "in"], by_type.into_values().collect::<Vec<_>>());
+}
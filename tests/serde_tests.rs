@@ -0,0 +1,137 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::ParseError,
+    hex::decode_hex,
+    splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection},
+    timecode::FrameRate,
+};
+
+#[test]
+fn test_serde_round_trip_time_signal() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    let json = serde_json::to_string(&splice_info_section).expect("should serialize");
+    assert_eq!(
+        splice_info_section,
+        serde_json::from_str::<SpliceInfoSection>(&json).expect("should deserialize")
+    );
+}
+
+#[test]
+fn test_serde_round_trip_splice_insert() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    let value = serde_json::to_value(&splice_info_section).expect("should serialize");
+    assert_eq!(
+        serde_json::json!(1207959695),
+        value["splice_command"]["SpliceInsert"]["event_id"]
+    );
+    assert_eq!(
+        splice_info_section,
+        serde_json::from_value::<SpliceInfoSection>(value).expect("should deserialize")
+    );
+}
+
+#[test]
+fn test_serde_serialize_parse_error() {
+    let error = SpliceInfoSection::try_from_hex_string("0xFC3").expect_err("should fail");
+    assert!(matches!(error, ParseError::DecodeHexError(_)));
+    assert_eq!(
+        serde_json::json!({ "DecodeHexError": "OddLength" }),
+        serde_json::to_value(&error).expect("should serialize")
+    );
+}
+
+#[test]
+fn test_serde_frame_rate_rejects_zero() {
+    let frame_rate = FrameRate::FPS_29_97_DROP_FRAME;
+    let json = serde_json::to_string(&frame_rate).expect("should serialize");
+    assert_eq!(
+        frame_rate,
+        serde_json::from_str::<FrameRate>(&json).expect("should deserialize")
+    );
+    assert!(serde_json::from_str::<FrameRate>(
+        r#"{"numerator":25,"denominator":0,"is_drop_frame":false}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<FrameRate>(
+        r#"{"numerator":0,"denominator":1,"is_drop_frame":false}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_serde_round_trip_parse_errors() {
+    let errors = [
+        SpliceInfoSection::try_from_hex_string("0xFC3").expect_err("should fail"),
+        SpliceInfoSection::try_from_hex_string("0xFC30").expect_err("should fail"),
+        ParseError::InvalidUUIDInSegmentationUPID("invalid length"),
+        ParseError::Utf8ConversionError {
+            error: String::from_utf8(b"ab\xF0\x90\x80x".to_vec())
+                .unwrap_err()
+                .utf8_error(),
+            description: "segmentation_upid",
+        },
+        ParseError::Utf8ConversionError {
+            error: String::from_utf8(b"abc\xE0".to_vec())
+                .unwrap_err()
+                .utf8_error(),
+            description: "segmentation_upid",
+        },
+    ];
+    for error in errors {
+        let json = serde_json::to_string(&error).expect("should serialize");
+        assert_eq!(
+            error,
+            serde_json::from_str::<ParseError>(&json).expect("should deserialize")
+        );
+    }
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_serde_round_trip_base64_errors() {
+    for error in [
+        base64::DecodeError::InvalidByte(3, b'*'),
+        base64::DecodeError::InvalidLength,
+        base64::DecodeError::InvalidLastSymbol(7, b'B'),
+        base64::DecodeError::InvalidPadding,
+    ] {
+        let error = ParseError::from(error);
+        let json = serde_json::to_string(&error).expect("should serialize");
+        assert_eq!(
+            error,
+            serde_json::from_str::<ParseError>(&json).expect("should deserialize")
+        );
+    }
+}
+
+#[test]
+fn test_serde_round_trip_non_fatal_errors() {
+    let config = ParserConfig {
+        crc_mismatch: ErrorHandling::NonFatal,
+        ..Default::default()
+    };
+    let bytes = decode_hex(
+        "FC3034000000000000FFFFF00506FE72BD0050001E021C435545494800008E7FCF0001A599B00808000000002CA0A18A3402009AC9D17F",
+    )
+    .expect("should be valid hex");
+    let splice_info_section = SpliceInfoSection::try_from_bytes_with_config(&bytes, &config)
+        .expect("should parse with non-fatal errors");
+    assert!(matches!(
+        splice_info_section.non_fatal_errors[..],
+        [ParseError::CRCMismatch { .. }]
+    ));
+    let json = serde_json::to_string(&splice_info_section).expect("should serialize");
+    assert_eq!(
+        splice_info_section,
+        serde_json::from_str::<SpliceInfoSection>(&json).expect("should deserialize")
+    );
+}
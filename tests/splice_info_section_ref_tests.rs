@@ -0,0 +1,176 @@
+use base64::prelude::*;
+use pretty_assertions::assert_eq;
+use scte35::{
+    splice_descriptor::{segmentation_descriptor::SegmentationUPIDType, SpliceDescriptor},
+    splice_info_section::SpliceInfoSection,
+    splice_info_section_ref::SpliceInfoSectionRef,
+};
+
+const BASE64_SAMPLES: [&str; 6] = [
+    "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=",
+    "/DBhAAAAAAAA///wBQb+qM1E7QBLAhdDVUVJSAAArX+fCAgAAAAALLLXnTUCAAIXQ1VFSUgAACZ/nwgIAAAAACyy150RAAACF0NVRUlIAAAnf58ICAAAAAAsstezEAAAihiGnw==",
+    "/DBHAAAAAAAA///wBQb+AAAAAAAxAi9DVUVJAAAAA3+/BCAGCis0AQEBBQEBDSATAAAA0skDbI8ZU0OrcBTS1xi/2hEAAPUV9+0=",
+    "/DB5AAAAAAAAAP/wBQb/DkfmpABjAhdDVUVJhPHPYH+/CAgAAAAABy4QajEBGAIcQ1VFSYTx71B//wAAK3NwCAgAAAAABy1cxzACGAIqQ1VFSYTx751/vwwbUlRMTjFIAQAAAAAxMzU2MTY2MjQ1NTUxQjEAAQAALL95dg==",
+    MID_BASE64,
+    MPU_BASE64,
+];
+
+const MID_BASE64: &str = "/DBwAAAAAAAAAP/wBQb/AAAAAABaAlhDVUVJAAAAAn//AABSZcANRAoMFHeL5eP2AAAAAAAACgwUd4vl4/YAAAAAAAAJJlNJR05BTDpMeTlFTUd4S1IwaEZaVXRwTUhkQ1VWWm5SVUZuWnowNgEB1Dao2g==";
+
+const MPU_BASE64: &str = "/DCRAAAAAAAAAP/wBQb/Y+5rBgB7AnlDVUVJAAAAZH/DAAD3NeEMZU5CQ1V7ImFzc2V0SWQiOiJwZWFjb2NrXzYwMDExMSIsImN1ZURhdGEiOnsiY3VlVHlwZSI6InN0YW5kYXJkX2JyZWFrIiwia2V5IjoicGIiLCJ2YWx1ZSI6InN0YW5kYXJkIn19MAAAqcgNEg==";
+
+fn samples() -> Vec<Vec<u8>> {
+    BASE64_SAMPLES
+        .iter()
+        .map(|sample| {
+            BASE64_STANDARD
+                .decode(sample)
+                .expect("should be valid base64")
+        })
+        .collect()
+}
+
+// MARK: - Conversion to owned
+
+#[test]
+fn test_section_ref_matches_owned_parse() {
+    for bytes in samples() {
+        let owned = SpliceInfoSection::try_from_bytes(&bytes).expect("should parse owned");
+        let section_ref = SpliceInfoSectionRef::try_from_bytes(&bytes).expect("should parse ref");
+        assert_eq!(owned.table_id, section_ref.table_id);
+        assert_eq!(owned.pts_adjustment, section_ref.pts_adjustment);
+        assert_eq!(owned.tier, section_ref.tier);
+        assert_eq!(owned.crc_32, section_ref.crc_32);
+        assert_eq!(
+            owned.splice_command,
+            section_ref
+                .splice_command
+                .try_to_owned()
+                .expect("should convert splice command")
+        );
+        assert_eq!(
+            owned.splice_descriptors.len(),
+            section_ref.splice_descriptors.len()
+        );
+        for (descriptor, descriptor_ref) in owned
+            .splice_descriptors
+            .iter()
+            .zip(section_ref.splice_descriptors.iter())
+        {
+            assert_eq!(
+                descriptor,
+                &descriptor_ref
+                    .try_to_owned()
+                    .expect("should convert descriptor")
+            );
+            let SpliceDescriptor::SegmentationDescriptor(segmentation_descriptor) = descriptor
+            else {
+                continue;
+            };
+            let segmentation_descriptor_ref = descriptor_ref
+                .segmentation_descriptor()
+                .expect("should be segmentation descriptor")
+                .expect("should parse segmentation descriptor");
+            assert_eq!(
+                segmentation_descriptor.event_id,
+                segmentation_descriptor_ref.event_id
+            );
+            let scheduled_event = segmentation_descriptor
+                .scheduled_event
+                .as_ref()
+                .expect("should have scheduled event");
+            let scheduled_event_ref = segmentation_descriptor_ref
+                .scheduled_event
+                .expect("should have scheduled event ref");
+            assert_eq!(
+                scheduled_event.segmentation_type_id,
+                scheduled_event_ref.segmentation_type_id
+            );
+            assert_eq!(scheduled_event.sub_segment, scheduled_event_ref.sub_segment);
+            assert_eq!(
+                scheduled_event.segmentation_upid,
+                scheduled_event_ref
+                    .segmentation_upid
+                    .try_to_owned()
+                    .expect("should convert UPID")
+            );
+        }
+        assert_eq!(
+            owned,
+            section_ref.try_to_owned().expect("should convert section")
+        );
+    }
+}
+
+// MARK: - Borrowed data
+
+#[test]
+fn test_section_ref_mpu_borrows_from_input() {
+    let bytes = BASE64_STANDARD
+        .decode(MPU_BASE64)
+        .expect("should be valid base64");
+    let section_ref = SpliceInfoSectionRef::try_from_bytes(&bytes).expect("should parse ref");
+    let upid = section_ref.splice_descriptors[0]
+        .segmentation_descriptor()
+        .expect("should be segmentation descriptor")
+        .expect("should parse segmentation descriptor")
+        .scheduled_event
+        .expect("should have scheduled event")
+        .segmentation_upid;
+    let mpu = upid.mpu().expect("should be MPU");
+    assert_eq!("NBCU", mpu.format_specifier);
+    assert!(mpu
+        .private_data
+        .starts_with(br#"{"assetId":"peacock_600111""#));
+    assert!(bytes.as_ptr_range().contains(&mpu.private_data.as_ptr()));
+    assert_eq!(None, upid.as_str());
+}
+
+#[test]
+fn test_section_ref_mid() {
+    let bytes = BASE64_STANDARD
+        .decode(MID_BASE64)
+        .expect("should be valid base64");
+    let section_ref = SpliceInfoSectionRef::try_from_bytes(&bytes).expect("should parse ref");
+    let upid = section_ref.splice_descriptors[0]
+        .segmentation_descriptor()
+        .expect("should be segmentation descriptor")
+        .expect("should parse segmentation descriptor")
+        .scheduled_event
+        .expect("should have scheduled event")
+        .segmentation_upid;
+    let mid = upid
+        .mid()
+        .expect("should be MID")
+        .expect("should parse MID");
+    assert_eq!(
+        vec![
+            SegmentationUPIDType::EIDR,
+            SegmentationUPIDType::EIDR,
+            SegmentationUPIDType::ADI
+        ],
+        mid.iter()
+            .map(|upid| upid.upid_type.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some("SIGNAL:Ly9EMGxKR0hFZUtpMHdCUVZnRUFnZz0"),
+        mid[2].as_str()
+    );
+    assert!(mid[0].as_str().is_none());
+}
+
+// MARK: - Malformed data
+
+#[test]
+fn test_section_ref_truncated_data_is_error() {
+    for bytes in samples() {
+        for length in 0..bytes.len() - 4 {
+            assert!(
+                SpliceInfoSectionRef::try_from_bytes(&bytes[..length]).is_err(),
+                "should fail for truncated length {}",
+                length
+            );
+        }
+    }
+}
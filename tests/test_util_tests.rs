@@ -0,0 +1,56 @@
+#![cfg(feature = "test-util")]
+
+use proptest::prelude::*;
+use scte35::{
+    splice_info_section::SpliceInfoSection, splice_info_section_ref::SpliceInfoSectionRef,
+    test_util,
+};
+
+proptest! {
+    // MARK: - Round trip
+
+    #[test]
+    fn test_generated_section_round_trip(section in test_util::splice_info_section()) {
+        let bytes = section.try_to_bytes().expect("should encode generated section");
+        prop_assert_eq!(
+            &section,
+            &SpliceInfoSection::try_from_bytes(&bytes).expect("should parse generated section")
+        );
+        prop_assert_eq!(
+            section,
+            SpliceInfoSectionRef::try_from_bytes(&bytes)
+                .expect("should parse generated section ref")
+                .try_to_owned()
+                .expect("should convert generated section ref")
+        );
+    }
+
+    #[test]
+    fn test_generated_descriptors_are_allowed_with_command(
+        section in test_util::splice_info_section()
+    ) {
+        let splice_command_type = section.splice_command.command_type();
+        for descriptor in &section.splice_descriptors {
+            prop_assert!(descriptor.tag().is_allowed_with(&splice_command_type));
+        }
+    }
+
+    // MARK: - Annotation
+
+    #[test]
+    fn test_generated_section_annotations_cover_every_bit(
+        section in test_util::splice_info_section()
+    ) {
+        let bytes = section.try_to_bytes().expect("should encode generated section");
+        let annotated_section = SpliceInfoSection::annotate(&bytes);
+        prop_assert_eq!(Some(&section), annotated_section.result.as_ref().ok());
+        let mut bit_offset = 0;
+        for annotation in annotated_section.iter() {
+            if annotation.children.is_empty() {
+                prop_assert_eq!(bit_offset, annotation.bit_offset);
+                bit_offset += annotation.bit_width;
+            }
+        }
+        prop_assert_eq!(bytes.len() * 8, bit_offset);
+    }
+}
@@ -0,0 +1,737 @@
+mod common;
+
+use common::TIME_SIGNAL_HEX;
+use scte35::{
+    splice_command::{
+        splice_insert::SpliceInsert, splice_schedule, time_signal::TimeSignal, SpliceCommand,
+    },
+    splice_descriptor::{
+        segmentation_descriptor::SegmentationDescriptor, time_descriptor::TimeDescriptor,
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+    time::{
+        duration_from_ticks, system_time_from_utc_splice_time, ticks_from_duration,
+        utc_splice_time_from_system_time, BreakDuration, ComponentSpliceTime, Pts90k, Rounding,
+        SpliceTime, GPS_EPOCH_UNIX_SECONDS,
+    },
+};
+use std::{
+    cmp::Ordering,
+    time::{Duration, UNIX_EPOCH},
+};
+
+// MARK: - Pts90k
+
+#[test]
+fn test_pts_90k_wrapping_add_and_sub() {
+    assert_eq!(Pts90k(300), Pts90k(100) + Pts90k(200));
+    assert_eq!(Pts90k(0), Pts90k::MAX + Pts90k(1));
+    assert_eq!(Pts90k(0xF), Pts90k(0x1_FFFF_FFF0) + Pts90k(0x1F));
+    assert_eq!(Pts90k::MAX, Pts90k(0) - Pts90k(1));
+    assert_eq!(Pts90k(0x1F), Pts90k(0xF) - Pts90k(0x1_FFFF_FFF0));
+}
+
+#[test]
+fn test_pts_90k_distance() {
+    assert_eq!(100, Pts90k(100).distance_to(Pts90k(200)));
+    assert_eq!(0x1F, Pts90k(0x1_FFFF_FFF0).distance_to(Pts90k(0xF)));
+    assert_eq!(Pts90k::WRAP - 100, Pts90k(200).distance_to(Pts90k(100)));
+    assert_eq!(-100, Pts90k(200).signed_distance_to(Pts90k(100)));
+    assert_eq!(0x1F, Pts90k(0x1_FFFF_FFF0).signed_distance_to(Pts90k(0xF)));
+    assert_eq!(-0x1F, Pts90k(0xF).signed_distance_to(Pts90k(0x1_FFFF_FFF0)));
+}
+
+#[test]
+fn test_pts_90k_wrapping_cmp() {
+    assert_eq!(Ordering::Equal, Pts90k(100).wrapping_cmp(Pts90k(100)));
+    assert_eq!(Ordering::Less, Pts90k(100).wrapping_cmp(Pts90k(200)));
+    assert_eq!(Ordering::Greater, Pts90k(200).wrapping_cmp(Pts90k(100)));
+    // Across the wrap, a small PTS is after a large one.
+    assert_eq!(Ordering::Less, Pts90k::MAX.wrapping_cmp(Pts90k(0)));
+    assert_eq!(Ordering::Greater, Pts90k(0).wrapping_cmp(Pts90k::MAX));
+}
+
+#[test]
+fn test_pts_90k_conversions() {
+    assert_eq!(Pts90k(90_000), Pts90k::from(90_000));
+    assert_eq!(90_000, u64::from(Pts90k(90_000)));
+    assert_eq!(Ok(Pts90k(1924989008)), "1924989008".parse());
+    assert!("-1".parse::<Pts90k>().is_err());
+    assert_eq!("1924989008", Pts90k(1924989008).to_string());
+    assert_eq!(Duration::from_secs(1), Pts90k(90_000).as_duration());
+    assert_eq!(
+        Pts90k(5426460),
+        Pts90k::from_duration(Duration::from_millis(60_294), Rounding::Nearest)
+    );
+}
+
+#[test]
+fn test_pts_adjustment_applied_to_pts_time() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let mut splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    splice_info_section.pts_adjustment = Pts90k(0x1_9000_0000);
+    let SpliceCommand::TimeSignal(time_signal) = &splice_info_section.splice_command else {
+        panic!("should be time signal");
+    };
+    let pts_time = time_signal
+        .splice_time
+        .pts_time
+        .expect("should have pts_time");
+    assert_eq!(
+        Pts90k(45940816),
+        pts_time + splice_info_section.pts_adjustment
+    );
+}
+
+#[test]
+fn test_adjusted_splice_time() {
+    let splice_insert = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x1_FFFF_FFF0))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .splice_time(Pts90k(0x20))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(Some(Pts90k(0x10)), splice_insert.adjusted_splice_time());
+
+    let immediate = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x100))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .immediate()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, immediate.adjusted_splice_time());
+
+    let component_mode = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x100))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .component(1, Some(Pts90k(0x20)))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, component_mode.adjusted_splice_time());
+
+    let splice_null = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x100))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, splice_null.adjusted_splice_time());
+}
+
+#[test]
+fn test_splice_pts() {
+    let time_signal = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x10))
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0x20)),
+            },
+        }))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(Some(Pts90k(0x30)), time_signal.splice_pts());
+
+    let program_mode = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x1_FFFF_FFF0))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .splice_time(Pts90k(0x20))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(Some(Pts90k(0x10)), program_mode.splice_pts());
+
+    // The earliest component is the one that is after the wrap of the clock once adjusted.
+    let component_mode = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x20))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .component(1, Some(Pts90k(0x10)))
+                .component(2, None)
+                .component(3, Some(Pts90k(0x1_FFFF_FFF0)))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(Some(Pts90k(0x10)), component_mode.splice_pts());
+
+    let immediate = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .immediate()
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, immediate.splice_pts());
+
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, splice_null.splice_pts());
+}
+
+#[test]
+fn test_planned_duration() {
+    let splice_insert = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .splice_time(Pts90k(0x20))
+                .break_duration(Pts90k(90_000 * 30), true)
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(
+        Some(Duration::from_secs(30)),
+        splice_insert.planned_duration()
+    );
+
+    let time_signal = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::TimeSignal(TimeSignal {
+            splice_time: SpliceTime {
+                pts_time: Some(Pts90k(0x20)),
+            },
+        }))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(1)
+                .provider_placement_opportunity_end()
+                .build()
+                .expect("should build segmentation descriptor"),
+        ))
+        .splice_descriptor(SpliceDescriptor::SegmentationDescriptor(
+            SegmentationDescriptor::builder(2)
+                .provider_placement_opportunity_start()
+                .duration_90khz(90_000 * 15)
+                .build()
+                .expect("should build segmentation descriptor"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(
+        Some(Duration::from_secs(15)),
+        time_signal.planned_duration()
+    );
+
+    let without_break_duration = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .splice_time(Pts90k(0x20))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, without_break_duration.planned_duration());
+
+    let splice_null = SpliceInfoSection::builder()
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(None, splice_null.planned_duration());
+}
+
+#[test]
+fn test_try_apply_pts_adjustment() {
+    let mut splice_info_section = SpliceInfoSection::builder()
+        .pts_adjustment(Pts90k(0x1_FFFF_FFF0))
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .component(1, Some(Pts90k(0x20)))
+                .component(2, Some(Pts90k(0x1_0000_0000)))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    splice_info_section
+        .try_apply_pts_adjustment()
+        .expect("should apply pts_adjustment");
+    let expected = SpliceInfoSection::builder()
+        .splice_command(SpliceCommand::SpliceInsert(
+            SpliceInsert::builder(1)
+                .component(1, Some(Pts90k(0x10)))
+                .component(2, Some(Pts90k(0xFFFF_FFF0)))
+                .build()
+                .expect("should build splice insert"),
+        ))
+        .build()
+        .expect("should build splice info section");
+    assert_eq!(expected, splice_info_section);
+    let hex_string = splice_info_section
+        .try_to_hex_string()
+        .expect("should encode");
+    assert_eq!(
+        Ok(splice_info_section),
+        SpliceInfoSection::try_from_hex_string(&hex_string)
+    );
+}
+
+#[test]
+fn test_try_apply_pts_adjustment_leaves_section_unchanged_on_error() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let mut splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    splice_info_section.tier = 0x1000;
+    splice_info_section.pts_adjustment = Pts90k(0x100);
+    let original = splice_info_section.clone();
+    assert!(splice_info_section.try_apply_pts_adjustment().is_err());
+    assert_eq!(original, splice_info_section);
+}
+
+// MARK: - Ordering
+
+#[test]
+fn test_splice_time_ordering() {
+    let mut splice_times = vec![
+        SpliceTime {
+            pts_time: Some(Pts90k(200)),
+        },
+        SpliceTime { pts_time: None },
+        SpliceTime {
+            pts_time: Some(Pts90k(100)),
+        },
+    ];
+    splice_times.sort();
+    assert_eq!(
+        vec![None, Some(Pts90k(100)), Some(Pts90k(200))],
+        splice_times
+            .into_iter()
+            .map(|splice_time| splice_time.pts_time)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_break_duration_ordering() {
+    let short = BreakDuration {
+        auto_return: true,
+        duration: Pts90k(100),
+    };
+    let long = BreakDuration {
+        auto_return: false,
+        duration: Pts90k(200),
+    };
+    assert_eq!(Ordering::Less, short.cmp(&long));
+    assert_eq!(
+        Ordering::Less,
+        BreakDuration {
+            auto_return: false,
+            ..short.clone()
+        }
+        .cmp(&short)
+    );
+    assert_eq!(Some(&long), [short.clone(), long.clone()].iter().max());
+}
+
+// MARK: - Component splice times
+
+#[test]
+fn test_splice_insert_component_splice_times() {
+    let scheduled_event = SpliceInsert::builder(1)
+        .component(1, Some(Pts90k(Pts90k::WRAP - 10)))
+        .component(2, Some(Pts90k(20)))
+        .component(3, Some(Pts90k(Pts90k::WRAP - 20)))
+        .build()
+        .expect("should build splice insert")
+        .scheduled_event
+        .expect("should be scheduled");
+    let pts_adjustment = Pts90k(5);
+    let arrival_pts = Pts90k(Pts90k::WRAP - 90_000);
+    assert_eq!(
+        vec![
+            ComponentSpliceTime {
+                component_tag: 1,
+                pts: Pts90k(Pts90k::WRAP - 5),
+                is_immediate: false,
+            },
+            ComponentSpliceTime {
+                component_tag: 2,
+                pts: Pts90k(25),
+                is_immediate: false,
+            },
+            ComponentSpliceTime {
+                component_tag: 3,
+                pts: Pts90k(Pts90k::WRAP - 15),
+                is_immediate: false,
+            },
+        ],
+        scheduled_event.component_splice_times(pts_adjustment, arrival_pts)
+    );
+    // The earliest is before the wrap of the clock.
+    assert_eq!(
+        Pts90k(Pts90k::WRAP - 15),
+        scheduled_event.earliest_splice_time(pts_adjustment, arrival_pts)
+    );
+    assert_eq!(
+        Some(Pts90k(25)),
+        scheduled_event.component_splice_time(2, pts_adjustment, arrival_pts)
+    );
+    assert_eq!(
+        None,
+        scheduled_event.component_splice_time(4, pts_adjustment, arrival_pts)
+    );
+}
+
+#[test]
+fn test_splice_insert_immediate_component_splice_times() {
+    let scheduled_event = SpliceInsert::builder(1)
+        .component(1, None)
+        .component(2, None)
+        .immediate()
+        .build()
+        .expect("should build splice insert")
+        .scheduled_event
+        .expect("should be scheduled");
+    let arrival_pts = Pts90k(90_000);
+    assert_eq!(
+        vec![
+            ComponentSpliceTime {
+                component_tag: 1,
+                pts: arrival_pts,
+                is_immediate: true,
+            },
+            ComponentSpliceTime {
+                component_tag: 2,
+                pts: arrival_pts,
+                is_immediate: true,
+            },
+        ],
+        scheduled_event.component_splice_times(Pts90k(5), arrival_pts)
+    );
+    assert_eq!(
+        arrival_pts,
+        scheduled_event.earliest_splice_time(Pts90k(5), arrival_pts)
+    );
+}
+
+#[test]
+fn test_splice_insert_program_splice_times() {
+    let scheduled_event = SpliceInsert::builder(1)
+        .splice_time(Pts90k(1_000))
+        .build()
+        .expect("should build splice insert")
+        .scheduled_event
+        .expect("should be scheduled");
+    assert_eq!(
+        Vec::<ComponentSpliceTime>::new(),
+        scheduled_event.component_splice_times(Pts90k(5), Pts90k(0))
+    );
+    assert_eq!(
+        Pts90k(1_005),
+        scheduled_event.earliest_splice_time(Pts90k(5), Pts90k(0))
+    );
+    assert_eq!(
+        Some(Pts90k(1_005)),
+        scheduled_event.component_splice_time(7, Pts90k(5), Pts90k(0))
+    );
+
+    let immediate = SpliceInsert::builder(1)
+        .immediate()
+        .build()
+        .expect("should build splice insert")
+        .scheduled_event
+        .expect("should be scheduled");
+    assert_eq!(
+        Pts90k(42),
+        immediate.earliest_splice_time(Pts90k(5), Pts90k(42))
+    );
+}
+
+#[test]
+fn test_component_segmentation_pts_offset() {
+    let descriptor = SegmentationDescriptor::builder(1)
+        .program_start()
+        .component_segment(1, Pts90k(0))
+        .component_segment(2, Pts90k(90_000))
+        .build()
+        .expect("should build segmentation descriptor");
+    let splice_time = SpliceTime {
+        pts_time: Some(Pts90k(Pts90k::WRAP - 45_000)),
+    };
+    assert_eq!(
+        vec![
+            ComponentSpliceTime {
+                component_tag: 1,
+                pts: Pts90k(Pts90k::WRAP - 44_990),
+                is_immediate: false,
+            },
+            ComponentSpliceTime {
+                component_tag: 2,
+                pts: Pts90k(45_010),
+                is_immediate: false,
+            },
+        ],
+        descriptor.component_splice_times(Some(&splice_time), Pts90k(10), Pts90k(0))
+    );
+
+    // The offset is applied to the immediate splice time when there is no pts_time.
+    let arrival_pts = Pts90k(1_000);
+    let immediate = ComponentSpliceTime {
+        component_tag: 2,
+        pts: Pts90k(91_000),
+        is_immediate: true,
+    };
+    assert_eq!(
+        immediate,
+        descriptor.component_splice_times(None, Pts90k(10), arrival_pts)[1]
+    );
+    assert_eq!(
+        immediate,
+        descriptor.component_splice_times(
+            Some(&SpliceTime { pts_time: None }),
+            Pts90k(10),
+            arrival_pts
+        )[1]
+    );
+}
+
+#[test]
+fn test_program_segmentation_has_no_component_splice_times() {
+    let splice_time = SpliceTime {
+        pts_time: Some(Pts90k(90_000)),
+    };
+    let program = SegmentationDescriptor::builder(1)
+        .program_start()
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        Vec::<ComponentSpliceTime>::new(),
+        program.component_splice_times(Some(&splice_time), Pts90k(0), Pts90k(0))
+    );
+    let cancelled = SegmentationDescriptor::builder(1)
+        .cancel()
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        Vec::<ComponentSpliceTime>::new(),
+        cancelled.component_splice_times(Some(&splice_time), Pts90k(0), Pts90k(0))
+    );
+}
+
+// MARK: - Duration conversion
+
+#[test]
+fn test_duration_from_ticks() {
+    assert_eq!(Duration::ZERO, duration_from_ticks(0));
+    assert_eq!(Duration::from_secs(1), duration_from_ticks(90_000));
+    assert_eq!(Duration::from_nanos(11_111), duration_from_ticks(1));
+    assert_eq!(Duration::from_nanos(22_222), duration_from_ticks(2));
+    assert_eq!(Duration::from_nanos(55_556), duration_from_ticks(5));
+    assert_eq!(
+        Duration::from_secs(95443) + Duration::from_nanos(717_677_778),
+        duration_from_ticks(0x1_FFFF_FFFF)
+    );
+}
+
+#[test]
+fn test_ticks_from_duration_rounding() {
+    let whole = Duration::from_millis(60_294);
+    for rounding in [Rounding::Down, Rounding::Nearest, Rounding::Up] {
+        assert_eq!(5426460, ticks_from_duration(whole, rounding));
+    }
+    let just_over_half = Duration::from_nanos(5_556);
+    assert_eq!(0, ticks_from_duration(just_over_half, Rounding::Down));
+    assert_eq!(1, ticks_from_duration(just_over_half, Rounding::Nearest));
+    assert_eq!(1, ticks_from_duration(just_over_half, Rounding::Up));
+    let just_under_half = Duration::from_nanos(5_555);
+    assert_eq!(0, ticks_from_duration(just_under_half, Rounding::Nearest));
+    assert_eq!(1, ticks_from_duration(just_under_half, Rounding::Up));
+    assert_eq!(u64::MAX, ticks_from_duration(Duration::MAX, Rounding::Down));
+}
+
+#[test]
+fn test_ticks_round_trip_through_duration() {
+    for ticks in [0, 1, 2, 5, 89_999, 5426370, 0x1_FFFF_FFFF] {
+        assert_eq!(
+            ticks,
+            ticks_from_duration(duration_from_ticks(ticks), Rounding::Nearest)
+        );
+    }
+}
+
+#[test]
+fn test_splice_time_and_break_duration_as_duration() {
+    let splice_time = SpliceTime::from_duration(Duration::from_secs(10), Rounding::Nearest);
+    assert_eq!(Some(Pts90k(900_000)), splice_time.pts_time);
+    assert_eq!(Some(Duration::from_secs(10)), splice_time.as_duration());
+    assert_eq!(None, SpliceTime { pts_time: None }.as_duration());
+
+    let break_duration =
+        BreakDuration::from_duration(Duration::from_millis(60_294), Rounding::Down, true);
+    assert_eq!(
+        BreakDuration {
+            auto_return: true,
+            duration: Pts90k(5426460)
+        },
+        break_duration
+    );
+    assert_eq!(Duration::from_millis(60_294), break_duration.as_duration());
+}
+
+#[test]
+fn test_segmentation_duration_as_duration() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    let SpliceDescriptor::SegmentationDescriptor(descriptor) =
+        &splice_info_section.splice_descriptors[0]
+    else {
+        panic!("should be segmentation descriptor");
+    };
+    assert_eq!(
+        Some(Duration::from_millis(307_000)),
+        descriptor
+            .scheduled_event
+            .as_ref()
+            .expect("should have scheduled event")
+            .segmentation_duration_as_duration()
+    );
+
+    let descriptor = SegmentationDescriptor::builder(1)
+        .provider_placement_opportunity_start()
+        .duration(Duration::from_nanos(30_000_005_556), Rounding::Down)
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        Some(2_700_000),
+        descriptor
+            .scheduled_event
+            .expect("should have scheduled event")
+            .segmentation_duration
+    );
+}
+
+// MARK: - utc_splice_time conversion
+
+#[test]
+fn test_utc_splice_time_system_time_conversion() {
+    assert_eq!(
+        UNIX_EPOCH + Duration::from_secs(GPS_EPOCH_UNIX_SECONDS),
+        system_time_from_utc_splice_time(0)
+    );
+    // 2024-01-01T00:00:00Z
+    let system_time = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+    assert_eq!(
+        Some(1_388_102_400),
+        utc_splice_time_from_system_time(system_time)
+    );
+    assert_eq!(
+        Some(1_388_102_400),
+        utc_splice_time_from_system_time(system_time + Duration::from_millis(999))
+    );
+    assert_eq!(system_time, system_time_from_utc_splice_time(1_388_102_400));
+    assert_eq!(None, utc_splice_time_from_system_time(UNIX_EPOCH));
+    assert_eq!(
+        None,
+        utc_splice_time_from_system_time(
+            system_time_from_utc_splice_time(u32::MAX) + Duration::from_secs(1)
+        )
+    );
+
+    let program_mode = splice_schedule::ProgramMode {
+        utc_splice_time: 1_388_102_400,
+    };
+    assert_eq!(system_time, program_mode.splice_system_time());
+    let component_mode = splice_schedule::ComponentMode {
+        component_tag: 1,
+        utc_splice_time: 1_388_102_400,
+    };
+    assert_eq!(system_time, component_mode.splice_system_time());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_utc_splice_time_date_time_conversion() {
+    use chrono::{DateTime, FixedOffset, Utc};
+    use scte35::time::{date_time_from_utc_splice_time, utc_splice_time_from_date_time};
+
+    let date_time = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+        .expect("should parse date time")
+        .with_timezone(&Utc);
+    assert_eq!(date_time, date_time_from_utc_splice_time(1_388_102_400));
+    assert_eq!(
+        Some(1_388_102_400),
+        utc_splice_time_from_date_time(&date_time)
+    );
+    let offset_date_time = DateTime::<FixedOffset>::parse_from_rfc3339("2024-01-01T01:00:00+01:00")
+        .expect("should parse date time");
+    assert_eq!(
+        Some(1_388_102_400),
+        utc_splice_time_from_date_time(&offset_date_time)
+    );
+    let before_gps_epoch =
+        DateTime::parse_from_rfc3339("1980-01-05T23:59:59Z").expect("should parse date time");
+    assert_eq!(None, utc_splice_time_from_date_time(&before_gps_epoch));
+    assert_eq!(
+        date_time,
+        splice_schedule::ProgramMode {
+            utc_splice_time: 1_388_102_400
+        }
+        .splice_date_time()
+    );
+}
+
+// MARK: - TimeDescriptor conversion
+
+#[test]
+fn test_time_descriptor_to_utc_and_ntp() {
+    let time_descriptor = TimeDescriptor {
+        identifier: 0x43554549,
+        tai_seconds: 1_704_067_237,
+        tai_ns: 250_000_000,
+        utc_offset: 37,
+    };
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::new(1_704_067_200, 250_000_000)),
+        time_descriptor.utc_system_time()
+    );
+    assert_eq!(3_913_056_000, time_descriptor.ntp_seconds());
+
+    let before_unix_epoch = TimeDescriptor {
+        identifier: 0x43554549,
+        tai_seconds: 10,
+        tai_ns: 0,
+        utc_offset: 37,
+    };
+    assert_eq!(None, before_unix_epoch.utc_system_time());
+    assert_eq!(2_208_988_773, before_unix_epoch.ntp_seconds());
+}
+
+#[test]
+fn test_time_descriptor_from_utc() {
+    let utc = UNIX_EPOCH + Duration::new(1_704_067_200, 999_999_999);
+    assert_eq!(
+        Some(TimeDescriptor {
+            identifier: 0x43554549,
+            tai_seconds: 1_704_067_237,
+            tai_ns: 999_999_999,
+            utc_offset: 37,
+        }),
+        TimeDescriptor::from_utc(utc, 37)
+    );
+    assert_eq!(
+        Some(utc),
+        TimeDescriptor::from_utc(utc, 37).and_then(|descriptor| descriptor.utc_system_time())
+    );
+    assert_eq!(
+        None,
+        TimeDescriptor::from_utc(UNIX_EPOCH - Duration::from_secs(1), 37)
+    );
+}
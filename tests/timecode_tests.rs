@@ -0,0 +1,117 @@
+use pretty_assertions::assert_eq;
+use scte35::{
+    time::{Pts90k, Rounding},
+    timecode::{FrameRate, Timecode},
+};
+
+// MARK: - Frame index
+
+#[test]
+fn test_frame_index_from_pts() {
+    assert_eq!(
+        25,
+        FrameRate::FPS_25.frame_index(Pts90k(90_000), Rounding::Down)
+    );
+    assert_eq!(
+        1,
+        FrameRate::FPS_29_97.frame_index(Pts90k(3003), Rounding::Down)
+    );
+    let frame_rate = FrameRate::FPS_29_97;
+    assert_eq!(0, frame_rate.frame_index(Pts90k(1501), Rounding::Down));
+    assert_eq!(0, frame_rate.frame_index(Pts90k(1501), Rounding::Nearest));
+    assert_eq!(1, frame_rate.frame_index(Pts90k(1502), Rounding::Nearest));
+    assert_eq!(1, frame_rate.frame_index(Pts90k(1), Rounding::Up));
+}
+
+#[test]
+fn test_pts_from_frame_index() {
+    assert_eq!(Pts90k(3003), FrameRate::FPS_29_97_DROP_FRAME.pts(1));
+    assert_eq!(Pts90k(1502), FrameRate::FPS_59_94.pts(1));
+    assert_eq!(Pts90k(3600), FrameRate::FPS_25.pts(1));
+    // Frame 2860000 at 29.97 fps is at PTS 8588580000, so frame 2861000 is past the wrap.
+    assert_eq!(
+        Pts90k(2861000 * 3003 - Pts90k::WRAP),
+        FrameRate::FPS_29_97.pts(2861000)
+    );
+    for frame_index in [0, 1, 1799, 1800, 17982, 100_000] {
+        let pts = FrameRate::FPS_23_976.pts(frame_index);
+        assert_eq!(
+            frame_index,
+            FrameRate::FPS_23_976.frame_index(pts, Rounding::Nearest)
+        );
+    }
+}
+
+#[test]
+fn test_frame_rate_new() {
+    assert_eq!(None, FrameRate::new(0, 1));
+    assert_eq!(None, FrameRate::new(25, 0));
+    let frame_rate = FrameRate::new(24000, 1001).expect("should be valid frame rate");
+    assert_eq!(FrameRate::FPS_23_976, frame_rate);
+    assert_eq!(24, frame_rate.timecode_base());
+    assert!(!frame_rate.is_drop_frame());
+    assert!(FrameRate::FPS_29_97_DROP_FRAME.is_drop_frame());
+}
+
+// MARK: - Timecode
+
+#[test]
+fn test_non_drop_frame_timecode() {
+    assert_eq!(
+        Timecode {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frames: 4,
+            is_drop_frame: false,
+        },
+        FrameRate::FPS_25.timecode(((3600 + 120 + 3) * 25) + 4)
+    );
+    assert_eq!(
+        "00:01:00:00",
+        FrameRate::FPS_29_97.timecode(1800).to_string()
+    );
+    assert_eq!(
+        "00:00:00:00",
+        FrameRate::FPS_24.timecode(24 * 60 * 60 * 24).to_string()
+    );
+}
+
+#[test]
+fn test_29_97_drop_frame_timecode() {
+    let frame_rate = FrameRate::FPS_29_97_DROP_FRAME;
+    assert_eq!("00:00:59;29", frame_rate.timecode(1799).to_string());
+    assert_eq!("00:01:00;02", frame_rate.timecode(1800).to_string());
+    assert_eq!("00:01:59;29", frame_rate.timecode(3597).to_string());
+    assert_eq!("00:02:00;02", frame_rate.timecode(3598).to_string());
+    assert_eq!("00:09:59;29", frame_rate.timecode(17981).to_string());
+    assert_eq!("00:10:00;00", frame_rate.timecode(17982).to_string());
+    assert_eq!("00:11:00;02", frame_rate.timecode(17982 + 1800).to_string());
+    assert_eq!("01:00:00;00", frame_rate.timecode(107892).to_string());
+}
+
+#[test]
+fn test_59_94_drop_frame_timecode() {
+    let frame_rate = FrameRate::FPS_59_94_DROP_FRAME;
+    assert_eq!("00:00:59;59", frame_rate.timecode(3599).to_string());
+    assert_eq!("00:01:00;04", frame_rate.timecode(3600).to_string());
+    assert_eq!("00:10:00;00", frame_rate.timecode(35964).to_string());
+    assert_eq!("01:00:00;00", frame_rate.timecode(215784).to_string());
+}
+
+#[test]
+fn test_timecode_at_pts() {
+    // One hour of 29.97 fps video is 107892 frames, each of 3003 ticks.
+    assert_eq!(
+        "01:00:00;00",
+        FrameRate::FPS_29_97_DROP_FRAME
+            .timecode_at(Pts90k(107892 * 3003), Rounding::Down)
+            .to_string()
+    );
+    assert_eq!(
+        "00:59:56:12",
+        FrameRate::FPS_29_97
+            .timecode_at(Pts90k(107892 * 3003), Rounding::Down)
+            .to_string()
+    );
+}
@@ -0,0 +1,305 @@
+mod common;
+
+use common::{segmentation_descriptor, time_signal};
+use pretty_assertions::assert_eq;
+use scte35::{
+    cue::AdBreak,
+    splice_command::SpliceCommand,
+    splice_descriptor::segmentation_descriptor::{
+        SegmentationDescriptor, SegmentationTypeID, SegmentationUPID,
+    },
+    time::Pts90k,
+    timeline::{Arrival, Opportunity, OpportunityState, Timeline},
+    tracker::{EventTracker, TrackedEvent},
+};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn start_pts_and_states(timeline: &Timeline) -> Vec<(u32, Pts90k, OpportunityState)> {
+    timeline
+        .opportunities()
+        .iter()
+        .map(|opportunity| {
+            (
+                opportunity.event_id,
+                opportunity.start_pts,
+                opportunity.state,
+            )
+        })
+        .collect()
+}
+
+// MARK: - Opportunities
+
+#[test]
+fn test_opportunity_from_ad_break() {
+    let (cue_out, cue_in) = AdBreak::new(1, 90_000 * 10, 90_000 * 30)
+        .upid(SegmentationUPID::MID(vec![
+            SegmentationUPID::AdID(String::from("ABCD0123456H")),
+            SegmentationUPID::new_ti(0x2CA0A18A),
+        ]))
+        .build()
+        .expect("should build ad break");
+    let wallclock = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let mut timeline = Timeline::new();
+    timeline.push(
+        &cue_out,
+        Arrival::new(Pts90k(90_000 * 12)).wallclock(wallclock),
+    );
+    let expected = Opportunity {
+        event_id: 1,
+        segmentation_type_id: SegmentationTypeID::ProviderPlacementOpportunityStart,
+        start_pts: Pts90k(90_000 * 10),
+        start_wallclock: Some(wallclock - Duration::from_secs(2)),
+        duration: Some(90_000 * 30),
+        segmentation_upids: vec![
+            SegmentationUPID::AdID(String::from("ABCD0123456H")),
+            SegmentationUPID::new_ti(0x2CA0A18A),
+        ],
+        state: OpportunityState::Open,
+    };
+    assert_eq!(vec![expected.clone()], timeline.opportunities());
+
+    // The in is 2 seconds early.
+    let mut cue_in = cue_in;
+    let SpliceCommand::TimeSignal(time_signal) = &mut cue_in.splice_command else {
+        panic!("should be time signal");
+    };
+    time_signal.splice_time.pts_time = Some(Pts90k(90_000 * 38));
+    timeline.push(&cue_in, Arrival::new(Pts90k(90_000 * 37)));
+    let ended = Opportunity {
+        duration: Some(90_000 * 28),
+        state: OpportunityState::Ended,
+        ..expected
+    };
+    assert_eq!(vec![ended.clone()], timeline.opportunities());
+    assert_eq!(Some(Duration::from_secs(28)), ended.duration_as_duration());
+    assert_eq!(Some(Pts90k(90_000 * 38)), ended.end_pts());
+}
+
+#[test]
+fn test_opportunities_are_ordered_by_start() {
+    let mut timeline = Timeline::new();
+    timeline.push(
+        &time_signal(
+            0,
+            Some(5000),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(100)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            Some(3000),
+            vec![segmentation_descriptor(
+                2,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(200)),
+    );
+    // The pts_adjustment is added to the pts_time.
+    timeline.push(
+        &time_signal(
+            1000,
+            Some(3000),
+            vec![segmentation_descriptor(
+                3,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(300)),
+    );
+    assert_eq!(
+        vec![
+            (2, Pts90k(3000), OpportunityState::Open),
+            (3, Pts90k(4000), OpportunityState::Open),
+            (1, Pts90k(5000), OpportunityState::Open),
+        ],
+        start_pts_and_states(&timeline)
+    );
+}
+
+#[test]
+fn test_opportunities_are_ordered_across_clock_wrap() {
+    let mut timeline = Timeline::new();
+    let before_wrap = Pts90k::MAX.ticks() - 1000;
+    timeline.push(
+        &time_signal(
+            0,
+            Some(500),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(before_wrap)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            Some(before_wrap + 500),
+            vec![segmentation_descriptor(
+                2,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(before_wrap + 100)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            Some(100),
+            vec![segmentation_descriptor(
+                3,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(50)),
+    );
+    assert_eq!(
+        vec![
+            (2, Pts90k(before_wrap + 500), OpportunityState::Open),
+            (3, Pts90k(100), OpportunityState::Open),
+            (1, Pts90k(500), OpportunityState::Open),
+        ],
+        start_pts_and_states(&timeline)
+    );
+}
+
+#[test]
+fn test_immediate_start_uses_arrival() {
+    let mut timeline = Timeline::new();
+    timeline.push(
+        &time_signal(
+            0,
+            None,
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(1234)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            None,
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityEnd,
+                None,
+            )],
+        ),
+        Arrival::new(Pts90k(2000)),
+    );
+    let opportunity = &timeline.opportunities()[0];
+    assert_eq!(Pts90k(1234), opportunity.start_pts);
+    assert_eq!(None, opportunity.start_wallclock);
+    assert_eq!(Some(766), opportunity.duration);
+    assert_eq!(OpportunityState::Ended, opportunity.state);
+}
+
+// MARK: - Cancellation and missing ends
+
+#[test]
+fn test_cancelled_opportunity_is_removed() {
+    let mut timeline = Timeline::new();
+    timeline.push(
+        &time_signal(
+            0,
+            Some(1000),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(100)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            Some(2000),
+            vec![segmentation_descriptor(
+                2,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(200)),
+    );
+    let cancel = SegmentationDescriptor::builder(1)
+        .cancel()
+        .build()
+        .expect("should build segmentation descriptor");
+    let events = timeline.push(
+        &time_signal(0, None, vec![cancel]),
+        Arrival::new(Pts90k(300)),
+    );
+    assert!(matches!(events.as_slice(), [TrackedEvent::Cancelled(_)]));
+    assert_eq!(
+        vec![(2, Pts90k(2000), OpportunityState::Open)],
+        start_pts_and_states(&timeline)
+    );
+}
+
+#[test]
+fn test_missing_end_and_removal_of_ended_opportunities() {
+    let mut timeline = Timeline::with_tracker(EventTracker::new().end_tolerance(10));
+    timeline.push(
+        &time_signal(
+            0,
+            Some(1000),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(100)),
+    );
+    timeline.push(
+        &time_signal(
+            0,
+            Some(1500),
+            vec![segmentation_descriptor(
+                2,
+                SegmentationTypeID::ProviderPlacementOpportunityStart,
+                Some(900),
+            )],
+        ),
+        Arrival::new(Pts90k(200)),
+    );
+    assert_eq!(
+        Vec::<TrackedEvent>::new(),
+        timeline.advance_to(Pts90k(1910))
+    );
+    let events = timeline.advance_to(Pts90k(1911));
+    assert!(matches!(events.as_slice(), [TrackedEvent::MissingEnd(_)]));
+    assert_eq!(
+        vec![
+            (1, Pts90k(1000), OpportunityState::EndMissing),
+            (2, Pts90k(1500), OpportunityState::Open),
+        ],
+        start_pts_and_states(&timeline)
+    );
+
+    timeline.remove_ended_before(Pts90k(1900));
+    assert_eq!(2, timeline.opportunities().len());
+    timeline.remove_ended_before(Pts90k(1901));
+    assert_eq!(
+        vec![(2, Pts90k(1500), OpportunityState::Open)],
+        start_pts_and_states(&timeline)
+    );
+}
@@ -0,0 +1,227 @@
+#![cfg(feature = "tracing")]
+mod common;
+
+use common::TIME_SIGNAL_HEX;
+use pretty_assertions::assert_eq;
+use scte35::splice_info_section::{ErrorHandling, ParserConfig, SpliceInfoSection};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Metadata, Subscriber,
+};
+use tracing_core::span::Current;
+
+// MARK: - Recording subscriber
+
+#[derive(Debug, PartialEq)]
+struct RecordedEvent {
+    level: Level,
+    fields: HashMap<String, String>,
+}
+
+struct RecordedSpan {
+    metadata: &'static Metadata<'static>,
+    fields: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct Recording {
+    next_span_id: AtomicU64,
+    spans: Mutex<HashMap<u64, RecordedSpan>>,
+    entered: Mutex<Vec<u64>>,
+    events: Mutex<Vec<RecordedEvent>>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Recording>);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes) -> Id {
+        let id = self.0.next_span_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut fields = HashMap::new();
+        attributes.record(&mut FieldVisitor(&mut fields));
+        let metadata = attributes.metadata();
+        self.0
+            .spans
+            .lock()
+            .unwrap()
+            .insert(id, RecordedSpan { metadata, fields });
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record) {
+        if let Some(span) = self.0.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            values.record(&mut FieldVisitor(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut fields = HashMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.events.lock().unwrap().push(RecordedEvent {
+            level: *event.metadata().level(),
+            fields,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.entered.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.entered.lock().unwrap().pop();
+    }
+
+    // Needed for the fields that are recorded on `Span::current()`.
+    fn current_span(&self) -> Current {
+        let Some(id) = self.0.entered.lock().unwrap().last().copied() else {
+            return Current::none();
+        };
+        let metadata = self.0.spans.lock().unwrap()[&id].metadata;
+        Current::new(Id::from_u64(id), metadata)
+    }
+}
+
+impl Recorder {
+    fn span_fields(&self, name: &str) -> HashMap<String, String> {
+        let spans = self.0.spans.lock().unwrap();
+        let mut matching = spans.values().filter(|span| span.metadata.name() == name);
+        let span = matching.next().expect("should have recorded span");
+        assert!(matching.next().is_none(), "should have recorded one span");
+        span.fields.clone()
+    }
+
+    fn messages(&self, level: Level) -> Vec<String> {
+        self.0
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.level == level)
+            .map(|event| event.fields["message"].clone())
+            .collect()
+    }
+}
+
+fn field_map(fields: &[(&str, &str)]) -> HashMap<String, String> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+// MARK: - Instrumentation
+
+#[test]
+fn test_section_span_records_fields() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse section");
+    });
+    assert_eq!(
+        field_map(&[
+            ("length", "55"),
+            ("tier", "0xFFF"),
+            ("splice_command_type", "Time Signal"),
+            ("event_id", "1207959694"),
+        ]),
+        recorder.span_fields("splice_info_section")
+    );
+    assert_eq!(
+        vec![String::from("Parsed SpliceInfoSection")],
+        recorder.messages(Level::DEBUG)
+    );
+    assert!(recorder.messages(Level::WARN).is_empty());
+}
+
+#[test]
+fn test_splice_descriptor_events() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX).expect("should parse section");
+    });
+    let events = recorder.0.events.lock().unwrap();
+    let descriptor_events = events
+        .iter()
+        .filter(|event| event.level == Level::TRACE)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![&RecordedEvent {
+            level: Level::TRACE,
+            fields: field_map(&[
+                ("message", "Parsed splice descriptor"),
+                ("index", "0"),
+                ("tag", "2"),
+                ("identifier", "1129661769"),
+                ("event_id", "1207959694"),
+                ("segmentation_type_id", "52"),
+            ]),
+        }],
+        descriptor_events
+    );
+}
+
+#[test]
+fn test_non_fatal_errors_are_warnings() {
+    let mut data = SpliceInfoSection::try_from_hex_string(TIME_SIGNAL_HEX)
+        .expect("should parse section")
+        .try_to_bytes()
+        .expect("should encode section");
+    data[8] = 0x01;
+    let config = ParserConfig {
+        crc_mismatch: ErrorHandling::NonFatal,
+        ..ParserConfig::default()
+    };
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        SpliceInfoSection::try_from_bytes_with_config(&data, &config)
+            .expect("should parse section");
+    });
+    assert_eq!(
+        vec![String::from("Non-fatal error parsing SpliceInfoSection")],
+        recorder.messages(Level::WARN)
+    );
+}
+
+#[test]
+fn test_failed_parse_is_recorded() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        SpliceInfoSection::try_from_bytes(&[0xFC, 0x30, 0x34]).expect_err("should fail to parse");
+    });
+    assert_eq!(
+        vec![String::from("Failed to parse SpliceInfoSection")],
+        recorder.messages(Level::DEBUG)
+    );
+    assert_eq!(
+        field_map(&[("length", "3")]),
+        recorder.span_fields("splice_info_section")
+    );
+}
@@ -0,0 +1,365 @@
+mod common;
+
+use common::{segmentation_descriptor, time_signal};
+use pretty_assertions::assert_eq;
+use scte35::{
+    cue::AdBreak,
+    splice_descriptor::segmentation_descriptor::{
+        SegmentationDescriptor, SegmentationTypeID, SegmentationUPID,
+    },
+    time::Pts90k,
+    tracker::{EventTracker, TrackedEvent, TrackedSegment},
+};
+
+fn segment(event_id: u32, start_pts: Option<u64>, duration: Option<u64>) -> TrackedSegment {
+    TrackedSegment {
+        event_id,
+        segmentation_type_id: SegmentationTypeID::BreakStart,
+        start_pts: start_pts.map(Pts90k),
+        segmentation_duration: duration,
+        segmentation_upid: SegmentationUPID::NotUsed,
+    }
+}
+
+// MARK: - Pairing
+
+#[test]
+fn test_start_is_paired_with_end() {
+    let mut tracker = EventTracker::new();
+    assert_eq!(
+        vec![TrackedEvent::Started(segment(1, Some(100), Some(900)))],
+        tracker.process(&time_signal(
+            0,
+            Some(100),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::BreakStart,
+                Some(900)
+            )]
+        ))
+    );
+    assert_eq!(
+        vec![segment(1, Some(100), Some(900))],
+        tracker.open_segments()
+    );
+    assert_eq!(
+        vec![TrackedEvent::Ended {
+            segment: segment(1, Some(100), Some(900)),
+            end_pts: Some(Pts90k(1000)),
+        }],
+        tracker.process(&time_signal(
+            0,
+            Some(1000),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::BreakEnd,
+                None
+            )]
+        ))
+    );
+    assert!(tracker.open_segments().is_empty());
+}
+
+#[test]
+fn test_ad_break_cues_are_paired() {
+    let (cue_out, cue_in) = AdBreak::new(7, 1000, 9000)
+        .upid_ti(0x2CA0A18A)
+        .build()
+        .expect("should build ad break");
+    let mut tracker = EventTracker::new();
+    let started = tracker.process(&cue_out);
+    let [TrackedEvent::Started(started)] = started.as_slice() else {
+        panic!("should start: {started:?}");
+    };
+    assert_eq!(
+        SegmentationTypeID::ProviderPlacementOpportunityStart,
+        started.segmentation_type_id
+    );
+    assert_eq!(
+        SegmentationUPID::new_ti(0x2CA0A18A),
+        started.segmentation_upid
+    );
+    assert_eq!(Some(Pts90k(10000)), started.expected_end_pts());
+    assert_eq!(
+        vec![TrackedEvent::Ended {
+            segment: started.clone(),
+            end_pts: Some(Pts90k(10000)),
+        }],
+        tracker.process(&cue_in)
+    );
+}
+
+#[test]
+fn test_events_are_paired_by_event_id_and_type() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![
+            segmentation_descriptor(1, SegmentationTypeID::BreakStart, None),
+            segmentation_descriptor(2, SegmentationTypeID::BreakStart, None),
+        ],
+    ));
+    let chapter_end = SegmentationDescriptor::builder(2)
+        .chapter_end()
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        vec![
+            TrackedEvent::UnmatchedEnd {
+                event_id: 2,
+                segmentation_type_id: SegmentationTypeID::ChapterEnd,
+                end_pts: Some(Pts90k(200)),
+            },
+            TrackedEvent::Ended {
+                segment: segment(2, Some(100), None),
+                end_pts: Some(Pts90k(200)),
+            },
+        ],
+        tracker.process(&time_signal(
+            0,
+            Some(200),
+            vec![
+                chapter_end,
+                segmentation_descriptor(2, SegmentationTypeID::BreakEnd, None)
+            ]
+        ))
+    );
+    assert_eq!(vec![segment(1, Some(100), None)], tracker.open_segments());
+}
+
+#[test]
+fn test_types_that_are_not_starts_or_ends_are_ignored() {
+    let mut tracker = EventTracker::new();
+    let content_identification = SegmentationDescriptor::builder(1)
+        .segmentation_type_id(SegmentationTypeID::ContentIdentification)
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        Vec::<TrackedEvent>::new(),
+        tracker.process(&time_signal(0, Some(100), vec![content_identification]))
+    );
+    assert!(tracker.open_segments().is_empty());
+}
+
+// MARK: - Cancellation and duplicates
+
+#[test]
+fn test_cancelled_segment_is_closed() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            Some(900),
+        )],
+    ));
+    let cancel = SegmentationDescriptor::builder(1)
+        .cancel()
+        .build()
+        .expect("should build segmentation descriptor");
+    assert_eq!(
+        vec![TrackedEvent::Cancelled(segment(1, Some(100), Some(900)))],
+        tracker.process(&time_signal(0, Some(200), vec![cancel.clone()]))
+    );
+    assert!(tracker.open_segments().is_empty());
+    assert_eq!(
+        Vec::<TrackedEvent>::new(),
+        tracker.process(&time_signal(0, Some(300), vec![cancel]))
+    );
+}
+
+#[test]
+fn test_resent_start_and_end_are_duplicates() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            None,
+        )],
+    ));
+    assert_eq!(
+        vec![TrackedEvent::Duplicate(segment(1, Some(100), None))],
+        tracker.process(&time_signal(
+            0,
+            Some(100),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::BreakStart,
+                None
+            )]
+        ))
+    );
+    tracker.process(&time_signal(
+        0,
+        Some(500),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakEnd,
+            None,
+        )],
+    ));
+    assert_eq!(
+        vec![TrackedEvent::Duplicate(segment(1, Some(100), None))],
+        tracker.process(&time_signal(
+            0,
+            Some(500),
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::BreakEnd,
+                None
+            )]
+        ))
+    );
+    assert!(tracker.open_segments().is_empty());
+}
+
+#[test]
+fn test_end_without_start_is_unmatched() {
+    let mut tracker = EventTracker::new();
+    assert_eq!(
+        vec![TrackedEvent::UnmatchedEnd {
+            event_id: 1,
+            segmentation_type_id: SegmentationTypeID::BreakEnd,
+            end_pts: None,
+        }],
+        tracker.process(&time_signal(
+            0,
+            None,
+            vec![segmentation_descriptor(
+                1,
+                SegmentationTypeID::BreakEnd,
+                None
+            )]
+        ))
+    );
+}
+
+// MARK: - Missing ends
+
+#[test]
+fn test_missing_end_after_segmentation_duration() {
+    let mut tracker = EventTracker::new().end_tolerance(50);
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![
+            segmentation_descriptor(1, SegmentationTypeID::BreakStart, Some(900)),
+            segmentation_descriptor(2, SegmentationTypeID::BreakStart, None),
+        ],
+    ));
+    assert_eq!(Vec::<TrackedEvent>::new(), tracker.advance_to(Pts90k(1050)));
+    assert_eq!(
+        vec![TrackedEvent::MissingEnd(segment(1, Some(100), Some(900)))],
+        tracker.advance_to(Pts90k(1051))
+    );
+    // A segment without a duration never has a missing end.
+    assert_eq!(vec![segment(2, Some(100), None)], tracker.open_segments());
+}
+
+#[test]
+fn test_missing_end_is_reported_before_the_events_of_a_later_message() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            Some(900),
+        )],
+    ));
+    assert_eq!(
+        vec![
+            TrackedEvent::MissingEnd(segment(1, Some(100), Some(900))),
+            TrackedEvent::Started(segment(2, Some(2000), None)),
+        ],
+        tracker.process(&time_signal(
+            0,
+            Some(2000),
+            vec![segmentation_descriptor(
+                2,
+                SegmentationTypeID::BreakStart,
+                None
+            )]
+        ))
+    );
+}
+
+#[test]
+fn test_missing_end_across_clock_wrap() {
+    let mut tracker = EventTracker::new();
+    let start = Pts90k::MAX.ticks() - 100;
+    tracker.process(&time_signal(
+        0,
+        Some(start),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            Some(200),
+        )],
+    ));
+    assert_eq!(Vec::<TrackedEvent>::new(), tracker.advance_to(Pts90k(50)));
+    assert_eq!(
+        vec![TrackedEvent::MissingEnd(segment(1, Some(start), Some(200)))],
+        tracker.advance_to(Pts90k(100))
+    );
+}
+
+#[test]
+fn test_immediate_segment_never_has_missing_end() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        None,
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            Some(200),
+        )],
+    ));
+    assert_eq!(
+        Vec::<TrackedEvent>::new(),
+        tracker.advance_to(Pts90k(1_000_000))
+    );
+    assert_eq!(vec![segment(1, None, Some(200))], tracker.open_segments());
+}
+
+#[test]
+fn test_process_at_reports_missing_ends_as_of_the_given_pts() {
+    let mut tracker = EventTracker::new();
+    tracker.process(&time_signal(
+        0,
+        Some(100),
+        vec![segmentation_descriptor(
+            1,
+            SegmentationTypeID::BreakStart,
+            Some(900),
+        )],
+    ));
+    // The next start is signaled well in advance, before the end of the first is due.
+    assert_eq!(
+        vec![TrackedEvent::Started(segment(2, Some(5000), None))],
+        tracker.process_at(
+            &time_signal(
+                0,
+                Some(5000),
+                vec![segmentation_descriptor(
+                    2,
+                    SegmentationTypeID::BreakStart,
+                    None
+                )]
+            ),
+            Pts90k(500)
+        )
+    );
+    assert_eq!(
+        vec![TrackedEvent::MissingEnd(segment(1, Some(100), Some(900)))],
+        tracker.process_at(&time_signal(0, Some(5000), vec![]), Pts90k(1001))
+    );
+}
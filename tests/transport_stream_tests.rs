@@ -0,0 +1,634 @@
+mod common;
+
+use common::{bytes, splice_info_section, SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{SectionReaderError, TransportStreamError},
+    splice_command::private_command::PrivateCommand,
+    splice_info_section::SpliceInfoSection,
+    time::Pts90k,
+    transport_stream::{
+        extract_sections, find_scte35_streams, pes_pts, Feeder, Packetizer, ProgramScanner,
+        SCTE35Stream, SectionExtractor, SpliceSectionReader,
+    },
+};
+use std::io::{self, Read};
+
+const PID: u16 = 0x1F0;
+
+// MARK: - Section extraction
+
+#[test]
+fn test_section_split_across_packets() {
+    let section = bytes(TIME_SIGNAL_HEX);
+    let (first, second) = section.split_at(20);
+    let data = [
+        packet(PID, true, 0, &[&[0], first].concat()),
+        packet(PID, false, 1, second),
+    ]
+    .concat();
+    assert_eq!(
+        Ok(vec![splice_info_section(TIME_SIGNAL_HEX)]),
+        extract_sections(&data, PID)
+    );
+}
+
+#[test]
+fn test_pointer_field_completes_previous_section() {
+    let time_signal = bytes(TIME_SIGNAL_HEX);
+    let splice_insert = bytes(SPLICE_INSERT_HEX);
+    let (first, second) = time_signal.split_at(30);
+    let data = [
+        packet(PID, true, 14, &[&[0], first].concat()),
+        packet(
+            PID,
+            true,
+            15,
+            &[&[second.len() as u8], second, &splice_insert].concat(),
+        ),
+    ]
+    .concat();
+    assert_eq!(
+        Ok(vec![
+            splice_info_section(TIME_SIGNAL_HEX),
+            splice_info_section(SPLICE_INSERT_HEX)
+        ]),
+        extract_sections(&data, PID)
+    );
+}
+
+#[test]
+fn test_multiple_sections_in_one_packet_with_stuffing() {
+    let payload = [&[0], &bytes(TIME_SIGNAL_HEX)[..], &bytes(SPLICE_INSERT_HEX)].concat();
+    let mut extractor = SectionExtractor::new(PID);
+    assert_eq!(
+        Ok(vec![
+            Ok(splice_info_section(TIME_SIGNAL_HEX)),
+            Ok(splice_info_section(SPLICE_INSERT_HEX))
+        ]),
+        extractor.push_packet(&stuffed_packet(PID, true, 0, &payload))
+    );
+}
+
+#[test]
+fn test_invalid_section_does_not_discard_others_in_packet() {
+    // A section with the section_syntax_indicator set, which a `SpliceInfoSection` never has.
+    let invalid_section = [0xFC, 0xB0, 0x03, 0x00, 0x00, 0x00];
+    let expected_error = SpliceInfoSection::try_from_bytes(&invalid_section)
+        .expect_err("should not be valid splice info section");
+    let payload = [&[0], &invalid_section[..], &bytes(SPLICE_INSERT_HEX)].concat();
+    let mut extractor = SectionExtractor::new(PID);
+    assert_eq!(
+        Ok(vec![
+            Err(expected_error),
+            Ok(splice_info_section(SPLICE_INSERT_HEX))
+        ]),
+        extractor.push_packet(&stuffed_packet(PID, true, 0, &payload))
+    );
+}
+
+#[test]
+fn test_discontinuity_discards_partial_section() {
+    let section = bytes(TIME_SIGNAL_HEX);
+    let (first, second) = section.split_at(20);
+    let mut extractor = SectionExtractor::new(PID);
+    assert_eq!(
+        Ok(vec![]),
+        extractor.push_packet(&packet(PID, true, 3, &[&[0], first].concat()))
+    );
+    // The packet with continuity_counter 4 has been lost.
+    assert_eq!(
+        Ok(vec![]),
+        extractor.push_packet(&packet(PID, false, 5, second))
+    );
+    // A duplicate packet is ignored.
+    let payload = [&[0], &bytes(SPLICE_INSERT_HEX)[..]].concat();
+    assert_eq!(
+        Ok(vec![Ok(splice_info_section(SPLICE_INSERT_HEX))]),
+        extractor.push_packet(&stuffed_packet(PID, true, 6, &payload))
+    );
+    assert_eq!(
+        Ok(vec![]),
+        extractor.push_packet(&stuffed_packet(PID, true, 6, &payload))
+    );
+}
+
+#[test]
+fn test_other_pids_and_invalid_packets() {
+    let payload = [&[0], &bytes(SPLICE_INSERT_HEX)[..]].concat();
+    let mut extractor = SectionExtractor::new(PID);
+    assert_eq!(
+        Ok(vec![]),
+        extractor.push_packet(&stuffed_packet(0x100, true, 0, &payload))
+    );
+    let mut invalid_packet = stuffed_packet(PID, true, 0, &payload);
+    invalid_packet[0] = 0x48;
+    assert_eq!(
+        Err(TransportStreamError::InvalidSyncByte(0x48)),
+        extractor.push_packet(&invalid_packet)
+    );
+    assert_eq!(
+        Err(TransportStreamError::InvalidPacketLength(100)),
+        extractor.push_packet(&invalid_packet[..100])
+    );
+    assert_eq!(
+        Err(TransportStreamError::InvalidPointerField(200)),
+        extractor.push_packet(&stuffed_packet(PID, true, 0, &[200]))
+    );
+}
+
+// MARK: - Program scanning
+
+#[test]
+fn test_find_scte35_streams() {
+    let pmt = pmt_section(
+        1,
+        0,
+        &[],
+        &[
+            (0x1B, 0x100, vec![]),
+            (0x0F, 0x101, vec![]),
+            (0x86, 0x1F0, vec![0x05, 0x04, b'C', b'U', b'E', b'I']),
+        ],
+    );
+    let data = [
+        stuffed_packet(
+            0,
+            true,
+            0,
+            &[&[0], &pat_section(&[(0, 0x10), (1, 0x1000)])[..]].concat(),
+        ),
+        stuffed_packet(0x1000, true, 0, &[&[0], &pmt[..]].concat()),
+    ]
+    .concat();
+    assert_eq!(
+        Ok(vec![SCTE35Stream {
+            program_number: 1,
+            pid: 0x1F0,
+            has_cuei_registration: true
+        }]),
+        find_scte35_streams(&data)
+    );
+}
+
+#[test]
+fn test_program_scanner_tracks_pmt_versions() {
+    let mut scanner = ProgramScanner::new();
+    let pat = [&[0], &pat_section(&[(1, 0x1000), (2, 0x1001)])[..]].concat();
+    assert_eq!(
+        Ok(vec![]),
+        scanner.push_packet(&stuffed_packet(0, true, 0, &pat))
+    );
+    // The CUEI registration descriptor is in the program info loop for program 2.
+    let pmt_2 = pmt_section(
+        2,
+        0,
+        &[0x05, 0x04, b'C', b'U', b'E', b'I'],
+        &[(0x86, 0x201, vec![])],
+    );
+    let stream_2 = SCTE35Stream {
+        program_number: 2,
+        pid: 0x201,
+        has_cuei_registration: true,
+    };
+    assert_eq!(
+        Ok(vec![stream_2.clone()]),
+        scanner.push_packet(&stuffed_packet(
+            0x1001,
+            true,
+            0,
+            &[&[0], &pmt_2[..]].concat()
+        ))
+    );
+    // A repeated PMT does not rediscover the stream.
+    assert_eq!(
+        Ok(vec![]),
+        scanner.push_packet(&stuffed_packet(
+            0x1001,
+            true,
+            1,
+            &[&[0], &pmt_2[..]].concat()
+        ))
+    );
+    // A new version of the PMT for program 1 adds a stream without a registration descriptor.
+    let pmt_1 = pmt_section(1, 1, &[], &[(0x1B, 0x100, vec![]), (0x86, 0x101, vec![])]);
+    let stream_1 = SCTE35Stream {
+        program_number: 1,
+        pid: 0x101,
+        has_cuei_registration: false,
+    };
+    assert_eq!(
+        Ok(vec![stream_1.clone()]),
+        scanner.push_packet(&stuffed_packet(
+            0x1000,
+            true,
+            0,
+            &[&[0], &pmt_1[..]].concat()
+        ))
+    );
+    assert_eq!(&[stream_2, stream_1][..], scanner.streams());
+}
+
+#[test]
+fn test_program_scanner_invalid_crc() {
+    let mut pat = pat_section(&[(1, 0x1000)]);
+    let last = pat.len() - 1;
+    pat[last] ^= 0xFF;
+    assert_eq!(
+        Err(TransportStreamError::InvalidProgramSectionCRC { table_id: 0x00 }),
+        ProgramScanner::new().push_packet(&stuffed_packet(0, true, 0, &[&[0], &pat[..]].concat()))
+    );
+}
+
+// MARK: - Packetization
+
+#[test]
+fn test_packetize_single_packet() {
+    let section = bytes(SPLICE_INSERT_HEX);
+    let mut packetizer = Packetizer::new(PID, 15);
+    let packets = packetizer.packetize_bytes(&section);
+    assert_eq!(
+        vec![stuffed_packet(
+            PID,
+            true,
+            15,
+            &[&[0], &section[..]].concat()
+        )],
+        packets
+            .iter()
+            .map(|packet| packet.to_vec())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(0, packetizer.continuity_counter());
+}
+
+#[test]
+fn test_packetize_round_trip_across_packets() {
+    let private_command = SpliceInfoSection::builder()
+        .splice_command(
+            PrivateCommand {
+                identifier: String::from("CUEI"),
+                private_bytes: (0..=255).collect(),
+            }
+            .into(),
+        )
+        .build()
+        .expect("should build splice info section");
+    let mut packetizer = Packetizer::new(PID, 0);
+    let packets = [
+        packetizer
+            .packetize(&private_command)
+            .expect("should packetize"),
+        packetizer
+            .packetize(&splice_info_section(SPLICE_INSERT_HEX))
+            .expect("should packetize"),
+    ]
+    .concat();
+    assert_eq!(3, packets.len());
+    assert_eq!(3, packetizer.continuity_counter());
+    assert_eq!(
+        vec![0x40, 0x00, 0x40],
+        packets
+            .iter()
+            .map(|packet| packet[1] & 0x40)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Ok(vec![
+            private_command,
+            splice_info_section(SPLICE_INSERT_HEX)
+        ]),
+        extract_sections(&packets.concat(), PID)
+    );
+}
+
+// MARK: - Streaming reader
+
+#[test]
+fn test_reader_transport_stream() {
+    let mut packetizer = Packetizer::new(PID, 0);
+    let stream = [
+        packetizer
+            .packetize(&splice_info_section(TIME_SIGNAL_HEX))
+            .expect("should packetize"),
+        vec![stuffed_packet(0x100, true, 0, &[0, 1, 2, 3])
+            .try_into()
+            .expect("should be 188 bytes")],
+        packetizer
+            .packetize(&splice_info_section(SPLICE_INSERT_HEX))
+            .expect("should packetize"),
+    ]
+    .concat()
+    .concat();
+    // Reading one byte at a time ensures that packets are reassembled across reads.
+    let reader = SpliceSectionReader::new(OneByteReader(&stream), PID);
+    assert_eq!(
+        vec![
+            Ok(splice_info_section(TIME_SIGNAL_HEX)),
+            Ok(splice_info_section(SPLICE_INSERT_HEX))
+        ],
+        reader.collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_reader_raw_sections() {
+    let stream = [bytes(SPLICE_INSERT_HEX), bytes(TIME_SIGNAL_HEX)].concat();
+    let reader = SpliceSectionReader::new_raw(OneByteReader(&stream));
+    assert_eq!(
+        vec![
+            Ok(splice_info_section(SPLICE_INSERT_HEX)),
+            Ok(splice_info_section(TIME_SIGNAL_HEX))
+        ],
+        reader.collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_reader_truncated_packet_ends_iteration() {
+    let packet = stuffed_packet(
+        PID,
+        true,
+        0,
+        &[&[0], &bytes(SPLICE_INSERT_HEX)[..]].concat(),
+    );
+    let stream = [&packet[..], &packet[..100]].concat();
+    let mut reader = SpliceSectionReader::new(stream.as_slice(), PID);
+    assert_eq!(
+        Some(Ok(splice_info_section(SPLICE_INSERT_HEX))),
+        reader.next()
+    );
+    assert_eq!(
+        Some(Err(SectionReaderError::TransportStreamError(
+            TransportStreamError::InvalidPacketLength(100)
+        ))),
+        reader.next()
+    );
+    assert_eq!(None, reader.next());
+}
+
+#[test]
+fn test_reader_io_error_ends_iteration() {
+    let mut reader = SpliceSectionReader::new_raw(FailingReader);
+    assert_eq!(
+        Some(Err(SectionReaderError::Io(String::from("failed")))),
+        reader.next()
+    );
+    assert_eq!(None, reader.next());
+}
+
+// MARK: - Sans-IO feeder
+
+#[test]
+fn test_feeder_arbitrary_chunks() {
+    let stream = Packetizer::new(PID, 0)
+        .packetize(&splice_info_section(TIME_SIGNAL_HEX))
+        .expect("should packetize")
+        .concat();
+    for chunk_size in [1, 7, 100, 188, 200] {
+        let mut feeder = Feeder::new(PID);
+        let results = stream
+            .chunks(chunk_size)
+            .flat_map(|chunk| feeder.push(chunk))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Ok(splice_info_section(TIME_SIGNAL_HEX))], results);
+        assert_eq!(None, feeder.finish());
+    }
+}
+
+#[test]
+fn test_feeder_resyncs_transport_stream() {
+    let packet = stuffed_packet(
+        PID,
+        true,
+        0,
+        &[&[0], &bytes(SPLICE_INSERT_HEX)[..]].concat(),
+    );
+    let mut feeder = Feeder::new(PID);
+    assert_eq!(
+        vec![
+            Err(SectionReaderError::TransportStreamError(
+                TransportStreamError::InvalidSyncByte(0x00)
+            )),
+            Ok(splice_info_section(SPLICE_INSERT_HEX))
+        ],
+        feeder.push(&[&[0x00, 0x01, 0x02], &packet[..]].concat())
+    );
+    assert!(feeder.push(&packet[..10]).is_empty());
+    assert_eq!(
+        Some(SectionReaderError::TransportStreamError(
+            TransportStreamError::InvalidPacketLength(10)
+        )),
+        feeder.finish()
+    );
+}
+
+#[test]
+fn test_feeder_raw_sections() {
+    let stream = [bytes(TIME_SIGNAL_HEX), bytes(SPLICE_INSERT_HEX)].concat();
+    let mut feeder = Feeder::new_raw();
+    assert_eq!(
+        vec![Ok(splice_info_section(TIME_SIGNAL_HEX))],
+        feeder.push(&stream[..60])
+    );
+    assert_eq!(
+        vec![Ok(splice_info_section(SPLICE_INSERT_HEX))],
+        feeder.push(&stream[60..])
+    );
+    assert!(feeder.push(&bytes(SPLICE_INSERT_HEX)[..20]).is_empty());
+    assert!(matches!(
+        feeder.finish(),
+        Some(SectionReaderError::ParseError(_))
+    ));
+}
+
+// MARK: - PES timestamps
+
+#[test]
+fn test_pes_pts() {
+    // A PTS that uses all 33 bits.
+    let pes_header = bytes("000001E000008080052BCAF500A1");
+    assert_eq!(
+        Ok(Some(Pts90k(0x1_72BD_0050))),
+        pes_pts(&packet(0x100, true, 0, &pes_header))
+    );
+    // PTS and DTS.
+    let pes_header = bytes("000001C0000080C00A31000178611100017861");
+    assert_eq!(
+        Ok(Some(Pts90k(15408))),
+        pes_pts(&stuffed_packet(0x101, true, 0, &pes_header))
+    );
+    // Not the start of a PES packet.
+    assert_eq!(Ok(None), pes_pts(&packet(0x100, false, 1, &pes_header)));
+    // No PTS.
+    assert_eq!(
+        Ok(None),
+        pes_pts(&packet(0x100, true, 2, &bytes("000001E00000800000")))
+    );
+    // A padding stream has no optional PES header.
+    assert_eq!(
+        Ok(None),
+        pes_pts(&packet(
+            0x100,
+            true,
+            3,
+            &bytes("000001BE0000808005210001786100")
+        ))
+    );
+    // A section rather than a PES packet.
+    assert_eq!(
+        Ok(None),
+        pes_pts(&stuffed_packet(
+            PID,
+            true,
+            0,
+            &[&[0x00], &bytes(TIME_SIGNAL_HEX)[..]].concat()
+        ))
+    );
+    assert_eq!(
+        Err(TransportStreamError::InvalidSyncByte(0x00)),
+        pes_pts(&[0; 188])
+    );
+}
+
+// MARK: - Helpers
+
+struct OneByteReader<'a>(&'a [u8]);
+
+impl Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, remaining)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = *first;
+        self.0 = remaining;
+        Ok(1)
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("failed"))
+    }
+}
+
+/// Creates a packet whose payload is exactly `payload`, using an adaptation field for stuffing.
+fn packet(
+    pid: u16,
+    payload_unit_start_indicator: bool,
+    continuity_counter: u8,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut packet = header(pid, payload_unit_start_indicator, continuity_counter);
+    if payload.len() < 184 {
+        packet[3] |= 0x30;
+        let adaptation_field_length = 183 - payload.len();
+        packet.push(adaptation_field_length as u8);
+        if adaptation_field_length > 0 {
+            packet.push(0x00);
+            packet.resize(5 + adaptation_field_length, 0xFF);
+        }
+    } else {
+        packet[3] |= 0x10;
+    }
+    packet.extend_from_slice(payload);
+    packet
+}
+
+/// Creates a packet whose payload starts with `payload` and is padded with 0xFF stuffing bytes.
+fn stuffed_packet(
+    pid: u16,
+    payload_unit_start_indicator: bool,
+    continuity_counter: u8,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut packet = header(pid, payload_unit_start_indicator, continuity_counter);
+    packet[3] |= 0x10;
+    packet.extend_from_slice(payload);
+    packet.resize(188, 0xFF);
+    packet
+}
+
+fn header(pid: u16, payload_unit_start_indicator: bool, continuity_counter: u8) -> Vec<u8> {
+    let [pid_high, pid_low] = pid.to_be_bytes();
+    vec![
+        0x47,
+        if payload_unit_start_indicator {
+            0x40
+        } else {
+            0x00
+        } | pid_high,
+        pid_low,
+        continuity_counter,
+    ]
+}
+
+fn pat_section(programs: &[(u16, u16)]) -> Vec<u8> {
+    let data = programs
+        .iter()
+        .flat_map(|(program_number, pid)| {
+            let [pid_high, pid_low] = pid.to_be_bytes();
+            let [program_high, program_low] = program_number.to_be_bytes();
+            [program_high, program_low, 0xE0 | pid_high, pid_low]
+        })
+        .collect::<Vec<u8>>();
+    long_section(0x00, 1, 0, &data)
+}
+
+fn pmt_section(
+    program_number: u16,
+    version: u8,
+    program_info: &[u8],
+    streams: &[(u8, u16, Vec<u8>)],
+) -> Vec<u8> {
+    let mut data = vec![0xE1, 0x00, 0xF0, program_info.len() as u8];
+    data.extend_from_slice(program_info);
+    for (stream_type, pid, es_info) in streams {
+        let [pid_high, pid_low] = pid.to_be_bytes();
+        data.extend_from_slice(&[
+            *stream_type,
+            0xE0 | pid_high,
+            pid_low,
+            0xF0,
+            es_info.len() as u8,
+        ]);
+        data.extend_from_slice(es_info);
+    }
+    long_section(0x02, program_number, version, &data)
+}
+
+fn long_section(table_id: u8, table_id_extension: u16, version: u8, data: &[u8]) -> Vec<u8> {
+    let section_length = (5 + data.len() + 4) as u16;
+    let [length_high, length_low] = section_length.to_be_bytes();
+    let [extension_high, extension_low] = table_id_extension.to_be_bytes();
+    let mut section = vec![
+        table_id,
+        0xB0 | length_high,
+        length_low,
+        extension_high,
+        extension_low,
+        0xC1 | (version << 1),
+        0x00,
+        0x00,
+    ];
+    section.extend_from_slice(data);
+    let crc = crc_32_mpeg_2(&section);
+    section.extend_from_slice(&crc.to_be_bytes());
+    section
+}
+
+fn crc_32_mpeg_2(data: &[u8]) -> u32 {
+    data.iter().fold(0xFFFFFFFF, |mut crc, byte| {
+        crc ^= (*byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x80000000 != 0 {
+                (crc << 1) ^ 0x04C11DB7
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
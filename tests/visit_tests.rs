@@ -0,0 +1,138 @@
+mod common;
+
+use common::{bytes, SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::ParseError,
+    splice_command::SpliceCommandType,
+    splice_info_section::{SAPType, SpliceInfoSection},
+    splice_info_section_ref::{SpliceCommandRef, SpliceDescriptorRef, SpliceInfoSectionRef},
+    time::Pts90k,
+    visit::{parse_with_visitor, SpliceInfoSectionHeader, SpliceVisitor},
+};
+use std::ops::ControlFlow;
+
+#[derive(Debug, PartialEq)]
+enum Visited<'a> {
+    Header(SpliceInfoSectionHeader),
+    SpliceCommand(SpliceCommandRef<'a>),
+    SpliceDescriptor(usize, SpliceDescriptorRef<'a>),
+}
+
+#[derive(Default)]
+struct Recorder<'a> {
+    visited: Vec<Visited<'a>>,
+    break_after: Option<usize>,
+}
+
+impl Recorder<'_> {
+    fn control_flow(&self) -> ControlFlow<()> {
+        if Some(self.visited.len()) == self.break_after {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+impl<'a> SpliceVisitor<'a> for Recorder<'a> {
+    fn visit_header(&mut self, header: &SpliceInfoSectionHeader) -> ControlFlow<()> {
+        self.visited.push(Visited::Header(header.clone()));
+        self.control_flow()
+    }
+
+    fn visit_splice_command(&mut self, splice_command: SpliceCommandRef<'a>) -> ControlFlow<()> {
+        self.visited.push(Visited::SpliceCommand(splice_command));
+        self.control_flow()
+    }
+
+    fn visit_splice_descriptor(
+        &mut self,
+        index: usize,
+        splice_descriptor: SpliceDescriptorRef<'a>,
+    ) -> ControlFlow<()> {
+        self.visited
+            .push(Visited::SpliceDescriptor(index, splice_descriptor));
+        self.control_flow()
+    }
+}
+
+// MARK: - Callbacks
+
+#[test]
+fn test_visits_parts_in_order() {
+    let data = bytes(TIME_SIGNAL_HEX);
+    let mut recorder = Recorder::default();
+    parse_with_visitor(&data, &mut recorder).expect("should visit section");
+    let section_ref = SpliceInfoSectionRef::try_from_bytes(&data).expect("should parse section");
+    assert_eq!(
+        vec![
+            Visited::Header(SpliceInfoSectionHeader {
+                table_id: 0xFC,
+                sap_type: SAPType::Unspecified,
+                protocol_version: 0,
+                pts_adjustment: Pts90k(0),
+                tier: 0xFFF,
+                crc_32: section_ref.crc_32,
+            }),
+            Visited::SpliceCommand(section_ref.splice_command.clone()),
+            Visited::SpliceDescriptor(0, section_ref.splice_descriptors[0].clone()),
+        ],
+        recorder.visited
+    );
+}
+
+#[test]
+fn test_splice_command_can_be_parsed_from_callback() {
+    let data = bytes(SPLICE_INSERT_HEX);
+    let mut recorder = Recorder::default();
+    parse_with_visitor(&data, &mut recorder).expect("should visit section");
+    let Visited::SpliceCommand(splice_command) = &recorder.visited[1] else {
+        panic!("should have visited splice command");
+    };
+    assert_eq!(
+        SpliceCommandType::SpliceInsert,
+        splice_command.splice_command_type
+    );
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&data)
+            .expect("should parse section")
+            .splice_command,
+        splice_command
+            .try_to_owned()
+            .expect("should parse splice command")
+    );
+}
+
+// MARK: - Early exit
+
+#[test]
+fn test_break_stops_walk() {
+    let data = bytes(TIME_SIGNAL_HEX);
+    let mut recorder = Recorder {
+        break_after: Some(1),
+        ..Default::default()
+    };
+    parse_with_visitor(&data, &mut recorder).expect("should visit section");
+    assert_eq!(1, recorder.visited.len());
+    assert!(matches!(recorder.visited[0], Visited::Header(_)));
+}
+
+#[test]
+fn test_break_ignores_invalid_data_after_break() {
+    let mut data = bytes(TIME_SIGNAL_HEX);
+    // Claim a longer descriptor_loop_length than is available.
+    let loop_start = 14 + 5;
+    data[loop_start + 1] = 0xFF;
+    let mut recorder = Recorder {
+        break_after: Some(2),
+        ..Default::default()
+    };
+    parse_with_visitor(&data, &mut recorder).expect("should visit section");
+    assert_eq!(2, recorder.visited.len());
+
+    let mut recorder = Recorder::default();
+    let error = parse_with_visitor(&data, &mut recorder).expect_err("should fail to visit");
+    assert!(matches!(error, ParseError::UnexpectedEndOfData { .. }));
+    assert_eq!(2, recorder.visited.len());
+}
@@ -0,0 +1,30 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use pretty_assertions::assert_eq;
+use scte35::wasm::parse;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const SPLICE_INSERT_BASE64: &str =
+    "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=";
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &JsValue::from_str(key)).expect("should get property")
+}
+
+#[wasm_bindgen_test]
+fn test_parse_returns_json_object() {
+    let section = parse(SPLICE_INSERT_BASE64).expect("should parse base64");
+    assert_eq!(Some(252.0), get(&section, "table_id").as_f64());
+    let splice_insert = get(&get(&section, "splice_command"), "SpliceInsert");
+    assert_eq!(
+        Some(1207959695.0),
+        get(&splice_insert, "splice_event_id").as_f64()
+    );
+    assert_eq!(Some(0x62DBA30A as f64), get(&section, "crc_32").as_f64());
+}
+
+#[wasm_bindgen_test]
+fn test_parse_invalid_message_throws() {
+    assert!(parse("/DAv").is_err());
+}
@@ -0,0 +1,260 @@
+#![cfg(feature = "xml")]
+
+mod common;
+
+use common::{SPLICE_INSERT_HEX, TIME_SIGNAL_HEX};
+use pretty_assertions::assert_eq;
+use scte35::{
+    error::{ParseError, XMLParseError},
+    splice_command::{splice_insert, SpliceCommand},
+    splice_descriptor::{
+        segmentation_descriptor::{SegmentationUPID, SegmentationUPIDType},
+        SpliceDescriptor,
+    },
+    splice_info_section::SpliceInfoSection,
+};
+
+// 14.2. splice_insert
+#[test]
+fn test_xml_splice_insert() {
+    let hex_string = SPLICE_INSERT_HEX;
+    let xml = r#"
+        <scte35:SpliceInfoSection xmlns:scte35="http://www.scte.org/schemas/35" ptsAdjustment="0" protocolVersion="0" sapType="3" tier="4095">
+            <scte35:SpliceInsert spliceEventId="1207959695" spliceEventCancelIndicator="false" outOfNetworkIndicator="true" spliceImmediateFlag="false" uniqueProgramId="0" availNum="0" availsExpected="0">
+                <scte35:Program>
+                    <scte35:SpliceTime ptsTime="1936310318"/>
+                </scte35:Program>
+                <scte35:BreakDuration autoReturn="true" duration="5426421"/>
+            </scte35:SpliceInsert>
+            <scte35:AvailDescriptor providerAvailId="309"/>
+        </scte35:SpliceInfoSection>
+    "#;
+    assert_eq!(
+        SpliceInfoSection::try_from_hex_string(hex_string)
+            .expect("should be valid splice info section from hex"),
+        SpliceInfoSection::try_from_xml_string(xml)
+            .expect("should be valid splice info section from xml")
+    );
+}
+
+// 14.4. time_signal – Program Start/End
+#[test]
+fn test_xml_time_signal_multiple_descriptors() {
+    let base64_string = "/DBIAAAAAAAA///wBQb+ek2ItgAyAhdDVUVJSAAAGH+fCAgAAAAALMvDRBEAAAIXQ1VFSUgAABl/nwgIAAAAACyk26AQAACZcuND";
+    let xml = r#"
+        <SpliceInfoSection xmlns="http://www.scte.org/schemas/35">
+            <TimeSignal>
+                <SpliceTime ptsTime="2051901622"/>
+            </TimeSignal>
+            <SegmentationDescriptor segmentationEventId="1207959576" segmentationTypeId="17" segmentNum="0" segmentsExpected="0">
+                <DeliveryRestrictions webDeliveryAllowedFlag="true" noRegionalBlackoutFlag="true" archiveAllowedFlag="true" deviceRestrictions="3"/>
+                <SegmentationUpid segmentationUpidType="8">000000002CCBC344</SegmentationUpid>
+            </SegmentationDescriptor>
+            <SegmentationDescriptor segmentationEventId="1207959577" segmentationTypeId="16" segmentNum="0" segmentsExpected="0">
+                <DeliveryRestrictions webDeliveryAllowedFlag="true" noRegionalBlackoutFlag="true" archiveAllowedFlag="true" deviceRestrictions="3"/>
+                <SegmentationUpid segmentationUpidType="8" segmentationUpidFormat="hexbinary">000000002CA4DBA0</SegmentationUpid>
+            </SegmentationDescriptor>
+        </SpliceInfoSection>
+    "#;
+    let data = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, base64_string)
+        .expect("should be valid base64");
+    assert_eq!(
+        SpliceInfoSection::try_from_bytes(&data).expect("should be valid splice info section"),
+        SpliceInfoSection::try_from_xml_string(xml)
+            .expect("should be valid splice info section from xml")
+    );
+}
+
+#[test]
+fn test_xml_multiple_upids_are_mid() {
+    let xml = r#"
+        <SpliceInfoSection xmlns="http://www.scte.org/schemas/35">
+            <TimeSignal>
+                <SpliceTime ptsTime="0"/>
+            </TimeSignal>
+            <SegmentationDescriptor segmentationEventId="2" segmentationTypeId="52" segmentNum="1" segmentsExpected="1" subSegmentNum="1" subSegmentsExpected="2">
+                <SegmentationUpid segmentationUpidType="3" segmentationUpidFormat="text">ABCD0123456H</SegmentationUpid>
+                <SegmentationUpid segmentationUpidType="15" segmentationUpidFormat="base-64">aHR0cHM6Ly9leGFtcGxlLmNvbQ==</SegmentationUpid>
+            </SegmentationDescriptor>
+        </SpliceInfoSection>
+    "#;
+    let splice_info_section = SpliceInfoSection::try_from_xml_string(xml)
+        .expect("should be valid splice info section from xml");
+    let SpliceDescriptor::SegmentationDescriptor(ref descriptor) =
+        splice_info_section.splice_descriptors[0]
+    else {
+        panic!("should be segmentation descriptor");
+    };
+    let scheduled_event = descriptor
+        .scheduled_event
+        .as_ref()
+        .expect("should have scheduled event");
+    assert_eq!(
+        SegmentationUPID::MID(vec![
+            SegmentationUPID::AdID(String::from("ABCD0123456H")),
+            SegmentationUPID::URI(String::from("https://example.com")),
+        ]),
+        scheduled_event.segmentation_upid
+    );
+    assert_eq!(None, scheduled_event.delivery_restrictions);
+    assert!(scheduled_event.sub_segment.is_some());
+    let encoded = splice_info_section
+        .try_to_bytes()
+        .expect("should encode splice info section");
+    assert_eq!(
+        splice_info_section,
+        SpliceInfoSection::try_from_bytes(&encoded).expect("should parse encoded bytes")
+    );
+}
+
+#[test]
+fn test_xml_splice_insert_immediate_cancel_and_binary() {
+    let xml = r#"
+        <SpliceInfoSection>
+            <SpliceInsert spliceEventId="10" spliceEventCancelIndicator="true"/>
+        </SpliceInfoSection>
+    "#;
+    let splice_info_section = SpliceInfoSection::try_from_xml_string(xml)
+        .expect("should be valid splice info section from xml");
+    assert_eq!(
+        SpliceCommand::SpliceInsert(splice_insert::SpliceInsert {
+            event_id: 10,
+            scheduled_event: None,
+        }),
+        splice_info_section.splice_command
+    );
+    let binary = format!(
+        "<Binary xmlns=\"http://www.scte.org/schemas/35\">{}</Binary>",
+        base64::Engine::encode(
+            &base64::prelude::BASE64_STANDARD,
+            splice_info_section
+                .try_to_bytes()
+                .expect("should encode splice info section")
+        )
+    );
+    assert_eq!(
+        splice_info_section,
+        SpliceInfoSection::try_from_xml_string(&binary).expect("should be valid binary element")
+    );
+}
+
+#[test]
+fn test_xml_errors() {
+    assert_eq!(
+        Err(XMLParseError::UnexpectedRootElement(String::from("Foo"))),
+        SpliceInfoSection::try_from_xml_string("<Foo/>")
+    );
+    assert_eq!(
+        Err(XMLParseError::MissingAttribute {
+            element: String::from("SpliceInsert"),
+            attribute: "spliceEventId",
+        }),
+        SpliceInfoSection::try_from_xml_string(
+            "<SpliceInfoSection><SpliceInsert/></SpliceInfoSection>"
+        )
+    );
+    assert_eq!(
+        Err(XMLParseError::InvalidAttribute {
+            element: String::from("SpliceTime"),
+            attribute: "ptsTime",
+            value: String::from("abc"),
+        }),
+        SpliceInfoSection::try_from_xml_string(
+            "<SpliceInfoSection><TimeSignal><SpliceTime ptsTime=\"abc\"/></TimeSignal></SpliceInfoSection>"
+        )
+    );
+    assert_eq!(
+        Err(XMLParseError::ParseError(
+            ParseError::UnexpectedSegmentationUPIDLength {
+                declared_segmentation_upid_length: 4,
+                expected_segmentation_upid_length: 12,
+                segmentation_upid_type: SegmentationUPIDType::AdID,
+            }
+        )),
+        SpliceInfoSection::try_from_xml_string(
+            r#"<SpliceInfoSection>
+                <TimeSignal/>
+                <SegmentationDescriptor segmentationEventId="1" segmentationTypeId="52">
+                    <SegmentationUpid segmentationUpidType="3" segmentationUpidFormat="text">ABCD</SegmentationUpid>
+                </SegmentationDescriptor>
+            </SpliceInfoSection>"#
+        )
+    );
+}
+
+#[test]
+fn test_xml_round_trip_samples() {
+    let base64_strings = [
+        "/DA0AAAAAAAA///wBQb+cr0AUAAeAhxDVUVJSAAAjn/PAAGlmbAICAAAAAAsoKGKNAIAmsnRfg==",
+        "/DAvAAAAAAAA///wFAVIAACPf+/+c2nALv4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=",
+        "/DAvAAAAAAAA///wBQb+dGKQoAAZAhdDVUVJSAAAjn+fCAgAAAAALKChijUCAKnMZ1g=",
+        "/DBIAAAAAAAA///wBQb+ek2ItgAyAhdDVUVJSAAAGH+fCAgAAAAALMvDRBEAAAIXQ1VFSUgAABl/nwgIAAAAACyk26AQAACZcuND",
+        "/DAvAAAAAAAA///wBQb+rr//ZAAZAhdDVUVJSAAACH+fCAgAAAAALKVs9RcAAJUdsKg=",
+        "/DBIAAAAAAAA///wBQb+ky44CwAyAhdDVUVJSAAACn+fCAgAAAAALKCh4xgAAAIXQ1VFSUgAAAl/nwgIAAAAACygoYoRAAC0IX6w",
+        "/DAvAAAAAAAA///wBQb+rvF8TAAZAhdDVUVJSAAAB3+fCAgAAAAALKVslxEAAMSHai4=",
+        "/DBhAAAAAAAA///wBQb+qM1E7QBLAhdDVUVJSAAArX+fCAgAAAAALLLXnTUCAAIXQ1VFSUgAACZ/nwgIAAAAACyy150RAAACF0NVRUlIAAAnf58ICAAAAAAsstezEAAAihiGnw==",
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAAA3//AAApPWwDDEFCQ0QwMTIzNDU2SBAAAGgCL9A=",
+        "/DBHAAAAAAAA///wBQb+AAAAAAAxAi9DVUVJAAAAA3+/BCAGCis0AQEBBQEBDSATAAAA0skDbI8ZU0OrcBTS1xi/2hEAAPUV9+0=",
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAABn//AAApPWwGDAAAAAA6jQAAAAAAABAAAPaArb4=",
+        "/DAzAAAAAAAA///wBQb+AAAAAAAdAhtDVUVJAAAABn+/BgwAAAAAOo0AAAAAAAARAAAT5alN",
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAAA3//AAApPWwHDE1WMDAwNDE0NjQwMBAAAIH4Mwc=",
+        "/DAzAAAAAAAA///wBQb+AAAAAAAdAhtDVUVJAAAAA3+/BwxNVjAwMDQxNDY0MDARAAB2a6fC",
+        "/DBLAAAAAAAA///wBQb+AAAAAAA1AjNDVUVJYgAFin//AABSZcAJH1NJR05BTDpEUjIxWjA3WlQ4YThhc25pdVVoZWlBPT00AADz3GdX",
+        "/DBEAAAAAAAA///wBQb+AFJlwAAuAixDVUVJYgAFin+/CR1TSUdOQUw6My1zUTROZ0ZUME9qUHNHNFdxVVFvdzUAAEukzlg=",
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAAA3//AAApPWwKDBR4+FrhALBoW4+xyBAAAGij1lQ=",
+        "/DA4AAAAAAAA///wBQb+AAAAAAAiAiBDVUVJAAAAA3//AAApPWwLDADx7/9odW1hbjAxMhAAALdaWG4=",
+        "/DAzAAAAAAAA///wBQb+AAAAAAAdAhtDVUVJAAAAA3+/CwwA8e//aHVtYW4wMTIRAABAycyr",
+        "/DB5AAAAAAAAAP/wBQb/DkfmpABjAhdDVUVJhPHPYH+/CAgAAAAABy4QajEBGAIcQ1VFSYTx71B//wAAK3NwCAgAAAAABy1cxzACGAIqQ1VFSYTx751/vwwbUlRMTjFIAQAAAAAxMzU2MTY2MjQ1NTUxQjEAAQAALL95dg==",
+        "/DA9AAAAAAAAAACABQb+0fha8wAnAiVDVUVJSAAAv3/PAAD4+mMNEQ4FTEEzMDkICAAAAAAuU4SBNAAAPIaCPw==",
+        "/DBZAAAAAAAA///wBQb+AAAAAABDAkFDVUVJAAAAC3//AAApMuAOLUFEUy1VUElEOmFhODViYmI2LTVjNDMtNGI2YS1iZWJiLWVlM2IxM2ViNzk5ORAAAJd2uP4=",
+        "/DBUAAAAAAAA///wBQb+AAAAAAA+AjxDVUVJAAAAC3+/Di1BRFMtVVBJRDphYTg1YmJiNi01YzQzLTRiNmEtYmViYi1lZTNiMTNlYjc5OTkRAACV15uV",
+        "/DBZAAAAAAAA///wBQb+AAAAAABDAkFDVUVJAAAACn//AAApMuAPLXVybjp1dWlkOmFhODViYmI2LTVjNDMtNGI2YS1iZWJiLWVlM2IxM2ViNzk5ORAAAFz7UQA=",
+        "/DBUAAAAAAAA///wBQb+AAAAAAA+AjxDVUVJAAAACn+/Dy11cm46dXVpZDphYTg1YmJiNi01YzQzLTRiNmEtYmViYi1lZTNiMTNlYjc5OTkRAAB2c6LA",
+        "/DAvAAAAAAAAAP///wViAAWKf+//CXVCAv4AUmXAAzUAAAAKAAhDVUVJADgyMWLvc/g=",
+        "/DAlAAAAAAAAAP/wFAUAAAPvf+//adb6P/4AUmXAAAAAAAAAoeikig==",
+        "/DAgAAAAAAAAAP/wDwUAAAPvf0//ahTGjwAAAAAAALda4HI=",
+        "/DAsAAAAAAAAAP/wDwUAAABef0/+zPACTQAAAAAADAEKQ1VFSbGfMTIxIxGolm3/////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////",
+        "/DBwAAAAAAAAAP/wBQb/AAAAAABaAlhDVUVJAAAAAn//AABSZcANRAoMFHeL5eP2AAAAAAAACgwUd4vl4/YAAAAAAAAJJlNJR05BTDpMeTlFTUd4S1IwaEZaVXRwTUhkQ1VWWm5SVUZuWnowNgEB1Dao2g==",
+    ];
+    for base64_string in base64_strings {
+        let Ok(splice_info_section) = SpliceInfoSection::try_from_bytes(
+            &base64::Engine::decode(&base64::prelude::BASE64_STANDARD, base64_string)
+                .expect("should be valid base64"),
+        ) else {
+            continue;
+        };
+        let xml = splice_info_section
+            .to_xml_string()
+            .unwrap_or_else(|e| panic!("{}: should emit xml: {}", base64_string, e));
+        let from_xml = SpliceInfoSection::try_from_xml_string(&xml)
+            .unwrap_or_else(|e| panic!("{}: should parse emitted xml: {}", base64_string, e));
+        assert_eq!(
+            splice_info_section.try_to_bytes(),
+            from_xml.try_to_bytes(),
+            "{}",
+            xml
+        );
+    }
+}
+
+// 14.1. time_signal – Placement Opportunity Start
+#[test]
+fn test_to_xml_string_time_signal() {
+    let hex_string = TIME_SIGNAL_HEX;
+    let splice_info_section = SpliceInfoSection::try_from_hex_string(hex_string)
+        .expect("should be valid splice info section from hex");
+    assert_eq!(
+        concat!(
+            r#"<SpliceInfoSection xmlns="http://www.scte.org/schemas/35" sapType="3" ptsAdjustment="0" protocolVersion="0" tier="4095">"#,
+            r#"<TimeSignal><SpliceTime ptsTime="1924989008"/></TimeSignal>"#,
+            r#"<SegmentationDescriptor segmentationEventId="1207959694" segmentationEventCancelIndicator="false" segmentationDuration="27630000" segmentationTypeId="52" segmentNum="2" segmentsExpected="0">"#,
+            r#"<DeliveryRestrictions webDeliveryAllowedFlag="false" noRegionalBlackoutFlag="true" archiveAllowedFlag="true" deviceRestrictions="3"/>"#,
+            r#"<SegmentationUpid segmentationUpidType="8" segmentationUpidFormat="hexbinary">000000002CA0A18A</SegmentationUpid>"#,
+            r#"</SegmentationDescriptor>"#,
+            r#"</SpliceInfoSection>"#,
+        ),
+        splice_info_section
+            .to_xml_string()
+            .expect("should emit xml")
+    );
+}